@@ -0,0 +1,615 @@
+# AgentPulse English messages: the reference catalog and the fallback for
+# every other language. Translations copy this file to `<lang>.ftl` and
+# translate the values; keys and `{ $placeholders }` stay as they are.
+
+## Status bar
+
+hint-run-once = run once
+hint-cancel = cancel
+hint-clear = clear
+hint-section = section
+hint-row = row
+hint-review = review
+hint-refresh = refresh
+hint-filter = filter
+hint-help = help
+hint-quit = quit
+hint-open = open
+hint-fetch = fetch
+hint-pull = pull
+hint-push = push
+hint-commit = commit
+hint-stash-files = stash files
+hint-group = group
+status-scanning = scanning
+status-grep-searching = searching { $pattern }…
+status-grep-results = { $pattern }: { $repos } repos, { $matches } matches
+
+## Help overlay
+
+help-title = Help
+help-search-hint = Type to search · ↑↓ PgUp PgDn scroll · Esc close
+help-search = Search: { $query }
+help-no-match = No keys match “{ $query }”
+help-cat-section = THIS SECTION ({ $section })
+help-cat-other = OTHER SECTIONS
+help-cat-navigation = NAVIGATION
+help-cat-actions = ACTIONS
+help-cat-git = GIT
+help-cat-general = GENERAL
+help-switch-section = Switch section
+help-jump-section = Jump to section
+help-move-down = Move down
+help-move-up = Move up
+help-bookmark = Bookmark repo
+help-jump-bookmark = Jump to bookmark
+help-recent-repos = Switch to a recently used repo
+help-review-action = Review selected action
+help-run-next-best = Run the next best action
+help-skip-next-best = Skip to the next suggestion
+help-confirm-action = Confirm pending action
+help-cancel-action = Cancel pending action
+help-refresh = Force refresh
+help-filter = Filter search
+help-grep = Search all repos for a string / commit
+help-time-travel = Compare saved snapshots over time
+help-integrations = Remote host connectivity
+help-task = File alert / suggestion as a task
+help-open-editor = Open in editor
+help-open-files = Open in file manager
+help-open-ticket = Open linked ticket
+help-open-pull-request = Open pull request or CI run
+help-fetch = Fetch
+help-pull = Pull
+help-push = Push
+help-commit = Commit tracked changes
+help-undo-commit = Undo last unpushed auto-commit
+help-stash = Stash selected files
+help-branch = Switch / create branch
+help-commit-log = Recent commits (unpushed marked)
+help-diff = View uncommitted diff
+help-stash-list = Browse stashes: pop / apply / drop
+help-cherry-pick = Cherry-pick commits onto a branch
+help-bundle = Bundle the repo to the backup drive
+help-group = Group by directory
+help-actionable = Actionable-only mode
+help-dismiss = Dismiss / restore suggestion
+help-setup = Setup watch dirs
+help-edit-setting = Edit / toggle setting
+help-save-settings = Save settings to the config file
+help-reload-settings = Reload settings from disk
+help-toggle-help = Toggle help
+help-quit = Quit
+help-cat-status = STATUS
+help-status-clean = Clean
+help-status-uncommitted = Uncommitted changes
+help-status-unpushed = Commits not pushed
+help-status-dirty = Uncommitted changes and commits not pushed
+help-status-no-remote = No remote
+
+## Dialogs
+
+confirm-title = Confirm Action
+confirm-action = Action:
+confirm-risk = Risk:
+confirm-preview = Command preview:
+confirm-destructive = Destructive action: review command before running.
+confirm-protected-again = `{ $branch }` is protected: Enter / y again to run anyway.
+confirm-protected = `{ $branch }` is protected: this needs confirming twice.
+branches-title = Switch Branch
+branches-header = Branches in { $repo }
+branches-no-upstream = no upstream
+branches-in-sync = in sync
+branches-on = on
+branches-off = off
+branches-dirty = ⚠ { $count } uncommitted change(s) · autostash
+branches-new = New branch:
+branches-hint-create = Enter review · Esc back to branches
+branches-hint-autostash = Enter switch · n new branch · a autostash · Esc cancel
+branches-hint = Enter switch · n new branch · Esc cancel
+cherry-title = Cherry-pick
+cherry-target-header = Apply { $count } commit(s) from { $branch } onto:
+cherry-header = Commits on { $branch } in { $repo } · { $count } marked
+cherry-worktree = worktree { $path }
+cherry-temp-worktree = temporary worktree
+cherry-hint-target = Enter review · Esc back to commits
+cherry-hint = Space mark · Enter choose branch · Esc cancel
+commit-bar-label = Commit:
+commit-bar-hint = Enter to confirm · Esc to cancel
+commit-log-title = Commit Log
+commit-log-header = { $repo } on { $branch }
+commit-log-unpushed = { $count } not pushed
+commit-log-hint-push = ↑ not on the remote yet · P review push · Esc close
+commit-log-hint = j/k move · Esc close
+grep-label = Search repos:
+grep-hint = string or commit hash · Enter search · Esc cancel
+integrations-title = Integrations
+integrations-none = No remote hosts probed yet — results appear after the next scan
+integrations-off = Connectivity probes are off. Enable them with [connectivity] enabled = true
+integrations-how = (each remote host gets `ssh -T` or an HTTPS connect, cached between scans)
+integrations-col-host = HOST
+integrations-col-via = VIA
+integrations-col-status = STATUS
+integrations-col-time = TIME
+integrations-col-repos = REPOS
+integrations-col-detail = DETAIL
+integrations-stale = Ahead/behind for repos on unreachable hosts is from their last fetch
+integrations-close = Any key to close
+recent-title = Recent Repos
+recent-no-match = No recent repo matches
+recent-hint = Type to filter · ↑↓ move · Enter jump · Esc close
+remote-title = New Remote
+remote-private = private
+remote-public = public
+remote-host = Host
+remote-name = Name
+remote-visibility = Visibility
+remote-intro = Create a remote repo, add it as origin, and push
+remote-change = ←/→ change
+remote-cli = Uses the `{ $cli }` CLI (must be logged in)
+remote-hint = ↑/↓ field · Enter review · Esc cancel
+stashes-title = Stashes
+stashes-header = Stashes in { $repo }
+stashes-detached = detached
+stashes-hint = Enter/p pop · a apply (keep) · d drop · Esc close
+stash-files-title = Partial Stash
+stash-files-header = Stash selected files in { $repo }
+stash-files-message = Message:
+stash-files-hint-message = Enter review stash · Esc back to files
+stash-files-hint = Space toggle · a all · Enter message · Esc cancel
+time-travel-title = Time Travel
+time-travel-unknown = unknown
+time-travel-hint-back = Esc back to snapshots
+time-travel-hint-second = Enter pick second snapshot · Esc clear
+time-travel-hint-first = Enter pick first snapshot · Esc close
+time-travel-header = { $count } saved snapshots, newest first — pick two to compare
+time-travel-entry = { $repos } repos · { $dirty } dirty · { $alerts } alerts · { $cost }
+time-travel-changes = { $count } change(s)
+time-travel-unchanged = Nothing changed between these snapshots
+time-travel-more = … { $count } more
+
+## Dashboard
+
+diff-title = Diff · { $repo } · { $count } file(s)
+diff-hint = j/k scroll · Space/PgUp page · n/N next/prev file · g/G top/end · Esc close
+diff-untracked = Untracked files ({ $count }):
+home-hero-title = Next best action
+home-hero-keys = Enter run · n skip
+home-hero-skip = n skip
+home-effort = ~{ $minutes } min
+home-morning = Good morning
+home-afternoon = Good afternoon
+home-evening = Good evening
+home-night = Good night
+home-all-clear = { $greeting }. { $count } repos monitored, all clear.
+home-attention = { $greeting }. { $count } repos monitored, { $attention } need attention.
+home-card-repos = Repos
+home-card-dirty = Dirty
+home-card-procs = Procs
+home-card-cost = AI Cost
+home-week = { $commits } commits · { $repos } repos
+home-this-week = This week
+home-activity-title = Activity ({ $weeks } weeks)
+home-no-activity = No commits on local branches in the last { $weeks } weeks.
+home-mon = Mon
+home-tue = Tue
+home-wed = Wed
+home-thu = Thu
+home-fri = Fri
+home-sat = Sat
+home-sun = Sun
+home-commits = { $count } commits
+home-active = { $days } active days · { $repos } repos
+home-busiest = busiest day: { $weekday } { $day } ({ $count })
+home-no-alerts = No alerts. Workspace looks healthy.
+home-alerts-title = Alerts ({ $count })
+too-small =
+    Terminal too small ({ $width }×{ $height })
+    Please resize to at least { $min_width }×{ $min_height }
+settings-col-setting = SETTING
+settings-col-value = VALUE
+settings-on = on
+settings-off = off
+settings-title = Settings · { $path }
+settings-title-unsaved = Settings · { $count } unsaved · w to save
+settings-input-dirs = comma-separated · Enter to keep · Esc to cancel
+settings-input = Enter to keep · Esc to cancel
+summary-scanning = Scanning…
+summary-never = Never
+summary-repos = { $count } repos
+summary-dirty = { $count } dirty
+summary-procs = { $count } proc
+summary-filter = filter: "{ $filter }"
+summary-focus = focus: actionable
+summary-power-save = power-save scanning
+summary-not-found = ⚠ not found:
+summary-role-attached = attached · read-only
+summary-role-read-only = read-only
+summary-role-demo = demo · synthetic data
+summary-role-replay = replay · read-only
+
+## Sections
+
+col-name = NAME
+col-branch = BRANCH
+col-dirty = DIRTY
+col-sync = SYNC
+col-stash = STASH
+col-next = NEXT
+col-repo = REPO
+col-path = PATH
+col-state = STATE
+col-action = ACTION
+col-pid = PID
+col-elapsed = ELAPSED
+col-command = COMMAND
+col-ecosystems = ECOSYSTEMS
+col-issues = ISSUES
+col-details = DETAILS
+col-dependency = DEPENDENCY
+col-used-by = USED BY
+col-via = VIA
+col-unshared = UNSHARED
+col-files = FILES
+col-missing = MISSING
+col-extra = EXTRA
+col-tracked = TRACKED
+col-license = LICENSE
+col-deps = DEPS
+col-flagged = FLAGGED
+col-pull-request = PULL REQUEST
+col-review = REVIEW
+col-ci = CI
+col-client = CLIENT
+col-file = FILE
+col-servers = SERVERS
+col-models = MODELS
+col-perms = PERMS
+col-drift = DRIFT
+col-when = WHEN
+col-result = RESULT
+col-output = OUTPUT
+col-status = STATUS
+col-server = SERVER
+col-source = SOURCE
+col-health = HEALTH
+col-detail = DETAIL
+col-provider = PROVIDER
+col-updated = UPDATED
+col-config = CONFIG
+col-sessions = SESSIONS
+col-input-tokens = INPUT TOKENS
+col-output-tokens = OUTPUT TOKENS
+col-cost = COST
+col-forecast = FORECAST
+col-notes = NOTES
+title-repos = Repos ({ $count })
+title-worktrees = Worktrees ({ $count })
+title-processes = Processes ({ $count })
+title-dependencies = Dependencies ({ $count })
+title-local-dependencies = Local Dependencies ({ $count })
+title-env-audit = Env Audit ({ $count })
+title-licenses = Licenses ({ $count })
+title-pull-requests = Pull Requests ({ $count })
+title-ai-clients = AI Clients ({ $count })
+title-action-log = Action Log ({ $count })
+title-mcp-health = MCP Health ({ $count })
+title-ai-usage-cost = AI Usage & Cost ({ $count })
+repos-empty = No repositories found — run `agentpulse --setup` to configure watch directories
+repos-no-match = No repos matching "{ $filter }"
+repos-detached = (detached)
+worktrees-empty = No worktree data yet.
+worktree-detached = detached
+worktree-bare = bare
+worktree-normal = normal
+processes-empty = No repo-scoped running processes detected.
+deps-empty = No known dependency manifests found in scanned repos.
+deps-empty-hint =
+    Each repo root is checked for package.json, Cargo.toml, pyproject.toml,
+    requirements.txt, go.mod, and Gemfile (plus their lockfiles).
+deps-empty-key = change watched directories
+deps-clean = clean
+local-deps-empty = No watched repo depends on another by path.
+local-deps-empty-hint =
+    Detected links: Cargo `path =`, package.json `file:` / `link:` /
+    workspaces, and go.mod `replace => ../dir` in repo root manifests.
+local-deps-shared = ✓ shared
+local-deps-unshared = { $dirty } dirty · { $unpushed } unpushed
+env-empty = No .env files found in scanned repos.
+licenses-empty = No repo with a manifest or LICENSE file found.
+licenses-empty-hint =
+    Licenses come from Cargo.toml, package.json, pyproject.toml, or LICENSE.
+    Set [licenses] scan_dependencies = true to audit direct dependencies.
+licenses-commercial = { $license } (commercial)
+licenses-none-commercial = none (commercial)
+licenses-unknown = { $count } ({ $unknown } unknown)
+prs-empty = No repo with an origin on GitHub or GitLab.
+prs-off = Pull request and CI status is off.
+prs-empty-hint =
+    Set [forge] enabled = true to ask GitHub and GitLab about each branch.
+    Uses gh/glab when installed, else GITHUB_TOKEN or GITLAB_TOKEN.
+    Self-hosted forges go in [forge] hosts.
+prs-none = no open PR
+prs-draft = draft
+prs-awaiting = awaiting
+ai-clients-loading = Loading AI client configs…
+ai-clients-empty = No AI client config files detected.
+ai-clients-empty-hint =
+    Searched Claude Desktop, ~/.claude/settings.json, ~/.claude.json,
+    ~/.cursor/mcp.json, VS Code's settings.json and mcp.json, ~/.codex/config.toml.
+ai-clients-unreadable = unreadable
+ai-clients-in-sync = in sync
+ai-clients-drift = { $count } difference(s)
+action-log-empty = No actions run yet.
+action-log-empty-hint =
+    Every action you or an agent runs is appended to { $path }.
+    Refused ones too, with the reason.
+action-log-ok = ok
+action-log-exit = exit { $code }
+action-log-refused = refused
+action-log-failed = failed
+stats-waiting = Waiting for the first scan to finish…
+stats-none = No scan statistics: this view was not produced by a local scan.
+stats-ignored-none = none
+stats-last-scan = Last scan
+stats-discover = discover
+stats-status = status
+stats-collectors = collectors
+stats-cache = Status cache
+stats-cache-counts = { $hits } hits · { $misses } misses · { $deferred } deferred
+stats-hit-rate = { $rate }% hit rate
+stats-repos = Repos
+stats-watched = { $count } watched
+stats-ignored = ignored_repos skipped
+stats-git = Git processes
+stats-refresh = Refresh
+stats-polled = interval poll (watch_mode off)
+stats-title = Scan Statistics
+stats-all-cached = Every repo was answered from the status cache.
+stats-slowest = Slowest status checks
+mcp-loading = Loading MCP health data…
+mcp-empty = No MCP configuration files detected.
+mcp-empty-hint =
+    Searched Claude Desktop, ~/.cursor/mcp.json, ~/.config/agentpulse/mcp.json,
+    and .mcp.json, .cursor/mcp.json, .vscode/mcp.json in each repo.
+mcp-empty-key = create ~/.config/agentpulse/mcp.json
+mcp-healthy = ● healthy
+mcp-unhealthy = ● unhealthy
+mcp-lint = { $count } lint · { $detail }
+costs-loading = Loading AI usage and cost data…
+costs-open-config = open config.toml
+costs-privacy = AI usage tracking disabled (privacy mode).
+costs-disabled = AI usage tracking disabled in [providers] config.
+costs-disabled-hint = Turn providers back on under [providers] in config.toml.
+costs-empty = No AI provider configured.
+costs-empty-hint =
+    Set ANTHROPIC_API_KEY, OPENAI_API_KEY, or GEMINI_API_KEY (admin keys unlock billing data),
+    or point them at a secret store under [credentials] in config.toml.
+costs-yes = yes
+costs-no = no
+dirty-one-file = 1 file
+dirty-files = { $count } files
+detail-no-repo = No selected repo
+detail-no-worktree = No selected worktree
+detail-no-process = No selected process
+detail-no-dependency = No selected dependency row
+detail-no-local-dependency = No selected local dependency
+detail-no-env = No selected env audit row
+detail-no-license = No selected license row
+detail-no-mcp = No selected MCP row
+detail-no-provider = No selected provider row
+detail-no-pull-request = No selected pull request row
+detail-no-ai-client = No selected AI client row
+detail-no-action = No action selected
+detail-no-slow-repo = No slow repo selected
+detail-home = Use Home for overview alerts
+detail-toggle = Enter toggles
+detail-edit = Enter edits
+detail-setting = { $name }: { $doc } · { $edit } · w saves · R reloads from disk
+
+## First-launch tour
+
+tour-title = Tour { $step }/{ $count } · { $title }
+tour-keys = Enter next · ← back · Esc skip
+tour-sidebar-title = Sections
+tour-sidebar = Every view lives in the sidebar. h/l or Tab moves between sections, 1–9 and 0 jump straight to one. This tour uses example repos; yours appear once it ends.
+tour-summary-title = Summary bar
+tour-summary = Totals across every watched repo: how many need attention, open alerts, and when the last scan ran. It refreshes on its own; r rescans now.
+tour-action-title = Action key
+tour-action = Select a repo or alert with j/k and press x to review its suggested next step. The hints along the bottom change with the section.
+tour-confirm-title = Confirmation
+tour-confirm = Nothing runs until you confirm. The dialog shows the exact command and its risk: Enter or y runs it once, Esc or n cancels. Press ? any time for every key.
+
+## Setup wizard
+
+setup-title = AgentPulse — Setup Wizard
+setup-intro = AgentPulse will scan directories you choose for git repos.
+setup-current = Current watch directories:
+setup-not-found = ✗ not found
+setup-keep-hint = Press Enter to keep these, or type new paths to replace them.
+setup-detected = Detected directories on this machine:
+setup-input-hint =
+    Enter a number to add a suggestion, a full path, or leave
+    blank to finish. Supports ~ and $HOME.
+setup-none-detected = No standard directories detected. Enter full paths below.
+setup-added = Added: { $path }
+setup-no-suggestion = No suggestion [{ $number }] — try again or enter a path
+setup-missing-path = Warning: { $path } does not exist yet — added anyway
+setup-keeping = Keeping current directories.
+setup-defaults = Nothing entered — using default directories.
+setup-saved = Saved to { $path }
+setup-tip = Tip: run `agentpulse --setup` anytime to change these.
+
+## Dashboard-wide alerts
+
+alert-deps-title = Dependency hygiene issues detected
+alert-deps-detail = { $count } repo(s) with dependency issues
+alert-deps-action = open dependency view
+alert-deps-message = Switch to Deps section in AgentPulse
+alert-env-title = Tracked env files may contain secrets
+alert-env-detail = { $count } repo(s) have tracked sensitive env files
+alert-env-action = review env audit
+alert-env-message = Switch to Env Audit section in AgentPulse
+alert-mcp-title = MCP server health issues
+alert-mcp-detail = { $count } MCP server(s) unhealthy
+alert-mcp-action = inspect MCP
+alert-mcp-message = Switch to MCP Health section in AgentPulse
+alert-provider-title = AI provider not configured
+alert-provider-detail = { $count } provider(s) missing config
+alert-host-stale = { $detail } · ahead/behind may be stale for { $count } repo(s)
+alert-host-action = open integrations
+alert-host-message = Press I in AgentPulse for per-host connectivity
+alert-vpn-title = Connect VPN
+alert-vpn-detail = { $hosts } unreachable · pull/push suggestions paused for { $count } repo(s)
+alert-collector-failed = { $collector } collector failed
+
+## Collector alerts
+
+alert-ssh-push-title = SSH pushes to { $host } will fail
+alert-backup-title = { $repo } has { $count } commit(s) only on this machine
+alert-changelog-title = { $repo } changelog is behind
+alert-changelog-detail = { $count } unlogged change(s) since last { $file } update ({ $feat } feat, { $fix } fix)
+alert-policy-title = { $repo } is missing { $count } policy file(s)
+alert-ci-failing-title = { $repo }: CI failing on { $branch }
+alert-ci-failing-detail = latest run failed
+alert-review-title = { $repo }: #{ $number } awaiting review
+alert-fsck-title = { $repo } failed its integrity check (git fsck)
+alert-fsck-detail = { $problems } · checked { $date } · recovery: { $recovery }
+alert-gc-title = { $repo }: .git could shrink by ~{ $size }
+alert-status-degraded-title = { $repo } status checks degraded
+alert-local-changes-title = { $repo } has local changes
+alert-local-changes-detail = { $count } modified/untracked file(s)
+alert-behind-title = { $repo } is behind remote
+alert-behind-detail = { $count } commit(s) behind
+alert-unpushed-title = { $repo } has unpushed commits
+alert-unpushed-detail = { $count } commit(s) ahead
+alert-detached-worktree-title = Detached worktree in { $repo }
+alert-detached-worktree-detail = { $path } is detached
+alert-copyleft-title = { $repo } (commercial) depends on copyleft code
+alert-licenses-title = { $repo } has { $count } dependency license(s) outside the allow list
+alert-local-deps-title = { $repo } has changes its local dependents can't share
+alert-local-deps-detail = { $uncommitted } uncommitted · { $unpushed } unpushed; { $dependents } build against it by path, but CI and teammates only see what is pushed
+alert-lost-work-title = Possible lost work in { $repo }
+alert-lost-work-detail = { $count } unreachable commit(s) in the last { $days } days; newest { $sha } "{ $subject }"
+alert-maintenance-title = { $repo } is large: enable background maintenance
+
+## Notifications
+
+notify-ctl-action = Running action from agentpulse ctl
+notify-demo-read-only = Demo mode: actions are disabled
+notify-replay-read-only = Replaying a saved snapshot: actions are disabled
+notify-instance-read-only = Read-only instance: run actions from the primary agentpulse
+notify-off-hours-digest = Since { $since }: { $count } change(s) outside working hours
+notify-new-critical = New critical: { $alerts }
+notify-repo-moved = Repo moved: { $from } → { $to }
+notify-watch-coverage = watch_mode: { $coverage }
+notify-review-action = Review action: { $action }
+notify-running-action = Running action: { $action }
+notify-action-cancelled = Action cancelled
+notify-action-refused = ✗  action — { $error }
+notify-action-committed = ✓  action — committed { $sha } ({ $stat }) ({ $hint })
+notify-action-done = ✓  action — done ({ $hint })
+notify-action-output = ✓  action — { $output } ({ $hint })
+notify-action-failed-committed = ✗  action — { $error } (committed { $sha } first; u undoes it)
+notify-action-failed = ✗  action — { $error } (review and retry)
+notify-refresh-queued = Refresh queued
+notify-filter-repos-only = Filter is available in Repos section
+notify-focus-actionable = Agent focus: showing actionable repos
+notify-focus-all = Agent focus: showing all repos
+notify-save-dismissals-failed = Could not save dismissals: { $error }
+notify-nothing-to-dismiss = Nothing to dismiss on this row
+notify-no-action = No action available on this row
+notify-nothing-needs-attention = Nothing needs attention
+notify-no-one-key-action = No one-key action; run: { $command }
+notify-review-task = Review task
+notify-nothing-to-file = Nothing to file as a task on this row
+notify-settings-saved = Saved { $count } setting(s) to { $path }
+notify-settings-saved-restart = Saved { $count } setting(s) to { $path } (watch_mode applies after a restart)
+notify-save-settings-failed = Could not save settings: { $error }
+notify-settings-reloaded = Reloaded { $path }
+notify-reload-settings-failed = Could not reload settings: { $error }
+notify-latest-ci-run = the latest CI run
+notify-opening = Opening { $target }
+notify-open-failed = Could not open { $target }: { $error }
+notify-no-pull-request = No open pull request or CI run for this branch
+notify-no-ticket-link = No link for { $ticket }: set [tickets] url
+notify-no-ticket = No ticket linked to this branch
+notify-review-bundle = Review bundle action
+notify-no-bundle-dir = Set [backup] bundle_dir to bundle repos
+notify-review-fetch = Review fetch action
+notify-review-pull = Review pull action
+notify-review-push = Review push action
+notify-no-commits = No commits yet
+notify-read-commits-failed = Could not read commits: { $error }
+notify-nothing-to-stash = No local changes to stash
+notify-list-changes-failed = Could not list changes: { $error }
+notify-no-changes = No local changes
+notify-read-diff-failed = Could not read diff: { $error }
+notify-no-stashes = No stashes
+notify-list-stashes-failed = Could not list stashes: { $error }
+notify-no-branches = No local branches yet
+notify-list-branches-failed = Could not list branches: { $error }
+notify-history-too-short = Not enough history yet: a snapshot is saved every { $minutes } minutes
+notify-read-history-failed = Could not read history: { $error }
+notify-search-cleared = Search cleared
+notify-grep-started = Searching { $count } repos for "{ $pattern }"…
+notify-select-files = Select files with Space first
+notify-bad-repo-name = Repo name must be non-empty without spaces
+notify-nothing-to-push = Nothing to push
+notify-committing = Committing "{ $message }"…
+notify-already-on-branch = Already on { $branch }
+notify-bad-branch-name = Branch name must be non-empty without spaces
+notify-branch-exists = { $branch } already exists
+notify-mark-commits = Mark commits with Space first
+notify-no-cherry-pick-target = No other local branch to cherry-pick onto
+notify-no-target-branch = No target branch
+notify-select-repo-to-bookmark = Select a repo to bookmark
+notify-bookmarked = Bookmarked { $repo } as '{ $slot }
+notify-no-bookmark = No bookmark '{ $slot } (set one with m{ $slot })
+notify-bookmark-missing = Bookmark '{ $slot }: { $path } not found
+notify-hidden-clean = { $repo } is hidden by show_clean = false
+notify-no-recent = No recently used repos yet
+notify-no-matching-repo = No matching repo
+notify-nothing-to-skip = Nothing to skip
+notify-skipped = Skipped { $repo }
+notify-skipped-wrapped = Skipped { $repo }; back to the first suggestion
+notify-restored = Restored suggestion for { $repo }
+notify-dismissed = Dismissed "{ $action }" for { $repo } until it changes
+notify-no-repo-selected = No repo selected
+notify-no-undo = No auto-commit to undo in this repo
+notify-undo-not-head = { $commit } is pushed or no longer HEAD
+notify-review-undo = Review undo: "{ $subject }" stays staged
+
+## Plain output (--plain)
+
+plain-intro = Plain output. Type r and Enter to refresh, a to list every repo, q to quit.
+plain-commands = Commands: r refresh, a list every repo or only those needing attention, q quit.
+plain-updated = Updated at { $time }.
+plain-unchanged = No changes since the last update.
+plain-summary = Repos: { $repos }. Need attention: { $attention }. Alerts: { $alerts }.
+plain-no-repos = No git repos found. Check your config.
+plain-repo = { $name } on { $branch }: { $state }.
+plain-repo-next = { $name } on { $branch }: { $state }. Next: { $action }.
+plain-clean = clean
+plain-clean-count = Clean repos not listed: { $count }.
+plain-detached = detached HEAD
+plain-no-remote = no remote
+plain-dirty-one = 1 uncommitted file
+plain-dirty-many = { $count } uncommitted files
+plain-ahead-one = 1 commit not pushed
+plain-ahead-many = { $count } commits not pushed
+plain-behind-one = 1 commit behind
+plain-behind-many = { $count } commits behind
+plain-stash-one = 1 stash
+plain-stash-many = { $count } stashes
+plain-alert = { $severity }: { $title }. { $detail }
+plain-severity-critical = Critical
+plain-severity-high = Important
+plain-severity-warn = Warning
+plain-severity-info = Note
+
+## Weekly stats (--stats)
+
+stats-no-commits = This week (since { $since }): no commits yet.
+stats-totals = This week (since { $since }): { $commits } commit(s) in { $repos } repo(s), +{ $insertions } -{ $deletions }
+stats-repo-commits = { $count } commit(s)
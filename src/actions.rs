@@ -1,15 +1,387 @@
-use crate::dashboard::ActionKind;
+use crate::audit::{self, AuditEntry};
+use crate::collectors::git_gc::{format_kib, git_weight};
+use crate::collectors::lost_work::{find_lost_commits, prune_expiry};
+use crate::config::{branch_matches, Config, PushSafetyConfig};
+use crate::dashboard::{ActionKind, RemoteForge, TaskBackend};
+use crate::git::{check_repo_status, RepoStatus};
+use crate::host_limit;
+use crate::i18n::tr;
 use crate::path_utils::resolve_binary_in_path;
+use crate::push_check;
 use anyhow::anyhow;
 use anyhow::Result;
-use std::ffi::OsString;
+use chrono::Local;
+use serde::Serialize;
+use std::cell::RefCell;
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio::sync::mpsc::Sender;
 
+/// Interpreters and launchers that would reintroduce shell interpretation;
+/// custom actions refuse them even when allowlisted.
+const SHELL_PROGRAMS: &[&str] = &[
+    "sh",
+    "bash",
+    "zsh",
+    "fish",
+    "dash",
+    "ksh",
+    "csh",
+    "tcsh",
+    "cmd",
+    "powershell",
+    "pwsh",
+    "env",
+];
+
+/// Config an action may need at run time.
+#[derive(Debug, Clone, Default)]
+pub struct ActionSettings {
+    pub push_safety: PushSafetyConfig,
+    /// `[actions] allowed_programs`, for `custom` actions.
+    pub allowed_programs: Vec<String>,
+    /// `[actions] max_per_host`; 0 = no limit.
+    pub max_per_host: usize,
+    /// `[actions] host_spacing_ms`.
+    pub host_spacing: Duration,
+    /// Scanned state of the target repo; when set, medium and high risk
+    /// actions check it is still current before running.
+    pub expected: Option<ExpectedState>,
+    /// [`Config::protected_branches`]: commits and pushes there are refused.
+    pub protected_branches: Vec<String>,
+    /// The protected branch the user confirmed a write to a second time.
+    pub allow_protected: Option<String>,
+    /// Let [`run_for_result`] run destructive actions, which it refuses
+    /// otherwise since no one confirms them (`--allow-destructive`).
+    pub allow_destructive: bool,
+    /// Where outcomes are appended; `None` keeps no audit trail.
+    pub audit_log: Option<PathBuf>,
+}
+
+impl ActionSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            push_safety: config.push_safety.clone(),
+            allowed_programs: config.actions.allowed_programs.clone(),
+            max_per_host: config.actions.max_per_host,
+            host_spacing: Duration::from_millis(config.actions.host_spacing_ms),
+            expected: None,
+            protected_branches: config.protected_branches(),
+            allow_protected: None,
+            allow_destructive: false,
+            audit_log: Some(audit::log_path()),
+        }
+    }
+}
+
+/// Append an action's outcome to the audit log, if `settings` keeps one.
+/// A log that can't be written never blocks the action.
+fn audit_outcome(
+    settings: &ActionSettings,
+    action: &ActionKind,
+    result: &Result<String>,
+    exit_code: Option<i32>,
+    refused: bool,
+) {
+    let Some(path) = &settings.audit_log else {
+        return;
+    };
+    let output = match result {
+        Ok(first) => first.lines().next().unwrap_or_default().to_string(),
+        Err(e) => e.to_string(),
+    };
+    let entry = AuditEntry {
+        refused,
+        ..AuditEntry::new(action, result.is_ok(), exit_code, &output)
+    };
+    let _ = audit::append(path, &entry);
+}
+
+/// The parts of a repo's status a suggested action was based on.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExpectedState {
+    pub branch: String,
+    pub dirty: usize,
+    pub unpushed: usize,
+    pub behind: usize,
+    pub stash: usize,
+    /// HEAD's sha, when the scan saw one.
+    pub head: Option<String>,
+}
+
+impl From<&RepoStatus> for ExpectedState {
+    fn from(status: &RepoStatus) -> Self {
+        Self {
+            branch: status.branch.clone(),
+            dirty: status.uncommitted_count,
+            unpushed: status.unpushed_count,
+            behind: status.behind_count,
+            stash: status.stash_count,
+            head: status.head_sha.clone(),
+        }
+    }
+}
+
+impl ExpectedState {
+    /// What differs in `now`, e.g. "dirty 2 → 3"; empty when nothing does.
+    fn changes(&self, now: &ExpectedState) -> Vec<String> {
+        let mut out = Vec::new();
+        if self.branch != now.branch {
+            out.push(format!("branch {} → {}", self.branch, now.branch));
+        }
+        // Same counts can hide a different commit, e.g. after a reset or an
+        // amend.
+        if let (Some(before), Some(after)) = (&self.head, &now.head) {
+            if before != after {
+                let short = |sha: &str| sha[..sha.len().min(8)].to_string();
+                out.push(format!("HEAD {} → {}", short(before), short(after)));
+            }
+        }
+        for (label, before, after) in [
+            ("dirty", self.dirty, now.dirty),
+            ("unpushed", self.unpushed, now.unpushed),
+            ("behind", self.behind, now.behind),
+            ("stash", self.stash, now.stash),
+        ] {
+            if before != after {
+                out.push(format!("{} {} → {}", label, before, after));
+            }
+        }
+        out
+    }
+}
+
+/// Checks that stop an action before it starts: a repo that moved since
+/// the scan, and a commit or push to a protected branch.
+async fn preflight(action: &ActionKind, repo_path: &str, settings: &ActionSettings) -> Result<()> {
+    if let Some(expected) = &settings.expected {
+        if action.risk_level() != "low" {
+            verify_state(repo_path, expected).await?;
+        }
+    }
+    if settings.protected_branches.is_empty() {
+        return Ok(());
+    }
+    let branch = match action {
+        ActionKind::GitPushSetUpstream { branch, .. } => branch.clone(),
+        _ if writes_branch(action) => {
+            unrecorded(run_git(repo_path, &["rev-parse", "--abbrev-ref", "HEAD"])).await?
+        }
+        _ => return Ok(()),
+    };
+    if branch_matches(&settings.protected_branches, &branch)
+        && settings.allow_protected.as_deref() != Some(branch.as_str())
+    {
+        return Err(anyhow!(
+            "`{}` is protected; commit on a feature branch, or confirm twice in the dashboard",
+            branch
+        ));
+    }
+    Ok(())
+}
+
+/// `[push_safety]` checks, against the same protected branches as
+/// [`preflight`] and honouring a second confirmation.
+async fn push_guard(repo_path: &str, settings: &ActionSettings) -> Result<()> {
+    push_check::guard(
+        Path::new(repo_path),
+        &settings.push_safety,
+        &settings.protected_branches,
+        settings.allow_protected.as_deref(),
+    )
+    .await
+}
+
+/// Fail unless the repo still looks the way `expected` says.
+async fn verify_state(repo_path: &str, expected: &ExpectedState) -> Result<()> {
+    let status = check_repo_status(Path::new(repo_path))
+        .await
+        .map_err(|e| anyhow!("could not re-check repo state: {}", e))?;
+    let changes = expected.changes(&ExpectedState::from(&status));
+    if changes.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("state changed, rescan ({})", changes.join(", ")))
+    }
+}
+
+/// Output lines kept per stream in an [`ActionResult`].
+const RESULT_TAIL_LINES: usize = 20;
+
+tokio::task_local! {
+    /// Commands run by the action in scope, for its result and audit entry.
+    static CAPTURED: RefCell<Vec<CommandOutput>>;
+}
+
+/// Run `fut` without adding its commands to the action's result, for
+/// lookups and cleanup the action itself isn't about.
+async fn unrecorded<T>(fut: impl std::future::Future<Output = T>) -> T {
+    CAPTURED.scope(RefCell::new(Vec::new()), fut).await
+}
+
+#[derive(Debug, Clone)]
+struct CommandOutput {
+    command: String,
+    exit_code: Option<i32>,
+    stdout: String,
+    stderr: String,
+}
+
+/// Outcome of one action for scripts and agents (`agentpulse run`).
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionResult {
+    pub ok: bool,
+    /// Exit code of the command that failed the action, or of its last
+    /// command when it succeeded; `None` when it ran none, or the command
+    /// was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// The one-line summary the dashboard would show.
+    pub message: String,
+    /// Every command run, in order.
+    pub commands: Vec<String>,
+    /// Last lines of that command's output.
+    pub stdout_tail: Vec<String>,
+    pub stderr_tail: Vec<String>,
+    /// Commit the action created, if any.
+    pub commit: Option<CommitRecord>,
+    /// Status of the action's repo afterwards; `None` without a repo.
+    pub repo_status: Option<RepoStatus>,
+}
+
+/// Run `action` here and now, with the same checks as [`run_action`], and
+/// collect a structured result instead of a notification.
+pub async fn run_for_result(action: &ActionKind, settings: &ActionSettings) -> ActionResult {
+    let started = std::time::Instant::now();
+    let repo_path = action.affected_repo_path();
+    let head_before = match repo_path {
+        Some(path) if creates_commit(action) => run_git(path, &["rev-parse", "HEAD"]).await.ok(),
+        _ => None,
+    };
+    let (result, refused, outputs) = CAPTURED
+        .scope(RefCell::new(Vec::new()), async {
+            let checked = match check_unconfirmed(
+                action,
+                &settings.allowed_programs,
+                settings.allow_destructive,
+            ) {
+                Err(e) => Err(e),
+                Ok(()) => match repo_path {
+                    Some(path) => preflight(action, path, settings).await,
+                    None => Ok(()),
+                },
+            };
+            let refused = checked.is_err();
+            let result = match checked {
+                Ok(()) => execute_action(action, settings).await,
+                Err(e) => Err(e),
+            };
+            (result, refused, CAPTURED.with(|captured| captured.take()))
+        })
+        .await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    let primary = primary_output(&outputs, result.is_ok());
+    let exit_code = primary.and_then(|o| o.exit_code);
+    audit_outcome(settings, action, &result, exit_code, refused);
+    let commit = match repo_path {
+        Some(path) if creates_commit(action) => capture_commit(path)
+            .await
+            .ok()
+            .filter(|c| head_before.as_deref() != Some(c.sha.as_str())),
+        _ => None,
+    };
+    let repo_status = match repo_path {
+        Some(path) => check_repo_status(Path::new(path)).await.ok(),
+        None => None,
+    };
+    ActionResult {
+        ok: result.is_ok(),
+        exit_code,
+        duration_ms,
+        message: match &result {
+            Ok(first) => first.clone(),
+            Err(e) => e.to_string(),
+        },
+        commands: outputs.iter().map(|o| o.command.clone()).collect(),
+        stdout_tail: primary
+            .map(|o| tail(&o.stdout, RESULT_TAIL_LINES))
+            .unwrap_or_default(),
+        stderr_tail: primary
+            .map(|o| tail(&o.stderr, RESULT_TAIL_LINES))
+            .unwrap_or_default(),
+        commit,
+        repo_status,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ActionCompletion {
     pub affected_repo_path: Option<String>,
+    /// Commit created by the action, if it made one.
+    pub commit: Option<CommitRecord>,
+}
+
+/// A commit made by an action, kept in the session history so it can be undone.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommitRecord {
+    pub repo_path: String,
+    pub sha: String,
+    pub subject: String,
+    /// `git diff --shortstat` summary, e.g. "2 files changed, 5 insertions(+)".
+    pub shortstat: String,
+}
+
+impl CommitRecord {
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(8)]
+    }
+}
+
+/// Read HEAD's hash, subject, and shortstat right after a commit action.
+async fn capture_commit(repo_path: &str) -> Result<CommitRecord> {
+    let head = run_git(repo_path, &["log", "-1", "--format=%H%x1f%s"]).await?;
+    let (sha, subject) = head
+        .split_once('\u{1f}')
+        .ok_or_else(|| anyhow!("could not read new commit"))?;
+    let shortstat = run_git(
+        repo_path,
+        &[
+            "diff-tree",
+            "--shortstat",
+            "--root",
+            "--no-commit-id",
+            "HEAD",
+        ],
+    )
+    .await?;
+    Ok(CommitRecord {
+        repo_path: repo_path.to_string(),
+        sha: sha.to_string(),
+        subject: subject.to_string(),
+        shortstat,
+    })
+}
+
+/// Adds, commits or pushes on the checked-out branch.
+pub fn writes_branch(action: &ActionKind) -> bool {
+    creates_commit(action)
+        || matches!(
+            action,
+            ActionKind::GitPush { .. }
+                | ActionKind::GitPushSetUpstream { .. }
+                | ActionKind::GitPullRebasePush { .. }
+        )
+}
+
+fn creates_commit(action: &ActionKind) -> bool {
+    matches!(
+        action,
+        ActionKind::GitAddCommit { .. }
+            | ActionKind::GitAddCommitPush { .. }
+            | ActionKind::GitAddCommitPullRebase { .. }
+    )
 }
 
 /// Open a repo in the configured editor (detached process).
@@ -32,128 +404,379 @@ pub fn open_in_editor(repo_path: &Path, editor: &str) -> Result<()> {
 
 /// Open a repo in the OS file manager.
 pub fn open_in_file_manager(repo_path: &Path) -> Result<()> {
+    open_with_system(repo_path.as_os_str())
+}
+
+/// Open a web page in the default browser.
+pub fn open_url(url: &str) -> Result<()> {
+    open_with_system(std::ffi::OsStr::new(url))
+}
+
+fn open_with_system(target: &std::ffi::OsStr) -> Result<()> {
     #[cfg(target_os = "macos")]
     {
-        std::process::Command::new("open").arg(repo_path).spawn()?;
+        std::process::Command::new("open").arg(target).spawn()?;
     }
     #[cfg(target_os = "linux")]
     {
-        std::process::Command::new("xdg-open")
-            .arg(repo_path)
-            .spawn()?;
+        std::process::Command::new("xdg-open").arg(target).spawn()?;
     }
     #[cfg(target_os = "windows")]
     {
-        std::process::Command::new("explorer")
-            .arg(repo_path)
-            .spawn()?;
+        std::process::Command::new("explorer").arg(target).spawn()?;
     }
     Ok(())
 }
 
-/// Run `git commit -a -m <message>` asynchronously; send a status notification when done.
-pub fn git_commit(
-    repo_path: &Path,
-    message: &str,
-    notif_tx: Sender<String>,
-    completion_tx: Sender<ActionCompletion>,
-) {
-    let path = repo_path.to_path_buf();
-    let message = message.to_string();
-    tokio::spawn(async move {
-        let result = tokio::process::Command::new("git")
-            .args(["commit", "-a", "-m", &message])
-            .current_dir(&path)
-            .output()
-            .await;
-        let name = path
-            .file_name()
-            .map(|n| n.to_string_lossy().into_owned())
-            .unwrap_or_default();
-        let msg = match result {
-            Ok(o) if o.status.success() => format!("✓  committed {} — \"{}\"", name, message),
-            Ok(o) => {
-                let err = String::from_utf8_lossy(&o.stderr);
-                let first = err.lines().next().unwrap_or("nothing to commit");
-                format!("✗  commit {} — {}", name, first)
-            }
-            Err(e) => format!("✗  commit {} — {}", name, e),
-        };
-        let _ = notif_tx.send(msg).await;
-        let _ = completion_tx
-            .send(ActionCompletion {
-                affected_repo_path: Some(path.to_string_lossy().to_string()),
-            })
-            .await;
-    });
-}
-
 /// Run a typed, allowlisted action asynchronously and report the first-line result.
+/// Pushes go through the `[push_safety]` checks first when enabled; risky
+/// actions are refused when the repo moved since `settings.expected`, and
+/// commits and pushes on a protected branch unless `allow_protected` names it.
 pub fn run_action(
     action: ActionKind,
+    settings: ActionSettings,
     notif_tx: Sender<String>,
     completion_tx: Sender<ActionCompletion>,
 ) {
     tokio::spawn(async move {
         let affected_repo_path = action.affected_repo_path().map(ToString::to_string);
-        let msg = match execute_action(&action).await {
+        let checked = match affected_repo_path.as_deref() {
+            Some(repo_path) => preflight(&action, repo_path, &settings).await,
+            None => Ok(()),
+        };
+        if let Err(e) = checked {
+            let msg = tr("notify-action-refused", &[("error", &e)]);
+            audit_outcome(&settings, &action, &Err(e), None, true);
+            let _ = notif_tx.send(msg).await;
+            // The completion refreshes the repo, which is the rescan asked for.
+            let _ = completion_tx
+                .send(ActionCompletion {
+                    affected_repo_path,
+                    commit: None,
+                })
+                .await;
+            return;
+        }
+        let head_before = match affected_repo_path.as_deref() {
+            Some(repo_path) if creates_commit(&action) => {
+                Some(run_git(repo_path, &["rev-parse", "HEAD"]).await.ok())
+            }
+            _ => None,
+        };
+        let slot = match affected_repo_path.as_deref() {
+            Some(repo_path) if action.needs_network() => match remote_host(repo_path).await {
+                Some(host) => Some(
+                    host_limit::acquire(&host, settings.max_per_host, settings.host_spacing).await,
+                ),
+                None => None,
+            },
+            _ => None,
+        };
+        let (result, outputs) = CAPTURED
+            .scope(RefCell::new(Vec::new()), async {
+                let result = execute_action(&action, &settings).await;
+                (result, CAPTURED.with(|captured| captured.take()))
+            })
+            .await;
+        drop(slot);
+        audit_outcome(
+            &settings,
+            &action,
+            &result,
+            primary_output(&outputs, result.is_ok()).and_then(|o| o.exit_code),
+            false,
+        );
+        // A later step (push, pull) can fail after the commit landed, so compare
+        // HEAD rather than trusting the overall result.
+        let commit = match (head_before, affected_repo_path.as_deref()) {
+            (Some(before), Some(repo_path)) => capture_commit(repo_path)
+                .await
+                .ok()
+                .filter(|c| before.as_deref() != Some(c.sha.as_str())),
+            _ => None,
+        };
+        let msg = match result {
             Ok(first) => {
                 let hint = success_hint(&action);
-                if first.is_empty() {
-                    format!("✓  action — done ({})", hint)
+                if let Some(c) = &commit {
+                    tr(
+                        "notify-action-committed",
+                        &[
+                            ("sha", &c.short_sha()),
+                            ("stat", &c.shortstat),
+                            ("hint", &hint),
+                        ],
+                    )
+                } else if first.is_empty() {
+                    tr("notify-action-done", &[("hint", &hint)])
                 } else {
-                    format!("✓  action — {} ({})", first, hint)
+                    tr(
+                        "notify-action-output",
+                        &[("output", &first), ("hint", &hint)],
+                    )
                 }
             }
-            Err(e) => format!("✗  action — {} (review and retry)", e),
+            Err(e) => match &commit {
+                Some(c) => tr(
+                    "notify-action-failed-committed",
+                    &[("error", &e), ("sha", &c.short_sha())],
+                ),
+                None => tr("notify-action-failed", &[("error", &e)]),
+            },
         };
         let _ = notif_tx.send(msg).await;
         let _ = completion_tx
-            .send(ActionCompletion { affected_repo_path })
+            .send(ActionCompletion {
+                affected_repo_path,
+                commit,
+            })
             .await;
     });
 }
 
-async fn execute_action(action: &ActionKind) -> Result<String> {
+async fn execute_action(action: &ActionKind, settings: &ActionSettings) -> Result<String> {
     match action {
         ActionKind::GitStatus { repo_path } => run_git(repo_path, &["status", "-sb"]).await,
         ActionKind::GitFetch { repo_path } => run_git(repo_path, &["fetch", "--quiet"]).await,
         ActionKind::GitPullRebase { repo_path } => run_git(repo_path, &["pull", "--rebase"]).await,
-        ActionKind::GitPush { repo_path } => run_git(repo_path, &["push"]).await,
+        ActionKind::GitPush { repo_path } => {
+            push_guard(repo_path, settings).await?;
+            run_git(repo_path, &["push"]).await
+        }
+        ActionKind::GitPushSetUpstream {
+            repo_path,
+            remote,
+            branch,
+        } => {
+            push_guard(repo_path, settings).await?;
+            run_git(repo_path, &["push", "-u", remote, branch]).await
+        }
         ActionKind::GitWorktreeList { repo_path } => {
             run_git(repo_path, &["worktree", "list"]).await
         }
         ActionKind::GitAddCommitPullRebase { repo_path, message } => {
-            run_git(repo_path, &["add", "-A"]).await?;
+            stage_tracked(repo_path).await?;
             run_git(repo_path, &["commit", "-m", message]).await?;
             run_git(repo_path, &["pull", "--rebase"]).await
         }
         ActionKind::GitPullRebasePush { repo_path } => {
             run_git(repo_path, &["pull", "--rebase"]).await?;
+            push_guard(repo_path, settings).await?;
             run_git(repo_path, &["push"]).await
         }
         ActionKind::GitAddCommitPush { repo_path, message } => {
-            run_git(repo_path, &["add", "-A"]).await?;
+            stage_tracked(repo_path).await?;
             run_git(repo_path, &["commit", "-m", message]).await?;
+            push_guard(repo_path, settings).await?;
             run_git(repo_path, &["push"]).await
         }
         ActionKind::GitAddCommit { repo_path, message } => {
-            run_git(repo_path, &["add", "-A"]).await?;
+            stage_tracked(repo_path).await?;
             run_git(repo_path, &["commit", "-m", message]).await
         }
         ActionKind::GitStashList { repo_path } => run_git(repo_path, &["stash", "list"]).await,
+        ActionKind::GitStashPushPaths {
+            repo_path,
+            message,
+            paths,
+        } => {
+            let mut args = vec![
+                "stash".to_string(),
+                "push".to_string(),
+                "--include-untracked".to_string(),
+                "-m".to_string(),
+                message.clone(),
+                "--".to_string(),
+            ];
+            args.extend(paths.iter().cloned());
+            run_cmd_owned(Some(repo_path), "git", args).await
+        }
+        ActionKind::GitStashApply {
+            repo_path,
+            stash,
+            oid,
+        } => {
+            let stash = stash_ref(repo_path, stash, oid.as_deref()).await?;
+            run_git(repo_path, &["stash", "apply", stash]).await
+        }
+        ActionKind::GitStashPop {
+            repo_path,
+            stash,
+            oid,
+        } => {
+            let stash = stash_ref(repo_path, stash, oid.as_deref()).await?;
+            run_git(repo_path, &["stash", "pop", stash]).await
+        }
+        ActionKind::GitStashDrop {
+            repo_path,
+            stash,
+            oid,
+        } => {
+            let stash = stash_ref(repo_path, stash, oid.as_deref()).await?;
+            run_git(repo_path, &["stash", "drop", stash]).await
+        }
         ActionKind::GitRemoteList { repo_path } => run_git(repo_path, &["remote", "-v"]).await,
         ActionKind::GitSwitchCreate { repo_path, branch } => {
             run_git(repo_path, &["switch", "-c", branch]).await
         }
+        ActionKind::GitSwitch {
+            repo_path,
+            branch,
+            autostash,
+        } => {
+            let stashed = *autostash
+                && !run_git(repo_path, &["status", "--porcelain"])
+                    .await?
+                    .is_empty();
+            if stashed {
+                let message = format!("agentpulse: autostash before switching to {}", branch);
+                run_git(repo_path, &["stash", "push", "-u", "-m", &message]).await?;
+            }
+            if let Err(e) = run_git(repo_path, &["switch", branch]).await {
+                if stashed {
+                    let _ = run_git(repo_path, &["stash", "pop"]).await;
+                }
+                return Err(e);
+            }
+            if stashed {
+                run_git(repo_path, &["stash", "pop"]).await.map_err(|e| {
+                    anyhow!(
+                        "switched to {}, but re-applying stashed changes failed ({}); they are kept in git stash",
+                        branch,
+                        e
+                    )
+                })?;
+                return Ok(format!("switched to {} with local changes", branch));
+            }
+            Ok(format!("switched to {}", branch))
+        }
+        ActionKind::GitCherryPick {
+            repo_path,
+            branch,
+            commits,
+        } => cherry_pick_onto(repo_path, branch, commits).await,
+        ActionKind::GitBranchAt {
+            repo_path,
+            branch,
+            commit,
+        } => {
+            run_git(repo_path, &["branch", branch, commit]).await?;
+            Ok(format!("created {} at {}", branch, commit))
+        }
+        ActionKind::GitUndoCommit { repo_path, commit } => {
+            let short = &commit[..commit.len().min(8)];
+            if !crate::git::is_unpushed_head(Path::new(repo_path), commit) {
+                return Err(anyhow!("{} is no longer an unpushed HEAD", short));
+            }
+            run_git(repo_path, &["reset", "--soft", "HEAD~1"]).await?;
+            Ok(format!("undid {}", short))
+        }
+        ActionKind::CreateRemoteRepo {
+            repo_path,
+            forge,
+            name,
+            private,
+        } => {
+            let cli = forge.cli();
+            if resolve_binary_in_path(cli).is_none() {
+                return Err(anyhow!("{} not found in PATH; install it and log in", cli));
+            }
+            // The first publish of a repo is its default branch, which is
+            // usually protected; the other push checks still apply.
+            push_check::guard(Path::new(repo_path), &settings.push_safety, &[], None).await?;
+            let visibility = if *private { "--private" } else { "--public" };
+            match forge {
+                RemoteForge::Github => {
+                    run_cmd(
+                        Some(repo_path),
+                        cli,
+                        &[
+                            "repo", "create", name, visibility, "--source", ".", "--remote",
+                            "origin", "--push",
+                        ],
+                    )
+                    .await?;
+                }
+                RemoteForge::Gitlab => {
+                    // glab adds `origin` itself when run inside a git checkout.
+                    run_cmd(Some(repo_path), cli, &["repo", "create", name, visibility]).await?;
+                    if unrecorded(run_git(repo_path, &["remote", "get-url", "origin"]))
+                        .await
+                        .is_err()
+                    {
+                        return Err(anyhow!(
+                            "created {} but origin was not added; run git remote add origin <url>",
+                            name
+                        ));
+                    }
+                    run_git(repo_path, &["push", "-u", "origin", "HEAD"]).await?;
+                }
+            }
+            Ok(format!("created {} on {} and pushed", name, forge.label()))
+        }
+        ActionKind::GitBundleCreate { repo_path, output } => {
+            bundle_repo(Path::new(repo_path), Path::new(output)).await
+        }
+        ActionKind::GitGc {
+            repo_path,
+            aggressive,
+        } => gc_repo(Path::new(repo_path), *aggressive).await,
+        ActionKind::GitMaintenanceStart {
+            repo_path,
+            scalar: true,
+        } => run_cmd(None, "scalar", &["register", repo_path]).await,
+        ActionKind::GitMaintenanceStart {
+            repo_path,
+            scalar: false,
+        } => run_git(repo_path, &["maintenance", "start"]).await,
+        ActionKind::DraftChangelog { repo_path } => {
+            let draft = crate::collectors::changelog::write_draft(Path::new(repo_path))?;
+            Ok(format!("draft written to {}", draft.display()))
+        }
         ActionKind::KillProcess { pid } => run_cmd_owned(None, "kill", vec![pid.to_string()]).await,
-        ActionKind::NpmInstallLockfile { repo_path } => {
-            run_cmd(Some(repo_path), "npm", &["install", "--package-lock-only"]).await
+        ActionKind::NpmInstallLockfile { repo_path, manager } => {
+            let binary = manager.binary();
+            if resolve_binary_in_path(binary).is_none() {
+                return Err(anyhow!("{} not found in PATH", binary));
+            }
+            run_cmd(Some(repo_path), binary, manager.lockfile_args()).await
         }
         ActionKind::CargoGenerateLockfile { repo_path } => {
             run_cmd(Some(repo_path), "cargo", &["generate-lockfile"]).await
         }
+        ActionKind::CargoUpdateWorkspace { repo_path } => {
+            run_cmd(Some(repo_path), "cargo", &["update", "--workspace"]).await
+        }
         ActionKind::UvLock { repo_path } => run_cmd(Some(repo_path), "uv", &["lock"]).await,
+        ActionKind::UvSync { repo_path } => run_cmd(Some(repo_path), "uv", &["sync"]).await,
+        ActionKind::PipVenvSync {
+            repo_path,
+            python,
+            recreate,
+            venv,
+        } => {
+            let dir = venv.as_deref().unwrap_or(".venv");
+            let venv = Path::new(repo_path).join(dir);
+            if *recreate || !venv.join("pyvenv.cfg").exists() {
+                let mut args = vec!["-m", "venv"];
+                if *recreate {
+                    args.push("--clear");
+                }
+                args.push(dir);
+                run_cmd(Some(repo_path), python, &args).await?;
+            }
+            let venv_python = if cfg!(windows) {
+                venv.join("Scripts").join("python.exe")
+            } else {
+                venv.join("bin").join("python")
+            };
+            run_cmd(
+                Some(repo_path),
+                &venv_python.to_string_lossy(),
+                &["-m", "pip", "install", "-r", "requirements.txt"],
+            )
+            .await
+        }
         ActionKind::PipCompileRequirements { repo_path } => {
             run_cmd(Some(repo_path), "pip-compile", &["requirements.txt"]).await
         }
@@ -179,6 +802,41 @@ async fn execute_action(action: &ActionKind) -> Result<String> {
             fs::copy(&from, &to)?;
             Ok("seeded .env from .env.example".to_string())
         }
+        ActionKind::Custom {
+            program, args, cwd, ..
+        } => {
+            let resolved =
+                validate_custom(program, args, cwd.as_deref(), &settings.allowed_programs)?;
+            run_cmd_owned(cwd.as_deref(), &resolved.to_string_lossy(), args.clone()).await
+        }
+        ActionKind::ScaffoldFiles { repo_path, files } => {
+            let destinations = files
+                .iter()
+                .map(|file| inside_repo(repo_path, &file.path))
+                .collect::<Result<Vec<_>>>()?;
+            let root = Path::new(repo_path).canonicalize()?;
+            let mut created = 0;
+            for (file, to) in files.iter().zip(destinations) {
+                if to.symlink_metadata().is_ok() {
+                    continue;
+                }
+                // A symlinked directory on the way could still lead out of
+                // the repo; what is left to create below it cannot.
+                let existing = to.ancestors().skip(1).find(|dir| dir.exists());
+                if !existing
+                    .is_some_and(|dir| dir.canonicalize().is_ok_and(|dir| dir.starts_with(&root)))
+                {
+                    return Err(anyhow!("{} is not a path inside the repo", file.path));
+                }
+                if let Some(parent) = to.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(&file.template, &to)
+                    .map_err(|e| anyhow!("copy {}: {}", file.template, e))?;
+                created += 1;
+            }
+            Ok(format!("created {} file(s)", created))
+        }
         ActionKind::ProbeBinaryHelp { binary } => run_cmd(None, binary, &["--help"]).await,
         ActionKind::CheckBinaryInPath { binary } => {
             if resolve_binary_in_path(binary).is_some() {
@@ -188,13 +846,391 @@ async fn execute_action(action: &ActionKind) -> Result<String> {
             }
         }
         ActionKind::ShowMessage { message } => Ok(message.clone()),
+        ActionKind::CreateMcpConfig { path } => {
+            let path = Path::new(path);
+            if path.exists() {
+                return Err(anyhow!("{} already exists", path.display()));
+            }
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, "{\n  \"mcpServers\": {}\n}\n")?;
+            Ok(format!("created {}", path.display()))
+        }
+        ActionKind::OpenInEditor { path, editor } => {
+            open_in_editor(Path::new(path), editor)?;
+            Ok(format!("opened {}", path))
+        }
+        ActionKind::CreateTask {
+            backend,
+            title,
+            notes,
+            file,
+        } => create_task(*backend, title, notes, file.as_deref()).await,
+    }
+}
+
+/// File `title` with its `notes` in the task manager `backend`.
+async fn create_task(
+    backend: TaskBackend,
+    title: &str,
+    notes: &[String],
+    file: Option<&str>,
+) -> Result<String> {
+    match backend {
+        TaskBackend::Markdown | TaskBackend::Org => {
+            let file = file.ok_or_else(|| anyhow!("no [tasks] file configured"))?;
+            let entry = task_entry(backend, title, notes, &Local::now());
+            append_task(Path::new(file), &entry)?;
+            Ok(format!("added to {}", file))
+        }
+        TaskBackend::Taskwarrior => {
+            // `--` keeps words like `project:x` in the title literal.
+            run_cmd(None, "task", &["rc.confirmation=off", "add", "--", title]).await?;
+            for note in notes {
+                run_cmd(
+                    None,
+                    "task",
+                    &["rc.confirmation=off", "+LATEST", "annotate", "--", note],
+                )
+                .await?;
+            }
+            Ok("added to Taskwarrior".to_string())
+        }
+        TaskBackend::Things => {
+            if !cfg!(target_os = "macos") {
+                return Err(anyhow!("Things is only available on macOS"));
+            }
+            let url = format!(
+                "things:///add?title={}&notes={}",
+                percent_encode(title),
+                percent_encode(&notes.join("\n"))
+            );
+            run_cmd(None, "open", &[&url]).await?;
+            Ok("sent to Things".to_string())
+        }
+    }
+}
+
+/// The text appended to a Markdown or Org task file.
+fn task_entry(
+    backend: TaskBackend,
+    title: &str,
+    notes: &[String],
+    at: &chrono::DateTime<Local>,
+) -> String {
+    let mut entry = match backend {
+        TaskBackend::Org => format!("* TODO {}\n  [{}]\n", title, at.format("%Y-%m-%d %a %H:%M")),
+        _ => format!("- [ ] {} ({})\n", title, at.format("%Y-%m-%d")),
+    };
+    for note in notes {
+        entry.push_str(&format!("  - {}\n", note));
+    }
+    entry
+}
+
+fn append_task(path: &Path, entry: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
     }
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        file.write_all(b"\n")?;
+    }
+    file.write_all(entry.as_bytes())?;
+    Ok(())
+}
+
+/// RFC 3986 percent-encoding of everything but unreserved characters.
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Cherry-pick `commits` onto `branch` without touching the current checkout:
+/// in the worktree that has `branch` checked out, else a temporary one.
+async fn cherry_pick_onto(repo_path: &str, branch: &str, commits: &[String]) -> Result<String> {
+    let checked_out = crate::git::list_worktree_branches(Path::new(repo_path))?
+        .into_iter()
+        .find(|(b, _)| b == branch)
+        .map(|(_, path)| path.to_string_lossy().to_string());
+    let (dir, temporary) = match checked_out {
+        Some(dir) => {
+            if !run_git(&dir, &["status", "--porcelain", "--untracked-files=no"])
+                .await?
+                .is_empty()
+            {
+                return Err(anyhow!(
+                    "{} has uncommitted changes in {}; commit or stash them first",
+                    branch,
+                    dir
+                ));
+            }
+            (dir, false)
+        }
+        None => {
+            let dir = std::env::temp_dir()
+                .join(format!(
+                    "agentpulse-cherry-pick-{}-{}",
+                    std::process::id(),
+                    branch.replace('/', "-")
+                ))
+                .to_string_lossy()
+                .to_string();
+            run_git(repo_path, &["worktree", "add", &dir, branch]).await?;
+            (dir, true)
+        }
+    };
+
+    let mut args = vec!["cherry-pick", "-x"];
+    args.extend(commits.iter().map(String::as_str));
+    let result = match run_git(&dir, &args).await {
+        Ok(_) => Ok(format!(
+            "picked {} commit(s) onto {}",
+            commits.len(),
+            branch
+        )),
+        Err(e) => {
+            let conflicts = conflicted_paths(&dir).await;
+            // Rolls back every commit of the sequence, not just the failed one.
+            let _ = run_git(&dir, &["cherry-pick", "--abort"]).await;
+            if conflicts.is_empty() {
+                Err(anyhow!(
+                    "cherry-pick onto {} failed and was aborted: {}",
+                    branch,
+                    e
+                ))
+            } else {
+                Err(anyhow!(
+                    "conflict in {}; cherry-pick aborted, {} unchanged",
+                    conflicts.join(", "),
+                    branch
+                ))
+            }
+        }
+    };
+    if temporary {
+        let _ = unrecorded(run_git(repo_path, &["worktree", "remove", "--force", &dir])).await;
+    }
+    result
+}
+
+/// Unmerged paths after a failed merge-like operation.
+async fn conflicted_paths(dir: &str) -> Vec<String> {
+    tokio::process::Command::new("git")
+        .args(["diff", "--name-only", "--diff-filter=U"])
+        .current_dir(dir)
+        .output()
+        .await
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Bundle every ref of the repo into `output`. The bundle is written next
+/// to it and verified first, so an interrupted copy never replaces a good one.
+pub async fn bundle_repo(repo_path: &Path, output: &Path) -> Result<String> {
+    // git runs in the repo, so a relative path must not resolve there.
+    let output = &std::path::absolute(output)?;
+    if let Some(dir) = output.parent() {
+        fs::create_dir_all(dir).map_err(|e| anyhow!("cannot create {}: {}", dir.display(), e))?;
+    }
+    let mut partial = output.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let repo = repo_path.to_string_lossy();
+    let written = partial.to_string_lossy();
+    let verified = async {
+        run_git(&repo, &["bundle", "create", &written, "--all"]).await?;
+        run_git(&repo, &["bundle", "verify", "--quiet", &written]).await
+    }
+    .await;
+    if let Err(err) = verified {
+        let _ = fs::remove_file(&partial);
+        return Err(err);
+    }
+    fs::rename(&partial, output)?;
+    let size = fs::metadata(output).map(|m| m.len()).unwrap_or(0);
+    Ok(format!(
+        "bundled and verified {} ({} KiB)",
+        output.display(),
+        size.div_ceil(1024)
+    ))
+}
+
+/// Run gc and report how much the object store shrank.
+async fn gc_repo(repo_path: &Path, aggressive: bool) -> Result<String> {
+    if aggressive {
+        let path = repo_path.to_path_buf();
+        let lost = tokio::task::spawn_blocking(move || find_lost_commits(&path))
+            .await
+            .map_err(|e| anyhow!("could not check for lost commits, not pruning: {}", e))?;
+        if !lost.is_empty() {
+            return Err(anyhow!(
+                "{} lost commit(s) would be pruned; rescue them first, or run a plain gc",
+                lost.len()
+            ));
+        }
+    }
+    let before = git_weight(repo_path);
+    let prune = format!("--prune={}", prune_expiry());
+    let args: &[&str] = if aggressive {
+        &["gc", &prune, "--aggressive"]
+    } else {
+        &["maintenance", "run", "--task=gc"]
+    };
+    run_git(&repo_path.to_string_lossy(), args).await?;
+    let after = git_weight(repo_path);
+    Ok(match (before, after) {
+        (Some(before), Some(after)) => format!(
+            "objects {} → {}",
+            format_kib(before.objects_kib()),
+            format_kib(after.objects_kib())
+        ),
+        _ => "gc finished".to_string(),
+    })
+}
+
+/// Host of the remote a bare `git fetch`/`git push` uses; `None` for local
+/// remotes.
+async fn remote_host(repo_path: &str) -> Option<String> {
+    let url = unrecorded(run_git(repo_path, &["ls-remote", "--get-url"]))
+        .await
+        .ok()?;
+    crate::collectors::auth::parse_remote_url(&url).map(|remote| remote.host)
 }
 
 async fn run_git(repo_path: &str, args: &[&str]) -> Result<String> {
     run_cmd(Some(repo_path), "git", args).await
 }
 
+/// `git add -u`, minus the repo's `[dirty_ignore]` paths.
+async fn stage_tracked(repo_path: &str) -> Result<String> {
+    let pathspecs = crate::git::dirty_pathspecs(Path::new(repo_path));
+    let mut args = vec!["add", "-u"];
+    if !pathspecs.is_empty() {
+        args.push("--");
+        args.extend(pathspecs.iter().map(String::as_str));
+    }
+    run_git(repo_path, &args).await
+}
+
+/// Checks for an action nobody confirmed in the dashboard (`agentpulse run`,
+/// `agentpulse ctl run-action`): destructive actions need
+/// `allow_destructive`, every program an action names must pass the same
+/// `[actions] allowed_programs` check as a custom action, paths it writes
+/// must stay inside its repo, and actions that write elsewhere are left to
+/// the dashboard.
+pub fn check_unconfirmed(
+    action: &ActionKind,
+    allowed_programs: &[String],
+    allow_destructive: bool,
+) -> Result<()> {
+    if action.is_destructive() && !allow_destructive {
+        return Err(anyhow!(
+            "destructive action refused; pass --allow-destructive to run it"
+        ));
+    }
+    match action {
+        ActionKind::ProbeBinaryHelp { binary: program }
+        | ActionKind::OpenInEditor {
+            editor: program, ..
+        } => validate_custom(program, &[], None, allowed_programs).map(drop),
+        ActionKind::PipVenvSync {
+            repo_path,
+            python,
+            venv,
+            ..
+        } => {
+            validate_custom(python, &[], None, allowed_programs)?;
+            match venv {
+                Some(venv) => inside_repo(repo_path, venv).map(drop),
+                None => Ok(()),
+            }
+        }
+        ActionKind::ScaffoldFiles { repo_path, files } => files
+            .iter()
+            .try_for_each(|file| inside_repo(repo_path, &file.path).map(drop)),
+        ActionKind::GitBundleCreate { .. }
+        | ActionKind::CreateMcpConfig { .. }
+        | ActionKind::CreateTask { file: Some(_), .. } => Err(anyhow!(
+            "`{}` writes outside the repo; run it from the dashboard",
+            action.preview()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// `relative` under `repo_path`, refused when it is absolute or climbs out
+/// with `..`.
+fn inside_repo(repo_path: &str, relative: &str) -> Result<PathBuf> {
+    use std::path::Component;
+    let escapes = Path::new(relative)
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+    if relative.is_empty() || escapes {
+        return Err(anyhow!("{} is not a path inside the repo", relative));
+    }
+    Ok(Path::new(repo_path).join(relative))
+}
+
+/// Check a custom action against the allowlist and return the program to
+/// run. Programs are matched literally, so `make` does not allow `/tmp/make`.
+fn validate_custom(
+    program: &str,
+    args: &[String],
+    cwd: Option<&str>,
+    allowed: &[String],
+) -> Result<PathBuf> {
+    if !allowed.iter().any(|p| p == program) {
+        return Err(anyhow!("{} is not in [actions] allowed_programs", program));
+    }
+    let stem = Path::new(program)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    if SHELL_PROGRAMS.contains(&stem.as_str()) {
+        return Err(anyhow!("custom actions cannot run a shell ({})", program));
+    }
+    if args.iter().any(|a| a.contains('\0')) {
+        return Err(anyhow!("custom action arguments cannot contain NUL"));
+    }
+    if let Some(dir) = cwd {
+        if !Path::new(dir).is_absolute() || !Path::new(dir).is_dir() {
+            return Err(anyhow!(
+                "custom action cwd must be an existing absolute directory"
+            ));
+        }
+    }
+    let path = Path::new(program);
+    if path.is_absolute() {
+        return path
+            .is_file()
+            .then(|| path.to_path_buf())
+            .ok_or_else(|| anyhow!("{} not found", program));
+    }
+    if program.contains(['/', '\\']) {
+        return Err(anyhow!(
+            "custom action programs must be a name or an absolute path"
+        ));
+    }
+    resolve_binary_in_path(program).ok_or_else(|| anyhow!("{} not found in PATH", program))
+}
+
 async fn run_cmd(current_dir: Option<&str>, program: &str, args: &[&str]) -> Result<String> {
     let mut cmd = tokio::process::Command::new(program);
     cmd.args(args);
@@ -202,6 +1238,7 @@ async fn run_cmd(current_dir: Option<&str>, program: &str, args: &[&str]) -> Res
         cmd.current_dir(dir);
     }
     let output = cmd.output().await?;
+    record_output(program, args.iter().copied(), &output);
     if output.status.success() {
         Ok(first_line(&output.stdout))
     } else {
@@ -220,12 +1257,12 @@ async fn run_cmd_owned(
     args: Vec<String>,
 ) -> Result<String> {
     let mut cmd = tokio::process::Command::new(program);
-    let owned_args: Vec<OsString> = args.into_iter().map(OsString::from).collect();
-    cmd.args(owned_args);
+    cmd.args(&args);
     if let Some(dir) = current_dir {
         cmd.current_dir(dir);
     }
     let output = cmd.output().await?;
+    record_output(program, args.iter().map(String::as_str), &output);
     if output.status.success() {
         Ok(first_line(&output.stdout))
     } else {
@@ -238,6 +1275,49 @@ async fn run_cmd_owned(
     }
 }
 
+/// Keep a command's outcome for the action in scope, when one is collecting.
+fn record_output<'a>(
+    program: &'a str,
+    args: impl Iterator<Item = &'a str>,
+    output: &std::process::Output,
+) {
+    let _ = CAPTURED.try_with(|captured| {
+        captured.borrow_mut().push(CommandOutput {
+            command: std::iter::once(program.to_string())
+                .chain(args.map(|arg| {
+                    if arg.is_empty() || arg.contains(char::is_whitespace) {
+                        format!("{:?}", arg)
+                    } else {
+                        arg.to_string()
+                    }
+                }))
+                .collect::<Vec<_>>()
+                .join(" "),
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    });
+}
+
+/// The command an action's outcome is about: the first one that failed
+/// when the action did (later ones are usually rollback), else the last.
+fn primary_output(outputs: &[CommandOutput], ok: bool) -> Option<&CommandOutput> {
+    let failed = (!ok)
+        .then(|| outputs.iter().find(|o| o.exit_code != Some(0)))
+        .flatten();
+    failed.or(outputs.last())
+}
+
+/// The last `n` non-empty lines of `text`.
+fn tail(text: &str, n: usize) -> Vec<String> {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(n)..]
+        .iter()
+        .map(|l| l.to_string())
+        .collect()
+}
+
 fn first_line(bytes: &[u8]) -> String {
     String::from_utf8_lossy(bytes)
         .lines()
@@ -263,12 +1343,53 @@ fn append_env_pattern_to_gitignore(repo_path: &str) -> Result<()> {
     Ok(())
 }
 
+/// `stash` if it is a `stash@{n}` selector and, given `oid`, still names
+/// that commit. Nothing else reaches git, so an action request can't smuggle
+/// in an option or another ref.
+async fn stash_ref<'a>(repo_path: &str, stash: &'a str, oid: Option<&str>) -> Result<&'a str> {
+    let index = stash
+        .strip_prefix("stash@{")
+        .and_then(|rest| rest.strip_suffix('}'));
+    if !index.is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())) {
+        return Err(anyhow!("not a stash entry: {}", stash));
+    }
+    if let Some(oid) = oid {
+        let current = unrecorded(run_git(repo_path, &["rev-parse", "--verify", "-q", stash]))
+            .await
+            .unwrap_or_default();
+        if current.trim() != oid {
+            return Err(anyhow!(
+                "{} is no longer the stash you picked; reopen the stash list",
+                stash
+            ));
+        }
+    }
+    Ok(stash)
+}
+
 fn success_hint(action: &ActionKind) -> &'static str {
     match action {
         ActionKind::KillProcess { .. } => "process stopped",
         ActionKind::IgnoreEnvFiles { .. } => "secrets protected; review git status",
+        ActionKind::DraftChangelog { .. } => "review and paste into CHANGELOG.md",
+        ActionKind::GitStashPushPaths { .. } => "selected files stashed; see git stash list",
+        ActionKind::GitStashApply { .. } => "stash applied and kept; drop it when done",
+        ActionKind::GitStashPop { .. } => "stash applied and dropped",
+        ActionKind::GitStashDrop { .. } => "stash dropped; recover it from git fsck while unpruned",
+        ActionKind::GitUndoCommit { .. } => "changes kept staged",
+        ActionKind::CreateRemoteRepo { .. } => "origin added; status will refresh",
+        ActionKind::GitSwitch { .. } => "branch checked out; status will refresh",
+        ActionKind::GitCherryPick { .. } => "commits applied with -x; push the branch when ready",
+        ActionKind::CreateMcpConfig { .. } => "add servers under mcpServers; MCP Health rescans",
+        ActionKind::CreateTask { .. } => "filed for later; the alert stays until it is fixed",
+        ActionKind::GitBundleCreate { .. } => "restore with git clone <bundle>",
+        ActionKind::GitGc { .. } => "repacked; sizes refresh within minutes",
+        ActionKind::GitMaintenanceStart { .. } => {
+            "maintenance scheduled; undo with git maintenance unregister"
+        }
         ActionKind::GitPullRebase { .. }
         | ActionKind::GitPush { .. }
+        | ActionKind::GitPushSetUpstream { .. }
         | ActionKind::GitAddCommit { .. }
         | ActionKind::GitAddCommitPush { .. }
         | ActionKind::GitAddCommitPullRebase { .. }
@@ -280,8 +1401,32 @@ fn success_hint(action: &ActionKind) -> &'static str {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
     use tokio::sync::mpsc;
 
+    /// A fresh repo on `main` with a committer set, under the temp dir.
+    fn init_repo(name: &str) -> PathBuf {
+        let base = std::env::temp_dir()
+            .join("agentpulse_actions_test")
+            .join(name);
+        let _ = fs::remove_dir_all(&base);
+        fs::create_dir_all(&base).unwrap();
+        git(&base, &["init", "-q", "-b", "main"]);
+        git(&base, &["config", "user.email", "test@test.com"]);
+        git(&base, &["config", "user.name", "Test"]);
+        base
+    }
+
+    /// Trimmed stdout of `git args` in `dir`.
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let out = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    }
+
     #[test]
     fn append_env_pattern_idempotent() {
         let base = std::env::temp_dir().join("agentpulse_gitignore_action_test");
@@ -300,11 +1445,75 @@ mod tests {
         let _ = fs::remove_dir_all(&base);
     }
 
+    #[test]
+    fn task_entries_carry_the_notes() {
+        let at = Local.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap();
+        let notes = vec![
+            "/work/api".to_string(),
+            "git -C \"/work/api\" pull --rebase".to_string(),
+        ];
+        assert_eq!(
+            task_entry(TaskBackend::Markdown, "api is behind", &notes, &at),
+            "- [ ] api is behind (2026-10-16)\n  - /work/api\n  - git -C \"/work/api\" pull --rebase\n"
+        );
+        assert!(task_entry(TaskBackend::Org, "api is behind", &[], &at)
+            .starts_with("* TODO api is behind\n  [2026-10-16 Fri 09:30]\n"));
+        assert_eq!(percent_encode("a b/c\n"), "a%20b%2Fc%0A");
+
+        let base = std::env::temp_dir().join("agentpulse_task_action_test");
+        let _ = fs::remove_dir_all(&base);
+        let file = base.join("notes/TODO.md");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "# Later").unwrap();
+        append_task(&file, "- [ ] one\n").unwrap();
+        append_task(&file, "- [ ] two\n").unwrap();
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "# Later\n- [ ] one\n- [ ] two\n"
+        );
+        let _ = fs::remove_dir_all(&base);
+    }
+
     #[test]
     fn resolves_git_in_path() {
         assert!(resolve_binary_in_path("git").is_some());
     }
 
+    #[tokio::test]
+    async fn custom_actions_run_only_allowlisted_programs_without_a_shell() {
+        let custom = |program: &str, cwd: Option<&str>| ActionKind::Custom {
+            program: program.to_string(),
+            args: vec!["--version".to_string()],
+            cwd: cwd.map(str::to_string),
+            risk: Default::default(),
+        };
+        let settings = ActionSettings {
+            allowed_programs: vec!["git".to_string(), "sh".to_string()],
+            ..ActionSettings::default()
+        };
+        let tmp = std::env::temp_dir();
+        let out = execute_action(&custom("git", tmp.to_str()), &settings)
+            .await
+            .unwrap();
+        assert!(out.starts_with("git version"));
+
+        for (program, cwd, expected) in [
+            ("make", None, "not in [actions] allowed_programs"),
+            ("sh", None, "cannot run a shell"),
+            ("git", Some("relative/dir"), "existing absolute directory"),
+        ] {
+            let err = execute_action(&custom(program, cwd), &settings)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains(expected), "{}", err);
+        }
+        assert!(
+            execute_action(&custom("git", None), &ActionSettings::default())
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn run_action_emits_completion_for_non_repo_action() {
         let (notif_tx, mut notif_rx) = mpsc::channel(1);
@@ -314,6 +1523,7 @@ mod tests {
             ActionKind::ShowMessage {
                 message: "hello".to_string(),
             },
+            ActionSettings::default(),
             notif_tx,
             done_tx,
         );
@@ -335,6 +1545,7 @@ mod tests {
             ActionKind::GitStatus {
                 repo_path: repo_path.to_string(),
             },
+            ActionSettings::default(),
             notif_tx,
             done_tx,
         );
@@ -343,4 +1554,478 @@ mod tests {
         let done = done_rx.recv().await.expect("completion expected");
         assert_eq!(done.affected_repo_path.as_deref(), Some(repo_path));
     }
+
+    #[tokio::test]
+    async fn partial_stash_leaves_unselected_files() {
+        let base = init_repo("partial_stash");
+        let git = |args: &[&str]| git(&base, args);
+        fs::write(base.join("keep.txt"), "v1").unwrap();
+        fs::write(base.join("stash.txt"), "v1").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        fs::write(base.join("keep.txt"), "v2").unwrap();
+        fs::write(base.join("stash.txt"), "v2").unwrap();
+        fs::write(base.join("new.txt"), "untracked").unwrap();
+
+        let action = ActionKind::GitStashPushPaths {
+            repo_path: base.to_string_lossy().to_string(),
+            message: "wip".to_string(),
+            paths: vec!["stash.txt".to_string(), "new.txt".to_string()],
+        };
+        execute_action(&action, &ActionSettings::default())
+            .await
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(base.join("keep.txt")).unwrap(), "v2");
+        assert_eq!(fs::read_to_string(base.join("stash.txt")).unwrap(), "v1");
+        assert!(!base.join("new.txt").exists());
+        assert!(git(&["stash", "list"]).contains("wip"));
+
+        // A newer stash shifts the picked one to stash@{1}; its oid notices.
+        let picked = crate::git::list_stashes(&base).unwrap().remove(0);
+        git(&["stash", "push", "-m", "later"]);
+        let drop = |stash: &str| ActionKind::GitStashDrop {
+            repo_path: base.to_string_lossy().to_string(),
+            stash: stash.to_string(),
+            oid: Some(picked.oid.clone()),
+        };
+        let err = execute_action(&drop("stash@{0}"), &ActionSettings::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no longer the stash you picked"));
+        execute_action(&drop("stash@{1}"), &ActionSettings::default())
+            .await
+            .unwrap();
+        let left = crate::git::list_stashes(&base).unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].message, "later");
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[tokio::test]
+    async fn commit_action_is_recorded_and_undoable() {
+        let base = init_repo("undo_commit");
+        let git = |args: &[&str]| git(&base, args);
+        fs::write(base.join("a.txt"), "a").unwrap();
+        fs::write(base.join("b.txt"), "").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        let init = git(&["rev-parse", "HEAD"]);
+        fs::write(base.join("b.txt"), "one\ntwo\n").unwrap();
+        fs::write(base.join("scratch.txt"), "notes").unwrap();
+
+        let repo_path = base.to_string_lossy().to_string();
+        let (notif_tx, mut notif_rx) = mpsc::channel(1);
+        let (done_tx, mut done_rx) = mpsc::channel(1);
+        run_action(
+            ActionKind::GitAddCommit {
+                repo_path: repo_path.clone(),
+                message: "add b".to_string(),
+            },
+            ActionSettings::default(),
+            notif_tx,
+            done_tx,
+        );
+        let notif = notif_rx.recv().await.unwrap();
+        let commit = done_rx
+            .recv()
+            .await
+            .unwrap()
+            .commit
+            .expect("commit recorded");
+        assert_eq!(commit.subject, "add b");
+        assert_eq!(commit.shortstat, "1 file changed, 2 insertions(+)");
+        assert!(notif.contains(commit.short_sha()));
+        // Only tracked changes go into the commit.
+        assert_eq!(git(&["status", "--porcelain"]), "?? scratch.txt");
+
+        let undo = ActionKind::GitUndoCommit {
+            repo_path,
+            commit: commit.sha.clone(),
+        };
+        execute_action(&undo, &ActionSettings::default())
+            .await
+            .unwrap();
+        assert_eq!(git(&["rev-parse", "HEAD"]), init);
+        assert_eq!(git(&["diff", "--cached", "--name-only"]), "b.txt");
+
+        // The recorded commit is gone from HEAD, so a second undo must refuse.
+        assert!(execute_action(&undo, &ActionSettings::default())
+            .await
+            .is_err());
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[tokio::test]
+    async fn run_for_result_reports_output_and_new_status() {
+        let base = init_repo("action_result");
+        let git = |args: &[&str]| git(&base, args);
+        fs::write(base.join("a.txt"), "a").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        fs::write(base.join("a.txt"), "a2").unwrap();
+        let repo_path = base.to_string_lossy().to_string();
+        let log = std::env::temp_dir().join("agentpulse_action_result_test.log");
+        let _ = fs::remove_file(&log);
+        let settings = ActionSettings {
+            audit_log: Some(log.clone()),
+            protected_branches: vec!["release/*".to_string()],
+            ..ActionSettings::default()
+        };
+
+        let result = run_for_result(
+            &ActionKind::GitAddCommit {
+                repo_path: repo_path.clone(),
+                message: "update a".to_string(),
+            },
+            &settings,
+        )
+        .await;
+        assert!(result.ok, "{:?}", result);
+        assert_eq!(result.exit_code, Some(0));
+        assert_eq!(
+            result.commands.last().unwrap(),
+            r#"git commit -m "update a""#
+        );
+        // The protected-branch lookup is not part of the action.
+        assert!(!result.commands.iter().any(|c| c.contains("rev-parse")));
+        assert!(result.stdout_tail.iter().any(|l| l.contains("update a")));
+        assert_eq!(result.commit.unwrap().subject, "update a");
+        assert_eq!(result.repo_status.unwrap().uncommitted_count, 0);
+
+        let result = run_for_result(
+            &ActionKind::GitSwitch {
+                repo_path: repo_path.clone(),
+                branch: "no-such-branch".to_string(),
+                autostash: false,
+            },
+            &settings,
+        )
+        .await;
+        assert!(!result.ok);
+        assert_ne!(result.exit_code, Some(0));
+        assert!(!result.stderr_tail.is_empty());
+        assert!(result.commit.is_none());
+
+        let drop = ActionKind::GitStashDrop {
+            repo_path: repo_path.clone(),
+            stash: "stash@{0}".to_string(),
+            oid: None,
+        };
+        let result = run_for_result(&drop, &settings).await;
+        assert!(!result.ok);
+        assert!(result.message.contains("--allow-destructive"));
+        assert!(result.commands.is_empty());
+
+        let logged = audit::load_recent(&log, 10);
+        assert_eq!(logged.len(), 3);
+        assert_eq!(logged[2].action, "git_add_commit");
+        assert!(logged[2].ok);
+        assert_eq!(logged[1].action, "git_switch");
+        assert!(!logged[1].ok);
+        assert_ne!(logged[1].exit_code, Some(0));
+        assert!(!logged[1].refused);
+        assert_eq!(logged[0].action, "git_stash_drop");
+        assert!(logged[0].refused);
+        let _ = fs::remove_file(&log);
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[tokio::test]
+    async fn unconfirmed_actions_cannot_escape_the_allowlist_or_the_repo() {
+        let probe: ActionKind =
+            serde_json::from_str(r#"{"type":"probe_binary_help","binary":"/tmp/x"}"#).unwrap();
+        let result = run_for_result(&probe, &ActionSettings::default()).await;
+        assert!(!result.ok);
+        assert!(
+            result.message.contains("allowed_programs"),
+            "{}",
+            result.message
+        );
+        assert!(result.commands.is_empty());
+
+        let allowed = vec!["python3".to_string()];
+        let sync = |venv: &str| ActionKind::PipVenvSync {
+            repo_path: "/src/app".to_string(),
+            python: "python3".to_string(),
+            recreate: false,
+            venv: Some(venv.to_string()),
+        };
+        assert!(check_unconfirmed(&sync(".venv"), &allowed, false).is_ok());
+        assert!(check_unconfirmed(&sync("../elsewhere"), &allowed, false).is_err());
+        assert!(check_unconfirmed(&sync("/tmp/venv"), &allowed, false).is_err());
+        assert!(check_unconfirmed(&sync(".venv"), &[], false).is_err());
+        let editor = ActionKind::OpenInEditor {
+            path: "/src/app".to_string(),
+            editor: "sh".to_string(),
+        };
+        assert!(check_unconfirmed(&editor, &[], false).is_err());
+        let mcp = ActionKind::CreateMcpConfig {
+            path: "/tmp/.mcp.json".to_string(),
+        };
+        assert!(check_unconfirmed(&mcp, &[], true).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn scaffold_files_stay_inside_the_repo() {
+        use crate::dashboard::ScaffoldFile;
+
+        let base = init_repo("scaffold");
+        let outside = std::env::temp_dir().join("agentpulse_scaffold_outside");
+        let _ = fs::remove_dir_all(&outside);
+        fs::create_dir_all(&outside).unwrap();
+        let template = outside.join("ci.yml");
+        fs::write(&template, "on: push").unwrap();
+        std::os::unix::fs::symlink(&outside, base.join("linked")).unwrap();
+        let scaffold = |path: &str| ActionKind::ScaffoldFiles {
+            repo_path: base.to_string_lossy().to_string(),
+            files: vec![ScaffoldFile {
+                path: path.to_string(),
+                template: template.to_string_lossy().to_string(),
+            }],
+        };
+
+        let settings = ActionSettings::default();
+        for escape in [
+            "../agentpulse_scaffold_outside/x.yml",
+            "/tmp/agentpulse_scaffold_x.yml",
+            "linked/x.yml",
+            "linked/deeper/x.yml",
+        ] {
+            assert!(
+                execute_action(&scaffold(escape), &settings).await.is_err(),
+                "{}",
+                escape
+            );
+        }
+        assert_eq!(fs::read_dir(&outside).unwrap().count(), 1);
+
+        execute_action(&scaffold(".github/workflows/ci.yml"), &settings)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(base.join(".github/workflows/ci.yml")).unwrap(),
+            "on: push"
+        );
+        let _ = fs::remove_dir_all(&outside);
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[tokio::test]
+    async fn commit_on_protected_branch_is_refused_until_confirmed() {
+        let base = init_repo("protected_branch");
+        let git = |args: &[&str]| git(&base, args);
+        fs::write(base.join("a.txt"), "a").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        git(&["switch", "-c", "release/1.0"]);
+        fs::write(base.join("a.txt"), "a2").unwrap();
+
+        let commit = ActionKind::GitAddCommit {
+            repo_path: base.to_string_lossy().to_string(),
+            message: "fix".to_string(),
+        };
+        let mut settings = ActionSettings {
+            protected_branches: vec!["release/*".to_string()],
+            ..ActionSettings::default()
+        };
+        let err = preflight(&commit, &base.to_string_lossy(), &settings)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("`release/1.0` is protected"));
+        let fetch = ActionKind::GitFetch {
+            repo_path: base.to_string_lossy().to_string(),
+        };
+        assert!(preflight(&fetch, &base.to_string_lossy(), &settings)
+            .await
+            .is_ok());
+        // A confirmation counts only for the branch it was given for.
+        settings.allow_protected = Some("release/2.0".to_string());
+        assert!(preflight(&commit, &base.to_string_lossy(), &settings)
+            .await
+            .is_err());
+        settings.allow_protected = Some("release/1.0".to_string());
+        assert!(preflight(&commit, &base.to_string_lossy(), &settings)
+            .await
+            .is_ok());
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[tokio::test]
+    async fn risky_action_refuses_when_repo_moved_since_scan() {
+        let base = init_repo("verify_state");
+        let git = |args: &[&str]| git(&base, args);
+        fs::write(base.join("a.txt"), "a").unwrap();
+        fs::write(base.join("b.txt"), "b").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        let init = git(&["rev-parse", "HEAD"]);
+        fs::write(base.join("a.txt"), "a2").unwrap();
+        let scanned = check_repo_status(&base).await.unwrap();
+        fs::write(base.join("b.txt"), "b2").unwrap();
+
+        let repo_path = base.to_string_lossy().to_string();
+        let settings = ActionSettings {
+            expected: Some(ExpectedState::from(&scanned)),
+            ..ActionSettings::default()
+        };
+        let (notif_tx, mut notif_rx) = mpsc::channel(1);
+        let (done_tx, mut done_rx) = mpsc::channel(1);
+        run_action(
+            ActionKind::GitAddCommit {
+                repo_path: repo_path.clone(),
+                message: "wip".to_string(),
+            },
+            settings,
+            notif_tx,
+            done_tx,
+        );
+        let notif = notif_rx.recv().await.unwrap();
+        assert_eq!(notif, "✗  action — state changed, rescan (dirty 1 → 2)");
+        let done = done_rx.recv().await.unwrap();
+        assert_eq!(done.affected_repo_path.as_deref(), Some(repo_path.as_str()));
+        assert_eq!(git(&["rev-parse", "HEAD"]), init);
+
+        // Equal counts on another commit are a change too.
+        git(&["commit", "-q", "--amend", "-m", "amended"]);
+        let mut scanned_again = check_repo_status(&base).await.unwrap();
+        scanned_again.head_sha = Some(init.clone());
+        let err = verify_state(&repo_path, &ExpectedState::from(&scanned_again))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("state changed, rescan (HEAD "));
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[tokio::test]
+    async fn switch_with_autostash_carries_local_changes() {
+        let base = init_repo("switch_autostash");
+        let git = |args: &[&str]| git(&base, args);
+        fs::write(base.join("a.txt"), "a").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        git(&["branch", "release"]);
+        fs::write(base.join("a.txt"), "edited").unwrap();
+
+        let switch = ActionKind::GitSwitch {
+            repo_path: base.to_string_lossy().to_string(),
+            branch: "release".to_string(),
+            autostash: true,
+        };
+        execute_action(&switch, &ActionSettings::default())
+            .await
+            .unwrap();
+        assert_eq!(git(&["branch", "--show-current"]), "release");
+        assert_eq!(fs::read_to_string(base.join("a.txt")).unwrap(), "edited");
+        assert_eq!(git(&["stash", "list"]), "");
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[tokio::test]
+    async fn cherry_pick_applies_onto_another_branch_and_aborts_on_conflict() {
+        let base = init_repo("cherry_pick");
+        let git = |args: &[&str]| git(&base, args);
+        fs::write(base.join("a.txt"), "a\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        git(&["branch", "release"]);
+        fs::write(base.join("fix.txt"), "fix\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "fix"]);
+        let fix = git(&["rev-parse", "HEAD"]);
+        fs::write(base.join("a.txt"), "main\n").unwrap();
+        git(&["commit", "-am", "edit a on main"]);
+        let clash = git(&["rev-parse", "HEAD"]);
+
+        let repo_path = base.to_string_lossy().to_string();
+        let pick = ActionKind::GitCherryPick {
+            repo_path: repo_path.clone(),
+            branch: "release".to_string(),
+            commits: vec![fix],
+        };
+        execute_action(&pick, &ActionSettings::default())
+            .await
+            .unwrap();
+        assert!(git(&["log", "-1", "--format=%B", "release"]).contains("cherry picked from commit"));
+        // The current checkout is untouched and the temporary worktree is gone.
+        assert_eq!(git(&["branch", "--show-current"]), "main");
+        assert_eq!(git(&["worktree", "list"]).lines().count(), 1);
+
+        git(&["switch", "release"]);
+        fs::write(base.join("a.txt"), "release\n").unwrap();
+        git(&["commit", "-am", "edit a on release"]);
+        let release_head = git(&["rev-parse", "HEAD"]);
+        let conflicting = ActionKind::GitCherryPick {
+            repo_path,
+            branch: "release".to_string(),
+            commits: vec![clash],
+        };
+        let err = execute_action(&conflicting, &ActionSettings::default())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("conflict in a.txt"), "{}", err);
+        assert_eq!(git(&["rev-parse", "HEAD"]), release_head);
+        assert_eq!(git(&["status", "--porcelain"]), "");
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[tokio::test]
+    async fn bundle_action_writes_a_verified_bundle() {
+        // Bundles and the restored clone land next to the repo.
+        let base = std::env::temp_dir()
+            .join("agentpulse_actions_test")
+            .join("bundle");
+        let _ = fs::remove_dir_all(&base);
+        let work = init_repo("bundle/work");
+        git(&work, &["commit", "--allow-empty", "-m", "only here"]);
+        git(&work, &["branch", "idea"]);
+
+        let output = base.join("drive/work.bundle");
+        let kind = ActionKind::GitBundleCreate {
+            repo_path: work.to_string_lossy().to_string(),
+            output: output.to_string_lossy().to_string(),
+        };
+        let message = execute_action(&kind, &ActionSettings::default())
+            .await
+            .unwrap();
+        assert!(message.starts_with("bundled and verified"));
+        assert!(!base.join("drive/work.bundle.partial").exists());
+
+        git(&base, &["clone", "-q", "drive/work.bundle", "restored"]);
+        let heads = git(&base.join("restored"), &["branch", "-r"]);
+        assert!(heads.contains("origin/main") && heads.contains("origin/idea"));
+
+        // A repo with nothing to bundle fails and leaves no partial file.
+        let empty = base.join("empty");
+        fs::create_dir_all(&empty).unwrap();
+        git(&empty, &["init"]);
+        assert!(bundle_repo(&empty, &base.join("drive/empty.bundle"))
+            .await
+            .is_err());
+        assert!(!base.join("drive/empty.bundle.partial").exists());
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[tokio::test]
+    async fn aggressive_gc_is_refused_while_lost_work_remains() {
+        let base = init_repo("gc_lost_work");
+        let git = |args: &[&str]| git(&base, args);
+        git(&["commit", "-q", "--allow-empty", "-m", "init"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "work in progress"]);
+        git(&["reset", "-q", "--hard", "HEAD~1"]);
+        let gc = ActionKind::GitGc {
+            repo_path: base.to_string_lossy().to_string(),
+            aggressive: true,
+        };
+        assert!(gc.is_destructive());
+        assert_eq!(gc.risk_level(), "high");
+
+        let err = execute_action(&gc, &ActionSettings::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("lost commit"), "{}", err);
+        let _ = fs::remove_dir_all(&base);
+    }
 }
@@ -1,5 +1,10 @@
-use crate::dashboard::ActionKind;
-use crate::git::Repo;
+use crate::config::{
+    branch_matches, Config, PriorityRule, RuleCounter, RulePriority, RulesConfig, ThresholdsConfig,
+};
+use crate::dashboard::{ActionKind, RemoteForge};
+use crate::git::{dirty_pathspec_suffix, Repo, RepoStatus};
+use std::collections::BTreeSet;
+use std::sync::{Mutex, OnceLock};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ActionPriority {
@@ -39,6 +44,244 @@ pub struct Recommendation {
     pub action: &'static str,
     pub command: String,
     pub reason: String,
+    /// Rough minutes of attention the recommendation needs (0 for idle repos).
+    pub effort_minutes: u32,
+    /// Whether `[thresholds]` and the age of local work may move the
+    /// priority; off for idle repos, detached HEADs and untracked files.
+    pub escalates: bool,
+}
+
+/// A repo counter measured against `[thresholds]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counter {
+    Behind,
+    Dirty,
+    Unpushed,
+    Stash,
+}
+
+/// Where a counter sits relative to its thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Zero, or below the warn threshold.
+    Quiet,
+    Warn,
+    Critical,
+}
+
+static THRESHOLDS: OnceLock<Mutex<ThresholdsConfig>> = OnceLock::new();
+
+fn thresholds() -> ThresholdsConfig {
+    THRESHOLDS
+        .get_or_init(|| Mutex::new(ThresholdsConfig::default()))
+        .lock()
+        .map(|cfg| *cfg)
+        .unwrap_or_default()
+}
+
+/// Apply `[thresholds]` to later recommendations and status colors.
+pub fn configure_thresholds(cfg: &ThresholdsConfig) {
+    if let Ok(mut current) = THRESHOLDS
+        .get_or_init(|| Mutex::new(ThresholdsConfig::default()))
+        .lock()
+    {
+        *current = *cfg;
+    }
+}
+
+/// `[rules]` as [`recommend`] consults them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleSet {
+    commit_message: String,
+    /// [`Config::protected_branches`]: local work there gets a new branch.
+    no_commit_branches: Vec<String>,
+    priority: Vec<PriorityRule>,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::from(&RulesConfig::default())
+    }
+}
+
+impl From<&RulesConfig> for RuleSet {
+    fn from(cfg: &RulesConfig) -> Self {
+        Self {
+            commit_message: cfg.commit_message.clone(),
+            no_commit_branches: cfg.no_commit_branches.clone(),
+            priority: cfg.priority.clone(),
+        }
+    }
+}
+
+impl From<&Config> for RuleSet {
+    fn from(cfg: &Config) -> Self {
+        Self {
+            no_commit_branches: cfg.protected_branches(),
+            ..Self::from(&cfg.rules)
+        }
+    }
+}
+
+impl RuleSet {
+    /// The commit message template with `{branch}`, `{repo}` and `{files}`
+    /// filled in; "wip" if that leaves nothing.
+    pub fn commit_message(&self, repo: &Repo, files: usize) -> String {
+        let message = self
+            .commit_message
+            .replace("{branch}", &repo.status.branch)
+            .replace("{repo}", &repo.name)
+            .replace("{files}", &files.to_string());
+        if message.trim().is_empty() {
+            "wip".to_string()
+        } else {
+            message
+        }
+    }
+
+    pub fn blocks_commit(&self, branch: &str) -> bool {
+        branch_matches(&self.no_commit_branches, branch)
+    }
+
+    /// Branch that local work on a no-commit branch is moved to.
+    pub fn work_branch(&self, branch: &str) -> String {
+        format!("wip/{}", branch)
+    }
+
+    /// The first priority rule matching `status`, with a reason fragment.
+    fn priority_for(
+        &self,
+        limits: &ThresholdsConfig,
+        status: &RepoStatus,
+    ) -> Option<(ActionPriority, String)> {
+        self.priority.iter().find_map(|rule| {
+            let (name, count) = match rule.counter {
+                RuleCounter::Behind => ("behind", status.behind_count),
+                RuleCounter::Dirty => ("dirty", dirty_count(limits, status)),
+                RuleCounter::Unpushed => ("unpushed", status.unpushed_count),
+                RuleCounter::Stash => ("stash", status.stash_count),
+            };
+            let on_branch =
+                rule.branches.is_empty() || branch_matches(&rule.branches, &status.branch);
+            if !on_branch || count == 0 || count < rule.at_least {
+                return None;
+            }
+            let priority = match rule.priority {
+                RulePriority::Critical => ActionPriority::Critical,
+                RulePriority::High => ActionPriority::High,
+                RulePriority::Medium => ActionPriority::Medium,
+                RulePriority::Low => ActionPriority::Low,
+            };
+            Some((
+                priority,
+                format!("Rule: {} ≥ {} → {}.", name, rule.at_least, priority.label()),
+            ))
+        })
+    }
+}
+
+static RULES: OnceLock<Mutex<RuleSet>> = OnceLock::new();
+
+fn rules() -> RuleSet {
+    RULES
+        .get_or_init(|| Mutex::new(RuleSet::default()))
+        .lock()
+        .map(|rules| rules.clone())
+        .unwrap_or_default()
+}
+
+/// Apply `[rules]` and the protected branches to later recommendations.
+pub fn configure_rules(cfg: &Config) {
+    if let Ok(mut current) = RULES.get_or_init(|| Mutex::new(RuleSet::default())).lock() {
+        *current = RuleSet::from(cfg);
+    }
+}
+
+static VPN_OFFLINE: OnceLock<Mutex<BTreeSet<String>>> = OnceLock::new();
+
+fn vpn_offline() -> BTreeSet<String> {
+    VPN_OFFLINE
+        .get_or_init(|| Mutex::new(BTreeSet::new()))
+        .lock()
+        .map(|repos| repos.clone())
+        .unwrap_or_default()
+}
+
+/// Repos, by path, whose remote sits behind a VPN host that is down. Later
+/// recommendations hold back their network actions until it is back.
+pub fn configure_vpn_offline<'a>(repos: impl IntoIterator<Item = &'a str>) {
+    if let Ok(mut current) = VPN_OFFLINE
+        .get_or_init(|| Mutex::new(BTreeSet::new()))
+        .lock()
+    {
+        *current = repos.into_iter().map(str::to_string).collect();
+    }
+}
+
+pub fn counter_level(counter: Counter, count: usize) -> Level {
+    level(&thresholds(), counter, count)
+}
+
+fn level(limits: &ThresholdsConfig, counter: Counter, count: usize) -> Level {
+    let (warn, critical) = match counter {
+        Counter::Behind => (limits.behind_warn, limits.behind_critical),
+        Counter::Dirty => (limits.dirty_file_warn, limits.dirty_file_critical),
+        Counter::Unpushed => (limits.unpushed_warn, limits.unpushed_critical),
+        Counter::Stash => (limits.stash_warn, 0),
+    };
+    if count == 0 {
+        Level::Quiet
+    } else if critical > 0 && count >= critical {
+        Level::Critical
+    } else if count >= warn {
+        Level::Warn
+    } else {
+        Level::Quiet
+    }
+}
+
+/// The highest level among a repo's behind, dirty and unpushed counters.
+pub fn repo_level(status: &RepoStatus) -> Level {
+    repo_level_with(&thresholds(), status)
+}
+
+fn repo_level_with(limits: &ThresholdsConfig, status: &RepoStatus) -> Level {
+    [
+        (Counter::Behind, status.behind_count),
+        (Counter::Dirty, dirty_count(limits, status)),
+        (Counter::Unpushed, status.unpushed_count),
+    ]
+    .into_iter()
+    .map(|(counter, count)| level(limits, counter, count))
+    .max()
+    .unwrap_or(Level::Quiet)
+}
+
+/// Changed tracked files. A status without a breakdown (from history or an
+/// older daemon) counts everything as tracked.
+fn tracked_changes(status: &RepoStatus) -> usize {
+    status
+        .uncommitted_count
+        .saturating_sub(status.changes.untracked)
+}
+
+/// Uncommitted files that count as local work under `untracked_actionable`.
+fn dirty_count(limits: &ThresholdsConfig, status: &RepoStatus) -> usize {
+    if limits.untracked_actionable {
+        status.uncommitted_count
+    } else {
+        tracked_changes(status)
+    }
+}
+
+/// Untracked files worth a review once nothing else needs doing.
+fn untracked_flagged(limits: &ThresholdsConfig, status: &RepoStatus) -> bool {
+    limits.untracked_actionable && status.changes.untracked > 0
+}
+
+/// Stashes worth a review under the current `stash_warn`.
+fn stash_flagged(limits: &ThresholdsConfig, status: &RepoStatus) -> bool {
+    level(limits, Counter::Stash, status.stash_count) != Level::Quiet
 }
 
 pub fn needs_attention(repo: &Repo) -> bool {
@@ -46,8 +289,111 @@ pub fn needs_attention(repo: &Repo) -> bool {
 }
 
 pub fn recommend(repo: &Repo) -> Recommendation {
+    let rec = recommend_with(
+        &thresholds(),
+        &rules(),
+        repo,
+        chrono::Utc::now().timestamp(),
+    );
+    hold_for_vpn(rec, repo, &vpn_offline())
+}
+
+/// A network recommendation for a repo in `offline` becomes "connect vpn",
+/// keeping its priority and command for once the host is back.
+fn hold_for_vpn(
+    mut rec: Recommendation,
+    repo: &Repo,
+    offline: &BTreeSet<String>,
+) -> Recommendation {
+    if offline.contains(repo.path.to_string_lossy().as_ref())
+        && action_kind(repo).is_some_and(|kind| kind.needs_network())
+    {
+        rec.short_action = "connect vpn";
+        rec.action = "connect to the VPN, then sync";
+        rec.reason = format!(
+            "{} Its remote is behind a VPN host that is down.",
+            rec.reason
+        );
+    }
+    rec
+}
+
+/// [`threshold_recommendation`], then the first matching `[rules]` priority
+/// override. Repos with nothing to do stay idle.
+fn recommend_with(
+    limits: &ThresholdsConfig,
+    rules: &RuleSet,
+    repo: &Repo,
+    now: i64,
+) -> Recommendation {
+    let mut rec = threshold_recommendation(limits, rules, repo, now);
+    if rec.priority == ActionPriority::Idle || repo.status.is_detached {
+        return rec;
+    }
+    if let Some((priority, reason)) = rules.priority_for(limits, &repo.status) {
+        rec.priority = priority;
+        rec.reason = format!("{} {}", rec.reason, reason);
+    }
+    rec
+}
+
+/// The fixed per-state recommendation, then moved by `[thresholds]`: any
+/// counter at its critical limit makes it critical, counters all below
+/// their warn limits hold it at low priority, and local work older than
+/// the age limits raises it again. Untracked files alone stay at low
+/// priority, however many or old.
+fn threshold_recommendation(
+    limits: &ThresholdsConfig,
+    rules: &RuleSet,
+    repo: &Repo,
+    now: i64,
+) -> Recommendation {
+    let mut rec = base_recommendation(limits, rules, repo);
+    let status = &repo.status;
+    if !rec.escalates {
+        return rec;
+    }
+    let counted = status.behind_count + dirty_count(limits, status) + status.unpushed_count > 0;
+    match repo_level_with(limits, status) {
+        Level::Critical => rec.priority = ActionPriority::Critical,
+        Level::Quiet if counted && rec.priority < ActionPriority::Low => {
+            rec.priority = ActionPriority::Low
+        }
+        _ => {}
+    }
+    if let Some(age) = status.work_since().map(|since| now.saturating_sub(since)) {
+        let past = |hours: u64| hours > 0 && age >= hours as i64 * 3600;
+        let escalated = if past(limits.work_age_critical_hours) {
+            ActionPriority::Critical
+        } else if past(limits.work_age_high_hours) {
+            ActionPriority::High
+        } else {
+            rec.priority
+        };
+        if escalated < rec.priority {
+            rec.priority = escalated;
+            rec.reason = format!("{} Local work is {} old.", rec.reason, describe_age(age));
+        }
+    }
+    rec
+}
+
+/// `30h`, or whole days past two.
+fn describe_age(secs: i64) -> String {
+    let hours = secs / 3600;
+    if hours < 48 {
+        format!("{}h", hours)
+    } else {
+        format!("{} days", hours / 24)
+    }
+}
+
+fn base_recommendation(limits: &ThresholdsConfig, rules: &RuleSet, repo: &Repo) -> Recommendation {
     let path = repo.path.to_string_lossy();
-    let cmd = |s: &str| format!("cd {:?} && {}", path, s);
+    let cmd = |s: &str| format!("cd {} && {}", shell_quote(&path), s);
+    let tracked = tracked_changes(&repo.status);
+    let stage = format!("git add -u{}", dirty_pathspec_suffix(&repo.path));
+    let message = rules.commit_message(repo, tracked);
 
     if repo.status.is_detached {
         return Recommendation {
@@ -56,19 +402,53 @@ pub fn recommend(repo: &Repo) -> Recommendation {
             action: "reattach HEAD to a branch",
             command: cmd("git switch -c rescue-work"),
             reason: "Repository is in detached HEAD state.".to_string(),
+            effort_minutes: 10,
+            escalates: false,
+        };
+    }
+
+    if tracked > 0 && rules.blocks_commit(&repo.status.branch) {
+        let priority = if repo.status.behind_count > 0 {
+            ActionPriority::Critical
+        } else if repo.status.unpushed_count > 0 && !repo.status.missing_upstream {
+            ActionPriority::High
+        } else {
+            ActionPriority::Medium
+        };
+        return Recommendation {
+            priority,
+            short_action: "branch off",
+            action: "move local work to a new branch",
+            command: cmd(&format!(
+                "git switch -c {}",
+                shell_quote(&rules.work_branch(&repo.status.branch))
+            )),
+            reason: format!(
+                "{} uncommitted file(s) on {}, which [rules] keeps free of direct commits.",
+                tracked, repo.status.branch
+            ),
+            effort_minutes: scaled_effort(3, tracked, 5),
+            escalates: true,
         };
     }
 
-    if repo.status.behind_count > 0 && repo.status.uncommitted_count > 0 {
+    if repo.status.behind_count > 0 && tracked > 0 {
         return Recommendation {
             priority: ActionPriority::Critical,
             short_action: "commit+rebase",
             action: "commit/stash local work, then pull --rebase",
-            command: cmd("git add -A && git commit -m \"wip\" && git pull --rebase"),
+            command: cmd(&format!(
+                "{} && git commit -m {} && git pull --rebase",
+                stage,
+                shell_quote(&message)
+            )),
             reason: format!(
                 "{} local changes + {} commits behind remote.",
-                repo.status.uncommitted_count, repo.status.behind_count
+                tracked, repo.status.behind_count
             ),
+            effort_minutes: scaled_effort(5, tracked, 5)
+                + scaled_effort(0, repo.status.behind_count, 2),
+            escalates: true,
         };
     }
 
@@ -82,6 +462,8 @@ pub fn recommend(repo: &Repo) -> Recommendation {
                 "{} ahead and {} behind remote (diverged).",
                 repo.status.unpushed_count, repo.status.behind_count
             ),
+            effort_minutes: scaled_effort(10, repo.status.behind_count, 2),
+            escalates: true,
         };
     }
 
@@ -92,29 +474,62 @@ pub fn recommend(repo: &Repo) -> Recommendation {
             action: "pull latest changes",
             command: cmd("git pull --rebase"),
             reason: format!("{} commits behind remote.", repo.status.behind_count),
+            effort_minutes: scaled_effort(2, repo.status.behind_count, 10),
+            escalates: true,
         };
     }
 
-    if repo.status.uncommitted_count > 0 && repo.status.unpushed_count > 0 {
+    if tracked > 0 && repo.status.unpushed_count > 0 && !repo.status.missing_upstream {
         return Recommendation {
             priority: ActionPriority::High,
             short_action: "commit+push",
             action: "commit local work and push",
-            command: cmd("git add -A && git commit -m \"wip\" && git push"),
+            command: cmd(&format!(
+                "{} && git commit -m {} && git push",
+                stage,
+                shell_quote(&message)
+            )),
             reason: format!(
                 "{} local changes + {} commits ahead.",
-                repo.status.uncommitted_count, repo.status.unpushed_count
+                tracked, repo.status.unpushed_count
             ),
+            effort_minutes: scaled_effort(5, tracked, 5),
+            escalates: true,
         };
     }
 
-    if repo.status.uncommitted_count > 0 {
+    if tracked > 0 {
         return Recommendation {
             priority: ActionPriority::Medium,
             short_action: "commit",
             action: "commit local work",
-            command: cmd("git add -A && git commit -m \"wip\""),
-            reason: format!("{} uncommitted file(s).", repo.status.uncommitted_count),
+            command: cmd(&format!(
+                "{} && git commit -m {}",
+                stage,
+                shell_quote(&message)
+            )),
+            reason: format!("{} uncommitted file(s).", tracked),
+            effort_minutes: scaled_effort(3, tracked, 5),
+            escalates: true,
+        };
+    }
+
+    if repo.status.missing_upstream {
+        return Recommendation {
+            priority: ActionPriority::Medium,
+            short_action: "publish",
+            action: "push branch and set upstream",
+            command: cmd(&format!(
+                "git push -u {} {}",
+                shell_quote(repo.status.publish_remote()),
+                shell_quote(&repo.status.branch)
+            )),
+            reason: format!(
+                "Branch {} has no upstream ({} commit(s) on no remote).",
+                repo.status.branch, repo.status.unpushed_count
+            ),
+            effort_minutes: 1,
+            escalates: true,
         };
     }
 
@@ -125,16 +540,35 @@ pub fn recommend(repo: &Repo) -> Recommendation {
             action: "push local commits",
             command: cmd("git push"),
             reason: format!("{} commit(s) ahead of remote.", repo.status.unpushed_count),
+            effort_minutes: 1,
+            escalates: true,
+        };
+    }
+
+    if untracked_flagged(limits, &repo.status) {
+        return Recommendation {
+            priority: ActionPriority::Low,
+            short_action: "add or ignore",
+            action: "review untracked files, then add or ignore them",
+            command: cmd("git status --short --untracked-files=all"),
+            reason: format!(
+                "{} untracked file(s), no tracked changes.",
+                repo.status.changes.untracked
+            ),
+            effort_minutes: scaled_effort(2, repo.status.changes.untracked, 10),
+            escalates: false,
         };
     }
 
-    if repo.status.stash_count > 0 {
+    if stash_flagged(limits, &repo.status) {
         return Recommendation {
             priority: ActionPriority::Low,
             short_action: "review stash",
             action: "review stashed work",
             command: cmd("git stash list"),
             reason: format!("{} stash entry(ies) present.", repo.status.stash_count),
+            effort_minutes: scaled_effort(5, repo.status.stash_count, 1),
+            escalates: true,
         };
     }
 
@@ -142,9 +576,14 @@ pub fn recommend(repo: &Repo) -> Recommendation {
         return Recommendation {
             priority: ActionPriority::Low,
             short_action: "set remote",
-            action: "configure remote tracking",
-            command: cmd("git remote -v"),
+            action: "create a remote repo and push",
+            command: cmd(&format!(
+                "gh repo create {} --private --source . --remote origin --push",
+                shell_quote(&repo.name)
+            )),
             reason: "No remote configured.".to_string(),
+            effort_minutes: 5,
+            escalates: true,
         };
     }
 
@@ -154,11 +593,37 @@ pub fn recommend(repo: &Repo) -> Recommendation {
         action: "no action needed",
         command: cmd("git status -sb"),
         reason: "Working tree and remote state are clean.".to_string(),
+        effort_minutes: 0,
+        escalates: false,
     }
 }
 
+/// `value` in single quotes for a POSIX shell, so `$`, backticks and `!` in
+/// commit messages and paths reach git as written.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// `base` minutes plus one per `per` items, capped so a huge change set
+/// doesn't swallow a whole budget on its own.
+fn scaled_effort(base: u32, count: usize, per: usize) -> u32 {
+    base + (count / per.max(1)).min(45) as u32
+}
+
+/// The action behind [`recommend`]; `None` for idle repos and for network
+/// actions held back by [`configure_vpn_offline`].
 pub fn recommended_action_kind(repo: &Repo) -> Option<ActionKind> {
+    action_kind(repo).filter(|kind| {
+        !(kind.needs_network() && vpn_offline().contains(repo.path.to_string_lossy().as_ref()))
+    })
+}
+
+fn action_kind(repo: &Repo) -> Option<ActionKind> {
     let repo_path = repo.path.to_string_lossy().to_string();
+    let limits = thresholds();
+    let rules = rules();
+    let tracked = tracked_changes(&repo.status);
+    let message = rules.commit_message(repo, tracked);
 
     if repo.status.is_detached {
         return Some(ActionKind::GitSwitchCreate {
@@ -167,10 +632,17 @@ pub fn recommended_action_kind(repo: &Repo) -> Option<ActionKind> {
         });
     }
 
-    if repo.status.behind_count > 0 && repo.status.uncommitted_count > 0 {
+    if tracked > 0 && rules.blocks_commit(&repo.status.branch) {
+        return Some(ActionKind::GitSwitchCreate {
+            repo_path,
+            branch: rules.work_branch(&repo.status.branch),
+        });
+    }
+
+    if repo.status.behind_count > 0 && tracked > 0 {
         return Some(ActionKind::GitAddCommitPullRebase {
             repo_path,
-            message: "wip".to_string(),
+            message: message.clone(),
         });
     }
 
@@ -182,17 +654,25 @@ pub fn recommended_action_kind(repo: &Repo) -> Option<ActionKind> {
         return Some(ActionKind::GitPullRebase { repo_path });
     }
 
-    if repo.status.uncommitted_count > 0 && repo.status.unpushed_count > 0 {
+    if tracked > 0 && repo.status.unpushed_count > 0 && !repo.status.missing_upstream {
         return Some(ActionKind::GitAddCommitPush {
             repo_path,
-            message: "wip".to_string(),
+            message: message.clone(),
         });
     }
 
-    if repo.status.uncommitted_count > 0 {
+    if tracked > 0 {
         return Some(ActionKind::GitAddCommit {
             repo_path,
-            message: "wip".to_string(),
+            message: message.clone(),
+        });
+    }
+
+    if repo.status.missing_upstream {
+        return Some(ActionKind::GitPushSetUpstream {
+            repo_path,
+            remote: repo.status.publish_remote().to_string(),
+            branch: repo.status.branch.clone(),
         });
     }
 
@@ -200,17 +680,66 @@ pub fn recommended_action_kind(repo: &Repo) -> Option<ActionKind> {
         return Some(ActionKind::GitPush { repo_path });
     }
 
-    if repo.status.stash_count > 0 {
+    if untracked_flagged(&limits, &repo.status) {
+        return Some(ActionKind::GitStatus { repo_path });
+    }
+
+    if stash_flagged(&limits, &repo.status) {
         return Some(ActionKind::GitStashList { repo_path });
     }
 
     if !repo.status.has_remote {
-        return Some(ActionKind::GitRemoteList { repo_path });
+        return Some(ActionKind::CreateRemoteRepo {
+            repo_path,
+            forge: RemoteForge::Github,
+            name: repo.name.clone(),
+            private: true,
+        });
     }
 
     None
 }
 
+/// Limits for the agent work queue (`--limit`, `--max-minutes`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    pub limit: Option<usize>,
+    pub max_minutes: Option<u32>,
+}
+
+impl Budget {
+    pub fn is_unbounded(&self) -> bool {
+        self.limit.is_none() && self.max_minutes.is_none()
+    }
+}
+
+/// Pick actionable recommendations in priority order until the budget is spent.
+/// Items that would overflow `max_minutes` are skipped so smaller ones can still fit.
+pub fn budget_queue<'a>(
+    items: &[(&'a Repo, Recommendation)],
+    budget: Budget,
+) -> Vec<(&'a Repo, Recommendation)> {
+    let mut queue = Vec::new();
+    let mut spent = 0u32;
+    for (repo, rec) in items
+        .iter()
+        .filter(|(_, r)| r.priority != ActionPriority::Idle)
+    {
+        if budget.limit.is_some_and(|limit| queue.len() >= limit) {
+            break;
+        }
+        if budget
+            .max_minutes
+            .is_some_and(|max| spent + rec.effort_minutes > max)
+        {
+            continue;
+        }
+        spent += rec.effort_minutes;
+        queue.push((*repo, rec.clone()));
+    }
+    queue
+}
+
 pub fn sorted_recommendations(repos: &[Repo]) -> Vec<(&Repo, Recommendation)> {
     let mut items: Vec<(&Repo, Recommendation)> = repos.iter().map(|r| (r, recommend(r))).collect();
     items.sort_by(|(repo_a, rec_a), (repo_b, rec_b)| {
@@ -226,7 +755,8 @@ pub fn sorted_recommendations(repos: &[Repo]) -> Vec<(&Repo, Recommendation)> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::{Repo, RepoStatus};
+    use crate::git::{ChangeCounts, Repo, RepoStatus};
+    use proptest::prelude::*;
     use std::path::PathBuf;
 
     fn repo_with_status(name: &str, status: RepoStatus) -> Repo {
@@ -235,6 +765,7 @@ mod tests {
             name: name.to_string(),
             status,
             last_checked: None,
+            identity: None,
         }
     }
 
@@ -245,12 +776,18 @@ mod tests {
             RepoStatus {
                 branch: "HEAD".to_string(),
                 uncommitted_count: 0,
+                changes: Default::default(),
                 unpushed_count: 0,
                 behind_count: 0,
                 stash_count: 0,
                 has_remote: true,
                 is_detached: true,
                 probe_errors: Vec::new(),
+                push: None,
+                missing_upstream: false,
+                dirty_since: None,
+                unpushed_since: None,
+                head_sha: None,
             },
         );
         let rec = recommend(&repo);
@@ -264,12 +801,18 @@ mod tests {
             RepoStatus {
                 branch: "main".to_string(),
                 uncommitted_count: 3,
+                changes: Default::default(),
                 unpushed_count: 2,
                 behind_count: 0,
                 stash_count: 0,
                 has_remote: true,
                 is_detached: false,
                 probe_errors: Vec::new(),
+                push: None,
+                missing_upstream: false,
+                dirty_since: None,
+                unpushed_since: None,
+                head_sha: None,
             },
         );
         let rec = recommend(&repo);
@@ -284,15 +827,510 @@ mod tests {
             RepoStatus {
                 branch: "main".to_string(),
                 uncommitted_count: 0,
+                changes: Default::default(),
                 unpushed_count: 0,
                 behind_count: 0,
                 stash_count: 0,
                 has_remote: true,
                 is_detached: false,
                 probe_errors: Vec::new(),
+                push: None,
+                missing_upstream: false,
+                dirty_since: None,
+                unpushed_since: None,
+                head_sha: None,
             },
         );
         let rec = recommend(&repo);
         assert_eq!(rec.priority, ActionPriority::Idle);
+        assert_eq!(rec.effort_minutes, 0);
+    }
+
+    fn dirty(name: &str, files: usize) -> Repo {
+        repo_with_status(
+            name,
+            RepoStatus {
+                branch: "main".to_string(),
+                uncommitted_count: files,
+                has_remote: true,
+                ..RepoStatus::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_effort_scales_with_change_size() {
+        assert!(
+            recommend(&dirty("big", 50)).effort_minutes
+                > recommend(&dirty("small", 1)).effort_minutes
+        );
+        assert!(recommend(&dirty("huge", 100_000)).effort_minutes <= 60);
+    }
+
+    #[test]
+    fn test_budget_queue_respects_limit_and_minutes() {
+        let repos = vec![dirty("a", 1), dirty("b", 40), dirty("c", 1), dirty("d", 1)];
+        let recs = sorted_recommendations(&repos);
+
+        let limited = budget_queue(
+            &recs,
+            Budget {
+                limit: Some(2),
+                max_minutes: None,
+            },
+        );
+        assert_eq!(limited.len(), 2);
+
+        // "b" (11 min) overflows an 8 minute budget; smaller items still fit.
+        let timed = budget_queue(
+            &recs,
+            Budget {
+                limit: None,
+                max_minutes: Some(8),
+            },
+        );
+        let names: Vec<&str> = timed.iter().map(|(r, _)| r.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "c"]);
+        assert!(timed.iter().map(|(_, r)| r.effort_minutes).sum::<u32>() <= 8);
+    }
+
+    #[test]
+    fn test_unbounded_budget_keeps_all_actionable() {
+        let mut repos = vec![dirty("a", 1), dirty("b", 2)];
+        repos.push(repo_with_status(
+            "clean",
+            RepoStatus {
+                branch: "main".to_string(),
+                has_remote: true,
+                ..RepoStatus::default()
+            },
+        ));
+        let recs = sorted_recommendations(&repos);
+        assert!(Budget::default().is_unbounded());
+        assert_eq!(budget_queue(&recs, Budget::default()).len(), 2);
+    }
+
+    #[test]
+    fn network_actions_wait_for_the_vpn() {
+        let status = |uncommitted_count, unpushed_count| RepoStatus {
+            branch: "main".to_string(),
+            uncommitted_count,
+            changes: ChangeCounts {
+                unstaged: uncommitted_count,
+                ..ChangeCounts::default()
+            },
+            unpushed_count,
+            has_remote: true,
+            ..RepoStatus::default()
+        };
+        let offline = BTreeSet::from(["/tmp/api".to_string()]);
+        let push = repo_with_status("api", status(0, 2));
+        let held = hold_for_vpn(recommend(&push), &push, &offline);
+        assert_eq!(held.short_action, "connect vpn");
+        assert_eq!(held.priority, recommend(&push).priority);
+
+        // Local work is still suggested; other repos are untouched.
+        let commit = repo_with_status("api", status(2, 0));
+        assert_eq!(
+            hold_for_vpn(recommend(&commit), &commit, &offline).short_action,
+            "commit"
+        );
+        let web = repo_with_status("web", status(0, 2));
+        assert_eq!(
+            hold_for_vpn(recommend(&web), &web, &offline).short_action,
+            "push"
+        );
+        // A clone with the same name under another root has its own remote.
+        let mut other = repo_with_status("api", status(0, 2));
+        other.path = PathBuf::from("/work/api");
+        assert_eq!(
+            hold_for_vpn(recommend(&other), &other, &offline).short_action,
+            "push"
+        );
+    }
+
+    #[test]
+    fn test_branch_without_upstream_is_published() {
+        let repo = repo_with_status(
+            "fresh",
+            RepoStatus {
+                branch: "feature/x".to_string(),
+                uncommitted_count: 2,
+                unpushed_count: 1,
+                has_remote: true,
+                missing_upstream: true,
+                ..RepoStatus::default()
+            },
+        );
+        // Local changes get committed first rather than pushed with a bare `git push`.
+        assert_eq!(recommend(&repo).short_action, "commit");
+
+        let repo = repo_with_status(
+            "fresh",
+            RepoStatus {
+                uncommitted_count: 0,
+                ..repo.status.clone()
+            },
+        );
+        let rec = recommend(&repo);
+        assert_eq!(rec.short_action, "publish");
+        assert!(rec.command.ends_with("git push -u 'origin' 'feature/x'"));
+        assert!(matches!(
+            recommended_action_kind(&repo),
+            Some(ActionKind::GitPushSetUpstream { remote, .. }) if remote == "origin"
+        ));
+
+        // Ref names may hold shell metacharacters; they stay one argument.
+        let mut hostile = repo.clone();
+        hostile.status.branch = "x;rm -rf ~".to_string();
+        assert!(recommend(&hostile)
+            .command
+            .ends_with("git push -u 'origin' 'x;rm -rf ~'"));
+    }
+
+    #[test]
+    fn test_no_remote_offers_create_remote() {
+        let repo = repo_with_status(
+            "local-only",
+            RepoStatus {
+                branch: "main".to_string(),
+                ..RepoStatus::default()
+            },
+        );
+        assert_eq!(recommend(&repo).short_action, "set remote");
+        assert!(matches!(
+            recommended_action_kind(&repo),
+            Some(ActionKind::CreateRemoteRepo { name, private: true, .. }) if name == "local-only"
+        ));
+    }
+
+    #[test]
+    fn thresholds_raise_and_lower_priority() {
+        let limits = ThresholdsConfig {
+            behind_warn: 5,
+            behind_critical: 20,
+            dirty_file_critical: 50,
+            stash_warn: 3,
+            ..ThresholdsConfig::default()
+        };
+        let with = |status: RepoStatus| {
+            recommend_with(
+                &limits,
+                &RuleSet::default(),
+                &repo_with_status("r", status),
+                0,
+            )
+        };
+        let behind = |count| RepoStatus {
+            behind_count: count,
+            has_remote: true,
+            ..RepoStatus::default()
+        };
+
+        assert_eq!(with(behind(1)).priority, ActionPriority::Low);
+        assert_eq!(with(behind(5)).priority, ActionPriority::High);
+        assert_eq!(with(behind(80)).priority, ActionPriority::Critical);
+        assert_eq!(with(behind(80)).short_action, "pull");
+
+        let dirty = |count| RepoStatus {
+            uncommitted_count: count,
+            has_remote: true,
+            ..RepoStatus::default()
+        };
+        assert_eq!(with(dirty(49)).priority, ActionPriority::Medium);
+        assert_eq!(with(dirty(50)).priority, ActionPriority::Critical);
+
+        let stash = |count| RepoStatus {
+            stash_count: count,
+            has_remote: true,
+            ..RepoStatus::default()
+        };
+        assert_eq!(with(stash(2)).priority, ActionPriority::Idle);
+        assert_eq!(with(stash(3)).priority, ActionPriority::Low);
+
+        // Defaults keep the fixed mapping: any count counts, nothing escalates.
+        let defaults = ThresholdsConfig::default();
+        assert_eq!(level(&defaults, Counter::Behind, 80), Level::Warn);
+        assert_eq!(level(&defaults, Counter::Stash, 0), Level::Quiet);
+        assert_eq!(
+            repo_level_with(&limits, &behind(1)),
+            Level::Quiet,
+            "below behind_warn"
+        );
+    }
+
+    #[test]
+    fn old_local_work_escalates() {
+        let limits = ThresholdsConfig::default();
+        let now = 1_700_000_000;
+        let dirty_for = |hours: i64| {
+            let status = RepoStatus {
+                uncommitted_count: 2,
+                has_remote: true,
+                dirty_since: Some(now - hours * 3600),
+                ..RepoStatus::default()
+            };
+            recommend_with(
+                &limits,
+                &RuleSet::default(),
+                &repo_with_status("r", status),
+                now,
+            )
+        };
+        assert_eq!(dirty_for(2).priority, ActionPriority::Medium);
+        assert_eq!(dirty_for(30).priority, ActionPriority::High);
+        let stale = dirty_for(80);
+        assert_eq!(stale.priority, ActionPriority::Critical);
+        assert!(stale.reason.ends_with("Local work is 3 days old."));
+
+        let unpushed = RepoStatus {
+            unpushed_count: 1,
+            has_remote: true,
+            unpushed_since: Some(now - 30 * 3600),
+            ..RepoStatus::default()
+        };
+        let off = ThresholdsConfig {
+            work_age_high_hours: 0,
+            ..limits
+        };
+        let repo = repo_with_status("r", unpushed);
+        assert_eq!(
+            recommend_with(&limits, &RuleSet::default(), &repo, now).priority,
+            ActionPriority::High
+        );
+        assert_eq!(
+            recommend_with(&off, &RuleSet::default(), &repo, now).priority,
+            ActionPriority::Medium
+        );
+    }
+
+    #[test]
+    fn untracked_files_alone_rank_low_and_are_never_committed() {
+        let limits = ThresholdsConfig {
+            dirty_file_critical: 50,
+            ..ThresholdsConfig::default()
+        };
+        let now = 1_700_000_000;
+        let status = |untracked, modified| RepoStatus {
+            uncommitted_count: untracked + modified,
+            changes: ChangeCounts {
+                unstaged: modified,
+                untracked,
+                ..ChangeCounts::default()
+            },
+            has_remote: true,
+            dirty_since: Some(now - 80 * 3600),
+            ..RepoStatus::default()
+        };
+
+        // Many old scratch files still only make a low-priority review.
+        let scratch = repo_with_status("r", status(60, 0));
+        let rec = recommend_with(&limits, &RuleSet::default(), &scratch, now);
+        assert_eq!(rec.priority, ActionPriority::Low);
+        assert!(!rec.escalates);
+        assert_eq!(rec.short_action, "add or ignore");
+        assert!(rec
+            .command
+            .ends_with("git status --short --untracked-files=all"));
+        assert!(matches!(
+            recommended_action_kind(&scratch),
+            Some(ActionKind::GitStatus { .. })
+        ));
+
+        // Modified tracked files outrank them, and only those are staged.
+        let modified = recommend_with(
+            &limits,
+            &RuleSet::default(),
+            &repo_with_status("r", status(3, 2)),
+            now,
+        );
+        assert!(modified.priority < rec.priority);
+        assert_eq!(
+            modified.reason,
+            "2 uncommitted file(s). Local work is 3 days old."
+        );
+        assert!(modified.command.contains("git add -u && git commit"));
+
+        let ignored = ThresholdsConfig {
+            untracked_actionable: false,
+            ..limits
+        };
+        let rec = recommend_with(&ignored, &RuleSet::default(), &scratch, now);
+        assert_eq!(rec.priority, ActionPriority::Idle);
+        assert_eq!(repo_level_with(&ignored, &scratch.status), Level::Quiet);
+    }
+
+    #[test]
+    fn commit_messages_are_single_quoted_for_the_shell() {
+        let rules = RuleSet::from(&RulesConfig {
+            commit_message: "wip: $HOME `id` it's {branch}".to_string(),
+            ..RulesConfig::default()
+        });
+        let mut repo = repo_with_status(
+            "r",
+            RepoStatus {
+                branch: "feature".to_string(),
+                uncommitted_count: 1,
+                has_remote: true,
+                ..RepoStatus::default()
+            },
+        );
+        repo.path = PathBuf::from("/tmp/a $b");
+        let rec = recommend_with(&ThresholdsConfig::default(), &rules, &repo, 0);
+        assert!(rec.command.starts_with("cd '/tmp/a $b' && "));
+        assert!(rec
+            .command
+            .ends_with(r#"git commit -m 'wip: $HOME `id` it'\''s feature'"#));
+    }
+
+    #[test]
+    fn rules_set_commit_message_branches_and_priority() {
+        let rules = RuleSet::from(&RulesConfig {
+            commit_message: "wip({branch}): {files} file(s)".to_string(),
+            no_commit_branches: vec!["main".to_string()],
+            priority: vec![
+                PriorityRule {
+                    counter: RuleCounter::Behind,
+                    at_least: 10,
+                    priority: RulePriority::Critical,
+                    branches: Vec::new(),
+                },
+                PriorityRule {
+                    counter: RuleCounter::Dirty,
+                    at_least: 1,
+                    priority: RulePriority::Critical,
+                    branches: vec!["release/*".to_string()],
+                },
+            ],
+        });
+        let limits = ThresholdsConfig::default();
+        let on = |branch: &str, dirty: usize, behind: usize| {
+            repo_with_status(
+                "r",
+                RepoStatus {
+                    branch: branch.to_string(),
+                    uncommitted_count: dirty,
+                    behind_count: behind,
+                    has_remote: true,
+                    ..RepoStatus::default()
+                },
+            )
+        };
+
+        let feature = recommend_with(&limits, &rules, &on("feature", 2, 0), 0);
+        assert_eq!(feature.short_action, "commit");
+        assert!(feature
+            .command
+            .ends_with("git commit -m 'wip(feature): 2 file(s)'"));
+
+        let main = recommend_with(&limits, &rules, &on("main", 2, 0), 0);
+        assert_eq!(main.short_action, "branch off");
+        assert!(main.command.ends_with("git switch -c 'wip/main'"));
+
+        // Rule branches take the same `*` patterns as protected branches.
+        let release = recommend_with(&limits, &rules, &on("release/1.2", 2, 0), 0);
+        assert_eq!(release.priority, ActionPriority::Critical);
+        assert_ne!(feature.priority, ActionPriority::Critical);
+
+        assert_eq!(
+            recommend_with(&limits, &rules, &on("feature", 0, 3), 0).priority,
+            ActionPriority::High
+        );
+        let far_behind = recommend_with(&limits, &rules, &on("feature", 0, 12), 0);
+        assert_eq!(far_behind.priority, ActionPriority::Critical);
+        assert!(far_behind.reason.ends_with("Rule: behind ≥ 10 → critical."));
+        // Rules never invent work for a clean repo.
+        assert_eq!(
+            recommend_with(&limits, &rules, &on("feature", 0, 0), 0).priority,
+            ActionPriority::Idle
+        );
+    }
+
+    fn any_status() -> impl Strategy<Value = RepoStatus> {
+        (
+            0usize..200,
+            0usize..200,
+            0usize..200,
+            0usize..200,
+            0usize..5,
+            any::<bool>(),
+            any::<bool>(),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(
+                    uncommitted,
+                    untracked,
+                    unpushed,
+                    behind,
+                    stash,
+                    has_remote,
+                    detached,
+                    missing_upstream,
+                )| {
+                    RepoStatus {
+                        branch: "main".to_string(),
+                        uncommitted_count: uncommitted,
+                        changes: ChangeCounts {
+                            untracked: untracked.min(uncommitted),
+                            ..ChangeCounts::default()
+                        },
+                        unpushed_count: unpushed,
+                        behind_count: behind,
+                        stash_count: stash,
+                        has_remote,
+                        is_detached: detached,
+                        missing_upstream,
+                        ..RepoStatus::default()
+                    }
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn recommendations_are_consistent_for_any_status(status in any_status()) {
+            let repo = repo_with_status("prop", status.clone());
+            let rec = recommend(&repo);
+            let idle = rec.priority == ActionPriority::Idle;
+
+            prop_assert!(rec.command.starts_with("cd '/tmp/prop' && "));
+            prop_assert_eq!(idle, rec.effort_minutes == 0);
+            prop_assert_eq!(idle, recommended_action_kind(&repo).is_none());
+            prop_assert_eq!(idle, !needs_attention(&repo));
+            prop_assert!(rec.effort_minutes <= 100);
+            if status.is_detached {
+                prop_assert_eq!(rec.priority, ActionPriority::Critical);
+            }
+            // Local work is never left behind as low priority.
+            if tracked_changes(&status) > 0 || status.behind_count > 0 {
+                prop_assert!(rec.priority <= ActionPriority::Medium);
+            }
+        }
+
+        #[test]
+        fn budget_queue_respects_limits(
+            statuses in proptest::collection::vec(any_status(), 0..12),
+            limit in proptest::option::of(0usize..6),
+            max_minutes in proptest::option::of(0u32..60),
+        ) {
+            let repos: Vec<Repo> = statuses
+                .into_iter()
+                .enumerate()
+                .map(|(i, status)| repo_with_status(&format!("r{}", i), status))
+                .collect();
+            let items = sorted_recommendations(&repos);
+            let queue = budget_queue(&items, Budget { limit, max_minutes });
+
+            prop_assert!(queue.iter().all(|(_, r)| r.priority != ActionPriority::Idle));
+            if let Some(limit) = limit {
+                prop_assert!(queue.len() <= limit);
+            }
+            if let Some(max) = max_minutes {
+                prop_assert!(queue.iter().map(|(_, r)| r.effort_minutes).sum::<u32>() <= max);
+            }
+            // The queue keeps priority order.
+            prop_assert!(queue.windows(2).all(|w| w[0].1.priority <= w[1].1.priority));
+        }
     }
 }
@@ -1,9 +1,31 @@
+use crate::actions::{self, ActionSettings, CommitRecord, ExpectedState};
 use crate::agent;
-use crate::config::Config;
-use crate::dashboard::{ActionCommand, DashboardSection, DashboardSnapshot};
-use crate::git::Repo;
+use crate::audit::AuditEntry;
+use crate::codeowners::Ownership;
+use crate::collectors::CollectMode;
+use crate::config::{branch_matches, Config, DisplayConfig};
+use crate::dashboard::{
+    ActionCommand, ActionKind, DashboardSection, DashboardSnapshot, RemoteForge, RepoTicket,
+};
+use crate::git::{
+    self, BranchInfo, ChangedFile, CommitSummary, LogEntry, Repo, StashEntry, WorkingDiff,
+};
+use crate::history::{self, Change, Digest, HistoryEntry};
+use crate::i18n::{t, tr};
+use crate::ipc::InstanceRole;
+use crate::recent::RecentRepos;
+use crate::search::RepoHit;
+use crate::settings::{SettingsForm, FIELDS};
+use crate::state::{Bookmark, SessionState};
+use crate::suppress::Suppressions;
+use crate::watcher::WatchCoverage;
 use chrono::{DateTime, Local};
-use std::time::Instant;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Action commits remembered for undo.
+const COMMIT_HISTORY_LIMIT: usize = 20;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppMode {
@@ -14,8 +36,781 @@ pub enum AppMode {
     Commit,
     /// Confirming a selected action; Enter runs, Esc cancels.
     ConfirmAction,
+    /// Picking files for a partial stash (`z` on a repo).
+    StashPick,
+    /// Creating a hosted repo for a repo without remotes.
+    RemoteWizard,
+    /// Switching or creating a branch (`B` on a repo).
+    BranchPick,
+    /// Marking commits to cherry-pick onto another branch (`C` on a repo).
+    CherryPick,
+    /// Browsing a repo's stash entries (`Z` on a repo).
+    Stashes,
+    /// Reading a repo's uncommitted diff (`D` on a repo).
+    Diff,
+    /// Reading a repo's recent commits (`L` on a repo).
+    CommitLog,
+    /// Typing a cross-repo search pattern (`G`).
+    Grep,
+    /// Comparing two saved snapshots (`T`).
+    TimeTravel,
+    /// Per-host connectivity (`I`).
+    Integrations,
+    /// First-launch walkthrough over example data.
+    Tour,
+    /// Typing a value in the Settings section; Enter keeps it, Esc cancels.
+    EditSetting,
+    /// Quick-switching to a recently used repo (`Ctrl-P`).
+    RecentRepos,
+}
+
+/// Stops of the first-launch tour, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TourStep {
+    Sidebar,
+    Summary,
+    ActionKey,
+    Confirm,
+}
+
+impl TourStep {
+    pub const ALL: [TourStep; 4] = [
+        TourStep::Sidebar,
+        TourStep::Summary,
+        TourStep::ActionKey,
+        TourStep::Confirm,
+    ];
+
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|step| *step == self).unwrap_or(0)
+    }
+}
+
+/// The tour's example workspace, drawn in place of the real one so every
+/// stop has something to point at even before the first scan finishes.
+pub struct Tour {
+    pub step: TourStep,
+    pub demo: Box<App>,
+}
+
+impl Tour {
+    fn new(config: &Config) -> Self {
+        let mut demo = App::new(config.clone());
+        demo.repos = crate::demo::repos();
+        demo.dashboard = crate::demo::snapshot(&demo.repos);
+        demo.is_scanning = false;
+        demo.last_scan = Some(Local::now());
+        let mut tour = Self {
+            step: TourStep::Sidebar,
+            demo: Box::new(demo),
+        };
+        tour.show(TourStep::Sidebar);
+        tour
+    }
+
+    /// Move to `step`, opening the example confirmation on its stop.
+    fn show(&mut self, step: TourStep) {
+        self.step = step;
+        let demo = &mut self.demo;
+        demo.clear_pending_action();
+        demo.section = match step {
+            TourStep::Sidebar | TourStep::Summary => DashboardSection::Home,
+            TourStep::ActionKey | TourStep::Confirm => DashboardSection::Repos,
+        };
+        demo.selected = 0;
+        if step == TourStep::Confirm {
+            if let Some(action) = demo.selected_action() {
+                demo.stage_action_confirmation(action);
+            }
+        }
+    }
+}
+
+/// Editable fields of the create-remote wizard, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardField {
+    Forge,
+    Name,
+    Visibility,
+}
+
+/// Create-remote wizard state, prefilled from a `CreateRemoteRepo` action.
+pub struct RemoteWizard {
+    pub repo_path: String,
+    pub forge: RemoteForge,
+    pub name: String,
+    pub private: bool,
+    pub field: WizardField,
+}
+
+impl RemoteWizard {
+    /// Start from a suggested action; prefers GitLab only when `gh` is
+    /// missing and `glab` is installed.
+    pub fn from_action(action: &ActionKind, has_gh: bool, has_glab: bool) -> Option<Self> {
+        let ActionKind::CreateRemoteRepo {
+            repo_path,
+            forge,
+            name,
+            private,
+        } = action
+        else {
+            return None;
+        };
+        let forge = if !has_gh && has_glab {
+            RemoteForge::Gitlab
+        } else {
+            *forge
+        };
+        Some(Self {
+            repo_path: repo_path.clone(),
+            forge,
+            name: name.clone(),
+            private: *private,
+            field: WizardField::Name,
+        })
+    }
+
+    pub fn move_field(&mut self, delta: i32) {
+        let fields = [
+            WizardField::Forge,
+            WizardField::Name,
+            WizardField::Visibility,
+        ];
+        let idx = fields.iter().position(|f| *f == self.field).unwrap_or(1) as i32;
+        self.field = fields[(idx + delta).clamp(0, 2) as usize];
+    }
+
+    /// Flip the forge or visibility, depending on the focused field.
+    pub fn toggle(&mut self) {
+        match self.field {
+            WizardField::Forge => {
+                self.forge = match self.forge {
+                    RemoteForge::Github => RemoteForge::Gitlab,
+                    RemoteForge::Gitlab => RemoteForge::Github,
+                }
+            }
+            WizardField::Visibility => self.private = !self.private,
+            WizardField::Name => {}
+        }
+    }
+
+    /// The create action; `None` while the name is blank or contains spaces.
+    pub fn to_action(&self) -> Option<ActionCommand> {
+        let name = self.name.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return None;
+        }
+        Some(ActionCommand::new(
+            format!("create {} on {}", name, self.forge.label()),
+            ActionKind::CreateRemoteRepo {
+                repo_path: self.repo_path.clone(),
+                forge: self.forge,
+                name: name.to_string(),
+                private: self.private,
+            },
+        ))
+    }
+}
+
+/// Quick switcher state for `Ctrl-P`.
+pub struct RecentPicker {
+    /// Scanned repos that have been used, by frecency: (path, name).
+    pub entries: Vec<(PathBuf, String)>,
+    pub cursor: usize,
+    /// Typed name filter.
+    pub query: String,
+}
+
+impl RecentPicker {
+    /// Entries whose name contains the query, ignoring case.
+    pub fn matches(&self) -> Vec<&(PathBuf, String)> {
+        let query = self.query.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|(_, name)| name.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    pub fn move_cursor(&mut self, delta: i32) {
+        let len = self.matches().len();
+        if len == 0 {
+            return;
+        }
+        self.cursor = (self.cursor as i32 + delta).clamp(0, len as i32 - 1) as usize;
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        self.query.push(c);
+        self.cursor = 0;
+    }
+
+    pub fn backspace(&mut self) {
+        self.query.pop();
+        self.cursor = 0;
+    }
+}
+
+/// Branch switcher state for `B` on a repo.
+pub struct BranchPicker {
+    pub repo_path: PathBuf,
+    pub repo_name: String,
+    pub branches: Vec<BranchInfo>,
+    pub cursor: usize,
+    /// Uncommitted changes in the working tree when the picker opened.
+    pub dirty_count: usize,
+    /// Stash local changes around the switch; on by default when dirty.
+    pub autostash: bool,
+    /// Typing a new branch name instead of picking one.
+    pub creating: bool,
+    pub new_name: String,
+}
+
+impl BranchPicker {
+    pub fn new(repo: &Repo, branches: Vec<BranchInfo>) -> Self {
+        let dirty_count = repo.status.uncommitted_count;
+        Self {
+            repo_path: repo.path.clone(),
+            repo_name: repo.name.clone(),
+            cursor: branches.iter().position(|b| b.is_current).unwrap_or(0),
+            branches,
+            dirty_count,
+            autostash: dirty_count > 0,
+            creating: false,
+            new_name: String::new(),
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: i32) {
+        if self.branches.is_empty() {
+            return;
+        }
+        let max = self.branches.len() as i32 - 1;
+        self.cursor = (self.cursor as i32 + delta).clamp(0, max) as usize;
+    }
+
+    /// Switch to the highlighted branch; `Err` explains why not.
+    pub fn switch_action(&self) -> Result<ActionCommand, String> {
+        let branch = self
+            .branches
+            .get(self.cursor)
+            .ok_or_else(|| t("notify-no-branches"))?;
+        if branch.is_current {
+            return Err(tr("notify-already-on-branch", &[("branch", &branch.name)]));
+        }
+        let label = if self.autostash && self.dirty_count > 0 {
+            format!("switch to {} (autostash)", branch.name)
+        } else {
+            format!("switch to {}", branch.name)
+        };
+        Ok(ActionCommand::new(
+            label,
+            ActionKind::GitSwitch {
+                repo_path: self.repo_path.to_string_lossy().to_string(),
+                branch: branch.name.clone(),
+                autostash: self.autostash,
+            },
+        ))
+    }
+
+    /// Create and switch to the typed branch; local changes come along.
+    pub fn create_action(&self) -> Result<ActionCommand, String> {
+        let name = self.new_name.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(t("notify-bad-branch-name"));
+        }
+        if self.branches.iter().any(|b| b.name == name) {
+            return Err(tr("notify-branch-exists", &[("branch", &name)]));
+        }
+        Ok(ActionCommand::new(
+            format!("create branch {}", name),
+            ActionKind::GitSwitchCreate {
+                repo_path: self.repo_path.to_string_lossy().to_string(),
+                branch: name.to_string(),
+            },
+        ))
+    }
+}
+
+/// Stash browser state for `Z` on a repo.
+pub struct StashBrowser {
+    pub repo_path: PathBuf,
+    pub repo_name: String,
+    pub entries: Vec<StashEntry>,
+    pub cursor: usize,
+}
+
+/// What the stash browser does with the highlighted entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StashOp {
+    Apply,
+    Pop,
+    Drop,
+}
+
+impl StashBrowser {
+    pub fn new(repo: &Repo, entries: Vec<StashEntry>) -> Self {
+        Self {
+            repo_path: repo.path.clone(),
+            repo_name: repo.name.clone(),
+            entries,
+            cursor: 0,
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: i32) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let max = self.entries.len() as i32 - 1;
+        self.cursor = (self.cursor as i32 + delta).clamp(0, max) as usize;
+    }
+
+    /// `op` on the highlighted entry, for the confirmation modal.
+    pub fn action(&self, op: StashOp) -> Option<ActionCommand> {
+        let entry = self.entries.get(self.cursor)?;
+        let repo_path = self.repo_path.to_string_lossy().to_string();
+        let stash = entry.name.clone();
+        let oid = Some(entry.oid.clone());
+        let (verb, kind) = match op {
+            StashOp::Apply => (
+                "apply",
+                ActionKind::GitStashApply {
+                    repo_path,
+                    stash,
+                    oid,
+                },
+            ),
+            StashOp::Pop => (
+                "pop",
+                ActionKind::GitStashPop {
+                    repo_path,
+                    stash,
+                    oid,
+                },
+            ),
+            StashOp::Drop => (
+                "drop",
+                ActionKind::GitStashDrop {
+                    repo_path,
+                    stash,
+                    oid,
+                },
+            ),
+        };
+        Some(ActionCommand::new(
+            format!("{} {} ({})", verb, entry.name, entry.message),
+            kind,
+        ))
+    }
+}
+
+/// Commits listed by the commit log view.
+pub const COMMIT_LOG_LIMIT: usize = 50;
+
+/// Push the repo's branch, setting the upstream when it has none.
+pub fn push_command(repo: &Repo) -> ActionCommand {
+    let repo_path = repo.path.to_string_lossy().to_string();
+    if repo.status.missing_upstream {
+        ActionCommand::new(
+            "push -u (set upstream)",
+            ActionKind::GitPushSetUpstream {
+                repo_path,
+                remote: repo.status.publish_remote().to_string(),
+                branch: repo.status.branch.clone(),
+            },
+        )
+    } else {
+        ActionCommand::new("push", ActionKind::GitPush { repo_path })
+    }
+}
+
+/// Commit log state for `L` on a repo.
+pub struct CommitLog {
+    pub repo_name: String,
+    pub branch: String,
+    pub entries: Vec<LogEntry>,
+    pub cursor: usize,
+    /// Offered with `P` while some listed commits are not on the remote.
+    pub push: Option<ActionCommand>,
+}
+
+impl CommitLog {
+    pub fn new(repo: &Repo, entries: Vec<LogEntry>) -> Self {
+        let push = entries
+            .iter()
+            .any(|e| e.unpushed)
+            .then(|| push_command(repo));
+        Self {
+            repo_name: repo.name.clone(),
+            branch: repo.status.branch.clone(),
+            entries,
+            cursor: 0,
+            push,
+        }
+    }
+
+    pub fn unpushed_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.unpushed).count()
+    }
+
+    pub fn move_cursor(&mut self, delta: i32) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let max = self.entries.len() as i32 - 1;
+        self.cursor = (self.cursor as i32 + delta).clamp(0, max) as usize;
+    }
 }
 
+/// Diff lines the viewer keeps; larger diffs are cut with a note.
+const DIFF_LINE_LIMIT: usize = 5000;
+
+/// Lines moved by PageUp / PageDown in the diff viewer.
+const DIFF_PAGE_LINES: i32 = 20;
+
+/// Diff viewer state for `D` on a repo.
+pub struct DiffView {
+    pub repo_name: String,
+    /// Stat summary, then untracked paths, then the unified diff.
+    pub lines: Vec<String>,
+    /// How many of `lines` are the stat summary at the top.
+    pub stat_lines: usize,
+    /// Index of the first visible line.
+    pub scroll: usize,
+    pub files_changed: usize,
+}
+
+impl DiffView {
+    pub fn new(repo_name: &str, diff: &WorkingDiff) -> Self {
+        let mut lines: Vec<String> = diff.stat.lines().map(str::to_string).collect();
+        let stat_lines = lines.len();
+        if !diff.untracked.is_empty() {
+            lines.push(String::new());
+            lines.push(tr("diff-untracked", &[("count", &diff.untracked.len())]));
+            lines.extend(diff.untracked.iter().map(|path| format!("  {}", path)));
+        }
+        lines.push(String::new());
+        lines.extend(diff.patch.lines().map(|line| line.replace('\t', "    ")));
+        if lines.len() > DIFF_LINE_LIMIT {
+            let total = lines.len();
+            lines.truncate(DIFF_LINE_LIMIT);
+            lines.push(format!(
+                "… {} more lines; open the repo to see the rest",
+                total - DIFF_LINE_LIMIT
+            ));
+        }
+        let files_changed = diff
+            .patch
+            .lines()
+            .filter(|line| line.starts_with("diff --git "))
+            .count()
+            + diff.untracked.len();
+        Self {
+            repo_name: repo_name.to_string(),
+            lines,
+            stat_lines,
+            scroll: 0,
+            files_changed,
+        }
+    }
+
+    pub fn scroll_by(&mut self, delta: i32) {
+        let max = self.lines.len().saturating_sub(1) as i32;
+        self.scroll = (self.scroll as i32 + delta).clamp(0, max) as usize;
+    }
+
+    pub fn page(&mut self, direction: i32) {
+        self.scroll_by(direction * DIFF_PAGE_LINES);
+    }
+
+    /// Scroll to the next (`1`) or previous (`-1`) file's `diff --git` header.
+    pub fn jump_file(&mut self, direction: i32) {
+        let is_header = |line: &String| line.starts_with("diff --git ");
+        let found = if direction > 0 {
+            self.lines
+                .iter()
+                .enumerate()
+                .skip(self.scroll + 1)
+                .find(|(_, line)| is_header(line))
+        } else {
+            self.lines
+                .iter()
+                .enumerate()
+                .take(self.scroll)
+                .rfind(|(_, line)| is_header(line))
+        };
+        if let Some((idx, _)) = found {
+            self.scroll = idx;
+        }
+    }
+}
+
+/// A cross-repo search and, once it finishes, the repos that matched.
+pub struct GrepResults {
+    pub pattern: String,
+    /// `None` while the search is running.
+    pub hits: Option<Vec<RepoHit>>,
+}
+
+/// A branch commits can be cherry-picked onto.
+pub struct CherryTarget {
+    pub branch: String,
+    /// Worktree that has the branch checked out; picks happen there.
+    pub worktree: Option<PathBuf>,
+}
+
+/// Cherry-pick helper state for `C` on a repo: mark commits from HEAD's
+/// history, then choose the branch to apply them to.
+pub struct CherryPicker {
+    pub repo_path: PathBuf,
+    pub repo_name: String,
+    pub current_branch: String,
+    /// Newest first, as `git log` lists them.
+    pub commits: Vec<CommitSummary>,
+    pub marked: Vec<bool>,
+    pub cursor: usize,
+    pub targets: Vec<CherryTarget>,
+    /// Choosing the target branch instead of marking commits.
+    pub choosing_target: bool,
+    pub target_cursor: usize,
+}
+
+impl CherryPicker {
+    pub fn new(
+        repo: &Repo,
+        commits: Vec<CommitSummary>,
+        branches: Vec<BranchInfo>,
+        worktrees: Vec<(String, PathBuf)>,
+    ) -> Self {
+        let targets = branches
+            .into_iter()
+            .filter(|b| !b.is_current)
+            .map(|b| CherryTarget {
+                worktree: worktrees
+                    .iter()
+                    .find(|(name, _)| *name == b.name)
+                    .map(|(_, path)| path.clone()),
+                branch: b.name,
+            })
+            .collect();
+        Self {
+            repo_path: repo.path.clone(),
+            repo_name: repo.name.clone(),
+            current_branch: repo.status.branch.clone(),
+            marked: vec![false; commits.len()],
+            commits,
+            cursor: 0,
+            targets,
+            choosing_target: false,
+            target_cursor: 0,
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: i32) {
+        let (cursor, len) = if self.choosing_target {
+            (&mut self.target_cursor, self.targets.len())
+        } else {
+            (&mut self.cursor, self.commits.len())
+        };
+        if len == 0 {
+            return;
+        }
+        *cursor = (*cursor as i32 + delta).clamp(0, len as i32 - 1) as usize;
+    }
+
+    pub fn toggle(&mut self) {
+        if let Some(mark) = self.marked.get_mut(self.cursor) {
+            *mark = !*mark;
+        }
+    }
+
+    /// Marked commit hashes in the order they must be applied (oldest first).
+    pub fn marked_commits(&self) -> Vec<String> {
+        self.commits
+            .iter()
+            .zip(&self.marked)
+            .rev()
+            .filter(|(_, marked)| **marked)
+            .map(|(commit, _)| commit.sha.clone())
+            .collect()
+    }
+
+    /// Move on to choosing the target; `Err` explains why not yet.
+    pub fn choose_target(&mut self) -> Result<(), String> {
+        if !self.marked.contains(&true) {
+            return Err(t("notify-mark-commits"));
+        }
+        if self.targets.is_empty() {
+            return Err(t("notify-no-cherry-pick-target"));
+        }
+        self.choosing_target = true;
+        Ok(())
+    }
+
+    pub fn to_action(&self) -> Result<ActionCommand, String> {
+        let target = self
+            .targets
+            .get(self.target_cursor)
+            .ok_or_else(|| t("notify-no-target-branch"))?;
+        let commits = self.marked_commits();
+        Ok(ActionCommand::new(
+            format!(
+                "cherry-pick {} commit(s) onto {}",
+                commits.len(),
+                target.branch
+            ),
+            ActionKind::GitCherryPick {
+                repo_path: self.repo_path.to_string_lossy().to_string(),
+                branch: target.branch.clone(),
+                commits,
+            },
+        ))
+    }
+}
+
+/// File picker state for `git stash push -- <paths>`.
+pub struct StashPicker {
+    pub repo_path: PathBuf,
+    pub repo_name: String,
+    pub files: Vec<ChangedFile>,
+    pub checked: Vec<bool>,
+    pub cursor: usize,
+    pub message: String,
+    /// Typing the stash message instead of moving through files.
+    pub editing_message: bool,
+}
+
+impl StashPicker {
+    pub fn new(repo: &Repo, files: Vec<ChangedFile>) -> Self {
+        Self {
+            repo_path: repo.path.clone(),
+            repo_name: repo.name.clone(),
+            checked: vec![false; files.len()],
+            files,
+            cursor: 0,
+            message: String::new(),
+            editing_message: false,
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: i32) {
+        if self.files.is_empty() {
+            return;
+        }
+        let max = self.files.len() as i32 - 1;
+        self.cursor = (self.cursor as i32 + delta).clamp(0, max) as usize;
+    }
+
+    pub fn toggle(&mut self) {
+        if let Some(c) = self.checked.get_mut(self.cursor) {
+            *c = !*c;
+        }
+    }
+
+    /// Check everything, or clear all if everything is already checked.
+    pub fn toggle_all(&mut self) {
+        let all = self.checked.iter().all(|c| *c);
+        self.checked.iter_mut().for_each(|c| *c = !all);
+    }
+
+    pub fn selected_paths(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .zip(&self.checked)
+            .filter(|(_, checked)| **checked)
+            .map(|(f, _)| f.path.clone())
+            .collect()
+    }
+
+    /// The stash action for the checked files; `None` when nothing is checked.
+    pub fn to_action(&self) -> Option<ActionCommand> {
+        let paths = self.selected_paths();
+        if paths.is_empty() {
+            return None;
+        }
+        let message = if self.message.trim().is_empty() {
+            format!("agentpulse: partial stash of {} file(s)", paths.len())
+        } else {
+            self.message.trim().to_string()
+        };
+        Some(ActionCommand::new(
+            format!("stash {} file(s)", paths.len()),
+            ActionKind::GitStashPushPaths {
+                repo_path: self.repo_path.to_string_lossy().to_string(),
+                message,
+                paths,
+            },
+        ))
+    }
+}
+
+/// Time-travel view state for `T`: pick two saved snapshots, then show what
+/// changed between them.
+pub struct TimeTravel {
+    /// Saved snapshots, newest first.
+    pub entries: Vec<HistoryEntry>,
+    pub cursor: usize,
+    /// First pick, waiting for the second.
+    pub from: Option<usize>,
+    /// Older and newer pick once both are chosen, with their diff.
+    pub compared: Option<(usize, usize, Vec<Change>)>,
+}
+
+impl TimeTravel {
+    pub fn new(mut entries: Vec<HistoryEntry>) -> Self {
+        entries.reverse();
+        Self {
+            entries,
+            cursor: 0,
+            from: None,
+            compared: None,
+        }
+    }
+
+    /// Opened straight on the overnight diff from `[schedule]`.
+    pub fn digest(digest: Digest) -> Self {
+        Self {
+            entries: vec![digest.to, digest.from],
+            cursor: 0,
+            from: None,
+            compared: Some((1, 0, digest.changes)),
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: i32) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let max = self.entries.len() as i32 - 1;
+        self.cursor = (self.cursor as i32 + delta).clamp(0, max) as usize;
+    }
+
+    /// Mark the entry under the cursor; the second mark computes the diff,
+    /// always from the older entry to the newer one.
+    pub fn select(&mut self, display: &DisplayConfig) {
+        match self.from {
+            None => self.from = Some(self.cursor),
+            Some(from) if from == self.cursor => self.from = None,
+            Some(from) => {
+                // Newest first, so the larger index is the older entry.
+                let (older, newer) = (from.max(self.cursor), from.min(self.cursor));
+                let changes = history::compare(&self.entries[older], &self.entries[newer], display);
+                self.compared = Some((older, newer, changes));
+            }
+        }
+    }
+
+    /// Step back from the diff to the list; `false` once there is nothing
+    /// left to back out of.
+    pub fn back(&mut self) -> bool {
+        self.compared.take().is_some() || self.from.take().is_some()
+    }
+}
+
+/// What a CODEOWNERS summary was loaded for: the repo, its dirty count and
+/// its last check.
+pub type OwnershipKey = (PathBuf, usize, Option<DateTime<Local>>);
+
 pub struct App {
     pub repos: Vec<Repo>,
     pub selected: usize,
@@ -39,10 +834,82 @@ pub struct App {
     pub notification: Option<(String, Instant)>,
     /// Action staged for confirmation in `ConfirmAction` mode.
     pub pending_action: Option<ActionCommand>,
+    /// Protected branch the staged action writes to, once confirmed the
+    /// first time; the second confirmation runs it.
+    pub protected_confirmed: Option<String>,
+    /// Scanned state of the staged action's repo when it was staged, so a
+    /// rescan while the dialog is open can't vouch for a changed repo.
+    pub pending_expected: Option<ExpectedState>,
+    /// Repo to re-select once the first scan lands (from a restored session).
+    pub restore_repo: Option<PathBuf>,
+    pub restore_repo_id: Option<String>,
+    /// On battery or idle: refresh less often and skip provider API calls.
+    pub power_save: bool,
+    /// Outside `[schedule]` working hours.
+    pub off_hours: bool,
+    /// Recommendations dismissed with `d`; hidden until the repo state changes.
+    pub suppressions: Suppressions,
+    /// Open partial-stash file picker (`StashPick` mode).
+    pub stash_picker: Option<StashPicker>,
+    /// Commits made by actions this session, oldest first.
+    pub commit_history: Vec<CommitRecord>,
+    /// Open create-remote wizard (`RemoteWizard` mode).
+    pub remote_wizard: Option<RemoteWizard>,
+    /// Open branch switcher (`BranchPick` mode).
+    pub branch_picker: Option<BranchPicker>,
+    /// Open cherry-pick helper (`CherryPick` mode).
+    pub cherry_picker: Option<CherryPicker>,
+    /// Open stash browser (`Stashes` mode).
+    pub stash_browser: Option<StashBrowser>,
+    /// Open diff viewer (`Diff` mode).
+    pub diff_view: Option<DiffView>,
+    /// Open commit log (`CommitLog` mode).
+    pub commit_log: Option<CommitLog>,
+    /// Which repos refresh on filesystem events; `None` without `watch_mode`.
+    pub watch_coverage: Option<WatchCoverage>,
+    /// Newest audit log entries, newest first, for the Action Log section.
+    pub action_log: Vec<AuditEntry>,
+    /// Open snapshot comparison (`TimeTravel` mode).
+    pub time_travel: Option<TimeTravel>,
+    /// Pattern being typed in `Grep` mode.
+    pub grep_input: String,
+    /// Latest cross-repo search; while set, Repos lists only its matches.
+    pub grep: Option<GrepResults>,
+    /// Whether another instance owns scanning and actions.
+    pub role: InstanceRole,
+    /// Critical conditions from the last scan; `None` before the first one.
+    pub critical_seen: Option<BTreeMap<String, String>>,
+    /// Summary bar flashes red until this instant (`[attention] flash`).
+    pub flash_until: Option<Instant>,
+    /// Config file this session was loaded from.
+    pub config_path: PathBuf,
+    /// Repos saved with `m1`..`m9`, keyed by config file, then slot.
+    pub profile_bookmarks: BTreeMap<PathBuf, BTreeMap<u8, Bookmark>>,
+    /// First key of a two-key sequence (`m` or `'`) awaiting its digit.
+    pub pending_key: Option<char>,
+    /// Help overlay filter text and scroll offset.
+    pub help_query: String,
+    pub help_scroll: usize,
+    /// Set while the first-launch tour is showing.
+    pub tour: Option<Tour>,
+    /// Whether the tour has been finished or skipped; persisted.
+    pub tour_seen: bool,
+    /// Settings section form, with edits not yet saved.
+    pub settings: SettingsForm,
+    /// Repo usage for the quick switcher; persisted.
+    pub recent: RecentRepos,
+    /// Open quick switcher (`RecentRepos` mode).
+    pub recent_picker: Option<RecentPicker>,
+    /// Next-best-action suggestions skipped with `n` on Home, this session.
+    pub hero_skipped: Vec<(PathBuf, &'static str)>,
+    /// CODEOWNERS summary for the selected repo (Repos section only).
+    pub ownership: Option<Ownership>,
+    ownership_key: Option<OwnershipKey>,
 }
 
 impl App {
     pub fn new(config: Config) -> Self {
+        let settings = SettingsForm::from_config(&config);
         Self {
             repos: Vec::new(),
             selected: 0,
@@ -60,17 +927,294 @@ impl App {
             dashboard: DashboardSnapshot::default(),
             notification: None,
             pending_action: None,
+            pending_expected: None,
+            protected_confirmed: None,
+            restore_repo: None,
+            restore_repo_id: None,
+            power_save: false,
+            off_hours: false,
+            suppressions: Suppressions::default(),
+            stash_picker: None,
+            commit_history: Vec::new(),
+            remote_wizard: None,
+            branch_picker: None,
+            cherry_picker: None,
+            stash_browser: None,
+            diff_view: None,
+            commit_log: None,
+            watch_coverage: None,
+            action_log: Vec::new(),
+            time_travel: None,
+            grep_input: String::new(),
+            grep: None,
+            role: InstanceRole::Primary,
+            critical_seen: None,
+            flash_until: None,
+            config_path: crate::config::resolve_config_path(None),
+            profile_bookmarks: BTreeMap::new(),
+            pending_key: None,
+            help_query: String::new(),
+            help_scroll: 0,
+            tour: None,
+            tour_seen: false,
+            settings,
+            recent: RecentRepos::default(),
+            recent_picker: None,
+            hero_skipped: Vec::new(),
+            ownership: None,
+            ownership_key: None,
+        }
+    }
+
+    pub fn collect_mode(&self) -> CollectMode {
+        if self.power_save || self.off_hours {
+            CollectMode::PowerSave
+        } else {
+            CollectMode::Full
+        }
+    }
+
+    /// Auto-refresh interval, stretched while power-saving.
+    pub fn refresh_interval(&self) -> Duration {
+        let multiplier = if self.power_save {
+            self.config.power.refresh_multiplier.max(1)
+        } else {
+            1
+        };
+        Duration::from_secs(self.config.refresh_interval_secs.saturating_mul(multiplier))
+    }
+
+    /// Apply a saved session. The repo selection is resolved after the first scan.
+    pub fn restore_session(&mut self, state: SessionState) {
+        self.section = state.section;
+        self.selected = state.selected;
+        self.filter_text = state.filter_text;
+        self.group_by_dir = state.group_by_dir;
+        self.agent_focus_mode = state.agent_focus_mode;
+        self.restore_repo = state.selected_repo;
+        self.restore_repo_id = state.selected_repo_id;
+        self.profile_bookmarks = state.profile_bookmarks;
+        self.tour_seen = state.tour_seen;
+        self.recent = state.recent;
+    }
+
+    /// Capture the UI state worth restoring on next launch.
+    pub fn session_state(&self) -> SessionState {
+        SessionState {
+            section: self.section,
+            selected: self.selected,
+            selected_repo: self.selected_repo().map(|r| r.path.clone()),
+            selected_repo_id: self.selected_repo().and_then(|r| r.identity.clone()),
+            filter_text: self.filter_text.clone(),
+            group_by_dir: self.group_by_dir,
+            agent_focus_mode: self.agent_focus_mode,
+            profile_bookmarks: self.profile_bookmarks.clone(),
+            tour_seen: self.tour_seen,
+            recent: self.recent.clone(),
+        }
+    }
+
+    pub fn start_tour(&mut self) {
+        self.tour = Some(Tour::new(&self.config));
+        self.mode = AppMode::Tour;
+    }
+
+    /// Step the tour forward or back; stepping past the last stop ends it.
+    pub fn tour_step(&mut self, forward: bool) {
+        let Some(tour) = self.tour.as_mut() else {
+            return;
+        };
+        let index = tour.step.index();
+        let next = if forward {
+            index + 1
+        } else {
+            index.saturating_sub(1)
+        };
+        match TourStep::ALL.get(next) {
+            Some(step) => tour.show(*step),
+            None => self.end_tour(),
+        }
+    }
+
+    /// Finish or skip the tour; it is not shown again.
+    pub fn end_tour(&mut self) {
+        self.tour = None;
+        self.tour_seen = true;
+        self.mode = AppMode::Normal;
+    }
+
+    /// Bookmarks of the profile in use: each config file keeps its own.
+    fn bookmarks(&self) -> Option<&BTreeMap<u8, Bookmark>> {
+        self.profile_bookmarks.get(&self.config_path)
+    }
+
+    /// Bookmark the selected repo in `slot` (1-9).
+    pub fn set_bookmark(&mut self, slot: u8) -> String {
+        let Some(repo) = self.selected_repo() else {
+            return t("notify-select-repo-to-bookmark");
+        };
+        let msg = tr(
+            "notify-bookmarked",
+            &[("repo", &repo.name), ("slot", &slot)],
+        );
+        let bookmark = Bookmark {
+            path: repo.path.clone(),
+            identity: repo.identity.clone(),
+        };
+        self.profile_bookmarks
+            .entry(self.config_path.clone())
+            .or_default()
+            .insert(slot, bookmark);
+        msg
+    }
+
+    /// Select the repo bookmarked in `slot`, switching to Repos and clearing
+    /// the filter or focus mode if they hide it.
+    pub fn jump_to_bookmark(&mut self, slot: u8) -> String {
+        let Some(bookmark) = self.bookmarks().and_then(|b| b.get(&slot)).cloned() else {
+            return tr("notify-no-bookmark", &[("slot", &slot)]);
+        };
+        let Some(repo) = self
+            .repos
+            .iter()
+            .find(|r| r.path == bookmark.path)
+            .or_else(|| {
+                self.repos
+                    .iter()
+                    .find(|r| bookmark.identity.is_some() && r.identity == bookmark.identity)
+            })
+        else {
+            return tr(
+                "notify-bookmark-missing",
+                &[("slot", &slot), ("path", &bookmark.path.display())],
+            );
+        };
+        let path = repo.path.clone();
+        let name = repo.name.clone();
+        if self.focus_repo(&path) {
+            format!("'{} {}", slot, name)
+        } else {
+            tr("notify-hidden-clean", &[("repo", &name)])
+        }
+    }
+
+    /// Select the repo at `path` in Repos, clearing the filter or focus mode
+    /// if they hide it. False if it stays hidden (clean, `show_clean` off).
+    fn focus_repo(&mut self, path: &Path) -> bool {
+        self.section = DashboardSection::Repos;
+        let position = |app: &Self| app.filtered_repos().iter().position(|r| r.path == path);
+        if position(self).is_none() {
+            self.filter_text.clear();
+            self.agent_focus_mode = false;
+            self.grep = None;
+        }
+        match position(self) {
+            Some(idx) => {
+                self.selected = idx;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Count a use of the repo at `path` for the quick switcher.
+    pub fn record_use(&mut self, path: &Path) {
+        if let Some(repo) = self.repos.iter().find(|r| r.path == path) {
+            self.recent.record(repo, Local::now().timestamp());
         }
     }
 
+    /// Open the quick switcher over the scanned repos that have been used,
+    /// found by path or else by identity.
+    pub fn open_recent(&mut self) {
+        let mut entries: Vec<(PathBuf, String)> = Vec::new();
+        for usage in self.recent.ranked(Local::now().timestamp()) {
+            let repo = self
+                .repos
+                .iter()
+                .find(|r| r.path == usage.path)
+                .or_else(|| {
+                    self.repos
+                        .iter()
+                        .find(|r| usage.identity.is_some() && r.identity == usage.identity)
+                });
+            if let Some(repo) = repo {
+                if !entries.iter().any(|(path, _)| *path == repo.path) {
+                    entries.push((repo.path.clone(), repo.name.clone()));
+                }
+            }
+        }
+        if entries.is_empty() {
+            self.notify(t("notify-no-recent"));
+            return;
+        }
+        // The first entry is usually the repo already selected.
+        let current = self.selected_repo().map(|r| r.path.clone());
+        let cursor = usize::from(entries.len() > 1 && current.as_ref() == Some(&entries[0].0));
+        self.recent_picker = Some(RecentPicker {
+            entries,
+            cursor,
+            query: String::new(),
+        });
+        self.mode = AppMode::RecentRepos;
+    }
+
+    /// Close the quick switcher and select the highlighted repo.
+    pub fn jump_to_recent(&mut self) -> String {
+        let picker = self.recent_picker.take();
+        self.mode = AppMode::Normal;
+        let Some((path, name)) = picker
+            .as_ref()
+            .and_then(|p| p.matches().get(p.cursor).map(|e| (*e).clone()))
+        else {
+            return t("notify-no-matching-repo");
+        };
+        if !self.focus_repo(&path) {
+            return tr("notify-hidden-clean", &[("repo", &name)]);
+        }
+        self.record_use(&path);
+        name
+    }
+
+    /// Re-select the restored repo once repos are available: by path first,
+    /// then by identity in case the repo was moved or renamed.
+    pub fn apply_restored_selection(&mut self) {
+        let path = self.restore_repo.take();
+        let id = self.restore_repo_id.take();
+        if self.section != DashboardSection::Repos || (path.is_none() && id.is_none()) {
+            return;
+        }
+        let repos = self.filtered_repos();
+        let idx = repos
+            .iter()
+            .position(|r| path.as_ref() == Some(&r.path))
+            .or_else(|| repos.iter().position(|r| id.is_some() && r.identity == id));
+        if let Some(idx) = idx {
+            self.selected = idx;
+        }
+    }
+
+    /// Non-idle recommendation that has not been dismissed.
+    pub fn wants_attention(&self, repo: &Repo) -> bool {
+        let rec = agent::recommend(repo);
+        rec.priority != agent::ActionPriority::Idle && !self.suppressions.is_dismissed(repo, &rec)
+    }
+
     /// Returns repos matching the current filter and `show_clean` setting,
     /// sorted by (parent dir, urgency, name) when grouping is active.
     pub fn filtered_repos(&self) -> Vec<&Repo> {
+        let grep_hits = self.grep.as_ref().and_then(|g| g.hits.as_ref());
         let mut repos: Vec<&Repo> = self
             .repos
             .iter()
-            .filter(|r| self.config.show_clean || r.needs_attention())
-            .filter(|r| !self.agent_focus_mode || agent::needs_attention(r))
+            .filter(|r| match grep_hits {
+                // Search results list clean repos too.
+                Some(hits) => hits.iter().any(|h| h.path == r.path),
+                None => {
+                    (self.config.show_clean || r.needs_attention())
+                        && (!self.agent_focus_mode || self.wants_attention(r))
+                }
+            })
             .filter(|r| {
                 if self.filter_text.is_empty() {
                     return true;
@@ -113,12 +1257,52 @@ impl App {
             DashboardSection::Worktrees => self.dashboard.worktrees.len(),
             DashboardSection::Processes => self.dashboard.processes.len(),
             DashboardSection::Dependencies => self.dashboard.dependencies.len(),
+            DashboardSection::LocalDeps => self.dashboard.local_deps.len(),
             DashboardSection::EnvAudit => self.dashboard.env_audit.len(),
+            DashboardSection::Licenses => self.dashboard.licenses.len(),
             DashboardSection::McpHealth => self.dashboard.mcp_servers.len(),
             DashboardSection::AiCosts => self.dashboard.providers.len(),
+            DashboardSection::PullRequests => self.dashboard.pull_requests.len(),
+            DashboardSection::AiClients => self.dashboard.ai_clients.len(),
+            DashboardSection::Stats => self
+                .dashboard
+                .scan_stats
+                .as_ref()
+                .map_or(0, |s| s.slowest.len()),
+            DashboardSection::ActionLog => self.action_log.len(),
+            DashboardSection::Settings => FIELDS.len(),
         }
     }
 
+    /// Start searching for the typed pattern; `None` when it is blank.
+    pub fn start_grep(&mut self) -> Option<String> {
+        self.mode = AppMode::Normal;
+        let pattern = self.grep_input.trim().to_string();
+        if pattern.is_empty() {
+            return None;
+        }
+        self.grep = Some(GrepResults {
+            pattern: pattern.clone(),
+            hits: None,
+        });
+        self.section = DashboardSection::Repos;
+        Some(pattern)
+    }
+
+    /// Store finished results; `None` if a newer search replaced this one.
+    pub fn finish_grep(&mut self, pattern: &str, hits: Vec<RepoHit>) -> Option<String> {
+        let grep = self.grep.as_mut().filter(|g| g.pattern == pattern)?;
+        let msg = format!(
+            "{:?} found in {} of {} repos",
+            pattern,
+            hits.len(),
+            self.repos.len()
+        );
+        grep.hits = Some(hits);
+        self.selected = 0;
+        Some(msg)
+    }
+
     pub fn move_selection(&mut self, delta: i32) {
         let len = self.active_row_count();
         if len == 0 {
@@ -136,10 +1320,223 @@ impl App {
         }
     }
 
+    /// The Home hero card: the most urgent recommendation that is not idle,
+    /// dismissed or skipped, with the action `Enter` reviews if it has one.
+    pub fn next_best_action(
+        &self,
+    ) -> Option<(&Repo, agent::Recommendation, Option<ActionCommand>)> {
+        let (repo, rec) = agent::sorted_recommendations(&self.repos)
+            .into_iter()
+            .find(|(repo, rec)| {
+                rec.priority != agent::ActionPriority::Idle
+                    && !self.suppressions.is_dismissed(repo, rec)
+                    && !self
+                        .hero_skipped
+                        .iter()
+                        .any(|(path, action)| *path == repo.path && *action == rec.short_action)
+            })?;
+        let action = self.repo_action(repo);
+        Some((repo, rec, action))
+    }
+
+    /// Skip the hero suggestion for this session; after the last one the
+    /// skipped ones come round again. Returns the notification text.
+    pub fn skip_next_best_action(&mut self) -> String {
+        let Some((repo, rec, _)) = self.next_best_action() else {
+            return t("notify-nothing-to-skip");
+        };
+        let skipped = (repo.path.clone(), rec.short_action);
+        let name = repo.name.clone();
+        self.hero_skipped.push(skipped);
+        if self.next_best_action().is_none() {
+            self.hero_skipped.clear();
+            return tr("notify-skipped-wrapped", &[("repo", &name)]);
+        }
+        tr("notify-skipped", &[("repo", &name)])
+    }
+
+    /// Dismiss the selected repo's recommendation, or restore it if already
+    /// dismissed. Returns the notification text, or `None` if nothing to do.
+    pub fn toggle_dismissed(&mut self) -> Option<String> {
+        let repo = self.selected_repo()?.clone();
+        if self.suppressions.restore(&repo) {
+            return Some(tr("notify-restored", &[("repo", &repo.name)]));
+        }
+        let rec = agent::recommend(&repo);
+        if rec.priority == agent::ActionPriority::Idle {
+            return None;
+        }
+        self.suppressions.dismiss(&repo, &rec);
+        Some(tr(
+            "notify-dismissed",
+            &[("action", &rec.short_action), ("repo", &repo.name)],
+        ))
+    }
+
+    pub fn record_commit(&mut self, commit: CommitRecord) {
+        self.commit_history.push(commit);
+        if self.commit_history.len() > COMMIT_HISTORY_LIMIT {
+            self.commit_history.remove(0);
+        }
+    }
+
+    /// Stage a soft reset of the selected repo's last action commit, provided
+    /// it is still HEAD and unpushed. Returns the notification text.
+    pub fn stage_undo_commit(&mut self) -> String {
+        let Some(repo) = self.selected_repo() else {
+            return t("notify-no-repo-selected");
+        };
+        let repo_path = repo.path.to_string_lossy().to_string();
+        let Some(commit) = self
+            .commit_history
+            .iter()
+            .rev()
+            .find(|c| c.repo_path == repo_path)
+            .cloned()
+        else {
+            return t("notify-no-undo");
+        };
+        if !git::is_unpushed_head(&repo.path, &commit.sha) {
+            return tr("notify-undo-not-head", &[("commit", &commit.short_sha())]);
+        }
+        self.stage_action_confirmation(ActionCommand::new(
+            format!("undo commit {} ({})", commit.short_sha(), commit.shortstat),
+            ActionKind::GitUndoCommit {
+                repo_path,
+                commit: commit.sha,
+            },
+        ));
+        tr("notify-review-undo", &[("subject", &commit.subject)])
+    }
+
+    /// The CODEOWNERS load due when the selected repo, its dirty count, or
+    /// its last check changed; `None` while the cached summary still holds.
+    /// The caller runs [`Ownership::load`] off the UI loop and hands the
+    /// answer to [`App::apply_ownership`].
+    pub fn refresh_ownership(&mut self) -> Option<OwnershipKey> {
+        let key = if self.section == DashboardSection::Repos {
+            self.selected_repo()
+                .map(|r| (r.path.clone(), r.status.uncommitted_count, r.last_checked))
+        } else {
+            None
+        };
+        if key == self.ownership_key {
+            return None;
+        }
+        // Another repo's owners would be wrong; the same repo's stay up
+        // until the new answer lands.
+        let same_repo = matches!(
+            (&key, &self.ownership_key),
+            (Some((new, ..)), Some((old, ..))) if new == old
+        );
+        if !same_repo {
+            self.ownership = None;
+        }
+        self.ownership_key = key.clone();
+        key
+    }
+
+    /// Take a finished CODEOWNERS load, unless the selection moved on since.
+    pub fn apply_ownership(&mut self, key: OwnershipKey, ownership: Option<Ownership>) {
+        if self.ownership_key.as_ref() == Some(&key) {
+            self.ownership = ownership;
+        }
+    }
+
+    /// Swap in a freshly re-checked repo, keeping the list in scan order
+    /// (most urgent first) and the Repos selection on the same repo. `false`
+    /// if the repo is not listed, so only a full scan can add it.
+    pub fn replace_repo(&mut self, repo: Repo) -> bool {
+        let selected = (self.section == DashboardSection::Repos)
+            .then(|| self.selected_repo().map(|r| r.path.clone()))
+            .flatten();
+        let Some(slot) = self.repos.iter_mut().find(|r| r.path == repo.path) else {
+            return false;
+        };
+        *slot = repo;
+        self.repos.sort_by(|a, b| {
+            b.urgency()
+                .cmp(&a.urgency())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        crate::dashboard::refresh_repo_rows(&mut self.dashboard, &self.repos);
+        if let Some(idx) =
+            selected.and_then(|path| self.filtered_repos().iter().position(|r| r.path == path))
+        {
+            self.selected = idx;
+        }
+        self.clamp_selection();
+        true
+    }
+
     pub fn selected_repo(&self) -> Option<&Repo> {
         self.filtered_repos().into_iter().nth(self.selected)
     }
 
+    /// The ticket linked from the repo's current branch, if any.
+    pub fn ticket_for(&self, repo: &Repo) -> Option<&RepoTicket> {
+        let path = repo.path.to_string_lossy();
+        self.dashboard
+            .tickets
+            .iter()
+            .find(|ticket| ticket.repo_path == path && ticket.branch == repo.status.branch)
+    }
+
+    /// The repo's recommended action; none while it needs a remote behind a
+    /// VPN host that is down.
+    fn repo_action(&self, repo: &Repo) -> Option<ActionCommand> {
+        let rec = agent::recommend(repo);
+        let action = agent::recommended_action_kind(repo)?;
+        Some(ActionCommand::new(rec.action, action))
+    }
+
+    /// The selected alert (Home) or repo recommendation (Repos) as a task
+    /// for the `[tasks]` backend, noting the repo path and suggested command.
+    pub fn selected_task(&self) -> Option<ActionCommand> {
+        let (title, notes) = match self.section {
+            DashboardSection::Home => {
+                let alert = self.dashboard.alerts.get(self.selected)?;
+                let repo = alert
+                    .repo
+                    .as_ref()
+                    .and_then(|name| self.repos.iter().find(|r| &r.name == name));
+                let notes = repo
+                    .map(|r| r.path.display().to_string())
+                    .into_iter()
+                    .chain((!alert.detail.is_empty()).then(|| alert.detail.clone()))
+                    .chain(alert.action.as_ref().map(|a| a.command.clone()))
+                    .collect();
+                (alert.title.clone(), notes)
+            }
+            DashboardSection::Repos => {
+                let repo = self.selected_repo()?;
+                let rec = agent::recommend(repo);
+                if rec.priority == agent::ActionPriority::Idle {
+                    return None;
+                }
+                (
+                    format!("{}: {}", repo.name, rec.action),
+                    vec![repo.path.display().to_string(), rec.reason, rec.command],
+                )
+            }
+            _ => return None,
+        };
+        let backend = self.config.tasks.backend;
+        Some(ActionCommand::new(
+            format!("file task in {}", backend.label()),
+            ActionKind::CreateTask {
+                backend,
+                title,
+                notes,
+                file: self
+                    .config
+                    .tasks
+                    .target_file()
+                    .map(|p| p.display().to_string()),
+            },
+        ))
+    }
+
     pub fn selected_action(&self) -> Option<ActionCommand> {
         match self.section {
             DashboardSection::Home => self
@@ -147,11 +1544,7 @@ impl App {
                 .alerts
                 .get(self.selected)
                 .and_then(|a| a.action.clone()),
-            DashboardSection::Repos => self.selected_repo().and_then(|repo| {
-                let rec = agent::recommend(repo);
-                agent::recommended_action_kind(repo)
-                    .map(|action| ActionCommand::new(rec.action, action))
-            }),
+            DashboardSection::Repos => self.selected_repo().and_then(|repo| self.repo_action(repo)),
             DashboardSection::Worktrees => self
                 .dashboard
                 .worktrees
@@ -167,17 +1560,104 @@ impl App {
                 .dependencies
                 .get(self.selected)
                 .and_then(|r| r.action.clone()),
+            // Whatever the dependency repo itself needs (commit, push, ...).
+            DashboardSection::LocalDeps => {
+                let dep = self.dashboard.local_deps.get(self.selected)?;
+                let repo = self
+                    .repos
+                    .iter()
+                    .find(|r| r.path.to_string_lossy() == dep.dependency_path)?;
+                self.repo_action(repo)
+            }
             DashboardSection::EnvAudit => self
                 .dashboard
                 .env_audit
                 .get(self.selected)
                 .and_then(|r| r.action.clone()),
+            DashboardSection::Licenses => None,
             DashboardSection::McpHealth => self
                 .dashboard
                 .mcp_servers
                 .get(self.selected)
-                .and_then(|r| r.action.clone()),
-            DashboardSection::AiCosts => None,
+                .and_then(|r| r.action.clone())
+                .or_else(|| self.empty_state_action()),
+            DashboardSection::AiCosts => self.empty_state_action(),
+            DashboardSection::AiClients => {
+                let client = self.dashboard.ai_clients.get(self.selected)?;
+                Some(ActionCommand::new(
+                    "open in editor",
+                    ActionKind::OpenInEditor {
+                        path: client.path.clone(),
+                        editor: self.editor(),
+                    },
+                ))
+            }
+            DashboardSection::PullRequests
+            | DashboardSection::Stats
+            | DashboardSection::ActionLog
+            | DashboardSection::Settings => None,
+        }
+    }
+
+    /// Settings to run `action` with; under `[actions] verify_state` they
+    /// carry the scanned status of the repo it targets.
+    pub fn action_settings(&self, action: &ActionKind) -> ActionSettings {
+        let mut settings = ActionSettings::from_config(&self.config);
+        settings.expected = self.expected_state(action);
+        settings
+    }
+
+    /// The scanned state `action` is based on, under `[actions] verify_state`.
+    fn expected_state(&self, action: &ActionKind) -> Option<ExpectedState> {
+        if !self.config.actions.verify_state {
+            return None;
+        }
+        action
+            .affected_repo_path()
+            .and_then(|path| self.repos.iter().find(|r| r.path.to_string_lossy() == path))
+            .map(|repo| ExpectedState::from(&repo.status))
+    }
+
+    /// `config.editor`, then `$EDITOR`, then VS Code.
+    pub fn editor(&self) -> String {
+        self.config
+            .editor
+            .clone()
+            .or_else(|| std::env::var("EDITOR").ok())
+            .unwrap_or_else(|| "code".to_string())
+    }
+
+    /// Whether no AI provider has usable data (all disabled or unconfigured).
+    pub fn ai_costs_unconfigured(&self) -> bool {
+        self.dashboard.providers.iter().all(|p| !p.configured)
+    }
+
+    /// Setup action offered by an empty section, run with `x`.
+    pub fn empty_state_action(&self) -> Option<ActionCommand> {
+        if self.is_scanning {
+            return None;
+        }
+        match self.section {
+            DashboardSection::McpHealth if self.dashboard.mcp_servers.is_empty() => {
+                let path = dirs::home_dir()?
+                    .join(".config")
+                    .join("agentpulse")
+                    .join("mcp.json");
+                Some(ActionCommand::new(
+                    "create ~/.config/agentpulse/mcp.json",
+                    ActionKind::CreateMcpConfig {
+                        path: path.to_string_lossy().to_string(),
+                    },
+                ))
+            }
+            DashboardSection::AiCosts if self.ai_costs_unconfigured() => Some(ActionCommand::new(
+                "open config.toml",
+                ActionKind::OpenInEditor {
+                    path: self.config_path.to_string_lossy().to_string(),
+                    editor: self.editor(),
+                },
+            )),
+            _ => None,
         }
     }
 
@@ -203,6 +1683,30 @@ impl App {
         self.clamp_selection();
     }
 
+    /// Enter on a Settings row: flip a toggle or start typing a value.
+    pub fn activate_setting(&mut self) {
+        self.settings.activate(self.selected);
+        if self.settings.input.is_some() {
+            self.notification = None;
+            self.mode = AppMode::EditSetting;
+        }
+    }
+
+    /// Save the edited settings to the config file and use the result.
+    /// Returns how many keys were written.
+    pub fn save_settings(&mut self) -> anyhow::Result<usize> {
+        let count = self.settings.modified_count();
+        self.config = self.settings.save(&self.config_path)?;
+        Ok(count)
+    }
+
+    /// Drop unsaved edits and refill the form from the config file.
+    pub fn reload_settings(&mut self) -> anyhow::Result<()> {
+        self.config = crate::config::load_config(Some(&self.config_path))?;
+        self.settings = SettingsForm::from_config(&self.config);
+        Ok(())
+    }
+
     /// Set a transient notification message (shown in the status bar for 4 s).
     pub fn notify(&mut self, msg: impl Into<String>) {
         self.notification = Some((msg.into(), Instant::now()));
@@ -217,15 +1721,576 @@ impl App {
         }
     }
 
+    /// Critical recommendations and alerts, keyed so each condition counts
+    /// once, with a short label for notifications.
+    fn critical_conditions(&self, repos: &[Repo]) -> BTreeMap<String, String> {
+        let mut found = BTreeMap::new();
+        for repo in repos {
+            let rec = agent::recommend(repo);
+            if rec.priority == agent::ActionPriority::Critical
+                && !self.suppressions.is_dismissed(repo, &rec)
+            {
+                found.insert(
+                    format!("{}|{}", repo.path.display(), rec.short_action),
+                    format!("{}: {}", repo.name, rec.action),
+                );
+            }
+        }
+        for alert in &self.dashboard.alerts {
+            if alert.severity == "critical" {
+                found.insert(
+                    format!(
+                        "alert|{}|{}",
+                        alert.title,
+                        alert.repo.as_deref().unwrap_or("")
+                    ),
+                    alert.title.clone(),
+                );
+            }
+        }
+        found
+    }
+
+    /// Record the critical conditions of `repos` and the dashboard alerts,
+    /// both from the same scan, and return those that are new since the
+    /// previous call. The first scan only sets the baseline.
+    pub fn take_new_critical(&mut self, repos: &[Repo]) -> Vec<String> {
+        let current = self.critical_conditions(repos);
+        let fresh = match &self.critical_seen {
+            Some(seen) => current
+                .iter()
+                .filter(|(key, _)| !seen.contains_key(*key))
+                .map(|(_, label)| label.clone())
+                .collect(),
+            None => Vec::new(),
+        };
+        self.critical_seen = Some(current);
+        fresh
+    }
+
+    pub fn is_flashing(&self) -> bool {
+        self.flash_until.is_some_and(|t| Instant::now() < t)
+    }
+
     pub fn stage_action_confirmation(&mut self, action: ActionCommand) {
+        if self.role.is_read_only() {
+            self.notify(self.role.read_only_notice());
+            return;
+        }
+        self.pending_expected = self.expected_state(&action.action);
         self.pending_action = Some(action);
+        self.protected_confirmed = None;
         self.mode = AppMode::ConfirmAction;
     }
 
+    /// The protected branch `action` would commit or push to, going by the
+    /// last scan of its repo.
+    pub fn protected_target(&self, action: &ActionKind) -> Option<String> {
+        if !actions::writes_branch(action) {
+            return None;
+        }
+        let branch = match action {
+            ActionKind::GitPushSetUpstream { branch, .. } => branch.clone(),
+            _ => {
+                let path = action.affected_repo_path()?;
+                let repo = self
+                    .repos
+                    .iter()
+                    .find(|r| r.path.to_string_lossy() == path)?;
+                repo.status.branch.clone()
+            }
+        };
+        branch_matches(&self.config.protected_branches(), &branch).then_some(branch)
+    }
+
+    /// Confirm the staged action and take it with the settings to run it
+    /// with. A write to a protected branch needs confirming twice: the first
+    /// call only records it and returns `None`.
+    pub fn confirm_pending_action(&mut self) -> Option<(ActionCommand, ActionSettings)> {
+        let staged = self.pending_action.as_ref()?;
+        let protected = self.protected_target(&staged.action);
+        if protected.is_some() && self.protected_confirmed.is_none() {
+            self.protected_confirmed = protected;
+            return None;
+        }
+        let action = self.pending_action.take()?;
+        let mut settings = ActionSettings::from_config(&self.config);
+        settings.expected = self.pending_expected.take();
+        settings.allow_protected = self.protected_confirmed.take();
+        self.mode = AppMode::Normal;
+        Some((action, settings))
+    }
+
     pub fn clear_pending_action(&mut self) {
         self.pending_action = None;
+        self.pending_expected = None;
+        self.protected_confirmed = None;
         if self.mode == AppMode::ConfirmAction {
             self.mode = AppMode::Normal;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_new_critical_conditions_are_reported() {
+        let mut app = App::new(Config::default());
+        let mut repo = Repo::new(PathBuf::from("/tmp/agentpulse_attention"));
+        repo.status.is_detached = true;
+        let mut scanned = vec![repo];
+
+        // The first scan is the baseline.
+        assert!(app.take_new_critical(&scanned).is_empty());
+
+        scanned[0].status.is_detached = false;
+        assert!(app.take_new_critical(&scanned).is_empty());
+
+        // Judged by the scan passed in, even before `app.repos` catches up.
+        scanned[0].status.is_detached = true;
+        let fresh = app.take_new_critical(&scanned);
+        assert!(app.repos.is_empty());
+        assert_eq!(fresh.len(), 1);
+        assert!(fresh[0].starts_with("agentpulse_attention"));
+        assert!(app.take_new_critical(&scanned).is_empty());
+    }
+
+    #[test]
+    fn ownership_for_a_stale_selection_is_dropped() {
+        let mut app = App::new(Config::default());
+        app.section = DashboardSection::Repos;
+        app.repos = vec![
+            Repo::new(PathBuf::from("/work/api")),
+            Repo::new(PathBuf::from("/work/web")),
+        ];
+        app.selected = 0;
+        let owners = Ownership {
+            repo_owners: vec!["@acme".to_string()],
+            dirty_owners: BTreeMap::new(),
+            unowned_dirty: 0,
+        };
+
+        let api = app.refresh_ownership().unwrap();
+        assert!(app.refresh_ownership().is_none());
+        app.selected = 1;
+        let web = app.refresh_ownership().unwrap();
+        app.apply_ownership(api, Some(owners.clone()));
+        assert_eq!(app.ownership, None);
+        app.apply_ownership(web, Some(owners.clone()));
+        assert_eq!(app.ownership, Some(owners));
+    }
+
+    #[test]
+    fn replaced_repo_keeps_its_selection_and_updates_rows() {
+        let mut app = App::new(Config::default());
+        app.section = DashboardSection::Repos;
+        let mut dirty = Repo::new(PathBuf::from("/work/api"));
+        dirty.status.uncommitted_count = 2;
+        app.repos = vec![dirty.clone(), Repo::new(PathBuf::from("/work/web"))];
+        app.selected = 0;
+
+        let mut clean = dirty;
+        clean.status.uncommitted_count = 0;
+        assert!(app.replace_repo(clean));
+        assert_eq!(app.repos[0].name, "api");
+        assert_eq!(app.selected_repo().unwrap().name, "api");
+        assert_eq!(app.dashboard.overview.dirty_repos, 0);
+        assert_eq!(app.dashboard.repos.len(), 2);
+
+        let mut web = Repo::new(PathBuf::from("/work/web"));
+        web.status.unpushed_count = 1;
+        assert!(app.replace_repo(web));
+        assert_eq!(app.repos[0].name, "web");
+        assert_eq!(app.selected_repo().unwrap().name, "api");
+        assert!(!app.replace_repo(Repo::new(PathBuf::from("/work/new"))));
+    }
+
+    #[test]
+    fn commit_to_protected_branch_needs_a_second_confirmation() {
+        let mut config = Config::default();
+        config.actions.protected_branches = vec!["main".to_string(), "release/*".to_string()];
+        let mut app = App::new(config);
+        let mut repo = Repo::new(PathBuf::from("/work/api"));
+        repo.status.branch = "release/2.0".to_string();
+        app.repos = vec![repo];
+        let commit = ActionCommand::new(
+            "commit",
+            ActionKind::GitAddCommit {
+                repo_path: "/work/api".to_string(),
+                message: "wip".to_string(),
+            },
+        );
+
+        app.stage_action_confirmation(commit.clone());
+        assert!(app.confirm_pending_action().is_none());
+        assert_eq!(app.protected_confirmed.as_deref(), Some("release/2.0"));
+        assert_eq!(app.mode, AppMode::ConfirmAction);
+        let (_, settings) = app.confirm_pending_action().unwrap();
+        assert_eq!(settings.allow_protected.as_deref(), Some("release/2.0"));
+        assert_eq!(app.mode, AppMode::Normal);
+
+        // Other branches, and actions that do not write, go through at once.
+        app.repos[0].status.branch = "feature".to_string();
+        app.stage_action_confirmation(commit);
+        let (_, settings) = app.confirm_pending_action().unwrap();
+        assert!(settings.allow_protected.is_none());
+        app.repos[0].status.branch = "main".to_string();
+        app.stage_action_confirmation(ActionCommand::new(
+            "fetch",
+            ActionKind::GitFetch {
+                repo_path: "/work/api".to_string(),
+            },
+        ));
+        assert!(app.confirm_pending_action().is_some());
+    }
+
+    #[test]
+    fn staged_actions_expect_the_state_they_were_staged_on() {
+        let mut config = Config::default();
+        config.actions.verify_state = true;
+        let mut app = App::new(config);
+        let mut repo = Repo::new(PathBuf::from("/work/api"));
+        repo.status.uncommitted_count = 2;
+        repo.status.head_sha = Some("1111111".to_string());
+        app.repos = vec![repo];
+        app.stage_action_confirmation(ActionCommand::new(
+            "commit",
+            ActionKind::GitAddCommit {
+                repo_path: "/work/api".to_string(),
+                message: "wip".to_string(),
+            },
+        ));
+
+        // A rescan while the dialog is open must not move the baseline.
+        app.repos[0].status.uncommitted_count = 5;
+        app.repos[0].status.head_sha = Some("2222222".to_string());
+        let (_, settings) = app.confirm_pending_action().unwrap();
+        let expected = settings.expected.unwrap();
+        assert_eq!(expected.dirty, 2);
+        assert_eq!(expected.head.as_deref(), Some("1111111"));
+        assert!(app.pending_expected.is_none());
+    }
+
+    #[test]
+    fn empty_sections_offer_setup_actions() {
+        let mut app = App::new(Config::default());
+        app.section = DashboardSection::McpHealth;
+        // Nothing is offered while the first scan is still running.
+        assert!(app.selected_action().is_none());
+
+        app.is_scanning = false;
+        let action = app.selected_action().unwrap();
+        assert!(
+            matches!(action.action, ActionKind::CreateMcpConfig { ref path } if path.ends_with("mcp.json"))
+        );
+
+        app.section = DashboardSection::AiCosts;
+        assert!(matches!(
+            app.selected_action().unwrap().action,
+            ActionKind::OpenInEditor { .. }
+        ));
+
+        app.section = DashboardSection::Repos;
+        assert!(app.empty_state_action().is_none());
+    }
+
+    #[test]
+    fn bookmark_jump_reveals_a_filtered_repo() {
+        let mut app = App::new(Config::default());
+        app.repos = vec![
+            Repo::new(PathBuf::from("/tmp/agentpulse_bm/alpha")),
+            Repo::new(PathBuf::from("/tmp/agentpulse_bm/beta")),
+        ];
+        app.section = DashboardSection::Repos;
+        app.selected = 1;
+        assert!(app.set_bookmark(3).contains("beta"));
+
+        app.section = DashboardSection::Home;
+        app.filter_text = "alpha".to_string();
+        app.jump_to_bookmark(3);
+        assert_eq!(app.section, DashboardSection::Repos);
+        assert!(app.filter_text.is_empty());
+        assert_eq!(app.selected_repo().unwrap().name, "beta");
+
+        assert!(app.jump_to_bookmark(4).starts_with("No bookmark"));
+        assert_eq!(app.bookmarks().map(BTreeMap::len), Some(1));
+
+        // Another profile starts without them.
+        app.config_path = PathBuf::from("/tmp/agentpulse_bm/work.toml");
+        assert!(app.jump_to_bookmark(3).starts_with("No bookmark"));
+        assert_eq!(app.session_state().profile_bookmarks.len(), 1);
+    }
+
+    #[test]
+    fn quick_switch_lists_used_repos_and_jumps() {
+        let mut app = App::new(Config::default());
+        app.repos = vec![
+            Repo::new(PathBuf::from("/tmp/agentpulse_recent/alpha")),
+            Repo::new(PathBuf::from("/tmp/agentpulse_recent/beta")),
+            Repo::new(PathBuf::from("/tmp/agentpulse_recent/gamma")),
+        ];
+        app.open_recent();
+        assert_eq!(app.mode, AppMode::Normal);
+
+        let beta = PathBuf::from("/tmp/agentpulse_recent/beta");
+        let gamma = PathBuf::from("/tmp/agentpulse_recent/gamma");
+        app.record_use(&gamma);
+        app.record_use(&beta);
+        app.record_use(&beta);
+        app.open_recent();
+        assert_eq!(app.mode, AppMode::RecentRepos);
+        let picker = app.recent_picker.as_mut().unwrap();
+        let names: Vec<_> = picker.matches().iter().map(|(_, n)| n.clone()).collect();
+        assert_eq!(names, ["beta", "gamma"]);
+
+        picker.type_char('g');
+        assert_eq!(picker.matches().len(), 1);
+        app.filter_text = "alpha".to_string();
+        assert_eq!(app.jump_to_recent(), "gamma");
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.section, DashboardSection::Repos);
+        assert_eq!(app.selected_repo().unwrap().path, gamma);
+        assert_eq!(app.session_state().recent.ranked(0).len(), 2);
+    }
+
+    #[test]
+    fn cherry_picks_marked_commits_oldest_first() {
+        let mut repo = Repo::new(PathBuf::from("/tmp/agentpulse_cherry"));
+        repo.status.branch = "main".to_string();
+        let commit = |sha: &str| CommitSummary {
+            sha: sha.to_string(),
+            subject: format!("commit {}", sha),
+            author: "Test".to_string(),
+            epoch: 0,
+        };
+        let branch = |name: &str, is_current: bool| BranchInfo {
+            name: name.to_string(),
+            is_current,
+            upstream: None,
+            ahead: 0,
+            behind: 0,
+            last_commit_epoch: 0,
+        };
+        let mut picker = CherryPicker::new(
+            &repo,
+            vec![commit("ccc"), commit("bbb"), commit("aaa")],
+            vec![branch("main", true), branch("release/1.2", false)],
+            vec![("release/1.2".to_string(), PathBuf::from("/tmp/wt"))],
+        );
+        assert_eq!(picker.targets.len(), 1);
+        assert!(picker.targets[0].worktree.is_some());
+        assert!(picker.choose_target().is_err());
+
+        picker.toggle();
+        picker.move_cursor(2);
+        picker.toggle();
+        picker.choose_target().unwrap();
+        let action = picker.to_action().unwrap();
+        assert!(matches!(
+            action.action,
+            ActionKind::GitCherryPick { ref branch, ref commits, .. }
+                if branch == "release/1.2" && commits == &["aaa", "ccc"]
+        ));
+    }
+
+    #[test]
+    fn stash_browser_targets_the_highlighted_entry() {
+        let repo = Repo::new(PathBuf::from("/tmp/agentpulse_stashes"));
+        let entry = |n: usize| StashEntry {
+            name: format!("stash@{{{}}}", n),
+            message: format!("work {}", n),
+            branch: Some("main".to_string()),
+            epoch: 0,
+            oid: format!("{:040x}", n),
+        };
+        let mut browser = StashBrowser::new(&repo, vec![entry(0), entry(1)]);
+        browser.move_cursor(5);
+        let drop = browser.action(StashOp::Drop).unwrap();
+        assert!(drop.action.is_destructive());
+        assert!(matches!(
+            drop.action,
+            ActionKind::GitStashDrop { ref stash, ref oid, .. }
+                if stash == "stash@{1}" && oid.as_deref() == Some(&format!("{:040x}", 1)[..])
+        ));
+        browser.move_cursor(-1);
+        let pop = browser.action(StashOp::Pop).unwrap();
+        assert_eq!(pop.action.risk_level(), "medium");
+        assert!(pop.command.ends_with("stash pop stash@{0}"));
+        assert!(StashBrowser::new(&repo, Vec::new())
+            .action(StashOp::Apply)
+            .is_none());
+    }
+
+    #[test]
+    fn commit_log_offers_push_only_for_unpushed_commits() {
+        let mut repo = Repo::new(PathBuf::from("/tmp/agentpulse_log"));
+        repo.status.branch = "feature".to_string();
+        repo.status.missing_upstream = true;
+        let entry = |sha: &str, unpushed| LogEntry {
+            commit: CommitSummary {
+                sha: sha.to_string(),
+                subject: format!("commit {}", sha),
+                author: "Test".to_string(),
+                epoch: 0,
+            },
+            unpushed,
+        };
+        let log = CommitLog::new(&repo, vec![entry("bbb", true), entry("aaa", false)]);
+        assert_eq!(log.unpushed_count(), 1);
+        assert!(matches!(
+            log.push.as_ref().map(|p| &p.action),
+            Some(ActionKind::GitPushSetUpstream { branch, .. }) if branch == "feature"
+        ));
+        assert!(CommitLog::new(&repo, vec![entry("aaa", false)])
+            .push
+            .is_none());
+    }
+
+    #[test]
+    fn diff_view_jumps_between_files() {
+        let diff = WorkingDiff {
+            stat: " a.rs | 2 +-\n b.rs | 1 +\n 2 files changed\n".to_string(),
+            patch: "diff --git a/a.rs b/a.rs\n@@ -1 +1 @@\n-old\n+new\n\
+                    diff --git a/b.rs b/b.rs\n@@ -0,0 +1 @@\n+\tadded\n"
+                .to_string(),
+            untracked: vec!["notes.txt".to_string()],
+        };
+        let mut view = DiffView::new("api", &diff);
+        assert_eq!(view.files_changed, 3);
+        assert!(view.lines.iter().any(|l| l == "+    added"));
+        view.jump_file(1);
+        assert_eq!(view.lines[view.scroll], "diff --git a/a.rs b/a.rs");
+        view.jump_file(1);
+        assert_eq!(view.lines[view.scroll], "diff --git a/b.rs b/b.rs");
+        view.jump_file(1);
+        assert_eq!(view.lines[view.scroll], "diff --git a/b.rs b/b.rs");
+        view.jump_file(-1);
+        assert_eq!(view.lines[view.scroll], "diff --git a/a.rs b/a.rs");
+        view.page(5);
+        assert_eq!(view.scroll, view.lines.len() - 1);
+    }
+
+    #[test]
+    fn grep_results_list_only_matching_repos() {
+        let mut app = App::new(Config {
+            show_clean: false,
+            ..Config::default()
+        });
+        let mut dirty = Repo::new(PathBuf::from("/tmp/agentpulse_grep/dirty"));
+        dirty.status.uncommitted_count = 1;
+        app.repos = vec![
+            dirty,
+            Repo::new(PathBuf::from("/tmp/agentpulse_grep/clean")),
+        ];
+        assert_eq!(app.filtered_repos().len(), 1);
+
+        app.grep_input = "old_api".to_string();
+        assert_eq!(app.start_grep().as_deref(), Some("old_api"));
+        // Nothing is hidden until the search finishes.
+        assert_eq!(app.filtered_repos().len(), 1);
+        assert!(app.finish_grep("stale", Vec::new()).is_none());
+
+        let hit = RepoHit {
+            name: "clean".to_string(),
+            path: PathBuf::from("/tmp/agentpulse_grep/clean"),
+            files: Vec::new(),
+            has_commit: true,
+            history: Vec::new(),
+        };
+        assert!(app
+            .finish_grep("old_api", vec![hit])
+            .unwrap()
+            .contains("1 of 2"));
+        let names: Vec<&str> = app
+            .filtered_repos()
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(names, ["clean"]);
+    }
+
+    #[test]
+    fn hero_card_picks_the_most_urgent_and_skips_round() {
+        let mut app = App::new(Config::default());
+        let mut behind = Repo::new(PathBuf::from("/tmp/agentpulse_hero/behind"));
+        behind.status.has_remote = true;
+        behind.status.behind_count = 2;
+        let mut detached = Repo::new(PathBuf::from("/tmp/agentpulse_hero/detached"));
+        detached.status.is_detached = true;
+        let mut clean = Repo::new(PathBuf::from("/tmp/agentpulse_hero/clean"));
+        clean.status.has_remote = true;
+        app.repos = vec![clean, behind, detached];
+
+        let (repo, rec, action) = app.next_best_action().unwrap();
+        assert_eq!(repo.name, "detached");
+        assert_eq!(rec.priority, agent::ActionPriority::Critical);
+        assert!(matches!(
+            action.unwrap().action,
+            ActionKind::GitSwitchCreate { .. }
+        ));
+
+        assert_eq!(app.skip_next_best_action(), "Skipped detached");
+        assert_eq!(app.next_best_action().unwrap().0.name, "behind");
+        assert!(app.skip_next_best_action().contains("back to the first"));
+        assert_eq!(app.next_best_action().unwrap().0.name, "detached");
+
+        app.repos.truncate(1);
+        assert!(app.next_best_action().is_none());
+    }
+
+    #[test]
+    fn alerts_and_recommendations_become_tasks() {
+        let mut app = App::new(Config {
+            tasks: crate::config::TasksConfig {
+                file: Some(PathBuf::from("/notes/TODO.md")),
+                ..Default::default()
+            },
+            ..Config::default()
+        });
+        let mut repo = Repo::new(PathBuf::from("/tmp/agentpulse_task/api"));
+        repo.status.has_remote = true;
+        repo.status.behind_count = 2;
+        app.repos = vec![repo];
+        app.dashboard.alerts = vec![crate::dashboard::DashboardAlert {
+            severity: "high".to_string(),
+            title: "api is behind remote".to_string(),
+            detail: "2 commit(s) behind".to_string(),
+            repo: Some("api".to_string()),
+            action: Some(ActionCommand::new(
+                "pull",
+                ActionKind::GitPullRebase {
+                    repo_path: "/tmp/agentpulse_task/api".to_string(),
+                },
+            )),
+        }];
+
+        let task = app.selected_task().unwrap();
+        assert_eq!(task.label, "file task in TODO.md");
+        let ActionKind::CreateTask {
+            title, notes, file, ..
+        } = task.action
+        else {
+            panic!("not a task");
+        };
+        assert_eq!(title, "api is behind remote");
+        assert_eq!(
+            notes,
+            [
+                "/tmp/agentpulse_task/api",
+                "2 commit(s) behind",
+                "git -C \"/tmp/agentpulse_task/api\" pull --rebase"
+            ]
+        );
+        assert_eq!(file.as_deref(), Some("/notes/TODO.md"));
+
+        app.section = DashboardSection::Repos;
+        let ActionKind::CreateTask { title, notes, .. } = app.selected_task().unwrap().action
+        else {
+            panic!("not a task");
+        };
+        assert!(title.starts_with("api: "), "{title}");
+        assert_eq!(notes[0], "/tmp/agentpulse_task/api");
+    }
+}
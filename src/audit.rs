@@ -0,0 +1,210 @@
+//! Audit trail of executed actions.
+//!
+//! Every action run from the TUI, `agentpulse ctl run-action` or
+//! `agentpulse run` is appended to `~/.local/share/agentpulse/actions.log`,
+//! one JSON object per line, including ones refused before they started.
+//! Past [`MAX_LOG_BYTES`] the file moves to `actions.log.1`, replacing the
+//! previous one; the Action Log section shows the newest [`VIEW_LIMIT`]
+//! entries, read from the end of the files.
+
+use crate::dashboard::ActionKind;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Entries the Action Log section loads.
+pub const VIEW_LIMIT: usize = 200;
+
+/// Size past which the log is rotated, a few thousand entries.
+const MAX_LOG_BYTES: u64 = 1 << 20;
+
+/// Bytes read from the end of the file at a time when loading entries.
+const TAIL_CHUNK: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Unix time the action finished.
+    pub at: i64,
+    #[serde(default)]
+    pub repo: Option<String>,
+    /// The action's `type`, e.g. `git_push`.
+    pub action: String,
+    /// Command preview, as shown in the confirmation dialog.
+    pub command: String,
+    pub ok: bool,
+    /// Exit code of the last command run; absent when none ran.
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+    /// First line of output, or why it failed.
+    #[serde(default)]
+    pub output: String,
+    /// Stopped by a pre-run check (moved repo, protected branch, destructive
+    /// guard) rather than failing on its own.
+    #[serde(default)]
+    pub refused: bool,
+}
+
+impl AuditEntry {
+    pub fn new(action: &ActionKind, ok: bool, exit_code: Option<i32>, output: &str) -> Self {
+        let kind = serde_json::to_value(action)
+            .ok()
+            .and_then(|v| v.get("type").and_then(|t| t.as_str()).map(str::to_string))
+            .unwrap_or_default();
+        Self {
+            at: chrono::Utc::now().timestamp(),
+            repo: action.affected_repo_path().map(str::to_string),
+            action: kind,
+            command: action.preview(),
+            ok,
+            exit_code,
+            output: output.to_string(),
+            refused: false,
+        }
+    }
+}
+
+/// `~/.local/share/agentpulse/actions.log`.
+pub fn log_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".local")
+        .join("share")
+        .join("agentpulse")
+        .join("actions.log")
+}
+
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".1");
+    path.with_file_name(name)
+}
+
+pub fn append(path: &Path, entry: &AuditEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::metadata(path).is_ok_and(|meta| meta.len() >= MAX_LOG_BYTES) {
+        std::fs::rename(path, rotated_path(path))?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    // One write, so concurrent appends can't interleave within a line.
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// The newest `limit` entries, newest first; unreadable lines are skipped.
+/// Older entries come from the rotated file when the current one runs out.
+pub fn load_recent(path: &Path, limit: usize) -> Vec<AuditEntry> {
+    let mut entries = read_tail(path, limit);
+    if entries.len() < limit {
+        entries.extend(read_tail(&rotated_path(path), limit - entries.len()));
+    }
+    entries
+}
+
+/// The last `limit` entries of one file, reading back from its end only as
+/// far as needed.
+fn read_tail(path: &Path, limit: usize) -> Vec<AuditEntry> {
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return Vec::new();
+    };
+    let mut start = file.seek(SeekFrom::End(0)).unwrap_or(0);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        let from = start.saturating_sub(TAIL_CHUNK);
+        let mut chunk = vec![0; (start - from) as usize];
+        if file.seek(SeekFrom::Start(from)).is_err() || file.read_exact(&mut chunk).is_err() {
+            return Vec::new();
+        }
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+        start = from;
+        // Before the start of the file, the first line may be cut short.
+        let whole = match start {
+            0 => &buf[..],
+            _ => buf
+                .iter()
+                .position(|b| *b == b'\n')
+                .map_or(&[][..], |at| &buf[at + 1..]),
+        };
+        let entries: Vec<AuditEntry> = whole
+            .split(|b| *b == b'\n')
+            .rev()
+            .filter_map(|line| serde_json::from_slice(line).ok())
+            .take(limit)
+            .collect();
+        if start == 0 || entries.len() >= limit {
+            return entries;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_and_reads_back_newest_first() {
+        let path = std::env::temp_dir()
+            .join("agentpulse_audit_test")
+            .join("actions.log");
+        let _ = std::fs::remove_file(&path);
+        let push = ActionKind::GitPush {
+            repo_path: "/src/api".to_string(),
+        };
+        append(&path, &AuditEntry::new(&push, false, Some(1), "rejected")).unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(file, "not json").unwrap();
+        append(&path, &AuditEntry::new(&push, true, Some(0), "")).unwrap();
+
+        let entries = load_recent(&path, 10);
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].ok);
+        assert_eq!(entries[1].action, "git_push");
+        assert_eq!(entries[1].repo.as_deref(), Some("/src/api"));
+        assert_eq!(entries[1].exit_code, Some(1));
+        assert_eq!(entries[1].output, "rejected");
+        assert_eq!(load_recent(&path, 1).len(), 1);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn rotates_a_full_log_and_reads_across_both_files() {
+        let path = std::env::temp_dir()
+            .join("agentpulse_audit_rotate_test")
+            .join("actions.log");
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(rotated_path(&path));
+        let push = ActionKind::GitPush {
+            repo_path: "/src/api".to_string(),
+        };
+        append(&path, &AuditEntry::new(&push, false, Some(1), "old")).unwrap();
+        // Filler longer than a read chunk, so the tail takes several reads.
+        let filler = format!("{}\n", "x".repeat(MAX_LOG_BYTES as usize));
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(filler.as_bytes()).unwrap();
+        append(&path, &AuditEntry::new(&push, false, Some(1), "middle")).unwrap();
+        append(&path, &AuditEntry::new(&push, true, Some(0), "new")).unwrap();
+
+        assert!(rotated_path(&path).is_file());
+        let outputs: Vec<String> = load_recent(&path, 10)
+            .into_iter()
+            .map(|entry| entry.output)
+            .collect();
+        assert_eq!(outputs, ["new", "middle", "old"]);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(rotated_path(&path));
+    }
+}
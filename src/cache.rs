@@ -0,0 +1,114 @@
+//! Housekeeping for state AgentPulse persists between runs.
+//!
+//! Backs `agentpulse cache clear` and the cache section of `agentpulse doctor`.
+
+use crate::collectors::fsck::{self, FsckResults};
+use crate::history;
+use crate::repo_index::{self, RepoIndex};
+use crate::scan_cache::{self, ScanCache};
+use crate::state;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// One persisted file and what is known about it.
+pub struct CacheFileStats {
+    pub label: &'static str,
+    pub path: PathBuf,
+    pub bytes: Option<u64>,
+    /// Human-readable summary of the contents (entry counts etc.).
+    pub detail: String,
+}
+
+fn cache_files() -> [(&'static str, PathBuf); 5] {
+    [
+        ("session state", state::state_path()),
+        ("repo index", repo_index::index_path()),
+        ("snapshot history", history::history_path()),
+        ("fsck results", fsck::results_path()),
+        ("scan cache", scan_cache::cache_path()),
+    ]
+}
+
+/// Collect size and entry statistics for every persisted cache file.
+pub fn stats(watch_dirs: &[PathBuf]) -> Vec<CacheFileStats> {
+    cache_files()
+        .into_iter()
+        .map(|(label, path)| {
+            let bytes = std::fs::metadata(&path).ok().map(|m| m.len());
+            let detail = if bytes.is_none() {
+                "not present".to_string()
+            } else if label == "repo index" {
+                let index = RepoIndex::load(&path);
+                if index.is_empty() {
+                    "empty".to_string()
+                } else {
+                    format!(
+                        "{} entries, {} stale",
+                        index.len(),
+                        index.stale_count(watch_dirs)
+                    )
+                }
+            } else if label == "snapshot history" {
+                format!("{} snapshots", history::load(&path).len())
+            } else if label == "fsck results" {
+                let results = FsckResults::load(&path);
+                let failed = results
+                    .repos
+                    .values()
+                    .filter(|r| !r.problems.is_empty())
+                    .count();
+                format!("{} repos checked, {} failed", results.repos.len(), failed)
+            } else if label == "scan cache" {
+                let cache = ScanCache::load(&path);
+                if cache.is_empty() {
+                    "empty".to_string()
+                } else {
+                    format!("{} repos", cache.len())
+                }
+            } else {
+                String::new()
+            };
+            CacheFileStats {
+                label,
+                path,
+                bytes,
+                detail,
+            }
+        })
+        .collect()
+}
+
+/// Delete every persisted cache file. Returns the paths that were removed.
+pub fn clear() -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+    for (_, path) in cache_files() {
+        if remove_if_present(&path)? {
+            removed.push(path);
+        }
+    }
+    Ok(removed)
+}
+
+fn remove_if_present(path: &Path) -> Result<bool> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_if_present_tolerates_missing_files() {
+        let dir = std::env::temp_dir().join("agentpulse_test_cache_remove");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+        std::fs::write(&path, "{}").unwrap();
+        assert!(remove_if_present(&path).unwrap());
+        assert!(!remove_if_present(&path).unwrap());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
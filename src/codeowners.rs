@@ -0,0 +1,222 @@
+//! CODEOWNERS (GitHub/GitLab syntax): who owns a repo and who would need to
+//! review its uncommitted files.
+
+use crate::collectors::compliance::wildcard_match;
+use crate::git;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Checked in order; the first one found is used, as on GitHub.
+const LOCATIONS: &[&str] = &[
+    ".github/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+];
+
+struct Rule {
+    /// Path segments; unanchored patterns start with `**`.
+    segments: Vec<String>,
+    /// `dir/*` owns direct children only, not everything below them.
+    direct_children_only: bool,
+    owners: Vec<String>,
+}
+
+impl Rule {
+    fn parse(pattern: &str, owners: Vec<String>) -> Self {
+        let anchored = pattern.starts_with('/') || pattern.trim_end_matches('/').contains('/');
+        let mut segments: Vec<String> = pattern
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        if !anchored {
+            segments.insert(0, "**".to_string());
+        }
+        Self {
+            direct_children_only: segments.len() > 1 && segments.last().is_some_and(|s| s == "*"),
+            segments,
+            owners,
+        }
+    }
+
+    /// Matches the path itself or, for directory patterns, anything below it.
+    fn matches(&self, path: &str) -> bool {
+        let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        if self.direct_children_only {
+            return match_segments(&self.segments, &parts);
+        }
+        (1..=parts.len()).any(|end| match_segments(&self.segments, &parts[..end]))
+    }
+}
+
+fn match_segments(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((head, rest)) if head == "**" => {
+            (0..=path.len()).any(|skip| match_segments(rest, &path[skip..]))
+        }
+        Some((head, rest)) => match path.split_first() {
+            Some((part, path_rest)) => {
+                wildcard_match(head, part) && match_segments(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    pub fn load(repo_path: &Path) -> Option<Self> {
+        LOCATIONS
+            .iter()
+            .find_map(|loc| fs::read_to_string(repo_path.join(loc)).ok())
+            .map(|raw| Self::parse(&raw))
+    }
+
+    fn parse(raw: &str) -> Self {
+        let rules = raw
+            .lines()
+            .map(|line| line.split(" #").next().unwrap_or("").trim())
+            // Comments and GitLab `[Section]` headers.
+            .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('['))
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let pattern = fields.next()?;
+                Some(Rule::parse(pattern, fields.map(str::to_string).collect()))
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Owners of `path`; the last matching rule wins, and a rule without
+    /// owners leaves the path unowned.
+    fn owners_for(&self, path: &str) -> &[String] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(path))
+            .map_or(&[], |rule| rule.owners.as_slice())
+    }
+}
+
+/// CODEOWNERS summary for one repo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ownership {
+    /// Owners of the repo root, i.e. the catch-all rule.
+    pub repo_owners: Vec<String>,
+    /// Owners who would review the uncommitted files, with how many each.
+    pub dirty_owners: BTreeMap<String, usize>,
+    /// Uncommitted files no rule assigns an owner to.
+    pub unowned_dirty: usize,
+}
+
+impl Ownership {
+    /// `None` when the repo has no CODEOWNERS file. Lists changed files only
+    /// when `dirty` says there are any.
+    pub fn load(repo_path: &Path, dirty: usize) -> Option<Self> {
+        let owners = CodeOwners::load(repo_path)?;
+        let files: Vec<String> = if dirty > 0 {
+            git::list_changed_files(repo_path)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|f| f.path)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Some(Self::from_files(&owners, &files))
+    }
+
+    fn from_files(owners: &CodeOwners, files: &[String]) -> Self {
+        let mut dirty_owners = BTreeMap::new();
+        let mut unowned_dirty = 0;
+        for file in files {
+            let file_owners = owners.owners_for(file);
+            if file_owners.is_empty() {
+                unowned_dirty += 1;
+            }
+            for owner in file_owners {
+                *dirty_owners.entry(owner.clone()).or_insert(0) += 1;
+            }
+        }
+        Self {
+            repo_owners: owners.owners_for("*").to_vec(),
+            dirty_owners,
+            unowned_dirty,
+        }
+    }
+
+    /// ` owners=@a,@b review=@a(2),@c(1)` for the selected-repo detail line.
+    pub fn detail(&self) -> String {
+        let mut out = format!(
+            " owners={}",
+            if self.repo_owners.is_empty() {
+                "none".to_string()
+            } else {
+                self.repo_owners.join(",")
+            }
+        );
+        if !self.dirty_owners.is_empty() || self.unowned_dirty > 0 {
+            let mut review: Vec<String> = self
+                .dirty_owners
+                .iter()
+                .map(|(owner, count)| format!("{}({})", owner, count))
+                .collect();
+            if self.unowned_dirty > 0 {
+                review.push(format!("unowned({})", self.unowned_dirty));
+            }
+            out.push_str(&format!(" review={}", review.join(",")));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+# Default owners
+*                   @acme/platform
+/docs/              @acme/docs
+*.rs                @alice
+/scripts/*          @bob
+[Frontend]
+web/**/*.ts         @acme/web # trailing comment
+/vendor/
+";
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let owners = CodeOwners::parse(SAMPLE);
+        assert_eq!(owners.owners_for("README.md"), ["@acme/platform"]);
+        assert_eq!(owners.owners_for("docs/guide/intro.md"), ["@acme/docs"]);
+        assert_eq!(owners.owners_for("docs/build.rs"), ["@alice"]);
+        assert_eq!(owners.owners_for("src/deep/main.rs"), ["@alice"]);
+        assert_eq!(owners.owners_for("scripts/release.sh"), ["@bob"]);
+        assert_eq!(owners.owners_for("scripts/ci/run.sh"), ["@acme/platform"]);
+        assert_eq!(owners.owners_for("web/app/pages/home.ts"), ["@acme/web"]);
+        assert!(owners.owners_for("vendor/lib/x.c").is_empty());
+    }
+
+    #[test]
+    fn summarizes_reviewers_for_dirty_files() {
+        let owners = CodeOwners::parse(SAMPLE);
+        let files: Vec<String> = ["src/lib.rs", "src/app.rs", "docs/a.md", "vendor/x.c"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let ownership = Ownership::from_files(&owners, &files);
+        assert_eq!(ownership.repo_owners, ["@acme/platform"]);
+        assert_eq!(ownership.unowned_dirty, 1);
+        assert_eq!(
+            ownership.detail(),
+            " owners=@acme/platform review=@acme/docs(1),@alice(2),unowned(1)"
+        );
+    }
+}
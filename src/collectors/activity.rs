@@ -0,0 +1,319 @@
+//! Commit activity: commits per day across the watched repos over the last
+//! [`WEEKS`] weeks, for the Home heatmap, and this week's commits and
+//! changed lines per repo, for the "This week" card and `--stats`.
+//!
+//! Only local branches are read, and only the user's own commits when
+//! `user.email` is set, so pulled-in work from others does not count. Each
+//! repo's log is cached until its branch or counts change (a new commit
+//! moves them) and re-read every few minutes regardless. Line counts are
+//! kept per commit, so a re-read only diffs commits not seen before.
+
+use super::CollectorResult;
+use crate::dashboard::{ActivityDay, RepoWeekStats};
+use crate::git::{std_git_command, Repo};
+use chrono::{DateTime, Datelike, Duration as Days, Local, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Weeks shown in the heatmap, the current one included.
+pub const WEEKS: usize = 8;
+
+const REFRESH_AFTER: Duration = Duration::from_secs(600);
+
+/// What invalidates a cached log: the window start, the branch, and the
+/// dirty and unpushed counts.
+type CacheKey = (NaiveDate, String, usize, usize);
+
+type ActivityCache = HashMap<PathBuf, (Instant, CacheKey, Vec<i64>)>;
+
+static ACTIVITY_CACHE: OnceLock<Mutex<ActivityCache>> = OnceLock::new();
+
+/// Per repo: when it was read, the key it was read under, this week's
+/// commits in log order, and insertions/deletions per commit.
+type WeekCache = HashMap<PathBuf, (Instant, CacheKey, Vec<String>, LineCounts)>;
+
+type LineCounts = HashMap<String, (usize, usize)>;
+
+static WEEK_CACHE: OnceLock<Mutex<WeekCache>> = OnceLock::new();
+
+/// Upper bound on commits diffed in one git call.
+const NUMSTAT_BATCH: usize = 200;
+
+/// Monday of the week containing `today`.
+pub fn week_start(today: NaiveDate) -> NaiveDate {
+    today - Days::days(today.weekday().num_days_from_monday() as i64)
+}
+
+/// Monday of the oldest week shown when today is `today`.
+pub fn window_start(today: NaiveDate) -> NaiveDate {
+    week_start(today) - Days::weeks(WEEKS as i64 - 1)
+}
+
+fn cache_key(repo: &Repo, since: NaiveDate) -> CacheKey {
+    (
+        since,
+        repo.status.branch.clone(),
+        repo.status.uncommitted_count,
+        repo.status.unpushed_count,
+    )
+}
+
+fn git_stdout(repo_path: &Path, args: &[&str]) -> Option<String> {
+    let out = std_git_command(args).current_dir(repo_path).output().ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// The field after the author in `git log --format=%ae%x1f<field>` lines,
+/// keeping only `email`'s commits when it is set.
+fn parse_log(raw: &str, email: Option<&str>) -> Vec<String> {
+    raw.lines()
+        .filter_map(|line| {
+            let (author, field) = line.split_once('\u{1f}')?;
+            if email.is_some_and(|email| !author.trim().eq_ignore_ascii_case(email)) {
+                return None;
+            }
+            Some(field.to_string())
+        })
+        .collect()
+}
+
+/// The repo's `user.email`, when set.
+fn own_email(repo_path: &Path) -> Option<String> {
+    git_stdout(repo_path, &["config", "user.email"])
+        .map(|email| email.trim().to_string())
+        .filter(|email| !email.is_empty())
+}
+
+/// `git log` on local branches since `since`, one `author<US>field` line
+/// per commit, keeping the user's commits.
+fn own_log(repo_path: &Path, since: NaiveDate, field: &str) -> Vec<String> {
+    let email = own_email(repo_path);
+    let since_arg = format!("--since={} 00:00", since.format("%Y-%m-%d"));
+    let format_arg = format!("--format=%ae%x1f{}", field);
+    git_stdout(repo_path, &["log", "--branches", &format_arg, &since_arg])
+        .map(|raw| parse_log(&raw, email.as_deref()))
+        .unwrap_or_default()
+}
+
+/// Commit times on local branches since `since`.
+fn commit_times(repo_path: &Path, since: NaiveDate) -> Vec<i64> {
+    own_log(repo_path, since, "%ct")
+        .iter()
+        .filter_map(|ts| ts.trim().parse().ok())
+        .collect()
+}
+
+fn cached_commit_times(repo: &Repo, since: NaiveDate) -> Vec<i64> {
+    let key = cache_key(repo, since);
+    let cache = ACTIVITY_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(guard) = cache.lock() {
+        if let Some((at, cached_key, times)) = guard.get(&repo.path) {
+            if *cached_key == key && at.elapsed() < REFRESH_AFTER {
+                return times.clone();
+            }
+        }
+    }
+    let times = commit_times(&repo.path, since);
+    if let Ok(mut guard) = cache.lock() {
+        guard.insert(repo.path.clone(), (Instant::now(), key, times.clone()));
+    }
+    times
+}
+
+/// Per-repo commit times bucketed into local days from `since` on.
+fn aggregate(per_repo: &[(String, Vec<i64>)], since: NaiveDate) -> Vec<ActivityDay> {
+    let mut days: BTreeMap<NaiveDate, BTreeMap<String, usize>> = BTreeMap::new();
+    for (name, times) in per_repo {
+        for ts in times {
+            let Some(date) = DateTime::from_timestamp(*ts, 0)
+                .map(|at| at.with_timezone(&Local).date_naive())
+                .filter(|date| *date >= since)
+            else {
+                continue;
+            };
+            *days
+                .entry(date)
+                .or_default()
+                .entry(name.clone())
+                .or_default() += 1;
+        }
+    }
+    days.into_iter()
+        .map(|(date, repos)| ActivityDay {
+            date: date.format("%Y-%m-%d").to_string(),
+            commits: repos.values().sum(),
+            repos,
+        })
+        .collect()
+}
+
+/// Insertions and deletions per commit from
+/// `git log --numstat --format=%x1e%H`; binary files count as zero.
+fn parse_numstat(raw: &str) -> LineCounts {
+    raw.split('\u{1e}')
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let sha = lines.next()?.trim();
+            if sha.is_empty() {
+                return None;
+            }
+            let counts = lines.fold((0, 0), |(ins, del), line| {
+                let mut parts = line.split('\t');
+                let mut next = || parts.next().and_then(|n| n.parse::<usize>().ok());
+                (ins + next().unwrap_or(0), del + next().unwrap_or(0))
+            });
+            Some((sha.to_string(), counts))
+        })
+        .collect()
+}
+
+/// This week's commits on local branches and their changed lines, diffing
+/// only commits missing from `known`.
+fn week_lines(
+    repo_path: &Path,
+    monday: NaiveDate,
+    mut known: LineCounts,
+) -> (Vec<String>, LineCounts) {
+    let shas = own_log(repo_path, monday, "%H");
+    known.retain(|sha, _| shas.contains(sha));
+    let missing: Vec<&str> = shas
+        .iter()
+        .filter(|sha| !known.contains_key(*sha))
+        .map(String::as_str)
+        .collect();
+    for batch in missing.chunks(NUMSTAT_BATCH) {
+        let mut args = vec!["log", "--no-walk=unsorted", "--numstat", "--format=%x1e%H"];
+        args.extend(batch);
+        if let Some(raw) = git_stdout(repo_path, &args) {
+            known.extend(parse_numstat(&raw));
+        }
+    }
+    (shas, known)
+}
+
+fn cached_week_stats(repo: &Repo, monday: NaiveDate) -> Option<RepoWeekStats> {
+    let key = cache_key(repo, monday);
+    let cache = WEEK_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let cached = cache
+        .lock()
+        .ok()
+        .and_then(|guard| guard.get(&repo.path).cloned());
+    let (shas, lines) = match cached {
+        Some((at, cached_key, shas, lines))
+            if cached_key == key && at.elapsed() < REFRESH_AFTER =>
+        {
+            (shas, lines)
+        }
+        Some((_, cached_key, _, lines)) if cached_key.0 == monday => {
+            week_lines(&repo.path, monday, lines)
+        }
+        _ => week_lines(&repo.path, monday, HashMap::new()),
+    };
+    if let Ok(mut guard) = cache.lock() {
+        guard.insert(
+            repo.path.clone(),
+            (Instant::now(), key, shas.clone(), lines.clone()),
+        );
+    }
+    week_stats(&repo.name, &shas, &lines)
+}
+
+fn week_stats(repo: &str, shas: &[String], lines: &LineCounts) -> Option<RepoWeekStats> {
+    if shas.is_empty() {
+        return None;
+    }
+    let (insertions, deletions) = shas
+        .iter()
+        .filter_map(|sha| lines.get(sha))
+        .fold((0, 0), |(ins, del), (i, d)| (ins + i, del + d));
+    Some(RepoWeekStats {
+        repo: repo.to_string(),
+        commits: shas.len(),
+        insertions,
+        deletions,
+    })
+}
+
+pub fn collect_activity(repos: &[Repo]) -> CollectorResult<ActivityDay> {
+    super::require_git("activity", repos)?;
+    let since = window_start(Local::now().date_naive());
+    let per_repo: Vec<(String, Vec<i64>)> = repos
+        .iter()
+        .map(|repo| (repo.name.clone(), cached_commit_times(repo, since)))
+        .collect();
+    Ok(aggregate(&per_repo, since))
+}
+
+pub fn collect_week_stats(repos: &[Repo]) -> CollectorResult<RepoWeekStats> {
+    super::require_git("week_stats", repos)?;
+    let monday = week_start(Local::now().date_naive());
+    let mut stats: Vec<RepoWeekStats> = repos
+        .iter()
+        .filter_map(|repo| cached_week_stats(repo, monday))
+        .collect();
+    stats.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.repo.cmp(&b.repo)));
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn window_starts_on_the_monday_seven_weeks_back() {
+        let thursday = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        assert_eq!(
+            window_start(thursday),
+            NaiveDate::from_ymd_opt(2026, 8, 24).unwrap()
+        );
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        assert_eq!(window_start(monday), window_start(thursday));
+    }
+
+    #[test]
+    fn keeps_own_commits_and_buckets_them_per_day_and_repo() {
+        let raw = "me@example.com\u{1f}100\nother@example.com\u{1f}200\nME@example.com\u{1f}300\n";
+        assert_eq!(parse_log(raw, Some("me@example.com")), ["100", "300"]);
+        assert_eq!(parse_log(raw, None), ["100", "200", "300"]);
+
+        let at = |day: u32, hour: u32| {
+            Local
+                .with_ymd_and_hms(2026, 10, day, hour, 0, 0)
+                .unwrap()
+                .timestamp()
+        };
+        let since = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let per_repo = vec![
+            ("api".to_string(), vec![at(12, 9), at(12, 17), at(14, 11)]),
+            ("web".to_string(), vec![at(12, 10), at(9, 10)]),
+        ];
+        let days = aggregate(&per_repo, since);
+        let summary: Vec<(&str, usize, usize)> = days
+            .iter()
+            .map(|d| (d.date.as_str(), d.commits, d.repos.len()))
+            .collect();
+        assert_eq!(summary, [("2026-10-12", 3, 2), ("2026-10-14", 1, 1)]);
+        assert_eq!(days[0].repos["api"], 2);
+    }
+
+    #[test]
+    fn sums_changed_lines_of_this_weeks_commits() {
+        let raw = "\u{1e}aaa\n\n10\t2\tsrc/lib.rs\n-\t-\tlogo.png\n3\t0\tREADME.md\n\u{1e}bbb\n\u{1e}ccc\n\n1\t1\tsrc/main.rs\n";
+        let lines = parse_numstat(raw);
+        assert_eq!(lines["aaa"], (13, 2));
+        assert_eq!(lines["bbb"], (0, 0));
+
+        let shas = vec!["aaa".to_string(), "bbb".to_string()];
+        let stats = week_stats("api", &shas, &lines).unwrap();
+        assert_eq!(
+            (stats.commits, stats.insertions, stats.deletions),
+            (2, 13, 2)
+        );
+        assert!(week_stats("api", &[], &lines).is_none());
+    }
+}
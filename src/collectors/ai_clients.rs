@@ -0,0 +1,531 @@
+//! Inventory of AI clients' own config files.
+//!
+//! Claude Desktop, Claude Code, Cursor, VS Code and Codex each keep their
+//! own list of MCP servers, so the same server ends up set up by hand in
+//! several places. Every file found is listed with its servers, models and
+//! permissions; servers and deny rules one client lacks, and servers, default
+//! models or rules it sets differently from another, are noted as drift.
+
+use super::ai_mcp::{extract_mcp_servers, home_join};
+use super::CollectorResult;
+use crate::dashboard::{AiClientConfig, ClientMcpServer};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    /// JSON, with the comments and trailing commas VS Code allows.
+    Json,
+    /// Codex `config.toml`.
+    Toml,
+}
+
+/// Files each client reads its global config from.
+fn candidate_client_files() -> Vec<(&'static str, PathBuf, Format)> {
+    let mut out = Vec::new();
+    let config_dir = dirs::config_dir();
+    if let Some(dir) = &config_dir {
+        out.push((
+            "Claude Desktop",
+            dir.join("Claude").join("claude_desktop_config.json"),
+            Format::Json,
+        ));
+    }
+    for (client, p) in [
+        (
+            "Claude Desktop",
+            ".config/claude/claude_desktop_config.json",
+        ),
+        ("Claude Desktop", ".claude/claude_desktop_config.json"),
+        ("Claude Code", ".claude/settings.json"),
+        ("Claude Code", ".claude.json"),
+        ("Cursor", ".cursor/mcp.json"),
+    ] {
+        if let Some(path) = home_join(p) {
+            out.push((client, path, Format::Json));
+        }
+    }
+    if let Some(dir) = &config_dir {
+        for file in ["settings.json", "mcp.json"] {
+            out.push((
+                "VS Code",
+                dir.join("Code").join("User").join(file),
+                Format::Json,
+            ));
+        }
+    }
+    let codex_home = std::env::var_os("CODEX_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home_join(".codex"));
+    if let Some(dir) = codex_home {
+        out.push(("Codex", dir.join("config.toml"), Format::Toml));
+    }
+    out
+}
+
+pub fn collect_ai_clients() -> CollectorResult<AiClientConfig> {
+    let mut seen = BTreeSet::new();
+    let mut configs = Vec::new();
+    for (client, path, format) in candidate_client_files() {
+        if !path.is_file() || !seen.insert(fs::canonicalize(&path).unwrap_or(path.clone())) {
+            continue;
+        }
+        let mut config = AiClientConfig {
+            client: client.to_string(),
+            path: path.to_string_lossy().to_string(),
+            ..AiClientConfig::default()
+        };
+        let mut parsed = config.clone();
+        let read = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read: {}", e))
+            .and_then(|raw| match format {
+                Format::Json => read_json_config(&mut parsed, &raw),
+                Format::Toml => read_codex_config(&mut parsed, &raw),
+            });
+        match read {
+            Ok(()) => config = parsed,
+            Err(err) => config.error = Some(err),
+        }
+        configs.push(config);
+    }
+    mark_drift(&mut configs);
+    Ok(configs)
+}
+
+/// Claude Desktop, Claude Code, Cursor and VS Code files.
+fn read_json_config(config: &mut AiClientConfig, raw: &str) -> Result<(), String> {
+    let value: Value =
+        serde_json::from_str(&strip_jsonc(raw)).map_err(|e| format!("invalid JSON: {}", e))?;
+    // VS Code's settings.json nests them under `mcp`.
+    for scope in std::iter::once(&value).chain(value.get("mcp")) {
+        config
+            .servers
+            .extend(
+                extract_mcp_servers(scope)
+                    .into_iter()
+                    .map(|entry| ClientMcpServer {
+                        name: entry.name,
+                        command: entry.command,
+                    }),
+            );
+    }
+    if let Some(model) = value.get("model").and_then(Value::as_str) {
+        config.models.push(model.to_string());
+    }
+    if let Some(permissions) = value.get("permissions") {
+        for kind in ["allow", "ask", "deny"] {
+            let rules = permissions.get(kind).and_then(Value::as_array);
+            for rule in rules.into_iter().flatten().filter_map(Value::as_str) {
+                config.permissions.push(format!("{} {}", kind, rule));
+            }
+        }
+        if let Some(mode) = permissions.get("defaultMode").and_then(Value::as_str) {
+            config.permissions.push(format!("defaultMode {}", mode));
+        }
+    }
+    if value.get("chat.tools.autoApprove").and_then(Value::as_bool) == Some(true) {
+        config
+            .permissions
+            .push("chat.tools.autoApprove".to_string());
+    }
+    Ok(())
+}
+
+fn read_codex_config(config: &mut AiClientConfig, raw: &str) -> Result<(), String> {
+    let table: toml::Table = toml::from_str(raw).map_err(|e| format!("invalid TOML: {}", e))?;
+    if let Some(model) = table.get("model").and_then(toml::Value::as_str) {
+        config.models.push(model.to_string());
+    }
+    let profiles = table.get("profiles").and_then(toml::Value::as_table);
+    for (name, profile) in profiles.into_iter().flatten() {
+        if let Some(model) = profile.get("model").and_then(toml::Value::as_str) {
+            config.models.push(format!("{} (profile {})", model, name));
+        }
+    }
+    let servers = table.get("mcp_servers").and_then(toml::Value::as_table);
+    for (name, server) in servers.into_iter().flatten() {
+        let args = server
+            .get("args")
+            .and_then(toml::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_str);
+        let command = match server.get("command").and_then(toml::Value::as_str) {
+            Some(command) => std::iter::once(command)
+                .chain(args)
+                .collect::<Vec<_>>()
+                .join(" "),
+            None => server
+                .get("url")
+                .and_then(toml::Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        };
+        config.servers.push(ClientMcpServer {
+            name: name.clone(),
+            command,
+        });
+    }
+    for key in ["approval_policy", "sandbox_mode"] {
+        if let Some(value) = table.get(key).and_then(toml::Value::as_str) {
+            config.permissions.push(format!("{} {}", key, value));
+        }
+    }
+    Ok(())
+}
+
+/// `raw` without `//` and `/* */` comments or trailing commas.
+fn strip_jsonc(raw: &str) -> String {
+    let mut uncommented = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            uncommented.push(c);
+            if c == '\\' {
+                uncommented.extend(chars.next());
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('/')) => while chars.next_if(|&n| n != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for n in chars.by_ref() {
+                    if prev == '*' && n == '/' {
+                        break;
+                    }
+                    prev = n;
+                }
+            }
+            _ => {
+                in_string = c == '"';
+                uncommented.push(c);
+            }
+        }
+    }
+
+    let mut out = String::with_capacity(uncommented.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (idx, c) in uncommented.char_indices() {
+        if in_string {
+            in_string = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' && uncommented[idx + 1..].trim_start().starts_with(['}', ']']) {
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// What one client sets up across its files, the first file to define
+/// each thing winning.
+#[derive(Default)]
+struct ClientSetup {
+    servers: BTreeMap<String, String>,
+    model: Option<String>,
+    /// Permission rule, e.g. `Read(.env)`, to `allow`, `ask` or `deny`.
+    rules: BTreeMap<String, String>,
+}
+
+/// Compare what clients set up: note on each client's first file the
+/// servers and deny rules others have and it lacks, and on the defining
+/// file a server run with a different command, a different default model,
+/// or a rule with another verdict than in another client. Servers and rules
+/// are only expected of clients that define any.
+fn mark_drift(configs: &mut [AiClientConfig]) {
+    let mut clients: BTreeMap<String, ClientSetup> = BTreeMap::new();
+    for config in configs.iter().filter(|c| c.error.is_none()) {
+        let setup = clients.entry(config.client.clone()).or_default();
+        for server in &config.servers {
+            setup
+                .servers
+                .entry(server.name.clone())
+                .or_insert_with(|| normalize(&server.command));
+        }
+        if setup.model.is_none() {
+            setup.model = default_model(config).map(str::to_string);
+        }
+        for (kind, rule) in config.permissions.iter().filter_map(|p| permission_rule(p)) {
+            setup
+                .rules
+                .entry(rule.to_string())
+                .or_insert_with(|| kind.to_string());
+        }
+    }
+    if clients.len() < 2 {
+        return;
+    }
+
+    let mut reported = BTreeSet::new();
+    for config in configs.iter_mut() {
+        let Some(own) = clients.get(&config.client) else {
+            continue;
+        };
+        if config.error.is_some() {
+            continue;
+        }
+        let others = || clients.iter().filter(|(name, _)| **name != config.client);
+
+        if reported.insert(config.client.clone()) {
+            let mut missing: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+            let mut unguarded: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+            for (client, setup) in others() {
+                if !own.servers.is_empty() {
+                    for name in setup
+                        .servers
+                        .keys()
+                        .filter(|n| !own.servers.contains_key(*n))
+                    {
+                        missing.entry(name).or_default().push(client);
+                    }
+                }
+                if !own.rules.is_empty() {
+                    let denied = setup.rules.iter().filter(|(_, kind)| *kind == "deny");
+                    for (rule, _) in denied.filter(|(rule, _)| !own.rules.contains_key(*rule)) {
+                        unguarded.entry(rule).or_default().push(client);
+                    }
+                }
+            }
+            for (name, defined_in) in missing {
+                config.drift.push(format!(
+                    "no {} server; {} define{} it",
+                    name,
+                    defined_in.join(", "),
+                    if defined_in.len() == 1 { "s" } else { "" }
+                ));
+            }
+            for (rule, denied_in) in unguarded {
+                config.drift.push(format!(
+                    "no rule for {}; {} den{} it",
+                    rule,
+                    denied_in.join(", "),
+                    if denied_in.len() == 1 { "ies" } else { "y" }
+                ));
+            }
+        }
+
+        let mut differs = Vec::new();
+        let mut compared = BTreeSet::new();
+        for server in &config.servers {
+            if !compared.insert(server.name.as_str()) {
+                continue;
+            }
+            let command = normalize(&server.command);
+            for (client, setup) in others() {
+                if let Some(theirs) = setup.servers.get(&server.name).filter(|t| **t != command) {
+                    differs.push(format!(
+                        "{} runs `{}` here, `{}` in {}",
+                        server.name, command, theirs, client
+                    ));
+                }
+            }
+        }
+        if let Some(model) = default_model(config) {
+            for (client, setup) in others() {
+                if let Some(theirs) = setup.model.as_deref().filter(|t| *t != model) {
+                    differs.push(format!(
+                        "model `{}` here, `{}` in {}",
+                        model, theirs, client
+                    ));
+                }
+            }
+        }
+        let mut compared = BTreeSet::new();
+        for (kind, rule) in config.permissions.iter().filter_map(|p| permission_rule(p)) {
+            if !compared.insert(rule) {
+                continue;
+            }
+            for (client, setup) in others() {
+                if let Some(theirs) = setup.rules.get(rule).filter(|t| *t != kind) {
+                    differs.push(format!(
+                        "{} is `{}` here, `{}` in {}",
+                        rule, kind, theirs, client
+                    ));
+                }
+            }
+        }
+        config.drift.extend(differs);
+    }
+}
+
+/// The model a client uses unless told otherwise, not a profile's.
+fn default_model(config: &AiClientConfig) -> Option<&str> {
+    config
+        .models
+        .first()
+        .map(String::as_str)
+        .filter(|model| !model.contains(" (profile "))
+}
+
+/// `("deny", "Read(.env)")` for an `allow`/`ask`/`deny` rule; approval
+/// settings such as `defaultMode` have no counterpart in other clients.
+fn permission_rule(permission: &str) -> Option<(&str, &str)> {
+    permission
+        .split_once(' ')
+        .filter(|(kind, _)| matches!(*kind, "allow" | "ask" | "deny"))
+}
+
+fn normalize(command: &str) -> String {
+    command.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(name: &str, command: &str) -> ClientMcpServer {
+        ClientMcpServer {
+            name: name.to_string(),
+            command: command.to_string(),
+        }
+    }
+
+    #[test]
+    fn reads_vscode_settings_and_claude_permissions() {
+        let raw = r#"{
+  // servers shared with the team
+  "mcp": {
+    "servers": {
+      "docs": { "url": "https://docs.example.com/mcp", }, /* remote */
+    },
+  },
+  "chat.tools.autoApprove": true,
+  "files.exclude": { "**/*.tmp//x": true },
+}"#;
+        let mut vscode = AiClientConfig::default();
+        read_json_config(&mut vscode, raw).unwrap();
+        assert_eq!(
+            vscode.servers,
+            [server("docs", "https://docs.example.com/mcp")]
+        );
+        assert_eq!(vscode.permissions, ["chat.tools.autoApprove"]);
+
+        let raw = r#"{
+  "model": "opus",
+  "permissions": { "allow": ["Bash(git:*)"], "deny": ["Read(.env)"], "defaultMode": "acceptEdits" }
+}"#;
+        let mut claude = AiClientConfig::default();
+        read_json_config(&mut claude, raw).unwrap();
+        assert_eq!(claude.models, ["opus"]);
+        assert_eq!(
+            claude.permissions,
+            [
+                "allow Bash(git:*)",
+                "deny Read(.env)",
+                "defaultMode acceptEdits"
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_codex_toml() {
+        let raw = r#"
+model = "gpt-5-codex"
+approval_policy = "on-request"
+
+[profiles.fast]
+model = "gpt-5-mini"
+
+[mcp_servers.github]
+command = "npx"
+args = ["-y", "@modelcontextprotocol/server-github"]
+"#;
+        let mut codex = AiClientConfig::default();
+        read_codex_config(&mut codex, raw).unwrap();
+        assert_eq!(codex.models, ["gpt-5-codex", "gpt-5-mini (profile fast)"]);
+        assert_eq!(
+            codex.servers,
+            [server(
+                "github",
+                "npx -y @modelcontextprotocol/server-github"
+            )]
+        );
+        assert_eq!(codex.permissions, ["approval_policy on-request"]);
+    }
+
+    #[test]
+    fn notes_missing_and_differing_servers_per_client() {
+        let config = |client: &str, servers: Vec<ClientMcpServer>| AiClientConfig {
+            client: client.to_string(),
+            path: format!("{}.json", client),
+            servers,
+            ..AiClientConfig::default()
+        };
+        let github = "npx -y @modelcontextprotocol/server-github";
+        let mut configs = vec![
+            config(
+                "Cursor",
+                vec![server("github", github), server("docs", "docs-mcp")],
+            ),
+            config(
+                "Claude Code",
+                vec![server("github", "docker run ghcr.io/github/mcp")],
+            ),
+            config("Claude Code", Vec::new()),
+            config("Codex", vec![server("github", &format!(" {} ", github))]),
+            config("VS Code", Vec::new()),
+        ];
+        mark_drift(&mut configs);
+
+        assert!(configs[0].drift[0].starts_with("github runs `npx"));
+        assert!(configs[0].drift[0].ends_with("in Claude Code"));
+        assert_eq!(configs[0].drift.len(), 1);
+        assert_eq!(configs[1].drift[0], "no docs server; Cursor defines it");
+        assert_eq!(configs[1].drift.len(), 3);
+        assert!(configs[2].drift.is_empty());
+        assert_eq!(configs[3].drift[0], "no docs server; Cursor defines it");
+        assert!(configs[3].drift[1].ends_with("in Claude Code"));
+        assert!(configs[4].drift.is_empty());
+    }
+
+    #[test]
+    fn notes_differing_models_and_permission_rules() {
+        let config = |client: &str, models: &[&str], permissions: &[&str]| AiClientConfig {
+            client: client.to_string(),
+            models: models.iter().map(|m| m.to_string()).collect(),
+            permissions: permissions.iter().map(|p| p.to_string()).collect(),
+            ..AiClientConfig::default()
+        };
+        let mut configs = vec![
+            config(
+                "Claude Code",
+                &["opus"],
+                &["deny Read(.env)", "deny Bash(rm:*)", "defaultMode plan"],
+            ),
+            config("Cursor", &["sonnet"], &["allow Read(.env)"]),
+            config(
+                "Codex",
+                &["gpt-5-mini (profile fast)"],
+                &["approval_policy never"],
+            ),
+        ];
+        mark_drift(&mut configs);
+
+        assert_eq!(
+            configs[0].drift,
+            [
+                "model `opus` here, `sonnet` in Cursor",
+                "Read(.env) is `deny` here, `allow` in Cursor",
+            ]
+        );
+        assert_eq!(
+            configs[1].drift,
+            [
+                "no rule for Bash(rm:*); Claude Code denies it",
+                "model `sonnet` here, `opus` in Claude Code",
+                "Read(.env) is `allow` here, `deny` in Claude Code",
+            ]
+        );
+        assert!(configs[2].drift.is_empty());
+    }
+}
@@ -1,4 +1,11 @@
-use crate::dashboard::{ActionCommand, ActionKind, McpServerHealth, ProviderKind, ProviderUsage};
+use super::bigquery;
+use super::mcp_lint;
+use super::CollectorResult;
+use crate::config::ProvidersConfig;
+use crate::credentials;
+use crate::dashboard::{
+    ActionCommand, ActionKind, LintSeverity, McpServerHealth, ProviderKind, ProviderUsage,
+};
 use crate::git::Repo;
 use crate::path_utils::{extract_command_binary, resolve_binary_in_path};
 use chrono::{Datelike, Duration as ChronoDuration, TimeZone, Utc};
@@ -56,8 +63,40 @@ impl ProviderApiCache {
 }
 
 static PROVIDER_API_CACHE: OnceLock<Mutex<ProviderApiCache>> = OnceLock::new();
+static PROVIDER_SETTINGS: OnceLock<Mutex<ProvidersConfig>> = OnceLock::new();
 
-pub fn collect_mcp_servers(repos: &[Repo]) -> Vec<McpServerHealth> {
+fn provider_settings() -> &'static Mutex<ProvidersConfig> {
+    PROVIDER_SETTINGS.get_or_init(|| Mutex::new(ProvidersConfig::default()))
+}
+
+/// Apply `[providers]` config to later collections. Returns `true` when the
+/// settings changed, so callers can drop snapshots taken under the old ones.
+pub fn set_provider_settings(cfg: &ProvidersConfig) -> bool {
+    match provider_settings().lock() {
+        Ok(mut current) if *current != *cfg => {
+            *current = cfg.clone();
+            // Live results fetched under other filters no longer apply.
+            if let Ok(mut cache) = PROVIDER_API_CACHE
+                .get_or_init(|| Mutex::new(ProviderApiCache::default()))
+                .lock()
+            {
+                *cache = ProviderApiCache::default();
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// `[providers] privacy_mode`: no tokens are read and no APIs called.
+pub(super) fn privacy_mode() -> bool {
+    provider_settings()
+        .lock()
+        .map(|settings| settings.privacy_mode)
+        .unwrap_or(true)
+}
+
+pub fn collect_mcp_servers(repos: &[Repo]) -> CollectorResult<McpServerHealth> {
     let mut config_paths = BTreeSet::new();
     for p in candidate_global_mcp_paths() {
         if p.exists() {
@@ -80,6 +119,8 @@ pub fn collect_mcp_servers(repos: &[Repo]) -> Vec<McpServerHealth> {
     }
 
     let mut out = Vec::new();
+    // Server rows, for the duplicate-name check across files.
+    let mut defined = Vec::new();
     for path in config_paths {
         let raw = match fs::read_to_string(&path) {
             Ok(s) => s,
@@ -91,6 +132,7 @@ pub fn collect_mcp_servers(repos: &[Repo]) -> Vec<McpServerHealth> {
                     healthy: false,
                     detail: e.to_string(),
                     action: None,
+                    lint: Vec::new(),
                 });
                 continue;
             }
@@ -106,6 +148,7 @@ pub fn collect_mcp_servers(repos: &[Repo]) -> Vec<McpServerHealth> {
                     healthy: false,
                     detail: e.to_string(),
                     action: None,
+                    lint: Vec::new(),
                 });
                 continue;
             }
@@ -120,11 +163,17 @@ pub fn collect_mcp_servers(repos: &[Repo]) -> Vec<McpServerHealth> {
                 healthy: false,
                 detail: "No mcpServers/servers entries found".to_string(),
                 action: None,
+                lint: Vec::new(),
             });
             continue;
         }
 
-        for (name, command) in servers {
+        let source = path.to_string_lossy().to_string();
+        for entry in servers {
+            let line = mcp_lint::key_line(&raw, entry.section, 1)
+                .and_then(|from| mcp_lint::key_line(&raw, &entry.name, from));
+            let lint = mcp_lint::lint_server(&entry.name, entry.config, &raw, line);
+            let command = entry.command;
             let (healthy, detail, binary) = check_server_command(&command);
             let action = if healthy {
                 if command.starts_with("http://") || command.starts_with("https://") {
@@ -148,62 +197,108 @@ pub fn collect_mcp_servers(repos: &[Repo]) -> Vec<McpServerHealth> {
                 ))
             };
 
+            defined.push((out.len(), (source.clone(), entry.name.clone(), line)));
             out.push(McpServerHealth {
-                source: path.to_string_lossy().to_string(),
-                server_name: name,
+                source: source.clone(),
+                server_name: entry.name,
                 command,
-                healthy,
+                healthy: healthy && !lint.iter().any(|f| f.severity == LintSeverity::Error),
                 detail,
                 action,
+                lint,
             });
         }
     }
 
+    let (rows, names): (Vec<usize>, Vec<_>) = defined.into_iter().unzip();
+    for (idx, found) in mcp_lint::duplicate_findings(&names) {
+        out[rows[idx]].lint.push(found);
+    }
+
     out.sort_by(|a, b| {
         a.healthy
             .cmp(&b.healthy)
             .then_with(|| a.server_name.cmp(&b.server_name))
             .then_with(|| a.source.cmp(&b.source))
     });
-    out
+    Ok(out)
 }
 
-pub fn collect_provider_usage() -> Vec<ProviderUsage> {
+pub fn collect_provider_usage() -> CollectorResult<ProviderUsage> {
+    let settings = provider_settings()
+        .lock()
+        .map(|s| s.clone())
+        .unwrap_or_default();
     let window = report_window();
 
-    vec![
-        collect_provider(
-            ProviderKind::Claude,
-            &["ANTHROPIC_ADMIN_API_KEY", "ANTHROPIC_API_KEY"],
-            &candidate_claude_roots(),
-            3.0,
-            15.0,
-            &window,
-            Some(fetch_claude_live_data),
-        ),
-        collect_provider(
-            ProviderKind::Gemini,
-            &[
-                "GEMINI_API_KEY",
-                "GOOGLE_API_KEY",
-                "AGENTPULSE_GEMINI_BQ_TABLE",
-            ],
-            &candidate_gemini_roots(),
-            1.25,
-            5.0,
-            &window,
-            Some(fetch_gemini_live_data),
-        ),
-        collect_provider(
-            ProviderKind::OpenAi,
-            &["OPENAI_ADMIN_KEY", "OPENAI_API_KEY"],
-            &candidate_openai_roots(),
-            5.0,
-            15.0,
-            &window,
-            Some(fetch_openai_live_data),
-        ),
-    ]
+    let mut out = Vec::new();
+    for provider in [
+        ProviderKind::Claude,
+        ProviderKind::Gemini,
+        ProviderKind::OpenAi,
+    ] {
+        if !settings.allows(provider.as_str()) {
+            out.push(disabled_provider(provider, settings.privacy_mode));
+            continue;
+        }
+        out.push(match provider {
+            ProviderKind::Claude => collect_provider(
+                ProviderKind::Claude,
+                &["ANTHROPIC_ADMIN_API_KEY", "ANTHROPIC_API_KEY"],
+                &candidate_claude_roots(),
+                3.0,
+                15.0,
+                &window,
+                Some(fetch_claude_live_data),
+            ),
+            ProviderKind::Gemini => collect_provider(
+                ProviderKind::Gemini,
+                &[
+                    "GEMINI_API_KEY",
+                    "GOOGLE_API_KEY",
+                    "AGENTPULSE_GEMINI_BQ_TABLE",
+                ],
+                &candidate_gemini_roots(),
+                1.25,
+                5.0,
+                &window,
+                Some(fetch_gemini_live_data),
+            ),
+            ProviderKind::OpenAi => collect_provider(
+                ProviderKind::OpenAi,
+                &["OPENAI_ADMIN_KEY", "OPENAI_API_KEY"],
+                &candidate_openai_roots(),
+                5.0,
+                15.0,
+                &window,
+                Some(fetch_openai_live_data),
+            ),
+        });
+    }
+    Ok(out)
+}
+
+/// Placeholder row for a provider that must not be inspected at all.
+fn disabled_provider(provider: ProviderKind, privacy_mode: bool) -> ProviderUsage {
+    let note = if privacy_mode {
+        "privacy mode: no key, log, or network access"
+    } else {
+        "disabled in [providers] config"
+    };
+    ProviderUsage {
+        provider,
+        configured: false,
+        config_sources: Vec::new(),
+        data_source: "disabled".to_string(),
+        source_updated_at_epoch_secs: 0,
+        sessions: 0,
+        total_input_tokens: 0,
+        total_output_tokens: 0,
+        estimated_cost_usd: 0.0,
+        forecast_usd: None,
+        budget_usd: None,
+        notes: vec![note.to_string()],
+    }
 }
 
 fn collect_provider(
@@ -221,9 +316,9 @@ fn collect_provider(
     let mut source_updated_at_epoch_secs: i64 = 0;
 
     for key in env_keys {
-        if std::env::var(key).is_ok() {
+        if let Some((_, source)) = credentials::lookup(key) {
             configured = true;
-            config_sources.push(format!("env:{}", key));
+            config_sources.push(source);
         }
     }
 
@@ -410,6 +505,8 @@ fn collect_provider(
         total_input_tokens: input_tokens,
         total_output_tokens: output_tokens,
         estimated_cost_usd,
+        forecast_usd: None,
+        budget_usd: None,
         notes,
     }
 }
@@ -471,9 +568,18 @@ fn report_window() -> ReportWindow {
 }
 
 fn fetch_openai_live_data(window: &ReportWindow) -> LiveFetchResult {
-    let Some(api_key) = first_env_value(&["OPENAI_ADMIN_KEY", "OPENAI_API_KEY"]) else {
+    let Some(api_key) = credentials::first_value(&["OPENAI_ADMIN_KEY", "OPENAI_API_KEY"]) else {
         return Ok(None);
     };
+    let (project_ids, key_ids) = provider_settings()
+        .lock()
+        .map(|cfg| {
+            (
+                cfg.openai_project_ids.clone(),
+                cfg.openai_api_key_ids.clone(),
+            )
+        })
+        .unwrap_or_default();
 
     let mut page: Option<String> = None;
     let mut pages = 0usize;
@@ -487,6 +593,8 @@ fn fetch_openai_live_data(window: &ReportWindow) -> LiveFetchResult {
             ("bucket_width", "1d".to_string()),
             ("limit", "31".to_string()),
         ];
+        params.extend(project_ids.iter().map(|id| ("project_ids", id.clone())));
+        params.extend(key_ids.iter().map(|id| ("api_key_ids", id.clone())));
         if let Some(cursor) = page.as_ref() {
             params.push(("page", cursor.clone()));
         }
@@ -539,6 +647,7 @@ fn fetch_openai_live_data(window: &ReportWindow) -> LiveFetchResult {
             ("bucket_width", "1d".to_string()),
             ("limit", "31".to_string()),
         ];
+        params.extend(project_ids.iter().map(|id| ("project_ids", id.clone())));
         if let Some(cursor) = page.as_ref() {
             params.push(("page", cursor.clone()));
         }
@@ -576,19 +685,31 @@ fn fetch_openai_live_data(window: &ReportWindow) -> LiveFetchResult {
         }
     }
 
+    let mut notes = vec!["source: OpenAI org usage/cost APIs".to_string()];
+    notes.extend(filter_notes("project", &project_ids, &key_ids));
     Ok(Some(ProviderLiveData {
         sessions: Some(sessions),
         total_input_tokens: Some(input_tokens),
         total_output_tokens: Some(output_tokens),
         cost_usd: Some(cost_usd),
-        notes: vec!["source: OpenAI org usage/cost APIs".to_string()],
+        notes,
     }))
 }
 
 fn fetch_claude_live_data(window: &ReportWindow) -> LiveFetchResult {
-    let Some(api_key) = first_env_value(&["ANTHROPIC_ADMIN_API_KEY", "ANTHROPIC_API_KEY"]) else {
+    let Some(api_key) = credentials::first_value(&["ANTHROPIC_ADMIN_API_KEY", "ANTHROPIC_API_KEY"])
+    else {
         return Ok(None);
     };
+    let (workspace_ids, key_ids) = provider_settings()
+        .lock()
+        .map(|cfg| {
+            (
+                cfg.claude_workspace_ids.clone(),
+                cfg.claude_api_key_ids.clone(),
+            )
+        })
+        .unwrap_or_default();
 
     let mut page: Option<String> = None;
     let mut pages = 0usize;
@@ -602,6 +723,12 @@ fn fetch_claude_live_data(window: &ReportWindow) -> LiveFetchResult {
             ("bucket_width", "1d".to_string()),
             ("limit", "31".to_string()),
         ];
+        params.extend(
+            workspace_ids
+                .iter()
+                .map(|id| ("workspace_ids[]", id.clone())),
+        );
+        params.extend(key_ids.iter().map(|id| ("api_key_ids[]", id.clone())));
         if let Some(cursor) = page.as_ref() {
             params.push(("page", cursor.clone()));
         }
@@ -654,6 +781,10 @@ fn fetch_claude_live_data(window: &ReportWindow) -> LiveFetchResult {
             ("bucket_width", "1d".to_string()),
             ("limit", "31".to_string()),
         ];
+        // The cost report can't filter by workspace, only group by it.
+        if !workspace_ids.is_empty() {
+            params.push(("group_by[]", "workspace_id".to_string()));
+        }
         if let Some(cursor) = page.as_ref() {
             params.push(("page", cursor.clone()));
         }
@@ -668,7 +799,7 @@ fn fetch_claude_live_data(window: &ReportWindow) -> LiveFetchResult {
             &params,
         )?;
 
-        accumulate_claude_cost(&value, &mut cost_usd);
+        accumulate_claude_cost(&value, &workspace_ids, &mut cost_usd);
 
         pages += 1;
         if pages >= read_env_usize("AGENTPULSE_PROVIDER_MAX_PAGES", 6) {
@@ -691,18 +822,39 @@ fn fetch_claude_live_data(window: &ReportWindow) -> LiveFetchResult {
         }
     }
 
+    let mut notes = vec![
+        "source: Anthropic usage/cost report APIs".to_string(),
+        "session count uses request fields when available".to_string(),
+    ];
+    notes.extend(filter_notes("workspace", &workspace_ids, &key_ids));
     Ok(Some(ProviderLiveData {
         sessions: Some(sessions),
         total_input_tokens: Some(input_tokens),
         total_output_tokens: Some(output_tokens),
         cost_usd: Some(cost_usd),
-        notes: vec![
-            "source: Anthropic usage/cost report APIs".to_string(),
-            "session count uses request fields when available".to_string(),
-        ],
+        notes,
     }))
 }
 
+/// Notes saying which `[providers]` filters narrowed the live numbers.
+fn filter_notes(scope: &str, scope_ids: &[String], key_ids: &[String]) -> Vec<String> {
+    let mut notes = Vec::new();
+    if !scope_ids.is_empty() {
+        notes.push(format!(
+            "filtered to {}(s): {}",
+            scope,
+            scope_ids.join(", ")
+        ));
+    }
+    if !key_ids.is_empty() {
+        notes.push(format!(
+            "usage filtered to {} API key(s); cost is not split by key",
+            key_ids.len()
+        ));
+    }
+    notes
+}
+
 fn fetch_gemini_live_data(window: &ReportWindow) -> LiveFetchResult {
     let Some(table) = std::env::var("AGENTPULSE_GEMINI_BQ_TABLE")
         .ok()
@@ -749,8 +901,44 @@ fn fetch_gemini_live_data(window: &ReportWindow) -> LiveFetchResult {
            AND ({service_filter})"
     );
 
+    let project = std::env::var("AGENTPULSE_GEMINI_BQ_PROJECT")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| table.split('.').next().unwrap_or_default().to_string());
+    // The REST API needs no tools; `bq` is only used for its own login, or
+    // for credentials the API path can't use (service accounts and the like).
+    let has_bq = resolve_binary_in_path("bq").is_some();
+    let (cost_usd, source) = if bigquery::has_credentials() || !has_bq {
+        let timeout = read_env_u64("AGENTPULSE_PROVIDER_TIMEOUT_SECS", 8);
+        match bigquery::query_number(&project, &sql, timeout) {
+            Ok(cost) => (cost, "BigQuery API"),
+            Err(
+                bigquery::BillingError::NoCredentials
+                | bigquery::BillingError::UnsupportedCredentials(_),
+            ) if has_bq => (query_cost_with_bq_cli(&sql)?, "bq CLI"),
+            Err(e) => return Err(e.to_string()),
+        }
+    } else {
+        (query_cost_with_bq_cli(&sql)?, "bq CLI")
+    };
+
+    Ok(Some(ProviderLiveData {
+        sessions: None,
+        total_input_tokens: None,
+        total_output_tokens: None,
+        cost_usd: Some(cost_usd),
+        notes: vec![
+            format!("source: billing export {} via {}", table, source),
+            "Gemini API does not expose org usage/cost endpoint; using billing export totals"
+                .to_string(),
+        ],
+    }))
+}
+
+fn query_cost_with_bq_cli(sql: &str) -> Result<f64, String> {
     let output = Command::new("bq")
-        .args(["query", "--use_legacy_sql=false", "--format=json", &sql])
+        .args(["query", "--use_legacy_sql=false", "--format=json", sql])
         .output()
         .map_err(|e| format!("failed to run bq CLI: {}", e))?;
 
@@ -774,18 +962,7 @@ fn fetch_gemini_live_data(window: &ReportWindow) -> LiveFetchResult {
             }
         }
     }
-
-    Ok(Some(ProviderLiveData {
-        sessions: None,
-        total_input_tokens: None,
-        total_output_tokens: None,
-        cost_usd: Some(cost_usd),
-        notes: vec![
-            format!("source: BigQuery billing export ({})", table),
-            "Gemini API does not expose org usage/cost endpoint; using billing export totals"
-                .to_string(),
-        ],
-    }))
+    Ok(cost_usd)
 }
 
 fn http_get_json(
@@ -805,22 +982,22 @@ fn http_get_json(
         full_url.push_str(&joined);
     }
 
-    let mut cmd = Command::new("curl");
-    cmd.arg("--silent")
-        .arg("--show-error")
-        .arg("--fail-with-body")
-        .arg("--connect-timeout")
-        .arg(timeout_secs.min(4).to_string())
-        .arg("--max-time")
-        .arg(timeout_secs.to_string());
-
-    for (k, v) in headers {
-        cmd.arg("-H").arg(format!("{}: {}", k, v));
-    }
-    cmd.arg(&full_url);
-
-    let output = cmd
-        .output()
+    let args = [
+        "--silent".to_string(),
+        "--show-error".to_string(),
+        "--fail-with-body".to_string(),
+        "--connect-timeout".to_string(),
+        timeout_secs.min(4).to_string(),
+        "--max-time".to_string(),
+        timeout_secs.to_string(),
+        full_url,
+    ];
+    // Headers carry the API keys, so they go through curl's stdin.
+    let secrets: Vec<(&str, String)> = headers
+        .iter()
+        .map(|(k, v)| ("header", format!("{}: {}", k, v)))
+        .collect();
+    let output = super::curl_with_secrets(&args, &secrets)
         .map_err(|e| format!("failed to run curl for {}: {}", url, e))?;
     if !output.status.success() {
         let mut detail = String::from_utf8_lossy(&output.stderr).trim().to_string();
@@ -844,7 +1021,7 @@ fn http_get_json(
         .map_err(|e| format!("failed to parse provider response json: {}", e))
 }
 
-fn url_encode_component(value: &str) -> String {
+pub(super) fn url_encode_component(value: &str) -> String {
     let mut out = String::new();
     for b in value.bytes() {
         if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
@@ -966,11 +1143,19 @@ fn accumulate_claude_usage_metrics(
     }
 }
 
-fn accumulate_claude_cost(value: &Value, cost_usd: &mut f64) {
+/// Sum cost-report amounts (cents); with `workspaces`, only results grouped
+/// under one of them count.
+fn accumulate_claude_cost(value: &Value, workspaces: &[String], cost_usd: &mut f64) {
     if let Some(buckets) = value.get("data").and_then(Value::as_array) {
         for bucket in buckets {
             if let Some(results) = bucket.get("results").and_then(Value::as_array) {
                 for result in results {
+                    let workspace = result.get("workspace_id").and_then(Value::as_str);
+                    if !workspaces.is_empty()
+                        && !workspace.is_some_and(|id| workspaces.iter().any(|w| w == id))
+                    {
+                        continue;
+                    }
                     if let Some(amount) = result.get("amount") {
                         let cents = value_as_f64(amount).unwrap_or(0.0);
                         *cost_usd += cents / 100.0;
@@ -1012,13 +1197,6 @@ fn value_as_f64(v: &Value) -> Option<f64> {
     v.as_u64().map(|n| n as f64)
 }
 
-fn first_env_value(keys: &[&str]) -> Option<String> {
-    keys.iter()
-        .filter_map(|k| std::env::var(k).ok())
-        .map(|v| v.trim().to_string())
-        .find(|v| !v.is_empty())
-}
-
 fn read_env_u64(key: &str, default_value: u64) -> u64 {
     std::env::var(key)
         .ok()
@@ -1323,11 +1501,21 @@ fn collect_jsonl_files_recursive(
     }
 }
 
-fn extract_mcp_servers(value: &Value) -> Vec<(String, String)> {
+/// One server entry of an MCP config file.
+pub(super) struct McpEntry<'a> {
+    /// `mcpServers` or `servers`.
+    pub(super) section: &'static str,
+    pub(super) name: String,
+    pub(super) config: &'a Value,
+    /// Command line with args, or the url; empty when neither is set.
+    pub(super) command: String,
+}
+
+pub(super) fn extract_mcp_servers(value: &Value) -> Vec<McpEntry<'_>> {
     let mut out = Vec::new();
 
-    for top_key in ["mcpServers", "servers"] {
-        let Some(obj) = value.get(top_key).and_then(|v| v.as_object()) else {
+    for section in ["mcpServers", "servers"] {
+        let Some(obj) = value.get(section).and_then(|v| v.as_object()) else {
             continue;
         };
 
@@ -1356,8 +1544,13 @@ fn extract_mcp_servers(value: &Value) -> Vec<(String, String)> {
                         command = format!("{} {}", command, suffix);
                     }
                 }
-                out.push((name.clone(), command));
             }
+            out.push(McpEntry {
+                section,
+                name: name.clone(),
+                config: cfg,
+                command,
+            });
         }
     }
 
@@ -1448,7 +1641,7 @@ fn walk_usage_files(path: &Path, depth: usize, max_depth: usize, out: &mut Vec<P
     }
 }
 
-fn home_join(path: &str) -> Option<PathBuf> {
+pub(super) fn home_join(path: &str) -> Option<PathBuf> {
     dirs::home_dir().map(|h| h.join(path))
 }
 
@@ -1517,6 +1710,23 @@ fn candidate_openai_roots() -> Vec<PathBuf> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn privacy_mode_disables_every_provider() {
+        let private = ProvidersConfig {
+            privacy_mode: true,
+            ..ProvidersConfig::default()
+        };
+        assert!(set_provider_settings(&private));
+        assert!(!set_provider_settings(&private));
+        let providers = collect_provider_usage().unwrap();
+        set_provider_settings(&ProvidersConfig::default());
+
+        assert_eq!(providers.len(), 3);
+        assert!(providers
+            .iter()
+            .all(|p| p.data_source == "disabled" && p.config_sources.is_empty()));
+    }
+
     #[test]
     fn extracts_mcp_servers() {
         let raw = r#"{
@@ -1528,7 +1738,10 @@ mod tests {
         let value: Value = serde_json::from_str(raw).unwrap();
         let servers = extract_mcp_servers(&value);
         assert_eq!(servers.len(), 2);
-        assert!(servers.iter().any(|(n, _)| n == "github"));
+        assert!(servers.iter().any(|e| e.name == "github"));
+        assert!(servers
+            .iter()
+            .any(|e| e.command == "http://localhost:3000/mcp"));
     }
 
     #[test]
@@ -1598,8 +1811,24 @@ mod tests {
         )
         .unwrap();
         let mut usd = 0.0;
-        accumulate_claude_cost(&cost, &mut usd);
+        accumulate_claude_cost(&cost, &[], &mut usd);
         assert!((usd - 1.7345).abs() < 0.0001);
+
+        let grouped: Value = serde_json::from_str(
+            r#"{
+              "data": [
+                { "results": [
+                  { "amount": "200", "workspace_id": "wrkspc_mine" },
+                  { "amount": "900", "workspace_id": "wrkspc_other" },
+                  { "amount": "50", "workspace_id": null }
+                ] }
+              ]
+            }"#,
+        )
+        .unwrap();
+        let mut mine = 0.0;
+        accumulate_claude_cost(&grouped, &["wrkspc_mine".to_string()], &mut mine);
+        assert!((mine - 2.0).abs() < 0.0001);
     }
 
     #[test]
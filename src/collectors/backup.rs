@@ -0,0 +1,231 @@
+//! Backup check for the repos listed in `[backup] critical_repos`: every
+//! commit on a local branch should be contained in some remote-tracking
+//! branch, i.e. exist somewhere besides this machine. Anything that is not
+//! raises a critical alert with a push to fix it.
+//!
+//! Remote-tracking refs are what this clone last saw of its remotes, so a
+//! commit counts as backed up once it has been pushed or fetched; no network
+//! is touched.
+
+use super::CollectorResult;
+use crate::agent;
+use crate::config::BackupConfig;
+use crate::dashboard::{ActionCommand, ActionKind, DashboardAlert};
+use crate::git::{std_git_command, Repo};
+use crate::i18n::tr;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+static BACKUP_SETTINGS: OnceLock<Mutex<BackupConfig>> = OnceLock::new();
+
+fn backup_settings() -> BackupConfig {
+    BACKUP_SETTINGS
+        .get_or_init(|| Mutex::new(BackupConfig::default()))
+        .lock()
+        .map(|cfg| cfg.clone())
+        .unwrap_or_default()
+}
+
+/// Apply `[backup]` config to later collections.
+pub fn configure_backup(cfg: &BackupConfig) {
+    if let Ok(mut current) = BACKUP_SETTINGS
+        .get_or_init(|| Mutex::new(BackupConfig::default()))
+        .lock()
+    {
+        *current = cfg.clone();
+    }
+}
+
+/// Commits that exist only in this clone.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LocalOnly {
+    /// Commits on local branches (or HEAD) that no remote-tracking ref contains.
+    pub commits: usize,
+    /// Whether HEAD itself is contained in a remote-tracking ref.
+    pub head_on_remote: bool,
+    /// Local branches holding such commits, with how many each.
+    pub branches: Vec<(String, usize)>,
+}
+
+fn git_stdout(repo_path: &Path, args: &[&str]) -> Option<String> {
+    let out = std_git_command(args).current_dir(repo_path).output().ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+fn count(repo_path: &Path, args: &[&str]) -> usize {
+    git_stdout(repo_path, args)
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0)
+}
+
+/// `None` when everything is on a remote (or the repo has no commits).
+pub fn local_only(repo_path: &Path) -> Option<LocalOnly> {
+    let commits = count(
+        repo_path,
+        &[
+            "rev-list",
+            "--count",
+            "HEAD",
+            "--branches",
+            "--not",
+            "--remotes",
+        ],
+    );
+    if commits == 0 {
+        return None;
+    }
+    let head_on_remote = git_stdout(
+        repo_path,
+        &[
+            "for-each-ref",
+            "--count=1",
+            "--contains=HEAD",
+            "--format=%(refname)",
+            "refs/remotes",
+        ],
+    )
+    .is_some_and(|refs| !refs.is_empty());
+    let branches = git_stdout(
+        repo_path,
+        &["for-each-ref", "--format=%(refname:short)", "refs/heads"],
+    )
+    .unwrap_or_default()
+    .lines()
+    .filter_map(|branch| {
+        let only_here = count(
+            repo_path,
+            &["rev-list", "--count", branch, "--not", "--remotes"],
+        );
+        (only_here > 0).then(|| (branch.to_string(), only_here))
+    })
+    .collect();
+    Some(LocalOnly {
+        commits,
+        head_on_remote,
+        branches,
+    })
+}
+
+fn backup_alert(repo: &Repo, found: &LocalOnly) -> DashboardAlert {
+    let mut detail = Vec::new();
+    if !repo.status.has_remote {
+        detail.push("no remote configured".to_string());
+    } else if !found.head_on_remote {
+        detail.push(format!("HEAD ({}) is on no remote", repo.status.branch));
+    }
+    if !found.branches.is_empty() {
+        let branches: Vec<String> = found
+            .branches
+            .iter()
+            .map(|(branch, commits)| format!("{} ({})", branch, commits))
+            .collect();
+        detail.push(format!("only here: {}", branches.join(", ")));
+    }
+    if repo.status.uncommitted_count > 0 {
+        detail.push(format!(
+            "plus {} uncommitted file(s)",
+            repo.status.uncommitted_count
+        ));
+    }
+
+    let repo_path = repo.path.to_string_lossy().to_string();
+    // Push the checked-out branch first when it is one of the unbacked ones.
+    let branch = found
+        .branches
+        .iter()
+        .map(|(branch, _)| branch)
+        .find(|branch| **branch == repo.status.branch)
+        .or(found.branches.first().map(|(branch, _)| branch));
+    let action = match (repo.status.has_remote, branch) {
+        (true, Some(branch)) => Some(ActionCommand::new(
+            format!("push {} to back it up", branch),
+            ActionKind::GitPushSetUpstream {
+                repo_path,
+                remote: repo.status.publish_remote().to_string(),
+                branch: branch.clone(),
+            },
+        )),
+        (false, _) => agent::recommended_action_kind(repo)
+            .filter(|kind| matches!(kind, ActionKind::CreateRemoteRepo { .. }))
+            .map(|kind| ActionCommand::new("create a remote to back it up", kind)),
+        (true, None) => None,
+    };
+
+    DashboardAlert {
+        severity: "critical".to_string(),
+        title: tr(
+            "alert-backup-title",
+            &[("repo", &repo.name), ("count", &found.commits)],
+        ),
+        detail: format!("critical repo · {}", detail.join(" · ")),
+        repo: Some(repo.name.clone()),
+        action,
+    }
+}
+
+pub fn collect_backup_alerts(repos: &[Repo]) -> CollectorResult<DashboardAlert> {
+    super::require_git("backup", repos)?;
+    let cfg = backup_settings();
+    Ok(repos
+        .iter()
+        .filter(|repo| cfg.is_critical(&repo.name, &repo.path))
+        .filter_map(|repo| Some(backup_alert(repo, &local_only(&repo.path)?)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn flags_commits_that_no_remote_has_seen() {
+        let base = std::env::temp_dir().join("agentpulse_backup_test");
+        let _ = fs::remove_dir_all(&base);
+        let remote = base.join("remote.git");
+        let work = base.join("work");
+        fs::create_dir_all(&work).unwrap();
+        let git = |dir: &Path, args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap()
+        };
+        git(&base, &["init", "--bare", "-b", "main", "remote.git"]);
+        git(&work, &["init", "-b", "main"]);
+        git(&work, &["config", "user.email", "test@test.com"]);
+        git(&work, &["config", "user.name", "Test"]);
+        git(
+            &work,
+            &["remote", "add", "origin", remote.to_str().unwrap()],
+        );
+        git(&work, &["commit", "--allow-empty", "-m", "first"]);
+        git(&work, &["push", "-u", "origin", "main"]);
+        assert_eq!(local_only(&work), None);
+
+        git(&work, &["switch", "-c", "experiment"]);
+        git(&work, &["commit", "--allow-empty", "-m", "idea"]);
+        git(&work, &["commit", "--allow-empty", "-m", "more"]);
+        git(&work, &["switch", "main"]);
+        let found = local_only(&work).unwrap();
+        assert_eq!(found.commits, 2);
+        assert!(found.head_on_remote);
+        assert_eq!(found.branches, [("experiment".to_string(), 2)]);
+
+        let mut repo = Repo::new(work.clone());
+        repo.status.branch = "main".to_string();
+        repo.status.has_remote = true;
+        let alert = backup_alert(&repo, &found);
+        assert_eq!(alert.severity, "critical");
+        assert_eq!(alert.title, "work has 2 commit(s) only on this machine");
+        assert_eq!(alert.detail, "critical repo · only here: experiment (2)");
+        assert!(matches!(
+            alert.action.unwrap().action,
+            ActionKind::GitPushSetUpstream { branch, .. } if branch == "experiment"
+        ));
+        let _ = fs::remove_dir_all(&base);
+    }
+}
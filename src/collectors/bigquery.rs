@@ -0,0 +1,337 @@
+//! Gemini spend from the Cloud Billing export through the BigQuery REST API.
+//!
+//! Authenticates with a ready token in `GOOGLE_OAUTH_ACCESS_TOKEN`, else the
+//! application-default credentials `gcloud auth application-default login`
+//! writes (or `GOOGLE_APPLICATION_CREDENTIALS` points at), so neither `bq`
+//! nor `gcloud` has to be installed; other credential types fall back to
+//! `bq` when it is. Requests go through `curl` like the other provider
+//! APIs; Google's error bodies are kept so a missing permission is reported
+//! as such rather than as an HTTP status.
+
+use crate::credentials;
+use serde_json::Value;
+use std::fmt;
+use std::path::PathBuf;
+
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+
+/// Why the billing export could not be read.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum BillingError {
+    /// No access token and no application-default credentials.
+    NoCredentials,
+    /// Application-default credentials of a type that needs request signing
+    /// (service account keys, workload identity federation).
+    UnsupportedCredentials(String),
+    /// The refresh token was revoked or has expired.
+    CredentialsRejected(String),
+    /// The account lacks a BigQuery permission in `project`.
+    PermissionDenied {
+        project: String,
+        message: String,
+    },
+    /// The billing export table, its dataset, or the project is missing.
+    NotFound(String),
+    /// The query did not finish within the request timeout.
+    Timeout,
+    Request(String),
+}
+
+impl fmt::Display for BillingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const LOGIN: &str = "run `gcloud auth application-default login`";
+        match self {
+            BillingError::NoCredentials => write!(
+                f,
+                "no Google credentials: {} or set GOOGLE_OAUTH_ACCESS_TOKEN",
+                LOGIN
+            ),
+            BillingError::UnsupportedCredentials(kind) => write!(
+                f,
+                "application-default credentials of type {} are not supported: {} \
+                 or set GOOGLE_OAUTH_ACCESS_TOKEN",
+                kind, LOGIN
+            ),
+            BillingError::CredentialsRejected(detail) => write!(
+                f,
+                "Google rejected the application-default credentials ({}): {} again",
+                detail, LOGIN
+            ),
+            BillingError::PermissionDenied { project, message } => write!(
+                f,
+                "BigQuery permission denied in {}: {} (needs roles/bigquery.jobUser on the \
+                 project and roles/bigquery.dataViewer on the billing export dataset)",
+                project, message
+            ),
+            BillingError::NotFound(message) => write!(f, "billing export not found: {}", message),
+            BillingError::Timeout => write!(f, "BigQuery query did not finish in time"),
+            BillingError::Request(message) => f.write_str(message),
+        }
+    }
+}
+
+/// A bearer token, and the project to bill API quota to when the
+/// credentials name one.
+#[derive(Debug, Clone, PartialEq)]
+struct AccessToken {
+    token: String,
+    quota_project: Option<String>,
+}
+
+/// The parts of an `authorized_user` credentials file a refresh needs.
+#[derive(Debug, Clone, PartialEq)]
+struct AuthorizedUser {
+    client_id: String,
+    client_secret: String,
+    refresh_token: String,
+    quota_project: Option<String>,
+}
+
+/// `GOOGLE_APPLICATION_CREDENTIALS`, else where gcloud keeps them.
+fn adc_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("GOOGLE_APPLICATION_CREDENTIALS") {
+        return Some(PathBuf::from(path));
+    }
+    let dir = if cfg!(windows) {
+        dirs::config_dir()?.join("gcloud")
+    } else {
+        dirs::home_dir()?.join(".config").join("gcloud")
+    };
+    Some(dir.join("application_default_credentials.json"))
+}
+
+/// Whether a token or credentials file is there to try; without one the
+/// `bq` CLI may still have its own login.
+pub(super) fn has_credentials() -> bool {
+    credentials::lookup("GOOGLE_OAUTH_ACCESS_TOKEN").is_some()
+        || adc_path().is_some_and(|path| path.is_file())
+}
+
+fn parse_adc(value: &Value) -> Result<AuthorizedUser, BillingError> {
+    let kind = value
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    if kind != "authorized_user" {
+        return Err(BillingError::UnsupportedCredentials(kind.to_string()));
+    }
+    let field = |key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| {
+                BillingError::Request(format!("application-default credentials lack {}", key))
+            })
+    };
+    Ok(AuthorizedUser {
+        client_id: field("client_id")?,
+        client_secret: field("client_secret")?,
+        refresh_token: field("refresh_token")?,
+        quota_project: field("quota_project_id").ok(),
+    })
+}
+
+fn access_token(timeout_secs: u64) -> Result<AccessToken, BillingError> {
+    if let Some((token, _)) = credentials::lookup("GOOGLE_OAUTH_ACCESS_TOKEN") {
+        return Ok(AccessToken {
+            token,
+            quota_project: None,
+        });
+    }
+    let raw = adc_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .ok_or(BillingError::NoCredentials)?;
+    let value: Value = serde_json::from_str(&raw).map_err(|e| {
+        BillingError::Request(format!("unreadable application-default credentials: {}", e))
+    })?;
+    let user = parse_adc(&value)?;
+    // The refresh token goes through curl's stdin, not the process list.
+    let encode = super::ai_mcp::url_encode_component;
+    let form = format!(
+        "client_id={}&client_secret={}&refresh_token={}&grant_type=refresh_token",
+        encode(&user.client_id),
+        encode(&user.client_secret),
+        encode(&user.refresh_token)
+    );
+    let response = curl_json(&[TOKEN_URL.to_string()], &[("data", form)], timeout_secs)?;
+    match response.get("access_token").and_then(Value::as_str) {
+        Some(token) => Ok(AccessToken {
+            token: token.to_string(),
+            quota_project: user.quota_project,
+        }),
+        None => Err(BillingError::CredentialsRejected(
+            response
+                .get("error_description")
+                .or_else(|| response.get("error"))
+                .and_then(Value::as_str)
+                .unwrap_or("no access token in response")
+                .to_string(),
+        )),
+    }
+}
+
+/// Run a standard-SQL query billed to `project` and return the first cell
+/// of the first row as a number (0 without rows).
+pub(super) fn query_number(
+    project: &str,
+    sql: &str,
+    timeout_secs: u64,
+) -> Result<f64, BillingError> {
+    let token = access_token(timeout_secs)?;
+    let body = serde_json::json!({
+        "query": sql,
+        "useLegacySql": false,
+        "timeoutMs": timeout_secs * 1000,
+    });
+    let mut args = vec![
+        "-H".to_string(),
+        "Content-Type: application/json".to_string(),
+    ];
+    if let Some(quota) = &token.quota_project {
+        args.extend(["-H".to_string(), format!("x-goog-user-project: {}", quota)]);
+    }
+    args.extend([
+        "--data".to_string(),
+        body.to_string(),
+        format!(
+            "https://bigquery.googleapis.com/bigquery/v2/projects/{}/queries",
+            super::ai_mcp::url_encode_component(project)
+        ),
+    ]);
+    // The query may use all of `timeoutMs` before answering.
+    let auth = ("header", format!("Authorization: Bearer {}", token.token));
+    let response = curl_json(&args, &[auth], timeout_secs + 4)?;
+    parse_query_response(&response, project)
+}
+
+/// `POST projects/{project}/queries` answer: the number, or Google's error.
+fn parse_query_response(value: &Value, project: &str) -> Result<f64, BillingError> {
+    if let Some(error) = value.get("error") {
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("request failed")
+            .to_string();
+        let status = error.get("status").and_then(Value::as_str);
+        let code = error.get("code").and_then(Value::as_u64);
+        return Err(match (status, code) {
+            (Some("PERMISSION_DENIED"), _) | (_, Some(403)) => BillingError::PermissionDenied {
+                project: project.to_string(),
+                message,
+            },
+            (Some("UNAUTHENTICATED"), _) | (_, Some(401)) => {
+                BillingError::CredentialsRejected(message)
+            }
+            (Some("NOT_FOUND"), _) | (_, Some(404)) => BillingError::NotFound(message),
+            _ => BillingError::Request(format!("BigQuery: {}", message)),
+        });
+    }
+    if value.get("jobComplete").and_then(Value::as_bool) == Some(false) {
+        return Err(BillingError::Timeout);
+    }
+    let Some(cell) = value.pointer("/rows/0/f/0/v") else {
+        return Ok(0.0);
+    };
+    match cell {
+        Value::Null => Ok(0.0),
+        Value::String(text) => text
+            .parse()
+            .map_err(|_| BillingError::Request(format!("BigQuery returned {:?}", text))),
+        other => other
+            .as_f64()
+            .ok_or_else(|| BillingError::Request(format!("BigQuery returned {}", other))),
+    }
+}
+
+/// POST with curl and parse the body whatever the status, since Google
+/// explains failures in it. `secrets` are handed to curl on stdin.
+fn curl_json(
+    args: &[String],
+    secrets: &[(&str, String)],
+    timeout_secs: u64,
+) -> Result<Value, BillingError> {
+    let mut all = vec![
+        "--silent".to_string(),
+        "--show-error".to_string(),
+        "--connect-timeout".to_string(),
+        timeout_secs.min(4).to_string(),
+        "--max-time".to_string(),
+        timeout_secs.to_string(),
+    ];
+    all.extend_from_slice(args);
+    let output = super::curl_with_secrets(&all, secrets)
+        .map_err(|e| BillingError::Request(format!("failed to run curl: {}", e)))?;
+    if let Ok(value) = serde_json::from_slice::<Value>(&output.stdout) {
+        return Ok(value);
+    }
+    let detail = String::from_utf8_lossy(&output.stderr);
+    let detail = detail.split_whitespace().collect::<Vec<_>>().join(" ");
+    Err(BillingError::Request(if detail.is_empty() {
+        "unexpected response from Google".to_string()
+    } else {
+        detail.chars().take(400).collect()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_only_user_application_default_credentials() {
+        let user = serde_json::json!({
+            "type": "authorized_user",
+            "client_id": "id.apps.googleusercontent.com",
+            "client_secret": "secret",
+            "refresh_token": "1//refresh",
+            "quota_project_id": "billing-prod"
+        });
+        let parsed = parse_adc(&user).unwrap();
+        assert_eq!(parsed.refresh_token, "1//refresh");
+        assert_eq!(parsed.quota_project.as_deref(), Some("billing-prod"));
+
+        let service = serde_json::json!({ "type": "service_account", "private_key": "..." });
+        let err = parse_adc(&service).unwrap_err();
+        assert_eq!(
+            err,
+            BillingError::UnsupportedCredentials("service_account".to_string())
+        );
+        assert!(err.to_string().contains("GOOGLE_OAUTH_ACCESS_TOKEN"));
+    }
+
+    #[test]
+    fn reads_the_total_or_explains_the_failure() {
+        let ok = serde_json::json!({
+            "jobComplete": true,
+            "rows": [{ "f": [{ "v": "42.5" }] }]
+        });
+        assert_eq!(parse_query_response(&ok, "billing-prod"), Ok(42.5));
+        let empty = serde_json::json!({ "jobComplete": true, "rows": [{ "f": [{ "v": null }] }] });
+        assert_eq!(parse_query_response(&empty, "billing-prod"), Ok(0.0));
+        let pending = serde_json::json!({ "jobComplete": false });
+        assert_eq!(
+            parse_query_response(&pending, "billing-prod"),
+            Err(BillingError::Timeout)
+        );
+
+        let denied = serde_json::json!({ "error": {
+            "code": 403,
+            "message": "User does not have bigquery.jobs.create permission in project billing-prod.",
+            "status": "PERMISSION_DENIED"
+        }});
+        let err = parse_query_response(&denied, "billing-prod").unwrap_err();
+        assert!(matches!(err, BillingError::PermissionDenied { .. }));
+        assert!(err.to_string().contains("roles/bigquery.jobUser"));
+
+        let missing = serde_json::json!({ "error": {
+            "code": 404,
+            "message": "Not found: Table billing-prod:export.gcp_billing",
+            "status": "NOT_FOUND"
+        }});
+        assert!(matches!(
+            parse_query_response(&missing, "billing-prod"),
+            Err(BillingError::NotFound(_))
+        ));
+    }
+}
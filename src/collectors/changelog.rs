@@ -0,0 +1,258 @@
+//! Changelog drift: conventional commits that landed after the last
+//! `CHANGELOG.md` update or release tag, plus a draft of the missing section.
+
+use super::CollectorResult;
+use crate::dashboard::{ActionCommand, ActionKind, DashboardAlert};
+use crate::git::{std_git_command, Repo};
+use crate::i18n::tr;
+use crate::path_utils::resolve_binary_in_path;
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+
+const CHANGELOG: &str = "CHANGELOG.md";
+
+/// A parsed `type(scope)!: description` commit subject.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConventionalCommit {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+}
+
+impl ConventionalCommit {
+    /// Types that normally earn a changelog line; anything breaking always does.
+    fn is_notable(&self) -> bool {
+        self.breaking || matches!(self.kind.as_str(), "feat" | "fix" | "perf" | "revert")
+    }
+
+    /// Keep a Changelog section this commit belongs under.
+    fn section(&self) -> &'static str {
+        match self.kind.as_str() {
+            "feat" => "Added",
+            "fix" => "Fixed",
+            "revert" => "Removed",
+            _ => "Changed",
+        }
+    }
+}
+
+pub fn parse_conventional(subject: &str) -> Option<ConventionalCommit> {
+    let (head, description) = subject.split_once(": ")?;
+    let (head, breaking) = match head.strip_suffix('!') {
+        Some(h) => (h, true),
+        None => (head, false),
+    };
+    let (kind, scope) = match head.split_once('(') {
+        Some((kind, rest)) => (kind, Some(rest.strip_suffix(')')?.to_string())),
+        None => (head, None),
+    };
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    let description = description.trim();
+    if description.is_empty() {
+        return None;
+    }
+    Some(ConventionalCommit {
+        kind: kind.to_string(),
+        scope,
+        breaking: breaking || description.starts_with("BREAKING"),
+        description: description.to_string(),
+    })
+}
+
+fn git_stdout(repo_path: &Path, args: &[&str]) -> Option<String> {
+    let out = std_git_command(args).current_dir(repo_path).output().ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+/// Notable conventional commits not yet covered by the changelog: reachable
+/// from HEAD but not from the last commit touching `CHANGELOG.md` or the
+/// latest tag. Empty when the repo has no committed changelog.
+pub fn pending_commits(repo_path: &Path) -> Vec<ConventionalCommit> {
+    if !repo_path.join(CHANGELOG).is_file() {
+        return Vec::new();
+    }
+    let Some(last_update) = git_stdout(repo_path, &["log", "-1", "--format=%H", "--", CHANGELOG])
+        .filter(|sha| !sha.is_empty())
+    else {
+        return Vec::new();
+    };
+    let mut args = vec![
+        "log",
+        "--no-merges",
+        "--format=%s",
+        "HEAD",
+        "--not",
+        &last_update,
+    ];
+    let tag = git_stdout(repo_path, &["describe", "--tags", "--abbrev=0"]);
+    if let Some(tag) = tag.as_deref() {
+        args.push(tag);
+    }
+    git_stdout(repo_path, &args)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(parse_conventional)
+        .filter(ConventionalCommit::is_notable)
+        .collect()
+}
+
+pub fn collect_changelog_alerts(repos: &[Repo]) -> CollectorResult<DashboardAlert> {
+    super::require_git("changelog", repos)?;
+    let mut alerts = Vec::new();
+    for repo in repos {
+        let pending = pending_commits(&repo.path);
+        if pending.is_empty() {
+            continue;
+        }
+        let count = |kind: &str| pending.iter().filter(|c| c.kind == kind).count();
+        alerts.push(DashboardAlert {
+            severity: "info".to_string(),
+            title: tr("alert-changelog-title", &[("repo", &repo.name)]),
+            detail: tr(
+                "alert-changelog-detail",
+                &[
+                    ("count", &pending.len()),
+                    ("file", &CHANGELOG),
+                    ("feat", &count("feat")),
+                    ("fix", &count("fix")),
+                ],
+            ),
+            repo: Some(repo.name.clone()),
+            action: Some(ActionCommand::new(
+                "draft changelog",
+                ActionKind::DraftChangelog {
+                    repo_path: repo.path.to_string_lossy().to_string(),
+                },
+            )),
+        });
+    }
+    Ok(alerts)
+}
+
+/// Render commits as an `[Unreleased]` section in Keep a Changelog layout.
+pub fn render_draft(commits: &[ConventionalCommit]) -> String {
+    let mut out = String::from("## [Unreleased]\n");
+    for section in ["Added", "Changed", "Fixed", "Removed"] {
+        let entries: Vec<_> = commits.iter().filter(|c| c.section() == section).collect();
+        if entries.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n### {}\n", section));
+        for c in entries {
+            let breaking = if c.breaking { "**BREAKING:** " } else { "" };
+            match &c.scope {
+                Some(scope) => {
+                    out.push_str(&format!("- {}{}: {}\n", breaking, scope, c.description))
+                }
+                None => out.push_str(&format!("- {}{}\n", breaking, c.description)),
+            }
+        }
+    }
+    out
+}
+
+/// Write a draft of the missing changelog section to a temp file for review.
+/// Uses `git-cliff --unreleased` when installed, else the built-in parser.
+pub fn write_draft(repo_path: &Path) -> Result<PathBuf> {
+    let name = repo_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "repo".to_string());
+    let out = std::env::temp_dir().join(format!("agentpulse-changelog-{}.md", name));
+
+    if resolve_binary_in_path("git-cliff").is_some() {
+        let status = std::process::Command::new("git-cliff")
+            .args(["--unreleased", "--output"])
+            .arg(&out)
+            .current_dir(repo_path)
+            .output()?;
+        if status.status.success() {
+            return Ok(out);
+        }
+    }
+
+    let pending = pending_commits(repo_path);
+    if pending.is_empty() {
+        return Err(anyhow!("no unlogged conventional commits"));
+    }
+    std::fs::write(&out, render_draft(&pending))?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn parses_conventional_subjects() {
+        assert_eq!(
+            parse_conventional("feat(ui): add heatmap"),
+            Some(ConventionalCommit {
+                kind: "feat".to_string(),
+                scope: Some("ui".to_string()),
+                breaking: false,
+                description: "add heatmap".to_string(),
+            })
+        );
+        assert!(parse_conventional("fix!: drop old flag").unwrap().breaking);
+        assert_eq!(parse_conventional("Merge branch 'main'"), None);
+        assert_eq!(parse_conventional("WIP: stuff"), None);
+        assert_eq!(parse_conventional("feat(ui: broken"), None);
+    }
+
+    #[test]
+    fn draft_groups_by_section() {
+        let commits: Vec<_> = [
+            "feat(cli): add --limit",
+            "fix: handle empty repos",
+            "perf!: rewrite scanner",
+        ]
+        .iter()
+        .filter_map(|s| parse_conventional(s))
+        .collect();
+        assert_eq!(
+            render_draft(&commits),
+            "## [Unreleased]\n\n### Added\n- cli: add --limit\n\n### Changed\n- **BREAKING:** rewrite scanner\n\n### Fixed\n- handle empty repos\n"
+        );
+    }
+
+    #[test]
+    fn pending_commits_start_after_last_changelog_update() {
+        let base = std::env::temp_dir().join("agentpulse_changelog_test");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&base)
+                .output()
+                .unwrap()
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        let commit = |file: &str, msg: &str| {
+            std::fs::write(base.join(file), msg).unwrap();
+            git(&["add", "."]);
+            git(&["commit", "-m", msg]);
+        };
+        commit("a.txt", "feat: before changelog");
+        commit(CHANGELOG, "docs: changelog for 0.1");
+        assert!(pending_commits(&base).is_empty());
+
+        commit("b.txt", "feat(api): new endpoint");
+        commit("c.txt", "chore: bump deps");
+        commit("d.txt", "tidy things up");
+        let pending = pending_commits(&base);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].description, "new endpoint");
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
@@ -0,0 +1,242 @@
+//! Repo template compliance: files every watched repo must have (LICENSE,
+//! CODEOWNERS, CI workflow, lint configs), read from
+//! `~/.config/agentpulse/policy.toml`:
+//!
+//! ```toml
+//! [[require]]
+//! path = "CODEOWNERS"
+//! alternatives = [".github/CODEOWNERS", "docs/CODEOWNERS"]
+//! template = "~/templates/CODEOWNERS"
+//!
+//! [[require]]
+//! path = "rustfmt.toml"
+//! when = "Cargo.toml"
+//! ```
+//!
+//! `*` matches within the last path component, e.g. `.github/workflows/*.yml`.
+
+use super::CollectorResult;
+use crate::config::{default_policy_path, expand_home};
+use crate::dashboard::{ActionCommand, ActionKind, CollectorError, DashboardAlert, ScaffoldFile};
+use crate::git::Repo;
+use crate::i18n::tr;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Policy {
+    #[serde(default)]
+    pub require: Vec<Requirement>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Requirement {
+    /// Path relative to the repo root.
+    pub path: String,
+    /// Other paths that satisfy the requirement just as well.
+    #[serde(default)]
+    pub alternatives: Vec<String>,
+    /// Only required in repos where this path exists.
+    #[serde(default)]
+    pub when: Option<String>,
+    /// File the scaffold action copies in when the requirement is unmet.
+    #[serde(default)]
+    pub template: Option<PathBuf>,
+}
+
+impl Requirement {
+    /// Where the scaffold action writes the template: `path`, or the
+    /// template's file name inside `path`'s directory for wildcard paths.
+    fn scaffold_path(&self, template: &Path) -> String {
+        if !self.path.contains('*') {
+            return self.path.clone();
+        }
+        let name = template
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match self.path.rsplit_once('/') {
+            Some((dir, _)) => format!("{}/{}", dir, name),
+            None => name,
+        }
+    }
+}
+
+/// The policy at the default location; `None` when there is none.
+pub fn load_policy() -> Result<Option<Policy>, CollectorError> {
+    let path = default_policy_path();
+    let Ok(raw) = fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    let mut policy: Policy = toml::from_str(&raw).map_err(|e| {
+        CollectorError::new("compliance", format!("{}: {}", path.display(), e.message()))
+    })?;
+    let home = dirs::home_dir().unwrap_or_default();
+    for req in &mut policy.require {
+        req.template = req.template.take().map(|t| expand_home(t, &home));
+    }
+    Ok(Some(policy))
+}
+
+pub fn collect_compliance_alerts(repos: &[Repo]) -> CollectorResult<DashboardAlert> {
+    Ok(match load_policy()? {
+        Some(policy) => compliance_alerts(repos, &policy),
+        None => Vec::new(),
+    })
+}
+
+/// One info alert per repo missing required files, with a scaffold action
+/// when any of the missing files has a template.
+pub fn compliance_alerts(repos: &[Repo], policy: &Policy) -> Vec<DashboardAlert> {
+    repos
+        .iter()
+        .filter_map(|repo| {
+            let missing = missing_requirements(&repo.path, policy);
+            if missing.is_empty() {
+                return None;
+            }
+            let files: Vec<ScaffoldFile> = missing
+                .iter()
+                .filter_map(|req| {
+                    let template = req.template.as_ref()?;
+                    Some(ScaffoldFile {
+                        path: req.scaffold_path(template),
+                        template: template.to_string_lossy().to_string(),
+                    })
+                })
+                .collect();
+            let action = (!files.is_empty()).then(|| {
+                ActionCommand::new(
+                    format!("scaffold {} file(s)", files.len()),
+                    ActionKind::ScaffoldFiles {
+                        repo_path: repo.path.to_string_lossy().to_string(),
+                        files,
+                    },
+                )
+            });
+            Some(DashboardAlert {
+                severity: "info".to_string(),
+                title: tr(
+                    "alert-policy-title",
+                    &[("repo", &repo.name), ("count", &missing.len())],
+                ),
+                detail: missing
+                    .iter()
+                    .map(|req| req.path.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                repo: Some(repo.name.clone()),
+                action,
+            })
+        })
+        .collect()
+}
+
+/// Requirements that apply to `root` and are not met by any of their paths.
+fn missing_requirements<'a>(root: &Path, policy: &'a Policy) -> Vec<&'a Requirement> {
+    policy
+        .require
+        .iter()
+        .filter(|req| req.when.as_deref().is_none_or(|when| exists(root, when)))
+        .filter(|req| {
+            !std::iter::once(&req.path)
+                .chain(&req.alternatives)
+                .any(|path| exists(root, path))
+        })
+        .collect()
+}
+
+/// Whether `pattern` (relative, `*` allowed in the last component) exists.
+fn exists(root: &Path, pattern: &str) -> bool {
+    if !pattern.contains('*') {
+        return root.join(pattern).exists();
+    }
+    let (dir, name) = match pattern.rsplit_once('/') {
+        Some((dir, name)) => (root.join(dir), name),
+        None => (root.to_path_buf(), pattern),
+    };
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .any(|entry| wildcard_match(name, &entry.file_name().to_string_lossy()))
+}
+
+/// `*` matches any run of characters; everything else matches literally.
+pub(crate) fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_wildcards_in_the_last_component() {
+        assert!(wildcard_match("*.yml", "ci.yml"));
+        assert!(wildcard_match(".eslintrc*", ".eslintrc.json"));
+        assert!(wildcard_match("a*b*c", "axxbyyc"));
+        assert!(!wildcard_match("*.yml", "ci.yaml"));
+        assert!(!wildcard_match("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn reports_missing_files_with_scaffold_for_templated_ones() {
+        let base = std::env::temp_dir().join("agentpulse_compliance_test");
+        let _ = fs::remove_dir_all(&base);
+        let repo_dir = base.join("svc");
+        fs::create_dir_all(repo_dir.join(".github/workflows")).unwrap();
+        fs::write(repo_dir.join("Cargo.toml"), "[package]\n").unwrap();
+        fs::write(repo_dir.join(".github/CODEOWNERS"), "* @team\n").unwrap();
+        fs::write(base.join("ci.yml"), "on: push\n").unwrap();
+
+        let policy: Policy = toml::from_str(&format!(
+            r#"
+            [[require]]
+            path = "LICENSE"
+            [[require]]
+            path = "CODEOWNERS"
+            alternatives = [".github/CODEOWNERS"]
+            [[require]]
+            path = ".github/workflows/*.yml"
+            template = "{}"
+            [[require]]
+            path = "package.json"
+            when = "tsconfig.json"
+            "#,
+            base.join("ci.yml").display()
+        ))
+        .unwrap();
+
+        let alerts = compliance_alerts(&[Repo::new(repo_dir.clone())], &policy);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].title, "svc is missing 2 policy file(s)");
+        assert_eq!(alerts[0].detail, "LICENSE, .github/workflows/*.yml");
+        let action = &alerts[0].action.as_ref().unwrap().action;
+        assert!(matches!(
+            action,
+            ActionKind::ScaffoldFiles { files, .. } if files.len() == 1 && files[0].path == ".github/workflows/ci.yml"
+        ));
+
+        fs::write(repo_dir.join("LICENSE"), "MIT\n").unwrap();
+        fs::write(repo_dir.join(".github/workflows/ci.yml"), "on: push\n").unwrap();
+        assert!(compliance_alerts(&[Repo::new(repo_dir)], &policy).is_empty());
+        let _ = fs::remove_dir_all(&base);
+    }
+}
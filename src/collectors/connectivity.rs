@@ -0,0 +1,330 @@
+//! Opt-in reachability probe of each remote host (`[connectivity]`).
+//!
+//! SSH hosts get `ssh -T` in batch mode, which exercises the network path,
+//! the host key and the key in one go; HTTPS hosts only get a TCP connect,
+//! since credentials are the credential-helper check's business. Every probe
+//! has a hard deadline, hosts are probed in parallel, and results are reused
+//! for `refresh_secs`, so a dead VPN costs one timeout per refresh at most.
+
+use super::auth::{remote_hosts, RemoteHost, RemoteUrl, Transport};
+use super::CollectorResult;
+use crate::config::ConnectivityConfig;
+use crate::dashboard::{HostProbe, ProbeStatus};
+use crate::git::Repo;
+use std::collections::HashMap;
+use std::io::Read;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+static CONNECTIVITY_SETTINGS: OnceLock<Mutex<ConnectivityConfig>> = OnceLock::new();
+
+fn connectivity_settings() -> ConnectivityConfig {
+    CONNECTIVITY_SETTINGS
+        .get_or_init(|| Mutex::new(ConnectivityConfig::default()))
+        .lock()
+        .map(|cfg| cfg.clone())
+        .unwrap_or_default()
+}
+
+/// Apply `[connectivity]` config to later collections.
+pub fn configure_connectivity(cfg: &ConnectivityConfig) {
+    if let Ok(mut current) = CONNECTIVITY_SETTINGS
+        .get_or_init(|| Mutex::new(ConnectivityConfig::default()))
+        .lock()
+    {
+        *current = cfg.clone();
+    }
+}
+
+type ProbeKey = (Transport, String);
+
+/// Last probe per host; the repo list is refreshed from each scan.
+static PROBE_CACHE: OnceLock<Mutex<HashMap<ProbeKey, (Instant, HostProbe)>>> = OnceLock::new();
+
+pub fn collect_host_probes(repos: &[Repo]) -> CollectorResult<HostProbe> {
+    let cfg = connectivity_settings();
+    if !cfg.enabled && cfg.vpn_hosts.is_empty() {
+        return Ok(Vec::new());
+    }
+    let timeout = Duration::from_secs(cfg.timeout_secs.max(1));
+    let refresh = Duration::from_secs(cfg.refresh_secs);
+    // Disabled probing still watches the VPN hosts the user listed.
+    let hosts: Vec<RemoteHost> = remote_hosts(repos)
+        .into_iter()
+        .filter(|remote| cfg.enabled || is_vpn_host(&cfg.vpn_hosts, &remote.url.host))
+        .collect();
+    let cache = PROBE_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cached = cache.lock().map(|c| c.clone()).unwrap_or_default();
+
+    let due: Vec<&RemoteHost> = hosts
+        .iter()
+        .filter(|remote| {
+            cached
+                .get(&key(&remote.url))
+                .is_none_or(|(at, _)| at.elapsed() >= refresh)
+        })
+        .collect();
+    let probed: Vec<HostProbe> = std::thread::scope(|scope| {
+        let handles: Vec<_> = due
+            .iter()
+            .map(|remote| scope.spawn(|| probe_host(remote, timeout)))
+            .collect();
+        handles.into_iter().filter_map(|h| h.join().ok()).collect()
+    });
+    for (remote, probe) in due.iter().zip(probed) {
+        cached.insert(key(&remote.url), (Instant::now(), probe));
+    }
+    if let Ok(mut guard) = cache.lock() {
+        *guard = cached.clone();
+    }
+
+    Ok(hosts
+        .iter()
+        .filter_map(|remote| {
+            let (_, probe) = cached.get(&key(&remote.url))?;
+            Some(HostProbe {
+                repos: remote.repos.clone(),
+                repo_paths: remote.repo_paths.clone(),
+                vpn: is_vpn_host(&cfg.vpn_hosts, &remote.url.host),
+                ..probe.clone()
+            })
+        })
+        .collect())
+}
+
+/// `host` matches an entry exactly, or a `*.suffix` entry by subdomain.
+fn is_vpn_host(patterns: &[String], host: &str) -> bool {
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim().to_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(suffix) => host
+                .strip_suffix(suffix)
+                .is_some_and(|sub| sub.ends_with('.')),
+            None => host == pattern,
+        }
+    })
+}
+
+fn key(url: &RemoteUrl) -> ProbeKey {
+    (url.transport, url.host.clone())
+}
+
+fn probe_host(remote: &RemoteHost, timeout: Duration) -> HostProbe {
+    let started = Instant::now();
+    let (status, detail) = match remote.url.transport {
+        Transport::Ssh => probe_ssh(&remote.url, timeout),
+        Transport::Https => probe_tcp(&remote.url.host, remote.url.port.unwrap_or(443), timeout),
+    };
+    HostProbe {
+        host: remote.url.host.clone(),
+        transport: remote.url.transport.as_str().to_string(),
+        status,
+        detail,
+        latency_ms: started.elapsed().as_millis() as u64,
+        repos: remote.repos.clone(),
+        repo_paths: remote.repo_paths.clone(),
+        vpn: false,
+    }
+}
+
+fn probe_ssh(url: &RemoteUrl, timeout: Duration) -> (ProbeStatus, String) {
+    let mut cmd = Command::new("ssh");
+    cmd.args([
+        "-T",
+        "-o",
+        "BatchMode=yes",
+        "-o",
+        "StrictHostKeyChecking=yes",
+    ])
+    .arg("-o")
+    .arg(format!("ConnectTimeout={}", timeout.as_secs()));
+    if let Some(port) = url.port {
+        cmd.arg("-p").arg(port.to_string());
+    }
+    cmd.arg(format!(
+        "{}@{}",
+        url.user.as_deref().unwrap_or("git"),
+        url.host
+    ));
+    match run_with_deadline(cmd, timeout) {
+        Ok((code, output)) => classify_ssh(code, &output),
+        Err(err) => (
+            ProbeStatus::Unreachable,
+            format!("ssh failed to start: {}", err),
+        ),
+    }
+}
+
+/// Exit code (`None` when killed at the deadline) and combined output.
+fn run_with_deadline(
+    mut cmd: Command,
+    timeout: Duration,
+) -> std::io::Result<(Option<i32>, String)> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let deadline = Instant::now() + timeout;
+    let code = loop {
+        if let Some(status) = child.try_wait()? {
+            break status.code();
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok((None, String::new()));
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    let mut output = String::new();
+    if let Some(mut out) = child.stdout.take() {
+        let _ = out.read_to_string(&mut output);
+    }
+    if let Some(mut err) = child.stderr.take() {
+        let _ = err.read_to_string(&mut output);
+    }
+    Ok((code, output))
+}
+
+/// ssh exits 255 for its own failures; anything else is the server talking
+/// back after a successful login (GitHub's greeting exits 1).
+fn classify_ssh(code: Option<i32>, output: &str) -> (ProbeStatus, String) {
+    let lower = output.to_lowercase();
+    let first_line = || {
+        output
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string()
+    };
+    match code {
+        None => (ProbeStatus::Unreachable, "timed out".to_string()),
+        Some(255) if lower.contains("identification has changed") => (
+            ProbeStatus::HostKey,
+            "host key changed since it was saved in known_hosts".to_string(),
+        ),
+        Some(255) if lower.contains("host key verification failed") => (
+            ProbeStatus::HostKey,
+            "host key not in known_hosts".to_string(),
+        ),
+        Some(255) if lower.contains("permission denied") => (
+            ProbeStatus::AuthFailed,
+            "server rejected every key offered".to_string(),
+        ),
+        Some(255) if lower.contains("could not resolve hostname") => {
+            (ProbeStatus::Unreachable, "DNS lookup failed".to_string())
+        }
+        Some(255) if lower.contains("timed out") => {
+            (ProbeStatus::Unreachable, "connection timed out".to_string())
+        }
+        Some(255) => (ProbeStatus::Unreachable, first_line()),
+        Some(_) => (ProbeStatus::Authenticated, first_line()),
+    }
+}
+
+/// TCP connect, DNS included, within `timeout`. The lookup runs on its own
+/// thread because the system resolver has no timeout of its own.
+fn probe_tcp(host: &str, port: u16, timeout: Duration) -> (ProbeStatus, String) {
+    let (tx, rx) = mpsc::channel();
+    let target = (host.to_string(), port);
+    std::thread::spawn(move || {
+        let started = Instant::now();
+        let result = match target.to_socket_addrs() {
+            Err(_) => (ProbeStatus::Unreachable, "DNS lookup failed".to_string()),
+            Ok(addrs) => {
+                let mut last = (ProbeStatus::Unreachable, "no addresses".to_string());
+                for addr in addrs {
+                    let left = timeout.saturating_sub(started.elapsed());
+                    if left.is_zero() {
+                        break;
+                    }
+                    match TcpStream::connect_timeout(&addr, left) {
+                        Ok(_) => {
+                            last = (ProbeStatus::Reachable, format!("connected to {}", addr));
+                            break;
+                        }
+                        Err(err) => last = (ProbeStatus::Unreachable, err.to_string()),
+                    }
+                }
+                last
+            }
+        };
+        let _ = tx.send(result);
+    });
+    rx.recv_timeout(timeout)
+        .unwrap_or_else(|_| (ProbeStatus::Unreachable, "timed out".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_ssh_outcomes() {
+        let cases = [
+            (
+                Some(1),
+                "Hi me! You've successfully authenticated, but GitHub does not provide shell access.",
+                ProbeStatus::Authenticated,
+            ),
+            (Some(0), "Welcome to GitLab, @me!", ProbeStatus::Authenticated),
+            (
+                Some(255),
+                "git@github.com: Permission denied (publickey).",
+                ProbeStatus::AuthFailed,
+            ),
+            (
+                Some(255),
+                "No ED25519 host key is known for example.com and you have requested strict checking.\nHost key verification failed.",
+                ProbeStatus::HostKey,
+            ),
+            (
+                Some(255),
+                "ssh: Could not resolve hostname git.corp: Name or service not known",
+                ProbeStatus::Unreachable,
+            ),
+            (None, "", ProbeStatus::Unreachable),
+        ];
+        for (code, output, expected) in cases {
+            assert_eq!(classify_ssh(code, output).0, expected, "{output}");
+        }
+        assert_eq!(
+            classify_ssh(
+                Some(255),
+                "ssh: connect to host h port 22: Connection refused"
+            )
+            .1,
+            "ssh: connect to host h port 22: Connection refused"
+        );
+    }
+
+    #[test]
+    fn matches_vpn_hosts_exactly_or_by_subdomain() {
+        let patterns = vec!["git.corp.example".to_string(), "*.internal.io".to_string()];
+        assert!(is_vpn_host(&patterns, "git.corp.example"));
+        assert!(is_vpn_host(&patterns, "gitlab.internal.io"));
+        assert!(!is_vpn_host(&patterns, "internal.io"));
+        assert!(!is_vpn_host(&patterns, "notinternal.io"));
+        assert!(!is_vpn_host(&patterns, "github.com"));
+    }
+
+    #[test]
+    fn tcp_probe_tells_open_from_closed_ports() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let timeout = Duration::from_secs(2);
+        assert_eq!(
+            probe_tcp("127.0.0.1", port, timeout).0,
+            ProbeStatus::Reachable
+        );
+        drop(listener);
+        assert_eq!(
+            probe_tcp("127.0.0.1", port, timeout).0,
+            ProbeStatus::Unreachable
+        );
+    }
+}
@@ -0,0 +1,624 @@
+//! Open pull/merge requests and CI status from GitHub and GitLab (`[forge]`).
+//!
+//! For repos whose `origin` is on github.com, gitlab.com or a host listed in
+//! `[forge] hosts`, the open PR/MR from the current branch and the latest CI
+//! run on it are asked for through `gh api`/`glab api` when installed, else
+//! the REST API with `GITHUB_TOKEN`/`GITLAB_TOKEN` through `curl`. Answers
+//! and failures alike are reused for `refresh_secs`. Under `[providers]
+//! privacy_mode` nothing is read or asked.
+
+use super::ai_mcp::url_encode_component;
+use super::auth::parse_remote_url;
+use super::{curl_with_secrets, CollectorResult};
+use crate::config::ForgeConfig;
+use crate::credentials;
+use crate::dashboard::{CiStatus, DashboardAlert, ForgeStatus, PullRequest, RemoteForge};
+use crate::git::{std_git_command, Repo};
+use crate::i18n::{t, tr};
+use crate::path_utils::resolve_binary_in_path;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const TIMEOUT_SECS: u64 = 8;
+
+static FORGE_SETTINGS: OnceLock<Mutex<ForgeConfig>> = OnceLock::new();
+
+fn forge_settings() -> ForgeConfig {
+    FORGE_SETTINGS
+        .get_or_init(|| Mutex::new(ForgeConfig::default()))
+        .lock()
+        .map(|cfg| cfg.clone())
+        .unwrap_or_default()
+}
+
+/// Apply `[forge]` config to later collections; a change drops cached
+/// answers.
+pub fn configure_forge(cfg: &ForgeConfig) {
+    if let Ok(mut current) = FORGE_SETTINGS
+        .get_or_init(|| Mutex::new(ForgeConfig::default()))
+        .lock()
+    {
+        if *current != *cfg {
+            *current = cfg.clone();
+            if let Ok(mut cache) = forge_cache().lock() {
+                cache.clear();
+            }
+        }
+    }
+}
+
+/// What the forge said about one branch.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct BranchStatus {
+    pull_request: Option<PullRequest>,
+    ci: CiStatus,
+    ci_name: Option<String>,
+    ci_url: Option<String>,
+}
+
+/// Keyed by repo path and branch, so switching branches asks again.
+type ForgeCache = HashMap<(String, String), (Instant, Result<BranchStatus, String>)>;
+
+static FORGE_CACHE: OnceLock<Mutex<ForgeCache>> = OnceLock::new();
+
+fn forge_cache() -> &'static Mutex<ForgeCache> {
+    FORGE_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A repo whose origin is on a known forge.
+struct Target<'a> {
+    repo: &'a Repo,
+    forge: RemoteForge,
+    host: String,
+    /// `owner/name`, or a GitLab `group/subgroup/name`.
+    project: String,
+}
+
+impl Target<'_> {
+    fn key(&self) -> (String, String) {
+        (
+            self.repo.path.to_string_lossy().into_owned(),
+            self.repo.status.branch.clone(),
+        )
+    }
+}
+
+/// The forge serving `host`: github.com, gitlab.com, or `[forge] hosts`.
+fn forge_for(cfg: &ForgeConfig, host: &str) -> Option<RemoteForge> {
+    match host {
+        "github.com" => Some(RemoteForge::Github),
+        "gitlab.com" => Some(RemoteForge::Gitlab),
+        _ => cfg
+            .hosts
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(host))
+            .map(|(_, forge)| *forge),
+    }
+}
+
+/// The project path of a remote URL, without `.git`.
+fn project_path(url: &str) -> Option<String> {
+    let url = url.trim();
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url.split_once(':')?.1,
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    path.contains('/').then(|| path.to_string())
+}
+
+fn target<'a>(cfg: &ForgeConfig, repo: &'a Repo) -> Option<Target<'a>> {
+    let output = std_git_command(&["remote", "get-url", "origin"])
+        .current_dir(&repo.path)
+        .output()
+        .ok()
+        .filter(|out| out.status.success())?;
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let host = parse_remote_url(&url)?.host;
+    Some(Target {
+        repo,
+        forge: forge_for(cfg, &host)?,
+        project: project_path(&url)?,
+        host,
+    })
+}
+
+/// How requests reach the forge.
+enum Client {
+    /// `gh api` / `glab api`, which bring their own login.
+    Cli,
+    /// REST through `curl` with this token.
+    Token(String),
+}
+
+fn client(forge: RemoteForge) -> Option<Client> {
+    if resolve_binary_in_path(forge.cli()).is_some() {
+        return Some(Client::Cli);
+    }
+    let names: &[&str] = match forge {
+        RemoteForge::Github => &["GITHUB_TOKEN", "GH_TOKEN"],
+        RemoteForge::Gitlab => &["GITLAB_TOKEN"],
+    };
+    credentials::first_value(names).map(Client::Token)
+}
+
+/// `GET path` against the forge's REST API (`path` has no leading slash).
+fn api_get(target: &Target, client: &Client, path: &str) -> Result<Value, String> {
+    let output = match client {
+        Client::Cli => {
+            let mut cmd = Command::new(target.forge.cli());
+            cmd.args(["api", "--hostname", &target.host, path]);
+            output_within(cmd, Duration::from_secs(TIMEOUT_SECS))?
+        }
+        Client::Token(token) => {
+            let (base, auth) = match target.forge {
+                RemoteForge::Github if target.host == "github.com" => (
+                    "https://api.github.com".to_string(),
+                    format!("Authorization: Bearer {}", token),
+                ),
+                RemoteForge::Github => (
+                    format!("https://{}/api/v3", target.host),
+                    format!("Authorization: Bearer {}", token),
+                ),
+                RemoteForge::Gitlab => (
+                    format!("https://{}/api/v4", target.host),
+                    format!("PRIVATE-TOKEN: {}", token),
+                ),
+            };
+            let args = [
+                "--silent",
+                "--show-error",
+                "--fail-with-body",
+                "--connect-timeout",
+                "4",
+                "--max-time",
+                &TIMEOUT_SECS.to_string(),
+                &format!("{}/{}", base, path),
+            ]
+            .map(str::to_string);
+            curl_with_secrets(&args, &[("header", auth)])
+                .map_err(|e| format!("failed to run curl: {}", e))?
+        }
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let detail = if stderr.trim().is_empty() {
+            stdout
+        } else {
+            stderr
+        };
+        let detail = detail.split_whitespace().collect::<Vec<_>>().join(" ");
+        return Err(detail.chars().take(200).collect());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("unexpected response: {}", e))
+}
+
+/// Run `cmd`, killing it after `timeout`. Stdout is drained on a thread so
+/// a large answer cannot stall the child.
+fn output_within(mut cmd: Command, timeout: Duration) -> Result<std::process::Output, String> {
+    let program = cmd.get_program().to_string_lossy().into_owned();
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run {}: {}", program, e))?;
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|p| Box::new(p) as _));
+    let stderr = drain(child.stderr.take().map(|p| Box::new(p) as _));
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(50)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "{} timed out after {}s",
+                    program,
+                    timeout.as_secs()
+                ));
+            }
+            Err(e) => return Err(format!("failed to wait for {}: {}", program, e)),
+        }
+    };
+    Ok(std::process::Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn fetch(target: &Target, client: &Client) -> Result<BranchStatus, String> {
+    let branch = url_encode_component(&target.repo.status.branch);
+    let (pull_request, runs) = match target.forge {
+        RemoteForge::Github => {
+            let owner = target.project.split('/').next().unwrap_or_default();
+            let pulls = api_get(
+                target,
+                client,
+                &format!(
+                    "repos/{}/pulls?state=open&head={}:{}",
+                    target.project, owner, branch
+                ),
+            )?;
+            let runs = api_get(
+                target,
+                client,
+                &format!(
+                    "repos/{}/actions/runs?branch={}&per_page=1",
+                    target.project, branch
+                ),
+            );
+            (
+                parse_github_pulls(&pulls),
+                runs.map(|r| parse_github_runs(&r)),
+            )
+        }
+        RemoteForge::Gitlab => {
+            let project = url_encode_component(&target.project);
+            let merge_requests = api_get(
+                target,
+                client,
+                &format!(
+                    "projects/{}/merge_requests?state=opened&source_branch={}",
+                    project, branch
+                ),
+            )?;
+            let pipelines = api_get(
+                target,
+                client,
+                &format!("projects/{}/pipelines?ref={}&per_page=1", project, branch),
+            );
+            (
+                parse_gitlab_merge_requests(&merge_requests),
+                pipelines.map(|p| parse_gitlab_pipelines(&p)),
+            )
+        }
+    };
+    // A repo without Actions or pipelines answers 404; that is "no CI".
+    let (ci, ci_name, ci_url) = runs.unwrap_or((CiStatus::Unknown, None, None));
+    Ok(BranchStatus {
+        pull_request,
+        ci,
+        ci_name,
+        ci_url,
+    })
+}
+
+fn text(value: &Value, pointer: &str) -> Option<String> {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn non_empty(value: &Value, key: &str) -> bool {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .is_some_and(|items| !items.is_empty())
+}
+
+/// `GET repos/{project}/pulls?head={owner}:{branch}`.
+fn parse_github_pulls(value: &Value) -> Option<PullRequest> {
+    let pull = value.get(0)?;
+    let draft = pull.get("draft").and_then(Value::as_bool).unwrap_or(false);
+    Some(PullRequest {
+        number: pull.get("number")?.as_u64()?,
+        title: text(pull, "/title").unwrap_or_default(),
+        url: text(pull, "/html_url").unwrap_or_default(),
+        draft,
+        // Reviewers drop off the requested list once they review.
+        awaiting_review: !draft
+            && (non_empty(pull, "requested_reviewers") || non_empty(pull, "requested_teams")),
+    })
+}
+
+/// `GET repos/{project}/actions/runs?branch={branch}&per_page=1`.
+fn parse_github_runs(value: &Value) -> (CiStatus, Option<String>, Option<String>) {
+    let Some(run) = value.pointer("/workflow_runs/0") else {
+        return (CiStatus::Unknown, None, None);
+    };
+    (
+        github_run_status(run),
+        text(run, "/name"),
+        text(run, "/html_url"),
+    )
+}
+
+/// A GitHub Actions run's outcome from its `status` and `conclusion`, as
+/// both the REST API and `gh run list --json` name them.
+pub fn github_run_status(run: &Value) -> CiStatus {
+    match (
+        run.get("status").and_then(Value::as_str),
+        run.get("conclusion").and_then(Value::as_str),
+    ) {
+        (Some("completed"), Some("success" | "skipped" | "neutral")) => CiStatus::Passing,
+        (Some("completed"), _) => CiStatus::Failing,
+        _ => CiStatus::Running,
+    }
+}
+
+/// `GET projects/{id}/merge_requests?source_branch={branch}&state=opened`.
+fn parse_gitlab_merge_requests(value: &Value) -> Option<PullRequest> {
+    let mr = value.get(0)?;
+    let draft = ["draft", "work_in_progress"]
+        .iter()
+        .any(|key| mr.get(*key).and_then(Value::as_bool).unwrap_or(false));
+    let awaiting_review = match mr.get("detailed_merge_status").and_then(Value::as_str) {
+        Some(status) => status == "not_approved",
+        None => non_empty(mr, "reviewers"),
+    };
+    Some(PullRequest {
+        number: mr.get("iid")?.as_u64()?,
+        title: text(mr, "/title").unwrap_or_default(),
+        url: text(mr, "/web_url").unwrap_or_default(),
+        draft,
+        awaiting_review: !draft && awaiting_review,
+    })
+}
+
+/// `GET projects/{id}/pipelines?ref={branch}&per_page=1`.
+fn parse_gitlab_pipelines(value: &Value) -> (CiStatus, Option<String>, Option<String>) {
+    let Some(pipeline) = value.get(0) else {
+        return (CiStatus::Unknown, None, None);
+    };
+    let status = match pipeline.get("status").and_then(Value::as_str) {
+        Some("success") => CiStatus::Passing,
+        Some("failed") => CiStatus::Failing,
+        Some(
+            "created" | "pending" | "running" | "preparing" | "waiting_for_resource" | "scheduled",
+        ) => CiStatus::Running,
+        _ => CiStatus::Unknown,
+    };
+    let name = pipeline
+        .get("id")
+        .and_then(Value::as_u64)
+        .map(|id| format!("pipeline #{}", id));
+    (status, name, text(pipeline, "/web_url"))
+}
+
+/// Ask about the targets not asked within `refresh`; the rest come from the
+/// cache.
+fn lookup_status(
+    cfg: &ForgeConfig,
+    targets: &[Target],
+    fetch_due: bool,
+) -> HashMap<(String, String), Result<BranchStatus, String>> {
+    let mut cached = forge_cache().lock().map(|c| c.clone()).unwrap_or_default();
+    if fetch_due {
+        let refresh = Duration::from_secs(cfg.refresh_secs);
+        let due: Vec<&Target> = targets
+            .iter()
+            .filter(|t| {
+                cached
+                    .get(&t.key())
+                    .is_none_or(|(at, _)| at.elapsed() >= refresh)
+            })
+            .collect();
+        let fetched: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = due
+                .iter()
+                .map(|target| {
+                    scope.spawn(|| match client(target.forge) {
+                        Some(client) => fetch(target, &client),
+                        None => Err(format!(
+                            "install {} or set {}",
+                            target.forge.cli(),
+                            match target.forge {
+                                RemoteForge::Github => "GITHUB_TOKEN",
+                                RemoteForge::Gitlab => "GITLAB_TOKEN",
+                            }
+                        )),
+                    })
+                })
+                .collect();
+            handles.into_iter().filter_map(|h| h.join().ok()).collect()
+        });
+        for (target, result) in due.into_iter().zip(fetched) {
+            cached.insert(target.key(), (Instant::now(), result));
+        }
+        if let Ok(mut guard) = forge_cache().lock() {
+            *guard = cached.clone();
+        }
+    }
+    cached
+        .into_iter()
+        .map(|(key, (_, result))| (key, result))
+        .collect()
+}
+
+/// PR and CI status for repos on a known forge, when `[forge] enabled`.
+/// With `fetch_due` false (power save) only cached answers are used.
+pub fn collect_forge_status(repos: &[Repo], fetch_due: bool) -> CollectorResult<ForgeStatus> {
+    let cfg = forge_settings();
+    if !cfg.enabled || super::ai_mcp::privacy_mode() {
+        return Ok(Vec::new());
+    }
+    let targets: Vec<Target> = repos
+        .iter()
+        .filter(|repo| repo.status.has_remote && !repo.status.is_detached)
+        .filter_map(|repo| target(&cfg, repo))
+        .collect();
+    let found = lookup_status(&cfg, &targets, fetch_due);
+    Ok(targets
+        .iter()
+        .map(|target| {
+            let (status, error) = match found.get(&target.key()) {
+                Some(Ok(status)) => (status.clone(), None),
+                Some(Err(err)) => (BranchStatus::default(), Some(err.clone())),
+                None => (BranchStatus::default(), None),
+            };
+            ForgeStatus {
+                repo: target.repo.name.clone(),
+                repo_path: target.repo.path.to_string_lossy().into_owned(),
+                branch: target.repo.status.branch.clone(),
+                forge: target.forge,
+                pull_request: status.pull_request,
+                ci: status.ci,
+                ci_name: status.ci_name,
+                ci_url: status.ci_url,
+                error,
+            }
+        })
+        .collect())
+}
+
+/// Failing CI on a branch, and PRs waiting on reviewers.
+pub fn collect_forge_alerts(rows: &[ForgeStatus]) -> Vec<DashboardAlert> {
+    let mut alerts = Vec::new();
+    for row in rows {
+        if row.ci == CiStatus::Failing {
+            alerts.push(DashboardAlert {
+                severity: "high".to_string(),
+                title: tr(
+                    "alert-ci-failing-title",
+                    &[("repo", &row.repo), ("branch", &row.branch)],
+                ),
+                detail: row
+                    .ci_url
+                    .clone()
+                    .or_else(|| row.ci_name.clone())
+                    .unwrap_or_else(|| t("alert-ci-failing-detail")),
+                repo: Some(row.repo.clone()),
+                action: None,
+            });
+        }
+        if let Some(pr) = row.pull_request.as_ref().filter(|pr| pr.awaiting_review) {
+            alerts.push(DashboardAlert {
+                severity: "info".to_string(),
+                title: tr(
+                    "alert-review-title",
+                    &[("repo", &row.repo), ("number", &pr.number)],
+                ),
+                detail: pr.title.clone(),
+                repo: Some(row.repo.clone()),
+                action: None,
+            });
+        }
+    }
+    alerts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn finds_the_forge_and_project_of_a_remote() {
+        let cfg = ForgeConfig {
+            hosts: BTreeMap::from([("Git.Corp.Example".to_string(), RemoteForge::Gitlab)]),
+            ..ForgeConfig::default()
+        };
+        assert_eq!(forge_for(&cfg, "github.com"), Some(RemoteForge::Github));
+        assert_eq!(
+            forge_for(&cfg, "git.corp.example"),
+            Some(RemoteForge::Gitlab)
+        );
+        assert_eq!(forge_for(&cfg, "bitbucket.org"), None);
+
+        assert_eq!(
+            project_path("git@github.com:acme/api.git").as_deref(),
+            Some("acme/api")
+        );
+        assert_eq!(
+            project_path("https://gitlab.com/acme/platform/api/").as_deref(),
+            Some("acme/platform/api")
+        );
+        assert_eq!(
+            project_path("ssh://git@git.corp.example:2222/team/web.git").as_deref(),
+            Some("team/web")
+        );
+        assert_eq!(project_path("https://github.com/acme"), None);
+    }
+
+    #[test]
+    fn reads_github_pulls_and_runs() {
+        let pulls = serde_json::json!([{
+            "number": 42,
+            "title": "Add SSO login",
+            "html_url": "https://github.com/acme/api/pull/42",
+            "draft": false,
+            "requested_reviewers": [{ "login": "sam" }],
+            "requested_teams": []
+        }]);
+        let pr = parse_github_pulls(&pulls).unwrap();
+        assert_eq!(pr.number, 42);
+        assert!(pr.awaiting_review);
+        assert_eq!(parse_github_pulls(&serde_json::json!([])), None);
+
+        let runs = |status: &str, conclusion: Value| {
+            parse_github_runs(&serde_json::json!({ "workflow_runs": [{
+                "name": "CI",
+                "status": status,
+                "conclusion": conclusion,
+                "html_url": "https://github.com/acme/api/actions/runs/7"
+            }]}))
+            .0
+        };
+        assert_eq!(runs("completed", "success".into()), CiStatus::Passing);
+        assert_eq!(runs("completed", "failure".into()), CiStatus::Failing);
+        assert_eq!(runs("in_progress", Value::Null), CiStatus::Running);
+        assert_eq!(
+            parse_github_runs(&serde_json::json!({ "workflow_runs": [] })).0,
+            CiStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn reads_gitlab_merge_requests_and_pipelines() {
+        let mrs = serde_json::json!([{
+            "iid": 7,
+            "title": "Draft: Retry uploads",
+            "web_url": "https://gitlab.com/acme/web/-/merge_requests/7",
+            "draft": true,
+            "detailed_merge_status": "not_approved"
+        }]);
+        let mr = parse_gitlab_merge_requests(&mrs).unwrap();
+        assert_eq!(mr.number, 7);
+        assert!(mr.draft);
+        assert!(!mr.awaiting_review, "drafts are not waiting on anyone");
+
+        let pipelines = serde_json::json!([{
+            "id": 991,
+            "status": "failed",
+            "web_url": "https://gitlab.com/acme/web/-/pipelines/991"
+        }]);
+        let (ci, name, _) = parse_gitlab_pipelines(&pipelines);
+        assert_eq!(ci, CiStatus::Failing);
+        assert_eq!(name.as_deref(), Some("pipeline #991"));
+
+        let row = ForgeStatus {
+            repo: "web".to_string(),
+            repo_path: "/src/web".to_string(),
+            branch: "retry".to_string(),
+            forge: RemoteForge::Gitlab,
+            pull_request: Some(mr),
+            ci,
+            ci_name: name,
+            ci_url: None,
+            error: None,
+        };
+        let alerts = collect_forge_alerts(&[row]);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].severity, "high");
+        assert_eq!(alerts[0].title, "web: CI failing on retry");
+    }
+}
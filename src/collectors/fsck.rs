@@ -0,0 +1,353 @@
+//! Integrity sweep (`[fsck]`): `git fsck --no-dangling` on a few repos a
+//! day, round-robin, longest-unchecked first.
+//!
+//! Object corruption on a laptop disk stays silent until a checkout or push
+//! trips over it, by which point backups may have rotated the good copy out.
+//! A full fsck is too slow to run every scan, so each scan starts at most one
+//! check, in the background, and only while the last 24 hours hold fewer
+//! than `repos_per_day` checks. Results are kept in
+//! `~/.config/agentpulse/fsck.json` and scans only read them, so the
+//! rotation and any failure survive restarts; a failed repo stays alerted
+//! until its next check passes.
+
+use super::CollectorResult;
+use crate::config::{DisplayConfig, FsckConfig};
+use crate::dashboard::{CollectorError, DashboardAlert};
+use crate::git::{std_git_command, Repo};
+use crate::i18n::tr;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// Problem lines kept per repo.
+const MAX_PROBLEMS: usize = 5;
+
+static FSCK_SETTINGS: OnceLock<Mutex<FsckConfig>> = OnceLock::new();
+
+/// `[display]`, for the check date in alerts.
+static DISPLAY: OnceLock<Mutex<DisplayConfig>> = OnceLock::new();
+
+/// Set while a background check runs, so later scans don't start another.
+static CHECK_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Why the last background check could not be run or saved, reported by
+/// the next scan.
+static CHECK_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+fn fsck_settings() -> FsckConfig {
+    FSCK_SETTINGS
+        .get_or_init(|| Mutex::new(FsckConfig::default()))
+        .lock()
+        .map(|cfg| cfg.clone())
+        .unwrap_or_default()
+}
+
+fn display_settings() -> DisplayConfig {
+    DISPLAY
+        .get_or_init(|| Mutex::new(DisplayConfig::default()))
+        .lock()
+        .map(|cfg| cfg.clone())
+        .unwrap_or_default()
+}
+
+/// Apply `[fsck]` and `[display]` config to later collections.
+pub fn configure_fsck(cfg: &FsckConfig, display: &DisplayConfig) {
+    if let Ok(mut current) = FSCK_SETTINGS
+        .get_or_init(|| Mutex::new(FsckConfig::default()))
+        .lock()
+    {
+        *current = cfg.clone();
+    }
+    if let Ok(mut current) = DISPLAY
+        .get_or_init(|| Mutex::new(DisplayConfig::default()))
+        .lock()
+    {
+        *current = display.clone();
+    }
+}
+
+/// Results location: `~/.config/agentpulse/fsck.json`.
+pub fn results_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".config")
+        .join("agentpulse")
+        .join("fsck.json")
+}
+
+/// The last check of one repo.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FsckResult {
+    pub checked_at: i64,
+    /// Empty when the check passed.
+    #[serde(default)]
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FsckResults {
+    #[serde(default)]
+    pub repos: BTreeMap<PathBuf, FsckResult>,
+}
+
+impl FsckResults {
+    /// Load the results, starting empty if the file is missing or unreadable.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// The repo to check now, if the daily budget allows one: never-checked
+    /// repos first, then the oldest check, but nothing checked in the last day.
+    fn due<'a>(&self, paths: &'a [PathBuf], now: i64, per_day: usize) -> Option<&'a PathBuf> {
+        let checked_today = self
+            .repos
+            .values()
+            .filter(|result| now - result.checked_at < DAY_SECS)
+            .count();
+        if checked_today >= per_day {
+            return None;
+        }
+        paths
+            .iter()
+            .map(|path| (self.repos.get(path).map(|r| r.checked_at), path))
+            .filter(|(at, _)| at.is_none_or(|at| now - at >= DAY_SECS))
+            .min_by_key(|(at, _)| *at)
+            .map(|(_, path)| path)
+    }
+}
+
+/// What a finished `git fsck` reported. A zero exit means healthy, whatever
+/// warnings (e.g. zero-padded file modes in old trees) it printed.
+fn fsck_problems(success: bool, output: &str) -> Vec<String> {
+    if success {
+        return Vec::new();
+    }
+    let lines: Vec<&str> = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let errors: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| !line.starts_with("warning"))
+        .collect();
+    let lines = if errors.is_empty() { lines } else { errors };
+    let mut problems: Vec<String> = lines
+        .iter()
+        .take(MAX_PROBLEMS)
+        .map(|line| line.to_string())
+        .collect();
+    if lines.len() > MAX_PROBLEMS {
+        problems.push(format!("… {} more", lines.len() - MAX_PROBLEMS));
+    }
+    if problems.is_empty() {
+        problems.push("git fsck failed without saying why".to_string());
+    }
+    problems
+}
+
+fn run_fsck(repo_path: &Path) -> Result<Vec<String>, String> {
+    let out = std_git_command(&["fsck", "--no-dangling", "--no-progress"])
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("failed to run git fsck: {}", e))?;
+    let mut output = String::from_utf8_lossy(&out.stdout).into_owned();
+    output.push_str(&String::from_utf8_lossy(&out.stderr));
+    Ok(fsck_problems(out.status.success(), &output))
+}
+
+fn recovery_steps(repo: &Repo) -> &'static str {
+    if repo.status.has_remote {
+        "copy the .git directory aside first, then re-clone from the remote and move \
+         local-only branches and uncommitted files over (or `git fetch` them from the copy)"
+    } else {
+        "copy the .git directory aside before anything else; missing objects can only come \
+         from another clone or a bundle (`git fetch <bundle>`)"
+    }
+}
+
+fn fsck_alert(repo: &Repo, result: &FsckResult, display: &DisplayConfig) -> DashboardAlert {
+    let checked = DateTime::from_timestamp(result.checked_at, 0)
+        .map(|at| display.date(&at.with_timezone(&Local)))
+        .unwrap_or_default();
+    DashboardAlert {
+        severity: "critical".to_string(),
+        title: tr("alert-fsck-title", &[("repo", &repo.name)]),
+        detail: tr(
+            "alert-fsck-detail",
+            &[
+                ("problems", &result.problems.join("; ")),
+                ("date", &checked),
+                ("recovery", &recovery_steps(repo)),
+            ],
+        ),
+        repo: Some(repo.name.clone()),
+        action: None,
+    }
+}
+
+/// Check `repo_path` off the scan and save the result to `path`, on the
+/// blocking pool when a runtime is around.
+fn start_check(repo_path: PathBuf, path: PathBuf) {
+    if CHECK_RUNNING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let job = move || {
+        let checked_at = Local::now().timestamp();
+        let outcome = run_fsck(&repo_path).and_then(|problems| {
+            let mut results = FsckResults::load(&path);
+            results.repos.insert(
+                repo_path,
+                FsckResult {
+                    checked_at,
+                    problems,
+                },
+            );
+            results
+                .save(&path)
+                .map_err(|err| format!("cannot write {}: {}", path.display(), err))
+        });
+        if let (Err(err), Ok(mut slot)) = (outcome, CHECK_ERROR.lock()) {
+            *slot = Some(err);
+        }
+        CHECK_RUNNING.store(false, Ordering::Release);
+    };
+    // Detached either way; the result lands in the file.
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        drop(handle.spawn_blocking(job));
+    } else {
+        drop(std::thread::spawn(job));
+    }
+}
+
+/// Start a check of the next due repo when `run_due` (not in power save),
+/// then alert on every repo whose last saved check failed.
+pub fn collect_fsck_alerts(repos: &[Repo], run_due: bool) -> CollectorResult<DashboardAlert> {
+    let cfg = fsck_settings();
+    if !cfg.enabled {
+        return Ok(Vec::new());
+    }
+    if let Some(err) = CHECK_ERROR.lock().ok().and_then(|mut slot| slot.take()) {
+        return Err(CollectorError::new("fsck", err));
+    }
+    let path = results_path();
+    let results = FsckResults::load(&path);
+    let paths: Vec<PathBuf> = repos.iter().map(|repo| repo.path.clone()).collect();
+    let now = Local::now().timestamp();
+    if let Some(repo_path) = run_due
+        .then(|| results.due(&paths, now, cfg.repos_per_day))
+        .flatten()
+    {
+        start_check(repo_path.clone(), path);
+    }
+
+    let display = display_settings();
+    Ok(repos
+        .iter()
+        .filter_map(|repo| {
+            let result = results.repos.get(&repo.path)?;
+            (!result.problems.is_empty()).then(|| fsck_alert(repo, result, &display))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_a_few_repos_a_day_longest_unchecked_first() {
+        let paths: Vec<PathBuf> = ["/src/a", "/src/b", "/src/c"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect();
+        let now = 10 * DAY_SECS;
+        let mut results = FsckResults::default();
+        let check = |results: &mut FsckResults, at: i64| {
+            let path = results.due(&paths, at, 2)?.clone();
+            results.repos.insert(
+                path.clone(),
+                FsckResult {
+                    checked_at: at,
+                    problems: Vec::new(),
+                },
+            );
+            Some(path)
+        };
+        assert_eq!(check(&mut results, now), Some(paths[0].clone()));
+        assert_eq!(check(&mut results, now + 60), Some(paths[1].clone()));
+        // Two checks in the last day use up the budget.
+        assert_eq!(check(&mut results, now + 120), None);
+        // A day later the never-checked repo goes first, then the oldest.
+        assert_eq!(check(&mut results, now + DAY_SECS), Some(paths[2].clone()));
+        assert_eq!(
+            check(&mut results, now + DAY_SECS + 60),
+            Some(paths[0].clone())
+        );
+        assert_eq!(check(&mut results, now + DAY_SECS + 120), None);
+    }
+
+    #[test]
+    fn reports_errors_but_not_warnings_of_a_failed_fsck() {
+        assert!(fsck_problems(true, "warning in tree 1a2b: zeroPaddedFilemode").is_empty());
+        let output = "warning in tree 1a2b: zeroPaddedFilemode\n\
+                      error: object file .git/objects/3c/4d is empty\n\
+                      missing blob 3c4d5e\n";
+        assert_eq!(
+            fsck_problems(false, output),
+            [
+                "error: object file .git/objects/3c/4d is empty",
+                "missing blob 3c4d5e"
+            ]
+        );
+        assert_eq!(
+            fsck_problems(false, ""),
+            ["git fsck failed without saying why"]
+        );
+    }
+
+    #[test]
+    fn finds_a_missing_object_in_a_real_repo() {
+        let base = std::env::temp_dir().join("agentpulse_fsck_test");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+        let git = |args: &[&str]| {
+            let out = std::process::Command::new("git")
+                .args(args)
+                .current_dir(&base)
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&out.stdout).trim().to_string()
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(base.join("a.txt"), "precious").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        assert_eq!(run_fsck(&base), Ok(Vec::new()));
+
+        let blob = git(&["rev-parse", "HEAD:a.txt"]);
+        let object = base.join(".git/objects").join(&blob[..2]).join(&blob[2..]);
+        std::fs::remove_file(object).unwrap();
+        let problems = run_fsck(&base).unwrap();
+        assert!(problems.iter().any(|p| p.contains(&blob)), "{problems:?}");
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
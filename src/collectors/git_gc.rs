@@ -0,0 +1,262 @@
+//! Heavy `.git` detector: repos carrying many loose objects, a pile of
+//! packs, garbage, or oversized reflogs, with a gc action sized to the repo.
+//!
+//! Sizes come from `git count-objects -v` (no object walk) and the reflog
+//! directory, cached per repo for a few minutes. The savings and duration in
+//! the alert are rough estimates: loose objects shrink about tenfold once
+//! packed with deltas, garbage and expired reflog entries go entirely, and
+//! an aggressive repack runs at something like 70 MiB of objects a minute.
+
+use super::CollectorResult;
+use crate::dashboard::{ActionCommand, ActionKind, DashboardAlert};
+use crate::git::{std_git_command, Repo};
+use crate::i18n::tr;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const REFRESH_AFTER: Duration = Duration::from_secs(600);
+
+type WeightCache = HashMap<PathBuf, (Instant, Option<GitWeight>)>;
+
+static WEIGHT_CACHE: OnceLock<Mutex<WeightCache>> = OnceLock::new();
+
+/// Git's own auto-gc limits (`gc.auto`, `gc.autoPackLimit`).
+const LOOSE_OBJECT_LIMIT: u64 = 6700;
+const PACK_LIMIT: u64 = 50;
+/// Garbage or reflogs past this many KiB are worth a mention.
+const NOTABLE_KIB: u64 = 10 * 1024;
+/// Below this estimated saving a gc is not worth suggesting.
+const MIN_SAVINGS_KIB: u64 = 50 * 1024;
+/// Past this many KiB of objects `--aggressive` takes too long; a plain gc
+/// through `git maintenance` is suggested instead.
+const AGGRESSIVE_MAX_KIB: u64 = 1024 * 1024;
+
+/// What `git count-objects -v` and the reflog directory report, in KiB.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GitWeight {
+    pub loose_count: u64,
+    pub loose_kib: u64,
+    pub packs: u64,
+    pub pack_kib: u64,
+    pub garbage_kib: u64,
+    pub reflog_kib: u64,
+}
+
+impl GitWeight {
+    pub fn objects_kib(&self) -> u64 {
+        self.loose_kib + self.pack_kib + self.garbage_kib
+    }
+
+    /// Why this `.git` is heavy; empty when it is not.
+    fn reasons(&self) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if self.loose_count >= LOOSE_OBJECT_LIMIT {
+            reasons.push(format!(
+                "{} loose objects ({})",
+                self.loose_count,
+                format_kib(self.loose_kib)
+            ));
+        }
+        if self.packs >= PACK_LIMIT {
+            reasons.push(format!("{} packs", self.packs));
+        }
+        if self.garbage_kib >= NOTABLE_KIB {
+            reasons.push(format!("{} of garbage", format_kib(self.garbage_kib)));
+        }
+        if self.reflog_kib >= NOTABLE_KIB {
+            reasons.push(format!("{} of reflogs", format_kib(self.reflog_kib)));
+        }
+        reasons
+    }
+
+    fn estimated_savings_kib(&self) -> u64 {
+        let repack = if self.packs > 1 {
+            self.pack_kib / 10
+        } else {
+            0
+        };
+        self.loose_kib * 9 / 10 + self.garbage_kib + repack + self.reflog_kib / 2
+    }
+
+    fn aggressive(&self) -> bool {
+        self.objects_kib() <= AGGRESSIVE_MAX_KIB
+    }
+
+    fn estimated_minutes(&self) -> u64 {
+        let kib_per_minute = if self.aggressive() { 70 } else { 700 } * 1024;
+        self.objects_kib().div_ceil(kib_per_minute).max(1)
+    }
+}
+
+/// `1.2 GiB`, `340 MiB`, `12 KiB`.
+pub fn format_kib(kib: u64) -> String {
+    if kib >= 1024 * 1024 {
+        format!("{:.1} GiB", kib as f64 / (1024.0 * 1024.0))
+    } else if kib >= 1024 {
+        format!("{} MiB", kib / 1024)
+    } else {
+        format!("{} KiB", kib)
+    }
+}
+
+fn git_stdout(repo_path: &Path, args: &[&str]) -> Option<String> {
+    let out = std_git_command(args).current_dir(repo_path).output().ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// `key: value` lines from `git count-objects -v`.
+fn parse_count_objects(raw: &str) -> GitWeight {
+    let fields: HashMap<&str, u64> = raw
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            Some((key.trim(), value.trim().parse().ok()?))
+        })
+        .collect();
+    let field = |key| fields.get(key).copied().unwrap_or(0);
+    GitWeight {
+        loose_count: field("count"),
+        loose_kib: field("size"),
+        packs: field("packs"),
+        pack_kib: field("size-pack"),
+        garbage_kib: field("size-garbage"),
+        reflog_kib: 0,
+    }
+}
+
+fn dir_kib(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let bytes: u64 = entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_kib(&entry.path()) * 1024,
+            _ => entry.metadata().map(|m| m.len()).unwrap_or(0),
+        })
+        .sum();
+    bytes / 1024
+}
+
+/// Object and reflog sizes of the repo's (common) git dir.
+pub fn git_weight(repo_path: &Path) -> Option<GitWeight> {
+    let mut weight = parse_count_objects(&git_stdout(repo_path, &["count-objects", "-v"])?);
+    if let Some(git_dir) = git_stdout(repo_path, &["rev-parse", "--git-common-dir"]) {
+        weight.reflog_kib = dir_kib(&repo_path.join(git_dir.trim()).join("logs"));
+    }
+    Some(weight)
+}
+
+fn cached_git_weight(repo_path: &Path) -> Option<GitWeight> {
+    let cache = WEIGHT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(guard) = cache.lock() {
+        if let Some((at, weight)) = guard.get(repo_path) {
+            if at.elapsed() < REFRESH_AFTER {
+                return *weight;
+            }
+        }
+    }
+    let weight = git_weight(repo_path);
+    if let Ok(mut guard) = cache.lock() {
+        guard.insert(repo_path.to_path_buf(), (Instant::now(), weight));
+    }
+    weight
+}
+
+fn gc_alert(repo: &Repo, weight: &GitWeight) -> Option<DashboardAlert> {
+    let reasons = weight.reasons();
+    let savings = weight.estimated_savings_kib();
+    if reasons.is_empty() || savings < MIN_SAVINGS_KIB {
+        return None;
+    }
+    let minutes = weight.estimated_minutes();
+    let mut detail = reasons;
+    detail.push(format!(
+        "{} of objects; gc may take ~{} min and load the machine meanwhile",
+        format_kib(weight.objects_kib()),
+        minutes
+    ));
+    if weight.aggressive() {
+        detail.push("unreachable commits are pruned: rescue lost work first".to_string());
+    }
+    Some(DashboardAlert {
+        severity: "warn".to_string(),
+        title: tr(
+            "alert-gc-title",
+            &[("repo", &repo.name), ("size", &format_kib(savings))],
+        ),
+        detail: detail.join(" · "),
+        repo: Some(repo.name.clone()),
+        action: Some(ActionCommand::new(
+            format!("gc (~{} smaller, ~{} min)", format_kib(savings), minutes),
+            ActionKind::GitGc {
+                repo_path: repo.path.to_string_lossy().to_string(),
+                aggressive: weight.aggressive(),
+            },
+        )),
+    })
+}
+
+pub fn collect_gc_alerts(repos: &[Repo]) -> CollectorResult<DashboardAlert> {
+    super::require_git("gc", repos)?;
+    Ok(repos
+        .iter()
+        .filter_map(|repo| gc_alert(repo, &cached_git_weight(&repo.path)?))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_gc_for_heavy_git_dirs_with_an_estimate() {
+        let raw = "count: 12000\nsize: 204800\nin-pack: 90000\npacks: 3\nsize-pack: 512000\n\
+                   prune-packable: 10\ngarbage: 0\nsize-garbage: 0\n";
+        let weight = parse_count_objects(raw);
+        assert_eq!(
+            (weight.loose_count, weight.loose_kib, weight.packs),
+            (12000, 204800, 3)
+        );
+        assert_eq!(weight.estimated_savings_kib(), 184320 + 51200);
+        assert!(weight.aggressive());
+        assert_eq!(weight.estimated_minutes(), 10);
+
+        let repo = Repo::new(PathBuf::from("/src/api"));
+        let alert = gc_alert(&repo, &weight).unwrap();
+        assert_eq!(alert.title, "api: .git could shrink by ~230 MiB");
+        assert!(alert
+            .detail
+            .starts_with("12000 loose objects (200 MiB) · 700 MiB of objects"));
+        let action = alert.action.unwrap();
+        assert_eq!(action.label, "gc (~230 MiB smaller, ~10 min)");
+        assert!(matches!(
+            action.action,
+            ActionKind::GitGc {
+                aggressive: true,
+                ..
+            }
+        ));
+
+        // Big object stores get a plain gc; tidy repos no alert at all.
+        let huge = GitWeight {
+            pack_kib: 4 * 1024 * 1024,
+            packs: 60,
+            ..weight
+        };
+        assert!(!huge.aggressive());
+        let tidy = GitWeight {
+            loose_count: 300,
+            loose_kib: 2048,
+            packs: 1,
+            pack_kib: 900_000,
+            ..GitWeight::default()
+        };
+        assert!(gc_alert(&repo, &tidy).is_none());
+        assert_eq!(format_kib(3 * 1024 * 1024 / 2), "1.5 GiB");
+    }
+}
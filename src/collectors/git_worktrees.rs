@@ -1,10 +1,13 @@
+use super::CollectorResult;
 use crate::agent;
-use crate::dashboard::{ActionCommand, ActionKind, DashboardAlert, RepoRow, WorktreeRow};
-use crate::git::Repo;
+use crate::dashboard::{
+    ActionCommand, ActionKind, CollectorError, DashboardAlert, RepoRow, WorktreeRow,
+};
+use crate::git::{std_git_command, Repo};
+use crate::i18n::tr;
 use std::path::Path;
-use std::process::Command;
 
-pub fn collect_repo_rows(repos: &[Repo]) -> Vec<RepoRow> {
+pub fn collect_repo_rows(repos: &[Repo]) -> CollectorResult<RepoRow> {
     let mut rows: Vec<RepoRow> = repos
         .iter()
         .map(|repo| {
@@ -21,6 +24,7 @@ pub fn collect_repo_rows(repos: &[Repo]) -> Vec<RepoRow> {
                 path: repo.path.to_string_lossy().to_string(),
                 branch: repo.status.branch.clone(),
                 dirty: repo.status.uncommitted_count,
+                changes: repo.status.changes,
                 ahead: repo.status.unpushed_count,
                 behind: repo.status.behind_count,
                 stash: repo.status.stash_count,
@@ -37,20 +41,21 @@ pub fn collect_repo_rows(repos: &[Repo]) -> Vec<RepoRow> {
             .then_with(|| b.ahead.cmp(&a.ahead))
             .then_with(|| a.name.cmp(&b.name))
     });
-    rows
+    Ok(rows)
 }
 
-pub fn collect_worktrees(repos: &[Repo]) -> Vec<WorktreeRow> {
+pub fn collect_worktrees(repos: &[Repo]) -> CollectorResult<WorktreeRow> {
     let mut rows: Vec<WorktreeRow> = Vec::new();
 
     for repo in repos {
-        let output = Command::new("git")
-            .args(["worktree", "list", "--porcelain"])
+        let output = std_git_command(&["worktree", "list", "--porcelain"])
             .current_dir(&repo.path)
-            .output();
+            .output()
+            // git itself could not run; no other repo will fare better.
+            .map_err(|e| CollectorError::new("worktrees", format!("git failed to start: {}", e)))?;
 
         match output {
-            Ok(o) if o.status.success() => {
+            o if o.status.success() => {
                 let parsed = parse_worktree_output(repo, &String::from_utf8_lossy(&o.stdout));
                 if parsed.is_empty() {
                     rows.push(default_worktree_row(repo));
@@ -63,7 +68,7 @@ pub fn collect_worktrees(repos: &[Repo]) -> Vec<WorktreeRow> {
     }
 
     rows.sort_by(|a, b| a.repo.cmp(&b.repo).then_with(|| a.path.cmp(&b.path)));
-    rows
+    Ok(rows)
 }
 
 pub fn collect_git_alerts(
@@ -95,7 +100,7 @@ pub fn collect_git_alerts(
 
         alerts.push(DashboardAlert {
             severity: "high".to_string(),
-            title: format!("{} status checks degraded", repo.name),
+            title: tr("alert-status-degraded-title", &[("repo", &repo.name)]),
             detail,
             repo: Some(repo.name.clone()),
             action: Some(ActionCommand::new(
@@ -111,8 +116,8 @@ pub fn collect_git_alerts(
         if row.dirty > 0 {
             alerts.push(DashboardAlert {
                 severity: "warn".to_string(),
-                title: format!("{} has local changes", row.name),
-                detail: format!("{} modified/untracked file(s)", row.dirty),
+                title: tr("alert-local-changes-title", &[("repo", &row.name)]),
+                detail: tr("alert-local-changes-detail", &[("count", &row.dirty)]),
                 repo: Some(row.name.clone()),
                 action: Some(ActionCommand::new(
                     "open status",
@@ -126,8 +131,8 @@ pub fn collect_git_alerts(
         if row.behind > 0 {
             alerts.push(DashboardAlert {
                 severity: "high".to_string(),
-                title: format!("{} is behind remote", row.name),
-                detail: format!("{} commit(s) behind", row.behind),
+                title: tr("alert-behind-title", &[("repo", &row.name)]),
+                detail: tr("alert-behind-detail", &[("count", &row.behind)]),
                 repo: Some(row.name.clone()),
                 action: Some(ActionCommand::new(
                     "pull --rebase",
@@ -141,8 +146,8 @@ pub fn collect_git_alerts(
         if row.ahead > 0 {
             alerts.push(DashboardAlert {
                 severity: "info".to_string(),
-                title: format!("{} has unpushed commits", row.name),
-                detail: format!("{} commit(s) ahead", row.ahead),
+                title: tr("alert-unpushed-title", &[("repo", &row.name)]),
+                detail: tr("alert-unpushed-detail", &[("count", &row.ahead)]),
                 repo: Some(row.name.clone()),
                 action: Some(ActionCommand::new(
                     "push",
@@ -157,8 +162,8 @@ pub fn collect_git_alerts(
     for wt in worktrees.iter().filter(|w| w.detached) {
         alerts.push(DashboardAlert {
             severity: "high".to_string(),
-            title: format!("Detached worktree in {}", wt.repo),
-            detail: format!("{} is detached", wt.path),
+            title: tr("alert-detached-worktree-title", &[("repo", &wt.repo)]),
+            detail: tr("alert-detached-worktree-detail", &[("path", &wt.path)]),
             repo: Some(wt.repo.clone()),
             action: Some(ActionCommand::new(
                 "inspect worktree",
@@ -284,12 +289,18 @@ mod tests {
         repo.status = RepoStatus {
             branch: "main".to_string(),
             uncommitted_count: 0,
+            changes: Default::default(),
             unpushed_count: 0,
             behind_count: 0,
             stash_count: 0,
             has_remote: true,
             is_detached: false,
             probe_errors: Vec::new(),
+            push: None,
+            missing_upstream: false,
+            dirty_since: None,
+            unpushed_since: None,
+            head_sha: None,
         };
 
         let raw = "worktree /tmp/example\nHEAD deadbeef\nbranch refs/heads/main\n\nworktree /tmp/example-wt\nHEAD cafe\ndetached\n";
@@ -305,16 +316,22 @@ mod tests {
         repo.status = RepoStatus {
             branch: "main".to_string(),
             uncommitted_count: 0,
+            changes: Default::default(),
             unpushed_count: 0,
             behind_count: 0,
             stash_count: 0,
             has_remote: true,
             is_detached: false,
             probe_errors: vec!["branch probe failed: timeout".to_string()],
+            push: None,
+            missing_upstream: false,
+            dirty_since: None,
+            unpushed_since: None,
+            head_sha: None,
         };
 
         let repos = vec![repo];
-        let repo_rows = collect_repo_rows(&repos);
+        let repo_rows = collect_repo_rows(&repos).unwrap();
         let alerts = collect_git_alerts(&repos, &repo_rows, &[]);
         assert!(alerts
             .iter()
@@ -0,0 +1,435 @@
+//! License audit: each repo's declared license and, with
+//! `[licenses] scan_dependencies`, its direct dependencies' licenses checked
+//! against the `allow` list. Repos that declare no license (or UNLICENSED /
+//! proprietary) count as commercial, where copyleft dependencies are flagged
+//! even without an allow list.
+
+use super::CollectorResult;
+use crate::config::LicensesConfig;
+use crate::dashboard::{DashboardAlert, FlaggedDependency, LicenseAudit};
+use crate::git::Repo;
+use crate::i18n::tr;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// SPDX id prefixes of strong copyleft licenses (LGPL and MPL are weak and
+/// not flagged on their own).
+const COPYLEFT_PREFIXES: &[&str] = &["GPL-", "AGPL-", "SSPL", "EUPL-", "OSL-", "CC-BY-SA-"];
+
+/// `(text marker, SPDX id)` for sniffing LICENSE files, most specific first.
+const LICENSE_TEXT_MARKERS: &[(&str, &str)] = &[
+    ("GNU AFFERO GENERAL PUBLIC LICENSE", "AGPL-3.0"),
+    ("GNU LESSER GENERAL PUBLIC LICENSE", "LGPL"),
+    ("Version 3, 29 June 2007", "GPL-3.0"),
+    ("GNU GENERAL PUBLIC LICENSE", "GPL-2.0"),
+    ("Mozilla Public License Version 2.0", "MPL-2.0"),
+    ("Apache License", "Apache-2.0"),
+    ("MIT License", "MIT"),
+    ("Permission is hereby granted, free of charge", "MIT"),
+    ("ISC License", "ISC"),
+    (
+        "Redistribution and use in source and binary forms",
+        "BSD-3-Clause",
+    ),
+    ("This is free and unencumbered software", "Unlicense"),
+];
+
+static LICENSE_SETTINGS: OnceLock<Mutex<LicensesConfig>> = OnceLock::new();
+
+fn license_settings() -> LicensesConfig {
+    LICENSE_SETTINGS
+        .get_or_init(|| Mutex::new(LicensesConfig::default()))
+        .lock()
+        .map(|cfg| cfg.clone())
+        .unwrap_or_default()
+}
+
+/// Apply `[licenses]` config to later collections.
+pub fn configure_licenses(cfg: &LicensesConfig) {
+    if let Ok(mut current) = LICENSE_SETTINGS
+        .get_or_init(|| Mutex::new(LicensesConfig::default()))
+        .lock()
+    {
+        *current = cfg.clone();
+    }
+}
+
+pub fn collect_license_audit(repos: &[Repo]) -> CollectorResult<LicenseAudit> {
+    Ok(audit_repos(repos, &license_settings()))
+}
+
+fn audit_repos(repos: &[Repo], cfg: &LicensesConfig) -> Vec<LicenseAudit> {
+    let registry = cfg.scan_dependencies.then(cargo_registry_sources);
+    let mut audits: Vec<LicenseAudit> = repos
+        .iter()
+        .filter_map(|repo| audit_repo(repo, cfg, registry.as_deref().unwrap_or(&[])))
+        .collect();
+    audits.sort_by(|a, b| {
+        b.flagged
+            .len()
+            .cmp(&a.flagged.len())
+            .then_with(|| a.repo.cmp(&b.repo))
+    });
+    audits
+}
+
+/// `None` for repos without a manifest or license file.
+fn audit_repo(repo: &Repo, cfg: &LicensesConfig, registry: &[PathBuf]) -> Option<LicenseAudit> {
+    let root = &repo.path;
+    let has_manifest = ["Cargo.toml", "package.json", "pyproject.toml"]
+        .iter()
+        .any(|m| root.join(m).exists());
+    let declared = declared_license(root);
+    if !has_manifest && declared.is_none() {
+        return None;
+    }
+    let commercial =
+        cfg.commercial_repos.contains(&repo.name) || declared.as_deref().is_none_or(is_proprietary);
+
+    let deps = if cfg.scan_dependencies {
+        let mut deps = npm_dependency_licenses(root);
+        deps.extend(cargo_dependency_licenses(root, registry));
+        deps
+    } else {
+        Vec::new()
+    };
+    let flagged = deps
+        .iter()
+        .filter_map(|(package, license)| {
+            let license = license.as_deref()?;
+            let copyleft = copyleft_only(license);
+            let disallowed = if cfg.allow.is_empty() {
+                commercial && copyleft
+            } else {
+                !allowed(license, &cfg.allow)
+            };
+            disallowed.then(|| FlaggedDependency {
+                package: package.clone(),
+                license: license.to_string(),
+                copyleft,
+            })
+        })
+        .collect();
+
+    Some(LicenseAudit {
+        repo: repo.name.clone(),
+        path: root.to_string_lossy().to_string(),
+        declared,
+        commercial,
+        dependencies_scanned: deps.len(),
+        unknown_licenses: deps.iter().filter(|(_, l)| l.is_none()).count(),
+        flagged,
+    })
+}
+
+/// One alert per repo with flagged dependencies; copyleft in a commercial
+/// repo is a warning, anything else outside the allow list is info.
+pub fn collect_license_alerts(audits: &[LicenseAudit]) -> Vec<DashboardAlert> {
+    audits
+        .iter()
+        .filter(|a| !a.flagged.is_empty())
+        .map(|audit| {
+            let copyleft = audit.commercial && audit.flagged.iter().any(|f| f.copyleft);
+            DashboardAlert {
+                severity: if copyleft { "warn" } else { "info" }.to_string(),
+                title: if copyleft {
+                    tr("alert-copyleft-title", &[("repo", &audit.repo)])
+                } else {
+                    tr(
+                        "alert-licenses-title",
+                        &[("repo", &audit.repo), ("count", &audit.flagged.len())],
+                    )
+                },
+                detail: audit
+                    .flagged
+                    .iter()
+                    .map(|f| format!("{} ({})", f.package, f.license))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                repo: Some(audit.repo.clone()),
+                action: None,
+            }
+        })
+        .collect()
+}
+
+/// License from the root manifest, else sniffed from a LICENSE/COPYING file.
+fn declared_license(root: &Path) -> Option<String> {
+    let from_manifest = fs::read_to_string(root.join("Cargo.toml"))
+        .ok()
+        .and_then(|raw| raw.parse::<toml::Table>().ok())
+        .and_then(|m| {
+            let package = m.get("package")?;
+            package
+                .get("license")
+                .and_then(|l| l.as_str())
+                .map(str::to_string)
+                .or_else(|| package.get("license-file").map(|_| "custom".to_string()))
+        })
+        .or_else(|| {
+            let raw = fs::read_to_string(root.join("package.json")).ok()?;
+            let manifest: serde_json::Value = serde_json::from_str(&raw).ok()?;
+            manifest
+                .get("license")
+                .and_then(|l| l.as_str())
+                .map(str::to_string)
+        })
+        .or_else(|| {
+            let raw = fs::read_to_string(root.join("pyproject.toml")).ok()?;
+            let project = raw.parse::<toml::Table>().ok()?.get("project")?.clone();
+            let license = project.get("license")?;
+            license
+                .as_str()
+                .or_else(|| license.get("text").and_then(|t| t.as_str()))
+                .map(str::to_string)
+        });
+    from_manifest.or_else(|| {
+        ["LICENSE", "LICENSE.md", "LICENSE.txt", "COPYING"]
+            .iter()
+            .find_map(|f| fs::read_to_string(root.join(f)).ok())
+            .map(|text| {
+                LICENSE_TEXT_MARKERS
+                    .iter()
+                    .find(|(marker, _)| text.contains(marker))
+                    .map_or("custom", |(_, id)| id)
+                    .to_string()
+            })
+    })
+}
+
+fn is_proprietary(license: &str) -> bool {
+    let upper = license.to_ascii_uppercase();
+    upper == "UNLICENSED" || upper.contains("PROPRIETARY") || upper.starts_with("SEE LICENSE IN")
+}
+
+/// SPDX expression as alternatives (`OR`, or Cargo's legacy `/`), each a
+/// list of ids that apply together (`AND`). Parentheses and `WITH`
+/// exceptions are dropped.
+fn alternatives(expr: &str) -> Vec<Vec<String>> {
+    let cleaned = expr.replace(['(', ')'], " ").replace('/', " OR ");
+    cleaned
+        .split(" OR ")
+        .map(|alt| {
+            alt.split(" AND ")
+                .filter_map(|id| id.split(" WITH ").next())
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|ids| !ids.is_empty())
+        .collect()
+}
+
+fn is_copyleft(id: &str) -> bool {
+    let upper = id.to_ascii_uppercase();
+    COPYLEFT_PREFIXES.iter().any(|p| upper.starts_with(p))
+}
+
+/// Every way to satisfy the expression pulls in copyleft terms.
+fn copyleft_only(expr: &str) -> bool {
+    let alts = alternatives(expr);
+    !alts.is_empty() && alts.iter().all(|ids| ids.iter().any(|id| is_copyleft(id)))
+}
+
+/// Some alternative uses only allowed ids (case-insensitive).
+fn allowed(expr: &str, allow: &[String]) -> bool {
+    alternatives(expr).iter().any(|ids| {
+        ids.iter()
+            .all(|id| allow.iter().any(|a| a.eq_ignore_ascii_case(id)))
+    })
+}
+
+/// `(package, license)` for package.json `dependencies` and
+/// `optionalDependencies`, from package-lock.json metadata or the installed
+/// package's manifest.
+fn npm_dependency_licenses(root: &Path) -> Vec<(String, Option<String>)> {
+    let Some(manifest) = fs::read_to_string(root.join("package.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+    else {
+        return Vec::new();
+    };
+    let lock = fs::read_to_string(root.join("package-lock.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok());
+    ["dependencies", "optionalDependencies"]
+        .iter()
+        .filter_map(|field| manifest.get(*field).and_then(|v| v.as_object()))
+        .flat_map(|deps| deps.keys())
+        .map(|name| {
+            let key = format!("node_modules/{}", name);
+            let license = lock
+                .as_ref()
+                .and_then(|l| l.get("packages")?.get(&key)?.get("license")?.as_str())
+                .map(str::to_string)
+                .or_else(|| {
+                    let raw = fs::read_to_string(root.join(&key).join("package.json")).ok()?;
+                    let installed: serde_json::Value = serde_json::from_str(&raw).ok()?;
+                    installed
+                        .get("license")
+                        .and_then(|l| l.as_str())
+                        .map(str::to_string)
+                });
+            (name.clone(), license)
+        })
+        .collect()
+}
+
+/// `~/.cargo/registry/src/*` (or under `$CARGO_HOME`).
+fn cargo_registry_sources() -> Vec<PathBuf> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| dirs::home_dir().unwrap_or_default().join(".cargo"));
+    fs::read_dir(cargo_home.join("registry").join("src"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .collect()
+}
+
+/// `(crate, license)` for shipped dependencies resolved through Cargo.lock and
+/// read from the crate's manifest in the local registry cache.
+fn cargo_dependency_licenses(root: &Path, registry: &[PathBuf]) -> Vec<(String, Option<String>)> {
+    let Some(manifest) = fs::read_to_string(root.join("Cargo.toml"))
+        .ok()
+        .and_then(|raw| raw.parse::<toml::Table>().ok())
+    else {
+        return Vec::new();
+    };
+    let mut versions: HashMap<String, Vec<String>> = HashMap::new();
+    if let Some(lock) = fs::read_to_string(root.join("Cargo.lock"))
+        .ok()
+        .and_then(|raw| raw.parse::<toml::Table>().ok())
+    {
+        for pkg in lock
+            .get("package")
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+        {
+            if let (Some(name), Some(version)) = (
+                pkg.get("name").and_then(|n| n.as_str()),
+                pkg.get("version").and_then(|v| v.as_str()),
+            ) {
+                versions
+                    .entry(name.to_string())
+                    .or_default()
+                    .push(version.to_string());
+            }
+        }
+    }
+    // Shipped dependencies only: `[dependencies]` and `[target.*.dependencies]`.
+    let targets = manifest
+        .get("target")
+        .and_then(|t| t.as_table())
+        .into_iter()
+        .flat_map(|t| t.values());
+    std::iter::once(&toml::Value::Table(manifest.clone()))
+        .chain(targets)
+        .filter_map(|t| t.get("dependencies")?.as_table())
+        .flatten()
+        // Path and git dependencies are not in the registry.
+        .filter(|(_, spec)| spec.get("path").is_none() && spec.get("git").is_none())
+        .map(|(key, spec)| {
+            let name = spec
+                .get("package")
+                .and_then(|p| p.as_str())
+                .unwrap_or(key)
+                .to_string();
+            let license = versions
+                .get(&name)
+                .into_iter()
+                .flatten()
+                .find_map(|version| {
+                    registry.iter().find_map(|src| {
+                        let raw = fs::read_to_string(
+                            src.join(format!("{}-{}", name, version)).join("Cargo.toml"),
+                        )
+                        .ok()?;
+                        raw.parse::<toml::Table>()
+                            .ok()?
+                            .get("package")?
+                            .get("license")?
+                            .as_str()
+                            .map(str::to_string)
+                    })
+                });
+            (name, license)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_spdx_expressions() {
+        assert!(copyleft_only("GPL-3.0-only"));
+        assert!(copyleft_only("(AGPL-3.0 AND MIT)"));
+        assert!(!copyleft_only("MIT OR GPL-2.0"));
+        assert!(!copyleft_only("LGPL-2.1"));
+        let allow = vec!["MIT".to_string(), "Apache-2.0".to_string()];
+        assert!(allowed("MIT/Apache-2.0", &allow));
+        assert!(allowed("Apache-2.0 WITH LLVM-exception", &allow));
+        assert!(!allowed("MIT AND BSD-3-Clause", &allow));
+        assert!(is_proprietary("SEE LICENSE IN LICENSE.txt"));
+    }
+
+    #[test]
+    fn flags_copyleft_dependencies_in_commercial_repos() {
+        let base = std::env::temp_dir().join("agentpulse_license_audit_test");
+        let _ = fs::remove_dir_all(&base);
+        for (name, license) in [("app", "UNLICENSED"), ("lib", "MIT")] {
+            let dir = base.join(name);
+            fs::create_dir_all(dir.join("node_modules/chart")).unwrap();
+            fs::write(
+                dir.join("package.json"),
+                format!(
+                    r#"{{"license":"{}","dependencies":{{"left-pad":"^1","chart":"^2"}},"devDependencies":{{"gpl-linter":"^1"}}}}"#,
+                    license
+                ),
+            )
+            .unwrap();
+            fs::write(
+                dir.join("package-lock.json"),
+                r#"{"packages":{"node_modules/left-pad":{"license":"WTFPL"},"node_modules/gpl-linter":{"license":"GPL-3.0"}}}"#,
+            )
+            .unwrap();
+            fs::write(
+                dir.join("node_modules/chart/package.json"),
+                r#"{"license":"GPL-3.0-or-later"}"#,
+            )
+            .unwrap();
+        }
+        let repos = [Repo::new(base.join("app")), Repo::new(base.join("lib"))];
+
+        let cfg = LicensesConfig {
+            scan_dependencies: true,
+            ..LicensesConfig::default()
+        };
+        let audits = audit_repos(&repos, &cfg);
+        assert_eq!(audits[0].repo, "app");
+        assert!(audits[0].commercial);
+        assert_eq!(audits[0].dependencies_scanned, 2);
+        assert_eq!(audits[0].flagged.len(), 1);
+        assert_eq!(audits[0].flagged[0].package, "chart");
+        assert!(audits[1].flagged.is_empty());
+        let alerts = collect_license_alerts(&audits);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].severity, "warn");
+
+        // With an allow list, anything else is flagged in every repo.
+        let cfg = LicensesConfig {
+            scan_dependencies: true,
+            allow: vec!["MIT".to_string()],
+            commercial_repos: Vec::new(),
+        };
+        let audits = audit_repos(&repos, &cfg);
+        assert!(audits.iter().all(|a| a.flagged.len() == 2));
+        let _ = fs::remove_dir_all(&base);
+    }
+}
@@ -0,0 +1,343 @@
+//! Local dependency graph: watched repos that build against another watched
+//! repo through a path link (Cargo `path =`, npm `file:`/`link:` or an
+//! out-of-tree workspace, go.mod `replace => ../dir`). Only root manifests
+//! are read.
+
+use super::CollectorResult;
+use crate::agent;
+use crate::dashboard::{ActionCommand, DashboardAlert, LocalDependency};
+use crate::git::Repo;
+use crate::i18n::tr;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const CARGO_DEP_TABLES: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+const NPM_DEP_FIELDS: &[&str] = &[
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+const NPM_PATH_PROTOCOLS: &[&str] = &["file:", "link:", "portal:"];
+
+/// `(package, relative path, kind)` links declared by a repo's root manifests.
+fn declared_links(repo_path: &Path) -> Vec<(String, String, &'static str)> {
+    let mut links = Vec::new();
+    if let Ok(raw) = fs::read_to_string(repo_path.join("Cargo.toml")) {
+        links.extend(
+            cargo_path_deps(&raw)
+                .into_iter()
+                .map(|(name, path)| (name, path, "cargo")),
+        );
+    }
+    if let Ok(raw) = fs::read_to_string(repo_path.join("package.json")) {
+        links.extend(
+            npm_path_deps(&raw)
+                .into_iter()
+                .map(|(name, path)| (name, path, "npm")),
+        );
+    }
+    if let Ok(raw) = fs::read_to_string(repo_path.join("go.mod")) {
+        links.extend(
+            go_replace_paths(&raw)
+                .into_iter()
+                .map(|(name, path)| (name, path, "go")),
+        );
+    }
+    links
+}
+
+/// `[dependencies]`, `[dev-dependencies]`, and `[build-dependencies]` of `parent`.
+pub(crate) fn dep_tables(parent: &toml::Table) -> Vec<&toml::Table> {
+    CARGO_DEP_TABLES
+        .iter()
+        .filter_map(|key| parent.get(*key).and_then(|v| v.as_table()))
+        .collect()
+}
+
+/// Path dependencies from `[dependencies]`-style tables, including
+/// `[workspace.dependencies]`, `[target.*.dependencies]`, and `[patch.*]`.
+fn cargo_path_deps(raw: &str) -> Vec<(String, String)> {
+    let Ok(manifest) = raw.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let nested = |key: &str| {
+        manifest
+            .get(key)
+            .and_then(|v| v.as_table())
+            .into_iter()
+            .flat_map(|t| t.values())
+            .filter_map(|v| v.as_table())
+    };
+    let mut tables = dep_tables(&manifest);
+    if let Some(workspace) = manifest.get("workspace").and_then(|v| v.as_table()) {
+        tables.extend(dep_tables(workspace));
+    }
+    for target in nested("target") {
+        tables.extend(dep_tables(target));
+    }
+    // `[patch.<registry>]` tables hold dependency specs directly.
+    tables.extend(nested("patch"));
+
+    tables
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, spec)| {
+            let path = spec.get("path")?.as_str()?;
+            Some((name.clone(), path.to_string()))
+        })
+        .collect()
+}
+
+/// `file:`/`link:`/`portal:` dependencies and literal (non-glob) workspaces.
+fn npm_path_deps(raw: &str) -> Vec<(String, String)> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(raw) else {
+        return Vec::new();
+    };
+    let mut deps = Vec::new();
+    for field in NPM_DEP_FIELDS {
+        for (name, spec) in manifest
+            .get(*field)
+            .and_then(|v| v.as_object())
+            .into_iter()
+            .flatten()
+        {
+            let Some(spec) = spec.as_str() else {
+                continue;
+            };
+            if let Some(path) = NPM_PATH_PROTOCOLS
+                .iter()
+                .find_map(|proto| spec.strip_prefix(proto))
+            {
+                deps.push((name.clone(), path.to_string()));
+            }
+        }
+    }
+    let workspaces = manifest.get("workspaces").and_then(|w| {
+        w.as_array()
+            .or_else(|| w.get("packages").and_then(|p| p.as_array()))
+    });
+    for entry in workspaces.into_iter().flatten().filter_map(|w| w.as_str()) {
+        if !entry.contains('*') {
+            deps.push((entry.to_string(), entry.to_string()));
+        }
+    }
+    deps
+}
+
+/// `replace module => ./local/dir` directives, single-line or in a block.
+fn go_replace_paths(raw: &str) -> Vec<(String, String)> {
+    let mut replaces = Vec::new();
+    let mut in_block = false;
+    for line in raw.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        let directive = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if let Some(rest) = line.strip_prefix("replace") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_block = true;
+                continue;
+            }
+            rest
+        } else {
+            continue;
+        };
+        let Some((module, target)) = directive.split_once("=>") else {
+            continue;
+        };
+        let module = module.split_whitespace().next().unwrap_or("").to_string();
+        let target = target.trim();
+        if target.starts_with("./") || target.starts_with("../") || target.starts_with('/') {
+            replaces.push((module, target.to_string()));
+        }
+    }
+    replaces
+}
+
+/// Resolve `.`/`..` without touching the filesystem (the target may not exist).
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Links from each repo into another watched repo, sorted by dependency.
+pub fn collect_local_deps(repos: &[Repo]) -> CollectorResult<LocalDependency> {
+    let roots: Vec<(PathBuf, &Repo)> = repos
+        .iter()
+        .map(|r| {
+            (
+                fs::canonicalize(&r.path).unwrap_or_else(|_| r.path.clone()),
+                r,
+            )
+        })
+        .collect();
+    let mut deps = Vec::new();
+    for (root, repo) in &roots {
+        for (package, rel, kind) in declared_links(&repo.path) {
+            let target = normalize(&root.join(&rel));
+            // The deepest watched repo containing the target owns it.
+            let Some((_, dependency)) = roots
+                .iter()
+                .filter(|(other, _)| target.starts_with(other))
+                .max_by_key(|(other, _)| other.components().count())
+            else {
+                continue;
+            };
+            if dependency.path == repo.path {
+                continue;
+            }
+            deps.push(LocalDependency {
+                dependent: repo.name.clone(),
+                dependent_path: repo.path.to_string_lossy().to_string(),
+                dependency: dependency.name.clone(),
+                dependency_path: dependency.path.to_string_lossy().to_string(),
+                package,
+                kind: kind.to_string(),
+                dependency_dirty: dependency.status.uncommitted_count,
+                dependency_unpushed: dependency.status.unpushed_count,
+            });
+        }
+    }
+    deps.sort_by(|a, b| {
+        a.dependency
+            .cmp(&b.dependency)
+            .then_with(|| a.dependent.cmp(&b.dependent))
+    });
+    deps.dedup_by(|a, b| {
+        a.dependent_path == b.dependent_path && a.dependency_path == b.dependency_path
+    });
+    Ok(deps)
+}
+
+/// One alert per dependency repo whose local changes its dependents build
+/// against, but CI and teammates cannot see until they are pushed.
+pub fn collect_local_dep_alerts(repos: &[Repo], deps: &[LocalDependency]) -> Vec<DashboardAlert> {
+    let mut dependents: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for dep in deps
+        .iter()
+        .filter(|d| d.dependency_dirty + d.dependency_unpushed > 0)
+    {
+        dependents
+            .entry(dep.dependency_path.as_str())
+            .or_default()
+            .push(dep.dependent.as_str());
+    }
+    dependents
+        .into_iter()
+        .filter_map(|(path, names)| {
+            let repo = repos.iter().find(|r| r.path.to_string_lossy() == path)?;
+            let rec = agent::recommend(repo);
+            Some(DashboardAlert {
+                severity: "warn".to_string(),
+                title: tr("alert-local-deps-title", &[("repo", &repo.name)]),
+                detail: tr(
+                    "alert-local-deps-detail",
+                    &[
+                        ("uncommitted", &repo.status.uncommitted_count),
+                        ("unpushed", &repo.status.unpushed_count),
+                        ("dependents", &names.join(", ")),
+                    ],
+                ),
+                repo: Some(repo.name.clone()),
+                action: agent::recommended_action_kind(repo)
+                    .map(|action| ActionCommand::new(rec.action, action)),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_path_links_from_each_ecosystem() {
+        let cargo = r#"
+            [dependencies]
+            serde = "1"
+            core = { path = "../core" }
+            [target.'cfg(unix)'.dev-dependencies]
+            testkit = { path = "../testkit" }
+            [patch.crates-io]
+            log = { path = "../forks/log" }
+        "#;
+        let mut found = cargo_path_deps(cargo);
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("core".to_string(), "../core".to_string()),
+                ("log".to_string(), "../forks/log".to_string()),
+                ("testkit".to_string(), "../testkit".to_string()),
+            ]
+        );
+
+        let npm = r#"{
+            "dependencies": { "react": "^18", "ui": "file:../ui" },
+            "devDependencies": { "lint": "link:../lint" },
+            "workspaces": ["packages/*", "../shared"]
+        }"#;
+        let names: Vec<String> = npm_path_deps(npm).into_iter().map(|(n, _)| n).collect();
+        assert_eq!(names, ["ui", "lint", "../shared"]);
+
+        let gomod = "module app\n\
+                     replace example.com/auth => ../auth\n\
+                     replace (\n\
+                     \texample.com/db v1.0.0 => ./vendor/db // local fork\n\
+                     \texample.com/log => example.com/log v1.2.0\n\
+                     )\n";
+        assert_eq!(
+            go_replace_paths(gomod),
+            vec![
+                ("example.com/auth".to_string(), "../auth".to_string()),
+                ("example.com/db".to_string(), "./vendor/db".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn links_dependents_to_watched_repos_and_warns_on_unshared_changes() {
+        let base = std::env::temp_dir().join("agentpulse_local_deps_test");
+        let _ = fs::remove_dir_all(&base);
+        fs::create_dir_all(base.join("core")).unwrap();
+        fs::create_dir_all(base.join("app")).unwrap();
+        fs::write(
+            base.join("app/Cargo.toml"),
+            "[package]\nname = \"app\"\n[dependencies]\ncore = { path = \"../core/crates/core\" }\n",
+        )
+        .unwrap();
+
+        let mut core = Repo::new(base.join("core"));
+        let app = Repo::new(base.join("app"));
+        let deps = collect_local_deps(&[core.clone(), app.clone()]).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(
+            (deps[0].dependent.as_str(), deps[0].dependency.as_str()),
+            ("app", "core")
+        );
+        assert!(collect_local_dep_alerts(&[core.clone(), app.clone()], &deps).is_empty());
+
+        core.status.unpushed_count = 2;
+        let repos = [core, app];
+        let alerts = collect_local_dep_alerts(&repos, &collect_local_deps(&repos).unwrap());
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].detail.contains("2 unpushed"));
+        assert!(alerts[0].detail.contains("app"));
+        let _ = fs::remove_dir_all(&base);
+    }
+}
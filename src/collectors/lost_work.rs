@@ -0,0 +1,382 @@
+//! Lost-work detector: recent commits that no ref reaches any more.
+//!
+//! Candidates come from the HEAD reflog (resets, checkouts away from work)
+//! and `git fsck` dangling commits (dropped stashes, deleted branches).
+//! Commits whose change (`git patch-id`), or whose author, author date and
+//! subject, still appear on a ref are treated as rewritten by amend/rebase
+//! rather than lost. `git fsck` is slow on large repos, so
+//! results are cached per repo and refreshed every few minutes.
+
+use super::CollectorResult;
+use crate::dashboard::{ActionCommand, ActionKind, DashboardAlert};
+use crate::git::{std_git_command, Repo};
+use crate::i18n::tr;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Upper bound on candidate commits passed to git in one call.
+const MAX_CANDIDATES: usize = 400;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LostCommit {
+    pub sha: String,
+    pub committed_at: i64,
+    pub subject: String,
+}
+
+type LostWorkCache = HashMap<PathBuf, (Instant, Vec<LostCommit>)>;
+
+static LOST_WORK_CACHE: OnceLock<Mutex<LostWorkCache>> = OnceLock::new();
+
+fn lookback_days() -> i64 {
+    std::env::var("AGENTPULSE_LOST_WORK_DAYS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(14)
+}
+
+/// `git gc --prune=` expiry matching the lookback, so a gc never deletes an
+/// unreachable commit recent enough for this detector to have shown it.
+pub fn prune_expiry() -> String {
+    format!("{}.days.ago", lookback_days())
+}
+
+fn refresh_after() -> Duration {
+    let secs = std::env::var("AGENTPULSE_LOST_WORK_REFRESH_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(600);
+    Duration::from_secs(secs)
+}
+
+fn git_stdout(repo_path: &Path, args: &[&str]) -> Option<String> {
+    let out = std_git_command(args).current_dir(repo_path).output().ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// Reflog entries as `(sha, unix time)`.
+fn parse_reflog(raw: &str) -> Vec<(String, i64)> {
+    raw.lines()
+        .filter_map(|line| {
+            let (sha, ts) = line.split_once(' ')?;
+            Some((sha.to_string(), ts.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// `dangling commit <sha>` lines from `git fsck`.
+fn parse_fsck_dangling(raw: &str) -> Vec<String> {
+    raw.lines()
+        .filter_map(|line| line.strip_prefix("dangling commit "))
+        .map(|sha| sha.trim().to_string())
+        .collect()
+}
+
+/// `git patch-id --stable` of each commit `git log -p <args>` lists, keyed
+/// by sha; `None` when git fails.
+fn patch_ids(repo_path: &Path, args: &[&str]) -> Option<HashMap<String, String>> {
+    let log = [
+        &["log", "-p", "--no-merges", "--no-color", "--no-ext-diff"],
+        args,
+    ]
+    .concat();
+    let patches = git_stdout(repo_path, &log)?;
+    let mut child = std_git_command(&["patch-id", "--stable"])
+        .current_dir(repo_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .ok()?;
+    let mut stdin = child.stdin.take()?;
+    // Fed from another thread so a full stdout pipe cannot stall the write.
+    let writer = std::thread::spawn(move || stdin.write_all(patches.as_bytes()));
+    let out = child.wait_with_output().ok()?;
+    writer.join().ok()?.ok()?;
+    Some(
+        String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (patch_id, sha) = line.split_once(' ')?;
+                Some((sha.to_string(), patch_id.to_string()))
+            })
+            .collect(),
+    )
+}
+
+/// Tips of the unreachable history: commits no other listed commit has as a parent.
+/// `raw` is `git log --format=%H%x1f%P%x1f%ct%x1f%ae %at%x1f%s`; tips whose
+/// `%ae %at%x1f%s` is in `live_authored` were amended or rebased, not lost.
+fn lost_tips(raw: &str, live_authored: &HashSet<String>) -> Vec<LostCommit> {
+    let rows: Vec<Vec<&str>> = raw
+        .lines()
+        .map(|l| l.splitn(5, '\u{1f}').collect::<Vec<_>>())
+        .filter(|parts| parts.len() == 5)
+        .collect();
+    let parents: HashSet<&str> = rows
+        .iter()
+        .flat_map(|parts| parts[1].split_whitespace())
+        .collect();
+    let mut tips: Vec<LostCommit> = rows
+        .iter()
+        .filter(|parts| !parents.contains(parts[0]))
+        .filter(|parts| !is_stash_internal(parts[4]))
+        .filter(|parts| !live_authored.contains(&parts[3..].join("\u{1f}")))
+        .map(|parts| LostCommit {
+            sha: parts[0].to_string(),
+            committed_at: parts[2].parse().unwrap_or(0),
+            subject: parts[4].to_string(),
+        })
+        .collect();
+    tips.sort_by_key(|c| std::cmp::Reverse(c.committed_at));
+    tips
+}
+
+/// Index/untracked helper commits that `git stash` creates alongside the WIP commit.
+fn is_stash_internal(subject: &str) -> bool {
+    subject.starts_with("index on ") || subject.starts_with("untracked files on ")
+}
+
+/// Recent commits that no branch, tag, remote, or stash reaches.
+pub fn find_lost_commits(repo_path: &Path) -> Vec<LostCommit> {
+    let since = chrono::Utc::now().timestamp() - lookback_days() * 86_400;
+    let mut candidates: Vec<String> = Vec::new();
+
+    if let Some(raw) = git_stdout(repo_path, &["reflog", "--format=%H %ct", "HEAD"]) {
+        candidates.extend(
+            parse_reflog(&raw)
+                .into_iter()
+                .filter(|(_, ts)| *ts >= since)
+                .map(|(sha, _)| sha),
+        );
+    }
+    if let Some(raw) = git_stdout(
+        repo_path,
+        &["fsck", "--no-reflogs", "--dangling", "--no-progress"],
+    ) {
+        candidates.extend(parse_fsck_dangling(&raw));
+    }
+    let mut seen = HashSet::new();
+    candidates.retain(|sha| seen.insert(sha.clone()));
+    candidates.truncate(MAX_CANDIDATES);
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+
+    let since_arg = format!("--since={}", since);
+    let mut args = vec![
+        "log",
+        "--ignore-missing",
+        "--format=%H%x1f%P%x1f%ct%x1f%ae %at%x1f%s",
+        &since_arg,
+    ];
+    args.extend(candidates.iter().map(String::as_str));
+    args.extend(["--not", "--all"]);
+    let Some(raw) = git_stdout(repo_path, &args) else {
+        return Vec::new();
+    };
+    if raw.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let live_authored: HashSet<String> = git_stdout(
+        repo_path,
+        &["log", "--all", "--format=%ae %at%x1f%s", &since_arg],
+    )
+    .unwrap_or_default()
+    .lines()
+    .map(str::to_string)
+    .collect();
+    let mut tips = lost_tips(&raw, &live_authored);
+    if tips.is_empty() {
+        return tips;
+    }
+
+    // A rewrite that also reset the author date still carries the same change.
+    let mut tip_args = vec!["--no-walk"];
+    tip_args.extend(tips.iter().map(|c| c.sha.as_str()));
+    let tip_patches = patch_ids(repo_path, &tip_args).unwrap_or_default();
+    let live_patches: HashSet<String> = patch_ids(repo_path, &["--all", &since_arg])
+        .unwrap_or_default()
+        .into_values()
+        .collect();
+    tips.retain(|c| {
+        tip_patches
+            .get(&c.sha)
+            .is_none_or(|patch_id| !live_patches.contains(patch_id))
+    });
+    tips
+}
+
+fn cached_lost_commits(repo_path: &Path) -> Vec<LostCommit> {
+    let cache = LOST_WORK_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(guard) = cache.lock() {
+        if let Some((at, lost)) = guard.get(repo_path) {
+            if at.elapsed() < refresh_after() {
+                return lost.clone();
+            }
+        }
+    }
+    let lost = find_lost_commits(repo_path);
+    if let Ok(mut guard) = cache.lock() {
+        guard.insert(repo_path.to_path_buf(), (Instant::now(), lost.clone()));
+    }
+    lost
+}
+
+pub fn collect_lost_work_alerts(repos: &[Repo]) -> CollectorResult<DashboardAlert> {
+    super::require_git("lost_work", repos)?;
+    let mut alerts = Vec::new();
+    for repo in repos {
+        let lost = cached_lost_commits(&repo.path);
+        let Some(newest) = lost.first() else {
+            continue;
+        };
+        let short = &newest.sha[..newest.sha.len().min(8)];
+        alerts.push(DashboardAlert {
+            severity: "warn".to_string(),
+            title: tr("alert-lost-work-title", &[("repo", &repo.name)]),
+            detail: tr(
+                "alert-lost-work-detail",
+                &[
+                    ("count", &lost.len()),
+                    ("days", &lookback_days()),
+                    ("sha", &short),
+                    ("subject", &newest.subject),
+                ],
+            ),
+            repo: Some(repo.name.clone()),
+            action: Some(ActionCommand::new(
+                "create rescue branch",
+                ActionKind::GitBranchAt {
+                    repo_path: repo.path.to_string_lossy().to_string(),
+                    branch: format!("rescue/{}", short),
+                    commit: newest.sha.clone(),
+                },
+            )),
+        });
+    }
+    Ok(alerts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn parses_reflog_and_fsck_output() {
+        assert_eq!(
+            parse_reflog("abc 1700000000\ndef 1700000100\nbroken\n"),
+            vec![
+                ("abc".to_string(), 1_700_000_000),
+                ("def".to_string(), 1_700_000_100)
+            ]
+        );
+        assert_eq!(
+            parse_fsck_dangling("dangling blob 111\ndangling commit 222\n"),
+            vec!["222".to_string()]
+        );
+    }
+
+    #[test]
+    fn tips_skip_ancestors_rewrites_and_stash_internals() {
+        let raw = [
+            "c3\u{1f}c2\u{1f}300\u{1f}me@x 300\u{1f}wip: parser",
+            "c2\u{1f}base\u{1f}200\u{1f}me@x 200\u{1f}wip: lexer",
+            "a1\u{1f}base\u{1f}250\u{1f}me@x 240\u{1f}fix typo",
+            "i1\u{1f}base\u{1f}260\u{1f}me@x 260\u{1f}index on main: 123 msg",
+            "w1\u{1f}base\u{1f}270\u{1f}me@x 270\u{1f}wip",
+        ]
+        .join("\n");
+        // `a1` was amended; a live `wip` from another time is a different commit.
+        let live: HashSet<String> = ["me@x 240\u{1f}fix typo", "me@x 280\u{1f}wip"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let tips = lost_tips(&raw, &live);
+        let shas: Vec<&str> = tips.iter().map(|c| c.sha.as_str()).collect();
+        assert_eq!(shas, ["c3", "w1"]);
+    }
+
+    #[test]
+    fn finds_commit_orphaned_by_reset() {
+        let base = std::env::temp_dir().join("agentpulse_lost_work_test");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&base)
+                .output()
+                .unwrap()
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(base.join("a.txt"), "a").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        assert!(find_lost_commits(&base).is_empty());
+
+        std::fs::write(base.join("b.txt"), "b").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "important work"]);
+        git(&["reset", "--hard", "HEAD~1"]);
+
+        let lost = find_lost_commits(&base);
+        assert_eq!(lost.len(), 1);
+        assert_eq!(lost[0].subject, "important work");
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn lost_wip_is_not_hidden_by_a_live_wip() {
+        let base = std::env::temp_dir().join("agentpulse_lost_wip_test");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+        let git = |args: &[&str]| {
+            let out = Command::new("git")
+                .args(args)
+                .current_dir(&base)
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&out.stdout).trim().to_string()
+        };
+        let now = chrono::Utc::now().timestamp();
+        let commit = |file: &str, message: &str, ago: i64| {
+            std::fs::write(base.join(file), file).unwrap();
+            git(&["add", "."]);
+            let date = format!("--date=@{}", now - ago);
+            git(&["commit", "-q", "-m", message, &date]);
+            git(&["rev-parse", "HEAD"])
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        commit("a.txt", "init", 300);
+        let lost = commit("b.txt", "wip", 200);
+        git(&["reset", "-q", "--hard", "HEAD~1"]);
+        commit("c.txt", "wip", 100);
+        // Rewrites of the live history are not lost work: a reworded commit
+        // keeps its change, an extended one its author date and subject.
+        git(&["commit", "-q", "--amend", "-m", "wip: c"]);
+        git(&["switch", "-q", "-c", "feature"]);
+        commit("d.txt", "feature", 50);
+        std::fs::write(base.join("e.txt"), "e").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "--amend", "--no-edit"]);
+
+        let found = find_lost_commits(&base);
+        assert_eq!(found.len(), 1, "{:?}", found);
+        assert_eq!(found[0].sha, lost);
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
@@ -0,0 +1,223 @@
+//! Background maintenance for very large repos.
+//!
+//! Every scan runs `git status` in every repo, and on a repo with tens of
+//! thousands of files or gigabytes of history that call is only fast with
+//! help: a commit-graph, scheduled repacks, and a filesystem monitor. This
+//! collector checks whether such a repo has `git maintenance` registered
+//! (the `maintenance.repo` list `git maintenance start` adds to), a
+//! commit-graph file, and `core.fsmonitor`, and suggests enabling
+//! maintenance when it is off. With `scalar` installed, `scalar register`
+//! is suggested instead: it does the same and turns on the fsmonitor too.
+
+use super::git_gc::{format_kib, git_weight};
+use super::CollectorResult;
+use crate::dashboard::{ActionCommand, ActionKind, DashboardAlert};
+use crate::git::{fsmonitor_configured, std_git_command, Repo};
+use crate::i18n::tr;
+use crate::path_utils::resolve_binary_in_path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const REFRESH_AFTER: Duration = Duration::from_secs(600);
+
+/// A repo counts as very large past either limit.
+const LARGE_TRACKED_FILES: u32 = 50_000;
+const LARGE_OBJECTS_KIB: u64 = 2 * 1024 * 1024;
+
+/// What makes `git status` fast on a big repo, and whether it is on.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MaintenanceStatus {
+    pub tracked_files: u32,
+    pub objects_kib: u64,
+    /// Listed in `maintenance.repo`, so scheduled maintenance covers it.
+    pub background: bool,
+    pub commit_graph: bool,
+    pub fsmonitor: bool,
+}
+
+impl MaintenanceStatus {
+    pub fn is_large(&self) -> bool {
+        self.tracked_files >= LARGE_TRACKED_FILES || self.objects_kib >= LARGE_OBJECTS_KIB
+    }
+}
+
+type MaintenanceCache = HashMap<PathBuf, (Instant, Option<MaintenanceStatus>)>;
+
+static MAINTENANCE_CACHE: OnceLock<Mutex<MaintenanceCache>> = OnceLock::new();
+
+fn git_stdout(repo_path: &Path, args: &[&str]) -> Option<String> {
+    let out = std_git_command(args).current_dir(repo_path).output().ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+/// Entry count from the index header (`DIRC`, version, count), without
+/// reading the entries.
+fn index_entries(index: &Path) -> u32 {
+    use std::io::Read;
+    let mut header = [0u8; 12];
+    let read = std::fs::File::open(index).and_then(|mut file| file.read_exact(&mut header));
+    if read.is_err() || &header[..4] != b"DIRC" {
+        return 0;
+    }
+    u32::from_be_bytes([header[8], header[9], header[10], header[11]])
+}
+
+pub fn maintenance_status(repo_path: &Path) -> Option<MaintenanceStatus> {
+    let git_dir = repo_path.join(git_stdout(repo_path, &["rev-parse", "--git-dir"])?);
+    let common_dir = repo_path.join(git_stdout(repo_path, &["rev-parse", "--git-common-dir"])?);
+    let info = common_dir.join("objects").join("info");
+    let toplevel = git_stdout(repo_path, &["rev-parse", "--show-toplevel"]);
+    let background = git_stdout(repo_path, &["config", "--get-all", "maintenance.repo"])
+        .unwrap_or_default()
+        .lines()
+        .any(|registered| {
+            Some(registered) == toplevel.as_deref() || Path::new(registered) == repo_path
+        });
+    Some(MaintenanceStatus {
+        tracked_files: index_entries(&git_dir.join("index")),
+        objects_kib: git_weight(repo_path).map_or(0, |w| w.objects_kib()),
+        background,
+        commit_graph: info.join("commit-graph").exists()
+            || info.join("commit-graphs/commit-graph-chain").exists(),
+        fsmonitor: fsmonitor_configured(
+            git_stdout(repo_path, &["config", "core.fsmonitor"]).as_deref(),
+        ),
+    })
+}
+
+fn cached_status(repo_path: &Path) -> Option<MaintenanceStatus> {
+    let cache = MAINTENANCE_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(guard) = cache.lock() {
+        if let Some((at, status)) = guard.get(repo_path) {
+            if at.elapsed() < REFRESH_AFTER {
+                return status.clone();
+            }
+        }
+    }
+    let status = maintenance_status(repo_path);
+    if let Ok(mut guard) = cache.lock() {
+        guard.insert(repo_path.to_path_buf(), (Instant::now(), status.clone()));
+    }
+    status
+}
+
+fn maintenance_alert(
+    repo: &Repo,
+    status: &MaintenanceStatus,
+    scalar: bool,
+) -> Option<DashboardAlert> {
+    if !status.is_large() || status.background {
+        return None;
+    }
+    let commit_graph = if status.commit_graph {
+        "present"
+    } else {
+        "missing"
+    };
+    let fsmonitor = if status.fsmonitor { "on" } else { "off" };
+    let detail = format!(
+        "{} tracked files, {} of objects · background maintenance off · commit-graph {} · \
+         fsmonitor {} · every scan runs git status here",
+        status.tracked_files,
+        format_kib(status.objects_kib),
+        commit_graph,
+        fsmonitor,
+    );
+    let label = if scalar {
+        "scalar register (maintenance + fsmonitor)"
+    } else {
+        "start background maintenance"
+    };
+    Some(DashboardAlert {
+        severity: "info".to_string(),
+        title: tr("alert-maintenance-title", &[("repo", &repo.name)]),
+        detail,
+        repo: Some(repo.name.clone()),
+        action: Some(ActionCommand::new(
+            label,
+            ActionKind::GitMaintenanceStart {
+                repo_path: repo.path.to_string_lossy().to_string(),
+                scalar,
+            },
+        )),
+    })
+}
+
+pub fn collect_maintenance_alerts(repos: &[Repo]) -> CollectorResult<DashboardAlert> {
+    super::require_git("maintenance", repos)?;
+    let scalar = resolve_binary_in_path("scalar").is_some();
+    Ok(repos
+        .iter()
+        .filter_map(|repo| maintenance_alert(repo, &cached_status(&repo.path)?, scalar))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_maintenance_for_large_repos_without_it() {
+        let repo = Repo::new(PathBuf::from("/src/monorepo"));
+        let status = MaintenanceStatus {
+            tracked_files: 120_000,
+            objects_kib: 3 * 1024 * 1024,
+            background: false,
+            commit_graph: true,
+            fsmonitor: false,
+        };
+        let alert = maintenance_alert(&repo, &status, false).unwrap();
+        assert_eq!(alert.severity, "info");
+        assert_eq!(
+            alert.detail,
+            "120000 tracked files, 3.0 GiB of objects · background maintenance off · \
+             commit-graph present · fsmonitor off · every scan runs git status here"
+        );
+        let action = alert.action.unwrap();
+        assert_eq!(action.command, "git -C \"/src/monorepo\" maintenance start");
+        let scalar = maintenance_alert(&repo, &status, true)
+            .unwrap()
+            .action
+            .unwrap();
+        assert_eq!(scalar.command, "scalar register \"/src/monorepo\"");
+
+        let registered = MaintenanceStatus {
+            background: true,
+            ..status.clone()
+        };
+        assert!(maintenance_alert(&repo, &registered, false).is_none());
+        let small = MaintenanceStatus {
+            tracked_files: 800,
+            objects_kib: 20_000,
+            ..status
+        };
+        assert!(maintenance_alert(&repo, &small, false).is_none());
+    }
+
+    #[test]
+    fn reads_tracked_file_count_from_the_index_header() {
+        let base = std::env::temp_dir().join("agentpulse_maintenance_test");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(&base)
+                .output()
+                .unwrap()
+        };
+        git(&["init"]);
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(base.join(name), name).unwrap();
+        }
+        git(&["add", "."]);
+        let status = maintenance_status(&base).unwrap();
+        assert_eq!(status.tracked_files, 3);
+        assert!(!status.background && !status.is_large());
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
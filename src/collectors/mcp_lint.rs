@@ -0,0 +1,364 @@
+//! Lint for MCP config entries, beyond "does the binary exist".
+//!
+//! Each server entry is checked on its own (env block shape, args, transport
+//! fields, required arguments of well-known servers), then names defined in
+//! more than one place are flagged. Findings carry the line of the offending
+//! key so the detail pane can point into the file.
+
+use crate::dashboard::{LintSeverity, McpLintFinding};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// A server whose config is incomplete without certain arguments or env.
+struct KnownServer {
+    package: &'static str,
+    /// Non-flag arguments needed after the package name.
+    positional: usize,
+    /// What the positional arguments are, for the message.
+    positional_hint: &'static str,
+    env: &'static [&'static str],
+}
+
+const KNOWN_SERVERS: &[KnownServer] = &[
+    KnownServer {
+        package: "@modelcontextprotocol/server-filesystem",
+        positional: 1,
+        positional_hint: "at least one allowed directory",
+        env: &[],
+    },
+    KnownServer {
+        package: "@modelcontextprotocol/server-postgres",
+        positional: 1,
+        positional_hint: "a postgresql:// connection URL",
+        env: &[],
+    },
+    KnownServer {
+        package: "@modelcontextprotocol/server-github",
+        positional: 0,
+        positional_hint: "",
+        env: &["GITHUB_PERSONAL_ACCESS_TOKEN"],
+    },
+    KnownServer {
+        package: "@modelcontextprotocol/server-slack",
+        positional: 0,
+        positional_hint: "",
+        env: &["SLACK_BOT_TOKEN", "SLACK_TEAM_ID"],
+    },
+    KnownServer {
+        package: "@modelcontextprotocol/server-brave-search",
+        positional: 0,
+        positional_hint: "",
+        env: &["BRAVE_API_KEY"],
+    },
+];
+
+/// Transports that take a `url` rather than a `command`.
+const REMOTE_TRANSPORTS: &[&str] = &["sse", "http", "streamable-http", "streamableHttp"];
+
+fn finding(line: Option<usize>, severity: LintSeverity, message: String) -> McpLintFinding {
+    McpLintFinding {
+        line,
+        severity,
+        message,
+    }
+}
+
+/// 1-based line of the first `"key":` at or after `from` (1-based).
+pub(super) fn key_line(raw: &str, key: &str, from: usize) -> Option<usize> {
+    let quoted = format!("\"{}\"", key);
+    raw.lines()
+        .enumerate()
+        .skip(from.saturating_sub(1))
+        .find(|(_, line)| {
+            line.match_indices(&quoted)
+                .any(|(at, _)| line[at + quoted.len()..].trim_start().starts_with(':'))
+        })
+        .map(|(idx, _)| idx + 1)
+}
+
+/// Problems in one server entry; `line` is where its name appears.
+pub(super) fn lint_server(
+    name: &str,
+    cfg: &Value,
+    raw: &str,
+    line: Option<usize>,
+) -> Vec<McpLintFinding> {
+    let mut out = Vec::new();
+    let Some(obj) = cfg.as_object() else {
+        out.push(finding(
+            line,
+            LintSeverity::Error,
+            format!("{} must be an object", name),
+        ));
+        return out;
+    };
+    // Line of one of this entry's keys; the entry's own line otherwise.
+    let at = |key: &str| {
+        if obj.contains_key(key) {
+            line.and_then(|l| key_line(raw, key, l)).or(line)
+        } else {
+            line
+        }
+    };
+    let env_line = at("env");
+
+    // env: an object of string values.
+    match obj.get("env") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(env)) => {
+            for (key, value) in env {
+                let key_at = env_line.and_then(|l| key_line(raw, key, l)).or(env_line);
+                if key.is_empty() || key.contains('=') {
+                    out.push(finding(
+                        key_at,
+                        LintSeverity::Error,
+                        format!("env key {:?} is not a variable name", key),
+                    ));
+                } else if !value.is_string() {
+                    out.push(finding(
+                        key_at,
+                        LintSeverity::Error,
+                        format!("env.{} must be a string, not {}", key, kind(value)),
+                    ));
+                }
+            }
+        }
+        Some(other) => out.push(finding(
+            env_line,
+            LintSeverity::Error,
+            format!("env must be an object of strings, not {}", kind(other)),
+        )),
+    }
+
+    let args: Vec<&str> = match obj.get("args") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(items)) => {
+            if items.iter().any(|v| !v.is_string()) {
+                out.push(finding(
+                    at("args"),
+                    LintSeverity::Error,
+                    "args must be a list of strings".to_string(),
+                ));
+            }
+            items.iter().filter_map(Value::as_str).collect()
+        }
+        Some(other) => {
+            out.push(finding(
+                at("args"),
+                LintSeverity::Error,
+                format!("args must be a list of strings, not {}", kind(other)),
+            ));
+            Vec::new()
+        }
+    };
+
+    // Transport: stdio servers need a command, remote ones a url.
+    let command = obj.get("command").and_then(Value::as_str).unwrap_or("");
+    let url = obj.get("url").and_then(Value::as_str).unwrap_or("");
+    let transport_key = if obj.contains_key("type") {
+        "type"
+    } else {
+        "transport"
+    };
+    let transport = obj.get(transport_key).and_then(Value::as_str);
+    match transport {
+        Some("stdio") if command.is_empty() => out.push(finding(
+            at(transport_key),
+            LintSeverity::Error,
+            "stdio transport needs a command".to_string(),
+        )),
+        Some(t) if REMOTE_TRANSPORTS.contains(&t) && url.is_empty() => out.push(finding(
+            at(transport_key),
+            LintSeverity::Error,
+            format!("{} transport needs a url", t),
+        )),
+        Some(t) if t != "stdio" && !REMOTE_TRANSPORTS.contains(&t) => out.push(finding(
+            at(transport_key),
+            LintSeverity::Error,
+            format!("unknown transport {:?}", t),
+        )),
+        _ => {}
+    }
+    if command.is_empty() && url.is_empty() {
+        out.push(finding(
+            line,
+            LintSeverity::Error,
+            "neither command nor url is set".to_string(),
+        ));
+    } else if !command.is_empty() && !url.is_empty() {
+        out.push(finding(
+            at("url"),
+            LintSeverity::Warn,
+            "both command and url are set; clients disagree on which wins".to_string(),
+        ));
+    }
+    if !url.is_empty() && !url.starts_with("http://") && !url.starts_with("https://") {
+        out.push(finding(
+            at("url"),
+            LintSeverity::Error,
+            format!("url {:?} is not http(s)", url),
+        ));
+    }
+
+    out.extend(lint_known_server(obj, &args, &at));
+    out
+}
+
+fn lint_known_server(
+    obj: &serde_json::Map<String, Value>,
+    args: &[&str],
+    at: &dyn Fn(&str) -> Option<usize>,
+) -> Vec<McpLintFinding> {
+    let mut out = Vec::new();
+    let command = obj.get("command").and_then(Value::as_str).unwrap_or("");
+    let words: Vec<&str> = command
+        .split_whitespace()
+        .chain(args.iter().copied())
+        .collect();
+    for known in KNOWN_SERVERS {
+        let Some(pos) = words.iter().position(|w| {
+            *w == known.package
+                || w.strip_prefix(known.package)
+                    .is_some_and(|v| v.starts_with('@'))
+        }) else {
+            continue;
+        };
+        let given = words[pos + 1..]
+            .iter()
+            .filter(|w| !w.starts_with('-'))
+            .count();
+        if given < known.positional {
+            out.push(finding(
+                at("args"),
+                LintSeverity::Error,
+                format!("{} needs {}", known.package, known.positional_hint),
+            ));
+        }
+        let env = obj.get("env").and_then(Value::as_object);
+        for var in known.env {
+            let set = env.is_some_and(|e| e.get(*var).and_then(Value::as_str).is_some())
+                || std::env::var_os(var).is_some();
+            if !set {
+                out.push(finding(
+                    at("env"),
+                    LintSeverity::Warn,
+                    format!("{} reads {} from env; it is not set", known.package, var),
+                ));
+            }
+        }
+    }
+    out
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Warn about server names defined more than once, across files or under
+/// both `mcpServers` and `servers`. `entries` is (source, name, line).
+pub(super) fn duplicate_findings(
+    entries: &[(String, String, Option<usize>)],
+) -> Vec<(usize, McpLintFinding)> {
+    let mut by_name: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (idx, (_, name, _)) in entries.iter().enumerate() {
+        by_name.entry(name.as_str()).or_default().push(idx);
+    }
+    let mut out = Vec::new();
+    for indexes in by_name.values().filter(|v| v.len() > 1) {
+        for &idx in indexes {
+            let others: Vec<String> = indexes
+                .iter()
+                .filter(|&&other| other != idx)
+                .map(|&other| match entries[other].2 {
+                    Some(line) => format!("{}:{}", entries[other].0, line),
+                    None => entries[other].0.clone(),
+                })
+                .collect();
+            out.push((
+                idx,
+                finding(
+                    entries[idx].2,
+                    LintSeverity::Warn,
+                    format!("also defined in {}", others.join(", ")),
+                ),
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(raw: &str, name: &str) -> Vec<McpLintFinding> {
+        let value: Value = serde_json::from_str(raw).unwrap();
+        let line = key_line(raw, name, 1);
+        lint_server(name, &value["mcpServers"][name], raw, line)
+    }
+
+    #[test]
+    fn flags_bad_env_transport_and_missing_args_with_lines() {
+        let raw = r#"{
+  "mcpServers": {
+    "fs": {
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-filesystem"],
+      "env": { "DEBUG": true }
+    },
+    "remote": { "type": "sse", "command": "node" },
+    "ok": { "command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem@1.0", "~/src"] }
+  }
+}"#;
+        let fs = lint(raw, "fs");
+        let messages: Vec<_> = fs.iter().map(|f| (f.line, f.message.as_str())).collect();
+        assert_eq!(
+            messages,
+            [
+                (Some(6), "env.DEBUG must be a string, not a boolean"),
+                (
+                    Some(5),
+                    "@modelcontextprotocol/server-filesystem needs at least one allowed directory"
+                ),
+            ]
+        );
+        let remote = lint(raw, "remote");
+        assert_eq!(remote.len(), 1);
+        assert_eq!(remote[0].message, "sse transport needs a url");
+        assert_eq!(remote[0].line, Some(8));
+        assert!(lint(raw, "ok").is_empty());
+    }
+
+    #[test]
+    fn warns_on_names_defined_twice() {
+        let entries = vec![
+            (
+                "~/.cursor/mcp.json".to_string(),
+                "github".to_string(),
+                Some(3),
+            ),
+            (
+                "/src/api/.mcp.json".to_string(),
+                "github".to_string(),
+                Some(4),
+            ),
+            (
+                "/src/api/.mcp.json".to_string(),
+                "docs".to_string(),
+                Some(8),
+            ),
+        ];
+        let found = duplicate_findings(&entries);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].0, 0);
+        assert_eq!(found[0].1.message, "also defined in /src/api/.mcp.json:4");
+        assert_eq!(found[1].1.severity, LintSeverity::Warn);
+    }
+}
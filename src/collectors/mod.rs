@@ -1,18 +1,56 @@
+use crate::config::ProvidersConfig;
 use crate::dashboard::{
-    DashboardAlert, DependencyHealth, EnvAuditResult, McpServerHealth, ProviderUsage, RepoProcess,
-    RepoRow, WorktreeRow,
+    ActivityDay, AiClientConfig, CollectorError, DashboardAlert, DependencyHealth, EnvAuditResult,
+    ForgeStatus, HostProbe, LicenseAudit, LocalDependency, McpServerHealth, ProviderUsage,
+    RepoProcess, RepoRow, RepoTicket, RepoWeekStats, WorktreeRow,
 };
-use crate::git::Repo;
+use crate::git::{std_git_command, Repo};
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
+pub mod activity;
+pub mod ai_clients;
 pub mod ai_mcp;
+pub mod auth;
+pub mod backup;
+mod bigquery;
+pub mod changelog;
+pub mod compliance;
+pub mod connectivity;
+pub mod forge;
+pub mod fsck;
+pub mod git_gc;
 pub mod git_worktrees;
+pub mod licenses;
+pub mod local_deps;
+pub mod lost_work;
+pub mod maintenance;
+mod mcp_lint;
 pub mod system_env_deps;
+pub mod tickets;
 
+pub use activity::{collect_activity, collect_week_stats};
+pub use ai_clients::collect_ai_clients;
 pub use ai_mcp::{collect_mcp_servers, collect_provider_usage};
+pub use auth::collect_auth_alerts;
+pub use backup::{collect_backup_alerts, configure_backup};
+pub use changelog::collect_changelog_alerts;
+pub use compliance::collect_compliance_alerts;
+pub use connectivity::{collect_host_probes, configure_connectivity};
+pub use forge::{collect_forge_alerts, collect_forge_status, configure_forge};
+pub use fsck::{collect_fsck_alerts, configure_fsck};
+pub use git_gc::collect_gc_alerts;
 pub use git_worktrees::{collect_git_alerts, collect_repo_rows, collect_worktrees};
+pub use licenses::{collect_license_alerts, collect_license_audit, configure_licenses};
+pub use local_deps::{collect_local_dep_alerts, collect_local_deps};
+pub use lost_work::collect_lost_work_alerts;
+pub use maintenance::collect_maintenance_alerts;
 pub use system_env_deps::{collect_dependency_health, collect_env_audit, collect_repo_processes};
+pub use tickets::{collect_tickets, configure_tickets};
+
+/// What a collector found, or why it could not look. `Ok(vec![])` means
+/// nothing was found.
+pub type CollectorResult<T> = Result<Vec<T>, CollectorError>;
 
 #[derive(Debug, Clone, Default)]
 pub struct CollectorOutput {
@@ -21,9 +59,19 @@ pub struct CollectorOutput {
     pub worktrees: Vec<WorktreeRow>,
     pub processes: Vec<RepoProcess>,
     pub dependencies: Vec<DependencyHealth>,
+    pub local_deps: Vec<LocalDependency>,
     pub env_audit: Vec<EnvAuditResult>,
+    pub licenses: Vec<LicenseAudit>,
     pub mcp_servers: Vec<McpServerHealth>,
+    pub ai_clients: Vec<AiClientConfig>,
     pub providers: Vec<ProviderUsage>,
+    pub host_probes: Vec<HostProbe>,
+    pub activity: Vec<ActivityDay>,
+    pub this_week: Vec<RepoWeekStats>,
+    pub tickets: Vec<RepoTicket>,
+    pub pull_requests: Vec<ForgeStatus>,
+    /// Collectors that failed; the fields they feed are left empty.
+    pub errors: Vec<CollectorError>,
 }
 
 #[derive(Clone)]
@@ -35,23 +83,172 @@ struct ProviderSnapshotCacheEntry {
 static PROVIDER_SNAPSHOT_CACHE: OnceLock<Mutex<Option<ProviderSnapshotCacheEntry>>> =
     OnceLock::new();
 
+/// How much work a collection pass may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CollectMode {
+    #[default]
+    Full,
+    /// Reuse the last provider snapshot instead of hitting usage APIs.
+    PowerSave,
+}
+
+/// Apply `[providers]` config; a change discards the cached provider snapshot.
+pub fn configure_providers(cfg: &ProvidersConfig) {
+    if ai_mcp::set_provider_settings(cfg) {
+        if let Ok(mut guard) = PROVIDER_SNAPSHOT_CACHE
+            .get_or_init(|| Mutex::new(None))
+            .lock()
+        {
+            *guard = None;
+        }
+    }
+}
+
 pub fn collect_all(repos: &[Repo]) -> CollectorOutput {
-    let repo_rows = collect_repo_rows(repos);
-    let worktrees = collect_worktrees(repos);
+    collect_all_with(repos, CollectMode::Full)
+}
+
+pub fn collect_all_with(repos: &[Repo], mode: CollectMode) -> CollectorOutput {
+    let mut errors = Vec::new();
+    let repo_rows = or_record(collect_repo_rows(repos), &mut errors);
+    let worktrees = or_record(collect_worktrees(repos), &mut errors);
+    let mut alerts = collect_git_alerts(repos, &repo_rows, &worktrees);
+    alerts.extend(or_record(collect_changelog_alerts(repos), &mut errors));
+    alerts.extend(or_record(collect_lost_work_alerts(repos), &mut errors));
+    alerts.extend(or_record(collect_gc_alerts(repos), &mut errors));
+    alerts.extend(or_record(collect_maintenance_alerts(repos), &mut errors));
+    alerts.extend(or_record(collect_backup_alerts(repos), &mut errors));
+    alerts.extend(or_record(
+        collect_fsck_alerts(repos, mode == CollectMode::Full),
+        &mut errors,
+    ));
+    alerts.extend(or_record(collect_auth_alerts(repos), &mut errors));
+    alerts.extend(or_record(collect_compliance_alerts(repos), &mut errors));
+    let local_deps = or_record(collect_local_deps(repos), &mut errors);
+    alerts.extend(collect_local_dep_alerts(repos, &local_deps));
+    let licenses = or_record(collect_license_audit(repos), &mut errors);
+    alerts.extend(collect_license_alerts(&licenses));
+    let pull_requests = or_record(
+        collect_forge_status(repos, mode == CollectMode::Full),
+        &mut errors,
+    );
+    alerts.extend(collect_forge_alerts(&pull_requests));
+    let providers = match mode {
+        CollectMode::Full => collect_provider_usage_cadenced(),
+        CollectMode::PowerSave => Ok(cached_provider_usage()),
+    };
 
     CollectorOutput {
-        alerts: collect_git_alerts(repos, &repo_rows, &worktrees),
+        alerts,
         repos: repo_rows,
         worktrees,
-        processes: collect_repo_processes(repos),
-        dependencies: collect_dependency_health(repos),
-        env_audit: collect_env_audit(repos),
-        mcp_servers: collect_mcp_servers(repos),
-        providers: collect_provider_usage_cadenced(),
+        processes: or_record(collect_repo_processes(repos), &mut errors),
+        dependencies: or_record(collect_dependency_health(repos), &mut errors),
+        local_deps,
+        env_audit: or_record(collect_env_audit(repos), &mut errors),
+        licenses,
+        mcp_servers: or_record(collect_mcp_servers(repos), &mut errors),
+        ai_clients: or_record(collect_ai_clients(), &mut errors),
+        providers: or_record(providers, &mut errors),
+        host_probes: or_record(collect_host_probes(repos), &mut errors),
+        activity: or_record(collect_activity(repos), &mut errors),
+        this_week: or_record(collect_week_stats(repos), &mut errors),
+        tickets: or_record(
+            collect_tickets(repos, mode == CollectMode::Full),
+            &mut errors,
+        ),
+        pull_requests,
+        errors,
     }
 }
 
-fn collect_provider_usage_cadenced() -> Vec<ProviderUsage> {
+/// The collected items, or none with the failure noted in `errors`.
+fn or_record<T>(result: CollectorResult<T>, errors: &mut Vec<CollectorError>) -> Vec<T> {
+    result.unwrap_or_else(|err| {
+        errors.push(err);
+        Vec::new()
+    })
+}
+
+/// How long a `git --version` check is trusted.
+const GIT_CHECK_TTL: Duration = Duration::from_secs(60);
+
+/// When git was last checked, and whether it ran.
+type GitCheck = (Instant, Result<(), String>);
+
+static GIT_CHECK: OnceLock<Mutex<Option<GitCheck>>> = OnceLock::new();
+
+/// `Err` for `collector` when git cannot be started. The per-repo git
+/// collectors read a failed command as "nothing to report", so without this
+/// a missing or broken git would look like a set of healthy repos.
+fn require_git(collector: &str, repos: &[Repo]) -> Result<(), CollectorError> {
+    if repos.is_empty() {
+        return Ok(());
+    }
+    let cache = GIT_CHECK.get_or_init(|| Mutex::new(None));
+    let cached = cache
+        .lock()
+        .ok()
+        .and_then(|guard| guard.clone())
+        .filter(|(at, _)| at.elapsed() < GIT_CHECK_TTL);
+    let result = match cached {
+        Some((_, result)) => result,
+        None => {
+            let result = match std_git_command(&["--version"]).output() {
+                Ok(out) if out.status.success() => Ok(()),
+                Ok(out) => Err(format!("git --version exited with {}", out.status)),
+                Err(e) => Err(format!("git failed to start: {}", e)),
+            };
+            if let Ok(mut guard) = cache.lock() {
+                *guard = Some((Instant::now(), result.clone()));
+            }
+            result
+        }
+    };
+    result.map_err(|message| CollectorError::new(collector, message))
+}
+
+/// Run `curl` with `args`, passing `secrets` as `(option, value)` pairs such
+/// as `("header", "Authorization: Bearer …")` in a config read from stdin
+/// (`-K -`), so tokens never show up in the process list.
+fn curl_with_secrets(
+    args: &[String],
+    secrets: &[(&str, String)],
+) -> std::io::Result<std::process::Output> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("curl")
+        .args(["-K", "-"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let config: String = secrets
+            .iter()
+            .map(|(option, value)| {
+                let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+                format!("{} = \"{}\"\n", option, value)
+            })
+            .collect();
+        stdin.write_all(config.as_bytes())?;
+    }
+    child.wait_with_output()
+}
+
+/// Last provider snapshot regardless of age (empty if never collected).
+fn cached_provider_usage() -> Vec<ProviderUsage> {
+    PROVIDER_SNAPSHOT_CACHE
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(|entry| entry.providers.clone()))
+        .unwrap_or_default()
+}
+
+fn collect_provider_usage_cadenced() -> CollectorResult<ProviderUsage> {
     let refresh_secs = std::env::var("AGENTPULSE_PROVIDER_REFRESH_SECS")
         .ok()
         .and_then(|v| v.parse::<u64>().ok())
@@ -63,12 +260,13 @@ fn collect_provider_usage_cadenced() -> Vec<ProviderUsage> {
     if let Ok(guard) = cache.lock() {
         if let Some(entry) = guard.as_ref() {
             if entry.generated_at.elapsed() < refresh_after {
-                return entry.providers.clone();
+                return Ok(entry.providers.clone());
             }
         }
     }
 
-    let providers = collect_provider_usage();
+    // Failures are not cached, so the next pass retries.
+    let providers = collect_provider_usage()?;
 
     if let Ok(mut guard) = cache.lock() {
         *guard = Some(ProviderSnapshotCacheEntry {
@@ -77,5 +275,5 @@ fn collect_provider_usage_cadenced() -> Vec<ProviderUsage> {
         });
     }
 
-    providers
+    Ok(providers)
 }
@@ -1,23 +1,44 @@
-use crate::dashboard::{ActionCommand, ActionKind, DependencyHealth, EnvAuditResult, RepoProcess};
-use crate::git::Repo;
+use super::CollectorResult;
+use crate::dashboard::{
+    ActionCommand, ActionKind, CollectorError, DependencyHealth, EnvAuditResult,
+    NodePackageManager, RepoProcess,
+};
+use crate::git::{std_git_command, Repo};
 use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
-pub fn collect_repo_processes(repos: &[Repo]) -> Vec<RepoProcess> {
+/// Node lockfiles in detection order.
+const NODE_LOCKFILES: &[&str] = &[
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "bun.lock",
+    "bun.lockb",
+];
+
+/// A manifest must be this much newer than its lockfile to count as drift;
+/// a fresh checkout writes both within the same moment.
+const LOCK_MTIME_SLACK: Duration = Duration::from_secs(2);
+
+pub fn collect_repo_processes(repos: &[Repo]) -> CollectorResult<RepoProcess> {
     let repo_paths: Vec<(String, String)> = repos
         .iter()
         .map(|r| (r.name.clone(), r.path.to_string_lossy().to_string()))
         .collect();
 
-    let output = match Command::new("ps")
+    let output = Command::new("ps")
         .args(["-axo", "pid=,etime=,command="])
         .output()
-    {
-        Ok(o) if o.status.success() => o,
-        _ => return Vec::new(),
-    };
+        .map_err(|e| CollectorError::new("processes", format!("ps failed to start: {}", e)))?;
+    if !output.status.success() {
+        return Err(CollectorError::new(
+            "processes",
+            format!("ps exited with {}", output.status),
+        ));
+    }
 
     let raw = String::from_utf8_lossy(&output.stdout);
     let mut rows = Vec::new();
@@ -56,10 +77,10 @@ pub fn collect_repo_processes(repos: &[Repo]) -> Vec<RepoProcess> {
 
     rows.sort_by(|a, b| a.repo.cmp(&b.repo).then_with(|| a.pid.cmp(&b.pid)));
     rows.truncate(200);
-    rows
+    Ok(rows)
 }
 
-pub fn collect_dependency_health(repos: &[Repo]) -> Vec<DependencyHealth> {
+pub fn collect_dependency_health(repos: &[Repo]) -> CollectorResult<DependencyHealth> {
     let mut out = Vec::new();
 
     for repo in repos {
@@ -70,20 +91,57 @@ pub fn collect_dependency_health(repos: &[Repo]) -> Vec<DependencyHealth> {
 
         let has = |f: &str| root.join(f).exists();
 
+        let stale =
+            |manifest: &str, lock: &str| modified_after(&root.join(manifest), &root.join(lock));
+
         if has("package.json") {
             ecosystems.push("node".to_string());
-            if !(has("package-lock.json")
-                || has("yarn.lock")
-                || has("pnpm-lock.yaml")
-                || has("bun.lockb"))
-            {
-                issues.push("package.json without lockfile".to_string());
-                action = Some(ActionCommand::new(
-                    "create lockfile",
-                    ActionKind::NpmInstallLockfile {
-                        repo_path: root.to_string_lossy().to_string(),
-                    },
-                ));
+            let manager = detect_node_package_manager(root);
+            let locks: Vec<&str> = NODE_LOCKFILES.iter().copied().filter(|f| has(f)).collect();
+            let lock_managers: BTreeSet<&str> = locks
+                .iter()
+                .filter_map(|f| lockfile_manager(f))
+                .map(NodePackageManager::binary)
+                .collect();
+            if lock_managers.len() > 1 {
+                issues.push(format!("conflicting lockfiles: {}", locks.join(", ")));
+            }
+            // Prefer the lockfile the detected manager writes when several exist.
+            let lock = locks
+                .iter()
+                .find(|f| {
+                    lockfile_manager(f).map(NodePackageManager::binary) == Some(manager.binary())
+                })
+                .or(locks.first());
+            match lock {
+                None => {
+                    issues.push("package.json without lockfile".to_string());
+                    action = Some(ActionCommand::new(
+                        format!("create lockfile ({})", manager.binary()),
+                        ActionKind::NpmInstallLockfile {
+                            repo_path: root.to_string_lossy().to_string(),
+                            manager,
+                        },
+                    ));
+                }
+                Some(lock) => {
+                    // package-lock.json records the manifest's ranges; compare
+                    // those instead of trusting mtimes when possible.
+                    let drifted = (*lock == "package-lock.json")
+                        .then(|| npm_lock_in_sync(root))
+                        .flatten()
+                        .map_or_else(|| stale("package.json", lock), |in_sync| !in_sync);
+                    if drifted {
+                        issues.push(format!("package.json changed after {}", lock));
+                        action = Some(ActionCommand::new(
+                            format!("refresh lockfile ({})", manager.binary()),
+                            ActionKind::NpmInstallLockfile {
+                                repo_path: root.to_string_lossy().to_string(),
+                                manager,
+                            },
+                        ));
+                    }
+                }
             }
         }
 
@@ -97,6 +155,24 @@ pub fn collect_dependency_health(repos: &[Repo]) -> Vec<DependencyHealth> {
                         repo_path: root.to_string_lossy().to_string(),
                     },
                 ));
+            } else {
+                let drift = match cargo_lock_missing(root) {
+                    Some(missing) if !missing.is_empty() => {
+                        Some(format!("Cargo.lock is missing {}", missing.join(", ")))
+                    }
+                    Some(_) => None,
+                    None => stale("Cargo.toml", "Cargo.lock")
+                        .then(|| "Cargo.toml changed after Cargo.lock".to_string()),
+                };
+                if let Some(issue) = drift {
+                    issues.push(issue);
+                    action.get_or_insert(ActionCommand::new(
+                        "sync Cargo.lock",
+                        ActionKind::CargoUpdateWorkspace {
+                            repo_path: root.to_string_lossy().to_string(),
+                        },
+                    ));
+                }
             }
         }
 
@@ -112,6 +188,17 @@ pub fn collect_dependency_health(repos: &[Repo]) -> Vec<DependencyHealth> {
                         repo_path: root.to_string_lossy().to_string(),
                     },
                 ));
+            } else if has("uv.lock") && stale("pyproject.toml", "uv.lock") {
+                issues.push("pyproject.toml changed after uv.lock".to_string());
+                action.get_or_insert(ActionCommand::new(
+                    "refresh uv.lock",
+                    ActionKind::UvLock {
+                        repo_path: root.to_string_lossy().to_string(),
+                    },
+                ));
+            } else if has("poetry.lock") && stale("pyproject.toml", "poetry.lock") {
+                issues
+                    .push("pyproject.toml changed after poetry.lock (run poetry lock)".to_string());
             }
             if has("requirements.txt") {
                 let unconstrained =
@@ -129,6 +216,10 @@ pub fn collect_dependency_health(repos: &[Repo]) -> Vec<DependencyHealth> {
                     ));
                 }
             }
+            if let Some((issue, sync)) = python_env_issue(root) {
+                issues.push(issue);
+                action.get_or_insert(sync);
+            }
         }
 
         if has("go.mod") {
@@ -141,6 +232,14 @@ pub fn collect_dependency_health(repos: &[Repo]) -> Vec<DependencyHealth> {
                         repo_path: root.to_string_lossy().to_string(),
                     },
                 ));
+            } else if stale("go.mod", "go.sum") {
+                issues.push("go.mod changed after go.sum".to_string());
+                action.get_or_insert(ActionCommand::new(
+                    "tidy go.sum",
+                    ActionKind::GoModTidy {
+                        repo_path: root.to_string_lossy().to_string(),
+                    },
+                ));
             }
         }
 
@@ -154,6 +253,14 @@ pub fn collect_dependency_health(repos: &[Repo]) -> Vec<DependencyHealth> {
                         repo_path: root.to_string_lossy().to_string(),
                     },
                 ));
+            } else if stale("Gemfile", "Gemfile.lock") {
+                issues.push("Gemfile changed after Gemfile.lock".to_string());
+                action.get_or_insert(ActionCommand::new(
+                    "refresh Gemfile.lock",
+                    ActionKind::BundleLock {
+                        repo_path: root.to_string_lossy().to_string(),
+                    },
+                ));
             }
         }
 
@@ -176,10 +283,149 @@ pub fn collect_dependency_health(repos: &[Repo]) -> Vec<DependencyHealth> {
             .cmp(&a.issue_count)
             .then_with(|| a.repo.cmp(&b.repo))
     });
-    out
+    Ok(out)
 }
 
-pub fn collect_env_audit(repos: &[Repo]) -> Vec<EnvAuditResult> {
+/// The package manager for a Node repo: the `packageManager` field of
+/// package.json wins, then whichever lockfile exists, else npm.
+fn detect_node_package_manager(root: &Path) -> NodePackageManager {
+    let declared = fs::read_to_string(root.join("package.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .and_then(|manifest| {
+            manifest
+                .get("packageManager")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        });
+    if let Some(spec) = declared {
+        let (name, version) = spec.split_once('@').unwrap_or((spec.as_str(), ""));
+        match name {
+            "npm" => return NodePackageManager::Npm,
+            "pnpm" => return NodePackageManager::Pnpm,
+            "bun" => return NodePackageManager::Bun,
+            "yarn" if version.starts_with("1.") => return NodePackageManager::YarnClassic,
+            "yarn" => return NodePackageManager::Yarn,
+            _ => {}
+        }
+    }
+    NODE_LOCKFILES
+        .iter()
+        .find(|f| root.join(f).exists())
+        .and_then(|f| {
+            if *f == "yarn.lock" {
+                // Yarn 2+ lockfiles carry a `__metadata` entry; v1 ones don't.
+                let raw = fs::read_to_string(root.join(f)).unwrap_or_default();
+                return Some(if raw.contains("__metadata:") {
+                    NodePackageManager::Yarn
+                } else {
+                    NodePackageManager::YarnClassic
+                });
+            }
+            lockfile_manager(f)
+        })
+        .unwrap_or_default()
+}
+
+/// Which package manager writes `lockfile` (yarn versions are not told apart).
+fn lockfile_manager(lockfile: &str) -> Option<NodePackageManager> {
+    match lockfile {
+        "package-lock.json" => Some(NodePackageManager::Npm),
+        "pnpm-lock.yaml" => Some(NodePackageManager::Pnpm),
+        "yarn.lock" => Some(NodePackageManager::Yarn),
+        "bun.lock" | "bun.lockb" => Some(NodePackageManager::Bun),
+        _ => None,
+    }
+}
+
+/// Whether `manifest` was modified after `lock`, beyond `LOCK_MTIME_SLACK`.
+fn modified_after(manifest: &Path, lock: &Path) -> bool {
+    let mtime = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+    match (mtime(manifest), mtime(lock)) {
+        (Some(manifest), Some(lock)) => manifest
+            .duration_since(lock)
+            .is_ok_and(|ahead| ahead > LOCK_MTIME_SLACK),
+        _ => false,
+    }
+}
+
+/// Compare package.json's dependency ranges with the root entry that npm
+/// lockfile v2+ records; `None` when there is no such entry to compare.
+fn npm_lock_in_sync(root: &Path) -> Option<bool> {
+    let read = |name: &str| -> Option<serde_json::Value> {
+        serde_json::from_str(&fs::read_to_string(root.join(name)).ok()?).ok()
+    };
+    let manifest = read("package.json")?;
+    let lock = read("package-lock.json")?;
+    let recorded = lock.get("packages")?.get("")?;
+    let empty = serde_json::Map::new();
+    Some(
+        [
+            "dependencies",
+            "devDependencies",
+            "optionalDependencies",
+            "peerDependencies",
+        ]
+        .iter()
+        .all(|field| {
+            let deps = |v: &serde_json::Value| {
+                v.get(*field)
+                    .and_then(|d| d.as_object())
+                    .unwrap_or(&empty)
+                    .clone()
+            };
+            deps(&manifest) == deps(recorded)
+        }),
+    )
+}
+
+/// Direct dependencies of the root package that `Cargo.lock` does not list;
+/// `None` for virtual workspaces or unreadable files.
+fn cargo_lock_missing(root: &Path) -> Option<Vec<String>> {
+    let manifest: toml::Table = fs::read_to_string(root.join("Cargo.toml"))
+        .ok()?
+        .parse()
+        .ok()?;
+    manifest.get("package")?;
+    let lock: toml::Table = fs::read_to_string(root.join("Cargo.lock"))
+        .ok()?
+        .parse()
+        .ok()?;
+    let locked: BTreeSet<&str> = lock
+        .get("package")?
+        .as_array()?
+        .iter()
+        .filter_map(|p| p.get("name")?.as_str())
+        .collect();
+
+    let mut tables = crate::collectors::local_deps::dep_tables(&manifest);
+    for target in manifest
+        .get("target")
+        .and_then(|t| t.as_table())
+        .into_iter()
+        .flat_map(|t| t.values())
+        .filter_map(|v| v.as_table())
+    {
+        tables.extend(crate::collectors::local_deps::dep_tables(target));
+    }
+    let missing = tables
+        .into_iter()
+        .flatten()
+        .map(|(name, spec)| {
+            // `foo = { package = "bar" }` locks as `bar`.
+            spec.get("package")
+                .and_then(|p| p.as_str())
+                .unwrap_or(name)
+                .to_string()
+        })
+        .filter(|name| !locked.contains(name.as_str()))
+        .collect::<BTreeSet<_>>();
+    Some(missing.into_iter().collect())
+}
+
+pub fn collect_env_audit(repos: &[Repo]) -> CollectorResult<EnvAuditResult> {
+    // Tracked secrets are found with `git ls-files`.
+    super::require_git("env_audit", repos)?;
     let mut out = Vec::new();
 
     for repo in repos {
@@ -266,7 +512,7 @@ pub fn collect_env_audit(repos: &[Repo]) -> Vec<EnvAuditResult> {
             })
             .then_with(|| a.repo.cmp(&b.repo))
     });
-    out
+    Ok(out)
 }
 
 fn discover_env_files(root: &Path) -> Vec<PathBuf> {
@@ -343,8 +589,7 @@ fn is_example_env_file(path: &Path) -> bool {
 }
 
 fn is_tracked_file(repo_root: &Path, rel_path: &str) -> bool {
-    match Command::new("git")
-        .args(["ls-files", "--error-unmatch", rel_path])
+    match std_git_command(&["ls-files", "--error-unmatch", rel_path])
         .current_dir(repo_root)
         .output()
     {
@@ -353,6 +598,254 @@ fn is_tracked_file(repo_root: &Path, rel_path: &str) -> bool {
     }
 }
 
+/// Virtualenv health for repos with a uv.lock or requirements.txt to sync
+/// from: missing, built for a different Python than `.python-version` pins,
+/// or holding package versions that differ from the lock. Only a venv the
+/// repo points at (see [`declared_venv`]) counts as missing; repos using one
+/// kept elsewhere (conda, pyenv, poetry) are skipped.
+fn python_env_issue(root: &Path) -> Option<(String, ActionCommand)> {
+    let repo_path = root.to_string_lossy().to_string();
+    let uv = root.join("uv.lock").exists();
+    let (lock_name, pins) = if uv {
+        (
+            "uv.lock",
+            uv_lock_pins(&fs::read_to_string(root.join("uv.lock")).ok()?),
+        )
+    } else if root.join("requirements.txt").exists() {
+        (
+            "requirements.txt",
+            requirement_pins(&fs::read_to_string(root.join("requirements.txt")).ok()?),
+        )
+    } else {
+        return None;
+    };
+    let dir = declared_venv(root)?;
+    let pinned = pinned_python_version(root);
+    let sync = |label: &str, recreate: bool| {
+        let kind = if uv {
+            ActionKind::UvSync {
+                repo_path: repo_path.clone(),
+            }
+        } else {
+            ActionKind::PipVenvSync {
+                repo_path: repo_path.clone(),
+                python: pinned
+                    .as_deref()
+                    .and_then(minor_version)
+                    .map_or_else(|| "python3".to_string(), |v| format!("python{}", v)),
+                recreate,
+                venv: (dir != ".venv").then(|| dir.clone()),
+            }
+        };
+        ActionCommand::new(label, kind)
+    };
+
+    let venv = root.join(&dir);
+    let Ok(cfg) = fs::read_to_string(venv.join("pyvenv.cfg")) else {
+        return Some((
+            format!("no {} virtualenv", dir),
+            sync(&format!("create {}", dir), false),
+        ));
+    };
+    if let (Some(pin), Some(built)) = (&pinned, venv_python_version(&cfg)) {
+        if !version_matches(&built, pin) {
+            return Some((
+                format!(
+                    "{} uses Python {} but .python-version pins {}",
+                    dir, built, pin
+                ),
+                sync(&format!("recreate {}", dir), true),
+            ));
+        }
+    }
+    let installed = installed_packages(&venv);
+    // uv.lock lists every platform and optional group, so only packages that
+    // are installed at another version count there; requirements.txt pins
+    // are all expected to be present.
+    let drifted = pins
+        .iter()
+        .filter(|(name, version)| match installed.get(name) {
+            Some(have) => have != version,
+            None => !uv,
+        })
+        .count();
+    (drifted > 0).then(|| {
+        (
+            format!(
+                "{} package(s) in {} differ from {}",
+                drifted, dir, lock_name
+            ),
+            sync(&format!("sync {}", dir), false),
+        )
+    })
+}
+
+/// The virtualenv directory a repo points at, relative to its root: the one
+/// `.envrc` activates or `.vscode/settings.json` picks as interpreter, else
+/// an existing `.venv` or `venv`. `None` when nothing points at one.
+fn declared_venv(root: &Path) -> Option<String> {
+    let inside = |path: &str| {
+        let path = path
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'')
+            .trim_start_matches("${workspaceFolder}/")
+            .trim_start_matches("./")
+            .trim_end_matches('/');
+        let relative = !path.is_empty() && !path.starts_with(['/', '~', '$']);
+        (relative && !path.split('/').any(|part| part == "..")).then(|| path.to_string())
+    };
+    let envrc = fs::read_to_string(root.join(".envrc")).unwrap_or_default();
+    let from_envrc = envrc.lines().find_map(|line| {
+        let line = line.trim();
+        let activated = ["source ", ". "]
+            .iter()
+            .find_map(|cmd| line.strip_prefix(cmd)?.strip_suffix("/bin/activate"));
+        let exported = line
+            .strip_prefix("export ")
+            .unwrap_or(line)
+            .strip_prefix("VIRTUAL_ENV=");
+        inside(activated.or(exported)?)
+    });
+    let settings = fs::read_to_string(root.join(".vscode").join("settings.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok());
+    let from_editor = ["python.defaultInterpreterPath", "python.pythonPath"]
+        .iter()
+        .find_map(|key| settings.as_ref()?.get(key)?.as_str())
+        .and_then(|python| {
+            let python = python.replace('\\', "/");
+            let dir = python
+                .strip_suffix("/bin/python")
+                .or_else(|| python.strip_suffix("/bin/python3"))
+                .or_else(|| python.strip_suffix("/Scripts/python.exe"))?;
+            inside(dir)
+        });
+    from_envrc.or(from_editor).or_else(|| {
+        [".venv", "venv"]
+            .into_iter()
+            .find(|dir| root.join(dir).is_dir())
+            .map(str::to_string)
+    })
+}
+
+/// First version in `.python-version` (pyenv and uv), without a `cpython-` prefix.
+fn pinned_python_version(root: &Path) -> Option<String> {
+    let raw = fs::read_to_string(root.join(".python-version")).ok()?;
+    raw.lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| l.trim_start_matches("cpython-").to_string())
+}
+
+/// Python version a venv was built with: `version` (venv module) or
+/// `version_info` (uv, virtualenv) in pyvenv.cfg.
+fn venv_python_version(cfg: &str) -> Option<String> {
+    cfg.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        matches!(key.trim(), "version" | "version_info").then(|| {
+            value
+                .trim()
+                .split('.')
+                .take(3)
+                .collect::<Vec<_>>()
+                .join(".")
+        })
+    })
+}
+
+/// `3.12.4` matches the pins `3`, `3.12`, and `3.12.4`.
+fn version_matches(built: &str, pin: &str) -> bool {
+    let built: Vec<&str> = built.split('.').collect();
+    pin.split('.')
+        .enumerate()
+        .all(|(i, part)| built.get(i) == Some(&part))
+}
+
+/// `3.12` from `3.12.4`, for picking a `python3.12` interpreter; `None`
+/// for pins that are not plain numbers (`pypy3.10`, `system`), which the
+/// interpreter name must not be built from.
+fn minor_version(version: &str) -> Option<String> {
+    let mut parts = version.trim().split('.');
+    let (major, minor) = (parts.next()?, parts.next()?);
+    let numeric = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    (numeric(major) && numeric(minor)).then(|| format!("{}.{}", major, minor))
+}
+
+/// PEP 503 normalized name: lowercase, runs of `-_.` become `-`.
+fn normalize_package(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !out.ends_with('-') {
+                out.push('-');
+            }
+        } else {
+            out.push(c.to_ascii_lowercase());
+        }
+    }
+    out
+}
+
+/// `[[package]]` name/version pairs from uv.lock, skipping the project
+/// itself (editable or virtual source).
+fn uv_lock_pins(raw: &str) -> Vec<(String, String)> {
+    let Ok(lock) = raw.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    lock.get("package")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|pkg| {
+            pkg.get("source")
+                .and_then(|s| s.as_table())
+                .is_none_or(|s| !s.contains_key("editable") && !s.contains_key("virtual"))
+        })
+        .filter_map(|pkg| {
+            Some((
+                normalize_package(pkg.get("name")?.as_str()?),
+                pkg.get("version")?.as_str()?.to_string(),
+            ))
+        })
+        .collect()
+}
+
+/// Exact `name==version` pins from requirements.txt; lines with environment
+/// markers are skipped since they may not apply to this machine.
+fn requirement_pins(raw: &str) -> Vec<(String, String)> {
+    raw.lines()
+        .map(|l| l.split('#').next().unwrap_or("").trim())
+        .filter(|l| !l.contains(';'))
+        .filter_map(|l| {
+            let (name, version) = l.split_once("==")?;
+            let name = name.split('[').next().unwrap_or(name);
+            let version = version.split_whitespace().next()?;
+            Some((normalize_package(name), version.to_string()))
+        })
+        .collect()
+}
+
+/// Installed distributions from `*.dist-info` folders in the venv's
+/// site-packages (`lib/python3.X/site-packages`, or `Lib/site-packages` on Windows).
+fn installed_packages(venv: &Path) -> std::collections::HashMap<String, String> {
+    let mut site_dirs = vec![venv.join("Lib").join("site-packages")];
+    if let Ok(entries) = fs::read_dir(venv.join("lib")) {
+        site_dirs.extend(entries.flatten().map(|e| e.path().join("site-packages")));
+    }
+    site_dirs
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let stem = file_name.strip_suffix(".dist-info")?;
+            let (name, version) = stem.split_once('-')?;
+            Some((normalize_package(name), version.to_string()))
+        })
+        .collect()
+}
+
 fn count_unconstrained_requirements(path: &Path) -> usize {
     let Ok(raw) = fs::read_to_string(path) else {
         return 0;
@@ -406,4 +899,192 @@ mod tests {
         assert_eq!(count_unconstrained_requirements(&tmp), 1);
         let _ = fs::remove_file(&tmp);
     }
+
+    #[test]
+    fn flags_lockfiles_that_drifted_from_their_manifest() {
+        let root = std::env::temp_dir().join("agentpulse_lock_drift_test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("package.json"),
+            r#"{"dependencies":{"left-pad":"^1.3.0","zod":"^3"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("package-lock.json"),
+            r#"{"lockfileVersion":3,"packages":{"":{"dependencies":{"left-pad":"^1.3.0"}}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n[dependencies]\nserde = \"1\"\njson = { package = \"serde_json\", version = \"1\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("Cargo.lock"),
+            "version = 3\n[[package]]\nname = \"demo\"\n[[package]]\nname = \"serde\"\n",
+        )
+        .unwrap();
+        fs::write(root.join("Gemfile"), "gem 'rails'\n").unwrap();
+        fs::write(root.join("Gemfile.lock"), "GEM\n").unwrap();
+        let old = std::time::SystemTime::now() - Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(root.join("Gemfile.lock"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let health = collect_dependency_health(&[Repo::new(root.clone())]).unwrap();
+        let issues = &health[0].issues;
+        assert!(issues.contains(&"package.json changed after package-lock.json".to_string()));
+        assert!(issues.contains(&"Cargo.lock is missing serde_json".to_string()));
+        assert!(issues.contains(&"Gemfile changed after Gemfile.lock".to_string()));
+        assert!(matches!(
+            health[0].action.as_ref().unwrap().action,
+            ActionKind::NpmInstallLockfile { .. }
+        ));
+
+        // Once the lockfile records the same ranges, npm is considered in sync.
+        fs::write(
+            root.join("package-lock.json"),
+            r#"{"lockfileVersion":3,"packages":{"":{"dependencies":{"left-pad":"^1.3.0","zod":"^3"}}}}"#,
+        )
+        .unwrap();
+        assert_eq!(npm_lock_in_sync(&root), Some(true));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn only_numeric_pins_name_an_interpreter() {
+        assert_eq!(minor_version("3.12.4").as_deref(), Some("3.12"));
+        assert_eq!(minor_version("3.12").as_deref(), Some("3.12"));
+        assert_eq!(minor_version("3"), None);
+        assert_eq!(minor_version("pypy3.10"), None);
+        assert_eq!(minor_version("3.12; rm -rf ~"), None);
+        assert_eq!(minor_version("../../bin/sh.1"), None);
+    }
+
+    #[test]
+    fn checks_the_venv_against_the_pinned_python_and_lock() {
+        let root = std::env::temp_dir().join("agentpulse_python_env_test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("requirements.txt"),
+            "Requests==2.32.0\nrich[jupyter]==13.7.1  # ui\ncolorama==0.4.6; sys_platform == 'win32'\n",
+        )
+        .unwrap();
+        fs::write(root.join(".python-version"), "3.12\n").unwrap();
+        let issue = |root: &Path| python_env_issue(root).map(|(issue, a)| (issue, a.action));
+
+        // Nothing points at a venv in the repo, so one kept elsewhere is fine.
+        assert!(issue(&root).is_none());
+        fs::write(root.join(".envrc"), "source env/bin/activate\n").unwrap();
+        let (text, action) = issue(&root).unwrap();
+        assert_eq!(text, "no env virtualenv");
+        assert!(matches!(
+            action,
+            ActionKind::PipVenvSync { ref python, recreate: false, venv: Some(ref venv), .. }
+                if python == "python3.12" && venv == "env"
+        ));
+        fs::remove_file(root.join(".envrc")).unwrap();
+        fs::create_dir_all(root.join(".vscode")).unwrap();
+        fs::write(
+            root.join(".vscode/settings.json"),
+            r#"{"python.defaultInterpreterPath": "${workspaceFolder}/.venv/bin/python"}"#,
+        )
+        .unwrap();
+        let (text, action) = issue(&root).unwrap();
+        assert_eq!(text, "no .venv virtualenv");
+        assert!(matches!(action, ActionKind::PipVenvSync { venv: None, .. }));
+        fs::remove_dir_all(root.join(".vscode")).unwrap();
+
+        let site = root.join(".venv/lib/python3.11/site-packages");
+        fs::create_dir_all(&site).unwrap();
+        fs::write(
+            root.join(".venv/pyvenv.cfg"),
+            "home = /usr/bin\nversion = 3.11.9\n",
+        )
+        .unwrap();
+        let (text, action) = issue(&root).unwrap();
+        assert_eq!(
+            text,
+            ".venv uses Python 3.11.9 but .python-version pins 3.12"
+        );
+        assert!(matches!(
+            action,
+            ActionKind::PipVenvSync { recreate: true, .. }
+        ));
+
+        fs::write(
+            root.join(".venv/pyvenv.cfg"),
+            "version_info = 3.12.4.final.0\n",
+        )
+        .unwrap();
+        fs::create_dir_all(site.join("requests-2.31.0.dist-info")).unwrap();
+        let (text, _) = issue(&root).unwrap();
+        assert_eq!(text, "2 package(s) in .venv differ from requirements.txt");
+
+        fs::remove_dir_all(site.join("requests-2.31.0.dist-info")).unwrap();
+        fs::create_dir_all(site.join("requests-2.32.0.dist-info")).unwrap();
+        fs::create_dir_all(site.join("rich-13.7.1.dist-info")).unwrap();
+        assert!(issue(&root).is_none());
+
+        // uv.lock lists every platform, so only installed packages are compared.
+        fs::write(
+            root.join("uv.lock"),
+            "version = 1\n[[package]]\nname = \"demo\"\nversion = \"0.1.0\"\nsource = { editable = \".\" }\n\
+             [[package]]\nname = \"rich\"\nversion = \"13.8.0\"\n\
+             [[package]]\nname = \"pywin32\"\nversion = \"306\"\n",
+        )
+        .unwrap();
+        let (text, action) = issue(&root).unwrap();
+        assert_eq!(text, "1 package(s) in .venv differ from uv.lock");
+        assert!(matches!(action, ActionKind::UvSync { .. }));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn lockfile_actions_use_the_repos_package_manager() {
+        let root = std::env::temp_dir().join("agentpulse_node_pm_test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("package.json"), r#"{"name":"demo"}"#).unwrap();
+        assert_eq!(detect_node_package_manager(&root), NodePackageManager::Npm);
+
+        fs::write(root.join("yarn.lock"), "# yarn lockfile v1\n").unwrap();
+        assert_eq!(
+            detect_node_package_manager(&root),
+            NodePackageManager::YarnClassic
+        );
+        fs::write(root.join("yarn.lock"), "__metadata:\n  version: 6\n").unwrap();
+        assert_eq!(detect_node_package_manager(&root), NodePackageManager::Yarn);
+
+        // `packageManager` wins over whatever lockfile is lying around.
+        fs::write(
+            root.join("package.json"),
+            r#"{"name":"demo","packageManager":"pnpm@9.1.0"}"#,
+        )
+        .unwrap();
+        fs::remove_file(root.join("yarn.lock")).unwrap();
+        let health = collect_dependency_health(&[Repo::new(root.clone())]).unwrap();
+        let action = health[0].action.as_ref().unwrap();
+        assert!(matches!(
+            action.action,
+            ActionKind::NpmInstallLockfile {
+                manager: NodePackageManager::Pnpm,
+                ..
+            }
+        ));
+        assert!(action.command.ends_with("pnpm install --lockfile-only"));
+
+        fs::write(root.join("pnpm-lock.yaml"), "lockfileVersion: '9.0'\n").unwrap();
+        fs::write(root.join("package-lock.json"), "{}").unwrap();
+        let health = collect_dependency_health(&[Repo::new(root.clone())]).unwrap();
+        assert!(health[0]
+            .issues
+            .contains(&"conflicting lockfiles: package-lock.json, pnpm-lock.yaml".to_string()));
+        let _ = fs::remove_dir_all(&root);
+    }
 }
@@ -0,0 +1,360 @@
+//! Tickets linked from branch names (`[tickets]`).
+//!
+//! The first match of `pattern` in a repo's branch is its ticket key. With a
+//! tracker and its token, the ticket's title and status are fetched through
+//! `curl` (Jira's REST API, Linear's GraphQL API), one request per distinct
+//! key, [`MAX_PARALLEL`] at a time. Results and failures alike are reused for
+//! `refresh_secs`, so a tracker outage costs one timeout per refresh at most.
+//! Under `[providers] privacy_mode` no token is read and nothing is fetched;
+//! keys still come from branch names.
+
+use super::{curl_with_secrets, CollectorResult};
+use crate::config::TicketsConfig;
+use crate::credentials;
+use crate::dashboard::{CollectorError, RepoTicket, TicketTracker};
+use crate::git::Repo;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const TIMEOUT_SECS: u64 = 8;
+
+/// Tracker requests in flight at once.
+const MAX_PARALLEL: usize = 8;
+
+static TICKET_SETTINGS: OnceLock<Mutex<TicketsConfig>> = OnceLock::new();
+
+fn ticket_settings() -> TicketsConfig {
+    TICKET_SETTINGS
+        .get_or_init(|| Mutex::new(TicketsConfig::default()))
+        .lock()
+        .map(|cfg| cfg.clone())
+        .unwrap_or_default()
+}
+
+/// Apply `[tickets]` config to later collections; a change drops fetched
+/// tickets.
+pub fn configure_tickets(cfg: &TicketsConfig) {
+    if let Ok(mut current) = TICKET_SETTINGS
+        .get_or_init(|| Mutex::new(TicketsConfig::default()))
+        .lock()
+    {
+        if *current != *cfg {
+            *current = cfg.clone();
+            if let Ok(mut cache) = ticket_cache().lock() {
+                cache.clear();
+            }
+        }
+    }
+}
+
+/// What the tracker said about a ticket.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct TicketInfo {
+    title: Option<String>,
+    status: Option<String>,
+    url: Option<String>,
+}
+
+type TicketCache = HashMap<String, (Instant, Result<TicketInfo, String>)>;
+
+static TICKET_CACHE: OnceLock<Mutex<TicketCache>> = OnceLock::new();
+
+fn ticket_cache() -> &'static Mutex<TicketCache> {
+    TICKET_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The first ticket key in `branch`, upper-cased.
+fn ticket_key(pattern: &Regex, branch: &str) -> Option<String> {
+    pattern
+        .find(branch)
+        .map(|m| m.as_str().to_uppercase())
+        .filter(|key| !key.is_empty())
+}
+
+/// The ticket's page under `[tickets] url`.
+fn ticket_url(cfg: &TicketsConfig, key: &str) -> Option<String> {
+    let base = cfg.url.as_deref()?.trim().trim_end_matches('/');
+    if base.is_empty() {
+        return None;
+    }
+    Some(match cfg.tracker {
+        Some(TicketTracker::Linear) => format!("{}/issue/{}", base, key),
+        Some(TicketTracker::Jira) | None => format!("{}/browse/{}", base, key),
+    })
+}
+
+/// The tracker's token, or `None` when there is nothing to fetch with.
+fn token(tracker: TicketTracker) -> Option<String> {
+    let name = match tracker {
+        TicketTracker::Jira => "JIRA_API_TOKEN",
+        TicketTracker::Linear => "LINEAR_API_KEY",
+    };
+    credentials::lookup(name).map(|(value, _)| value)
+}
+
+fn fetch(
+    cfg: &TicketsConfig,
+    tracker: TicketTracker,
+    token: &str,
+    key: &str,
+) -> Result<TicketInfo, String> {
+    match tracker {
+        TicketTracker::Jira => {
+            let base = cfg
+                .url
+                .as_deref()
+                .map(|url| url.trim().trim_end_matches('/'))
+                .filter(|url| !url.is_empty())
+                .ok_or("[tickets] url is needed to look up Jira tickets")?;
+            let args = [format!(
+                "{}/rest/api/2/issue/{}?fields=summary,status",
+                base, key
+            )];
+            // Jira Cloud wants email + API token; Data Center a personal token.
+            let auth = match credentials::lookup("JIRA_EMAIL") {
+                Some((email, _)) => ("user", format!("{}:{}", email, token)),
+                None => ("header", format!("Authorization: Bearer {}", token)),
+            };
+            let mut info = parse_jira(&curl_json(&args, auth)?)?;
+            info.url = ticket_url(cfg, key);
+            Ok(info)
+        }
+        TicketTracker::Linear => {
+            let body = serde_json::json!({
+                "query": "query($id: String!) { issue(id: $id) { title url state { name } } }",
+                "variables": { "id": key },
+            });
+            let args = [
+                "https://api.linear.app/graphql".to_string(),
+                "-H".to_string(),
+                "Content-Type: application/json".to_string(),
+                "--data".to_string(),
+                body.to_string(),
+            ];
+            let auth = ("header", format!("Authorization: {}", token));
+            parse_linear(&curl_json(&args, auth)?)
+        }
+    }
+}
+
+/// `auth` is a curl config option and value, kept off the command line.
+fn curl_json(args: &[String], auth: (&str, String)) -> Result<Value, String> {
+    let mut full: Vec<String> = [
+        "--silent",
+        "--show-error",
+        "--fail-with-body",
+        "--connect-timeout",
+        "4",
+        "--max-time",
+    ]
+    .map(str::to_string)
+    .to_vec();
+    full.push(TIMEOUT_SECS.to_string());
+    full.extend_from_slice(args);
+    let output =
+        curl_with_secrets(&full, &[auth]).map_err(|e| format!("failed to run curl: {}", e))?;
+    if !output.status.success() {
+        let detail = String::from_utf8_lossy(&output.stderr);
+        let detail = detail.split_whitespace().collect::<Vec<_>>().join(" ");
+        return Err(detail.chars().take(200).collect());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("unexpected response: {}", e))
+}
+
+/// `GET /rest/api/2/issue/{key}?fields=summary,status`.
+fn parse_jira(value: &Value) -> Result<TicketInfo, String> {
+    if let Some(message) = value
+        .get("errorMessages")
+        .and_then(Value::as_array)
+        .and_then(|errors| errors.first())
+        .and_then(Value::as_str)
+    {
+        return Err(message.to_string());
+    }
+    let fields = value.get("fields").ok_or("response has no fields")?;
+    Ok(TicketInfo {
+        title: fields
+            .get("summary")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        status: fields
+            .pointer("/status/name")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        url: None,
+    })
+}
+
+/// `issue(id:) { title url state { name } }`.
+fn parse_linear(value: &Value) -> Result<TicketInfo, String> {
+    if let Some(message) = value.pointer("/errors/0/message").and_then(Value::as_str) {
+        return Err(message.to_string());
+    }
+    let issue = value
+        .pointer("/data/issue")
+        .filter(|issue| !issue.is_null())
+        .ok_or("ticket not found")?;
+    let text = |pointer: &str| {
+        issue
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    Ok(TicketInfo {
+        title: text("/title"),
+        status: text("/state/name"),
+        url: text("/url"),
+    })
+}
+
+/// Fetch the keys not fetched within `refresh`; the rest come from the cache.
+fn lookup_tickets(
+    cfg: &TicketsConfig,
+    keys: &[String],
+    fetch_due: bool,
+) -> HashMap<String, Result<TicketInfo, String>> {
+    let mut cached = ticket_cache().lock().map(|c| c.clone()).unwrap_or_default();
+    let tracker = cfg
+        .tracker
+        .filter(|_| fetch_due && !super::ai_mcp::privacy_mode());
+    if let Some((tracker, token)) = tracker.and_then(|tracker| Some((tracker, token(tracker)?))) {
+        let refresh = Duration::from_secs(cfg.refresh_secs);
+        let due: Vec<&String> = keys
+            .iter()
+            .filter(|key| {
+                cached
+                    .get(*key)
+                    .is_none_or(|(at, _)| at.elapsed() >= refresh)
+            })
+            .collect();
+        for batch in due.chunks(MAX_PARALLEL) {
+            let fetched: Vec<_> = std::thread::scope(|scope| {
+                let handles: Vec<_> = batch
+                    .iter()
+                    .map(|key| scope.spawn(|| fetch(cfg, tracker, &token, key)))
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|h| {
+                        h.join()
+                            .unwrap_or_else(|_| Err("ticket lookup panicked".to_string()))
+                    })
+                    .collect()
+            });
+            for (key, result) in batch.iter().zip(fetched) {
+                cached.insert((*key).clone(), (Instant::now(), result));
+            }
+        }
+        if let Ok(mut guard) = ticket_cache().lock() {
+            *guard = cached.clone();
+        }
+    }
+    cached
+        .into_iter()
+        .map(|(key, (_, result))| (key, result))
+        .collect()
+}
+
+/// Ticket keys in the repos' branches. With `fetch_due` false (power save)
+/// only cached tracker data is used.
+pub fn collect_tickets(repos: &[Repo], fetch_due: bool) -> CollectorResult<RepoTicket> {
+    let cfg = ticket_settings();
+    let pattern = cfg
+        .regex()
+        .map_err(|err| CollectorError::new("tickets", format!("{:#}", err)))?;
+    let Some(pattern) = pattern else {
+        return Ok(Vec::new());
+    };
+    let linked: Vec<(&Repo, String)> = repos
+        .iter()
+        .filter(|repo| !repo.status.is_detached)
+        .filter_map(|repo| Some((repo, ticket_key(&pattern, &repo.status.branch)?)))
+        .collect();
+    let mut keys: Vec<String> = linked.iter().map(|(_, key)| key.clone()).collect();
+    keys.sort();
+    keys.dedup();
+    let found = lookup_tickets(&cfg, &keys, fetch_due);
+
+    Ok(linked
+        .into_iter()
+        .map(|(repo, key)| {
+            let (info, error) = match found.get(&key) {
+                Some(Ok(info)) => (info.clone(), None),
+                Some(Err(err)) => (TicketInfo::default(), Some(err.clone())),
+                None => (TicketInfo::default(), None),
+            };
+            RepoTicket {
+                repo: repo.name.clone(),
+                repo_path: repo.path.to_string_lossy().into_owned(),
+                branch: repo.status.branch.clone(),
+                url: info.url.or_else(|| ticket_url(&cfg, &key)),
+                key,
+                title: info.title,
+                status: info.status,
+                error,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_ticket_key_in_branch_names() {
+        let cfg = TicketsConfig {
+            pattern: Some(r"(PROJ|OPS)-\d+".to_string()),
+            url: Some("https://acme.atlassian.net/".to_string()),
+            ..TicketsConfig::default()
+        };
+        let pattern = cfg.regex().unwrap().unwrap();
+        assert_eq!(
+            ticket_key(&pattern, "feat/PROJ-142-login").as_deref(),
+            Some("PROJ-142")
+        );
+        assert_eq!(
+            ticket_key(&pattern, "me/ops-7-fix-deploy").as_deref(),
+            Some("OPS-7")
+        );
+        assert_eq!(ticket_key(&pattern, "main"), None);
+        assert_eq!(
+            ticket_url(&cfg, "PROJ-142").as_deref(),
+            Some("https://acme.atlassian.net/browse/PROJ-142")
+        );
+
+        let bad = TicketsConfig {
+            pattern: Some("PROJ-(".to_string()),
+            ..TicketsConfig::default()
+        };
+        assert!(bad.regex().is_err());
+        assert!(TicketsConfig::default().regex().unwrap().is_none());
+    }
+
+    #[test]
+    fn reads_title_and_status_from_tracker_responses() {
+        let jira = serde_json::json!({
+            "key": "PROJ-142",
+            "fields": { "summary": "Login with SSO", "status": { "name": "In Progress" } }
+        });
+        let info = parse_jira(&jira).unwrap();
+        assert_eq!(info.title.as_deref(), Some("Login with SSO"));
+        assert_eq!(info.status.as_deref(), Some("In Progress"));
+        let missing = serde_json::json!({ "errorMessages": ["Issue does not exist"] });
+        assert_eq!(parse_jira(&missing).unwrap_err(), "Issue does not exist");
+
+        let linear = serde_json::json!({ "data": { "issue": {
+            "title": "Retry flaky uploads",
+            "url": "https://linear.app/acme/issue/ENG-12/retry-flaky-uploads",
+            "state": { "name": "Todo" }
+        } } });
+        let info = parse_linear(&linear).unwrap();
+        assert_eq!(info.status.as_deref(), Some("Todo"));
+        assert!(info.url.unwrap().ends_with("/ENG-12/retry-flaky-uploads"));
+        let not_found = serde_json::json!({ "data": { "issue": null } });
+        assert_eq!(parse_linear(&not_found).unwrap_err(), "ticket not found");
+    }
+}
@@ -1,32 +1,164 @@
-use anyhow::Result;
+use crate::credentials::CredentialsConfig;
+use crate::dashboard::{RemoteForge, TaskBackend, TicketTracker};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, Weekday};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct Config {
+    /// Directories scanned recursively for git repositories (`~` and `$HOME`
+    /// expand).
     #[serde(default = "default_directories")]
     pub watch_directories: Vec<PathBuf>,
 
+    /// Seconds between automatic refreshes.
     #[serde(default = "default_refresh")]
     pub refresh_interval_secs: u64,
 
+    /// Seconds between re-checks of clean repos whose git metadata is
+    /// unchanged; dirty, ahead/behind, and recently modified repos refresh
+    /// every pass.
+    #[serde(default = "default_clean_sweep")]
+    pub clean_sweep_secs: u64,
+
+    /// Directory depth to recurse when looking for `.git` folders.
     #[serde(default = "default_depth")]
     pub max_scan_depth: usize,
 
+    /// Command run by Enter on a repo. Unset: `$EDITOR`, then `code`.
     #[serde(default)]
     pub editor: Option<String>,
 
+    /// Show clean repos; false lists only dirty ones.
     #[serde(default = "default_show_clean")]
     pub show_clean: bool,
 
+    /// Repository directory names to skip entirely.
     #[serde(default)]
     pub ignored_repos: Vec<String>,
 
-    /// Use filesystem events (notify crate) instead of polling for auto-refresh.
-    /// More responsive but slightly higher resource use. Default: false.
+    /// Refresh when a repo's git metadata or working tree changes; watched
+    /// repos skip the interval poll. Repos beyond the OS watch limit keep
+    /// polling.
     #[serde(default)]
     pub watch_mode: bool,
 
+    /// Language of TUI and CLI text (`de`, `pt-BR`, ...); `auto` follows
+    /// `$LANG`. Translations are `<lang>.ftl` files in
+    /// `~/.config/agentpulse/locales/`; untranslated messages stay English.
+    #[serde(default = "default_language")]
+    pub language: String,
+
+    /// Number, cost, and time formatting, and status colors.
+    #[serde(default)]
+    pub display: DisplayConfig,
+
+    /// Power-save scanning: stretch refresh and skip AI provider refreshes.
+    #[serde(default)]
+    pub power: PowerConfig,
+
+    /// Scan-time git process priority and concurrency (user actions are
+    /// unaffected).
+    #[serde(default)]
+    pub processes: ProcessConfig,
+
+    /// Pre-push checks for pushes started from AgentPulse: conflict markers,
+    /// oversized files, likely secrets, and protected branches.
+    #[serde(default)]
+    pub push_safety: PushSafetyConfig,
+
+    /// AI usage tracking switches. `privacy_mode` stops all provider env/log
+    /// reads and network calls.
+    #[serde(default)]
+    pub providers: ProvidersConfig,
+
+    /// API keys and tokens from a secret store instead of env vars, keyed by
+    /// the env var they replace. Sources: `keychain` (service/account), `pass`
+    /// (path), `1password` (reference).
+    #[serde(default)]
+    pub credentials: CredentialsConfig,
+
+    /// Interrupt a background pane when a new critical condition appears
+    /// between scans.
+    #[serde(default)]
+    pub attention: AttentionConfig,
+
+    /// Counter limits: at or above a `*_critical` value the repo is critical
+    /// (0 = off); below a `*_warn` value a counter alone keeps the repo at low
+    /// priority.
+    #[serde(default)]
+    pub thresholds: ThresholdsConfig,
+
+    /// Recommendation rules: the suggested commit message, branches never
+    /// committed to directly, and priority overrides checked in order (first
+    /// match wins).
+    #[serde(default)]
+    pub rules: RulesConfig,
+
+    /// License audit: repos without a license (or UNLICENSED) count as
+    /// commercial; copyleft dependencies there, or any outside `allow`, raise
+    /// alerts.
+    #[serde(default)]
+    pub licenses: LicensesConfig,
+
+    /// Programs that `custom` actions may run (arguments are passed directly,
+    /// never through a shell), per-host limits for network actions, and
+    /// protected branches.
+    #[serde(default)]
+    pub actions: ActionsConfig,
+
+    /// Probe each remote host (`ssh -T` for SSH, a TCP connect for HTTPS) and
+    /// show reachability and auth in the Integrations panel. Talks to the
+    /// network.
+    #[serde(default)]
+    pub connectivity: ConnectivityConfig,
+
+    /// Working hours. Outside them attention alerts are held back, AI usage is
+    /// reused instead of refetched, and the first scan back opens one digest of
+    /// what changed.
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+
+    /// Where `t` files the selected alert or recommendation as a task, with the
+    /// repo path and suggested command as notes.
+    #[serde(default)]
+    pub tasks: TasksConfig,
+
+    /// Link branches to tickets: the key matched in each branch name is shown
+    /// per repo and `O` opens it; with a tracker token the title and status are
+    /// fetched too.
+    #[serde(default)]
+    pub tickets: TicketsConfig,
+
+    /// Open pull/merge requests and CI for each repo's branch on GitHub or
+    /// GitLab, via `gh`/`glab` or `GITHUB_TOKEN` / `GITLAB_TOKEN`. Talks to the
+    /// network.
+    #[serde(default)]
+    pub forge: ForgeConfig,
+
+    /// Repos whose work must exist somewhere besides this machine: a commit on
+    /// any local branch that no remote-tracking branch contains raises a
+    /// critical alert.
+    #[serde(default)]
+    pub backup: BackupConfig,
+
+    /// Integrity sweep: `git fsck --no-dangling` on a few repos a day,
+    /// longest-unchecked first; corruption raises a critical alert with
+    /// recovery steps.
+    #[serde(default)]
+    pub fsck: FsckConfig,
+
+    /// Paths whose changes never make a repo dirty, keyed by repo name or path:
+    /// git glob pathspecs relative to the repo root, left out of the dirty
+    /// count and of commit actions. A directory covers everything in it.
+    #[serde(default)]
+    pub dirty_ignore: DirtyIgnoreConfig,
+
     /// Directories that exist in config but were not found on disk (populated at load time, never serialised).
     #[serde(skip)]
     pub missing_directories: Vec<PathBuf>,
@@ -37,16 +169,856 @@ impl Default for Config {
         Self {
             watch_directories: default_directories(),
             refresh_interval_secs: default_refresh(),
+            clean_sweep_secs: default_clean_sweep(),
             max_scan_depth: default_depth(),
             editor: None,
             show_clean: true,
             ignored_repos: Vec::new(),
             watch_mode: false,
+            language: default_language(),
+            display: DisplayConfig::default(),
+            power: PowerConfig::default(),
+            processes: ProcessConfig::default(),
+            push_safety: PushSafetyConfig::default(),
+            providers: ProvidersConfig::default(),
+            credentials: CredentialsConfig::new(),
+            attention: AttentionConfig::default(),
+            thresholds: ThresholdsConfig::default(),
+            rules: RulesConfig::default(),
+            licenses: LicensesConfig::default(),
+            actions: ActionsConfig::default(),
+            connectivity: ConnectivityConfig::default(),
+            schedule: ScheduleConfig::default(),
+            tasks: TasksConfig::default(),
+            tickets: TicketsConfig::default(),
+            forge: ForgeConfig::default(),
+            backup: BackupConfig::default(),
+            fsck: FsckConfig::default(),
+            dirty_ignore: DirtyIgnoreConfig::new(),
             missing_directories: Vec::new(),
         }
     }
 }
 
+impl Config {
+    /// Branch patterns where commits and pushes need a second confirmation
+    /// and committing is never suggested: `[actions] protected_branches`,
+    /// plus the older `[push_safety]` (when enabled) and `[rules]
+    /// no_commit_branches` lists it replaces.
+    pub fn protected_branches(&self) -> Vec<String> {
+        let push_safety = if self.push_safety.enabled {
+            self.push_safety
+                .protected_branches
+                .clone()
+                .unwrap_or_else(default_protected_branches)
+        } else {
+            Vec::new()
+        };
+        let mut patterns: Vec<String> = Vec::new();
+        for pattern in self
+            .actions
+            .protected_branches
+            .iter()
+            .chain(&push_safety)
+            .chain(&self.rules.no_commit_branches)
+        {
+            if !patterns.contains(pattern) {
+                patterns.push(pattern.clone());
+            }
+        }
+        patterns
+    }
+}
+
+/// Whether `branch` matches one of `patterns` (`*` wildcard).
+pub fn branch_matches(patterns: &[String], branch: &str) -> bool {
+    patterns
+        .iter()
+        .any(|p| crate::collectors::compliance::wildcard_match(p, branch))
+}
+
+/// `[display]` section: how numbers, costs, and timestamps are rendered.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct DisplayConfig {
+    /// Show wall-clock times as `14:05` instead of `2:05 PM`.
+    #[serde(default)]
+    pub clock_24h: bool,
+
+    /// strftime pattern for calendar dates.
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+
+    /// Symbol prefixed to cost figures.
+    #[serde(default = "default_currency_symbol")]
+    pub currency_symbol: String,
+
+    /// Multiplier applied to USD costs before display (e.g. 0.92 for EUR).
+    #[serde(default = "default_currency_rate")]
+    pub currency_rate: f64,
+
+    /// Digit group separator.
+    #[serde(default = "default_thousands_separator")]
+    pub thousands_separator: String,
+
+    /// Decimal point.
+    #[serde(default = "default_decimal_separator")]
+    pub decimal_separator: String,
+
+    /// Status colors: `default`, `colorblind` (Okabe–Ito, safe for red-green
+    /// color blindness) or `monochrome`. `NO_COLOR` turns `default` into
+    /// monochrome.
+    #[serde(default = "default_palette")]
+    pub palette: String,
+
+    /// Show a letter beside each repo's status glyph: C clean, U uncommitted, P
+    /// unpushed, D dirty (both), N no remote.
+    #[serde(default)]
+    pub status_letters: bool,
+
+    /// How repo paths are shown: `full`, `home` (`~/work/client/api`),
+    /// `relative` to their watch directory (`client/api`) or `short`
+    /// (`~/w/c/api`). JSON keeps full paths.
+    #[serde(default = "default_path_style")]
+    pub path_style: String,
+
+    /// Draw borders, arrows and status glyphs with ASCII only. Unset: on for
+    /// `TERM=dumb`/`linux`/`vt*`, non-UTF-8 locales and the legacy Windows
+    /// console.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ascii: Option<bool>,
+}
+
+impl DisplayConfig {
+    /// Calendar date in `date_format`, or `%Y-%m-%d` when that is not valid
+    /// strftime. The UI goes through `format::date`; this is for collectors.
+    pub fn date<Tz: chrono::TimeZone>(&self, t: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        use std::fmt::Write as _;
+        // chrono reports invalid specifiers as a fmt::Error; `to_string()` would panic.
+        let mut out = String::new();
+        if write!(out, "{}", t.format(&self.date_format)).is_err() {
+            out.clear();
+            let _ = write!(out, "{}", t.format("%Y-%m-%d"));
+        }
+        out
+    }
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            clock_24h: false,
+            date_format: default_date_format(),
+            currency_symbol: default_currency_symbol(),
+            currency_rate: default_currency_rate(),
+            thousands_separator: default_thousands_separator(),
+            decimal_separator: default_decimal_separator(),
+            palette: default_palette(),
+            status_letters: false,
+            path_style: default_path_style(),
+            ascii: None,
+        }
+    }
+}
+
+/// `[power]` section: stretch refresh and skip network collectors to save power.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct PowerConfig {
+    /// Enter power-save scanning while running on battery.
+    #[serde(default = "default_battery_saver")]
+    pub battery_saver: bool,
+
+    /// Multiplier applied to `refresh_interval_secs` while power-saving.
+    #[serde(default = "default_refresh_multiplier")]
+    pub refresh_multiplier: u64,
+
+    /// Also power-save after this many seconds without input (0 = off).
+    #[serde(default)]
+    pub idle_after_secs: u64,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        Self {
+            battery_saver: default_battery_saver(),
+            refresh_multiplier: default_refresh_multiplier(),
+            idle_after_secs: 0,
+        }
+    }
+}
+
+/// `[processes]` section: keep background git work from competing with builds/IDEs.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct ProcessConfig {
+    /// Niceness for scan-time git (Unix `nice`, below-normal class on Windows);
+    /// 0 = normal.
+    #[serde(default)]
+    pub git_nice: i32,
+
+    /// Run scan-time git in the idle I/O class (`ionice -c3`, Linux only).
+    #[serde(default)]
+    pub git_idle_io: bool,
+
+    /// Maximum git child processes running at once during a scan.
+    #[serde(default = "default_max_git_processes")]
+    pub max_git_processes: usize,
+}
+
+impl Default for ProcessConfig {
+    fn default() -> Self {
+        Self {
+            git_nice: 0,
+            git_idle_io: false,
+            max_git_processes: default_max_git_processes(),
+        }
+    }
+}
+
+/// `[push_safety]` section: block pushes whose outgoing diff looks wrong.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct PushSafetyConfig {
+    /// Run the checks before every push action.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Outgoing files larger than this (KiB) fail the check.
+    #[serde(default = "default_max_file_kb")]
+    pub max_file_kb: u64,
+
+    /// Deprecated: merged into `[actions] protected_branches` while the
+    /// checks are enabled; `main` and `master` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected_branches: Option<Vec<String>>,
+}
+
+impl Default for PushSafetyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_file_kb: default_max_file_kb(),
+            protected_branches: None,
+        }
+    }
+}
+
+/// `[providers]` section: opt individual AI providers out of usage tracking.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ProvidersConfig {
+    /// Never read provider API keys or logs, or call usage APIs; forge status
+    /// and ticket details are not fetched either.
+    #[serde(default)]
+    pub privacy_mode: bool,
+
+    /// Track Claude / Anthropic usage.
+    #[serde(default = "default_provider_enabled")]
+    pub claude: bool,
+
+    /// Track Gemini usage.
+    #[serde(default = "default_provider_enabled")]
+    pub gemini: bool,
+
+    /// Track OpenAI usage.
+    #[serde(default = "default_provider_enabled")]
+    pub openai: bool,
+
+    /// Monthly budget in USD by provider; alerts when the month-end forecast
+    /// exceeds it.
+    #[serde(default)]
+    pub budgets: BTreeMap<String, f64>,
+
+    /// Anthropic workspaces to count usage and cost for; empty counts the whole
+    /// org.
+    #[serde(default)]
+    pub claude_workspace_ids: Vec<String>,
+
+    /// Anthropic API key IDs to count usage for; cost is not split by key.
+    #[serde(default)]
+    pub claude_api_key_ids: Vec<String>,
+
+    /// OpenAI projects to count usage and cost for; empty counts the whole org.
+    #[serde(default)]
+    pub openai_project_ids: Vec<String>,
+
+    /// OpenAI API key IDs to count usage for; cost is not split by key.
+    #[serde(default)]
+    pub openai_api_key_ids: Vec<String>,
+}
+
+impl Default for ProvidersConfig {
+    fn default() -> Self {
+        Self {
+            privacy_mode: false,
+            claude: true,
+            gemini: true,
+            openai: true,
+            budgets: BTreeMap::new(),
+            claude_workspace_ids: Vec::new(),
+            claude_api_key_ids: Vec::new(),
+            openai_project_ids: Vec::new(),
+            openai_api_key_ids: Vec::new(),
+        }
+    }
+}
+
+impl ProvidersConfig {
+    /// Whether `provider` (`claude`, `gemini`, `openai`) may be collected.
+    pub fn allows(&self, provider: &str) -> bool {
+        !self.privacy_mode
+            && match provider {
+                "claude" => self.claude,
+                "gemini" => self.gemini,
+                "openai" => self.openai,
+                _ => true,
+            }
+    }
+}
+
+/// `[attention]` section: interrupt a background pane when a new critical
+/// condition (e.g. detached HEAD) appears between scans. Both are opt-in.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct AttentionConfig {
+    /// Ring the terminal bell.
+    #[serde(default)]
+    pub bell: bool,
+
+    /// Flash the summary bar red for a few seconds.
+    #[serde(default)]
+    pub flash: bool,
+}
+
+/// `[rules]` section: adjustments to the built-in recommendations.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct RulesConfig {
+    /// Message for suggested commits; `{branch}`, `{repo}` and `{files}` are
+    /// filled in.
+    #[serde(default = "default_commit_message")]
+    pub commit_message: String,
+
+    /// Deprecated: merged into `[actions] protected_branches`, where
+    /// committing is never suggested either.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub no_commit_branches: Vec<String>,
+
+    /// Overrides checked in order, the first match winning: `{ counter,
+    /// at_least, priority, branches? }` with counters `behind`, `dirty`,
+    /// `unpushed` and `stash`.
+    #[serde(default)]
+    pub priority: Vec<PriorityRule>,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self {
+            commit_message: default_commit_message(),
+            no_commit_branches: Vec::new(),
+            priority: Vec::new(),
+        }
+    }
+}
+
+/// `{ counter = "behind", at_least = 10, priority = "critical" }`, optionally
+/// limited to some `branches`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct PriorityRule {
+    pub counter: RuleCounter,
+    pub at_least: usize,
+    pub priority: RulePriority,
+    /// Branch patterns the rule applies to (`*` wildcard, e.g. `release/*`);
+    /// empty applies it on every branch.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<String>,
+}
+
+/// Repo counter a priority rule looks at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleCounter {
+    Behind,
+    Dirty,
+    Unpushed,
+    Stash,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RulePriority {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+/// `[thresholds]` section: per-counter limits. A counter at or above its
+/// `*_critical` makes the repo critical; one below its `*_warn` does not
+/// raise the repo above low priority on its own. 0 turns a critical limit off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct ThresholdsConfig {
+    /// Commits behind the upstream before pulling is more than low priority.
+    #[serde(default = "default_warn_threshold")]
+    pub behind_warn: usize,
+
+    /// Commits behind that make the repo critical.
+    #[serde(default)]
+    pub behind_critical: usize,
+
+    /// Uncommitted files before committing is more than low priority.
+    #[serde(default = "default_warn_threshold")]
+    pub dirty_file_warn: usize,
+
+    /// Uncommitted files that make the repo critical.
+    #[serde(default)]
+    pub dirty_file_critical: usize,
+
+    /// Unpushed commits before pushing is more than low priority.
+    #[serde(default = "default_warn_threshold")]
+    pub unpushed_warn: usize,
+
+    /// Unpushed commits that make the repo critical.
+    #[serde(default)]
+    pub unpushed_critical: usize,
+
+    /// Stash entries before the repo is flagged for a stash review.
+    #[serde(default = "default_warn_threshold")]
+    pub stash_warn: usize,
+
+    /// Hours work may stay uncommitted or unpushed (per the scan history)
+    /// before the repo is high priority; 0 = off.
+    #[serde(default = "default_work_age_high")]
+    pub work_age_high_hours: u64,
+
+    /// Hours before such work makes the repo critical; 0 = off.
+    #[serde(default = "default_work_age_critical")]
+    pub work_age_critical_hours: u64,
+
+    /// Count untracked files as local work. Repos with only untracked files get
+    /// a low-priority review; off, they count as clean.
+    #[serde(default = "default_untracked_actionable")]
+    pub untracked_actionable: bool,
+}
+
+impl Default for ThresholdsConfig {
+    fn default() -> Self {
+        Self {
+            behind_warn: default_warn_threshold(),
+            behind_critical: 0,
+            dirty_file_warn: default_warn_threshold(),
+            dirty_file_critical: 0,
+            unpushed_warn: default_warn_threshold(),
+            unpushed_critical: 0,
+            stash_warn: default_warn_threshold(),
+            work_age_high_hours: default_work_age_high(),
+            work_age_critical_hours: default_work_age_critical(),
+            untracked_actionable: default_untracked_actionable(),
+        }
+    }
+}
+
+fn default_commit_message() -> String {
+    "wip".to_string()
+}
+
+fn default_warn_threshold() -> usize {
+    1
+}
+
+fn default_work_age_high() -> u64 {
+    24
+}
+
+fn default_work_age_critical() -> u64 {
+    72
+}
+
+fn default_untracked_actionable() -> bool {
+    true
+}
+
+/// `[licenses]` section: dependency license scanning for the Licenses view.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct LicensesConfig {
+    /// Read direct dependencies' licenses (npm lockfile, local Cargo registry).
+    #[serde(default)]
+    pub scan_dependencies: bool,
+
+    /// SPDX ids dependencies may use; empty only flags copyleft in commercial repos.
+    #[serde(default)]
+    pub allow: Vec<String>,
+
+    /// Repo names treated as commercial even if they declare a license.
+    #[serde(default)]
+    pub commercial_repos: Vec<String>,
+}
+
+/// `[actions]` section: the allowlist for `custom` actions, which run a
+/// program directly (never through a shell). Empty disables them. Also caps
+/// fetches, pulls and pushes against one remote host.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ActionsConfig {
+    /// Program names (looked up in PATH) or absolute paths; empty disables
+    /// custom actions.
+    #[serde(default)]
+    pub allowed_programs: Vec<String>,
+
+    /// Fetches, pulls and pushes running against one remote host at once; 0 =
+    /// no limit.
+    #[serde(default = "default_max_per_host")]
+    pub max_per_host: usize,
+
+    /// Milliseconds between the starts of network actions on one host.
+    #[serde(default = "default_host_spacing")]
+    pub host_spacing_ms: u64,
+
+    /// Re-check a repo's status before medium/high risk actions; refuse if it
+    /// changed since the scan.
+    #[serde(default)]
+    pub verify_state: bool,
+
+    /// Branch patterns (`*` wildcard) that need two confirmations to commit or
+    /// push to; committing there is never suggested.
+    #[serde(default)]
+    pub protected_branches: Vec<String>,
+}
+
+impl Default for ActionsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_programs: Vec::new(),
+            max_per_host: default_max_per_host(),
+            host_spacing_ms: default_host_spacing(),
+            verify_state: false,
+            protected_branches: Vec::new(),
+        }
+    }
+}
+
+fn default_max_per_host() -> usize {
+    4
+}
+
+fn default_host_spacing() -> u64 {
+    250
+}
+
+/// `[connectivity]` section: probe each remote host (`ssh -T` for SSH, a TCP
+/// connect for HTTPS) so network trouble is not mistaken for stale sync data.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ConnectivityConfig {
+    /// Run the probes; off by default because they talk to the network.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Hard limit for one host's probe.
+    #[serde(default = "default_probe_timeout")]
+    pub timeout_secs: u64,
+
+    /// Seconds a probe result is reused before the host is probed again.
+    #[serde(default = "default_probe_refresh")]
+    pub refresh_secs: u64,
+
+    /// VPN-only hosts (exact or `*.suffix`), probed even when disabled; while
+    /// one is down its repos get one "Connect VPN" alert instead of pull/push
+    /// suggestions.
+    #[serde(default)]
+    pub vpn_hosts: Vec<String>,
+}
+
+impl Default for ConnectivityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout_secs: default_probe_timeout(),
+            refresh_secs: default_probe_refresh(),
+            vpn_hosts: Vec::new(),
+        }
+    }
+}
+
+/// `[schedule]` section: working hours. Outside them attention alerts are
+/// held back, AI usage is reused instead of refetched, and the first scan
+/// back shows one digest of what changed.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ScheduleConfig {
+    /// Local `HH:MM-HH:MM`, e.g. `09:00-18:00` (may wrap past midnight); empty
+    /// means always working.
+    #[serde(default)]
+    pub hours: String,
+
+    /// Working days (`mon` … `sun`).
+    #[serde(default = "default_work_days")]
+    pub days: Vec<String>,
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            hours: String::new(),
+            days: default_work_days(),
+        }
+    }
+}
+
+impl ScheduleConfig {
+    /// Start and end of the working day; `None` when `hours` is unset.
+    pub fn hours(&self) -> Result<Option<(NaiveTime, NaiveTime)>> {
+        let hours = self.hours.trim();
+        if hours.is_empty() {
+            return Ok(None);
+        }
+        let parse = |raw: &str| {
+            NaiveTime::parse_from_str(raw.trim(), "%H:%M")
+                .with_context(|| format!("[schedule] hours: {:?} is not HH:MM", raw.trim()))
+        };
+        let (start, end) = hours
+            .split_once('-')
+            .with_context(|| format!("[schedule] hours: expected HH:MM-HH:MM, got {:?}", hours))?;
+        Ok(Some((parse(start)?, parse(end)?)))
+    }
+
+    pub fn weekdays(&self) -> Result<Vec<Weekday>> {
+        self.days
+            .iter()
+            .map(|day| {
+                day.trim()
+                    .parse::<Weekday>()
+                    .map_err(|_| anyhow::anyhow!("[schedule] days: unknown day {:?}", day))
+            })
+            .collect()
+    }
+
+    /// Whether `at` is inside working hours. Hours past midnight belong to
+    /// the day they started on. Always true when unset or invalid.
+    pub fn is_working(&self, at: &DateTime<Local>) -> bool {
+        let (Ok(Some((start, end))), Ok(days)) = (self.hours(), self.weekdays()) else {
+            return true;
+        };
+        let time = at.time();
+        let today = at.weekday();
+        if start <= end {
+            days.contains(&today) && start <= time && time < end
+        } else {
+            (days.contains(&today) && time >= start) || (days.contains(&today.pred()) && time < end)
+        }
+    }
+}
+
+/// `[tasks]` section: the task manager `t` files alerts and recommendations
+/// into, with the repo path and suggested command as notes.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct TasksConfig {
+    /// `markdown` (a checklist file), `org`, `taskwarrior` (`task add`) or
+    /// `things` (macOS).
+    #[serde(default)]
+    pub backend: TaskBackend,
+
+    /// File for `markdown` and `org`; `~/TODO.md` or `~/todo.org` when unset.
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+}
+
+impl TasksConfig {
+    /// The file tasks are appended to, for the file-based backends.
+    pub fn target_file(&self) -> Option<PathBuf> {
+        let default = match self.backend {
+            TaskBackend::Markdown => "TODO.md",
+            TaskBackend::Org => "todo.org",
+            TaskBackend::Taskwarrior | TaskBackend::Things => return None,
+        };
+        self.file
+            .clone()
+            .or_else(|| dirs::home_dir().map(|home| home.join(default)))
+    }
+}
+
+/// `[backup]` section: repos that must never hold the only copy of a
+/// commit. Any commit on a local branch that no remote-tracking ref
+/// contains raises a critical alert.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct BackupConfig {
+    /// Repo names or paths (`~` expanded) whose unpushed commits raise a
+    /// critical alert.
+    #[serde(default)]
+    pub critical_repos: Vec<String>,
+
+    /// Directory (e.g. on an external drive) where `b` and `agentpulse bundle`
+    /// write verified `git bundle` backups.
+    #[serde(default)]
+    pub bundle_dir: Option<PathBuf>,
+}
+
+impl BackupConfig {
+    pub fn is_critical(&self, name: &str, path: &Path) -> bool {
+        self.critical_repos
+            .iter()
+            .any(|entry| entry == name || Path::new(entry) == path)
+    }
+
+    /// `<bundle_dir>/<repo>-<path hash>-<date>.bundle`; a second bundle the
+    /// same day replaces the first. The hash of the repo's path keeps clones
+    /// with the same name (`~/work/api`, `~/oss/api`) apart.
+    pub fn bundle_path(&self, repo_path: &Path, today: NaiveDate) -> Option<PathBuf> {
+        let dir = self.bundle_dir.as_ref()?;
+        let name = repo_path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "repo".to_string());
+        let hash = crate::git::fnv1a(&[repo_path.to_string_lossy().as_bytes()]);
+        Some(dir.join(format!(
+            "{}-{:08x}-{}.bundle",
+            name,
+            hash as u32,
+            today.format("%Y-%m-%d")
+        )))
+    }
+}
+
+/// `[dirty_ignore]` section: repo name or path (`~` expanded) to git glob
+/// pathspecs, e.g. `research = ["notebooks/*.ipynb", "docs/_build"]`.
+/// Matching changes are left out of the dirty count and of commit actions.
+/// A path entry wins over a name entry for the same repo.
+pub type DirtyIgnoreConfig = BTreeMap<String, Vec<String>>;
+
+/// `[tickets]` section: link branches to the tickets they implement. A key
+/// matching `pattern` in the branch name is shown per repo; with a tracker
+/// and its token the ticket's title and status are fetched too.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct TicketsConfig {
+    /// Ticket key regex, e.g. `PROJ-\d+`, matched case-insensitively against
+    /// branch names; keys are shown upper-case. Unset turns ticket linking off.
+    #[serde(default)]
+    pub pattern: Option<String>,
+
+    /// `jira` (`JIRA_API_TOKEN`, plus `JIRA_EMAIL` on Cloud) or `linear`
+    /// (`LINEAR_API_KEY`); unset shows keys without fetching anything.
+    #[serde(default)]
+    pub tracker: Option<TicketTracker>,
+
+    /// Jira site (`https://acme.atlassian.net`) or Linear workspace
+    /// (`https://linear.app/acme`): where keys are looked up and opened.
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Seconds a fetched ticket title/status is reused before fetching again.
+    #[serde(default = "default_ticket_refresh")]
+    pub refresh_secs: u64,
+}
+
+impl Default for TicketsConfig {
+    fn default() -> Self {
+        Self {
+            pattern: None,
+            tracker: None,
+            url: None,
+            refresh_secs: default_ticket_refresh(),
+        }
+    }
+}
+
+impl TicketsConfig {
+    /// The compiled `pattern`; `None` when ticket linking is off.
+    pub fn regex(&self) -> Result<Option<regex::Regex>> {
+        let Some(pattern) = self.pattern.as_deref().filter(|p| !p.trim().is_empty()) else {
+            return Ok(None);
+        };
+        regex::RegexBuilder::new(pattern.trim())
+            .case_insensitive(true)
+            .build()
+            .map(Some)
+            .with_context(|| format!("[tickets] pattern: {:?} is not a valid regex", pattern))
+    }
+}
+
+/// `[fsck]` section: an integrity sweep that runs `git fsck --no-dangling`
+/// on `repos_per_day` repos a day, longest-unchecked first, so object
+/// corruption shows up as an alert instead of at the next checkout.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct FsckConfig {
+    /// Run the daily integrity sweep (off by default).
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Repos checked per 24 hours, one per scan; the rest wait their turn.
+    #[serde(default = "default_fsck_per_day")]
+    pub repos_per_day: usize,
+}
+
+impl Default for FsckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            repos_per_day: default_fsck_per_day(),
+        }
+    }
+}
+
+fn default_fsck_per_day() -> usize {
+    3
+}
+
+fn default_ticket_refresh() -> u64 {
+    600
+}
+
+/// `[forge]` section: the open PR/MR and latest CI run of each repo's branch,
+/// asked through `gh`/`glab` when installed, else the REST API with
+/// `GITHUB_TOKEN` or `GITLAB_TOKEN`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ForgeConfig {
+    /// Show the Pull Requests section and alert on failing CI; off by default
+    /// since every repo costs API calls.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Seconds a repo's PR and CI status is reused before asking again.
+    #[serde(default = "default_forge_refresh")]
+    pub refresh_secs: u64,
+
+    /// Self-hosted forges by host, e.g. `"git.corp.example" = "gitlab"`;
+    /// github.com and gitlab.com are always known.
+    #[serde(default)]
+    pub hosts: BTreeMap<String, RemoteForge>,
+}
+
+impl Default for ForgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            refresh_secs: default_forge_refresh(),
+            hosts: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_forge_refresh() -> u64 {
+    300
+}
+
+fn default_work_days() -> Vec<String> {
+    ["mon", "tue", "wed", "thu", "fri"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_language() -> String {
+    "auto".to_string()
+}
+
+fn default_probe_timeout() -> u64 {
+    5
+}
+
+fn default_probe_refresh() -> u64 {
+    600
+}
+
+impl AttentionConfig {
+    pub fn enabled(&self) -> bool {
+        self.bell || self.flash
+    }
+}
+
 pub fn default_directories() -> Vec<PathBuf> {
     let home = dirs::home_dir().unwrap_or_default();
     vec![
@@ -60,6 +1032,10 @@ fn default_refresh() -> u64 {
     10
 }
 
+fn default_clean_sweep() -> u64 {
+    120
+}
+
 fn default_depth() -> usize {
     3
 }
@@ -68,6 +1044,58 @@ fn default_show_clean() -> bool {
     true
 }
 
+fn default_battery_saver() -> bool {
+    true
+}
+
+fn default_refresh_multiplier() -> u64 {
+    4
+}
+
+fn default_max_git_processes() -> usize {
+    24
+}
+
+fn default_provider_enabled() -> bool {
+    true
+}
+
+fn default_max_file_kb() -> u64 {
+    5 * 1024
+}
+
+fn default_protected_branches() -> Vec<String> {
+    vec!["main".to_string(), "master".to_string()]
+}
+
+fn default_date_format() -> String {
+    "%Y-%m-%d".to_string()
+}
+
+fn default_currency_symbol() -> String {
+    "$".to_string()
+}
+
+fn default_currency_rate() -> f64 {
+    1.0
+}
+
+fn default_thousands_separator() -> String {
+    ",".to_string()
+}
+
+fn default_decimal_separator() -> String {
+    ".".to_string()
+}
+
+fn default_palette() -> String {
+    "default".to_string()
+}
+
+fn default_path_style() -> String {
+    "home".to_string()
+}
+
 /// Default config file location: `~/.config/agentpulse/config.toml`.
 pub fn default_config_path() -> PathBuf {
     dirs::home_dir()
@@ -77,6 +1105,16 @@ pub fn default_config_path() -> PathBuf {
         .join("config.toml")
 }
 
+/// Repo template policy (`[[require]]` files every repo must have):
+/// `~/.config/agentpulse/policy.toml`.
+pub fn default_policy_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".config")
+        .join("agentpulse")
+        .join("policy.toml")
+}
+
 /// Legacy config location used by GitPulse: `~/.config/gitpulse/config.toml`.
 pub fn legacy_config_path() -> PathBuf {
     dirs::home_dir()
@@ -86,30 +1124,134 @@ pub fn legacy_config_path() -> PathBuf {
         .join("config.toml")
 }
 
-/// Load config, creating a default file on first run if none exists.
-pub fn load_config(config_path: Option<&PathBuf>) -> Result<Config> {
-    let path = if let Some(path) = config_path {
-        path.clone()
-    } else {
-        let preferred = default_config_path();
-        if preferred.exists() {
-            preferred
-        } else {
-            let legacy = legacy_config_path();
-            if legacy.exists() {
-                legacy
-            } else {
-                preferred
+/// Previous config versions kept as `config.toml.bak.1` (newest) .. `.bak.N`.
+const CONFIG_BACKUPS: usize = 3;
+
+/// A config write lock older than this is left over from a crash.
+const STALE_LOCK: Duration = Duration::from_secs(30);
+
+/// Exclusive `<config>.lock` file, removed on drop.
+struct ConfigLock {
+    path: PathBuf,
+}
+
+impl ConfigLock {
+    fn acquire(config_path: &Path) -> Result<Self> {
+        let path = sidecar_path(config_path, "lock");
+        for _ in 0..2 {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    let _ = write!(file, "{}", std::process::id());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let age = std::fs::metadata(&path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|t| t.elapsed().ok());
+                    if age.is_some_and(|a| a < STALE_LOCK) {
+                        let owner = std::fs::read_to_string(&path).unwrap_or_default();
+                        anyhow::bail!(
+                            "{} is being written by another agentpulse (pid {})",
+                            config_path.display(),
+                            owner.trim()
+                        );
+                    }
+                    let _ = std::fs::remove_file(&path);
+                }
+                Err(e) => return Err(e.into()),
             }
         }
-    };
+        anyhow::bail!("could not lock {}", config_path.display())
+    }
+}
+
+impl Drop for ConfigLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// `config.toml` -> `config.toml.<suffix>`.
+fn sidecar_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Shift `.bak.1..N-1` up by one and copy the current file to `.bak.1`.
+fn rotate_backups(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    for n in (1..CONFIG_BACKUPS).rev() {
+        let from = sidecar_path(path, &format!("bak.{}", n));
+        if from.exists() {
+            std::fs::rename(&from, sidecar_path(path, &format!("bak.{}", n + 1)))?;
+        }
+    }
+    std::fs::copy(path, sidecar_path(path, "bak.1"))?;
+    Ok(())
+}
+
+/// Replace the config file without ever leaving a partial file behind: take
+/// the write lock, back up the old version, write a temp file, then rename.
+pub fn write_config_atomic(path: &Path, content: &str) -> Result<()> {
+    update_config_atomic(path, |_| Ok(content.to_string()))
+}
+
+/// [`write_config_atomic`] with the new content derived from the current
+/// file (empty if missing) under the same lock, so no other writer can land
+/// between the read and the write.
+pub fn update_config_atomic(path: &Path, edit: impl FnOnce(&str) -> Result<String>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let _lock = ConfigLock::acquire(path)?;
+    let content = edit(&std::fs::read_to_string(path).unwrap_or_default())?;
+    rotate_backups(path)?;
+    let tmp = sidecar_path(path, &format!("tmp.{}", std::process::id()));
+    let written = std::fs::File::create(&tmp).and_then(|mut file| {
+        file.write_all(content.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|_| std::fs::rename(&tmp, path)) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Load config, creating a default file on first run if none exists.
+/// The config file in use: `config_path` if given, else the preferred
+/// location, falling back to the legacy one when only that exists.
+pub fn resolve_config_path(config_path: Option<&PathBuf>) -> PathBuf {
+    if let Some(path) = config_path {
+        return path.clone();
+    }
+    let preferred = default_config_path();
+    let legacy = legacy_config_path();
+    if !preferred.exists() && legacy.exists() {
+        legacy
+    } else {
+        preferred
+    }
+}
+
+pub fn load_config(config_path: Option<&PathBuf>) -> Result<Config> {
+    let path = resolve_config_path(config_path);
 
     if !path.exists() {
         // First run: write a default config with explanatory comments.
         // Ignore errors (e.g. read-only path, permission denied) — just use defaults.
         if let Some(parent) = path.parent() {
             if std::fs::create_dir_all(parent).is_ok() {
-                let _ = std::fs::write(&path, default_config_toml());
+                let _ = write_config_atomic(&path, default_config_toml());
             }
         }
         return Ok(Config::default());
@@ -126,6 +1268,29 @@ pub fn load_config(config_path: Option<&PathBuf>) -> Result<Config> {
         .map(|p| expand_home(p, &home))
         .collect();
 
+    config.schedule.hours()?;
+    config.schedule.weekdays()?;
+    config.tasks.file = config.tasks.file.map(|p| expand_home(p, &home));
+    config.tickets.regex()?;
+    config.backup.bundle_dir = config.backup.bundle_dir.map(|p| expand_home(p, &home));
+    config.backup.critical_repos = config
+        .backup
+        .critical_repos
+        .into_iter()
+        .map(|entry| {
+            expand_home(PathBuf::from(entry), &home)
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+    config.dirty_ignore = std::mem::take(&mut config.dirty_ignore)
+        .into_iter()
+        .map(|(repo, patterns)| {
+            let repo = expand_home(PathBuf::from(repo), &home);
+            (repo.to_string_lossy().into_owned(), patterns)
+        })
+        .collect();
+
     // Validate: record directories that don't exist (non-fatal)
     config.missing_directories = config
         .watch_directories
@@ -138,7 +1303,7 @@ pub fn load_config(config_path: Option<&PathBuf>) -> Result<Config> {
 }
 
 /// Expand `~` and `$HOME` prefixes to the actual home directory.
-fn expand_home(path: PathBuf, home: &Path) -> PathBuf {
+pub(crate) fn expand_home(path: PathBuf, home: &Path) -> PathBuf {
     let s = path.to_string_lossy();
 
     if let Some(stripped) = s.strip_prefix("~/") {
@@ -172,6 +1337,10 @@ watch_directories = [
 # How often to auto-refresh status (seconds).
 refresh_interval_secs = 10
 
+# Clean, untouched repos are re-checked at most this often (seconds); dirty,
+# ahead/behind, and recently modified repos still refresh every pass.
+# clean_sweep_secs = 120
+
 # Maximum directory depth to recurse when looking for .git folders.
 max_scan_depth = 3
 
@@ -185,8 +1354,171 @@ show_clean = true
 # Repository directory names to skip entirely.
 # ignored_repos = ["old-project", "archived-thing"]
 
-# Experimental: planned file-watcher mode (currently polling is always used).
+# Refresh as soon as a repo's git metadata or working tree changes; only that
+# repo is re-checked, and watched repos skip the interval poll. Git-ignored
+# paths are not watched. Repos beyond the OS watch limit keep polling.
 # watch_mode = false
+
+# Language of TUI and CLI text; "auto" follows $LANG. Translations are
+# `<lang>.ftl` files in ~/.config/agentpulse/locales/ (see locales/en.ftl).
+# language = "auto"
+
+# Number, cost, and time formatting.
+# [display]
+# clock_24h = false
+# date_format = "%Y-%m-%d"
+# currency_symbol = "$"
+# currency_rate = 1.0          # multiplier applied to USD costs
+# thousands_separator = ","
+# decimal_separator = "."
+# palette = "default"          # "colorblind" (Okabe–Ito) or "monochrome"; NO_COLOR implies monochrome
+# status_letters = false       # C/U/P/D/N letter beside each repo's status glyph
+# path_style = "home"          # "full", "relative" (to the watch dir) or "short" (~/w/c/api)
+# ascii = true                 # ASCII-only borders and glyphs (unset: detect from TERM/locale)
+
+# Power-save scanning: stretch refresh and skip AI provider refreshes.
+# [power]
+# battery_saver = true         # throttle while on battery
+# refresh_multiplier = 4       # refresh_interval_secs × this while throttled
+# idle_after_secs = 0          # also throttle after N seconds without input (0 = off)
+
+# Scan-time git process priority and concurrency (user actions are unaffected).
+# [processes]
+# git_nice = 10                # nice level on Unix, below-normal class on Windows (0 = off)
+# git_idle_io = true           # ionice -c3 on Linux
+# max_git_processes = 24       # concurrent git children, independent of repo concurrency
+
+# Pre-push checks for pushes started from AgentPulse: conflict markers,
+# oversized files, likely secrets, and [actions] protected_branches.
+# [push_safety]
+# enabled = true
+# max_file_kb = 5120
+
+# AI usage tracking. privacy_mode stops all provider env/log reads and
+# network calls, forge and ticket lookups included; per-provider switches turn off
+# a single provider. Budgets are monthly, in USD; an end-of-month forecast
+# above one raises an alert. In an org account the usage APIs cover the whole
+# organization; the workspace, project and API key ID lists narrow them to
+# your own spend.
+# [providers]
+# privacy_mode = false
+# claude = true
+# gemini = true
+# openai = false
+# budgets = { claude = 200.0, openai = 50.0 }
+# claude_workspace_ids = ["wrkspc_01AbCd"]
+# openai_project_ids = ["proj_abc123"]
+
+# API keys and tokens from a secret store instead of env vars, keyed by the
+# env var they replace. Sources: keychain (macOS Keychain, Secret Service,
+# Windows Credential Manager target "<service>:<account>"), pass, 1password.
+# [credentials]
+# OPENAI_ADMIN_KEY = { source = "keychain", service = "agentpulse", account = "openai" }
+# ANTHROPIC_ADMIN_API_KEY = { source = "pass", path = "api/anthropic-admin" }
+# GEMINI_API_KEY = { source = "1password", reference = "op://Private/Gemini/credential" }
+
+# Get attention when a new critical condition (detached HEAD, local changes
+# on a branch that is behind) appears between scans.
+# [attention]
+# bell = true
+# flash = true
+
+# Counter limits. At or above a *_critical value the repo is critical (0 = off);
+# below a *_warn value a counter alone keeps the repo at low priority.
+# [thresholds]
+# behind_warn = 5
+# behind_critical = 20
+# dirty_file_critical = 50
+# stash_warn = 3
+# work_age_high_hours = 24       # uncommitted/unpushed this long → high
+# work_age_critical_hours = 72   # → critical
+# untracked_actionable = false   # repos with only untracked files count as clean
+
+# Recommendation rules. The commit message fills in {branch}, {repo} and
+# {files}; on [actions] protected_branches local work is moved to a new branch
+# instead of committed. Priority rules are checked in order and the first
+# match wins.
+# [rules]
+# commit_message = "wip({branch}): {files} file(s)"
+# priority = [
+#   { counter = "behind", at_least = 10, priority = "critical" },
+#   { counter = "dirty", at_least = 1, priority = "low", branches = ["scratch"] },
+# ]
+
+# License audit. Repos declaring no license (or UNLICENSED / proprietary) count
+# as commercial; copyleft dependencies there, or any outside `allow`, raise alerts.
+# [licenses]
+# scan_dependencies = true
+# allow = ["MIT", "Apache-2.0", "BSD-3-Clause", "ISC"]
+# commercial_repos = ["billing-api"]
+
+# Programs that `custom` actions (from collectors or `agentpulse ctl run-action`)
+# may run. Arguments are passed directly, never through a shell. Fetch, pull and
+# push wait their turn per remote host so bulk runs are not throttled. With
+# verify_state, medium/high risk actions first re-check the repo and stop with
+# "state changed, rescan" if it moved since the last scan. Commits and pushes on
+# protected_branches are refused; the dashboard asks a second time instead, and
+# recommendations move local work there to a new branch rather than commit it.
+# [actions]
+# allowed_programs = ["make", "just"]
+# max_per_host = 4
+# host_spacing_ms = 250
+# verify_state = false
+# protected_branches = ["main", "master", "release/*"]
+
+# Working hours. Outside them the bell/flash stay quiet, AI usage is reused
+# instead of refetched, and the first scan back opens one digest of what changed.
+# [schedule]
+# hours = "09:00-18:00"
+# days = ["mon", "tue", "wed", "thu", "fri"]
+
+# Where `t` files the selected alert or recommendation as a task, with the
+# repo path and suggested command as notes: markdown, org, taskwarrior, things.
+# [tasks]
+# backend = "markdown"
+# file = "~/TODO.md"
+
+# Repos whose work must exist somewhere besides this machine. A commit on any
+# local branch that no remote-tracking branch contains raises a critical alert.
+# [backup]
+# critical_repos = ["billing-api", "~/notes"]
+# bundle_dir = "/Volumes/Backup/git"   # `b` / `agentpulse bundle` write verified git bundles here
+
+# Link branches to tickets: a key matching `pattern` in the branch name is shown
+# per repo and `O` opens it. With a tracker and its token (JIRA_API_TOKEN plus
+# JIRA_EMAIL, or LINEAR_API_KEY) the title and status show in the detail pane.
+# [tickets]
+# pattern = "PROJ-\\d+"
+# tracker = "jira"
+# url = "https://acme.atlassian.net"
+# refresh_secs = 600
+
+# Open pull/merge requests and CI for each repo's branch on GitHub or GitLab,
+# via `gh`/`glab` or GITHUB_TOKEN / GITLAB_TOKEN. Failing CI and PRs awaiting
+# review raise alerts. Talks to the network.
+# [forge]
+# enabled = true
+# refresh_secs = 300
+# hosts = { "git.corp.example" = "gitlab" }
+
+# Integrity sweep: `git fsck --no-dangling` on a few repos a day, one per scan,
+# longest-unchecked first. Corruption raises a critical alert with recovery steps.
+# [fsck]
+# enabled = true
+# repos_per_day = 3
+
+# Paths whose changes never make a repo dirty, per repo name or path: left out
+# of the dirty count and of commit actions (git glob pathspecs).
+# [dirty_ignore]
+# research = ["notebooks/*.ipynb", "docs/_build"]
+
+# Probe each remote host (`ssh -T` for SSH, a TCP connect for HTTPS) and show
+# reachability and auth in the Integrations panel (`I`). Talks to the network.
+# [connectivity]
+# enabled = true
+# timeout_secs = 5
+# refresh_secs = 600
+# vpn_hosts = ["git.corp.example", "*.internal.example"]   # probed even when disabled
 "#
 }
 
@@ -225,6 +1557,132 @@ mod tests {
         std::fs::remove_file(&path).unwrap();
     }
 
+    #[test]
+    fn test_schedule_working_hours() {
+        use chrono::TimeZone;
+        let at = |day: u32, hour: u32| Local.with_ymd_and_hms(2026, 3, day, hour, 30, 0).unwrap();
+        // 2026-03-06 is a Friday, the 7th a Saturday.
+        let office = ScheduleConfig {
+            hours: "09:00-18:00".to_string(),
+            ..ScheduleConfig::default()
+        };
+        assert!(office.is_working(&at(6, 9)));
+        assert!(!office.is_working(&at(6, 18)));
+        assert!(!office.is_working(&at(7, 10)));
+
+        let night = ScheduleConfig {
+            hours: "22:00-06:00".to_string(),
+            days: vec!["fri".to_string()],
+        };
+        assert!(night.is_working(&at(6, 23)));
+        assert!(night.is_working(&at(7, 5)));
+        assert!(!night.is_working(&at(7, 23)));
+
+        assert!(ScheduleConfig::default().is_working(&at(7, 3)));
+        let broken = ScheduleConfig {
+            hours: "9-5".to_string(),
+            ..ScheduleConfig::default()
+        };
+        assert!(broken.hours().is_err());
+    }
+
+    #[test]
+    fn test_load_config_display_section() {
+        let dir = std::env::temp_dir().join("agentpulse_test_config_display");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(
+            &path,
+            "[display]\nclock_24h = true\ncurrency_symbol = \"€\"\ncurrency_rate = 0.9\n",
+        )
+        .unwrap();
+        let cfg = load_config(Some(&path)).unwrap();
+        assert!(cfg.display.clock_24h);
+        assert_eq!(cfg.display.currency_symbol, "€");
+        assert_eq!(cfg.display.thousands_separator, ","); // still default
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_attention_is_opt_in() {
+        assert!(!Config::default().attention.enabled());
+        let cfg: Config = toml::from_str("[attention]\nbell = true\n").unwrap();
+        assert!(cfg.attention.bell);
+        assert!(!cfg.attention.flash);
+    }
+
+    #[test]
+    fn test_providers_section() {
+        let cfg: Config = toml::from_str("[providers]\nopenai = false\n").unwrap();
+        assert!(cfg.providers.allows("claude"));
+        assert!(!cfg.providers.allows("openai"));
+
+        let private: Config = toml::from_str("[providers]\nprivacy_mode = true\n").unwrap();
+        assert!(!private.providers.allows("claude"));
+    }
+
+    #[test]
+    fn test_atomic_write_rotates_backups() {
+        let dir = std::env::temp_dir().join("agentpulse_test_config_atomic");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("config.toml");
+        for n in 1..=5 {
+            write_config_atomic(&path, &format!("refresh_interval_secs = {}\n", n)).unwrap();
+        }
+        let read = |p: PathBuf| std::fs::read_to_string(p).unwrap();
+        assert_eq!(read(path.clone()), "refresh_interval_secs = 5\n");
+        assert_eq!(
+            read(sidecar_path(&path, "bak.1")),
+            "refresh_interval_secs = 4\n"
+        );
+        assert_eq!(
+            read(sidecar_path(&path, "bak.3")),
+            "refresh_interval_secs = 2\n"
+        );
+        assert!(!sidecar_path(&path, "bak.4").exists());
+        assert!(!sidecar_path(&path, "lock").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_atomic_update_edits_the_current_file() {
+        let dir = std::env::temp_dir().join("agentpulse_test_config_update");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("config.toml");
+        write_config_atomic(&path, "show_clean = true\n").unwrap();
+        update_config_atomic(&path, |old| Ok(format!("{}editor = \"vim\"\n", old))).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "show_clean = true\neditor = \"vim\"\n"
+        );
+
+        // A failed edit writes nothing and releases the lock.
+        assert!(update_config_atomic(&path, |_| anyhow::bail!("no")).is_err());
+        assert!(!sidecar_path(&path, "lock").exists());
+        assert!(std::fs::read_to_string(&path).unwrap().contains("vim"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_atomic_write_respects_live_lock() {
+        let dir = std::env::temp_dir().join("agentpulse_test_config_lock");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("config.toml");
+        write_config_atomic(&path, "show_clean = true\n").unwrap();
+
+        let held = ConfigLock::acquire(&path).unwrap();
+        let err = write_config_atomic(&path, "show_clean = false\n").unwrap_err();
+        assert!(err.to_string().contains("another agentpulse"));
+        drop(held);
+
+        write_config_atomic(&path, "show_clean = false\n").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "show_clean = false\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_expand_home_tilde() {
         let home = PathBuf::from("/home/user");
@@ -251,4 +1709,50 @@ mod tests {
         assert_eq!(cfg.missing_directories.len(), 1);
         std::fs::remove_file(&path).unwrap();
     }
+
+    #[test]
+    fn protected_branches_merge_the_older_lists() {
+        let cfg: Config = toml::from_str(
+            r#"
+            [actions]
+            protected_branches = ["release/*"]
+            [rules]
+            no_commit_branches = ["trunk"]
+            [push_safety]
+            enabled = false
+            protected_branches = ["prod"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.protected_branches(), vec!["release/*", "trunk"]);
+
+        let mut cfg = cfg;
+        cfg.push_safety.enabled = true;
+        assert_eq!(cfg.protected_branches(), vec!["release/*", "prod", "trunk"]);
+        cfg.push_safety.protected_branches = None;
+        assert_eq!(
+            cfg.protected_branches(),
+            vec!["release/*", "main", "master", "trunk"]
+        );
+        assert!(branch_matches(&cfg.protected_branches(), "release/2.0"));
+        assert!(!branch_matches(&cfg.protected_branches(), "feature"));
+    }
+
+    #[test]
+    fn bundle_paths_keep_same_named_repos_apart() {
+        let backup = BackupConfig {
+            bundle_dir: Some(PathBuf::from("/backups")),
+            ..BackupConfig::default()
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let work = backup.bundle_path(Path::new("/work/api"), today).unwrap();
+        let oss = backup.bundle_path(Path::new("/oss/api"), today).unwrap();
+        assert_ne!(work, oss);
+        let name = work.file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.starts_with("api-") && name.ends_with("-2026-03-01.bundle"));
+        assert_eq!(name.len(), "api-00000000-2026-03-01.bundle".len());
+        assert!(BackupConfig::default()
+            .bundle_path(Path::new("/a"), today)
+            .is_none());
+    }
 }
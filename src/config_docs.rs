@@ -0,0 +1,276 @@
+//! `agentpulse config docs`: a commented reference of every config key.
+//!
+//! Keys and descriptions come from the JSON schema of [`Config`], that is
+//! from the doc comments in `config.rs`, and defaults from serializing
+//! `Config::default()`. The tests fail when a key has no doc comment, so the
+//! reference cannot drift from the model.
+
+use crate::config::Config;
+use anyhow::Result;
+use serde_json::Value;
+use std::sync::OnceLock;
+
+/// Sections keyed by user-chosen names (env vars, repo names) rather than
+/// by fields; the reference shows an example entry for them.
+const MAP_SECTIONS: &[&str] = &["credentials", "dirty_ignore"];
+
+/// Width of the comment lines in the reference.
+const WRAP: usize = 80;
+
+/// Shown for keys that have no default value.
+const EXAMPLES: &[(&str, &str, &str)] = &[
+    ("", "editor", "\"cursor\""),
+    ("display", "ascii", "true"),
+    (
+        "push_safety",
+        "protected_branches",
+        "[\"main\", \"master\"]",
+    ),
+    ("rules", "no_commit_branches", "[\"main\"]"),
+    ("tasks", "file", "\"~/notes/TODO.md\""),
+    ("backup", "bundle_dir", "\"/Volumes/Backup/git\""),
+    ("tickets", "pattern", "\"PROJ-\\\\d+\""),
+    ("tickets", "tracker", "\"jira\""),
+    ("tickets", "url", "\"https://acme.atlassian.net\""),
+    (
+        "credentials",
+        "OPENAI_ADMIN_KEY",
+        "{ source = \"keychain\", service = \"agentpulse\", account = \"openai\" }",
+    ),
+    (
+        "dirty_ignore",
+        "research",
+        "[\"notebooks/*.ipynb\", \"docs/_build\"]",
+    ),
+];
+
+/// A section of the reference: `name` is empty for the top-level keys.
+struct SectionDoc {
+    name: String,
+    doc: String,
+    /// `(key, description)` in field order.
+    keys: Vec<(String, String)>,
+}
+
+/// Every section in field order, top-level keys first, read once from the
+/// schema.
+fn sections() -> &'static [SectionDoc] {
+    static SECTIONS: OnceLock<Vec<SectionDoc>> = OnceLock::new();
+    SECTIONS.get_or_init(|| {
+        let schema = serde_json::to_value(schemars::schema_for!(Config)).unwrap_or_default();
+        let mut top = SectionDoc {
+            name: String::new(),
+            doc: String::new(),
+            keys: Vec::new(),
+        };
+        let mut sections = Vec::new();
+        for (name, field) in properties(&schema) {
+            let definition = field
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| schema.pointer(reference.trim_start_matches('#')));
+            let keys = match definition {
+                Some(definition) => properties(definition)
+                    .map(|(key, field)| (key.clone(), description(field)))
+                    .collect(),
+                None if MAP_SECTIONS.contains(&name.as_str()) => Vec::new(),
+                None => {
+                    top.keys.push((name.clone(), description(field)));
+                    continue;
+                }
+            };
+            sections.push(SectionDoc {
+                name: name.clone(),
+                doc: description(field),
+                keys,
+            });
+        }
+        sections.insert(0, top);
+        sections
+    })
+}
+
+fn properties(schema: &Value) -> impl Iterator<Item = (&String, &Value)> {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+}
+
+/// A field's doc comment on one line; empty without one.
+fn description(field: &Value) -> String {
+    field
+        .get("description")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Every key with its description and default, all commented out.
+pub fn reference_toml() -> Result<String> {
+    let defaults = toml::Value::try_from(Config::default())?;
+    let home = dirs::home_dir().map(|h| h.to_string_lossy().to_string());
+
+    let mut out = String::from(
+        "# AgentPulse reference configuration (`agentpulse config docs`).\n\
+         # Every supported key with its default; uncomment a line to change it.\n",
+    );
+    for section in sections() {
+        let name = section.name.as_str();
+        if !name.is_empty() {
+            out.push_str("\n\n");
+            push_comment(&mut out, &section.doc);
+            out.push_str(&format!("# [{}]\n", name));
+        }
+        for (key, doc) in &section.keys {
+            let value = lookup(&defaults, name, key)
+                .map(|v| display_value(v, home.as_deref()))
+                .or_else(|| example(name, key).map(str::to_string))
+                .unwrap_or_default();
+            out.push('\n');
+            push_comment(&mut out, doc);
+            out.push_str(&format!("# {} = {}\n", key, value));
+        }
+        for (_, key, value) in EXAMPLES.iter().filter(|(s, _, _)| *s == name) {
+            if !section.keys.iter().any(|(k, _)| k == key) {
+                out.push_str(&format!("\n# {} = {}\n", key, value));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// `text` as `# ` comment lines wrapped at [`WRAP`] columns.
+fn push_comment(out: &mut String, text: &str) {
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > WRAP - 2 {
+            out.push_str(&format!("# {}\n", line));
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        out.push_str(&format!("# {}\n", line));
+    }
+}
+
+/// Description of `key` in `section` (`""` for top-level keys).
+pub fn key_doc(section: &str, key: &str) -> Option<&'static str> {
+    sections()
+        .iter()
+        .find(|s| s.name == section)?
+        .keys
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, doc)| doc.as_str())
+}
+
+pub fn lookup<'a>(defaults: &'a toml::Value, section: &str, key: &str) -> Option<&'a toml::Value> {
+    if section.is_empty() {
+        defaults.get(key)
+    } else {
+        defaults.get(section)?.get(key)
+    }
+}
+
+fn example(section: &str, key: &str) -> Option<&'static str> {
+    EXAMPLES
+        .iter()
+        .find(|(s, k, _)| *s == section && *k == key)
+        .map(|(_, _, v)| *v)
+}
+
+/// TOML literal for a default, with the home directory shown as `~`.
+fn display_value(value: &toml::Value, home: Option<&str>) -> String {
+    let text = value.to_string();
+    match home {
+        Some(home) if !home.is_empty() => text.replace(home, "~"),
+        _ => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_config_key_is_documented() {
+        let defaults = toml::Value::try_from(Config::default()).unwrap();
+        // Sections come from the schema, which also lists `Option` fields
+        // that serialize to nothing.
+        for key in defaults.as_table().unwrap().keys() {
+            assert!(
+                sections().iter().any(|s| &s.name == key)
+                    || sections()[0].keys.iter().any(|(k, _)| k == key),
+                "{} is missing from the schema",
+                key
+            );
+        }
+        for section in sections() {
+            assert!(
+                section.name.is_empty() || !section.doc.is_empty(),
+                "[{}] has no doc comment",
+                section.name
+            );
+            if MAP_SECTIONS.contains(&section.name.as_str()) {
+                assert!(
+                    EXAMPLES.iter().any(|(s, _, _)| *s == section.name),
+                    "[{}] has no example entry",
+                    section.name
+                );
+            }
+            for (key, doc) in &section.keys {
+                assert!(
+                    !doc.is_empty(),
+                    "{}.{} has no doc comment",
+                    section.name,
+                    key
+                );
+                assert!(
+                    lookup(&defaults, &section.name, key).is_some()
+                        || example(&section.name, key).is_some(),
+                    "{}.{} has neither a default nor an example",
+                    section.name,
+                    key
+                );
+            }
+        }
+        assert!(key_doc("tickets", "tracker").is_some_and(|doc| doc.contains("linear")));
+    }
+
+    #[test]
+    fn uncommented_reference_is_a_valid_config() {
+        let reference = reference_toml().unwrap();
+        assert!(reference
+            .lines()
+            .all(|line| line.is_empty() || line.starts_with('#')));
+        let keys: Vec<&str> = sections()
+            .iter()
+            .flat_map(|s| s.keys.iter().map(|(k, _)| k.as_str()))
+            .chain(EXAMPLES.iter().map(|(_, k, _)| *k))
+            .collect();
+        let active: String = reference
+            .lines()
+            .filter_map(|line| line.strip_prefix("# "))
+            .filter(|line| {
+                line.starts_with('[') || keys.iter().any(|k| line.starts_with(&format!("{} = ", k)))
+            })
+            .map(|line| format!("{}\n", line))
+            .collect();
+        let cfg: Config = toml::from_str(&active).unwrap();
+        assert_eq!(
+            cfg.refresh_interval_secs,
+            Config::default().refresh_interval_secs
+        );
+        assert_eq!(cfg.editor.as_deref(), Some("cursor"));
+        assert_eq!(cfg.credentials.len(), 1);
+        assert_eq!(cfg.tickets.pattern.as_deref(), Some("PROJ-\\d+"));
+    }
+}
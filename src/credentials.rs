@@ -0,0 +1,274 @@
+//! Credential lookup: environment variables first, then the secret store
+//! configured for that name under `[credentials]`.
+//!
+//! Secret stores are read through their CLIs (`security`, `secret-tool`,
+//! PowerShell, `pass`, `op`) so no native keychain bindings are needed.
+//! Results are cached for the session; unlocking a keychain once is enough.
+//! A CLI that has not answered within [`LOOKUP_TIMEOUT`] is given up on
+//! until the next lookup.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How long a secret store's CLI gets, e.g. to wait for an unlock prompt.
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Where a credential lives, e.g.
+/// `OPENAI_API_KEY = { source = "keychain", service = "agentpulse", account = "openai" }`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub enum CredentialSource {
+    /// macOS Keychain, Secret Service (`secret-tool`), or Windows Credential Manager.
+    Keychain { service: String, account: String },
+    /// `pass show <path>`; the first line is the secret.
+    Pass { path: String },
+    /// `op read <reference>`, e.g. `op://Private/OpenAI/credential`.
+    #[serde(rename = "1password")]
+    OnePassword { reference: String },
+}
+
+impl CredentialSource {
+    /// Short label for config-source listings.
+    pub fn label(&self) -> &'static str {
+        match self {
+            CredentialSource::Keychain { .. } => "keychain",
+            CredentialSource::Pass { .. } => "pass",
+            CredentialSource::OnePassword { .. } => "1password",
+        }
+    }
+}
+
+/// `[credentials]` section, keyed by the environment variable it stands in for.
+pub type CredentialsConfig = BTreeMap<String, CredentialSource>;
+
+#[derive(Default)]
+struct CredentialState {
+    sources: CredentialsConfig,
+    /// Store lookups already attempted this session (`None` = not found).
+    resolved: HashMap<String, Option<String>>,
+}
+
+static CREDENTIALS: OnceLock<Mutex<CredentialState>> = OnceLock::new();
+
+fn state() -> &'static Mutex<CredentialState> {
+    CREDENTIALS.get_or_init(|| Mutex::new(CredentialState::default()))
+}
+
+/// Apply `[credentials]` config; changed sources are looked up again.
+pub fn configure(sources: &CredentialsConfig) {
+    if let Ok(mut state) = state().lock() {
+        if state.sources != *sources {
+            state.sources = sources.clone();
+            state.resolved.clear();
+        }
+    }
+}
+
+/// A non-empty credential and where it came from (`env:NAME` or `keychain:NAME`).
+pub fn lookup(name: &str) -> Option<(String, String)> {
+    if let Ok(value) = std::env::var(name) {
+        let value = value.trim();
+        if !value.is_empty() {
+            return Some((value.to_string(), format!("env:{}", name)));
+        }
+    }
+    let (source, cached) = {
+        let state = state().lock().ok()?;
+        let source = state.sources.get(name)?.clone();
+        let cached = state.resolved.get(name).cloned();
+        (source, cached)
+    };
+    // The store's CLI may wait on an unlock prompt, so it runs without the
+    // lock held; other collectors keep their own lookups going meanwhile.
+    let value = match cached {
+        Some(value) => value,
+        None => {
+            let value = read_source(&source)?;
+            if let Ok(mut state) = state().lock() {
+                // Unless `configure` pointed the name elsewhere meanwhile.
+                if state.sources.get(name) == Some(&source) {
+                    state.resolved.insert(name.to_string(), value.clone());
+                }
+            }
+            value
+        }
+    }?;
+    Some((value, format!("{}:{}", source.label(), name)))
+}
+
+/// First credential found among `names`, value only.
+pub fn first_value(names: &[&str]) -> Option<String> {
+    names.iter().find_map(|n| lookup(n)).map(|(value, _)| value)
+}
+
+/// The secret, `Some(None)` if the store doesn't have it, or `None` if its
+/// CLI timed out, which is not remembered so a later lookup asks again.
+fn read_source(source: &CredentialSource) -> Option<Option<String>> {
+    let command = match source {
+        CredentialSource::Keychain { service, account } => keychain_command(service, account),
+        CredentialSource::Pass { path } => {
+            let mut cmd = Command::new("pass");
+            cmd.args(["show", path]);
+            cmd
+        }
+        CredentialSource::OnePassword { reference } => {
+            let mut cmd = Command::new("op");
+            cmd.args(["read", "--no-newline", reference]);
+            cmd
+        }
+    };
+    let output = output_within(command, LOOKUP_TIMEOUT)?;
+    Some(output.and_then(|stdout| first_secret_line(&String::from_utf8_lossy(&stdout))))
+}
+
+/// Stdout of `cmd` if it succeeds, `Some(None)` if it fails, or `None` if it
+/// is still running after `timeout`, e.g. at an unanswered unlock prompt.
+fn output_within(mut cmd: Command, timeout: Duration) -> Option<Option<Vec<u8>>> {
+    let Ok(mut child) = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return Some(None);
+    };
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(50)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            Err(_) => return Some(None),
+        }
+    };
+    let mut stdout = Vec::new();
+    if let Some(mut pipe) = child.stdout.take() {
+        let _ = pipe.read_to_end(&mut stdout);
+    }
+    Some(status.success().then_some(stdout))
+}
+
+fn first_secret_line(raw: &str) -> Option<String> {
+    raw.lines()
+        .next()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(target_os = "macos")]
+fn keychain_command(service: &str, account: &str) -> Command {
+    let mut cmd = Command::new("security");
+    cmd.args(["find-generic-password", "-s", service, "-a", account, "-w"]);
+    cmd
+}
+
+#[cfg(target_os = "windows")]
+fn keychain_command(service: &str, account: &str) -> Command {
+    // Generic credentials are stored under the target `<service>:<account>`.
+    const READER: &str = r#"Add-Type @'
+using System; using System.Runtime.InteropServices;
+public static class APCred {
+  [StructLayout(LayoutKind.Sequential, CharSet = CharSet.Unicode)]
+  struct CREDENTIAL {
+    public int Flags; public int Type; public string TargetName; public string Comment;
+    public int LastWrittenLow; public int LastWrittenHigh;
+    public int CredentialBlobSize; public IntPtr CredentialBlob; public int Persist;
+    public int AttributeCount; public IntPtr Attributes; public string TargetAlias; public string UserName;
+  }
+  [DllImport("advapi32.dll", CharSet = CharSet.Unicode, SetLastError = true)]
+  static extern bool CredReadW(string target, int type, int flags, out IntPtr cred);
+  [DllImport("advapi32.dll")] static extern void CredFree(IntPtr cred);
+  public static string Read(string target) {
+    IntPtr p;
+    if (!CredReadW(target, 1, 0, out p)) return null;
+    try {
+      var c = (CREDENTIAL)Marshal.PtrToStructure(p, typeof(CREDENTIAL));
+      return Marshal.PtrToStringUni(c.CredentialBlob, c.CredentialBlobSize / 2);
+    } finally { CredFree(p); }
+  }
+}
+'@
+"#;
+    let script = format!(
+        "{}$s = [APCred]::Read('{}:{}'); if ($s -eq $null) {{ exit 1 }}; $s",
+        READER,
+        service.replace('\'', "''"),
+        account.replace('\'', "''")
+    );
+    let mut cmd = Command::new("powershell");
+    cmd.args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+    cmd
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn keychain_command(service: &str, account: &str) -> Command {
+    let mut cmd = Command::new("secret-tool");
+    cmd.args(["lookup", "service", service, "account", account]);
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_credential_sources() {
+        let raw = r#"
+OPENAI_API_KEY = { source = "keychain", service = "agentpulse", account = "openai" }
+ANTHROPIC_API_KEY = { source = "pass", path = "api/anthropic" }
+GITHUB_TOKEN = { source = "1password", reference = "op://Private/GitHub/token" }
+"#;
+        let cfg: CredentialsConfig = toml::from_str(raw).unwrap();
+        assert_eq!(
+            cfg["OPENAI_API_KEY"],
+            CredentialSource::Keychain {
+                service: "agentpulse".to_string(),
+                account: "openai".to_string()
+            }
+        );
+        assert_eq!(cfg["ANTHROPIC_API_KEY"].label(), "pass");
+        assert_eq!(cfg["GITHUB_TOKEN"].label(), "1password");
+    }
+
+    #[test]
+    fn secret_is_first_non_empty_line() {
+        assert_eq!(
+            first_secret_line("s3cret\nurl: example.com\n"),
+            Some("s3cret".to_string())
+        );
+        assert_eq!(first_secret_line("\n"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stalled_store_clis_are_given_up_on() {
+        let mut sleep = Command::new("sleep");
+        sleep.arg("5");
+        assert_eq!(output_within(sleep, Duration::from_millis(100)), None);
+        let mut echo = Command::new("echo");
+        echo.arg("s3cret");
+        assert_eq!(
+            output_within(echo, LOOKUP_TIMEOUT),
+            Some(Some(b"s3cret\n".to_vec()))
+        );
+        assert_eq!(
+            output_within(Command::new("false"), LOOKUP_TIMEOUT),
+            Some(None)
+        );
+    }
+
+    #[test]
+    fn unconfigured_names_fall_through() {
+        assert!(lookup("AGENTPULSE_TEST_NO_SUCH_CREDENTIAL").is_none());
+    }
+}
@@ -1,8 +1,12 @@
-use crate::collectors::{collect_all, CollectorOutput};
+use crate::collectors::{
+    collect_all, collect_all_with, collect_repo_rows, CollectMode, CollectorOutput,
+};
 use crate::dashboard::models::{
-    ActionCommand, ActionKind, DashboardAlert, DashboardSnapshot, OverviewMetrics, ProviderKind,
+    vpn_offline_repos, ActionCommand, ActionKind, DashboardAlert, DashboardSnapshot,
+    OverviewMetrics, ProbeStatus, ProviderKind, SCHEMA_VERSION,
 };
 use crate::git::Repo;
+use crate::i18n::{t, tr};
 use std::collections::HashSet;
 
 pub fn collect_and_build(repos: &[Repo]) -> DashboardSnapshot {
@@ -10,15 +14,12 @@ pub fn collect_and_build(repos: &[Repo]) -> DashboardSnapshot {
     build_snapshot(repos, collected)
 }
 
+pub fn collect_and_build_with(repos: &[Repo], mode: CollectMode) -> DashboardSnapshot {
+    let collected = collect_all_with(repos, mode);
+    build_snapshot(repos, collected)
+}
+
 pub fn build_snapshot(repos: &[Repo], mut collected: CollectorOutput) -> DashboardSnapshot {
-    let total_repos = repos.len();
-    let actionable_repos = repos.iter().filter(|r| r.needs_attention()).count();
-    let dirty_repos = repos
-        .iter()
-        .filter(|r| r.status.uncommitted_count > 0)
-        .count();
-    let repos_ahead = repos.iter().filter(|r| r.status.unpushed_count > 0).count();
-    let repos_behind = repos.iter().filter(|r| r.status.behind_count > 0).count();
     let total_worktrees = collected.worktrees.len();
     let repo_processes = collected.processes.len();
 
@@ -35,13 +36,9 @@ pub fn build_snapshot(repos: &[Repo], mut collected: CollectorOutput) -> Dashboa
     let mcp_unhealthy = collected.mcp_servers.iter().filter(|m| !m.healthy).count();
 
     collected.alerts.extend(build_system_alerts(&collected));
+    hide_vpn_blocked_actions(&mut collected);
     dedupe_alerts(&mut collected.alerts);
-    collected.alerts.sort_by(|a, b| {
-        severity_rank(&b.severity)
-            .cmp(&severity_rank(&a.severity))
-            .then_with(|| b.action.is_some().cmp(&a.action.is_some()))
-            .then_with(|| a.title.cmp(&b.title))
-    });
+    sort_alerts(&mut collected.alerts);
     collected.alerts.truncate(120);
 
     let mut providers = collected.providers;
@@ -51,29 +48,69 @@ pub fn build_snapshot(repos: &[Repo], mut collected: CollectorOutput) -> Dashboa
             .then_with(|| b.estimated_cost_usd.total_cmp(&a.estimated_cost_usd))
     });
 
-    DashboardSnapshot {
+    let mut snapshot = DashboardSnapshot {
+        schema_version: SCHEMA_VERSION,
         generated_at_epoch_secs: chrono::Utc::now().timestamp(),
         overview: OverviewMetrics {
-            total_repos,
-            actionable_repos,
-            dirty_repos,
-            repos_ahead,
-            repos_behind,
             total_worktrees,
             repo_processes,
             env_issues,
             dep_issues,
             mcp_unhealthy,
+            ..OverviewMetrics::default()
         },
         alerts: collected.alerts,
         repos: collected.repos,
         worktrees: collected.worktrees,
         processes: collected.processes,
         dependencies: collected.dependencies,
+        local_deps: collected.local_deps,
         env_audit: collected.env_audit,
+        licenses: collected.licenses,
         mcp_servers: collected.mcp_servers,
+        ai_clients: collected.ai_clients,
         providers,
+        collector_errors: collected.errors,
+        host_probes: collected.host_probes,
+        scan_stats: None,
+        activity: collected.activity,
+        this_week: collected.this_week,
+        tickets: collected.tickets,
+        pull_requests: collected.pull_requests,
+    };
+    count_repos(&mut snapshot.overview, repos);
+    snapshot
+}
+
+/// Re-derive the repo rows and counts after `repos` changed without a full
+/// collection, e.g. one repo re-checked after an action. Alerts and the
+/// other collectors' output wait for the next scan.
+pub fn refresh_repo_rows(snapshot: &mut DashboardSnapshot, repos: &[Repo]) {
+    if let Ok(rows) = collect_repo_rows(repos) {
+        snapshot.repos = rows;
     }
+    count_repos(&mut snapshot.overview, repos);
+}
+
+fn count_repos(overview: &mut OverviewMetrics, repos: &[Repo]) {
+    overview.total_repos = repos.len();
+    overview.actionable_repos = repos.iter().filter(|r| r.needs_attention()).count();
+    overview.dirty_repos = repos
+        .iter()
+        .filter(|r| r.status.uncommitted_count > 0)
+        .count();
+    overview.repos_ahead = repos.iter().filter(|r| r.status.unpushed_count > 0).count();
+    overview.repos_behind = repos.iter().filter(|r| r.status.behind_count > 0).count();
+}
+
+/// Most severe first, actionable before informational, then by title.
+pub fn sort_alerts(alerts: &mut [DashboardAlert]) {
+    alerts.sort_by(|a, b| {
+        severity_rank(&b.severity)
+            .cmp(&severity_rank(&a.severity))
+            .then_with(|| b.action.is_some().cmp(&a.action.is_some()))
+            .then_with(|| a.title.cmp(&b.title))
+    });
 }
 
 fn build_system_alerts(collected: &CollectorOutput) -> Vec<DashboardAlert> {
@@ -87,13 +124,13 @@ fn build_system_alerts(collected: &CollectorOutput) -> Vec<DashboardAlert> {
     if dep_issues > 0 {
         alerts.push(DashboardAlert {
             severity: "warn".to_string(),
-            title: "Dependency hygiene issues detected".to_string(),
-            detail: format!("{} repo(s) with dependency issues", dep_issues),
+            title: t("alert-deps-title"),
+            detail: tr("alert-deps-detail", &[("count", &dep_issues)]),
             repo: None,
             action: Some(ActionCommand::new(
-                "open dependency view",
+                t("alert-deps-action"),
                 ActionKind::ShowMessage {
-                    message: "Switch to Deps section in AgentPulse".to_string(),
+                    message: t("alert-deps-message"),
                 },
             )),
         });
@@ -107,13 +144,13 @@ fn build_system_alerts(collected: &CollectorOutput) -> Vec<DashboardAlert> {
     if env_risky > 0 {
         alerts.push(DashboardAlert {
             severity: "high".to_string(),
-            title: "Tracked env files may contain secrets".to_string(),
-            detail: format!("{} repo(s) have tracked sensitive env files", env_risky),
+            title: t("alert-env-title"),
+            detail: tr("alert-env-detail", &[("count", &env_risky)]),
             repo: None,
             action: Some(ActionCommand::new(
-                "review env audit",
+                t("alert-env-action"),
                 ActionKind::ShowMessage {
-                    message: "Switch to Env Audit section in AgentPulse".to_string(),
+                    message: t("alert-env-message"),
                 },
             )),
         });
@@ -123,24 +160,92 @@ fn build_system_alerts(collected: &CollectorOutput) -> Vec<DashboardAlert> {
     if mcp_bad > 0 {
         alerts.push(DashboardAlert {
             severity: "warn".to_string(),
-            title: "MCP server health issues".to_string(),
-            detail: format!("{} MCP server(s) unhealthy", mcp_bad),
+            title: t("alert-mcp-title"),
+            detail: tr("alert-mcp-detail", &[("count", &mcp_bad)]),
             repo: None,
             action: Some(ActionCommand::new(
-                "inspect MCP",
+                t("alert-mcp-action"),
                 ActionKind::ShowMessage {
-                    message: "Switch to MCP Health section in AgentPulse".to_string(),
+                    message: t("alert-mcp-message"),
                 },
             )),
         });
     }
 
-    let provider_unconfigured = collected.providers.iter().filter(|p| !p.configured).count();
+    let provider_unconfigured = collected
+        .providers
+        .iter()
+        .filter(|p| !p.configured && p.data_source != "disabled")
+        .count();
     if provider_unconfigured > 0 {
         alerts.push(DashboardAlert {
             severity: "info".to_string(),
-            title: "AI provider not configured".to_string(),
-            detail: format!("{} provider(s) missing config", provider_unconfigured),
+            title: t("alert-provider-title"),
+            detail: tr(
+                "alert-provider-detail",
+                &[("count", &provider_unconfigured)],
+            ),
+            repo: None,
+            action: None,
+        });
+    }
+
+    // VPN hosts that are down get the single alert below instead.
+    for probe in collected.host_probes.iter().filter(|p| {
+        !matches!(
+            p.status,
+            ProbeStatus::Authenticated | ProbeStatus::Reachable
+        ) && (!p.vpn || p.status.is_reachable())
+    }) {
+        let detail = if probe.status.is_reachable() {
+            probe.detail.clone()
+        } else {
+            tr(
+                "alert-host-stale",
+                &[("detail", &probe.detail), ("count", &probe.repos.len())],
+            )
+        };
+        alerts.push(DashboardAlert {
+            severity: "warn".to_string(),
+            title: format!("{} {}", probe.host, probe.status.label()),
+            detail,
+            repo: None,
+            action: Some(ActionCommand::new(
+                t("alert-host-action"),
+                ActionKind::ShowMessage {
+                    message: t("alert-host-message"),
+                },
+            )),
+        });
+    }
+
+    let vpn_down: Vec<&str> = collected
+        .host_probes
+        .iter()
+        .filter(|p| p.vpn && !p.status.is_reachable())
+        .map(|p| p.host.as_str())
+        .collect();
+    if !vpn_down.is_empty() {
+        alerts.push(DashboardAlert {
+            severity: "warn".to_string(),
+            title: t("alert-vpn-title"),
+            detail: tr(
+                "alert-vpn-detail",
+                &[
+                    ("hosts", &vpn_down.join(", ")),
+                    ("count", &vpn_offline_repos(&collected.host_probes).len()),
+                ],
+            ),
+            repo: None,
+            action: None,
+        });
+    }
+
+    for err in &collected.errors {
+        alerts.push(DashboardAlert {
+            severity: "info".to_string(),
+            title: tr("alert-collector-failed", &[("collector", &err.collector)]),
+            detail: err.message.clone(),
             repo: None,
             action: None,
         });
@@ -149,6 +254,35 @@ fn build_system_alerts(collected: &CollectorOutput) -> Vec<DashboardAlert> {
     alerts
 }
 
+/// Drop network actions for repos behind a VPN host that is down; they
+/// would only fail. Local suggestions (commit, stash, ...) stay.
+fn hide_vpn_blocked_actions(collected: &mut CollectorOutput) {
+    let blocked: HashSet<String> = vpn_offline_repos(&collected.host_probes)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    if blocked.is_empty() {
+        return;
+    }
+    let network =
+        |action: &Option<ActionCommand>| action.as_ref().is_some_and(|a| a.action.needs_network());
+    let blocked_path = |action: &Option<ActionCommand>| {
+        action
+            .as_ref()
+            .and_then(|a| a.action.affected_repo_path())
+            .is_some_and(|path| blocked.contains(path))
+    };
+    collected
+        .alerts
+        .retain(|alert| !(blocked_path(&alert.action) && network(&alert.action)));
+    for row in &mut collected.repos {
+        if blocked.contains(&row.path) && network(&row.action) {
+            row.action = None;
+            row.recommendation = "connect vpn".to_string();
+        }
+    }
+}
+
 fn dedupe_alerts(alerts: &mut Vec<DashboardAlert>) {
     let mut seen = HashSet::new();
     alerts.retain(|alert| {
@@ -184,6 +318,7 @@ fn provider_rank(kind: ProviderKind) -> u8 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dashboard::models::{CollectorError, HostProbe, RepoRow};
 
     #[test]
     fn dedupe_alerts_removes_duplicates() {
@@ -206,4 +341,85 @@ mod tests {
         dedupe_alerts(&mut alerts);
         assert_eq!(alerts.len(), 1);
     }
+
+    #[test]
+    fn collector_errors_become_info_alerts_and_stay_on_the_snapshot() {
+        let collected = CollectorOutput {
+            errors: vec![CollectorError::new("processes", "ps failed to start")],
+            ..Default::default()
+        };
+        let snapshot = build_snapshot(&[], collected);
+        assert_eq!(snapshot.collector_errors.len(), 1);
+        let alert = snapshot
+            .alerts
+            .iter()
+            .find(|a| a.title == "processes collector failed")
+            .unwrap();
+        assert_eq!(alert.severity, "info");
+        assert_eq!(alert.detail, "ps failed to start");
+    }
+
+    #[test]
+    fn vpn_host_down_replaces_pull_push_suggestions_with_one_alert() {
+        let pull = |repo: &str| {
+            Some(ActionCommand::new(
+                "pull --rebase",
+                ActionKind::GitPullRebase {
+                    repo_path: format!("/src/{}", repo),
+                },
+            ))
+        };
+        let row = |name: &str| RepoRow {
+            name: name.to_string(),
+            path: format!("/src/{}", name),
+            branch: "main".to_string(),
+            dirty: 0,
+            changes: Default::default(),
+            ahead: 0,
+            behind: 2,
+            stash: 0,
+            recommendation: "pull".to_string(),
+            action: pull(name),
+        };
+        let alert = |repo: &str| DashboardAlert {
+            severity: "high".to_string(),
+            title: format!("{} is behind remote", repo),
+            detail: "2 commit(s) behind".to_string(),
+            repo: Some(repo.to_string()),
+            action: pull(repo),
+        };
+        let collected = CollectorOutput {
+            alerts: vec![alert("billing"), alert("ledger"), alert("web")],
+            repos: vec![row("billing"), row("ledger"), row("web")],
+            host_probes: vec![HostProbe {
+                host: "git.corp.example".to_string(),
+                transport: "ssh".to_string(),
+                status: ProbeStatus::Unreachable,
+                detail: "timed out".to_string(),
+                latency_ms: 5_000,
+                repos: vec!["billing".to_string(), "ledger".to_string()],
+                repo_paths: vec!["/src/billing".to_string(), "/src/ledger".to_string()],
+                vpn: true,
+            }],
+            ..Default::default()
+        };
+        let snapshot = build_snapshot(&[], collected);
+
+        let titles: Vec<&str> = snapshot.alerts.iter().map(|a| a.title.as_str()).collect();
+        assert_eq!(titles, ["web is behind remote", "Connect VPN"]);
+        assert!(snapshot.alerts[1].detail.contains("paused for 2 repo(s)"));
+        let actions: Vec<(&str, bool)> = snapshot
+            .repos
+            .iter()
+            .map(|r| (r.recommendation.as_str(), r.action.is_some()))
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                ("connect vpn", false),
+                ("connect vpn", false),
+                ("pull", true)
+            ]
+        );
+    }
 }
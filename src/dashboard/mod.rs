@@ -1,9 +1,13 @@
 pub mod builder;
 pub mod models;
 
-pub use builder::collect_and_build;
+pub use builder::{collect_and_build, collect_and_build_with, refresh_repo_rows, sort_alerts};
 pub use models::{
-    ActionCommand, ActionKind, DashboardAlert, DashboardSection, DashboardSnapshot,
-    DependencyHealth, EnvAuditResult, McpServerHealth, ProviderKind, ProviderUsage, RepoProcess,
-    RepoRow, WorktreeRow,
+    ActionCommand, ActionKind, ActivityDay, AiClientConfig, CiStatus, ClientMcpServer,
+    CollectorError, DashboardAlert, DashboardSection, DashboardSnapshot, DependencyHealth,
+    EnvAuditResult, FlaggedDependency, ForgeStatus, HostProbe, LicenseAudit, LintSeverity,
+    LocalDependency, McpLintFinding, McpServerHealth, NodePackageManager, ProbeStatus,
+    ProviderKind, ProviderUsage, PullRequest, RemoteForge, RepoProcess, RepoRow, RepoTicket,
+    RepoTiming, RepoWeekStats, ScaffoldFile, ScanStats, TaskBackend, TicketTracker, WeekTotals,
+    WorktreeRow, SCHEMA_VERSION,
 };
@@ -1,7 +1,20 @@
+use crate::git::{dirty_pathspec_suffix, ChangeCounts};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Version of the `--dashboard-json` and `--agent-json` layouts.
+///
+/// New fields keep the version and must deserialize from older output (a
+/// serde default); renaming, removing or retyping a field bumps it.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(default)]
 pub struct DashboardSnapshot {
+    /// [`SCHEMA_VERSION`] of the producer; 0 for snapshots that predate it.
+    pub schema_version: u32,
     pub generated_at_epoch_secs: i64,
     pub overview: OverviewMetrics,
     pub alerts: Vec<DashboardAlert>,
@@ -9,12 +22,45 @@ pub struct DashboardSnapshot {
     pub worktrees: Vec<WorktreeRow>,
     pub processes: Vec<RepoProcess>,
     pub dependencies: Vec<DependencyHealth>,
+    /// Path links between watched repos.
+    pub local_deps: Vec<LocalDependency>,
     pub env_audit: Vec<EnvAuditResult>,
+    pub licenses: Vec<LicenseAudit>,
     pub mcp_servers: Vec<McpServerHealth>,
+    /// Config files of AI clients found in the home directory.
+    pub ai_clients: Vec<AiClientConfig>,
     pub providers: Vec<ProviderUsage>,
+    /// Collectors that failed this pass; their sections may be incomplete.
+    pub collector_errors: Vec<CollectorError>,
+    /// Remote host reachability; empty unless `[connectivity]` is enabled.
+    pub host_probes: Vec<HostProbe>,
+    /// Timings and cache counters from the TUI's scan loop; absent elsewhere.
+    pub scan_stats: Option<ScanStats>,
+    /// Commits per day over the last eight weeks, oldest first; days
+    /// without commits are left out.
+    pub activity: Vec<ActivityDay>,
+    /// The user's commits since Monday, per repo with any, busiest first.
+    pub this_week: Vec<RepoWeekStats>,
+    /// Ticket keys found in branch names; empty unless `[tickets] pattern`
+    /// is set.
+    pub tickets: Vec<RepoTicket>,
+    /// Open pull/merge request and CI per GitHub or GitLab repo; empty
+    /// unless `[forge] enabled` is set.
+    pub pull_requests: Vec<ForgeStatus>,
 }
 
 impl DashboardSnapshot {
+    /// Paths of repos with a remote on a VPN-only host that is unreachable
+    /// right now.
+    pub fn vpn_offline_repos(&self) -> BTreeSet<&str> {
+        vpn_offline_repos(&self.host_probes)
+    }
+
+    /// JSON Schema of the serialized snapshot.
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(DashboardSnapshot)).unwrap_or_default()
+    }
+
     pub fn total_estimated_cost_usd(&self) -> f64 {
         self.providers
             .iter()
@@ -23,29 +69,43 @@ impl DashboardSnapshot {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub enum DashboardSection {
     Home,
     Repos,
     Worktrees,
     Processes,
     Dependencies,
+    LocalDeps,
     EnvAudit,
+    Licenses,
     McpHealth,
     AiCosts,
+    PullRequests,
+    AiClients,
+    Stats,
+    ActionLog,
+    Settings,
 }
 
 impl DashboardSection {
-    pub fn all() -> [DashboardSection; 8] {
+    pub fn all() -> [DashboardSection; 15] {
         [
             DashboardSection::Home,
             DashboardSection::Repos,
             DashboardSection::Worktrees,
             DashboardSection::Processes,
             DashboardSection::Dependencies,
+            DashboardSection::LocalDeps,
             DashboardSection::EnvAudit,
+            DashboardSection::Licenses,
             DashboardSection::McpHealth,
             DashboardSection::AiCosts,
+            DashboardSection::PullRequests,
+            DashboardSection::AiClients,
+            DashboardSection::Stats,
+            DashboardSection::ActionLog,
+            DashboardSection::Settings,
         ]
     }
 
@@ -55,8 +115,16 @@ impl DashboardSection {
             DashboardSection::Repos | DashboardSection::Worktrees => "WORKSPACE",
             DashboardSection::Processes
             | DashboardSection::Dependencies
-            | DashboardSection::EnvAudit => "MONITOR",
-            DashboardSection::McpHealth | DashboardSection::AiCosts => "INTEGRATIONS",
+            | DashboardSection::LocalDeps
+            | DashboardSection::EnvAudit
+            | DashboardSection::Licenses => "MONITOR",
+            DashboardSection::McpHealth
+            | DashboardSection::AiCosts
+            | DashboardSection::PullRequests
+            | DashboardSection::AiClients => "INTEGRATIONS",
+            DashboardSection::Stats | DashboardSection::ActionLog | DashboardSection::Settings => {
+                "INTERNAL"
+            }
         }
     }
 
@@ -67,14 +135,22 @@ impl DashboardSection {
             DashboardSection::Worktrees => "Worktrees",
             DashboardSection::Processes => "Processes",
             DashboardSection::Dependencies => "Deps",
+            DashboardSection::LocalDeps => "Local Deps",
             DashboardSection::EnvAudit => "Env Audit",
+            DashboardSection::Licenses => "Licenses",
             DashboardSection::McpHealth => "MCP Health",
             DashboardSection::AiCosts => "AI Costs",
+            DashboardSection::PullRequests => "Pull Requests",
+            DashboardSection::AiClients => "AI Clients",
+            DashboardSection::Stats => "Stats",
+            DashboardSection::ActionLog => "Action Log",
+            DashboardSection::Settings => "Settings",
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(default)]
 pub struct OverviewMetrics {
     pub total_repos: usize,
     pub actionable_repos: usize,
@@ -88,7 +164,125 @@ pub struct OverviewMetrics {
     pub mcp_unhealthy: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Where a task filed from an alert goes (`[tasks] backend`).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskBackend {
+    /// A `- [ ]` checklist item appended to a Markdown file.
+    #[default]
+    Markdown,
+    /// A `* TODO` heading appended to an Org file.
+    Org,
+    /// `task add`, with the notes as annotations.
+    Taskwarrior,
+    /// The `things:///add` URL scheme (macOS).
+    Things,
+}
+
+impl TaskBackend {
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskBackend::Markdown => "TODO.md",
+            TaskBackend::Org => "Org",
+            TaskBackend::Taskwarrior => "Taskwarrior",
+            TaskBackend::Things => "Things",
+        }
+    }
+}
+
+/// Issue tracker that branch ticket keys are looked up in (`[tickets] tracker`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketTracker {
+    /// Jira Cloud or Data Center, `JIRA_API_TOKEN` (plus `JIRA_EMAIL` on Cloud).
+    Jira,
+    /// Linear, `LINEAR_API_KEY`.
+    Linear,
+}
+
+/// Hosting service a new remote repository is created on.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteForge {
+    Github,
+    Gitlab,
+}
+
+impl RemoteForge {
+    pub fn label(self) -> &'static str {
+        match self {
+            RemoteForge::Github => "GitHub",
+            RemoteForge::Gitlab => "GitLab",
+        }
+    }
+
+    /// CLI used to create the repository.
+    pub fn cli(self) -> &'static str {
+        match self {
+            RemoteForge::Github => "gh",
+            RemoteForge::Gitlab => "glab",
+        }
+    }
+}
+
+/// Node package manager a repo uses, so lockfile actions never add a second,
+/// conflicting lockfile.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NodePackageManager {
+    #[default]
+    Npm,
+    Pnpm,
+    /// Yarn 2+ (Berry).
+    Yarn,
+    /// Yarn 1.x, which has no lockfile-only mode.
+    YarnClassic,
+    Bun,
+}
+
+impl NodePackageManager {
+    pub fn binary(self) -> &'static str {
+        match self {
+            NodePackageManager::Npm => "npm",
+            NodePackageManager::Pnpm => "pnpm",
+            NodePackageManager::Yarn | NodePackageManager::YarnClassic => "yarn",
+            NodePackageManager::Bun => "bun",
+        }
+    }
+
+    /// Arguments that write the lockfile without touching `node_modules`
+    /// where the tool supports it.
+    pub fn lockfile_args(self) -> &'static [&'static str] {
+        match self {
+            NodePackageManager::Npm => &["install", "--package-lock-only"],
+            NodePackageManager::Pnpm => &["install", "--lockfile-only"],
+            NodePackageManager::Yarn => &["install", "--mode", "update-lockfile"],
+            NodePackageManager::YarnClassic | NodePackageManager::Bun => &["install"],
+        }
+    }
+}
+
+/// How much confirmation an action needs.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskLevel {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl RiskLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RiskLevel::Low => "low",
+            RiskLevel::Medium => "medium",
+            RiskLevel::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ActionKind {
     GitStatus {
@@ -103,9 +297,18 @@ pub enum ActionKind {
     GitPush {
         repo_path: String,
     },
+    /// `git push -u <remote> <branch>` for a branch with no upstream yet.
+    GitPushSetUpstream {
+        repo_path: String,
+        remote: String,
+        branch: String,
+    },
     GitWorktreeList {
         repo_path: String,
     },
+    /// Commits tracked changes only (`add -u`, minus `[dirty_ignore]` paths):
+    /// untracked files are never swept into a wip commit. Likewise the other
+    /// commit actions.
     GitAddCommitPullRebase {
         repo_path: String,
         message: String,
@@ -124,6 +327,35 @@ pub enum ActionKind {
     GitStashList {
         repo_path: String,
     },
+    /// Stash only the given paths (tracked or untracked), leaving the rest.
+    GitStashPushPaths {
+        repo_path: String,
+        message: String,
+        paths: Vec<String>,
+    },
+    /// Apply a stash entry (`stash@{n}`) and keep it in the list. With `oid`,
+    /// refused unless `stash` still names that commit, as the stash
+    /// browser's entries do; indexes shift when stashes come and go.
+    GitStashApply {
+        repo_path: String,
+        stash: String,
+        #[serde(default)]
+        oid: Option<String>,
+    },
+    /// Apply a stash entry and drop it once it applies cleanly.
+    GitStashPop {
+        repo_path: String,
+        stash: String,
+        #[serde(default)]
+        oid: Option<String>,
+    },
+    /// Delete a stash entry without applying it.
+    GitStashDrop {
+        repo_path: String,
+        stash: String,
+        #[serde(default)]
+        oid: Option<String>,
+    },
     GitRemoteList {
         repo_path: String,
     },
@@ -131,21 +363,100 @@ pub enum ActionKind {
         repo_path: String,
         branch: String,
     },
+    /// Check out an existing local branch, optionally stashing local changes
+    /// first and restoring them afterwards.
+    GitSwitch {
+        repo_path: String,
+        branch: String,
+        autostash: bool,
+    },
+    /// Apply commits (oldest first) onto another local branch with
+    /// `cherry-pick -x`, in the worktree that has it checked out or a
+    /// temporary one; a conflict aborts and leaves the branch unchanged.
+    GitCherryPick {
+        repo_path: String,
+        branch: String,
+        commits: Vec<String>,
+    },
+    /// Create a branch at an existing commit (e.g. rescuing unreachable work).
+    GitBranchAt {
+        repo_path: String,
+        branch: String,
+        commit: String,
+    },
+    /// Soft-reset an unpushed commit made by an action; its changes stay staged.
+    GitUndoCommit {
+        repo_path: String,
+        commit: String,
+    },
+    /// `git bundle create <output> --all`, verified before it replaces
+    /// `output`: an offline copy of every ref for work that cannot be pushed.
+    GitBundleCreate {
+        repo_path: String,
+        output: String,
+    },
+    /// Repack and prune a heavy `.git`: `git gc --prune=<lookback> --aggressive`
+    /// (unreachable commits inside the lost-work lookback are kept), or
+    /// `git maintenance run --task=gc` when `aggressive` would take too long.
+    GitGc {
+        repo_path: String,
+        aggressive: bool,
+    },
+    /// Register the repo for scheduled background maintenance:
+    /// `git maintenance start`, or `scalar register` (which also enables the
+    /// fsmonitor) when `scalar` is installed.
+    GitMaintenanceStart {
+        repo_path: String,
+        scalar: bool,
+    },
+    /// Create a hosted repo, add it as `origin`, and push the current branch.
+    CreateRemoteRepo {
+        repo_path: String,
+        forge: RemoteForge,
+        name: String,
+        private: bool,
+    },
+    /// Draft the missing changelog section into a temp file for review.
+    DraftChangelog {
+        repo_path: String,
+    },
     KillProcess {
         pid: i32,
     },
+    /// Create or refresh the Node lockfile with the repo's package manager.
     NpmInstallLockfile {
         repo_path: String,
+        #[serde(default)]
+        manager: NodePackageManager,
     },
     CargoGenerateLockfile {
         repo_path: String,
     },
+    /// Re-resolve only the workspace's own manifests into an existing Cargo.lock.
+    CargoUpdateWorkspace {
+        repo_path: String,
+    },
     UvLock {
         repo_path: String,
     },
     PipCompileRequirements {
         repo_path: String,
     },
+    /// Create `.venv` (honouring `.python-version`) and sync it to uv.lock.
+    UvSync {
+        repo_path: String,
+    },
+    /// Create the virtualenv with `python` if missing (or `recreate` it),
+    /// then install requirements.txt into it.
+    PipVenvSync {
+        repo_path: String,
+        python: String,
+        #[serde(default)]
+        recreate: bool,
+        /// Virtualenv directory relative to the repo; `.venv` when unset.
+        #[serde(default)]
+        venv: Option<String>,
+    },
     GoModTidy {
         repo_path: String,
     },
@@ -159,6 +470,22 @@ pub enum ActionKind {
     SeedEnvFromExample {
         repo_path: String,
     },
+    /// Run an allowlisted program (`[actions] allowed_programs`) with
+    /// literal arguments, without a shell.
+    Custom {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        cwd: Option<String>,
+        #[serde(default)]
+        risk: RiskLevel,
+    },
+    /// Copy policy templates into the repo; existing files are never overwritten.
+    ScaffoldFiles {
+        repo_path: String,
+        files: Vec<ScaffoldFile>,
+    },
     ProbeBinaryHelp {
         binary: String,
     },
@@ -168,6 +495,25 @@ pub enum ActionKind {
     ShowMessage {
         message: String,
     },
+    /// Write an empty MCP config so servers can be added to it.
+    CreateMcpConfig {
+        path: String,
+    },
+    OpenInEditor {
+        path: String,
+        editor: String,
+    },
+    /// File a task for later: the title plus note lines (repo path,
+    /// suggested command) in the configured task manager.
+    CreateTask {
+        backend: TaskBackend,
+        title: String,
+        #[serde(default)]
+        notes: Vec<String>,
+        /// Target file for `markdown` and `org`.
+        #[serde(default)]
+        file: Option<String>,
+    },
 }
 
 impl ActionKind {
@@ -179,12 +525,21 @@ impl ActionKind {
                 format!("git -C {:?} pull --rebase", repo_path)
             }
             ActionKind::GitPush { repo_path } => format!("git -C {:?} push", repo_path),
+            ActionKind::GitPushSetUpstream {
+                repo_path,
+                remote,
+                branch,
+            } => format!("git -C {:?} push -u {} {:?}", repo_path, remote, branch),
             ActionKind::GitWorktreeList { repo_path } => {
                 format!("git -C {:?} worktree list", repo_path)
             }
             ActionKind::GitAddCommitPullRebase { repo_path, message } => format!(
-                "git -C {:?} add -A && git -C {:?} commit -m {:?} && git -C {:?} pull --rebase",
-                repo_path, repo_path, message, repo_path
+                "git -C {:?} add -u{} && git -C {:?} commit -m {:?} && git -C {:?} pull --rebase",
+                repo_path,
+                dirty_pathspec_suffix(Path::new(repo_path)),
+                repo_path,
+                message,
+                repo_path
             ),
             ActionKind::GitPullRebasePush { repo_path } => {
                 format!(
@@ -193,30 +548,165 @@ impl ActionKind {
                 )
             }
             ActionKind::GitAddCommitPush { repo_path, message } => format!(
-                "git -C {:?} add -A && git -C {:?} commit -m {:?} && git -C {:?} push",
-                repo_path, repo_path, message, repo_path
+                "git -C {:?} add -u{} && git -C {:?} commit -m {:?} && git -C {:?} push",
+                repo_path,
+                dirty_pathspec_suffix(Path::new(repo_path)),
+                repo_path,
+                message,
+                repo_path
             ),
             ActionKind::GitAddCommit { repo_path, message } => format!(
-                "git -C {:?} add -A && git -C {:?} commit -m {:?}",
-                repo_path, repo_path, message
+                "git -C {:?} add -u{} && git -C {:?} commit -m {:?}",
+                repo_path,
+                dirty_pathspec_suffix(Path::new(repo_path)),
+                repo_path,
+                message
             ),
             ActionKind::GitStashList { repo_path } => {
                 format!("git -C {:?} stash list", repo_path)
             }
+            ActionKind::GitStashPushPaths {
+                repo_path,
+                message,
+                paths,
+            } => format!(
+                "git -C {:?} stash push -u -m {:?} -- {}",
+                repo_path,
+                message,
+                paths
+                    .iter()
+                    .map(|p| format!("{:?}", p))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            ActionKind::GitStashApply {
+                repo_path, stash, ..
+            } => {
+                format!("git -C {:?} stash apply {}", repo_path, stash)
+            }
+            ActionKind::GitStashPop {
+                repo_path, stash, ..
+            } => {
+                format!("git -C {:?} stash pop {}", repo_path, stash)
+            }
+            ActionKind::GitStashDrop {
+                repo_path, stash, ..
+            } => {
+                format!("git -C {:?} stash drop {}", repo_path, stash)
+            }
             ActionKind::GitRemoteList { repo_path } => {
                 format!("git -C {:?} remote -v", repo_path)
             }
             ActionKind::GitSwitchCreate { repo_path, branch } => {
                 format!("git -C {:?} switch -c {:?}", repo_path, branch)
             }
-            ActionKind::KillProcess { pid } => format!("kill {}", pid),
-            ActionKind::NpmInstallLockfile { repo_path } => {
-                format!("npm --prefix {:?} install --package-lock-only", repo_path)
+            ActionKind::GitSwitch {
+                repo_path,
+                branch,
+                autostash: true,
+            } => format!(
+                "git -C {0:?} stash push -u && git -C {0:?} switch {1:?} && git -C {0:?} stash pop",
+                repo_path, branch
+            ),
+            ActionKind::GitSwitch {
+                repo_path, branch, ..
+            } => format!("git -C {:?} switch {:?}", repo_path, branch),
+            ActionKind::GitCherryPick {
+                repo_path,
+                branch,
+                commits,
+            } => format!(
+                "git -C {:?} cherry-pick -x {}  # onto {:?}, aborts on conflict",
+                repo_path,
+                commits
+                    .iter()
+                    .map(|c| &c[..c.len().min(8)])
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                branch
+            ),
+            ActionKind::GitBranchAt {
+                repo_path,
+                branch,
+                commit,
+            } => format!("git -C {:?} branch {:?} {}", repo_path, branch, commit),
+            ActionKind::GitUndoCommit { repo_path, commit } => {
+                format!("git -C {:?} reset --soft {}^", repo_path, commit)
             }
+            ActionKind::GitBundleCreate { repo_path, output } => format!(
+                "git -C {:?} bundle create {:?} --all && git -C {:?} bundle verify {:?}",
+                repo_path, output, repo_path, output
+            ),
+            ActionKind::GitGc {
+                repo_path,
+                aggressive: true,
+            } => format!(
+                "git -C {:?} gc --prune={} --aggressive",
+                repo_path,
+                crate::collectors::lost_work::prune_expiry()
+            ),
+            ActionKind::GitGc {
+                repo_path,
+                aggressive: false,
+            } => format!("git -C {:?} maintenance run --task=gc", repo_path),
+            ActionKind::GitMaintenanceStart {
+                repo_path,
+                scalar: true,
+            } => format!("scalar register {:?}", repo_path),
+            ActionKind::GitMaintenanceStart {
+                repo_path,
+                scalar: false,
+            } => format!("git -C {:?} maintenance start", repo_path),
+            ActionKind::CreateRemoteRepo {
+                repo_path,
+                forge,
+                name,
+                private,
+            } => {
+                let visibility = if *private { "--private" } else { "--public" };
+                match forge {
+                    RemoteForge::Github => format!(
+                        "gh repo create {:?} {} --source {:?} --remote origin --push",
+                        name, visibility, repo_path
+                    ),
+                    RemoteForge::Gitlab => format!(
+                        "glab repo create {:?} {} && git -C {:?} push -u origin HEAD",
+                        name, visibility, repo_path
+                    ),
+                }
+            }
+            ActionKind::DraftChangelog { repo_path } => format!(
+                "git-cliff --unreleased (or built-in parser) for {:?} -> temp file",
+                repo_path
+            ),
+            ActionKind::KillProcess { pid } => format!("kill {}", pid),
+            ActionKind::NpmInstallLockfile { repo_path, manager } => format!(
+                "cd {:?} && {} {}",
+                repo_path,
+                manager.binary(),
+                manager.lockfile_args().join(" ")
+            ),
             ActionKind::CargoGenerateLockfile { repo_path } => {
                 format!("cargo -C {:?} generate-lockfile", repo_path)
             }
+            ActionKind::CargoUpdateWorkspace { repo_path } => {
+                format!("cargo -C {:?} update --workspace", repo_path)
+            }
             ActionKind::UvLock { repo_path } => format!("uv --directory {:?} lock", repo_path),
+            ActionKind::UvSync { repo_path } => format!("uv --directory {:?} sync", repo_path),
+            ActionKind::PipVenvSync {
+                repo_path,
+                python,
+                recreate,
+                venv,
+            } => {
+                let venv = venv.as_deref().unwrap_or(".venv");
+                let clear = if *recreate { " --clear" } else { "" };
+                format!(
+                    "cd {:?} && {} -m venv{} {} && {}/bin/python -m pip install -r {}",
+                    repo_path, python, clear, venv, venv, "requirements.txt"
+                )
+            }
             ActionKind::PipCompileRequirements { repo_path } => {
                 format!("pip-compile {:?}/requirements.txt", repo_path)
             }
@@ -230,9 +720,53 @@ impl ActionKind {
             ActionKind::SeedEnvFromExample { repo_path } => {
                 format!("copy {:?}/.env.example -> {:?}/.env", repo_path, repo_path)
             }
+            ActionKind::Custom {
+                program, args, cwd, ..
+            } => {
+                let command = std::iter::once(program)
+                    .chain(args)
+                    .map(|a| {
+                        if a.is_empty() || a.contains(|c: char| c.is_whitespace() || c == '"') {
+                            format!("{:?}", a)
+                        } else {
+                            a.clone()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                match cwd {
+                    Some(dir) => format!("cd {:?} && {}", dir, command),
+                    None => command,
+                }
+            }
+            ActionKind::ScaffoldFiles { repo_path, files } => files
+                .iter()
+                .map(|f| format!("copy {:?} -> {:?}/{}", f.template, repo_path, f.path))
+                .collect::<Vec<_>>()
+                .join(" && "),
             ActionKind::ProbeBinaryHelp { binary } => format!("{:?} --help", binary),
             ActionKind::CheckBinaryInPath { binary } => format!("which {:?}", binary),
             ActionKind::ShowMessage { message } => format!("echo {:?}", message),
+            ActionKind::CreateMcpConfig { path } => {
+                format!("write {:?} with an empty mcpServers map", path)
+            }
+            ActionKind::OpenInEditor { path, editor } => format!("{} {:?}", editor, path),
+            ActionKind::CreateTask {
+                backend,
+                title,
+                file,
+                ..
+            } => match (backend, file) {
+                (TaskBackend::Markdown, Some(file)) => {
+                    format!("append \"- [ ] {}\" to {:?}", title, file)
+                }
+                (TaskBackend::Org, Some(file)) => {
+                    format!("append \"* TODO {}\" to {:?}", title, file)
+                }
+                (TaskBackend::Taskwarrior, _) => format!("task add {:?}", title),
+                (TaskBackend::Things, _) => format!("open things:///add?title={:?}", title),
+                (_, None) => format!("add task {:?}", title),
+            },
         }
     }
 
@@ -242,33 +776,80 @@ impl ActionKind {
             | ActionKind::GitFetch { repo_path }
             | ActionKind::GitPullRebase { repo_path }
             | ActionKind::GitPush { repo_path }
+            | ActionKind::GitPushSetUpstream { repo_path, .. }
             | ActionKind::GitWorktreeList { repo_path }
             | ActionKind::GitAddCommitPullRebase { repo_path, .. }
             | ActionKind::GitPullRebasePush { repo_path }
             | ActionKind::GitAddCommitPush { repo_path, .. }
             | ActionKind::GitAddCommit { repo_path, .. }
             | ActionKind::GitStashList { repo_path }
+            | ActionKind::GitStashPushPaths { repo_path, .. }
+            | ActionKind::GitStashApply { repo_path, .. }
+            | ActionKind::GitStashPop { repo_path, .. }
+            | ActionKind::GitStashDrop { repo_path, .. }
             | ActionKind::GitRemoteList { repo_path }
             | ActionKind::GitSwitchCreate { repo_path, .. }
-            | ActionKind::NpmInstallLockfile { repo_path }
+            | ActionKind::GitSwitch { repo_path, .. }
+            | ActionKind::GitCherryPick { repo_path, .. }
+            | ActionKind::GitBranchAt { repo_path, .. }
+            | ActionKind::GitUndoCommit { repo_path, .. }
+            | ActionKind::GitBundleCreate { repo_path, .. }
+            | ActionKind::GitGc { repo_path, .. }
+            | ActionKind::GitMaintenanceStart { repo_path, .. }
+            | ActionKind::CreateRemoteRepo { repo_path, .. }
+            | ActionKind::DraftChangelog { repo_path }
+            | ActionKind::NpmInstallLockfile { repo_path, .. }
             | ActionKind::CargoGenerateLockfile { repo_path }
+            | ActionKind::CargoUpdateWorkspace { repo_path }
             | ActionKind::UvLock { repo_path }
+            | ActionKind::UvSync { repo_path }
+            | ActionKind::PipVenvSync { repo_path, .. }
             | ActionKind::PipCompileRequirements { repo_path }
             | ActionKind::GoModTidy { repo_path }
             | ActionKind::BundleLock { repo_path }
             | ActionKind::IgnoreEnvFiles { repo_path, .. }
-            | ActionKind::SeedEnvFromExample { repo_path } => Some(repo_path),
+            | ActionKind::SeedEnvFromExample { repo_path }
+            | ActionKind::ScaffoldFiles { repo_path, .. } => Some(repo_path),
+            ActionKind::Custom { cwd, .. } => cwd.as_deref(),
             ActionKind::KillProcess { .. }
             | ActionKind::ProbeBinaryHelp { .. }
             | ActionKind::CheckBinaryInPath { .. }
-            | ActionKind::ShowMessage { .. } => None,
+            | ActionKind::ShowMessage { .. }
+            | ActionKind::CreateMcpConfig { .. }
+            | ActionKind::OpenInEditor { .. }
+            | ActionKind::CreateTask { .. } => None,
         }
     }
 
+    /// Talks to a remote, so it fails while the remote is unreachable.
+    pub fn needs_network(&self) -> bool {
+        matches!(
+            self,
+            ActionKind::GitFetch { .. }
+                | ActionKind::GitPullRebase { .. }
+                | ActionKind::GitPush { .. }
+                | ActionKind::GitPushSetUpstream { .. }
+                | ActionKind::GitAddCommitPullRebase { .. }
+                | ActionKind::GitPullRebasePush { .. }
+                | ActionKind::GitAddCommitPush { .. }
+        )
+    }
+
     pub fn is_destructive(&self) -> bool {
         matches!(
             self,
-            ActionKind::KillProcess { .. } | ActionKind::IgnoreEnvFiles { .. }
+            ActionKind::KillProcess { .. }
+                | ActionKind::IgnoreEnvFiles { .. }
+                | ActionKind::GitStashDrop { .. }
+                // Pruning deletes unreachable commits older than the lookback.
+                | ActionKind::GitGc {
+                    aggressive: true,
+                    ..
+                }
+                | ActionKind::Custom {
+                    risk: RiskLevel::High,
+                    ..
+                }
         )
     }
 
@@ -282,9 +863,21 @@ impl ActionKind {
                 | ActionKind::GitAddCommitPush { .. }
                 | ActionKind::GitAddCommit { .. }
                 | ActionKind::GitSwitchCreate { .. }
+                | ActionKind::GitSwitch { .. }
+                | ActionKind::GitCherryPick { .. }
+                | ActionKind::GitStashPushPaths { .. }
+                | ActionKind::GitStashApply { .. }
+                | ActionKind::GitStashPop { .. }
+                | ActionKind::GitUndoCommit { .. }
+                | ActionKind::GitGc { .. }
+                | ActionKind::GitMaintenanceStart { .. }
+                | ActionKind::PipVenvSync { .. }
+                | ActionKind::CreateRemoteRepo { .. }
                 | ActionKind::GitPullRebase { .. }
                 | ActionKind::GitFetch { .. }
-                | ActionKind::GitPush { .. } => "medium",
+                | ActionKind::GitPush { .. }
+                | ActionKind::GitPushSetUpstream { .. } => "medium",
+                ActionKind::Custom { risk, .. } => risk.as_str(),
                 _ => "low",
             }
         }
@@ -299,7 +892,14 @@ impl ActionKind {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A repo-relative file and the template it is created from.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ScaffoldFile {
+    pub path: String,
+    pub template: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ActionCommand {
     pub label: String,
     /// Human-readable preview of what will run.
@@ -318,7 +918,246 @@ impl ActionCommand {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A collector that could not run, as opposed to one that found nothing.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct CollectorError {
+    pub collector: String,
+    pub message: String,
+}
+
+impl CollectorError {
+    pub fn new(collector: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            collector: collector.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for CollectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.collector, self.message)
+    }
+}
+
+impl std::error::Error for CollectorError {}
+
+/// Where the last scan spent its time, for tuning depth and ignore settings.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+#[serde(default)]
+pub struct ScanStats {
+    /// Walking `watch_directories` for repos.
+    pub discover_ms: u64,
+    /// Git status checks (cache misses only).
+    pub status_ms: u64,
+    /// Dashboard collectors.
+    pub collect_ms: u64,
+    pub repos_found: usize,
+    /// Repos answered from the status cache without running git.
+    pub cache_hits: usize,
+    pub cache_misses: usize,
+    /// Clean repos due for their sweep but left for a later pass.
+    pub deferred: usize,
+    /// Directory names skipped by `ignored_repos`.
+    pub ignored: Vec<String>,
+    /// Git processes started during the scan and collectors.
+    pub git_spawns: u64,
+    /// Slowest status checks, slowest first.
+    pub slowest: Vec<RepoTiming>,
+}
+
+impl ScanStats {
+    pub fn total_ms(&self) -> u64 {
+        self.discover_ms + self.status_ms + self.collect_ms
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct RepoTiming {
+    pub repo: String,
+    pub path: String,
+    pub ms: u64,
+}
+
+/// Commits made on one local day across the watched repos.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ActivityDay {
+    /// `YYYY-MM-DD` in local time.
+    pub date: String,
+    pub commits: usize,
+    /// Commits per repo name.
+    pub repos: BTreeMap<String, usize>,
+}
+
+/// One repo's commits and changed lines since Monday.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct RepoWeekStats {
+    pub repo: String,
+    pub commits: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+/// A ticket key found in a repo's branch name, with the ticket's title and
+/// status when the tracker could be asked.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct RepoTicket {
+    pub repo: String,
+    pub repo_path: String,
+    pub branch: String,
+    pub key: String,
+    /// The ticket's page, from the tracker or `[tickets] url`.
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Why the lookup failed; the key and link are still shown.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// The open pull/merge request and latest CI run for a repo's branch on
+/// GitHub or GitLab.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ForgeStatus {
+    pub repo: String,
+    pub repo_path: String,
+    pub branch: String,
+    pub forge: RemoteForge,
+    /// The open PR/MR from `branch`, if there is one.
+    #[serde(default)]
+    pub pull_request: Option<PullRequest>,
+    #[serde(default)]
+    pub ci: CiStatus,
+    /// Workflow or pipeline name of the latest run.
+    #[serde(default)]
+    pub ci_name: Option<String>,
+    #[serde(default)]
+    pub ci_url: Option<String>,
+    /// Why the forge could not be asked; the last answer is not kept.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    #[serde(default)]
+    pub draft: bool,
+    /// Reviews were requested and the approval is still missing.
+    #[serde(default)]
+    pub awaiting_review: bool,
+}
+
+/// Outcome of the latest CI run on a branch.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CiStatus {
+    Passing,
+    Failing,
+    /// Queued or in progress.
+    Running,
+    /// No runs on the branch, or no CI.
+    #[default]
+    Unknown,
+}
+
+impl CiStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            CiStatus::Passing => "passing",
+            CiStatus::Failing => "failing",
+            CiStatus::Running => "running",
+            CiStatus::Unknown => "—",
+        }
+    }
+}
+
+/// [`RepoWeekStats`] summed over the workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WeekTotals {
+    pub repos: usize,
+    pub commits: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+impl WeekTotals {
+    pub fn of(stats: &[RepoWeekStats]) -> WeekTotals {
+        WeekTotals {
+            repos: stats.len(),
+            commits: stats.iter().map(|s| s.commits).sum(),
+            insertions: stats.iter().map(|s| s.insertions).sum(),
+            deletions: stats.iter().map(|s| s.deletions).sum(),
+        }
+    }
+}
+
+/// One remote host as seen by the `[connectivity]` probe.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct HostProbe {
+    pub host: String,
+    /// `ssh` or `https`.
+    pub transport: String,
+    pub status: ProbeStatus,
+    pub detail: String,
+    pub latency_ms: u64,
+    /// Repos with a remote on this host.
+    pub repos: Vec<String>,
+    /// Their paths, in the same order.
+    #[serde(default)]
+    pub repo_paths: Vec<String>,
+    /// Listed in `[connectivity] vpn_hosts`.
+    #[serde(default)]
+    pub vpn: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeStatus {
+    /// Connected over SSH and the server accepted a key.
+    Authenticated,
+    /// Connected over HTTPS; credentials are not tried.
+    Reachable,
+    /// Connected, but the server rejected every key offered.
+    AuthFailed,
+    /// The host key is unknown or changed (`known_hosts`).
+    HostKey,
+    /// DNS failure, timeout, refused or no route.
+    Unreachable,
+}
+
+/// Paths of repos behind VPN hosts in `probes` that did not answer.
+pub fn vpn_offline_repos(probes: &[HostProbe]) -> BTreeSet<&str> {
+    probes
+        .iter()
+        .filter(|p| p.vpn && !p.status.is_reachable())
+        .flat_map(|p| p.repo_paths.iter().map(String::as_str))
+        .collect()
+}
+
+impl ProbeStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            ProbeStatus::Authenticated => "authenticated",
+            ProbeStatus::Reachable => "reachable",
+            ProbeStatus::AuthFailed => "auth failed",
+            ProbeStatus::HostKey => "host key",
+            ProbeStatus::Unreachable => "unreachable",
+        }
+    }
+
+    /// The network path works, whatever happened after connecting.
+    pub fn is_reachable(self) -> bool {
+        self != ProbeStatus::Unreachable
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DashboardAlert {
     pub severity: String,
     pub title: String,
@@ -327,12 +1166,15 @@ pub struct DashboardAlert {
     pub action: Option<ActionCommand>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RepoRow {
     pub name: String,
     pub path: String,
     pub branch: String,
     pub dirty: usize,
+    /// `dirty` by kind: staged, unstaged, untracked, conflicted.
+    #[serde(default)]
+    pub changes: ChangeCounts,
     pub ahead: usize,
     pub behind: usize,
     pub stash: usize,
@@ -340,7 +1182,7 @@ pub struct RepoRow {
     pub action: Option<ActionCommand>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WorktreeRow {
     pub repo: String,
     pub path: String,
@@ -350,7 +1192,7 @@ pub struct WorktreeRow {
     pub action: Option<ActionCommand>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RepoProcess {
     pub repo: String,
     pub pid: i32,
@@ -359,7 +1201,7 @@ pub struct RepoProcess {
     pub action: Option<ActionCommand>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DependencyHealth {
     pub repo: String,
     pub path: String,
@@ -369,7 +1211,44 @@ pub struct DependencyHealth {
     pub action: Option<ActionCommand>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A watched repo building against another watched repo by path.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LocalDependency {
+    pub dependent: String,
+    pub dependent_path: String,
+    pub dependency: String,
+    pub dependency_path: String,
+    /// Package, crate, or module name the link is declared under.
+    pub package: String,
+    /// Manifest ecosystem: `cargo`, `npm`, or `go`.
+    pub kind: String,
+    pub dependency_dirty: usize,
+    pub dependency_unpushed: usize,
+}
+
+/// A repo's declared license and any dependency licenses it should review.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LicenseAudit {
+    pub repo: String,
+    pub path: String,
+    /// SPDX expression from the manifest, or sniffed from a LICENSE file.
+    pub declared: Option<String>,
+    /// No license, UNLICENSED / proprietary, or listed in `commercial_repos`.
+    pub commercial: bool,
+    pub dependencies_scanned: usize,
+    pub unknown_licenses: usize,
+    pub flagged: Vec<FlaggedDependency>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FlaggedDependency {
+    pub package: String,
+    pub license: String,
+    /// Every alternative of the license expression is strong copyleft.
+    pub copyleft: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EnvAuditResult {
     pub repo: String,
     pub path: String,
@@ -380,7 +1259,7 @@ pub struct EnvAuditResult {
     pub action: Option<ActionCommand>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct McpServerHealth {
     pub source: String,
     pub server_name: String,
@@ -388,9 +1267,65 @@ pub struct McpServerHealth {
     pub healthy: bool,
     pub detail: String,
     pub action: Option<ActionCommand>,
+    /// Config problems in this entry; any error makes it unhealthy.
+    #[serde(default)]
+    pub lint: Vec<McpLintFinding>,
+}
+
+/// A problem found linting an MCP config entry.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct McpLintFinding {
+    /// 1-based line in the entry's `source`, when it could be located.
+    pub line: Option<usize>,
+    pub severity: LintSeverity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    /// The entry cannot work as written; the server is unhealthy.
+    Error,
+    /// Likely a mistake, though the server may still start.
+    Warn,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+impl LintSeverity {
+    pub fn label(self) -> &'static str {
+        match self {
+            LintSeverity::Error => "error",
+            LintSeverity::Warn => "warn",
+        }
+    }
+}
+
+/// An AI client's own config file and what it sets up.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+#[serde(default)]
+pub struct AiClientConfig {
+    /// `Claude Desktop`, `Claude Code`, `Cursor`, `VS Code` or `Codex`.
+    pub client: String,
+    pub path: String,
+    pub servers: Vec<ClientMcpServer>,
+    pub models: Vec<String>,
+    /// Allow/deny rules and approval settings, as written in the file.
+    pub permissions: Vec<String>,
+    /// Servers, deny rules, the default model or permission verdicts that
+    /// differ from the other clients.
+    pub drift: Vec<String>,
+    /// Why the file could not be parsed; the lists are empty then.
+    pub error: Option<String>,
+}
+
+/// An MCP server as one client defines it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ClientMcpServer {
+    pub name: String,
+    /// Command line with arguments, or the url of a remote server.
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub enum ProviderKind {
     Claude,
     Gemini,
@@ -407,12 +1342,12 @@ impl ProviderKind {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProviderUsage {
     pub provider: ProviderKind,
     pub configured: bool,
     pub config_sources: Vec<String>,
-    /// `live`, `local_logs`, `heuristic`, or `unconfigured`.
+    /// `live`, `local_logs`, `heuristic`, `unconfigured`, or `disabled`.
     pub data_source: String,
     /// Unix epoch seconds representing when the source data was last updated.
     pub source_updated_at_epoch_secs: i64,
@@ -420,6 +1355,12 @@ pub struct ProviderUsage {
     pub total_input_tokens: u64,
     pub total_output_tokens: u64,
     pub estimated_cost_usd: f64,
+    /// Spend projected to the end of the month from the cost history.
+    #[serde(default)]
+    pub forecast_usd: Option<f64>,
+    /// This provider's `[providers] budgets` entry.
+    #[serde(default)]
+    pub budget_usd: Option<f64>,
     pub notes: Vec<String>,
 }
 
@@ -452,6 +1393,8 @@ mod tests {
             total_input_tokens: 0,
             total_output_tokens: 0,
             estimated_cost_usd: 12.5,
+            forecast_usd: None,
+            budget_usd: None,
             notes: vec![],
         });
         s.providers.push(ProviderUsage {
@@ -464,11 +1407,31 @@ mod tests {
             total_input_tokens: 0,
             total_output_tokens: 0,
             estimated_cost_usd: 7.5,
+            forecast_usd: None,
+            budget_usd: None,
             notes: vec![],
         });
         assert_eq!(s.total_estimated_cost_usd(), 20.0);
     }
 
+    #[test]
+    fn older_snapshots_still_parse_and_the_schema_lists_every_field() {
+        let old: DashboardSnapshot = serde_json::from_str(
+            r#"{"generated_at_epoch_secs": 5, "overview": {"total_repos": 2}, "alerts": []}"#,
+        )
+        .unwrap();
+        assert_eq!(old.schema_version, 0);
+        assert_eq!(old.overview.total_repos, 2);
+        assert!(old.collector_errors.is_empty());
+
+        let schema = DashboardSnapshot::json_schema();
+        let fields = schema["properties"].as_object().unwrap();
+        let serialized = serde_json::to_value(DashboardSnapshot::default()).unwrap();
+        for key in serialized.as_object().unwrap().keys() {
+            assert!(fields.contains_key(key), "schema is missing {key}");
+        }
+    }
+
     #[test]
     fn action_command_preview_is_derived() {
         let action = ActionCommand::new(
@@ -479,6 +1442,14 @@ mod tests {
         );
         assert_eq!(action.label, "pull");
         assert!(action.command.contains("pull --rebase"));
+
+        let switch = ActionKind::GitSwitch {
+            repo_path: "/tmp/repo".to_string(),
+            branch: "main".to_string(),
+            autostash: true,
+        };
+        // Each step of a chained preview runs in the repo, wherever it is pasted.
+        assert_eq!(switch.preview().matches("git -C \"/tmp/repo\" ").count(), 3);
     }
 
     #[test]
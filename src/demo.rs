@@ -0,0 +1,485 @@
+//! Synthetic workspace for `agentpulse --demo`: a fixed set of repos and a
+//! dashboard snapshot built from them without scanning, network, or writes.
+
+use crate::collectors::activity::window_start;
+use crate::collectors::{
+    collect_git_alerts, collect_license_alerts, collect_local_dep_alerts, collect_repo_rows,
+    CollectorOutput,
+};
+use crate::dashboard::builder::build_snapshot;
+use crate::dashboard::{
+    ActionCommand, ActionKind, ActivityDay, AiClientConfig, ClientMcpServer, DashboardAlert,
+    DashboardSnapshot, DependencyHealth, EnvAuditResult, FlaggedDependency, LicenseAudit,
+    LocalDependency, McpServerHealth, NodePackageManager, ProviderKind, ProviderUsage, RepoProcess,
+    RepoWeekStats, WorktreeRow,
+};
+use crate::git::{ChangeCounts, PushDivergence, Repo, RepoStatus};
+use chrono::{Datelike, Local};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Root the demo repos pretend to live under.
+pub const ROOT: &str = "/home/demo/code";
+
+fn path(name: &str) -> String {
+    format!("{}/{}", ROOT, name)
+}
+
+fn repo(name: &str, status: RepoStatus) -> Repo {
+    let mut repo = Repo::new(PathBuf::from(path(name)));
+    repo.status = status;
+    repo.last_checked = Some(Local::now());
+    repo
+}
+
+fn status(branch: &str) -> RepoStatus {
+    RepoStatus {
+        branch: branch.to_string(),
+        has_remote: true,
+        ..RepoStatus::default()
+    }
+}
+
+/// One repo per state worth showing off, from detached HEAD to clean.
+pub fn repos() -> Vec<Repo> {
+    vec![
+        repo(
+            "payments-api",
+            RepoStatus {
+                uncommitted_count: 7,
+                changes: ChangeCounts {
+                    staged: 2,
+                    unstaged: 4,
+                    untracked: 1,
+                    conflicted: 0,
+                },
+                behind_count: 3,
+                ..status("main")
+            },
+        ),
+        repo(
+            "web-app",
+            RepoStatus {
+                uncommitted_count: 2,
+                changes: ChangeCounts {
+                    unstaged: 2,
+                    ..ChangeCounts::default()
+                },
+                unpushed_count: 5,
+                ..status("feature/checkout")
+            },
+        ),
+        repo(
+            "mobile",
+            RepoStatus {
+                is_detached: true,
+                ..status("HEAD")
+            },
+        ),
+        repo(
+            "design-system",
+            RepoStatus {
+                unpushed_count: 2,
+                push: Some(PushDivergence {
+                    target: "fork/tokens-v2".to_string(),
+                    upstream_ahead: 4,
+                    push_behind: 0,
+                }),
+                ..status("tokens-v2")
+            },
+        ),
+        repo(
+            "infra",
+            RepoStatus {
+                behind_count: 12,
+                ..status("main")
+            },
+        ),
+        repo(
+            "ml-pipeline",
+            RepoStatus {
+                unpushed_count: 1,
+                missing_upstream: true,
+                ..status("exp/feature-store")
+            },
+        ),
+        repo(
+            "docs",
+            RepoStatus {
+                stash_count: 2,
+                ..status("main")
+            },
+        ),
+        repo(
+            "scratch",
+            RepoStatus {
+                uncommitted_count: 1,
+                changes: ChangeCounts {
+                    untracked: 1,
+                    ..ChangeCounts::default()
+                },
+                has_remote: false,
+                ..status("main")
+            },
+        ),
+        repo("cli", status("main")),
+        repo("shared-utils", status("main")),
+    ]
+}
+
+/// A full snapshot for `repos`, assembled the same way a real scan is.
+pub fn snapshot(repos: &[Repo]) -> DashboardSnapshot {
+    let repo_rows = collect_repo_rows(repos).unwrap_or_default();
+    let worktrees = worktrees();
+    let local_deps = local_deps(repos);
+    let licenses = licenses();
+    let mut alerts = collect_git_alerts(repos, &repo_rows, &worktrees);
+    alerts.extend(collect_local_dep_alerts(repos, &local_deps));
+    alerts.extend(collect_license_alerts(&licenses));
+    alerts.push(DashboardAlert {
+        severity: "warn".to_string(),
+        title: "payments-api has no CHANGELOG entry for 14 commits".to_string(),
+        detail: "last release v2.3.0 · 14 commits since".to_string(),
+        repo: Some("payments-api".to_string()),
+        action: Some(ActionCommand::new(
+            "draft changelog",
+            ActionKind::DraftChangelog {
+                repo_path: path("payments-api"),
+            },
+        )),
+    });
+
+    build_snapshot(
+        repos,
+        CollectorOutput {
+            alerts,
+            repos: repo_rows,
+            worktrees,
+            processes: processes(),
+            dependencies: dependencies(),
+            local_deps,
+            env_audit: env_audit(),
+            licenses,
+            mcp_servers: mcp_servers(),
+            ai_clients: ai_clients(),
+            providers: providers(),
+            host_probes: Vec::new(),
+            activity: activity(repos),
+            this_week: this_week(),
+            tickets: Vec::new(),
+            pull_requests: Vec::new(),
+            errors: Vec::new(),
+        },
+    )
+}
+
+fn worktrees() -> Vec<WorktreeRow> {
+    [
+        ("web-app", "web-app", "feature/checkout"),
+        ("web-app", "web-app-hotfix", "hotfix/cart-total"),
+        ("payments-api", "payments-api", "main"),
+        ("payments-api", "payments-api-review", "review/pr-482"),
+    ]
+    .into_iter()
+    .map(|(repo, dir, branch)| WorktreeRow {
+        repo: repo.to_string(),
+        path: path(dir),
+        branch: branch.to_string(),
+        detached: false,
+        bare: false,
+        action: Some(ActionCommand::new(
+            "list worktrees",
+            ActionKind::GitWorktreeList {
+                repo_path: path(repo),
+            },
+        )),
+    })
+    .collect()
+}
+
+fn processes() -> Vec<RepoProcess> {
+    [
+        (
+            "web-app",
+            48213,
+            "02:14:09",
+            "node node_modules/.bin/vite --port 5173",
+        ),
+        ("payments-api", 48377, "01:02:41", "cargo watch -x run"),
+        ("ml-pipeline", 51902, "3-04:11:20", "python -m jupyter lab"),
+    ]
+    .into_iter()
+    .map(|(repo, pid, elapsed, command)| RepoProcess {
+        repo: repo.to_string(),
+        pid,
+        elapsed: elapsed.to_string(),
+        command: command.to_string(),
+        action: Some(ActionCommand::new(
+            "kill process",
+            ActionKind::KillProcess { pid },
+        )),
+    })
+    .collect()
+}
+
+fn dependencies() -> Vec<DependencyHealth> {
+    vec![
+        DependencyHealth {
+            repo: "web-app".to_string(),
+            path: path("web-app"),
+            ecosystems: vec!["node".to_string()],
+            issue_count: 1,
+            issues: vec!["package.json is newer than pnpm-lock.yaml".to_string()],
+            action: Some(ActionCommand::new(
+                "refresh lockfile (pnpm)",
+                ActionKind::NpmInstallLockfile {
+                    repo_path: path("web-app"),
+                    manager: NodePackageManager::Pnpm,
+                },
+            )),
+        },
+        DependencyHealth {
+            repo: "ml-pipeline".to_string(),
+            path: path("ml-pipeline"),
+            ecosystems: vec!["python".to_string()],
+            issue_count: 1,
+            issues: vec![".venv uses Python 3.11 but .python-version pins 3.12".to_string()],
+            action: Some(ActionCommand::new(
+                "sync .venv",
+                ActionKind::UvSync {
+                    repo_path: path("ml-pipeline"),
+                },
+            )),
+        },
+        DependencyHealth {
+            repo: "payments-api".to_string(),
+            path: path("payments-api"),
+            ecosystems: vec!["rust".to_string()],
+            issue_count: 0,
+            issues: Vec::new(),
+            action: None,
+        },
+    ]
+}
+
+fn local_deps(repos: &[Repo]) -> Vec<LocalDependency> {
+    let find = |name: &str| repos.iter().find(|r| r.name == name);
+    [
+        ("web-app", "design-system", "@acme/tokens", "npm"),
+        ("payments-api", "shared-utils", "acme-utils", "cargo"),
+    ]
+    .into_iter()
+    .filter_map(|(dependent, dependency, package, kind)| {
+        let dep = find(dependency)?;
+        Some(LocalDependency {
+            dependent: dependent.to_string(),
+            dependent_path: path(dependent),
+            dependency: dependency.to_string(),
+            dependency_path: path(dependency),
+            package: package.to_string(),
+            kind: kind.to_string(),
+            dependency_dirty: dep.status.uncommitted_count,
+            dependency_unpushed: dep.status.unpushed_count,
+        })
+    })
+    .collect()
+}
+
+fn env_audit() -> Vec<EnvAuditResult> {
+    vec![
+        EnvAuditResult {
+            repo: "payments-api".to_string(),
+            path: path("payments-api"),
+            env_files: vec![".env".to_string(), ".env.example".to_string()],
+            missing_keys: vec!["STRIPE_WEBHOOK_SECRET".to_string()],
+            extra_keys: Vec::new(),
+            tracked_secret_files: Vec::new(),
+            action: None,
+        },
+        EnvAuditResult {
+            repo: "scratch".to_string(),
+            path: path("scratch"),
+            env_files: vec![".env".to_string()],
+            missing_keys: Vec::new(),
+            extra_keys: Vec::new(),
+            tracked_secret_files: vec![".env".to_string()],
+            action: Some(ActionCommand::new(
+                "ignore env files",
+                ActionKind::IgnoreEnvFiles {
+                    repo_path: path("scratch"),
+                    files: vec![".env".to_string()],
+                },
+            )),
+        },
+    ]
+}
+
+fn licenses() -> Vec<LicenseAudit> {
+    vec![
+        LicenseAudit {
+            repo: "payments-api".to_string(),
+            path: path("payments-api"),
+            declared: None,
+            commercial: true,
+            dependencies_scanned: 214,
+            unknown_licenses: 2,
+            flagged: vec![FlaggedDependency {
+                package: "libgit-sys".to_string(),
+                license: "GPL-3.0-only".to_string(),
+                copyleft: true,
+            }],
+        },
+        LicenseAudit {
+            repo: "web-app".to_string(),
+            path: path("web-app"),
+            declared: Some("MIT".to_string()),
+            commercial: false,
+            dependencies_scanned: 1_187,
+            unknown_licenses: 0,
+            flagged: Vec::new(),
+        },
+    ]
+}
+
+fn mcp_servers() -> Vec<McpServerHealth> {
+    vec![
+        McpServerHealth {
+            source: "~/.cursor/mcp.json".to_string(),
+            server_name: "github".to_string(),
+            command: "npx -y @modelcontextprotocol/server-github".to_string(),
+            healthy: true,
+            detail: "npx found in PATH".to_string(),
+            action: None,
+            lint: Vec::new(),
+        },
+        McpServerHealth {
+            source: "~/.cursor/mcp.json".to_string(),
+            server_name: "postgres".to_string(),
+            command: "mcp-postgres --dsn $DATABASE_URL".to_string(),
+            healthy: false,
+            detail: "mcp-postgres not found in PATH".to_string(),
+            action: Some(ActionCommand::new(
+                "check binary",
+                ActionKind::CheckBinaryInPath {
+                    binary: "mcp-postgres".to_string(),
+                },
+            )),
+            lint: Vec::new(),
+        },
+    ]
+}
+
+fn ai_clients() -> Vec<AiClientConfig> {
+    let server = |name: &str, command: &str| ClientMcpServer {
+        name: name.to_string(),
+        command: command.to_string(),
+    };
+    let github = "npx -y @modelcontextprotocol/server-github";
+    vec![
+        AiClientConfig {
+            client: "Cursor".to_string(),
+            path: "~/.cursor/mcp.json".to_string(),
+            servers: vec![
+                server("github", github),
+                server("postgres", "mcp-postgres --dsn $DATABASE_URL"),
+            ],
+            ..AiClientConfig::default()
+        },
+        AiClientConfig {
+            client: "Claude Code".to_string(),
+            path: "~/.claude/settings.json".to_string(),
+            servers: vec![server("github", github)],
+            models: vec!["opus".to_string()],
+            permissions: vec!["allow Bash(git:*)".to_string()],
+            drift: vec!["no postgres server; Cursor defines it".to_string()],
+            ..AiClientConfig::default()
+        },
+    ]
+}
+
+fn providers() -> Vec<ProviderUsage> {
+    let now = Local::now().timestamp();
+    let usage = |provider, sessions, input, output, cost| ProviderUsage {
+        provider,
+        configured: true,
+        config_sources: vec!["demo".to_string()],
+        data_source: "local_logs".to_string(),
+        source_updated_at_epoch_secs: now - 600,
+        sessions,
+        total_input_tokens: input,
+        total_output_tokens: output,
+        estimated_cost_usd: cost,
+        forecast_usd: Some(cost * 2.3),
+        budget_usd: None,
+        notes: vec!["synthetic demo data".to_string()],
+    };
+    vec![
+        usage(ProviderKind::Claude, 42, 18_400_000, 1_230_000, 61.84),
+        usage(ProviderKind::OpenAi, 17, 5_200_000, 610_000, 18.27),
+        usage(ProviderKind::Gemini, 6, 2_100_000, 140_000, 3.12),
+    ]
+}
+
+fn this_week() -> Vec<RepoWeekStats> {
+    let stats = |repo: &str, commits, insertions, deletions| RepoWeekStats {
+        repo: repo.to_string(),
+        commits,
+        insertions,
+        deletions,
+    };
+    vec![
+        stats("web-app", 9, 412, 138),
+        stats("payments-api", 5, 187, 64),
+        stats("design-system", 2, 36, 12),
+    ]
+}
+
+/// Weekday commits spread over the first few repos.
+fn activity(repos: &[Repo]) -> Vec<ActivityDay> {
+    let today = Local::now().date_naive();
+    let names: Vec<&str> = repos.iter().take(4).map(|r| r.name.as_str()).collect();
+    window_start(today)
+        .iter_days()
+        .take_while(|date| *date <= today)
+        .filter(|date| date.weekday().num_days_from_monday() < 5)
+        .map(|date| {
+            let seed = date.ordinal() as usize;
+            let repos: BTreeMap<String, usize> = names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| (name.to_string(), (seed * (i + 3) + i) % 4))
+                .filter(|(_, commits)| *commits > 0)
+                .collect();
+            ActivityDay {
+                date: date.format("%Y-%m-%d").to_string(),
+                commits: repos.values().sum(),
+                repos,
+            }
+        })
+        .filter(|day| day.commits > 0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_section_has_data() {
+        let repos = repos();
+        let snapshot = snapshot(&repos);
+        assert_eq!(snapshot.repos.len(), repos.len());
+        assert!(snapshot.overview.actionable_repos > 0);
+        assert!(!snapshot.alerts.is_empty());
+        assert!(!snapshot.worktrees.is_empty());
+        assert!(!snapshot.processes.is_empty());
+        assert!(!snapshot.dependencies.is_empty());
+        assert!(!snapshot.local_deps.is_empty());
+        assert!(!snapshot.env_audit.is_empty());
+        assert!(!snapshot.licenses.is_empty());
+        assert!(!snapshot.mcp_servers.is_empty());
+        assert!(snapshot.total_estimated_cost_usd() > 0.0);
+        assert!(snapshot.collector_errors.is_empty());
+        assert!(repos.iter().all(|r| r.path.starts_with(ROOT)));
+    }
+}
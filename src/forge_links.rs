@@ -0,0 +1,200 @@
+//! Web links for repos, branches, and unpushed diffs, derived from git remotes.
+//!
+//! Used by the agent outputs so a reader can jump straight to the forge.
+//! Only GitHub, GitLab, and Bitbucket URL layouts are known; other hosts get
+//! a repo link but no branch/compare links.
+
+use crate::git::{self, Repo};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Forge {
+    GitHub,
+    GitLab,
+    Bitbucket,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoLinks {
+    pub web_url: String,
+    pub branch_url: Option<String>,
+    /// Diff of unpushed commits against the upstream branch (meaningful once pushed).
+    pub compare_url: Option<String>,
+}
+
+/// Convert a git remote URL (scp-style, ssh://, https://) to its https web URL.
+pub fn web_url(remote: &str) -> Option<String> {
+    let remote = remote.trim();
+    let (host, path) = if let Some(rest) = remote
+        .strip_prefix("https://")
+        .or_else(|| remote.strip_prefix("http://"))
+        .or_else(|| remote.strip_prefix("ssh://"))
+        .or_else(|| remote.strip_prefix("git://"))
+    {
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        // Drop an explicit port (ssh://git@host:2222/...).
+        let host = host.split(':').next()?;
+        (host, path)
+    } else if let Some((user_host, path)) = remote.split_once(':') {
+        // scp-like: git@github.com:owner/repo.git
+        // A slash before the colon, or a single letter (`C:\...`), means a local path.
+        if user_host.contains('/') || user_host.len() == 1 {
+            return None;
+        }
+        let host = user_host.rsplit('@').next()?;
+        (host, path)
+    } else {
+        return None;
+    };
+
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+    if host.is_empty() || path.is_empty() {
+        return None;
+    }
+    Some(format!("https://{}/{}", host, path))
+}
+
+fn forge_for(web_url: &str) -> Forge {
+    let host = web_url
+        .strip_prefix("https://")
+        .and_then(|rest| rest.split('/').next())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if host.contains("github") {
+        Forge::GitHub
+    } else if host.contains("gitlab") {
+        Forge::GitLab
+    } else if host.contains("bitbucket") {
+        Forge::Bitbucket
+    } else {
+        Forge::Other
+    }
+}
+
+/// Build links for a branch. `compare` is `(upstream_branch, head_sha)` when a
+/// diff link for unpushed commits is wanted.
+pub fn build_links(
+    remote: &str,
+    branch: Option<&str>,
+    compare: Option<(&str, &str)>,
+) -> Option<RepoLinks> {
+    let web = web_url(remote)?;
+    let forge = forge_for(&web);
+    let branch_url = branch.and_then(|b| match forge {
+        Forge::GitHub => Some(format!("{}/tree/{}", web, b)),
+        Forge::GitLab => Some(format!("{}/-/tree/{}", web, b)),
+        Forge::Bitbucket => Some(format!("{}/src/{}", web, b)),
+        Forge::Other => None,
+    });
+    let compare_url = compare.and_then(|(base, head)| match forge {
+        Forge::GitHub => Some(format!("{}/compare/{}...{}", web, base, head)),
+        Forge::GitLab => Some(format!("{}/-/compare/{}...{}", web, base, head)),
+        Forge::Bitbucket | Forge::Other => None,
+    });
+    Some(RepoLinks {
+        web_url: web,
+        branch_url,
+        compare_url,
+    })
+}
+
+/// Resolve links for every repo with a remote. With `diff_links`, repos with
+/// unpushed commits also get a compare link.
+pub async fn collect_links(repos: &[Repo], diff_links: bool) -> HashMap<PathBuf, RepoLinks> {
+    let mut links = HashMap::new();
+    for repo in repos.iter().filter(|r| r.status.has_remote) {
+        let upstream = git::get_upstream(&repo.path).await;
+        let remote_name = upstream
+            .as_ref()
+            .map(|(remote, _)| remote.as_str())
+            .unwrap_or("origin");
+        let Some(remote_url) = git::get_remote_url(&repo.path, remote_name).await else {
+            continue;
+        };
+        let branch = (!repo.status.is_detached).then_some(repo.status.branch.as_str());
+        let head = if diff_links && repo.status.unpushed_count > 0 {
+            git::get_head_sha(&repo.path).await
+        } else {
+            None
+        };
+        let compare = upstream
+            .as_ref()
+            .zip(head.as_deref())
+            .map(|((_, base), head)| (base.as_str(), head));
+        if let Some(l) = build_links(&remote_url, branch, compare) {
+            links.insert(repo.path.clone(), l);
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn web_url_from_common_remote_forms() {
+        let expected = Some("https://github.com/me/app".to_string());
+        assert_eq!(web_url("git@github.com:me/app.git"), expected);
+        assert_eq!(web_url("https://github.com/me/app.git"), expected);
+        assert_eq!(web_url("https://token@github.com/me/app"), expected);
+        assert_eq!(web_url("ssh://git@github.com:22/me/app.git"), expected);
+        assert_eq!(
+            web_url("git@gitlab.com:group/sub/app.git"),
+            Some("https://gitlab.com/group/sub/app".to_string())
+        );
+    }
+
+    #[test]
+    fn web_url_rejects_local_paths() {
+        assert_eq!(web_url("/srv/git/app.git"), None);
+        assert_eq!(web_url("../app"), None);
+        assert_eq!(web_url("C:\\src\\app"), None);
+        assert_eq!(web_url(""), None);
+    }
+
+    #[test]
+    fn github_branch_and_compare_links() {
+        let links = build_links(
+            "git@github.com:me/app.git",
+            Some("feat/x"),
+            Some(("main", "abc123")),
+        )
+        .unwrap();
+        assert_eq!(links.web_url, "https://github.com/me/app");
+        assert_eq!(
+            links.branch_url.as_deref(),
+            Some("https://github.com/me/app/tree/feat/x")
+        );
+        assert_eq!(
+            links.compare_url.as_deref(),
+            Some("https://github.com/me/app/compare/main...abc123")
+        );
+    }
+
+    #[test]
+    fn gitlab_uses_dash_routes() {
+        let links = build_links("https://gitlab.com/g/app.git", Some("dev"), None).unwrap();
+        assert_eq!(
+            links.branch_url.as_deref(),
+            Some("https://gitlab.com/g/app/-/tree/dev")
+        );
+        assert!(links.compare_url.is_none());
+    }
+
+    #[test]
+    fn unknown_host_gets_repo_link_only() {
+        let links = build_links(
+            "git@git.example.com:team/app.git",
+            Some("main"),
+            Some(("main", "abc")),
+        )
+        .unwrap();
+        assert_eq!(links.web_url, "https://git.example.com/team/app");
+        assert!(links.branch_url.is_none());
+        assert!(links.compare_url.is_none());
+    }
+}
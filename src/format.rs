@@ -0,0 +1,386 @@
+//! Display formatting for numbers, currency, timestamps, and column text.
+//!
+//! Every user-visible number, cost, or time goes through here so the
+//! `[display]` config section applies uniformly across the TUI and CLI output.
+//! Column text is measured in terminal cells, not chars, so CJK and emoji
+//! (two cells each) keep tables aligned.
+
+use crate::config::{Config, DisplayConfig};
+use crate::ui::theme;
+use chrono::{DateTime, Local, TimeZone};
+use std::path::{Path, PathBuf};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Format an integer with the configured thousands separator (e.g. `1,234,567`).
+pub fn number(cfg: &DisplayConfig, n: u64) -> String {
+    group_thousands(&n.to_string(), &cfg.thousands_separator)
+}
+
+/// Format a USD amount converted by `currency_rate` and prefixed with `currency_symbol`.
+pub fn currency(cfg: &DisplayConfig, usd: f64) -> String {
+    let amount = usd * cfg.currency_rate;
+    let fixed = format!("{:.2}", amount.abs());
+    let (int_part, frac_part) = fixed.split_once('.').unwrap_or((&fixed, "00"));
+    let sign = if amount < 0.0 && fixed != "0.00" {
+        "-"
+    } else {
+        ""
+    };
+    format!(
+        "{}{}{}{}{}",
+        sign,
+        cfg.currency_symbol,
+        group_thousands(int_part, &cfg.thousands_separator),
+        cfg.decimal_separator,
+        frac_part
+    )
+}
+
+/// A change in spend, always signed (`+$2.50`, `-€1,00`).
+pub fn currency_delta(cfg: &DisplayConfig, usd: f64) -> String {
+    let amount = currency(cfg, usd);
+    if amount.starts_with('-') {
+        amount
+    } else {
+        format!("+{}", amount)
+    }
+}
+
+/// Wall-clock time of day, honouring `clock_24h` (`14:05` vs `2:05 PM`).
+pub fn clock<Tz: TimeZone>(cfg: &DisplayConfig, t: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let pattern = if cfg.clock_24h { "%H:%M" } else { "%-I:%M %p" };
+    t.format(pattern).to_string()
+}
+
+/// Calendar date using the configured `date_format`.
+pub fn date<Tz: TimeZone>(cfg: &DisplayConfig, t: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    cfg.date(t)
+}
+
+/// Date followed by clock time, e.g. `2024-05-01 14:05`.
+pub fn date_time<Tz: TimeZone>(cfg: &DisplayConfig, t: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    format!("{} {}", date(cfg, t), clock(cfg, t))
+}
+
+/// Compact relative age for a duration in seconds (`42s`, `5m`, `3h`, `2d`).
+pub fn age(secs: i64) -> String {
+    let secs = secs.max(0);
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else if secs < 86_400 {
+        format!("{}h", secs / 3600)
+    } else {
+        format!("{}d", secs / 86_400)
+    }
+}
+
+/// Compact elapsed time for a measurement in milliseconds (`850ms`, `1.2s`).
+pub fn millis(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    }
+}
+
+/// Relative age of a unix timestamp (`5m ago`), or `unknown` when unset.
+pub fn ago_epoch(epoch_secs: i64) -> String {
+    if epoch_secs <= 0 {
+        return "unknown".to_string();
+    }
+    let now = chrono::Utc::now().timestamp();
+    format!("{} ago", age(now.saturating_sub(epoch_secs)))
+}
+
+/// Relative age of a local timestamp (`12s ago`).
+pub fn ago(t: &DateTime<Local>) -> String {
+    let secs = Local::now().signed_duration_since(*t).num_seconds();
+    format!("{} ago", age(secs))
+}
+
+/// A repo or worktree path as `[display] path_style` asks: `full`, `home`
+/// (`~/work/client/api`), `relative` to its watch directory (`client/api`)
+/// or `short` (`~/w/c/api`). Machine output keeps the full path.
+pub fn path(cfg: &Config, path: impl AsRef<Path>) -> String {
+    style_path(
+        &cfg.display.path_style,
+        path.as_ref(),
+        &cfg.watch_directories,
+        dirs::home_dir().as_deref(),
+    )
+}
+
+fn style_path(style: &str, path: &Path, roots: &[PathBuf], home: Option<&Path>) -> String {
+    let tilde = || match home.and_then(|home| path.strip_prefix(home).ok()) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    };
+    match style {
+        "home" => tilde(),
+        "relative" => roots
+            .iter()
+            .filter_map(|root| path.strip_prefix(root).ok())
+            .min_by_key(|rest| rest.components().count())
+            .map(|rest| {
+                if rest.as_os_str().is_empty() {
+                    path.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string()
+                } else {
+                    rest.display().to_string()
+                }
+            })
+            .unwrap_or_else(tilde),
+        "short" => {
+            let full = tilde();
+            let parts: Vec<&str> = full.split('/').collect();
+            let last = parts.len().saturating_sub(1);
+            parts
+                .iter()
+                .enumerate()
+                .map(|(i, part)| {
+                    if i == last || part.is_empty() || *part == "~" {
+                        part.to_string()
+                    } else {
+                        // Keep the dot of hidden directories: `.config` -> `.c`.
+                        let keep = if part.starts_with('.') { 2 } else { 1 };
+                        part.chars().take(keep).collect()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/")
+        }
+        _ => path.display().to_string(),
+    }
+}
+
+/// Terminal cells `text` occupies.
+pub fn width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// `text` cut to at most `max` cells, ending in `…` when shortened.
+pub fn truncate(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    let mut out = take_cells(text.chars(), max.saturating_sub(1));
+    if max > 0 {
+        out.push_str(&theme::glyph("…"));
+    }
+    out
+}
+
+/// `text` cut to at most `max` cells by dropping its middle, so both the
+/// root and the last component of a path stay visible (`~/src/…/api`).
+pub fn truncate_middle(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let budget = max - 1;
+    let tail_cells = budget.div_ceil(2);
+    let head = take_cells(text.chars(), budget - tail_cells);
+    let tail: String = take_cells(text.chars().rev(), tail_cells)
+        .chars()
+        .rev()
+        .collect();
+    format!("{}{}{}", head, theme::glyph("…"), tail)
+}
+
+/// `text` truncated to `cells` and padded on the right to exactly `cells`.
+pub fn pad(text: &str, cells: usize) -> String {
+    let text = truncate(text, cells);
+    let fill = cells.saturating_sub(width(&text));
+    format!("{}{}", text, " ".repeat(fill))
+}
+
+/// Like [`pad`], right-aligned.
+pub fn pad_left(text: &str, cells: usize) -> String {
+    let text = truncate(text, cells);
+    let fill = cells.saturating_sub(width(&text));
+    format!("{}{}", " ".repeat(fill), text)
+}
+
+/// Leading chars of `chars` fitting in `cells`; a wide char that would
+/// straddle the limit is left out.
+fn take_cells(chars: impl Iterator<Item = char>, cells: usize) -> String {
+    let mut used = 0;
+    chars
+        .take_while(|c| {
+            used += c.width().unwrap_or(0);
+            used <= cells
+        })
+        .collect()
+}
+
+fn group_thousands(digits: &str, sep: &str) -> String {
+    let mut result = String::with_capacity(digits.len() + digits.len() / 3 * sep.len());
+    let len = digits.chars().count();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (len - i).is_multiple_of(3) {
+            result.push_str(sep);
+        }
+        result.push(c);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_follow_the_configured_style() {
+        let home = Path::new("/home/me");
+        let roots = vec![PathBuf::from("/home/me/work"), PathBuf::from("/srv/git")];
+        let repo = Path::new("/home/me/work/client/api");
+        let style = |name: &str, path: &Path| style_path(name, path, &roots, Some(home));
+        assert_eq!(style("full", repo), "/home/me/work/client/api");
+        assert_eq!(style("home", repo), "~/work/client/api");
+        assert_eq!(style("relative", repo), "client/api");
+        assert_eq!(style("short", repo), "~/w/c/api");
+        assert_eq!(
+            style("short", Path::new("/home/me/.config/tool")),
+            "~/.c/tool"
+        );
+        assert_eq!(style("short", Path::new("/srv/git/web")), "/s/g/web");
+        assert_eq!(style("relative", Path::new("/srv/git")), "git");
+        assert_eq!(style("relative", Path::new("/opt/x")), "/opt/x");
+        assert_eq!(style("home", home), "~");
+    }
+
+    #[test]
+    fn column_text_is_measured_in_cells() {
+        assert_eq!(width("api"), 3);
+        assert_eq!(width("日本語"), 6);
+        assert_eq!(truncate("日本語リポジトリ", 7), "日本語…");
+        assert_eq!(width(&truncate("日本語リポジトリ", 8)), 7);
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(pad("日本", 6), "日本  ");
+        assert_eq!(pad_left("🚀x", 5), "  🚀x");
+        assert_eq!(width(&pad("日本語リポジトリ", 9)), 9);
+        assert_eq!(
+            truncate_middle("~/src/github.com/acme/api-server", 16),
+            "~/src/g…i-server"
+        );
+        assert_eq!(truncate_middle("~/src/api", 20), "~/src/api");
+        assert_eq!(truncate_middle("~/プロジェクト/日本語", 9), "~/プ…本語");
+        assert_eq!(truncate_middle("~/プロジェクト/日本語", 8), "~/…本語");
+    }
+    use chrono::Utc;
+
+    fn us() -> DisplayConfig {
+        DisplayConfig::default()
+    }
+
+    fn eu() -> DisplayConfig {
+        DisplayConfig {
+            clock_24h: true,
+            date_format: "%d.%m.%Y".to_string(),
+            currency_symbol: "€".to_string(),
+            currency_rate: 0.5,
+            thousands_separator: ".".to_string(),
+            decimal_separator: ",".to_string(),
+            ..DisplayConfig::default()
+        }
+    }
+
+    #[test]
+    fn number_groups_thousands() {
+        assert_eq!(number(&us(), 0), "0");
+        assert_eq!(number(&us(), 42), "42");
+        assert_eq!(number(&us(), 999), "999");
+        assert_eq!(number(&us(), 1_000), "1,000");
+        assert_eq!(number(&us(), 12_345), "12,345");
+        assert_eq!(number(&us(), 999_999), "999,999");
+        assert_eq!(number(&us(), 1_234_567), "1,234,567");
+        assert_eq!(number(&us(), 1_000_000_000), "1,000,000,000");
+    }
+
+    #[test]
+    fn number_respects_separator() {
+        assert_eq!(number(&eu(), 1_234_567), "1.234.567");
+        let none = DisplayConfig {
+            thousands_separator: String::new(),
+            ..us()
+        };
+        assert_eq!(number(&none, 1_234_567), "1234567");
+    }
+
+    #[test]
+    fn currency_defaults_to_usd() {
+        assert_eq!(currency(&us(), 0.0), "$0.00");
+        assert_eq!(currency(&us(), 12.345), "$12.35");
+        assert_eq!(currency(&us(), 1234.5), "$1,234.50");
+    }
+
+    #[test]
+    fn currency_applies_rate_and_symbols() {
+        assert_eq!(currency(&eu(), 2469.0), "€1.234,50");
+        assert_eq!(currency(&eu(), -4.0), "-€2,00");
+        assert_eq!(currency_delta(&us(), 2.5), "+$2.50");
+        assert_eq!(currency_delta(&eu(), -4.0), "-€2,00");
+    }
+
+    #[test]
+    fn clock_honours_24h_setting() {
+        let t = Utc.with_ymd_and_hms(2024, 5, 1, 14, 5, 0).unwrap();
+        assert_eq!(clock(&us(), &t), "2:05 PM");
+        assert_eq!(clock(&eu(), &t), "14:05");
+    }
+
+    #[test]
+    fn date_uses_configured_format() {
+        let t = Utc.with_ymd_and_hms(2024, 5, 1, 14, 5, 0).unwrap();
+        assert_eq!(date(&us(), &t), "2024-05-01");
+        assert_eq!(date_time(&eu(), &t), "01.05.2024 14:05");
+    }
+
+    #[test]
+    fn invalid_date_format_falls_back() {
+        let t = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+        let cfg = DisplayConfig {
+            date_format: "%Q".to_string(),
+            ..us()
+        };
+        assert_eq!(date(&cfg, &t), "2024-05-01");
+    }
+
+    #[test]
+    fn age_buckets() {
+        assert_eq!(age(-5), "0s");
+        assert_eq!(age(59), "59s");
+        assert_eq!(age(60), "1m");
+        assert_eq!(age(3_599), "59m");
+        assert_eq!(age(3_600), "1h");
+        assert_eq!(age(86_400), "1d");
+    }
+
+    #[test]
+    fn millis_switches_to_seconds() {
+        assert_eq!(millis(850), "850ms");
+        assert_eq!(millis(1_250), "1.2s");
+    }
+
+    #[test]
+    fn ago_epoch_unknown_when_unset() {
+        assert_eq!(ago_epoch(0), "unknown");
+        assert_eq!(ago_epoch(-1), "unknown");
+    }
+}
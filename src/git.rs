@@ -1,15 +1,26 @@
+use crate::config::{DirtyIgnoreConfig, ProcessConfig};
 use anyhow::Result;
 use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use tokio::process::Command;
+use tokio::sync::Semaphore;
 
 /// The status of a single git repository.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RepoStatus {
     pub branch: String,
     pub uncommitted_count: usize,
-    /// Commits ahead of the upstream (unpushed).
+    /// `uncommitted_count` split into staged, unstaged, untracked and
+    /// conflicted paths.
+    #[serde(default)]
+    pub changes: ChangeCounts,
+    /// Commits not yet on the push destination (unpushed).
     pub unpushed_count: usize,
     /// Commits behind the upstream (need pull).
     pub behind_count: usize,
@@ -18,15 +29,65 @@ pub struct RepoStatus {
     pub is_detached: bool,
     /// Non-fatal probe errors captured while collecting repo status.
     pub probe_errors: Vec<String>,
+    /// Set when pushes go to a different remote than the upstream
+    /// (triangular workflow); `unpushed_count` then tracks the push side.
+    pub push: Option<PushDivergence>,
+    /// The branch has no upstream yet (e.g. right after `git switch -c`), so a
+    /// plain `git push` would fail; `unpushed_count` counts commits on no remote.
+    pub missing_upstream: bool,
+    /// Unix time the repo was first seen continuously dirty, from the scan
+    /// history; unset when unknown or clean.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dirty_since: Option<i64>,
+    /// Same for having unpushed commits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unpushed_since: Option<i64>,
+    /// HEAD's sha; unset before the first commit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head_sha: Option<String>,
+}
+
+impl RepoStatus {
+    /// When the oldest local-only work (uncommitted or unpushed) started.
+    pub fn work_since(&self) -> Option<i64> {
+        self.dirty_since
+            .into_iter()
+            .chain(self.unpushed_since)
+            .min()
+    }
+
+    /// Remote to publish a branch without upstream to: the configured push
+    /// remote, else `origin`.
+    pub fn publish_remote(&self) -> &str {
+        self.push
+            .as_ref()
+            .and_then(|p| p.target.split_once('/'))
+            .map_or("origin", |(remote, _)| remote)
+    }
+}
+
+/// Fetch/push deltas for a branch whose push destination is not its upstream.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PushDivergence {
+    /// Push destination as `remote/branch`, e.g. `fork/feature`.
+    pub target: String,
+    /// Commits ahead of the upstream (not yet merged on the fetch side).
+    pub upstream_ahead: usize,
+    /// Commits on the push destination that are missing locally.
+    pub push_behind: usize,
 }
 
 /// A discovered git repository with its current status.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Repo {
     pub path: PathBuf,
     pub name: String,
     pub status: RepoStatus,
+    /// Local to this process; not shared with attached instances.
+    #[serde(skip)]
     pub last_checked: Option<DateTime<Local>>,
+    /// Stable identity that survives moves/renames (see `get_repo_identity`).
+    pub identity: Option<String>,
 }
 
 impl Repo {
@@ -41,6 +102,7 @@ impl Repo {
             name,
             status: RepoStatus::default(),
             last_checked: None,
+            identity: None,
         }
     }
 
@@ -90,60 +152,605 @@ pub enum StatusColor {
 
 const TIMEOUT: Duration = Duration::from_secs(5);
 
+// ─── scan-time process limits ───────────────────────────────────────────────
+
+struct ProcessLimits {
+    nice: i32,
+    idle_io: bool,
+    max_processes: usize,
+    slots: Arc<Semaphore>,
+}
+
+static PROCESS_LIMITS: OnceLock<Mutex<ProcessLimits>> = OnceLock::new();
+static PRIORITY_TOOLS: OnceLock<(bool, bool)> = OnceLock::new();
+/// Git commands built for scans and collectors since startup.
+static GIT_SPAWNS: AtomicU64 = AtomicU64::new(0);
+
+/// Running total of scan/collector git processes, for the Stats section.
+pub fn git_spawn_count() -> u64 {
+    GIT_SPAWNS.load(Ordering::Relaxed)
+}
+
+fn process_limits() -> &'static Mutex<ProcessLimits> {
+    PROCESS_LIMITS.get_or_init(|| {
+        let defaults = ProcessConfig::default();
+        Mutex::new(ProcessLimits {
+            nice: defaults.git_nice,
+            idle_io: defaults.git_idle_io,
+            max_processes: defaults.max_git_processes,
+            slots: Arc::new(Semaphore::new(defaults.max_git_processes.max(1))),
+        })
+    })
+}
+
+/// Apply `[processes]` config to subsequent scan-time git calls.
+pub fn configure_process_limits(cfg: &ProcessConfig) {
+    if let Ok(mut limits) = process_limits().lock() {
+        limits.nice = cfg.git_nice;
+        limits.idle_io = cfg.git_idle_io;
+        if limits.max_processes != cfg.max_git_processes {
+            // In-flight permits keep the old semaphore alive until they finish.
+            limits.max_processes = cfg.max_git_processes;
+            limits.slots = Arc::new(Semaphore::new(cfg.max_git_processes.max(1)));
+        }
+    }
+}
+
+/// Program + leading args that run git at the configured priority,
+/// e.g. `["ionice", "-c3", "nice", "-n", "10", "git"]`.
+fn priority_argv(nice: i32, idle_io: bool, has_nice: bool, has_ionice: bool) -> Vec<String> {
+    let mut argv = Vec::new();
+    if cfg!(unix) {
+        if idle_io && has_ionice {
+            argv.extend(["ionice".to_string(), "-c3".to_string()]);
+        }
+        if nice != 0 && has_nice {
+            argv.extend(["nice".to_string(), "-n".to_string(), nice.to_string()]);
+        }
+    }
+    argv.push("git".to_string());
+    argv
+}
+
+fn current_priority_argv() -> Vec<String> {
+    GIT_SPAWNS.fetch_add(1, Ordering::Relaxed);
+    let (nice, idle_io) = process_limits()
+        .lock()
+        .map(|l| (l.nice, l.idle_io))
+        .unwrap_or((0, false));
+    let &(has_nice, has_ionice) = PRIORITY_TOOLS.get_or_init(|| {
+        (
+            crate::path_utils::resolve_binary_in_path("nice").is_some(),
+            crate::path_utils::resolve_binary_in_path("ionice").is_some(),
+        )
+    });
+    priority_argv(nice, idle_io, has_nice, has_ionice)
+}
+
+#[cfg(windows)]
+const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x0000_4000;
+
+#[cfg(windows)]
+fn below_normal_priority() -> bool {
+    process_limits().lock().map(|l| l.nice > 0).unwrap_or(false)
+}
+
+/// Async git command for scan-time probes, honouring `[processes]` priority.
+/// Probes never refresh `.git/index`: that would race the user's own git
+/// commands and wake `watch_mode` watchers on every scan.
+fn git_command(args: &[&str]) -> Command {
+    let argv = current_priority_argv();
+    let mut cmd = Command::new(&argv[0]);
+    cmd.args(&argv[1..])
+        .args(args)
+        .env("GIT_OPTIONAL_LOCKS", "0");
+    #[cfg(windows)]
+    if below_normal_priority() {
+        cmd.creation_flags(BELOW_NORMAL_PRIORITY_CLASS);
+    }
+    cmd
+}
+
+/// Blocking git command for collectors, honouring `[processes]` priority.
+/// Like [`git_command`], it never takes optional locks.
+pub fn std_git_command(args: &[&str]) -> std::process::Command {
+    let argv = current_priority_argv();
+    let mut cmd = std::process::Command::new(&argv[0]);
+    cmd.args(&argv[1..])
+        .args(args)
+        .env("GIT_OPTIONAL_LOCKS", "0");
+    #[cfg(windows)]
+    if below_normal_priority() {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(BELOW_NORMAL_PRIORITY_CLASS);
+    }
+    cmd
+}
+
+/// Wait for a free git process slot (bounded by `max_git_processes`).
+async fn acquire_git_slot() -> Option<tokio::sync::OwnedSemaphorePermit> {
+    let slots = process_limits().lock().ok()?.slots.clone();
+    slots.acquire_owned().await.ok()
+}
+
 async fn run_git(repo_path: &Path, args: &[&str]) -> Result<String> {
-    let output = tokio::time::timeout(
-        TIMEOUT,
-        Command::new("git")
-            .args(args)
-            .current_dir(repo_path)
-            .output(),
-    )
-    .await??;
+    let _slot = acquire_git_slot().await;
+    let output =
+        tokio::time::timeout(TIMEOUT, git_command(args).current_dir(repo_path).output()).await??;
     Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
-pub async fn get_branch(repo_path: &Path) -> Result<(String, bool)> {
-    let raw = run_git(repo_path, &["rev-parse", "--abbrev-ref", "HEAD"]).await?;
-    let branch = raw.trim().to_string();
-    let is_detached = branch == "HEAD";
-    Ok((branch, is_detached))
+/// Like [`run_git`], but a non-zero exit is an error carrying git's stderr.
+async fn run_git_strict(repo_path: &Path, args: &[&str]) -> Result<String> {
+    let _slot = acquire_git_slot().await;
+    let output =
+        tokio::time::timeout(TIMEOUT, git_command(args).current_dir(repo_path).output()).await??;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
-pub async fn get_uncommitted_count(repo_path: &Path) -> Result<usize> {
-    let raw = run_git(repo_path, &["status", "--porcelain"]).await?;
-    Ok(raw.lines().filter(|l| !l.trim().is_empty()).count())
+/// Changed paths by kind, from `git status --porcelain=v2`. A file staged
+/// and then edited again counts as both staged and unstaged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ChangeCounts {
+    pub staged: usize,
+    pub unstaged: usize,
+    pub untracked: usize,
+    /// Unmerged paths left by a conflicted merge, rebase or stash pop.
+    pub conflicted: usize,
+}
+
+/// One `git status --porcelain=v2 --branch`: the branch header and the
+/// changed paths.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorktreeStatus {
+    /// `None` when detached.
+    pub head: Option<String>,
+    /// HEAD's sha; `None` before the first commit.
+    pub oid: Option<String>,
+    /// `remote/branch`; `None` without an upstream or when its
+    /// remote-tracking ref is gone.
+    pub upstream: Option<String>,
+    /// `(ahead, behind)` of the upstream; `None` under `--no-ahead-behind`.
+    pub ahead_behind: Option<(usize, usize)>,
+    /// Stash entries, with `--show-stash`.
+    pub stash: usize,
+    /// Changed paths, untracked included.
+    pub entries: usize,
+    pub changes: ChangeCounts,
+}
+
+/// Branch, upstream ahead/behind and the changed paths outside the excluded
+/// `pathspecs`, in one call.
+pub async fn get_worktree_status(repo_path: &Path, pathspecs: &[String]) -> Result<WorktreeStatus> {
+    let args = with_dirty_pathspecs(&["status", "--porcelain=v2", "--branch"], pathspecs);
+    let raw = run_git_strict(repo_path, &args).await?;
+    Ok(parse_status_v2(&raw))
 }
 
-/// Returns `(ahead, behind, has_remote)`.
-pub async fn get_remote_counts(repo_path: &Path) -> Result<(usize, usize, bool)> {
-    let remote_raw = run_git(repo_path, &["remote"]).await?;
-    let has_remote = !remote_raw.trim().is_empty();
-    if !has_remote {
-        return Ok((0, 0, false));
+fn parse_status_v2(raw: &str) -> WorktreeStatus {
+    let mut status = WorktreeStatus::default();
+    // Only trusted once `branch.ab` shows the remote-tracking ref exists.
+    let mut upstream = None;
+    for line in raw.lines() {
+        let Some(header) = line.strip_prefix("# ") else {
+            count_entry(line, &mut status);
+            continue;
+        };
+        match header.split_once(' ') {
+            Some(("branch.oid", oid)) if oid != "(initial)" => status.oid = Some(oid.to_string()),
+            Some(("branch.head", head)) if head != "(detached)" => {
+                status.head = Some(head.to_string())
+            }
+            Some(("branch.upstream", name)) => upstream = Some(name.to_string()),
+            Some(("branch.ab", ab)) => {
+                status.upstream = upstream.take();
+                status.ahead_behind = ab.split_once(' ').and_then(|(ahead, behind)| {
+                    Some((
+                        ahead.strip_prefix('+')?.parse().ok()?,
+                        behind.strip_prefix('-')?.parse().ok()?,
+                    ))
+                });
+            }
+            Some(("stash", count)) => status.stash = count.parse().unwrap_or(0),
+            _ => {}
+        }
     }
+    status
+}
 
-    let parse_count = |args: &'static [&'static str], path: PathBuf| async move {
-        let result = tokio::time::timeout(
-            TIMEOUT,
-            Command::new("git").args(args).current_dir(&path).output(),
-        )
-        .await;
-        match result {
-            Ok(Ok(o)) if o.status.success() => String::from_utf8_lossy(&o.stdout)
-                .trim()
-                .parse::<usize>()
-                .unwrap_or(0),
-            _ => 0,
+/// `1 XY …` (changed), `2 XY …` (renamed or copied), `u XY …` (unmerged),
+/// `? path` (untracked); X is the index side, Y the worktree side.
+fn count_entry(line: &str, status: &mut WorktreeStatus) {
+    let changes = &mut status.changes;
+    match line.split_once(' ') {
+        Some(("1" | "2", rest)) => {
+            let mut xy = rest.chars();
+            changes.staged += usize::from(xy.next().is_some_and(|x| x != '.'));
+            changes.unstaged += usize::from(xy.next().is_some_and(|y| y != '.'));
         }
-    };
+        Some(("u", _)) => changes.conflicted += 1,
+        Some(("?", _)) => changes.untracked += 1,
+        _ => return,
+    }
+    status.entries += 1;
+}
 
-    let path = repo_path.to_path_buf();
+/// Push remote from `branch.<name>.pushRemote`, falling back to `remote.pushDefault`.
+pub async fn get_push_remote(repo_path: &Path, branch: &str) -> Option<String> {
+    let key = format!("branch.{}.pushRemote", branch);
+    for key in [key.as_str(), "remote.pushDefault"] {
+        if let Ok(raw) = run_git(repo_path, &["config", "--get", key]).await {
+            let remote = raw.trim();
+            if !remote.is_empty() {
+                return Some(remote.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Ahead/behind against the configured push remote when it differs from the
+/// upstream remote. Returns `(target, ahead, behind)`.
+///
+/// A branch never pushed there has no remote-tracking ref yet; forks usually
+/// start from the upstream, so commits not on any remote count as ahead.
+async fn get_push_counts(
+    repo_path: &Path,
+    branch: &str,
+    upstream_remote: Option<&str>,
+) -> Option<(String, usize, usize)> {
+    let remote = get_push_remote(repo_path, branch).await?;
+    if upstream_remote == Some(remote.as_str()) {
+        return None;
+    }
+    let target = format!("{}/{}", remote, branch);
+    let tracking = format!("refs/remotes/{}", target);
+    let has_tracking = run_git(repo_path, &["rev-parse", "--verify", "--quiet", &tracking])
+        .await
+        .is_ok_and(|raw| !raw.trim().is_empty());
+    if !has_tracking {
+        let ahead = count_commits(repo_path, "HEAD --not --remotes").await;
+        return Some((target, ahead, 0));
+    }
+    let ahead_range = format!("{}..HEAD", tracking);
+    let behind_range = format!("HEAD..{}", tracking);
     let (ahead, behind) = tokio::join!(
-        parse_count(&["rev-list", "--count", "@{upstream}..HEAD"], path.clone()),
-        parse_count(&["rev-list", "--count", "HEAD..@{upstream}"], path),
+        count_commits(repo_path, &ahead_range),
+        count_commits(repo_path, &behind_range),
     );
+    Some((target, ahead, behind))
+}
+
+/// `git rev-list --count <revs>` (whitespace-separated), treating failures as zero.
+async fn count_commits(repo_path: &Path, revs: &str) -> usize {
+    let mut args = vec!["rev-list", "--count"];
+    args.extend(revs.split_whitespace());
+    run_git(repo_path, &args)
+        .await
+        .ok()
+        .and_then(|raw| raw.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// A changed path from `git status --porcelain`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedFile {
+    /// Two-letter porcelain status, e.g. ` M`, `A `, `??`.
+    pub status: String,
+    pub path: String,
+}
+
+/// Changed and untracked files, for pickers that act on a subset.
+pub fn list_changed_files(repo_path: &Path) -> Result<Vec<ChangedFile>> {
+    let output = std_git_command(&["status", "--porcelain", "-z"])
+        .current_dir(repo_path)
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("git status failed");
+    }
+    Ok(parse_porcelain_z(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// A local branch with its upstream deltas, for the branch switcher.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchInfo {
+    pub name: String,
+    pub is_current: bool,
+    pub upstream: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    /// Committer date of the branch tip (Unix seconds).
+    pub last_commit_epoch: i64,
+}
+
+/// Local branches, most recently committed first.
+pub fn list_branches(repo_path: &Path) -> Result<Vec<BranchInfo>> {
+    let output = std_git_command(&[
+        "for-each-ref",
+        "--sort=-committerdate",
+        "--format=%(refname:short)%1f%(HEAD)%1f%(upstream:short)%1f%(upstream:track,nobracket)%1f%(committerdate:unix)",
+        "refs/heads",
+    ])
+    .current_dir(repo_path)
+    .output()?;
+    if !output.status.success() {
+        anyhow::bail!("git for-each-ref failed");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_branch_line)
+        .collect())
+}
+
+fn parse_branch_line(line: &str) -> Option<BranchInfo> {
+    let mut fields = line.split('\x1f');
+    let name = fields.next()?.to_string();
+    let is_current = fields.next()? == "*";
+    let upstream = Some(fields.next()?.to_string()).filter(|u| !u.is_empty());
+    let (ahead, behind) = parse_track(fields.next()?);
+    let last_commit_epoch = fields.next()?.trim().parse().unwrap_or(0);
+    Some(BranchInfo {
+        name,
+        is_current,
+        upstream,
+        ahead,
+        behind,
+        last_commit_epoch,
+    })
+}
+
+/// `(ahead, behind)` from `%(upstream:track,nobracket)`: e.g. "ahead 2,
+/// behind 3", "behind 1", "gone", or empty when in sync.
+fn parse_track(track: &str) -> (usize, usize) {
+    let (mut ahead, mut behind) = (0, 0);
+    for part in track.split(", ") {
+        match part.split_once(' ') {
+            Some(("ahead", n)) => ahead = n.parse().unwrap_or(0),
+            Some(("behind", n)) => behind = n.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    (ahead, behind)
+}
+
+/// One entry of `git log`, for commit pickers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitSummary {
+    pub sha: String,
+    pub subject: String,
+    pub author: String,
+    /// Author date (Unix seconds).
+    pub epoch: i64,
+}
+
+impl CommitSummary {
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(8)]
+    }
+}
+
+/// The last `limit` commits on HEAD, newest first.
+pub fn recent_commits(repo_path: &Path, limit: usize) -> Result<Vec<CommitSummary>> {
+    let output = std_git_command(&[
+        "log",
+        &format!("-{}", limit),
+        "--format=%H%x1f%s%x1f%an%x1f%at",
+    ])
+    .current_dir(repo_path)
+    .output()?;
+    if !output.status.success() {
+        anyhow::bail!("git log failed");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x1f');
+            Some(CommitSummary {
+                sha: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                epoch: fields.next()?.trim().parse().unwrap_or(0),
+            })
+        })
+        .collect())
+}
+
+/// One entry of `git stash list`, for the stash browser.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StashEntry {
+    /// Reflog selector, e.g. `stash@{0}`.
+    pub name: String,
+    pub message: String,
+    /// Branch the stash was made on; `None` from a detached HEAD.
+    pub branch: Option<String>,
+    /// Committer date of the stash (Unix seconds).
+    pub epoch: i64,
+    /// The stash commit, which stays put while `name` shifts.
+    pub oid: String,
+}
+
+/// Stash entries, newest first.
+pub fn list_stashes(repo_path: &Path) -> Result<Vec<StashEntry>> {
+    let output = std_git_command(&["stash", "list", "--format=%gd%x1f%gs%x1f%ct%x1f%H"])
+        .current_dir(repo_path)
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("git stash list failed");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_stash_line)
+        .collect())
+}
+
+/// Splits the reflog subject git writes for stashes: "WIP on main: abc123
+/// subject" for a bare `git stash`, "On main: message" with `-m`.
+fn parse_stash_line(line: &str) -> Option<StashEntry> {
+    let mut fields = line.split('\x1f');
+    let name = fields.next()?.to_string();
+    let subject = fields.next()?;
+    let epoch = fields.next()?.trim().parse().unwrap_or(0);
+    let oid = fields.next()?.trim().to_string();
+    let (branch, message) = subject
+        .strip_prefix("WIP on ")
+        .or_else(|| subject.strip_prefix("On "))
+        .and_then(|rest| rest.split_once(": "))
+        .map(|(branch, message)| (Some(branch.to_string()), message.to_string()))
+        .unwrap_or((None, subject.to_string()));
+    Some(StashEntry {
+        name,
+        message,
+        branch: branch.filter(|b| b != "(no branch)"),
+        epoch,
+        oid,
+    })
+}
+
+/// Uncommitted changes against HEAD, for the diff viewer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkingDiff {
+    /// `git diff --stat` summary.
+    pub stat: String,
+    /// Unified diff of tracked files.
+    pub patch: String,
+    /// Untracked paths, which `git diff` leaves out.
+    pub untracked: Vec<String>,
+}
+
+impl WorkingDiff {
+    pub fn is_empty(&self) -> bool {
+        self.patch.trim().is_empty() && self.untracked.is_empty()
+    }
+}
+
+/// Staged and unstaged changes against HEAD (or the empty tree before the
+/// first commit), without colour or external diff drivers.
+pub fn working_diff(repo_path: &Path) -> Result<WorkingDiff> {
+    const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+    let has_head = std_git_command(&["rev-parse", "--verify", "--quiet", "HEAD"])
+        .current_dir(repo_path)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    let base = if has_head { "HEAD" } else { EMPTY_TREE };
+    let run = |args: &[&str]| -> Result<String> {
+        let output = std_git_command(args).current_dir(repo_path).output()?;
+        if !output.status.success() {
+            anyhow::bail!("git {} failed", args[0]);
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    Ok(WorkingDiff {
+        stat: run(&["diff", "--no-color", "--no-ext-diff", "--stat", base])?,
+        patch: run(&["diff", "--no-color", "--no-ext-diff", base])?,
+        untracked: run(&["ls-files", "--others", "--exclude-standard"])?
+            .lines()
+            .map(str::to_string)
+            .collect(),
+    })
+}
+
+/// A commit in the per-repo log, flagged when the remote doesn't have it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub commit: CommitSummary,
+    /// Not on the upstream yet (or on no remote, without an upstream), so the
+    /// next push sends it.
+    pub unpushed: bool,
+}
+
+/// The last `limit` commits on HEAD, newest first, marking the ones a push
+/// would send.
+pub fn get_recent_commits(repo_path: &Path, limit: usize) -> Result<Vec<LogEntry>> {
+    let commits = recent_commits(repo_path, limit)?;
+    let max_count = format!("--max-count={}", limit);
+    let rev_list = |args: &[&str]| {
+        std_git_command(args)
+            .current_dir(repo_path)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+    };
+    let unpushed = rev_list(&["rev-list", &max_count, "@{upstream}..HEAD"])
+        .or_else(|| rev_list(&["rev-list", &max_count, "HEAD", "--not", "--remotes"]))
+        .unwrap_or_default();
+    let unpushed: HashSet<&str> = unpushed.lines().collect();
+    Ok(commits
+        .into_iter()
+        .map(|commit| LogEntry {
+            unpushed: unpushed.contains(commit.sha.as_str()),
+            commit,
+        })
+        .collect())
+}
+
+/// Local branches checked out in a worktree, from `git worktree list --porcelain`.
+pub fn parse_worktree_branches(raw: &str) -> Vec<(String, PathBuf)> {
+    let mut out = Vec::new();
+    let mut path = None;
+    for line in raw.lines() {
+        if let Some(p) = line.strip_prefix("worktree ") {
+            path = Some(PathBuf::from(p));
+        } else if let Some(branch) = line.strip_prefix("branch refs/heads/") {
+            if let Some(p) = path.take() {
+                out.push((branch.to_string(), p));
+            }
+        }
+    }
+    out
+}
+
+/// Branches checked out in any worktree of the repo, with their paths.
+pub fn list_worktree_branches(repo_path: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let output = std_git_command(&["worktree", "list", "--porcelain"])
+        .current_dir(repo_path)
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("git worktree list failed");
+    }
+    Ok(parse_worktree_branches(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
 
-    Ok((ahead, behind, true))
+/// True when `sha` is still HEAD and no remote-tracking ref contains it,
+/// i.e. rewriting it cannot affect anyone else.
+pub fn is_unpushed_head(repo_path: &Path, sha: &str) -> bool {
+    let stdout = |args: &[&str]| {
+        std_git_command(args)
+            .current_dir(repo_path)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+    stdout(&["rev-parse", "HEAD"]).as_deref() == Some(sha)
+        && stdout(&["rev-list", "-1", sha, "--not", "--remotes"]).as_deref() == Some(sha)
+}
+
+fn parse_porcelain_z(raw: &str) -> Vec<ChangedFile> {
+    let mut files = Vec::new();
+    let mut entries = raw.split('\0').filter(|e| !e.is_empty());
+    while let Some(entry) = entries.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        let (status, path) = entry.split_at(3);
+        let status = status[..2].to_string();
+        // Renames and copies are followed by their original path.
+        if status.starts_with('R') || status.starts_with('C') {
+            entries.next();
+        }
+        files.push(ChangedFile {
+            status,
+            path: path.to_string(),
+        });
+    }
+    files
 }
 
 /// Count stashed changes.
@@ -152,45 +759,345 @@ pub async fn get_stash_count(repo_path: &Path) -> Result<usize> {
     Ok(raw.lines().filter(|l| !l.trim().is_empty()).count())
 }
 
-/// Check all status for a single repo concurrently.
+/// Upstream of the current branch as `(remote, branch)`, e.g. `("origin", "main")`.
+pub async fn get_upstream(repo_path: &Path) -> Option<(String, String)> {
+    let raw = run_git(repo_path, &["rev-parse", "--abbrev-ref", "@{upstream}"])
+        .await
+        .ok()?;
+    let (remote, branch) = raw.trim().split_once('/')?;
+    Some((remote.to_string(), branch.to_string()))
+}
+
+pub async fn get_remote_url(repo_path: &Path, remote: &str) -> Option<String> {
+    let raw = run_git(repo_path, &["remote", "get-url", remote])
+        .await
+        .ok()?;
+    let url = raw.trim();
+    (!url.is_empty()).then(|| url.to_string())
+}
+
+pub async fn get_head_sha(repo_path: &Path) -> Option<String> {
+    let raw = run_git(repo_path, &["rev-parse", "HEAD"]).await.ok()?;
+    let sha = raw.trim();
+    (!sha.is_empty() && sha != "HEAD").then(|| sha.to_string())
+}
+
+/// Cheap stand-in for a repo's [`RepoStatus`], for caches that can't trust
+/// file mtimes: HEAD plus a hash of the upstream and stash refs, the index
+/// file and the repo config. No `git status` runs, so it costs a few
+/// milliseconds where a status walks the whole worktree; the price is that
+/// edits git hasn't recorded yet (unstaged changes, new files) leave it
+/// unchanged until something is staged, committed or fetched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusFingerprint {
+    /// HEAD's sha, or `(initial)` before the first commit.
+    pub head: String,
+    pub status_hash: String,
+}
+
+pub async fn status_fingerprint(repo_path: &Path) -> Option<StatusFingerprint> {
+    let (dirs, head, upstream) = tokio::join!(
+        run_git_strict(
+            repo_path,
+            &["rev-parse", "--absolute-git-dir", "--git-common-dir"]
+        ),
+        // Both exit 1 with no output when the ref doesn't exist yet.
+        run_git(repo_path, &["rev-parse", "--verify", "-q", "HEAD"]),
+        run_git(repo_path, &["rev-parse", "--verify", "-q", "@{upstream}"]),
+    );
+    let (dirs, head, upstream) = (dirs.ok()?, head.ok()?, upstream.ok()?);
+    let mut lines = dirs.lines();
+    let git_dir = PathBuf::from(lines.next()?.trim());
+    let common_dir = repo_path.join(lines.next()?.trim());
+    let head = match head.trim() {
+        "" => "(initial)".to_string(),
+        sha => sha.to_string(),
+    };
+    let read = |path: PathBuf| std::fs::read(path).unwrap_or_default();
+    // HEAD's file names the branch; the stash reflog holds every entry.
+    let files = [
+        read(git_dir.join("HEAD")),
+        read(git_dir.join("index")),
+        read(common_dir.join("config")),
+        read(common_dir.join("logs").join("refs").join("stash")),
+    ];
+    let pathspecs = dirty_pathspecs(repo_path);
+    let mut parts: Vec<&[u8]> = vec![upstream.trim().as_bytes()];
+    parts.extend(files.iter().map(Vec::as_slice));
+    parts.extend(pathspecs.iter().map(String::as_bytes));
+    Some(StatusFingerprint {
+        head,
+        status_hash: format!("{:016x}", fnv1a(&parts)),
+    })
+}
+
+/// FNV-1a over `parts`; unlike `DefaultHasher`, stable across builds, so
+/// hashes can be persisted.
+pub(crate) fn fnv1a(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for &byte in part.iter().chain(&[0]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Path-independent repo identity: the root commit hash, or the origin URL for
+/// repos without commits. Returns `None` when neither is available.
+pub async fn get_repo_identity(repo_path: &Path) -> Option<String> {
+    if let Ok(raw) = run_git(repo_path, &["rev-list", "--max-parents=0", "HEAD"]).await {
+        // Merged histories can have several roots; pick one deterministically.
+        if let Some(root) = raw.lines().map(str::trim).filter(|l| !l.is_empty()).min() {
+            return Some(format!("root:{}", root));
+        }
+    }
+    let url = run_git(repo_path, &["config", "--get", "remote.origin.url"])
+        .await
+        .ok()?;
+    let url = url.trim();
+    (!url.is_empty()).then(|| format!("remote:{}", url))
+}
+
+// ─── dirty_ignore ───────────────────────────────────────────────────────────
+
+static DIRTY_IGNORE: OnceLock<Mutex<DirtyIgnoreConfig>> = OnceLock::new();
+
+/// Apply `[dirty_ignore]` to later status probes and commit actions.
+pub fn configure_dirty_ignore(cfg: &DirtyIgnoreConfig) {
+    if let Ok(mut current) = DIRTY_IGNORE
+        .get_or_init(|| Mutex::new(DirtyIgnoreConfig::new()))
+        .lock()
+    {
+        *current = cfg.clone();
+    }
+}
+
+/// [`pathspecs_for`] under the configured `[dirty_ignore]`.
+pub fn dirty_pathspecs(repo_path: &Path) -> Vec<String> {
+    match DIRTY_IGNORE.get().map(Mutex::lock) {
+        Some(Ok(cfg)) => pathspecs_for(&cfg, repo_path),
+        _ => Vec::new(),
+    }
+}
+
+/// Pathspecs covering the repo minus its `cfg` patterns (looked up by path,
+/// then directory name, so a path entry wins over a same-named repo entry);
+/// empty when it has none.
+fn pathspecs_for(cfg: &DirtyIgnoreConfig, repo_path: &Path) -> Vec<String> {
+    let name = repo_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let patterns = cfg
+        .get(repo_path.to_string_lossy().as_ref())
+        .or_else(|| cfg.get(&name))
+        .filter(|patterns| !patterns.is_empty());
+    patterns.map_or_else(Vec::new, |patterns| {
+        std::iter::once(".".to_string())
+            .chain(patterns.iter().map(|p| format!(":(exclude,glob){}", p)))
+            .collect()
+    })
+}
+
+/// [`dirty_pathspecs`] as a ` -- . ":(exclude,glob)…"` suffix for shown
+/// commands.
+pub fn dirty_pathspec_suffix(repo_path: &Path) -> String {
+    let pathspecs = dirty_pathspecs(repo_path);
+    if pathspecs.is_empty() {
+        return String::new();
+    }
+    let quoted: Vec<String> = pathspecs.iter().map(|spec| format!("{:?}", spec)).collect();
+    format!(" -- {}", quoted.join(" "))
+}
+
+/// `args`, then `--` and the repo's [`dirty_pathspecs`] if it has any.
+fn with_dirty_pathspecs<'a>(args: &[&'a str], pathspecs: &'a [String]) -> Vec<&'a str> {
+    let mut args = args.to_vec();
+    if !pathspecs.is_empty() {
+        args.push("--");
+        args.extend(pathspecs.iter().map(String::as_str));
+    }
+    args
+}
+
+// ─── fsmonitor fast path ────────────────────────────────────────────────────
+
+const FSMONITOR_RECHECK: Duration = Duration::from_secs(600);
+
+type FsmonitorCache = HashMap<PathBuf, (Instant, bool)>;
+
+static FSMONITOR_CACHE: OnceLock<Mutex<FsmonitorCache>> = OnceLock::new();
+
+/// `core.fsmonitor` is `true` for git's builtin daemon, or a hook path.
+pub fn fsmonitor_configured(value: Option<&str>) -> bool {
+    value.is_some_and(|value| !value.is_empty() && value != "false")
+}
+
+/// Whether the repo has an fsmonitor, re-read every few minutes.
+async fn has_fsmonitor(repo_path: &Path) -> bool {
+    let cache = FSMONITOR_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some((at, enabled)) = cache.lock().ok().and_then(|c| c.get(repo_path).copied()) {
+        if at.elapsed() < FSMONITOR_RECHECK {
+            return enabled;
+        }
+    }
+    let value = run_git(repo_path, &["config", "core.fsmonitor"]).await.ok();
+    let enabled = fsmonitor_configured(value.as_deref().map(str::trim));
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(repo_path.to_path_buf(), (Instant::now(), enabled));
+    }
+    enabled
+}
+
+/// Upstream and push sides of a branch from one `git for-each-ref`.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct BranchTracking {
+    upstream_remote: Option<String>,
+    /// "gone" when the upstream's remote-tracking ref no longer exists.
+    upstream_track: String,
+    push_remote: Option<String>,
+    /// Unset when the push destination has no remote-tracking ref yet.
+    push_track: Option<String>,
+}
+
+fn parse_branch_tracking(raw: &str) -> Option<BranchTracking> {
+    let mut fields = raw.trim_end_matches('\n').split('\x1f');
+    let upstream_remote = fields.next()?;
+    let upstream_track = fields.next()?;
+    let push_ref = fields.next()?;
+    let push_remote = fields.next()?;
+    let push_track = fields.next()?;
+    let some = |field: &str| Some(field.to_string()).filter(|f| !f.is_empty());
+    Some(BranchTracking {
+        upstream_remote: some(upstream_remote),
+        upstream_track: upstream_track.to_string(),
+        push_remote: some(push_remote),
+        push_track: (!push_ref.is_empty()).then(|| push_track.to_string()),
+    })
+}
+
+/// [`check_repo_status`] in two git processes for the common case: status
+/// with branch and stash headers (no ahead/behind walk; the fsmonitor keeps
+/// the worktree part cheap) plus one `for-each-ref` for upstream and push
+/// ahead/behind. `None` when git rejects the flags, so the caller falls back.
+async fn fast_repo_status(repo_path: &Path, pathspecs: &[String]) -> Option<RepoStatus> {
+    let args = with_dirty_pathspecs(
+        &[
+            "status",
+            "--porcelain=v2",
+            "--branch",
+            "--show-stash",
+            "--no-ahead-behind",
+        ],
+        pathspecs,
+    );
+    let raw = run_git_strict(repo_path, &args).await.ok()?;
+    let v2 = parse_status_v2(&raw);
+    let mut status = RepoStatus {
+        branch: v2.head.clone().unwrap_or_else(|| "HEAD".to_string()),
+        uncommitted_count: v2.entries,
+        changes: v2.changes,
+        stash_count: v2.stash,
+        is_detached: v2.head.is_none(),
+        head_sha: v2.oid.clone(),
+        ..RepoStatus::default()
+    };
+    let Some(branch) = v2.head else {
+        status.has_remote = has_any_remote(repo_path).await;
+        return Some(status);
+    };
+
+    let refname = format!("refs/heads/{}", branch);
+    let format = "--format=%(upstream:remotename)%1f%(upstream:track,nobracket)%1f\
+                  %(push)%1f%(push:remotename)%1f%(push:track,nobracket)";
+    let tracking = run_git_strict(repo_path, &["for-each-ref", format, &refname])
+        .await
+        .ok()
+        .and_then(|raw| parse_branch_tracking(&raw))
+        .unwrap_or_default();
+    let upstream = v2.upstream.as_ref();
+    status.has_remote =
+        upstream.is_some() || tracking.push_remote.is_some() || has_any_remote(repo_path).await;
+    if !status.has_remote {
+        return Some(status);
+    }
+    let (ahead, behind) = parse_track(&tracking.upstream_track);
+    status.unpushed_count = ahead;
+    status.behind_count = behind;
+    status.missing_upstream = upstream.is_none();
+
+    let upstream_remote = tracking.upstream_remote.filter(|_| upstream.is_some());
+    match tracking.push_remote {
+        Some(push_remote) if upstream_remote.as_ref() != Some(&push_remote) => {
+            let (push_ahead, push_behind) = match &tracking.push_track {
+                Some(track) => parse_track(track),
+                None => (count_commits(repo_path, "HEAD --not --remotes").await, 0),
+            };
+            status.push = Some(PushDivergence {
+                target: format!("{}/{}", push_remote, branch),
+                upstream_ahead: ahead,
+                push_behind,
+            });
+            status.unpushed_count = push_ahead;
+        }
+        _ if upstream.is_none() => {
+            status.unpushed_count = count_commits(repo_path, "HEAD --not --remotes").await;
+        }
+        _ => {}
+    }
+    Some(status)
+}
+
+async fn has_any_remote(repo_path: &Path) -> bool {
+    run_git(repo_path, &["remote"])
+        .await
+        .is_ok_and(|raw| !raw.trim().is_empty())
+}
+
+/// Check all status for a single repo concurrently. Repos with an fsmonitor
+/// take [`fast_repo_status`], which needs about half the git processes.
 pub async fn check_repo_status(repo_path: &Path) -> Result<RepoStatus> {
-    let (branch_res, uncommitted_res, remote_res, stash_res) = tokio::join!(
-        get_branch(repo_path),
-        get_uncommitted_count(repo_path),
-        get_remote_counts(repo_path),
+    let pathspecs = dirty_pathspecs(repo_path);
+    if has_fsmonitor(repo_path).await {
+        if let Some(status) = fast_repo_status(repo_path, &pathspecs).await {
+            return Ok(status);
+        }
+    }
+    full_repo_status(repo_path, &pathspecs).await
+}
+
+async fn full_repo_status(repo_path: &Path, pathspecs: &[String]) -> Result<RepoStatus> {
+    let (worktree_res, remote_res, stash_res) = tokio::join!(
+        get_worktree_status(repo_path, pathspecs),
+        run_git(repo_path, &["remote"]),
         get_stash_count(repo_path),
     );
 
     let mut probe_errors = Vec::<String>::new();
 
-    let (branch, is_detached) = match branch_res {
+    let worktree = match worktree_res {
         Ok(v) => v,
         Err(e) => {
             probe_errors.push(format!(
-                "branch probe failed: {}",
+                "status probe failed: {}",
                 compact_error(e.to_string())
             ));
-            ("unknown".to_string(), false)
+            WorktreeStatus {
+                head: Some("unknown".to_string()),
+                ..WorktreeStatus::default()
+            }
         }
     };
-    let uncommitted_count = match uncommitted_res {
-        Ok(v) => v,
-        Err(e) => {
-            probe_errors.push(format!(
-                "worktree probe failed: {}",
-                compact_error(e.to_string())
-            ));
-            0
-        }
-    };
-    let (unpushed_count, behind_count, has_remote) = match remote_res {
-        Ok(v) => v,
+    let has_remote = match remote_res {
+        Ok(raw) => !raw.trim().is_empty(),
         Err(e) => {
             probe_errors.push(format!(
                 "remote probe failed: {}",
                 compact_error(e.to_string())
             ));
-            (0, 0, false)
+            false
         }
     };
     let stash_count = match stash_res {
@@ -204,15 +1111,50 @@ pub async fn check_repo_status(repo_path: &Path) -> Result<RepoStatus> {
         }
     };
 
+    let is_detached = worktree.head.is_none();
+    let branch = worktree.head.unwrap_or_else(|| "HEAD".to_string());
+    let (mut unpushed_count, behind_count) = match worktree.ahead_behind {
+        Some(counts) if has_remote => counts,
+        _ => (0, 0),
+    };
+    let mut push = None;
+    let mut missing_upstream = false;
+    if has_remote && !is_detached {
+        missing_upstream = worktree.upstream.is_none();
+        let upstream_remote = worktree
+            .upstream
+            .as_deref()
+            .and_then(|upstream| upstream.split_once('/'))
+            .map(|(remote, _)| remote);
+        if let Some((target, ahead, push_behind)) =
+            get_push_counts(repo_path, &branch, upstream_remote).await
+        {
+            push = Some(PushDivergence {
+                target,
+                upstream_ahead: unpushed_count,
+                push_behind,
+            });
+            unpushed_count = ahead;
+        } else if missing_upstream {
+            unpushed_count = count_commits(repo_path, "HEAD --not --remotes").await;
+        }
+    }
+
     Ok(RepoStatus {
         branch,
-        uncommitted_count,
+        uncommitted_count: worktree.entries,
+        changes: worktree.changes,
         unpushed_count,
         behind_count,
         stash_count,
         has_remote,
         is_detached,
         probe_errors,
+        push,
+        missing_upstream,
+        dirty_since: None,
+        unpushed_since: None,
+        head_sha: worktree.oid,
     })
 }
 
@@ -230,6 +1172,25 @@ mod tests {
     use super::*;
     use std::process::Command as StdCommand;
 
+    #[test]
+    fn priority_argv_defaults_to_plain_git() {
+        assert_eq!(priority_argv(0, false, true, true), vec!["git"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn priority_argv_wraps_with_available_tools() {
+        assert_eq!(
+            priority_argv(10, true, true, true),
+            vec!["ionice", "-c3", "nice", "-n", "10", "git"]
+        );
+        assert_eq!(
+            priority_argv(10, true, true, false),
+            vec!["nice", "-n", "10", "git"]
+        );
+        assert_eq!(priority_argv(10, false, false, false), vec!["git"]);
+    }
+
     fn init_test_repo(name: &str) -> PathBuf {
         let base = std::env::temp_dir().join("agentpulse_git_test").join(name);
         let _ = std::fs::remove_dir_all(&base);
@@ -247,6 +1208,73 @@ mod tests {
         base
     }
 
+    /// Full probe, checking the fsmonitor fast path reports the same.
+    async fn probe_both(repo_path: &Path) -> RepoStatus {
+        probe_excluding(repo_path, &[]).await
+    }
+
+    async fn probe_excluding(repo_path: &Path, pathspecs: &[String]) -> RepoStatus {
+        let full = full_repo_status(repo_path, pathspecs).await.unwrap();
+        let fast = fast_repo_status(repo_path, pathspecs).await.unwrap();
+        assert_eq!(format!("{:?}", fast), format!("{:?}", full));
+        full
+    }
+
+    #[test]
+    fn parses_status_v2_headers_and_tracking() {
+        let raw = "# branch.oid 97ce0cb\n# branch.head main\n# branch.upstream origin/main\n\
+                   # branch.ab +? -?\n# stash 2\n1 A. N... 000000 100644 100644 0 975f g\n? h\n";
+        assert_eq!(
+            parse_status_v2(raw),
+            WorktreeStatus {
+                head: Some("main".to_string()),
+                oid: Some("97ce0cb".to_string()),
+                upstream: Some("origin/main".to_string()),
+                ahead_behind: None,
+                stash: 2,
+                entries: 2,
+                changes: ChangeCounts {
+                    staged: 1,
+                    untracked: 1,
+                    ..ChangeCounts::default()
+                },
+            }
+        );
+        let mixed = parse_status_v2(
+            "# branch.head main\n# branch.upstream origin/main\n# branch.ab +3 -1\n\
+             1 MM N... 100644 100644 100644 1a 2b src/lib.rs\n\
+             1 .D N... 100644 100644 000000 3c 3c old.rs\n\
+             2 R. N... 100644 100644 100644 4d 4d R100 new.rs\tnew_old.rs\n\
+             u UU N... 100644 100644 100644 100644 5e 6f 7a Cargo.lock\n? a\n? b\n",
+        );
+        assert_eq!(mixed.ahead_behind, Some((3, 1)));
+        assert_eq!(mixed.entries, 6);
+        assert_eq!(
+            mixed.changes,
+            ChangeCounts {
+                staged: 2,
+                unstaged: 2,
+                untracked: 2,
+                conflicted: 1,
+            }
+        );
+        // A gone upstream has no `branch.ab` line.
+        let gone = parse_status_v2("# branch.head main\n# branch.upstream origin/main\n");
+        assert_eq!((gone.upstream, gone.ahead_behind), (None, None));
+        let detached = parse_status_v2("# branch.oid 97ce0cb\n# branch.head (detached)\n");
+        assert_eq!(detached.head, None);
+
+        let tracking =
+            parse_branch_tracking("origin\x1fahead 1, behind 2\x1f\x1ffork\x1f\n").unwrap();
+        assert_eq!(tracking.upstream_remote.as_deref(), Some("origin"));
+        assert_eq!(parse_track(&tracking.upstream_track), (1, 2));
+        assert_eq!(tracking.push_remote.as_deref(), Some("fork"));
+        assert_eq!(tracking.push_track, None);
+        assert!(fsmonitor_configured(Some("true")));
+        assert!(!fsmonitor_configured(Some("false")));
+        assert!(!fsmonitor_configured(None));
+    }
+
     #[tokio::test]
     async fn test_clean_repo_has_zero_counts() {
         let base = init_test_repo("clean");
@@ -270,15 +1298,98 @@ mod tests {
         std::fs::remove_dir_all(&base).unwrap();
     }
 
+    #[test]
+    fn parses_porcelain_z_with_renames() {
+        let raw = " M src/main.rs\0R  new.rs\0old.rs\0?? notes.txt\0";
+        let files = parse_porcelain_z(raw);
+        let paths: Vec<_> = files
+            .iter()
+            .map(|f| (f.status.as_str(), f.path.as_str()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![(" M", "src/main.rs"), ("R ", "new.rs"), ("??", "notes.txt")]
+        );
+    }
+
     #[tokio::test]
     async fn test_uncommitted_changes_counted() {
         let base = init_test_repo("dirty");
         std::fs::write(base.join("file.txt"), "change").unwrap();
-        let count = get_uncommitted_count(&base).await.unwrap();
-        assert_eq!(count, 1);
+        let status = get_worktree_status(&base, &[]).await.unwrap();
+        assert_eq!(status.entries, 1);
+        assert_eq!(status.changes.untracked, 1);
+
+        std::fs::write(base.join("staged.txt"), "staged").unwrap();
+        StdCommand::new("git")
+            .args(["add", "staged.txt"])
+            .current_dir(&base)
+            .output()
+            .unwrap();
+        let status = probe_both(&base).await;
+        assert_eq!(status.uncommitted_count, 2);
+        assert_eq!(
+            status.changes,
+            ChangeCounts {
+                staged: 1,
+                untracked: 1,
+                ..ChangeCounts::default()
+            }
+        );
         std::fs::remove_dir_all(&base).unwrap();
     }
 
+    #[tokio::test]
+    async fn dirty_ignore_patterns_are_left_out() {
+        let base = init_test_repo("dirty_ignore");
+        let git = |args: &[&str]| {
+            StdCommand::new("git")
+                .args(args)
+                .current_dir(&base)
+                .output()
+                .unwrap()
+        };
+        std::fs::create_dir_all(base.join("notebooks")).unwrap();
+        std::fs::write(base.join("notebooks/eda.ipynb"), "{}").unwrap();
+        std::fs::write(base.join("main.py"), "").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        std::fs::write(base.join("notebooks/eda.ipynb"), "{\"outputs\": []}").unwrap();
+        std::fs::write(base.join("notebooks/scratch.ipynb"), "{}").unwrap();
+        let cfg = DirtyIgnoreConfig::from([(
+            "dirty_ignore".to_string(),
+            vec!["notebooks/*.ipynb".to_string()],
+        )]);
+        let pathspecs = pathspecs_for(&cfg, &base);
+        assert_eq!(pathspecs, [".", ":(exclude,glob)notebooks/*.ipynb"]);
+        assert_eq!(
+            probe_excluding(&base, &pathspecs).await.uncommitted_count,
+            0
+        );
+
+        std::fs::write(base.join("main.py"), "print()").unwrap();
+        let status = probe_excluding(&base, &pathspecs).await;
+        assert_eq!((status.uncommitted_count, status.changes.unstaged), (1, 1));
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn dirty_ignore_path_entries_win_over_repo_names() {
+        let cfg = DirtyIgnoreConfig::from([
+            ("api".to_string(), vec!["*.log".to_string()]),
+            ("/work/other/api".to_string(), vec!["tmp/**".to_string()]),
+        ]);
+        assert_eq!(
+            pathspecs_for(&cfg, Path::new("/work/other/api")),
+            [".", ":(exclude,glob)tmp/**"]
+        );
+        assert_eq!(
+            pathspecs_for(&cfg, Path::new("/work/api")),
+            [".", ":(exclude,glob)*.log"]
+        );
+        assert!(pathspecs_for(&cfg, Path::new("/work/web")).is_empty());
+    }
+
     #[tokio::test]
     async fn test_stash_count() {
         let base = init_test_repo("stash");
@@ -307,6 +1418,191 @@ mod tests {
             .unwrap();
         let count = get_stash_count(&base).await.unwrap();
         assert_eq!(count, 1);
+        assert_eq!(probe_both(&base).await.stash_count, 1);
         std::fs::remove_dir_all(&base).unwrap();
     }
+
+    #[tokio::test]
+    async fn test_identity_survives_rename() {
+        let base = init_test_repo("identity");
+        assert!(get_repo_identity(&base).await.is_none());
+        std::fs::write(base.join("README.md"), "hello").unwrap();
+        StdCommand::new("git")
+            .args(["add", "."])
+            .current_dir(&base)
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .args(["commit", "-m", "init"])
+            .current_dir(&base)
+            .output()
+            .unwrap();
+        let before = get_repo_identity(&base).await.unwrap();
+        assert!(before.starts_with("root:"));
+
+        let moved = base.with_file_name("identity_moved");
+        let _ = std::fs::remove_dir_all(&moved);
+        std::fs::rename(&base, &moved).unwrap();
+        assert_eq!(get_repo_identity(&moved).await.unwrap(), before);
+        std::fs::remove_dir_all(&moved).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_triangular_push_counts() {
+        let base = init_test_repo("triangular");
+        let git = |args: &[&str]| {
+            let out = StdCommand::new("git")
+                .args(args)
+                .current_dir(&base)
+                .output()
+                .unwrap();
+            assert!(out.status.success(), "git {:?} failed", args);
+        };
+        let upstream = base.with_file_name("triangular_upstream.git");
+        let fork = base.with_file_name("triangular_fork.git");
+        for bare in [&upstream, &fork] {
+            let _ = std::fs::remove_dir_all(bare);
+            StdCommand::new("git")
+                .args(["init", "--bare"])
+                .arg(bare)
+                .output()
+                .unwrap();
+        }
+        std::fs::write(base.join("README.md"), "hello").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        git(&["branch", "-M", "main"]);
+        git(&["remote", "add", "origin", upstream.to_str().unwrap()]);
+        git(&["remote", "add", "fork", fork.to_str().unwrap()]);
+        git(&["push", "-u", "origin", "main"]);
+        git(&["config", "remote.pushDefault", "fork"]);
+
+        // Never pushed to the fork: the local commit counts as unpushed.
+        std::fs::write(base.join("a.txt"), "a").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "a"]);
+        let status = probe_both(&base).await;
+        assert_eq!(status.unpushed_count, 1);
+        let push = status.push.clone().unwrap();
+        assert_eq!(push.target, "fork/main");
+        assert_eq!(push.upstream_ahead, 1);
+
+        // Pushed to the fork: nothing left to push, still ahead of upstream.
+        git(&["push", "fork", "main"]);
+        let status = probe_both(&base).await;
+        assert_eq!(status.unpushed_count, 0);
+        assert_eq!(status.push.unwrap().upstream_ahead, 1);
+
+        // A branch-level pushRemote matching the upstream disables the split.
+        git(&["config", "branch.main.pushRemote", "origin"]);
+        let status = probe_both(&base).await;
+        assert!(status.push.is_none());
+        assert_eq!(status.unpushed_count, 1);
+
+        for dir in [&base, &upstream, &fork] {
+            std::fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_new_branch_reports_missing_upstream() {
+        let base = init_test_repo("missing_upstream");
+        let git = |args: &[&str]| {
+            let out = StdCommand::new("git")
+                .args(args)
+                .current_dir(&base)
+                .output()
+                .unwrap();
+            assert!(out.status.success(), "git {:?} failed", args);
+        };
+        let remote = base.with_file_name("missing_upstream_remote.git");
+        let _ = std::fs::remove_dir_all(&remote);
+        StdCommand::new("git")
+            .args(["init", "--bare"])
+            .arg(&remote)
+            .output()
+            .unwrap();
+        std::fs::write(base.join("README.md"), "hello").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+        git(&["branch", "-M", "main"]);
+        git(&["remote", "add", "origin", remote.to_str().unwrap()]);
+        git(&["push", "-u", "origin", "main"]);
+        assert!(!probe_both(&base).await.missing_upstream);
+
+        git(&["switch", "-c", "feature"]);
+        std::fs::write(base.join("a.txt"), "a").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "a"]);
+        let status = probe_both(&base).await;
+        assert!(status.missing_upstream);
+        assert_eq!(status.unpushed_count, 1);
+        assert_eq!(status.publish_remote(), "origin");
+
+        let branches = list_branches(&base).unwrap();
+        let feature = branches.iter().find(|b| b.name == "feature").unwrap();
+        assert!(feature.is_current);
+        assert_eq!(feature.upstream, None);
+        let main = branches.iter().find(|b| b.name == "main").unwrap();
+        assert_eq!(main.upstream.as_deref(), Some("origin/main"));
+
+        // Without an upstream, commits on no remote are the ones to push.
+        let log = get_recent_commits(&base, 10).unwrap();
+        let flags: Vec<_> = log
+            .iter()
+            .map(|e| (e.commit.subject.as_str(), e.unpushed))
+            .collect();
+        assert_eq!(flags, [("a", true), ("init", false)]);
+
+        for dir in [&base, &remote] {
+            std::fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn parses_branch_tracking_counts() {
+        let branch =
+            parse_branch_line("release\x1f \x1forigin/release\x1fahead 2, behind 3\x1f1700000000")
+                .unwrap();
+        assert!(!branch.is_current);
+        assert_eq!((branch.ahead, branch.behind), (2, 3));
+        assert_eq!(branch.last_commit_epoch, 1_700_000_000);
+        let gone = parse_branch_line("old\x1f*\x1forigin/old\x1fgone\x1f0").unwrap();
+        assert!(gone.is_current);
+        assert_eq!((gone.ahead, gone.behind), (0, 0));
+    }
+
+    #[test]
+    fn parses_stash_entries() {
+        let wip = parse_stash_line(
+            "stash@{0}\x1fWIP on main: 1a2b3c4 Fix login\x1f1700000000\x1f5e6f70a",
+        )
+        .unwrap();
+        assert_eq!(wip.name, "stash@{0}");
+        assert_eq!(wip.oid, "5e6f70a");
+        assert_eq!(wip.branch.as_deref(), Some("main"));
+        assert_eq!(wip.message, "1a2b3c4 Fix login");
+        assert_eq!(wip.epoch, 1_700_000_000);
+        let named =
+            parse_stash_line("stash@{1}\x1fOn feature/x: half-done: parser\x1f0\x1fb").unwrap();
+        assert_eq!(named.branch.as_deref(), Some("feature/x"));
+        assert_eq!(named.message, "half-done: parser");
+        let detached =
+            parse_stash_line("stash@{2}\x1fWIP on (no branch): 9f8e7d6 probe\x1f0\x1fc").unwrap();
+        assert_eq!(detached.branch, None);
+    }
+
+    #[test]
+    fn maps_checked_out_branches_to_worktrees() {
+        let raw = "worktree /src/app\nHEAD abc\nbranch refs/heads/main\n\n\
+                   worktree /src/app-detached\nHEAD def\ndetached\n\n\
+                   worktree /src/app-release\nHEAD 123\nbranch refs/heads/release/1.2\n";
+        assert_eq!(
+            parse_worktree_branches(raw),
+            vec![
+                ("main".to_string(), PathBuf::from("/src/app")),
+                ("release/1.2".to_string(), PathBuf::from("/src/app-release")),
+            ]
+        );
+    }
 }
@@ -0,0 +1,753 @@
+//! Snapshot history for the time-travel view (`T`).
+//!
+//! The primary instance appends a compact summary of each scan to
+//! `~/.config/agentpulse/history.jsonl`, at most one per
+//! [`RECORD_INTERVAL_SECS`], keeping the newest [`MAX_ENTRIES`]. Any two
+//! entries can then be compared repo by repo.
+
+use crate::config::{Config, DisplayConfig};
+use crate::dashboard::{self, DashboardAlert, DashboardSnapshot};
+use crate::format;
+use crate::git::Repo;
+use anyhow::Result;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Minimum gap between recorded entries.
+pub const RECORD_INTERVAL_SECS: i64 = 15 * 60;
+/// About three weeks of hourly-or-denser use.
+pub const MAX_ENTRIES: usize = 500;
+
+/// What the comparison needs from one scan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub at: i64,
+    #[serde(default)]
+    pub repos: Vec<RepoPoint>,
+    #[serde(default)]
+    pub alerts: Vec<AlertPoint>,
+    /// Estimated spend per provider (`claude`, `openai`, ...).
+    #[serde(default)]
+    pub costs: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepoPoint {
+    pub name: String,
+    pub path: String,
+    pub branch: String,
+    pub dirty: usize,
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AlertPoint {
+    pub severity: String,
+    pub title: String,
+    pub repo: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn from_snapshot(snapshot: &DashboardSnapshot) -> Self {
+        let mut costs = BTreeMap::new();
+        for provider in &snapshot.providers {
+            *costs
+                .entry(provider.provider.as_str().to_string())
+                .or_insert(0.0) += provider.estimated_cost_usd;
+        }
+        Self {
+            at: snapshot.generated_at_epoch_secs,
+            repos: snapshot
+                .repos
+                .iter()
+                .map(|row| RepoPoint {
+                    name: row.name.clone(),
+                    path: row.path.clone(),
+                    branch: row.branch.clone(),
+                    dirty: row.dirty,
+                    ahead: row.ahead,
+                    behind: row.behind,
+                })
+                .collect(),
+            alerts: snapshot
+                .alerts
+                .iter()
+                .map(|alert| AlertPoint {
+                    severity: alert.severity.clone(),
+                    title: alert.title.clone(),
+                    repo: alert.repo.clone(),
+                })
+                .collect(),
+            costs,
+        }
+    }
+}
+
+/// History location: `~/.config/agentpulse/history.jsonl`.
+pub fn history_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".config")
+        .join("agentpulse")
+        .join("history.jsonl")
+}
+
+/// Entries oldest first; unreadable lines are skipped.
+pub fn load(path: &Path) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Held while recording, so snapshots recorded from background threads
+/// can't both pass the interval check and write twice.
+static RECORDING: Mutex<()> = Mutex::new(());
+
+/// Append `entry` unless the last one is more recent than
+/// [`RECORD_INTERVAL_SECS`]. Returns whether it was written.
+pub fn record(path: &Path, entry: &HistoryEntry) -> Result<bool> {
+    let _recording = RECORDING.lock();
+    let mut entries = load(path);
+    if entries
+        .last()
+        .is_some_and(|last| entry.at - last.at < RECORD_INTERVAL_SECS)
+    {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if entries.len() < MAX_ENTRIES {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        return Ok(true);
+    }
+    entries.push(entry.clone());
+    let keep = &entries[entries.len() - MAX_ENTRIES..];
+    let mut raw = String::new();
+    for kept in keep {
+        raw.push_str(&serde_json::to_string(kept)?);
+        raw.push('\n');
+    }
+    std::fs::write(path, raw)?;
+    Ok(true)
+}
+
+/// Since when each repo has been dirty and ahead, taken from the trailing
+/// run of entries that show it so. A repo missing from an entry starts over.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkAges {
+    since: HashMap<String, (Option<i64>, Option<i64>)>,
+}
+
+impl WorkAges {
+    pub fn from_entries(entries: &[HistoryEntry]) -> Self {
+        let mut since: HashMap<String, (Option<i64>, Option<i64>)> = HashMap::new();
+        for entry in entries {
+            let mut next = HashMap::with_capacity(entry.repos.len());
+            for point in &entry.repos {
+                let (dirty, ahead) = since.get(&point.path).copied().unwrap_or_default();
+                let started =
+                    |was: Option<i64>, count: usize| (count > 0).then(|| was.unwrap_or(entry.at));
+                next.insert(
+                    point.path.clone(),
+                    (started(dirty, point.dirty), started(ahead, point.ahead)),
+                );
+            }
+            since = next;
+        }
+        Self { since }
+    }
+
+    /// Set `dirty_since` / `unpushed_since` on repos that are still dirty or ahead.
+    pub fn apply(&self, repos: &mut [Repo]) {
+        for repo in repos {
+            let (dirty, ahead) = self
+                .since
+                .get(repo.path.to_string_lossy().as_ref())
+                .copied()
+                .unwrap_or_default();
+            let status = &mut repo.status;
+            status.dirty_since = dirty.filter(|_| status.uncommitted_count > 0);
+            status.unpushed_since = ahead.filter(|_| status.unpushed_count > 0);
+        }
+    }
+}
+
+static WORK_AGES: Mutex<Option<(Option<SystemTime>, WorkAges)>> = Mutex::new(None);
+
+/// Annotate freshly scanned repos with how long their work has been local,
+/// rereading the history file only when it has changed.
+pub fn annotate_work_ages(repos: &mut [Repo]) {
+    let path = history_path();
+    let modified = std::fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok();
+    let Ok(mut cached) = WORK_AGES.lock() else {
+        return;
+    };
+    if cached.as_ref().is_none_or(|(at, _)| *at != modified) {
+        *cached = Some((modified, WorkAges::from_entries(&load(&path))));
+    }
+    if let Some((_, ages)) = cached.as_ref() {
+        ages.apply(repos);
+    }
+}
+
+/// Holds the end-of-day snapshot while `[schedule]` says it is off hours,
+/// so the first scan back yields one digest instead of a string of alerts.
+#[derive(Debug, Default)]
+pub struct OffHours {
+    last_working: Option<HistoryEntry>,
+    baseline: Option<HistoryEntry>,
+}
+
+/// What changed between the end of one working day and the next scan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Digest {
+    pub from: HistoryEntry,
+    pub to: HistoryEntry,
+    pub changes: Vec<Change>,
+}
+
+impl OffHours {
+    /// Feed each new snapshot; returns the digest on the first one back in
+    /// working hours, when anything changed.
+    pub fn observe(
+        &mut self,
+        entry: HistoryEntry,
+        working: bool,
+        display: &DisplayConfig,
+    ) -> Option<Digest> {
+        if !working {
+            if self.baseline.is_none() {
+                self.baseline = Some(self.last_working.take().unwrap_or(entry));
+            }
+            return None;
+        }
+        let digest = self.baseline.take().and_then(|from| {
+            let changes = compare(&from, &entry, display);
+            (!changes.is_empty()).then(|| Digest {
+                from,
+                to: entry.clone(),
+                changes,
+            })
+        });
+        self.last_working = Some(entry);
+        digest
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    RepoAdded,
+    RepoRemoved,
+    NewDirty,
+    Cleaned,
+    BranchChanged,
+    SyncChanged,
+    AlertNew,
+    AlertResolved,
+    CostDelta,
+}
+
+impl ChangeKind {
+    /// `+` for things that appeared, `-` for things that went away.
+    pub fn sign(self) -> &'static str {
+        match self {
+            ChangeKind::RepoAdded | ChangeKind::NewDirty | ChangeKind::AlertNew => "+",
+            ChangeKind::RepoRemoved | ChangeKind::Cleaned | ChangeKind::AlertResolved => "-",
+            ChangeKind::BranchChanged | ChangeKind::SyncChanged | ChangeKind::CostDelta => "~",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChangeKind::RepoAdded => "new repo",
+            ChangeKind::RepoRemoved => "repo gone",
+            ChangeKind::NewDirty => "now dirty",
+            ChangeKind::Cleaned => "cleaned",
+            ChangeKind::BranchChanged => "branch",
+            ChangeKind::SyncChanged => "sync",
+            ChangeKind::AlertNew => "new alert",
+            ChangeKind::AlertResolved => "resolved",
+            ChangeKind::CostDelta => "cost",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub subject: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl Change {
+    fn new(kind: ChangeKind, subject: &str, before: String, after: String) -> Self {
+        Self {
+            kind,
+            subject: subject.to_string(),
+            before,
+            after,
+        }
+    }
+}
+
+/// What changed between two entries, repos first, then alerts and costs.
+pub fn compare(from: &HistoryEntry, to: &HistoryEntry, display: &DisplayConfig) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let before: BTreeMap<&str, &RepoPoint> =
+        from.repos.iter().map(|r| (r.path.as_str(), r)).collect();
+    let after: BTreeMap<&str, &RepoPoint> = to.repos.iter().map(|r| (r.path.as_str(), r)).collect();
+    let sync = |r: &RepoPoint| format!("↑{} ↓{}", r.ahead, r.behind);
+
+    for (path, new) in &after {
+        let Some(old) = before.get(path) else {
+            changes.push(Change::new(
+                ChangeKind::RepoAdded,
+                &new.name,
+                String::new(),
+                new.branch.clone(),
+            ));
+            continue;
+        };
+        if old.dirty == 0 && new.dirty > 0 {
+            changes.push(Change::new(
+                ChangeKind::NewDirty,
+                &new.name,
+                "clean".to_string(),
+                format!("{} file(s)", new.dirty),
+            ));
+        } else if old.dirty > 0 && new.dirty == 0 {
+            changes.push(Change::new(
+                ChangeKind::Cleaned,
+                &new.name,
+                format!("{} file(s)", old.dirty),
+                "clean".to_string(),
+            ));
+        }
+        if old.branch != new.branch {
+            changes.push(Change::new(
+                ChangeKind::BranchChanged,
+                &new.name,
+                old.branch.clone(),
+                new.branch.clone(),
+            ));
+        }
+        if (old.ahead, old.behind) != (new.ahead, new.behind) {
+            changes.push(Change::new(
+                ChangeKind::SyncChanged,
+                &new.name,
+                sync(old),
+                sync(new),
+            ));
+        }
+    }
+    for (path, old) in &before {
+        if !after.contains_key(path) {
+            changes.push(Change::new(
+                ChangeKind::RepoRemoved,
+                &old.name,
+                old.branch.clone(),
+                String::new(),
+            ));
+        }
+    }
+
+    let old_alerts: BTreeSet<&AlertPoint> = from.alerts.iter().collect();
+    let new_alerts: BTreeSet<&AlertPoint> = to.alerts.iter().collect();
+    for alert in new_alerts.difference(&old_alerts) {
+        changes.push(Change::new(
+            ChangeKind::AlertNew,
+            &alert.title,
+            String::new(),
+            alert.severity.clone(),
+        ));
+    }
+    for alert in old_alerts.difference(&new_alerts) {
+        changes.push(Change::new(
+            ChangeKind::AlertResolved,
+            &alert.title,
+            alert.severity.clone(),
+            String::new(),
+        ));
+    }
+
+    let providers: BTreeSet<&String> = from.costs.keys().chain(to.costs.keys()).collect();
+    for provider in providers {
+        let old = from.costs.get(provider).copied().unwrap_or(0.0);
+        let new = to.costs.get(provider).copied().unwrap_or(0.0);
+        if (new - old).abs() >= 0.01 {
+            changes.push(Change::new(
+                ChangeKind::CostDelta,
+                provider,
+                format::currency(display, old),
+                format!(
+                    "{} ({})",
+                    format::currency(display, new),
+                    format::currency_delta(display, new - old)
+                ),
+            ));
+        }
+    }
+    changes
+}
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// Month-end spend from month-to-date cost `points` (oldest first) and the
+/// `current` total at `now`, for the month spanning `month` (Unix seconds).
+/// The daily rate is the spend between recorded days, later days weighing
+/// more; with fewer than two recorded days it is the average so far.
+fn forecast_month_end(points: &[(i64, f64)], current: f64, now: i64, month: (i64, i64)) -> f64 {
+    let (start, end) = month;
+    let today = (now - start) / DAY_SECS;
+    // The last total seen on each finished day; today's is still growing.
+    let mut day_ends: BTreeMap<i64, f64> = BTreeMap::new();
+    for &(at, cost) in points {
+        let day = (at - start).div_euclid(DAY_SECS);
+        if at >= start && day < today {
+            day_ends.insert(day, cost);
+        }
+    }
+    let days: Vec<(i64, f64)> = day_ends.into_iter().collect();
+    let (mut weighted, mut weights) = (0.0, 0.0);
+    for pair in days.windows(2) {
+        let ((from_day, from), (to_day, to)) = (pair[0], pair[1]);
+        let weight = (to_day + 1) as f64;
+        weighted += (to - from).max(0.0) / (to_day - from_day) as f64 * weight;
+        weights += weight;
+    }
+    let rate = if weights > 0.0 {
+        weighted / weights
+    } else {
+        current / ((now - start) as f64 / DAY_SECS as f64).max(1.0)
+    };
+    current + rate * (end - now).max(0) as f64 / DAY_SECS as f64
+}
+
+/// Start and end of the UTC month holding `now`, the window provider costs
+/// are reported for.
+fn month_bounds(now: DateTime<Utc>) -> Option<(i64, i64)> {
+    let start = Utc
+        .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()?;
+    let (year, month) = match now.month() {
+        12 => (now.year() + 1, 1),
+        month => (now.year(), month + 1),
+    };
+    let end = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+    Some((start.timestamp(), end.timestamp()))
+}
+
+/// Set each provider's month-end forecast and budget, and alert on budgets
+/// the forecast or the spend so far goes over.
+pub fn annotate_cost_forecasts(snapshot: &mut DashboardSnapshot, config: &Config) {
+    // A rolling lookback window is not month-to-date; nothing to project.
+    let lookback = std::env::var("AGENTPULSE_COST_LOOKBACK_DAYS")
+        .ok()
+        .and_then(|days| days.parse::<i64>().ok());
+    if lookback.is_some_and(|days| days > 0) {
+        return;
+    }
+    apply_cost_forecasts(snapshot, config, &load(&history_path()), Utc::now());
+}
+
+fn apply_cost_forecasts(
+    snapshot: &mut DashboardSnapshot,
+    config: &Config,
+    entries: &[HistoryEntry],
+    now: DateTime<Utc>,
+) {
+    let Some(month) = month_bounds(now) else {
+        return;
+    };
+    let display = &config.display;
+    let mut alerts = Vec::new();
+    for provider in snapshot.providers.iter_mut().filter(|p| p.configured) {
+        let name = provider.provider.as_str();
+        let points: Vec<(i64, f64)> = entries
+            .iter()
+            .filter_map(|entry| Some((entry.at, *entry.costs.get(name)?)))
+            .collect();
+        let spent = provider.estimated_cost_usd;
+        let forecast = forecast_month_end(&points, spent, now.timestamp(), month);
+        let budget = config
+            .providers
+            .budgets
+            .get(name)
+            .copied()
+            .filter(|b| *b > 0.0);
+        provider.forecast_usd = Some(forecast);
+        provider.budget_usd = budget;
+        let Some(budget) = budget else {
+            continue;
+        };
+        let alert = if spent >= budget {
+            (
+                "high",
+                format!("{} is over its monthly budget", name),
+                format!(
+                    "{} spent of {}",
+                    format::currency(display, spent),
+                    format::currency(display, budget)
+                ),
+            )
+        } else if forecast > budget {
+            (
+                "warn",
+                format!("{} is on pace to exceed its monthly budget", name),
+                format!(
+                    "forecast {} by month end · {} spent of {}",
+                    format::currency(display, forecast),
+                    format::currency(display, spent),
+                    format::currency(display, budget)
+                ),
+            )
+        } else {
+            continue;
+        };
+        let (severity, title, detail) = alert;
+        alerts.push(DashboardAlert {
+            severity: severity.to_string(),
+            title,
+            detail,
+            repo: None,
+            action: None,
+        });
+    }
+    if !alerts.is_empty() {
+        snapshot.alerts.extend(alerts);
+        dashboard::sort_alerts(&mut snapshot.alerts);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(name: &str, branch: &str, dirty: usize, ahead: usize) -> RepoPoint {
+        RepoPoint {
+            name: name.to_string(),
+            path: format!("/src/{}", name),
+            branch: branch.to_string(),
+            dirty,
+            ahead,
+            behind: 0,
+        }
+    }
+
+    fn alert(title: &str) -> AlertPoint {
+        AlertPoint {
+            severity: "warn".to_string(),
+            title: title.to_string(),
+            repo: None,
+        }
+    }
+
+    #[test]
+    fn compares_repos_alerts_and_costs() {
+        let from = HistoryEntry {
+            at: 0,
+            repos: vec![
+                point("api", "main", 0, 0),
+                point("web", "main", 3, 0),
+                point("old", "main", 0, 0),
+            ],
+            alerts: vec![alert("web is dirty"), alert("deps drift")],
+            costs: BTreeMap::from([("claude".to_string(), 10.0)]),
+        };
+        let to = HistoryEntry {
+            at: 3600,
+            repos: vec![
+                point("api", "feature/x", 2, 1),
+                point("web", "main", 0, 0),
+                point("new", "main", 0, 0),
+            ],
+            alerts: vec![alert("deps drift"), alert("api is dirty")],
+            costs: BTreeMap::from([("claude".to_string(), 12.5)]),
+        };
+        let changes = compare(&from, &to, &DisplayConfig::default());
+        let summary: Vec<(ChangeKind, &str)> = changes
+            .iter()
+            .map(|c| (c.kind, c.subject.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (ChangeKind::NewDirty, "api"),
+                (ChangeKind::BranchChanged, "api"),
+                (ChangeKind::SyncChanged, "api"),
+                (ChangeKind::RepoAdded, "new"),
+                (ChangeKind::Cleaned, "web"),
+                (ChangeKind::RepoRemoved, "old"),
+                (ChangeKind::AlertNew, "api is dirty"),
+                (ChangeKind::AlertResolved, "web is dirty"),
+                (ChangeKind::CostDelta, "claude"),
+            ]
+        );
+        let cost = changes.last().unwrap();
+        assert_eq!(
+            (cost.before.as_str(), cost.after.as_str()),
+            ("$10.00", "$12.50 (+$2.50)")
+        );
+        assert!(compare(&to, &to, &DisplayConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn work_ages_follow_the_trailing_dirty_run() {
+        let point = |path: &str, dirty: usize, ahead: usize| RepoPoint {
+            name: path.to_string(),
+            path: path.to_string(),
+            branch: "main".to_string(),
+            dirty,
+            ahead,
+            behind: 0,
+        };
+        let entry = |at: i64, repos: Vec<RepoPoint>| HistoryEntry {
+            at,
+            repos,
+            alerts: Vec::new(),
+            costs: BTreeMap::new(),
+        };
+        let entries = vec![
+            entry(100, vec![point("/a", 1, 0), point("/b", 2, 1)]),
+            entry(200, vec![point("/a", 0, 0), point("/b", 3, 1)]),
+            entry(300, vec![point("/a", 4, 2), point("/b", 1, 0)]),
+        ];
+        let ages = WorkAges::from_entries(&entries);
+
+        let repo = |path: &str, dirty: usize, ahead: usize| {
+            let mut repo = Repo::new(PathBuf::from(path));
+            repo.status.uncommitted_count = dirty;
+            repo.status.unpushed_count = ahead;
+            repo
+        };
+        let mut repos = vec![repo("/a", 5, 2), repo("/b", 1, 1), repo("/c", 1, 0)];
+        ages.apply(&mut repos);
+        assert_eq!(repos[0].status.dirty_since, Some(300));
+        assert_eq!(repos[0].status.unpushed_since, Some(300));
+        assert_eq!(repos[1].status.dirty_since, Some(100));
+        // Ahead again now, but not in the last entry: unknown, not old.
+        assert_eq!(repos[1].status.unpushed_since, None);
+        assert_eq!(repos[2].status.work_since(), None);
+    }
+
+    #[test]
+    fn off_hours_changes_arrive_as_one_digest() {
+        let entry = |at: i64, dirty: usize| HistoryEntry {
+            at,
+            repos: vec![RepoPoint {
+                name: "api".to_string(),
+                path: "/src/api".to_string(),
+                branch: "main".to_string(),
+                dirty,
+                ahead: 0,
+                behind: 0,
+            }],
+            alerts: Vec::new(),
+            costs: BTreeMap::new(),
+        };
+        let display = DisplayConfig::default();
+        let mut off_hours = OffHours::default();
+        assert_eq!(off_hours.observe(entry(1, 0), true, &display), None);
+        assert_eq!(off_hours.observe(entry(2, 0), true, &display), None);
+        assert_eq!(off_hours.observe(entry(3, 4), false, &display), None);
+        assert_eq!(off_hours.observe(entry(4, 5), false, &display), None);
+
+        let digest = off_hours.observe(entry(5, 5), true, &display).unwrap();
+        assert_eq!((digest.from.at, digest.to.at), (2, 5));
+        assert_eq!(digest.changes.len(), 1);
+        assert_eq!(digest.changes[0].kind, ChangeKind::NewDirty);
+        // Back to normal afterwards, and a quiet night yields nothing.
+        assert_eq!(off_hours.observe(entry(6, 0), true, &display), None);
+        off_hours.observe(entry(7, 0), false, &display);
+        assert_eq!(off_hours.observe(entry(8, 0), true, &display), None);
+    }
+
+    #[test]
+    fn records_at_most_one_entry_per_interval() {
+        let dir = std::env::temp_dir().join("agentpulse_history_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("history.jsonl");
+        let entry = |at| HistoryEntry {
+            at,
+            repos: Vec::new(),
+            alerts: Vec::new(),
+            costs: BTreeMap::new(),
+        };
+        assert!(record(&path, &entry(1_000)).unwrap());
+        assert!(!record(&path, &entry(1_000 + RECORD_INTERVAL_SECS - 1)).unwrap());
+        assert!(record(&path, &entry(1_000 + RECORD_INTERVAL_SECS)).unwrap());
+        let at: Vec<i64> = load(&path).iter().map(|e| e.at).collect();
+        assert_eq!(at, [1_000, 1_000 + RECORD_INTERVAL_SECS]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn forecasts_month_end_spend_and_alerts_over_budget() {
+        let now = Utc.with_ymd_and_hms(2026, 4, 11, 12, 0, 0).unwrap();
+        let month = month_bounds(now).unwrap();
+        let day = |d: i64| month.0 + d * DAY_SECS + DAY_SECS / 2;
+        // Spend picked up from 10 to 20 a day; the later day weighs more.
+        let points = [(day(7), 40.0), (day(8), 50.0), (day(9), 70.0)];
+        let forecast = forecast_month_end(&points, 75.0, now.timestamp(), month);
+        let rate = (10.0 * 9.0 + 20.0 * 10.0) / 19.0;
+        assert!((forecast - (75.0 + rate * 19.5)).abs() < 1e-9);
+        // Without history, the average so far carries on.
+        let linear = forecast_month_end(&[], 30.0, now.timestamp(), month);
+        assert!((linear - (30.0 + 30.0 / 10.5 * 19.5)).abs() < 1e-9);
+
+        let mut config = Config::default();
+        config.providers.budgets.insert("claude".to_string(), 300.0);
+        let mut snapshot = DashboardSnapshot::default();
+        snapshot.providers.push(crate::dashboard::ProviderUsage {
+            provider: crate::dashboard::ProviderKind::Claude,
+            configured: true,
+            config_sources: Vec::new(),
+            data_source: "live".to_string(),
+            source_updated_at_epoch_secs: 0,
+            sessions: 0,
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+            estimated_cost_usd: 75.0,
+            forecast_usd: None,
+            budget_usd: None,
+            notes: Vec::new(),
+        });
+        let entries: Vec<HistoryEntry> = points
+            .iter()
+            .map(|&(at, cost)| HistoryEntry {
+                at,
+                repos: Vec::new(),
+                alerts: Vec::new(),
+                costs: BTreeMap::from([("claude".to_string(), cost)]),
+            })
+            .collect();
+        apply_cost_forecasts(&mut snapshot, &config, &entries, now);
+        assert_eq!(snapshot.providers[0].budget_usd, Some(300.0));
+        assert_eq!(
+            snapshot.alerts.len(),
+            1,
+            "still under budget, but not for long"
+        );
+        assert_eq!(snapshot.alerts[0].severity, "warn");
+        assert_eq!(
+            snapshot.alerts[0].title,
+            "claude is on pace to exceed its monthly budget"
+        );
+    }
+}
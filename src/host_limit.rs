@@ -0,0 +1,100 @@
+//! Per-host limits for actions that talk to a remote.
+//!
+//! Fetching dozens of repos at once from one host gets throttled by GitHub
+//! and swamps SSH connection multiplexing. Network actions take a slot for
+//! their remote host first: at most `[actions] max_per_host` run against one
+//! host at a time, and their starts are `host_spacing_ms` apart. Other hosts
+//! are not held up.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+struct HostGate {
+    limit: usize,
+    slots: Arc<Semaphore>,
+    /// Earliest start of the next action against this host.
+    next_start: tokio::sync::Mutex<Instant>,
+}
+
+impl HostGate {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            slots: Arc::new(Semaphore::new(limit.max(1))),
+            next_start: tokio::sync::Mutex::new(Instant::now()),
+        }
+    }
+}
+
+static GATES: OnceLock<Mutex<HashMap<String, Arc<HostGate>>>> = OnceLock::new();
+
+fn gate(host: &str, limit: usize) -> Arc<HostGate> {
+    let mut gates = GATES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let gate = gates
+        .entry(host.to_string())
+        .or_insert_with(|| Arc::new(HostGate::new(limit)));
+    // A changed limit starts a fresh gate; actions holding the old one
+    // finish undisturbed.
+    if gate.limit != limit {
+        *gate = Arc::new(HostGate::new(limit));
+    }
+    Arc::clone(gate)
+}
+
+/// Held while an action talks to its host; dropping it frees the slot.
+pub struct HostSlot {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+/// Wait for a slot on `host`: fewer than `limit` others in flight (0 = no
+/// limit) and `spacing` since the previous start.
+pub async fn acquire(host: &str, limit: usize, spacing: Duration) -> HostSlot {
+    if limit == 0 && spacing.is_zero() {
+        return HostSlot { _permit: None };
+    }
+    let gate = gate(host, limit);
+    let permit = match limit {
+        0 => None,
+        _ => Arc::clone(&gate.slots).acquire_owned().await.ok(),
+    };
+    if !spacing.is_zero() {
+        let mut next = gate.next_start.lock().await;
+        tokio::time::sleep_until(*next).await;
+        *next = Instant::now() + spacing;
+    }
+    HostSlot { _permit: permit }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn caps_concurrency_per_host_and_spaces_starts() {
+        let first = acquire("limit-test.example", 2, Duration::ZERO).await;
+        let _second = acquire("limit-test.example", 2, Duration::ZERO).await;
+        let third = tokio::time::timeout(
+            Duration::from_millis(50),
+            acquire("limit-test.example", 2, Duration::ZERO),
+        )
+        .await;
+        assert!(third.is_err(), "a third fetch waits for a free slot");
+        // Another host is not held up by this one.
+        let _other = acquire("other-test.example", 2, Duration::ZERO).await;
+        drop(first);
+        let _third = acquire("limit-test.example", 2, Duration::ZERO).await;
+
+        let spacing = Duration::from_millis(80);
+        let started = Instant::now();
+        for _ in 0..3 {
+            acquire("spacing-test.example", 0, spacing).await;
+        }
+        assert!(started.elapsed() >= spacing * 2);
+    }
+}
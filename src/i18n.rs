@@ -0,0 +1,289 @@
+//! Message catalog for user-facing text.
+//!
+//! Messages live in Fluent-style `.ftl` files: `key = text` with `{ $name }`
+//! placeables, `#` comments, and indented continuation lines for multi-line
+//! values. English is compiled in from `locales/en.ftl`; a translation is a
+//! `<lang>.ftl` next to it (listed in [`BUNDLED`]) or in
+//! `~/.config/agentpulse/locales/`, and only needs the keys it translates.
+//! Missing keys fall back to English, then to the key itself.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+/// The reference catalog every other language falls back to.
+pub const ENGLISH: &str = include_str!("../locales/en.ftl");
+
+/// Catalogs shipped in the binary, by language tag.
+pub const BUNDLED: &[(&str, &str)] = &[("en", ENGLISH)];
+
+/// Parsed messages of one language.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Parse `.ftl` source; lines that are not messages are ignored.
+    pub fn parse(source: &str) -> Self {
+        let mut messages: HashMap<String, String> = HashMap::new();
+        let mut current: Option<String> = None;
+        for line in source.lines() {
+            if line.starts_with([' ', '\t']) && !line.trim().is_empty() {
+                if let Some(value) = current.as_ref().and_then(|key| messages.get_mut(key)) {
+                    if !value.is_empty() {
+                        value.push('\n');
+                    }
+                    value.push_str(line.trim());
+                }
+                continue;
+            }
+            current = None;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = trimmed.split_once('=') {
+                let key = key.trim();
+                if !key.is_empty() && !key.contains(char::is_whitespace) {
+                    messages.insert(key.to_string(), value.trim().to_string());
+                    current = Some(key.to_string());
+                }
+            }
+        }
+        Self { messages }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+}
+
+/// Rewrites a message template before its placeables are filled.
+pub type TemplateFilter = for<'a> fn(&'a str) -> Cow<'a, str>;
+
+struct Active {
+    translation: Catalog,
+    english: Catalog,
+    filter: Option<TemplateFilter>,
+}
+
+static ACTIVE: OnceLock<RwLock<Active>> = OnceLock::new();
+
+fn active() -> &'static RwLock<Active> {
+    ACTIVE.get_or_init(|| {
+        RwLock::new(Active {
+            translation: Catalog::default(),
+            english: Catalog::parse(ENGLISH),
+            filter: None,
+        })
+    })
+}
+
+/// The `language` setting as a tag: `auto` reads `LC_ALL`, `LC_MESSAGES`
+/// and `LANG` (`pt_BR.UTF-8` becomes `pt-BR`); `C` and `POSIX` are English.
+pub fn resolve_language(setting: &str) -> String {
+    let raw = if setting.trim().is_empty() || setting.trim() == "auto" {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+    } else {
+        setting.trim().to_string()
+    };
+    let tag = raw
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-");
+    match tag.as_str() {
+        "" | "C" | "POSIX" => "en".to_string(),
+        _ => tag,
+    }
+}
+
+/// Where user translations live: `~/.config/agentpulse/locales/`.
+pub fn locales_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".config")
+        .join("agentpulse")
+        .join("locales")
+}
+
+/// Catalog for `tag`, trying the full tag then the base language (`pt-BR`,
+/// then `pt`), user files before bundled ones. `None` when nothing matches.
+pub fn load_catalog(tag: &str, user_dir: &Path) -> Option<Catalog> {
+    let base = tag.split('-').next().unwrap_or(tag);
+    let candidates = if base == tag {
+        vec![tag]
+    } else {
+        vec![tag, base]
+    };
+    candidates.into_iter().find_map(|candidate| {
+        std::fs::read_to_string(user_dir.join(format!("{}.ftl", candidate)))
+            .ok()
+            .or_else(|| {
+                BUNDLED
+                    .iter()
+                    .find(|(bundled, _)| *bundled == candidate)
+                    .map(|(_, source)| source.to_string())
+            })
+            .map(|source| Catalog::parse(&source))
+    })
+}
+
+/// Switch the catalog used by [`t`] and [`tr`]. Returns the language in use,
+/// which is `en` when no catalog matches the setting.
+pub fn set_language(setting: &str) -> String {
+    let tag = resolve_language(setting);
+    let (language, translation) = match load_catalog(&tag, &locales_dir()) {
+        Some(catalog) => (tag, catalog),
+        None => ("en".to_string(), Catalog::default()),
+    };
+    if let Ok(mut active) = active().write() {
+        active.translation = translation;
+    }
+    language
+}
+
+/// Pass every template through `filter` (e.g. the TUI's ASCII glyphs) while
+/// leaving the values filled into it, such as repo names, untouched.
+pub fn set_template_filter(filter: TemplateFilter) {
+    if let Ok(mut active) = active().write() {
+        active.filter = Some(filter);
+    }
+}
+
+/// The message for `key`.
+pub fn t(key: &str) -> String {
+    tr(key, &[])
+}
+
+/// The message for `key` with `{ $name }` placeables filled from `args`.
+pub fn tr(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let Ok(active) = active().read() else {
+        return key.to_string();
+    };
+    let template = active
+        .translation
+        .get(key)
+        .or_else(|| active.english.get(key))
+        .unwrap_or(key);
+    match active.filter {
+        Some(filter) => format_message(&filter(template), args),
+        None => format_message(template, args),
+    }
+}
+
+/// Fill `{ $name }` placeables; unknown ones are left as written.
+fn format_message(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let placeable = &rest[start..start + len + 1];
+        let name = placeable[1..placeable.len() - 1]
+            .trim()
+            .trim_start_matches('$');
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => out.push_str(&value.to_string()),
+            None => out.push_str(placeable),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_placeables_comments_and_multiline_values() {
+        let catalog = Catalog::parse(
+            "# comment\n## group\ngreeting = Hello, { $name }!\nlong =\n    first line\n    second line\n\nbroken line\n",
+        );
+        assert_eq!(catalog.get("greeting"), Some("Hello, { $name }!"));
+        assert_eq!(catalog.get("long"), Some("first line\nsecond line"));
+        assert_eq!(catalog.messages.len(), 2);
+        assert_eq!(
+            format_message(
+                "{ $count } of { $total } ({ $other })",
+                &[("count", &3), ("total", &"5")]
+            ),
+            "3 of 5 ({ $other })"
+        );
+    }
+
+    #[test]
+    fn resolves_languages_with_fallback_to_base_and_english() {
+        assert_eq!(resolve_language("pt_BR.UTF-8"), "pt-BR");
+        assert_eq!(resolve_language("C"), "en");
+        assert_eq!(resolve_language("de"), "de");
+
+        let dir = std::env::temp_dir().join("agentpulse_i18n_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("pt.ftl"), "hint-quit = sair\n").unwrap();
+        let pt = load_catalog("pt-BR", &dir).unwrap();
+        assert_eq!(pt.get("hint-quit"), Some("sair"));
+        assert_eq!(pt.get("hint-help"), None);
+        assert!(load_catalog("en", &dir).is_some());
+        assert!(load_catalog("xx", &dir).is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Every `t("…")` / `tr("…", …)` key used in the source exists in English.
+    #[test]
+    fn english_catalog_covers_every_key_in_use() {
+        let english = Catalog::parse(ENGLISH);
+        let mut missing = Vec::new();
+        let mut pending = vec![PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src")];
+        while let Some(dir) = pending.pop() {
+            for entry in std::fs::read_dir(&dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
+                    let source = std::fs::read_to_string(&path).unwrap();
+                    for key in used_keys(&source) {
+                        if english.get(key).is_none() {
+                            missing.push(format!("{}: {}", path.display(), key));
+                        }
+                    }
+                }
+            }
+        }
+        assert!(
+            missing.is_empty(),
+            "missing from locales/en.ftl: {missing:?}"
+        );
+    }
+
+    fn used_keys(source: &str) -> Vec<&str> {
+        let mut keys = Vec::new();
+        for call in ["t(\"", "tr(\""] {
+            for (at, _) in source.match_indices(call) {
+                let ident_before = source[..at]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_');
+                let rest = &source[at + call.len()..];
+                let key = rest.split('"').next().unwrap_or_default();
+                // Keys are kebab-case; this skips unrelated `t("` matches.
+                if !ident_before && key.starts_with(char::is_lowercase) && key.contains('-') {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+}
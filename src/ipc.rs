@@ -0,0 +1,462 @@
+//! Instance socket: single-instance detection and `agentpulse ctl`.
+//!
+//! The first TUI binds `~/.config/agentpulse/agentpulse.sock` and serves its
+//! latest scan there. A second TUI finds the socket, and can attach to that
+//! data instead of scanning the same repos again; scripts can ask for a
+//! refresh or run an action. The protocol is one JSON request per line,
+//! answered by one JSON line, e.g. `{"cmd":"refresh"}`.
+
+use crate::actions;
+use crate::dashboard::{ActionKind, DashboardSnapshot};
+use crate::git::{Repo, RepoStatus};
+use crate::i18n::t;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+
+/// How long a client waits for the other instance to answer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How this TUI relates to other running instances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceRole {
+    /// Scans, serves the socket, and runs actions.
+    Primary,
+    /// Mirrors the primary's snapshot; never scans on its own.
+    Attached,
+    /// Scans on its own but writes no shared state and runs no actions.
+    ReadOnly,
+    /// Shows the synthetic `--demo` workspace; never scans or writes.
+    Demo,
+    /// Shows a saved snapshot from `--replay`; never scans or writes.
+    Replay,
+}
+
+impl InstanceRole {
+    pub fn is_read_only(self) -> bool {
+        self != InstanceRole::Primary
+    }
+
+    /// Status-bar label; `None` for the primary instance.
+    pub fn label(self) -> Option<String> {
+        let key = match self {
+            InstanceRole::Primary => return None,
+            InstanceRole::Attached => "summary-role-attached",
+            InstanceRole::ReadOnly => "summary-role-read-only",
+            InstanceRole::Demo => "summary-role-demo",
+            InstanceRole::Replay => "summary-role-replay",
+        };
+        Some(t(key))
+    }
+
+    /// Shown when a read-only role is asked to change something.
+    pub fn read_only_notice(self) -> String {
+        t(match self {
+            InstanceRole::Demo => "notify-demo-read-only",
+            InstanceRole::Replay => "notify-replay-read-only",
+            _ => "notify-instance-read-only",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "kebab-case")]
+pub enum Request {
+    Ping,
+    GetSnapshot,
+    /// Rescan now (queued behind a scan already running).
+    Refresh,
+    /// Run an action with the TUI's checks but without its confirmation,
+    /// e.g. `{"cmd":"run-action","action":{"type":"git_fetch","repo_path":"/src/app"}}`.
+    /// Destructive actions are refused unless `allow_destructive` is set.
+    RunAction {
+        action: ActionKind,
+        #[serde(default)]
+        allow_destructive: bool,
+    },
+}
+
+/// Work a socket client asked the event loop to do.
+#[derive(Debug, Clone)]
+pub enum ControlCommand {
+    Refresh,
+    /// Checked again by the event loop against the live config.
+    RunAction {
+        action: ActionKind,
+        allow_destructive: bool,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Response {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<SharedSnapshot>,
+}
+
+impl Response {
+    fn error(msg: impl Into<String>) -> Self {
+        Self {
+            error: Some(msg.into()),
+            ..Self::default()
+        }
+    }
+}
+
+/// The latest scan of the primary instance.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SharedSnapshot {
+    pub repos: Vec<Repo>,
+    pub dashboard: DashboardSnapshot,
+}
+
+impl SharedSnapshot {
+    /// Parse `agentpulse ctl get-snapshot` output, or a bare
+    /// `--dashboard-json` snapshot whose repos are rebuilt from its rows.
+    pub fn from_export(raw: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(raw)?;
+        if value.get("dashboard").is_some() {
+            return Ok(serde_json::from_value(value)?);
+        }
+        let dashboard: DashboardSnapshot = serde_json::from_value(value)?;
+        let repos = dashboard
+            .repos
+            .iter()
+            .map(|row| {
+                let mut repo = Repo::new(PathBuf::from(&row.path));
+                repo.name = row.name.clone();
+                repo.status = RepoStatus {
+                    branch: row.branch.clone(),
+                    uncommitted_count: row.dirty,
+                    changes: row.changes,
+                    unpushed_count: row.ahead,
+                    behind_count: row.behind,
+                    stash_count: row.stash,
+                    // Rows keep only the recommendation, which implies these.
+                    has_remote: row.recommendation != "set remote",
+                    is_detached: row.recommendation == "reattach",
+                    missing_upstream: row.recommendation == "publish",
+                    ..RepoStatus::default()
+                };
+                repo
+            })
+            .collect();
+        Ok(Self { repos, dashboard })
+    }
+}
+
+/// Socket location: `~/.config/agentpulse/agentpulse.sock`.
+pub fn socket_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".config")
+        .join("agentpulse")
+        .join("agentpulse.sock")
+}
+
+/// Channels a server uses to read and drive the running instance.
+#[derive(Clone)]
+pub struct Controls {
+    pub snapshot: watch::Receiver<SharedSnapshot>,
+    pub commands: mpsc::Sender<ControlCommand>,
+    /// `[actions] allowed_programs` when the socket was opened.
+    pub allowed_programs: Vec<String>,
+}
+
+fn respond(request: Request, controls: &Controls) -> Response {
+    let command = match request {
+        Request::Ping => {
+            return Response {
+                ok: true,
+                pid: Some(std::process::id()),
+                ..Response::default()
+            }
+        }
+        Request::GetSnapshot => {
+            return Response {
+                ok: true,
+                snapshot: Some(controls.snapshot.borrow().clone()),
+                ..Response::default()
+            }
+        }
+        Request::Refresh => ControlCommand::Refresh,
+        Request::RunAction {
+            action,
+            allow_destructive,
+        } => {
+            // No one confirms a socket request, so it gets the same checks
+            // as `agentpulse run`.
+            if let Err(e) =
+                actions::check_unconfirmed(&action, &controls.allowed_programs, allow_destructive)
+            {
+                return Response::error(e.to_string());
+            }
+            ControlCommand::RunAction {
+                action,
+                allow_destructive,
+            }
+        }
+    };
+    match controls.commands.try_send(command) {
+        Ok(()) => Response {
+            ok: true,
+            ..Response::default()
+        },
+        Err(_) => Response::error("instance is busy; try again"),
+    }
+}
+
+fn respond_line(line: &str, controls: &Controls) -> Response {
+    match serde_json::from_str::<Request>(line) {
+        Ok(request) => respond(request, controls),
+        Err(e) => Response::error(format!("bad request: {}", e)),
+    }
+}
+
+/// A bound socket; stops serving and removes the socket file when dropped.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub struct Server {
+    path: PathBuf,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.task.abort();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Serve `controls` on `path`. Call only after `probe` found no live
+/// instance: a leftover socket file is treated as stale and replaced.
+#[cfg(unix)]
+pub fn serve(path: &Path, controls: Controls) -> Result<Server> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let _ = std::fs::remove_file(path);
+    // The socket can run actions: keep it to the current user. It is bound
+    // in a private directory and only moved into place once it is 0600, so
+    // it is never reachable with the umask's permissions.
+    let staging = path.with_extension(format!("{}.d", std::process::id()));
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::DirBuilder::new().mode(0o700).create(&staging)?;
+    let staged = staging.join("agentpulse.sock");
+    let bound = tokio::net::UnixListener::bind(&staged).and_then(|listener| {
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+        std::fs::rename(&staged, path)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_dir_all(&staging);
+    let listener = bound?;
+    let task = tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let controls = controls.clone();
+            tokio::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut lines = BufReader::new(read).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let response = respond_line(&line, &controls);
+                    let Ok(mut out) = serde_json::to_string(&response) else {
+                        break;
+                    };
+                    out.push('\n');
+                    if write.write_all(out.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    Ok(Server {
+        path: path.to_path_buf(),
+        task,
+    })
+}
+
+#[cfg(not(unix))]
+pub fn serve(_path: &Path, _controls: Controls) -> Result<Server> {
+    anyhow::bail!("instance sockets are only supported on Unix")
+}
+
+/// Send one request to the instance serving `path`.
+#[cfg(unix)]
+pub async fn request(path: &Path, request: &Request) -> Result<Response> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let exchange = async {
+        let stream = tokio::net::UnixStream::connect(path).await?;
+        let (read, mut write) = stream.into_split();
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        write.write_all(line.as_bytes()).await?;
+        let mut reply = String::new();
+        BufReader::new(read).read_line(&mut reply).await?;
+        let response: Response = serde_json::from_str(&reply)?;
+        if !response.ok {
+            anyhow::bail!(response
+                .error
+                .unwrap_or_else(|| "request failed".to_string()));
+        }
+        Ok(response)
+    };
+    tokio::time::timeout(REQUEST_TIMEOUT, exchange)
+        .await
+        .map_err(|_| anyhow::anyhow!("no answer from {}", path.display()))?
+}
+
+#[cfg(not(unix))]
+pub async fn request(_path: &Path, _request: &Request) -> Result<Response> {
+    anyhow::bail!("instance sockets are only supported on Unix")
+}
+
+/// Pid of an instance already serving `path`, if one answers.
+pub async fn probe(path: &Path) -> Option<u32> {
+    request(path, &Request::Ping).await.ok()?.pid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controls() -> (
+        watch::Sender<SharedSnapshot>,
+        mpsc::Receiver<ControlCommand>,
+        Controls,
+    ) {
+        let (snapshot_tx, snapshot) = watch::channel(SharedSnapshot::default());
+        let (commands, commands_rx) = mpsc::channel(1);
+        let controls = Controls {
+            snapshot,
+            commands,
+            allowed_programs: vec!["git".to_string()],
+        };
+        (snapshot_tx, commands_rx, controls)
+    }
+
+    #[test]
+    fn requests_use_kebab_case_commands() {
+        assert_eq!(
+            serde_json::to_string(&Request::GetSnapshot).unwrap(),
+            r#"{"cmd":"get-snapshot"}"#
+        );
+        let (_tx, _rx, controls) = controls();
+        let response = respond_line(r#"{"cmd":"reboot"}"#, &controls);
+        assert!(!response.ok);
+        assert!(response.error.unwrap().contains("bad request"));
+    }
+
+    #[test]
+    fn commands_are_forwarded_to_the_event_loop() {
+        let (_tx, mut rx, controls) = controls();
+        let line = r#"{"cmd":"run-action","action":{"type":"git_fetch","repo_path":"/src/app"}}"#;
+        assert!(respond_line(line, &controls).ok);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(ControlCommand::RunAction { action: ActionKind::GitFetch { repo_path }, .. }) if repo_path == "/src/app"
+        ));
+
+        assert!(respond_line(r#"{"cmd":"refresh"}"#, &controls).ok);
+        // The queue holds one command; a second is refused rather than blocking.
+        let busy = respond_line(r#"{"cmd":"refresh"}"#, &controls);
+        assert!(!busy.ok);
+        assert!(matches!(rx.try_recv(), Ok(ControlCommand::Refresh)));
+    }
+
+    #[test]
+    fn destructive_actions_need_an_explicit_opt_in() {
+        let (_tx, mut rx, controls) = controls();
+        let stash_drop = r#"{"cmd":"run-action","action":{"type":"git_stash_drop","repo_path":"/src/app","stash":"stash@{0}"}}"#;
+        let refused = respond_line(stash_drop, &controls);
+        assert!(refused.error.unwrap().contains("--allow-destructive"));
+        assert!(rx.try_recv().is_err());
+
+        let allowed = stash_drop.replace(r#"}}"#, r#"},"allow_destructive":true}"#);
+        assert!(respond_line(&allowed, &controls).ok);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(ControlCommand::RunAction {
+                action: ActionKind::GitStashDrop { .. },
+                allow_destructive: true
+            })
+        ));
+    }
+
+    #[test]
+    fn actions_naming_programs_need_the_allowlist() {
+        let (_tx, mut rx, controls) = controls();
+        let probe =
+            r#"{"cmd":"run-action","action":{"type":"probe_binary_help","binary":"/tmp/x"}}"#;
+        let refused = respond_line(probe, &controls);
+        assert!(refused.error.unwrap().contains("allowed_programs"));
+        let scaffold = r#"{"cmd":"run-action","action":{"type":"scaffold_files","repo_path":"/src/app","files":[{"path":"../../etc/cron.d/x","template":"/tmp/t"}]}}"#;
+        assert!(!respond_line(scaffold, &controls).ok);
+        assert!(rx.try_recv().is_err());
+
+        let git = r#"{"cmd":"run-action","action":{"type":"probe_binary_help","binary":"git"}}"#;
+        assert!(respond_line(git, &controls).ok);
+        assert!(rx.try_recv().is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn attached_client_reads_the_published_snapshot() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("agentpulse_ipc_{}.sock", std::process::id()));
+        assert_eq!(probe(&path).await, None);
+
+        let (tx, _rx, controls) = controls();
+        let server = serve(&path, controls).unwrap();
+        assert_eq!(probe(&path).await, Some(std::process::id()));
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        tx.send_modify(|s| s.repos.push(Repo::new(PathBuf::from("/tmp/demo"))));
+        let snapshot = request(&path, &Request::GetSnapshot)
+            .await
+            .unwrap()
+            .snapshot
+            .unwrap();
+        assert_eq!(snapshot.repos.len(), 1);
+        assert_eq!(snapshot.repos[0].name, "demo");
+
+        drop(server);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn replays_either_export_format() {
+        let repo = Repo::new(PathBuf::from("/src/app"));
+        let shared = SharedSnapshot {
+            repos: vec![repo],
+            dashboard: DashboardSnapshot::default(),
+        };
+        let raw = serde_json::to_string(&shared).unwrap();
+        let replayed = SharedSnapshot::from_export(&raw).unwrap();
+        assert_eq!(replayed.repos[0].path, PathBuf::from("/src/app"));
+
+        let dashboard = r#"{
+            "schema_version": 1,
+            "generated_at_epoch_secs": 1700000000,
+            "repos": [{"name": "api", "path": "/src/api", "branch": "main", "dirty": 2,
+                       "ahead": 0, "behind": 0, "stash": 0, "recommendation": "set remote",
+                       "action": null}]
+        }"#;
+        let replayed = SharedSnapshot::from_export(dashboard).unwrap();
+        assert_eq!(replayed.dashboard.generated_at_epoch_secs, 1700000000);
+        let status = &replayed.repos[0].status;
+        assert_eq!((status.uncommitted_count, status.has_remote), (2, false));
+
+        assert!(SharedSnapshot::from_export("42").is_err());
+    }
+}
@@ -0,0 +1,166 @@
+//! Normal-mode key bindings, as listed in the help overlay.
+//!
+//! The dispatch in `main.rs` and this list must agree: a key handled there
+//! belongs here too, with the sections it works in. A test checks that
+//! every key the Normal-mode dispatch matches is listed.
+
+use crate::dashboard::DashboardSection;
+use crate::i18n::t;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Navigation,
+    Actions,
+    Git,
+    General,
+}
+
+impl Category {
+    pub fn all() -> [Category; 4] {
+        [
+            Category::Navigation,
+            Category::Actions,
+            Category::Git,
+            Category::General,
+        ]
+    }
+
+    pub fn title(self) -> String {
+        match self {
+            Category::Navigation => t("help-cat-navigation"),
+            Category::Actions => t("help-cat-actions"),
+            Category::Git => t("help-cat-git"),
+            Category::General => t("help-cat-general"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub keys: &'static str,
+    pub description: String,
+    pub category: Category,
+    /// Sections where the key does something; empty for every section.
+    pub sections: &'static [DashboardSection],
+}
+
+impl Binding {
+    pub fn applies_to(&self, section: DashboardSection) -> bool {
+        self.sections.is_empty() || self.sections.contains(&section)
+    }
+
+    /// Case-insensitive match on the keys or the description.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.keys.to_lowercase().contains(&query)
+            || self.description.to_lowercase().contains(&query)
+    }
+}
+
+const HOME: &[DashboardSection] = &[DashboardSection::Home];
+const REPOS: &[DashboardSection] = &[DashboardSection::Repos];
+const HOME_REPOS: &[DashboardSection] = &[DashboardSection::Home, DashboardSection::Repos];
+const PULL_REQUESTS: &[DashboardSection] = &[DashboardSection::PullRequests];
+const SETTINGS: &[DashboardSection] = &[DashboardSection::Settings];
+
+/// Every Normal-mode binding, in help order.
+pub fn bindings() -> Vec<Binding> {
+    use Category::*;
+    let bind = |keys, description, category, sections| Binding {
+        keys,
+        description,
+        category,
+        sections,
+    };
+    vec![
+        bind("h/l Tab", t("help-switch-section"), Navigation, &[]),
+        bind("1..9, 0", t("help-jump-section"), Navigation, &[]),
+        bind("j / ↓", t("help-move-down"), Navigation, &[]),
+        bind("k / ↑", t("help-move-up"), Navigation, &[]),
+        bind("m1..m9", t("help-bookmark"), Navigation, REPOS),
+        bind("'1..'9", t("help-jump-bookmark"), Navigation, &[]),
+        bind("Ctrl-P", t("help-recent-repos"), Navigation, &[]),
+        bind("x / X", t("help-review-action"), Actions, &[]),
+        bind("Enter / y", t("help-confirm-action"), Actions, &[]),
+        bind("Esc / n", t("help-cancel-action"), Actions, &[]),
+        bind("r", t("help-refresh"), Actions, &[]),
+        bind("/", t("help-filter"), Actions, &[]),
+        bind("G", t("help-grep"), Actions, &[]),
+        bind("T", t("help-time-travel"), Actions, &[]),
+        bind("I", t("help-integrations"), Actions, &[]),
+        bind("Enter", t("help-run-next-best"), Actions, HOME),
+        bind("n", t("help-skip-next-best"), Actions, HOME),
+        bind("t", t("help-task"), Actions, HOME_REPOS),
+        bind("Enter", t("help-open-editor"), Actions, REPOS),
+        bind("o", t("help-open-files"), Actions, REPOS),
+        bind("O", t("help-open-ticket"), Actions, REPOS),
+        bind("Enter", t("help-open-pull-request"), Actions, PULL_REQUESTS),
+        bind("f", t("help-fetch"), Git, REPOS),
+        bind("p", t("help-pull"), Git, REPOS),
+        bind("P", t("help-push"), Git, REPOS),
+        bind("c", t("help-commit"), Git, REPOS),
+        bind("u", t("help-undo-commit"), Git, REPOS),
+        bind("z", t("help-stash"), Git, REPOS),
+        bind("B", t("help-branch"), Git, REPOS),
+        bind("L", t("help-commit-log"), Git, REPOS),
+        bind("D", t("help-diff"), Git, REPOS),
+        bind("Z", t("help-stash-list"), Git, REPOS),
+        bind("C", t("help-cherry-pick"), Git, REPOS),
+        bind("b", t("help-bundle"), Git, REPOS),
+        bind("g", t("help-group"), General, REPOS),
+        bind("A", t("help-actionable"), General, REPOS),
+        bind("d", t("help-dismiss"), General, REPOS),
+        bind("Enter", t("help-edit-setting"), General, SETTINGS),
+        bind("w", t("help-save-settings"), General, SETTINGS),
+        bind("R", t("help-reload-settings"), General, SETTINGS),
+        bind("s", t("help-setup"), General, &[]),
+        bind("?", t("help-toggle-help"), General, &[]),
+        bind("q / Q / Ctrl-C", t("help-quit"), General, &[]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_every_key_the_normal_mode_dispatch_handles() {
+        let source = include_str!("main.rs");
+        let start = source.find("AppMode::Normal => match key.code {").unwrap();
+        let end = start + source[start..].find("        AppMode::Search =>").unwrap();
+        let listed: Vec<String> = bindings()
+            .iter()
+            .flat_map(|b| b.keys.split([' ', ',']))
+            .flat_map(|token| {
+                // "h/l" lists both keys; a bare "/" is the key itself. Case
+                // matters: `d` and `D` are different keys.
+                let mut keys = vec![token.to_string()];
+                keys.extend(token.split('/').map(str::to_string));
+                keys
+            })
+            .collect();
+        let mut missing = Vec::new();
+        for (at, _) in source[start..end].match_indices("KeyCode::Char('") {
+            let key = source[start + at + 15..].chars().next().unwrap();
+            if !listed.contains(&key.to_string()) {
+                missing.push(key);
+            }
+        }
+        assert!(
+            missing.is_empty(),
+            "keys missing from the keymap: {missing:?}"
+        );
+    }
+
+    #[test]
+    fn filters_by_section_and_query() {
+        let all = bindings();
+        let push = all.iter().find(|b| b.keys == "P").unwrap();
+        assert!(push.applies_to(DashboardSection::Repos));
+        assert!(!push.applies_to(DashboardSection::Worktrees));
+        assert!(push.matches("PUSH"));
+        assert!(!push.matches("stash"));
+        assert!(all.iter().filter(|b| b.matches("")).count() == all.len());
+    }
+}
@@ -1,9 +1,55 @@
+//! Workspace scanning and recommendations behind the `agentpulse` TUI.
+//!
+//! The crate root is the stable surface: [`scan`] finds and checks the
+//! configured repos, [`snapshot`] runs the collectors over them, and
+//! [`recommend`] picks the next action for one repo. Build with
+//! `default-features = false` to leave out the terminal UI (ratatui,
+//! crossterm, clap).
+//!
+//! ```no_run
+//! # async fn run() -> anyhow::Result<()> {
+//! let config = agentpulse::load_config(None)?;
+//! let repos = agentpulse::scan(&config).await;
+//! for repo in &repos {
+//!     let rec = agentpulse::recommend(repo);
+//!     println!("{}: {}", repo.name, rec.action);
+//! }
+//! let snapshot = agentpulse::snapshot(&repos);
+//! println!("{} alerts", snapshot.alerts.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The modules below are public too, but may change between minor releases.
+
 pub mod agent;
 pub mod collectors;
 pub mod dashboard;
 // Re-export modules so integration tests in tests/ can access them.
 pub mod config;
+pub mod credentials;
 pub mod git;
+pub mod i18n;
 pub mod monitor;
 pub mod path_utils;
+pub mod repo_index;
+pub mod scan_cache;
 pub mod scanner;
+
+pub use agent::{recommend, ActionPriority, Recommendation};
+pub use collectors::CollectMode;
+pub use config::{load_config, Config};
+pub use dashboard::{ActionCommand, ActionKind, DashboardAlert, DashboardSnapshot};
+pub use git::{Repo, RepoStatus};
+
+/// Find every repo under the configured watch directories and check its
+/// git status. Dirty repos sort first.
+pub async fn scan(config: &Config) -> Vec<Repo> {
+    monitor::scan_all(config, &mut monitor::StatusCache::new()).await
+}
+
+/// Run every collector over `repos` (usage APIs included) and assemble the
+/// dashboard snapshot.
+pub fn snapshot(repos: &[Repo]) -> DashboardSnapshot {
+    dashboard::collect_and_build(repos)
+}
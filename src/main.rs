@@ -1,41 +1,85 @@
 mod actions;
 mod agent;
 mod app;
+mod audit;
+mod cache;
+mod codeowners;
 mod collectors;
 mod config;
+mod config_docs;
+mod credentials;
 mod dashboard;
+mod demo;
+mod forge_links;
+mod format;
 mod git;
+mod history;
+mod host_limit;
+mod i18n;
+mod ipc;
+mod keymap;
 mod monitor;
 mod path_utils;
+mod plain;
+mod power;
+mod push_check;
+mod recent;
+mod release;
+mod repo_index;
+mod scan_cache;
 mod scanner;
+mod search;
+mod settings;
 mod setup;
+mod state;
+mod suppress;
 mod ui;
+mod watcher;
 
 use agent::{needs_attention as needs_agent_attention, sorted_recommendations, ActionPriority};
 use anyhow::Result;
-use app::{App, AppMode};
-use chrono::Local;
-use clap::Parser;
+use app::{
+    App, AppMode, BranchPicker, CherryPicker, CommitLog, DiffView, RemoteWizard, StashBrowser,
+    StashOp, StashPicker, TimeTravel, WizardField,
+};
+use chrono::{Local, TimeZone};
+use clap::{Parser, Subcommand, ValueEnum};
+use codeowners::Ownership;
+use collectors::CollectMode;
 use config::{default_config_path, legacy_config_path};
 use crossterm::{
     event::{Event, KeyCode, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use forge_links::RepoLinks;
 use git::Repo;
+use i18n::{t, tr};
+use ipc::InstanceRole;
 use monitor::StatusCache;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::{
-    io,
+    collections::HashMap,
+    io::{self, Write},
     path::{Path, PathBuf},
     time::{Duration, Instant},
 };
 use tokio::sync::mpsc::Sender;
 
+/// Fixed data shown instead of scanning (`--demo`, `--replay`).
+static OFFLINE_SNAPSHOT: std::sync::OnceLock<ipc::SharedSnapshot> = std::sync::OnceLock::new();
+
+/// How long the summary bar stays red after a new critical condition.
+const ATTENTION_FLASH: Duration = Duration::from_secs(3);
+
+/// Commits of HEAD's history offered by the cherry-pick helper.
+const CHERRY_PICK_LOG_LIMIT: usize = 50;
+
 #[derive(Parser, Debug)]
 #[command(
     name = "agentpulse",
-    about = "Agent-first terminal hub for monitoring local Git repositories"
+    about = "Agent-first terminal hub for monitoring local Git repositories",
+    group = clap::ArgGroup::new("headless").args(["once", "watch"])
 )]
 struct Cli {
     /// Path to config file (default: ~/.config/agentpulse/config.toml)
@@ -54,10 +98,22 @@ struct Cli {
     #[arg(long)]
     once: bool,
 
-    /// Output results as JSON — requires --once
-    #[arg(long, requires = "once")]
+    /// Output results as JSON — requires --once or --watch
+    #[arg(long, requires = "headless")]
     json: bool,
 
+    /// Keep scanning without the TUI and reprint the table whenever repo
+    /// status changes (with --json, one JSON array per line)
+    #[arg(
+        long,
+        conflicts_with_all = ["summary", "agent_brief", "agent_json", "dashboard_json", "plain", "stats", "demo", "replay"]
+    )]
+    watch: bool,
+
+    /// Seconds between --watch scans [default: refresh_interval_secs]
+    #[arg(long, value_name = "SECS", requires = "watch")]
+    interval: Option<u64>,
+
     /// Output a markdown handoff brief for coding agents, then exit
     #[arg(
         long,
@@ -72,16 +128,209 @@ struct Cli {
     )]
     agent_json: bool,
 
-    /// Output full dashboard snapshot JSON (repos, processes, deps, env, MCP, AI), then exit
+    /// Output full dashboard snapshot JSON (repos, worktrees, processes, deps,
+    /// env audit, MCP health, provider usage), then exit
     #[arg(
         long,
+        visible_aliases = ["snapshot", "snapshot-json"],
         conflicts_with_all = ["json", "summary", "agent_brief", "agent_json"]
     )]
     dashboard_json: bool,
 
+    /// Cap the agent work queue at N recommendations (--agent-brief / --agent-json)
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+
+    /// Only queue recommendations fitting this estimated effort budget (minutes)
+    #[arg(long, value_name = "MINUTES")]
+    max_minutes: Option<u32>,
+
+    /// Include compare links for unpushed commits in --agent-brief / --agent-json
+    #[arg(long)]
+    diff_links: bool,
+
+    /// Screen-reader friendly output: plain sentences, no box drawing or color,
+    /// reprinted only when something changes (with --once, print once and exit)
+    #[arg(
+        long,
+        conflicts_with_all = ["json", "summary", "agent_brief", "agent_json", "dashboard_json", "demo", "replay"]
+    )]
+    plain: bool,
+
     /// Print a one-line summary and exit (exit 1 if any repos are actionable)
     #[arg(long)]
     summary: bool,
+
+    /// Reuse statuses of repos whose HEAD, refs, index and config are
+    /// unchanged since the last one-shot run, for CI checkouts (misses
+    /// unstaged edits and new files)
+    #[arg(long)]
+    cache: bool,
+
+    /// Print this week's commits, repos touched and changed lines (since Monday), then exit
+    #[arg(
+        long,
+        conflicts_with_all = ["once", "json", "summary", "agent_brief", "agent_json", "dashboard_json", "plain", "demo", "replay"]
+    )]
+    stats: bool,
+
+    /// Print the JSON Schema of --dashboard-json (default) or --agent-json output, then exit
+    #[arg(
+        long,
+        value_enum,
+        value_name = "OUTPUT",
+        num_args = 0..=1,
+        default_missing_value = "dashboard"
+    )]
+    schema: Option<SchemaOutput>,
+
+    /// Open the TUI on a synthetic workspace (no scanning, network, or writes)
+    #[arg(
+        long,
+        conflicts_with_all = ["setup", "once", "summary", "agent_brief", "agent_json", "dashboard_json"]
+    )]
+    demo: bool,
+
+    /// Open the TUI read-only on a saved snapshot (`ctl get-snapshot` or --dashboard-json output)
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["demo", "setup", "once", "summary", "agent_brief", "agent_json", "dashboard_json"]
+    )]
+    replay: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+impl Cli {
+    fn budget(&self) -> agent::Budget {
+        agent::Budget {
+            limit: self.limit,
+            max_minutes: self.max_minutes,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage state persisted between runs (session, repo index)
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Print environment, config, and cache diagnostics
+    Doctor,
+    /// Inspect the configuration format
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Send a command to the running TUI (Unix socket)
+    Ctl {
+        #[command(subcommand)]
+        action: CtlAction,
+    },
+    /// Run one action now and print the result as JSON: exit code, duration,
+    /// output tails and the repo's status afterwards (exit 1 if it fails)
+    Run {
+        /// Action JSON, e.g. '{"type":"git_fetch","repo_path":"/src/app"}'
+        action: String,
+        /// Allow commits and pushes on this branch although it matches
+        /// [actions] protected_branches
+        #[arg(long, value_name = "BRANCH")]
+        allow_protected: Option<String>,
+        /// Allow high-risk actions that discard work (stash drop, aggressive
+        /// gc, killing a process, …)
+        #[arg(long)]
+        allow_destructive: bool,
+    },
+    /// Find which watched repos contain a string or commit (exit 1 if none)
+    Grep {
+        /// Fixed string to search tracked files for, or a commit hash
+        pattern: String,
+        /// Also search history for commits adding or removing the string (slower)
+        #[arg(long)]
+        history: bool,
+        /// Output matches as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Markdown release-readiness report across repos: unreleased commits,
+    /// CI, dirty trees and unmerged release branches (exit 1 if any is blocked)
+    ReleaseCheck {
+        /// Tags that mark a release (git glob)
+        #[arg(long, default_value = "v*", value_name = "GLOB")]
+        tag_pattern: String,
+        /// Branches that hold release work (git glob)
+        #[arg(long, default_value = "release/*", value_name = "GLOB")]
+        release_branches: String,
+        /// Output the per-repo results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write a verified `git bundle` of every repo with commits that exist
+    /// only on this machine (exit 1 if any bundle fails)
+    Bundle {
+        /// Directory to write bundles to [default: [backup] bundle_dir]
+        #[arg(long, value_name = "DIR")]
+        to: Option<PathBuf>,
+    },
+    /// Print the workspace state on every interval and whenever a repo
+    /// changes, one compact JSON document per line, until the reader goes away
+    Watch {
+        /// Document to emit
+        #[arg(long, value_enum, default_value = "agent-json")]
+        format: WatchFormat,
+        /// Seconds between documents when nothing changes
+        #[arg(long, default_value_t = 30, value_name = "SECS")]
+        interval: u64,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum WatchFormat {
+    /// The --agent-json document
+    AgentJson,
+    /// The --dashboard-json snapshot
+    DashboardJson,
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigAction {
+    /// Print a commented reference config with every key and its default
+    Docs,
+}
+
+#[derive(Subcommand, Debug)]
+enum CtlAction {
+    /// Check that an instance is running and print its pid
+    Ping,
+    /// Rescan now, e.g. from a post-commit hook
+    Refresh,
+    /// Print the instance's latest repos and dashboard as JSON
+    GetSnapshot,
+    /// Run an action with the TUI's checks but without its confirmation
+    RunAction {
+        /// Action JSON, e.g. '{"type":"git_fetch","repo_path":"/src/app"}'
+        action: String,
+        /// Allow high-risk actions that discard work (stash drop, aggressive
+        /// gc, killing a process, …)
+        #[arg(long)]
+        allow_destructive: bool,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum SchemaOutput {
+    Dashboard,
+    Agent,
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheAction {
+    /// Delete all persisted cache files
+    Clear,
 }
 
 #[tokio::main]
@@ -90,6 +339,46 @@ async fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
+    if let Some(command) = &cli.command {
+        return run_command(command, &cli).await;
+    }
+
+    if let Some(output) = cli.schema {
+        let schema = match output {
+            SchemaOutput::Dashboard => dashboard::DashboardSnapshot::json_schema(),
+            SchemaOutput::Agent => agent_json_schema(),
+        };
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
+    if cli.demo {
+        let repos = demo::repos();
+        let dashboard = demo::snapshot(&repos);
+        let _ = OFFLINE_SNAPSHOT.set(ipc::SharedSnapshot { repos, dashboard });
+        let cfg = config::Config {
+            watch_directories: vec![PathBuf::from(demo::ROOT)],
+            ..config::Config::default()
+        };
+        return run_tui(cfg, None, Some(InstanceRole::Demo)).await;
+    }
+
+    if let Some(path) = &cli.replay {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("cannot read {}: {}", path.display(), e))?;
+        let shared = ipc::SharedSnapshot::from_export(&raw)
+            .map_err(|e| anyhow::anyhow!("{} is not a snapshot: {}", path.display(), e))?;
+        let _ = OFFLINE_SNAPSHOT.set(shared);
+        // Display settings still apply, but a missing config is not written.
+        let config_path = config::resolve_config_path(cli.config.as_ref());
+        let cfg = if config_path.exists() {
+            config::load_config(Some(&config_path))?
+        } else {
+            config::Config::default()
+        };
+        return run_tui(cfg, None, Some(InstanceRole::Replay)).await;
+    }
+
     // First-run detection: config file doesn't exist yet
     let config_path = cli.config.as_ref();
     let is_first_run = config_path
@@ -104,6 +393,13 @@ async fn main() -> Result<()> {
             println!("  No config found — let's pick which directories to scan.");
         }
         let existing = config::load_config(config_path).ok();
+        i18n::set_language(existing.as_ref().map_or("auto", |c| c.language.as_str()));
+        ui::theme::configure(
+            &existing
+                .as_ref()
+                .map(|c| c.display.clone())
+                .unwrap_or_default(),
+        );
         setup::run_setup(existing.as_ref(), cli.config.as_ref())?
     } else {
         config::load_config(config_path)?
@@ -115,7 +411,8 @@ async fn main() -> Result<()> {
     }
 
     if cli.summary {
-        let repos = monitor::scan_all(&cfg, &mut StatusCache::new()).await;
+        let mut repos = one_shot_scan(&cfg, cli.cache).await;
+        history::annotate_work_ages(&mut repos);
         let snapshot = dashboard::collect_and_build(&repos);
         let total = repos.len();
         let actionable = repos.iter().filter(|r| needs_agent_attention(r)).count();
@@ -125,7 +422,7 @@ async fn main() -> Result<()> {
             .count();
         let unpushed = repos.iter().filter(|r| r.status.unpushed_count > 0).count();
         println!(
-            "agentpulse: {} repos | {} actionable | {} dirty | {} unpushed | {} proc | {} dep-issues | {} env-issues | ${:.2} ai cost",
+            "agentpulse: {} repos | {} actionable | {} dirty | {} unpushed | {} proc | {} dep-issues | {} env-issues | {} ai cost",
             total,
             actionable,
             dirty,
@@ -133,24 +430,81 @@ async fn main() -> Result<()> {
             snapshot.overview.repo_processes,
             snapshot.overview.dep_issues,
             snapshot.overview.env_issues,
-            snapshot.total_estimated_cost_usd(),
+            format::currency(&cfg.display, snapshot.total_estimated_cost_usd()),
         );
         std::process::exit(if actionable > 0 { 1 } else { 0 });
     }
 
-    if cli.once || cli.agent_brief || cli.agent_json || cli.dashboard_json {
+    if cli.stats {
         let repos = monitor::scan_all(&cfg, &mut StatusCache::new()).await;
+        let stats = collectors::collect_week_stats(&repos)?;
+        let monday = collectors::activity::week_start(Local::now().date_naive());
+        print!("{}", week_report(&stats, monday, &cfg.display));
+        return Ok(());
+    }
+
+    if cli.plain {
+        i18n::set_language(&cfg.language);
+        let any_actionable = plain::run(&cfg, cli.once).await?;
+        std::process::exit(if any_actionable { 1 } else { 0 });
+    }
+
+    if cli.watch {
+        i18n::set_language(&cfg.language);
+        ui::theme::configure(&cfg.display);
+        // Also the cache's staleness limit, so a dirty repo is re-probed each scan.
+        if let Some(secs) = cli.interval {
+            cfg.refresh_interval_secs = secs;
+        }
+        return watch_status(&cfg, cli.json).await;
+    }
+
+    if cli.once || cli.agent_brief || cli.agent_json || cli.dashboard_json {
+        ui::theme::configure(&cfg.display);
+        let mut repos = one_shot_scan(&cfg, cli.cache).await;
+        history::annotate_work_ages(&mut repos);
+        if cli.agent_brief || cli.agent_json {
+            hold_vpn_blocked_actions(&repos).await;
+        }
         if cli.agent_brief {
-            print_agent_brief(&repos);
+            let links = forge_links::collect_links(&repos, cli.diff_links).await;
+            let suppressions = suppress::Suppressions::load(&suppress::suppressions_path());
+            let mut out = String::new();
+            write_agent_brief(
+                &mut out,
+                &repos,
+                &links,
+                &suppressions,
+                &cfg,
+                cli.budget(),
+                &Local::now().to_rfc3339(),
+            )?;
+            print!("{}", out);
         } else if cli.agent_json {
-            print_agent_json(&repos);
+            let links = forge_links::collect_links(&repos, cli.diff_links).await;
+            let suppressions = suppress::Suppressions::load(&suppress::suppressions_path());
+            let mut out = String::new();
+            write_agent_json(
+                &mut out,
+                &repos,
+                &links,
+                &suppressions,
+                cli.budget(),
+                &Local::now().to_rfc3339(),
+            )?;
+            print!("{}", out);
         } else if cli.dashboard_json {
-            let snapshot = dashboard::collect_and_build(&repos);
+            let mut snapshot = dashboard::collect_and_build(&repos);
+            history::annotate_cost_forecasts(&mut snapshot, &cfg);
             println!("{}", serde_json::to_string_pretty(&snapshot)?);
         } else if cli.json {
-            print_json(&repos);
+            let mut out = String::new();
+            write_json(&mut out, &repos)?;
+            print!("{}", out);
         } else {
-            print_table(&repos);
+            let mut out = String::new();
+            write_table(&mut out, &repos)?;
+            print!("{}", out);
         }
         let any_actionable = repos.iter().any(needs_agent_attention);
         std::process::exit(if any_actionable { 1 } else { 0 });
@@ -161,7 +515,32 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    run_tui(cfg, cli.config).await
+    run_tui(cfg, cli.config, None).await
+}
+
+/// Probe the remote hosts so recommendations hold back network actions for
+/// repos behind a VPN host that is down, as the dashboard does.
+async fn hold_vpn_blocked_actions(repos: &[Repo]) {
+    let repos = repos.to_vec();
+    let probes = tokio::task::spawn_blocking(move || collectors::collect_host_probes(&repos))
+        .await
+        .ok()
+        .and_then(|probes| probes.ok())
+        .unwrap_or_default();
+    agent::configure_vpn_offline(dashboard::models::vpn_offline_repos(&probes));
+}
+
+/// Scan for a run that prints once and exits. With `use_cache`, repos whose
+/// HEAD and status hash match the last such run reuse its status.
+async fn one_shot_scan(cfg: &config::Config, use_cache: bool) -> Vec<Repo> {
+    if !use_cache {
+        return monitor::scan_all(cfg, &mut StatusCache::new()).await;
+    }
+    let path = scan_cache::cache_path();
+    let mut cache = scan_cache::ScanCache::load(&path);
+    let repos = scan_cache::scan_all(cfg, &mut cache).await;
+    let _ = cache.save(&path);
+    repos
 }
 
 fn check_git_installed() -> Result<()> {
@@ -173,10 +552,426 @@ fn check_git_installed() -> Result<()> {
     }
 }
 
+// ─── subcommands ────────────────────────────────────────────────────────────
+
+async fn run_command(command: &Command, cli: &Cli) -> Result<()> {
+    match command {
+        Command::Cache {
+            action: CacheAction::Clear,
+        } => {
+            let removed = cache::clear()?;
+            if removed.is_empty() {
+                println!("Cache already empty.");
+            }
+            for path in removed {
+                println!("Removed {}", path.display());
+            }
+            Ok(())
+        }
+        Command::Doctor => {
+            print_doctor(cli)?;
+            Ok(())
+        }
+        Command::Config {
+            action: ConfigAction::Docs,
+        } => {
+            print!("{}", config_docs::reference_toml()?);
+            Ok(())
+        }
+        Command::Ctl { action } => run_ctl(action).await,
+        Command::Run {
+            action,
+            allow_protected,
+            allow_destructive,
+        } => {
+            let cfg = cli_config(cli)?;
+            let action: dashboard::ActionKind = serde_json::from_str(action)
+                .map_err(|e| anyhow::anyhow!("invalid action JSON: {}", e))?;
+            git::configure_process_limits(&cfg.processes);
+            git::configure_dirty_ignore(&cfg.dirty_ignore);
+            let mut settings = actions::ActionSettings::from_config(&cfg);
+            settings.allow_protected = allow_protected.clone();
+            settings.allow_destructive = *allow_destructive;
+            let result = actions::run_for_result(&action, &settings).await;
+            println!("{}", serde_json::to_string_pretty(&result)?);
+            std::process::exit(if result.ok { 0 } else { 1 });
+        }
+        Command::Grep {
+            pattern,
+            history,
+            json,
+        } => {
+            let cfg = cli_config(cli)?;
+            let paths = monitor::watched_repo_paths(&cfg);
+            let searched = paths.len();
+            let hits =
+                search::search(paths, pattern, *history, cfg.processes.max_git_processes).await;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&hits)?);
+            } else {
+                print!("{}", search::format_report(pattern, searched, &hits));
+            }
+            std::process::exit(if hits.is_empty() { 1 } else { 0 });
+        }
+        Command::ReleaseCheck {
+            tag_pattern,
+            release_branches,
+            json,
+        } => {
+            let cfg = cli_config(cli)?;
+            let query = release::ReleaseQuery {
+                tag_pattern: tag_pattern.clone(),
+                branch_pattern: release_branches.clone(),
+            };
+            let paths = monitor::watched_repo_paths(&cfg);
+            let releases = release::check(paths, &query, cfg.processes.max_git_processes).await;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&releases)?);
+            } else {
+                let date = format::date(&cfg.display, &Local::now());
+                print!("{}", release::format_report(&query, &date, &releases));
+            }
+            let blocked = releases.iter().any(|r| !r.blockers().is_empty());
+            std::process::exit(if blocked { 1 } else { 0 });
+        }
+        Command::Bundle { to } => {
+            let mut cfg = cli_config(cli)?;
+            if let Some(dir) = to {
+                cfg.backup.bundle_dir = Some(dir.clone());
+            }
+            let Some(dir) = cfg.backup.bundle_dir.clone() else {
+                anyhow::bail!("no destination: pass --to DIR or set [backup] bundle_dir");
+            };
+            std::process::exit(if run_bundle(&cfg, &dir).await { 0 } else { 1 });
+        }
+        Command::Watch { format, interval } => run_watch(cli, *format, *interval).await,
+    }
+}
+
+/// `bundle`: bundle each watched repo with local-only commits into `dir`,
+/// one line per repo. Returns whether every bundle was written.
+async fn run_bundle(cfg: &config::Config, dir: &Path) -> bool {
+    let today = Local::now().date_naive();
+    let pending: Vec<(String, PathBuf, usize)> = monitor::watched_repo_paths(cfg)
+        .into_iter()
+        .filter_map(|path| {
+            let found = collectors::backup::local_only(&path)?;
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            Some((name, path, found.commits))
+        })
+        .collect();
+    if pending.is_empty() {
+        println!("Nothing to bundle: every commit is on a remote.");
+        return true;
+    }
+
+    let mut failed = 0;
+    for (name, path, commits) in &pending {
+        let Some(output) = cfg.backup.bundle_path(path, today) else {
+            continue;
+        };
+        match actions::bundle_repo(path, &output).await {
+            Ok(message) => println!("✓ {} ({} local-only commit(s)): {}", name, commits, message),
+            Err(err) => {
+                failed += 1;
+                println!("✗ {}: {:#}", name, err);
+            }
+        }
+    }
+    println!(
+        "\nBundled {} of {} repo(s) to {}",
+        pending.len() - failed,
+        pending.len(),
+        dir.display()
+    );
+    failed == 0
+}
+
+/// `watch`: rescan and print a document every `interval` seconds, or sooner
+/// when a watched git dir changes. Returns once stdout is closed.
+async fn run_watch(cli: &Cli, format: WatchFormat, interval: u64) -> Result<()> {
+    use std::io::Write;
+
+    let cfg = cli_config(cli)?;
+    let interval = Duration::from_secs(interval.max(1));
+    let mut cache = StatusCache::new();
+    let (watch_tx, mut watch_rx) = tokio::sync::mpsc::unbounded_channel::<PathBuf>();
+    let mut repo_watcher = watcher::RepoWatcher::new(watch_tx);
+    loop {
+        let mut repos = monitor::scan_all(&cfg, &mut cache).await;
+        history::annotate_work_ages(&mut repos);
+        let paths: Vec<PathBuf> = repos.iter().map(|r| r.path.clone()).collect();
+        repo_watcher.sync(&paths);
+        repo_watcher.refresh_ignores().await;
+
+        let document = match format {
+            WatchFormat::AgentJson => {
+                hold_vpn_blocked_actions(&repos).await;
+                let links = forge_links::collect_links(&repos, cli.diff_links).await;
+                let suppressions = suppress::Suppressions::load(&suppress::suppressions_path());
+                let mut out = String::new();
+                write_agent_json(
+                    &mut out,
+                    &repos,
+                    &links,
+                    &suppressions,
+                    cli.budget(),
+                    &Local::now().to_rfc3339(),
+                )?;
+                one_line(&out)
+            }
+            WatchFormat::DashboardJson => {
+                let mut snapshot = dashboard::collect_and_build(&repos);
+                history::annotate_cost_forecasts(&mut snapshot, &cfg);
+                serde_json::to_string(&snapshot)?
+            }
+        };
+        let mut stdout = std::io::stdout().lock();
+        if writeln!(stdout, "{}", document)
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            return Ok(());
+        }
+        drop(stdout);
+
+        wait_for_rescan(interval, &mut watch_rx, &mut repo_watcher, &mut cache).await;
+    }
+}
+
+/// `--watch`: rescan every `refresh_interval_secs`, or sooner when a watched
+/// git dir changes, and print the table (or a one-line `--json` array)
+/// whenever it differs from the last one printed. Returns once stdout is
+/// closed.
+async fn watch_status(cfg: &config::Config, json: bool) -> Result<()> {
+    use std::io::Write;
+
+    let interval = Duration::from_secs(cfg.refresh_interval_secs.max(1));
+    let mut cache = StatusCache::new();
+    let (watch_tx, mut watch_rx) = tokio::sync::mpsc::unbounded_channel::<PathBuf>();
+    let mut repo_watcher = watcher::RepoWatcher::new(watch_tx);
+    let mut last = String::new();
+    loop {
+        let mut repos = monitor::scan_all(cfg, &mut cache).await;
+        history::annotate_work_ages(&mut repos);
+        let paths: Vec<PathBuf> = repos.iter().map(|r| r.path.clone()).collect();
+        repo_watcher.sync(&paths);
+        repo_watcher.refresh_ignores().await;
+
+        let mut body = String::new();
+        if json {
+            write_json(&mut body, &repos)?;
+            body = format!("{}\n", one_line(&body));
+        } else {
+            write_table(&mut body, &repos)?;
+        }
+        if body != last {
+            let mut stdout = std::io::stdout().lock();
+            let header = if json {
+                String::new()
+            } else {
+                let time = format::clock(&cfg.display, &Local::now());
+                format!("\n{}\n", i18n::tr("plain-updated", &[("time", &time)]))
+            };
+            if write!(stdout, "{}{}", header, body)
+                .and_then(|_| stdout.flush())
+                .is_err()
+            {
+                return Ok(());
+            }
+            last = body;
+        }
+
+        wait_for_rescan(interval, &mut watch_rx, &mut repo_watcher, &mut cache).await;
+    }
+}
+
+/// Sleep until `interval` passes or a watched repo changes; in the latter
+/// case drop the changed repos from `cache` so the next scan re-probes them.
+async fn wait_for_rescan(
+    interval: Duration,
+    watch_rx: &mut tokio::sync::mpsc::UnboundedReceiver<PathBuf>,
+    repo_watcher: &mut watcher::RepoWatcher,
+    cache: &mut StatusCache,
+) {
+    tokio::select! {
+        _ = tokio::time::sleep(interval) => {}
+        Some(path) = watch_rx.recv() => {
+            // One commit touches several files; let the burst settle.
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            let mut changed = vec![path];
+            while let Ok(path) = watch_rx.try_recv() {
+                changed.push(path);
+            }
+            for path in changed {
+                if let Some(repo) = repo_watcher.observe(&path) {
+                    invalidate_cache_for_repo(cache, &repo);
+                }
+            }
+            repo_watcher.refresh_ignores().await;
+        }
+    }
+}
+
+async fn run_ctl(action: &CtlAction) -> Result<()> {
+    let request = match action {
+        CtlAction::Ping => ipc::Request::Ping,
+        CtlAction::Refresh => ipc::Request::Refresh,
+        CtlAction::GetSnapshot => ipc::Request::GetSnapshot,
+        CtlAction::RunAction {
+            action,
+            allow_destructive,
+        } => ipc::Request::RunAction {
+            action: serde_json::from_str(action)
+                .map_err(|e| anyhow::anyhow!("invalid action JSON: {}", e))?,
+            allow_destructive: *allow_destructive,
+        },
+    };
+    let socket = ipc::socket_path();
+    if !socket.exists() {
+        anyhow::bail!(
+            "no running agentpulse instance ({} not found)",
+            socket.display()
+        );
+    }
+    let response = ipc::request(&socket, &request).await?;
+    match (action, response) {
+        (CtlAction::Ping, ipc::Response { pid: Some(pid), .. }) => {
+            println!("agentpulse running (pid {})", pid)
+        }
+        (
+            CtlAction::GetSnapshot,
+            ipc::Response {
+                snapshot: Some(snapshot),
+                ..
+            },
+        ) => println!("{}", serde_json::to_string_pretty(&snapshot)?),
+        (CtlAction::Refresh, _) => println!("Refresh queued"),
+        _ => println!("Action queued; the result appears in the dashboard"),
+    }
+    Ok(())
+}
+
+/// Config for subcommands: `--config`/`--dir` applied, and never writes a
+/// default config file; just uses what's there.
+fn cli_config(cli: &Cli) -> Result<config::Config> {
+    let config_path = config::resolve_config_path(cli.config.as_ref());
+    let mut cfg = if config_path.exists() {
+        config::load_config(Some(&config_path))?
+    } else {
+        config::Config::default()
+    };
+    if !cli.dirs.is_empty() {
+        cfg.watch_directories = cli.dirs.clone();
+    }
+    i18n::set_language(&cfg.language);
+    Ok(cfg)
+}
+
+fn print_doctor(cli: &Cli) -> Result<()> {
+    let config_path = config::resolve_config_path(cli.config.as_ref());
+    let cfg = cli_config(cli)?;
+    // The collectors below read their settings from globals.
+    monitor::configure(&cfg);
+
+    let git_version = std::process::Command::new("git")
+        .arg("--version")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_else(|_| "not found".to_string());
+
+    println!("agentpulse doctor");
+    println!();
+    println!("  git        {}", git_version);
+    println!(
+        "  config     {} ({})",
+        config_path.display(),
+        if config_path.exists() {
+            "found"
+        } else {
+            "missing, using defaults"
+        }
+    );
+    println!("  watching   {} directories", cfg.watch_directories.len());
+    for dir in &cfg.watch_directories {
+        let marker = if dir.exists() { "ok" } else { "missing" };
+        println!("             {} [{}]", dir.display(), marker);
+    }
+    if !cfg.credentials.is_empty() {
+        println!();
+        println!("  credentials");
+        for (name, source) in &cfg.credentials {
+            let state = match credentials::lookup(name) {
+                Some((_, from)) if from.starts_with("env:") => "ok (env overrides)",
+                Some(_) => "ok",
+                None => "not found",
+            };
+            println!("    {:<24} {:<10} {}", name, source.label(), state);
+        }
+    }
+    println!();
+    println!("  cache");
+    for stat in cache::stats(&cfg.watch_directories) {
+        let size = stat
+            .bytes
+            .map(|b| format!("{} bytes", format::number(&cfg.display, b)))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "    {:<14} {:<12} {}  {}",
+            stat.label,
+            size,
+            stat.detail,
+            stat.path.display()
+        );
+    }
+    println!();
+    println!("  collectors");
+    let repos: Vec<git::Repo> = monitor::watched_repo_paths(&cfg)
+        .into_iter()
+        .map(git::Repo::new)
+        .collect();
+    // Power-save reuses cached provider usage instead of calling usage APIs.
+    let collected = collectors::collect_all_with(&repos, collectors::CollectMode::PowerSave);
+    if collected.errors.is_empty() {
+        println!("    ok ({} repos)", repos.len());
+    }
+    for err in &collected.errors {
+        println!("    {:<14} {}", err.collector, err.message);
+    }
+    let large: Vec<(&git::Repo, collectors::maintenance::MaintenanceStatus)> = repos
+        .iter()
+        .filter_map(|repo| {
+            let status = collectors::maintenance::maintenance_status(&repo.path)?;
+            status.is_large().then_some((repo, status))
+        })
+        .collect();
+    if !large.is_empty() {
+        println!();
+        println!("  large repos    maintenance  commit-graph  fsmonitor");
+        let on = |on: bool| if on { "on" } else { "off" };
+        for (repo, status) in large {
+            println!(
+                "    {:<12} {:<12} {:<13} {}",
+                repo.name,
+                on(status.background),
+                on(status.commit_graph),
+                on(status.fsmonitor)
+            );
+        }
+    }
+    Ok(())
+}
+
 // ─── TUI ────────────────────────────────────────────────────────────────────
 
 /// Run the TUI, automatically re-launching after setup if the user presses `s`.
-async fn run_tui(initial_config: config::Config, config_path: Option<PathBuf>) -> Result<()> {
+async fn run_tui(
+    initial_config: config::Config,
+    config_path: Option<PathBuf>,
+    offline_role: Option<InstanceRole>,
+) -> Result<()> {
+    i18n::set_language(&initial_config.language);
+    ui::theme::configure(&initial_config.display);
     // Restore terminal on panic
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
@@ -187,6 +982,32 @@ async fn run_tui(initial_config: config::Config, config_path: Option<PathBuf>) -
 
     let mut cfg = initial_config;
 
+    let socket = ipc::socket_path();
+    let role = match offline_role {
+        Some(role) => role,
+        None => match ipc::probe(&socket).await {
+            Some(pid) => match prompt_second_instance(pid)? {
+                Some(role) => role,
+                None => return Ok(()),
+            },
+            None => InstanceRole::Primary,
+        },
+    };
+    let (publisher, snapshot) = tokio::sync::watch::channel(ipc::SharedSnapshot::default());
+    let (commands, mut ctl_rx) = tokio::sync::mpsc::channel::<ipc::ControlCommand>(8);
+    // Held for the whole session so reconfiguring keeps the socket up.
+    let _server = match role {
+        InstanceRole::Primary => {
+            let controls = ipc::Controls {
+                snapshot,
+                commands,
+                allowed_programs: cfg.actions.allowed_programs.clone(),
+            };
+            ipc::serve(&socket, controls).ok()
+        }
+        _ => None,
+    };
+
     loop {
         // ── launch TUI ───────────────────────────────────────────────────────
         enable_raw_mode()?;
@@ -195,7 +1016,15 @@ async fn run_tui(initial_config: config::Config, config_path: Option<PathBuf>) -
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
-        let reconfigure = event_loop(&mut terminal, cfg.clone()).await;
+        let reconfigure = event_loop(
+            &mut terminal,
+            cfg.clone(),
+            config_path.as_ref(),
+            role,
+            &publisher,
+            &mut ctl_rx,
+        )
+        .await;
 
         // Always restore terminal before doing anything else
         let _ = disable_raw_mode();
@@ -215,18 +1044,62 @@ async fn run_tui(initial_config: config::Config, config_path: Option<PathBuf>) -
     Ok(())
 }
 
+/// Ask what to do when another TUI already answers on the instance socket.
+/// `None` means quit.
+fn prompt_second_instance(pid: u32) -> Result<Option<InstanceRole>> {
+    println!("agentpulse is already running (pid {}).", pid);
+    println!("  [a] attach to its data (no extra scanning)");
+    println!("  [r] run read-only with a separate scan");
+    println!("  [q] quit");
+    print!("Choice [a]: ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(match answer.trim().to_lowercase().as_str() {
+        "" | "a" | "attach" => Some(InstanceRole::Attached),
+        "r" | "read-only" => Some(InstanceRole::ReadOnly),
+        _ => None,
+    })
+}
+
 /// Returns `Ok(true)` when the user wants to reconfigure (presses `s`).
 async fn event_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     config: config::Config,
+    config_path: Option<&PathBuf>,
+    role: InstanceRole,
+    publisher: &tokio::sync::watch::Sender<ipc::SharedSnapshot>,
+    ctl_rx: &mut tokio::sync::mpsc::Receiver<ipc::ControlCommand>,
 ) -> Result<bool> {
     let mut app = App::new(config.clone());
+    app.role = role;
+    app.config_path = config::resolve_config_path(config_path);
+    let session_path = state::state_path();
+    let repo_index_path = repo_index::index_path();
+    let mut repo_index = repo_index::RepoIndex::default();
+    let suppressions_path = suppress::suppressions_path();
+    // Demo and replay views start fresh instead of from the user's state.
+    let audit_path = OFFLINE_SNAPSHOT.get().is_none().then(audit::log_path);
+    if let Some(path) = &audit_path {
+        app.action_log = audit::load_recent(path, audit::VIEW_LIMIT);
+    }
+    if OFFLINE_SNAPSHOT.get().is_none() {
+        app.restore_session(state::load_session(&session_path));
+        repo_index = repo_index::RepoIndex::load(&repo_index_path);
+        app.suppressions = suppress::Suppressions::load(&suppressions_path);
+        if !app.tour_seen {
+            app.start_tour();
+        }
+    }
     let (scan_tx, mut scan_rx) = tokio::sync::mpsc::channel::<Vec<Repo>>(1);
     let (cache_tx, mut cache_rx) = tokio::sync::mpsc::channel::<StatusCache>(1);
-    let (dash_tx, mut dash_rx) = tokio::sync::mpsc::channel::<dashboard::DashboardSnapshot>(1);
+    let (dash_tx, mut dash_rx) = tokio::sync::mpsc::channel::<DashboardUpdate>(1);
     let (notif_tx, mut notif_rx) = tokio::sync::mpsc::channel::<String>(8);
     let (action_done_tx, mut action_done_rx) =
         tokio::sync::mpsc::channel::<actions::ActionCompletion>(8);
+    let (grep_tx, mut grep_rx) = tokio::sync::mpsc::channel::<(String, Vec<search::RepoHit>)>(1);
+    // One repo re-checked after an action, with its refreshed cache entry.
+    let (rescan_tx, mut rescan_rx) = tokio::sync::mpsc::channel::<(Repo, StatusCache)>(8);
 
     // SIGTERM: restore terminal cleanly
     let (term_tx, mut term_rx) = tokio::sync::mpsc::channel::<()>(1);
@@ -245,32 +1118,79 @@ async fn event_loop(
 
     let mut current_cache = StatusCache::new();
     trigger_scan(
+        role,
         config,
+        CollectMode::Full,
         scan_tx.clone(),
         current_cache.clone(),
         cache_tx.clone(),
         dash_tx.clone(),
     );
     let mut pending_rescan = false;
+    // watch_mode: paths changed in watched repos, mapped to repos below.
+    let (watch_tx, mut watch_rx) = tokio::sync::mpsc::unbounded_channel::<PathBuf>();
+    let mut repo_watcher = (app.config.watch_mode && !role.is_read_only())
+        .then(|| watcher::RepoWatcher::new(watch_tx));
+    // Repo → last event, re-checked once events stop for WATCH_DEBOUNCE.
+    let mut watch_changed: HashMap<PathBuf, Instant> = HashMap::new();
+    let (ignore_tx, mut ignore_rx) =
+        tokio::sync::mpsc::unbounded_channel::<(PathBuf, Vec<PathBuf>)>();
+    let (ownership_tx, mut ownership_rx) =
+        tokio::sync::mpsc::unbounded_channel::<(app::OwnershipKey, Option<Ownership>)>();
+    let (load_tx, mut load_rx) = tokio::sync::mpsc::unbounded_channel::<LoadedView>();
 
     let mut last_refresh = Instant::now();
+    let mut last_input = Instant::now();
+    let mut off_hours = history::OffHours::default();
+    let mut power = power::PowerMonitor::new();
 
     loop {
+        while let Ok((key, ownership)) = ownership_rx.try_recv() {
+            app.apply_ownership(key, ownership);
+        }
+        if let Some(key) = app.refresh_ownership() {
+            spawn_ownership_load(key, &ownership_tx);
+        }
         terminal.draw(|f| ui::render(f, &app))?;
 
         // Drain all pending notifications
         while let Ok(msg) = notif_rx.try_recv() {
             app.notify(msg);
         }
+        while let Ok(loaded) = load_rx.try_recv() {
+            // A view the user has moved on from in the meantime isn't opened.
+            if app.mode == AppMode::Normal {
+                loaded(&mut app);
+            }
+        }
         while let Ok(done) = action_done_rx.try_recv() {
-            if let Some(repo_path) = done.affected_repo_path.as_deref() {
-                invalidate_cache_for_repo(&mut current_cache, Path::new(repo_path));
+            if let Some(path) = &audit_path {
+                app.action_log = audit::load_recent(path, audit::VIEW_LIMIT);
+            }
+            if let Some(commit) = done.commit {
+                app.record_commit(commit);
             }
-            if app.is_scanning {
+            let known = done
+                .affected_repo_path
+                .as_deref()
+                .map(Path::new)
+                .filter(|path| app.repos.iter().any(|r| r.path == *path));
+            if let Some(path) = known {
+                app.record_use(path);
+                // Only the repo the action touched; the next poll covers the rest.
+                spawn_rescan(path, &current_cache, &rescan_tx);
+                // A scan already running may have read the repo before the action.
+                if app.is_scanning {
+                    invalidate_cache_for_repo(&mut current_cache, path);
+                    pending_rescan = true;
+                }
+            } else if app.is_scanning {
                 pending_rescan = true;
             } else {
                 trigger_scan(
+                    app.role,
                     app.config.clone(),
+                    app.collect_mode(),
                     scan_tx.clone(),
                     current_cache.clone(),
                     cache_tx.clone(),
@@ -280,23 +1200,167 @@ async fn event_loop(
             }
         }
 
+        while let Ok((repo, entry)) = rescan_rx.try_recv() {
+            current_cache.extend(entry);
+            if app.replace_repo(repo) && role == InstanceRole::Primary {
+                publisher.send_modify(|shared| {
+                    shared.repos = app.repos.clone();
+                    shared.dashboard = app.dashboard.clone();
+                });
+            }
+        }
+
+        if let Ok((pattern, hits)) = grep_rx.try_recv() {
+            if let Some(msg) = app.finish_grep(&pattern, hits) {
+                app.notify(msg);
+            }
+        }
+
+        // Requests from `agentpulse ctl`
+        while let Ok(command) = ctl_rx.try_recv() {
+            match command {
+                ipc::ControlCommand::Refresh if app.is_scanning => pending_rescan = true,
+                ipc::ControlCommand::Refresh => {
+                    trigger_scan(
+                        app.role,
+                        app.config.clone(),
+                        app.collect_mode(),
+                        scan_tx.clone(),
+                        current_cache.clone(),
+                        cache_tx.clone(),
+                        dash_tx.clone(),
+                    );
+                    app.is_scanning = true;
+                }
+                ipc::ControlCommand::RunAction {
+                    action,
+                    allow_destructive,
+                } => {
+                    let settings = app.action_settings(&action);
+                    // The allowlist may have changed since the socket opened.
+                    match actions::check_unconfirmed(
+                        &action,
+                        &settings.allowed_programs,
+                        allow_destructive,
+                    ) {
+                        Ok(()) => {
+                            app.notify(t("notify-ctl-action"));
+                            actions::run_action(
+                                action,
+                                settings,
+                                notif_tx.clone(),
+                                action_done_tx.clone(),
+                            );
+                        }
+                        Err(e) => app.notify(e.to_string()),
+                    }
+                }
+            }
+        }
+
         if let Ok(updated) = cache_rx.try_recv() {
             current_cache = updated;
         }
-        if let Ok(snapshot) = dash_rx.try_recv() {
+        if let Ok((scanned, snapshot)) = dash_rx.try_recv() {
             app.dashboard = snapshot;
+            agent::configure_vpn_offline(app.dashboard.vpn_offline_repos());
+            // Picks up actions run elsewhere, e.g. by `agentpulse run`.
+            if let Some(path) = &audit_path {
+                app.action_log = audit::load_recent(path, audit::VIEW_LIMIT);
+            }
             app.clamp_selection();
+            let entry = history::HistoryEntry::from_snapshot(&app.dashboard);
+            if role == InstanceRole::Primary {
+                publisher.send_modify(|shared| shared.dashboard = app.dashboard.clone());
+                // Reading and trimming the file is left to a blocking thread.
+                let recorded = entry.clone();
+                tokio::task::spawn_blocking(move || {
+                    history::record(&history::history_path(), &recorded)
+                });
+            }
+            if let Some(digest) = off_hours.observe(entry, !app.off_hours, &app.config.display) {
+                let since = Local
+                    .timestamp_opt(digest.from.at, 0)
+                    .single()
+                    .map(|t| format::clock(&app.config.display, &t))
+                    .unwrap_or_default();
+                app.notify(tr(
+                    "notify-off-hours-digest",
+                    &[("since", &since), ("count", &digest.changes.len())],
+                ));
+                if app.mode == AppMode::Normal {
+                    app.time_travel = Some(TimeTravel::digest(digest));
+                    app.mode = AppMode::TimeTravel;
+                }
+            }
+            // Off hours: still mark conditions as seen, so the morning gets
+            // the digest rather than a burst of them.
+            let fresh = app.take_new_critical(&scanned);
+            if !fresh.is_empty() && app.config.attention.enabled() && !app.off_hours {
+                if app.config.attention.bell {
+                    let _ = io::stdout()
+                        .write_all(b"\x07")
+                        .and_then(|_| io::stdout().flush());
+                }
+                if app.config.attention.flash {
+                    app.flash_until = Some(Instant::now() + ATTENTION_FLASH);
+                }
+                app.notify(tr("notify-new-critical", &[("alerts", &fresh.join(" · "))]));
+            }
         }
 
         if let Ok(repos) = scan_rx.try_recv() {
             app.repos = repos;
+            app.apply_restored_selection();
+            let previous_index = repo_index.clone();
+            let moves = repo_index.record(&app.repos);
+            repo_index.prune(&app.config.watch_directories);
+            for mv in &moves {
+                current_cache.remove(&mv.from);
+                app.notify(tr(
+                    "notify-repo-moved",
+                    &[("from", &mv.from.display()), ("to", &mv.to.display())],
+                ));
+            }
+            // Only the primary instance owns the on-disk index and dismissals.
+            if repo_index != previous_index && !role.is_read_only() {
+                let _ = repo_index.save(&repo_index_path);
+            }
+            if app.suppressions.prune(&app.repos) && !role.is_read_only() {
+                let _ = app.suppressions.save(&suppressions_path);
+            }
+            if role == InstanceRole::Primary {
+                publisher.send_modify(|shared| shared.repos = app.repos.clone());
+            }
+            if let Some(repo_watcher) = repo_watcher.as_mut() {
+                let paths: Vec<PathBuf> = app.repos.iter().map(|r| r.path.clone()).collect();
+                let polled_before = repo_watcher.coverage.polled.len();
+                repo_watcher.sync(&paths);
+                spawn_ignore_refresh(repo_watcher, &ignore_tx);
+                let coverage = &repo_watcher.coverage;
+                if coverage.polled.len() > polled_before && coverage.fallback_reason.is_some() {
+                    app.notify(tr(
+                        "notify-watch-coverage",
+                        &[("coverage", &coverage.summary())],
+                    ));
+                }
+                app.watch_coverage = Some(coverage.clone());
+            }
             app.is_scanning = false;
-            app.last_scan = Some(Local::now());
+            app.last_scan = match role {
+                // Show when the replayed snapshot was taken, not when it was loaded.
+                InstanceRole::Replay => Local
+                    .timestamp_opt(app.dashboard.generated_at_epoch_secs, 0)
+                    .single(),
+                _ => Some(Local::now()),
+            };
             last_refresh = Instant::now();
 
             if pending_rescan {
                 trigger_scan(
+                    app.role,
                     app.config.clone(),
+                    app.collect_mode(),
                     scan_tx.clone(),
                     current_cache.clone(),
                     cache_tx.clone(),
@@ -307,6 +1371,35 @@ async fn event_loop(
             }
         }
 
+        if let Some(repo_watcher) = repo_watcher.as_mut() {
+            while let Ok(path) = watch_rx.try_recv() {
+                if let Some(repo) = repo_watcher.observe(&path) {
+                    watch_changed.insert(repo, Instant::now());
+                }
+            }
+            spawn_ignore_refresh(repo_watcher, &ignore_tx);
+            while let Ok((repo, ignored)) = ignore_rx.try_recv() {
+                repo_watcher.apply_ignored(&repo, ignored);
+                app.watch_coverage = Some(repo_watcher.coverage.clone());
+            }
+            // A running scan may already have read these repos; they wait
+            // for it so its older answer can't land after theirs.
+            if !app.is_scanning {
+                let settled: Vec<PathBuf> = watch_changed
+                    .iter()
+                    .filter(|(_, at)| at.elapsed() >= WATCH_DEBOUNCE)
+                    .map(|(repo, _)| repo.clone())
+                    .collect();
+                for repo in settled {
+                    watch_changed.remove(&repo);
+                    invalidate_cache_for_repo(&mut current_cache, &repo);
+                    if app.repos.iter().any(|r| r.path == repo) {
+                        spawn_rescan(&repo, &current_cache, &rescan_tx);
+                    }
+                }
+            }
+        }
+
         if term_rx.try_recv().is_ok() {
             break;
         }
@@ -315,35 +1408,46 @@ async fn event_loop(
 
         if crossterm::event::poll(Duration::from_millis(100))? {
             match crossterm::event::read()? {
-                Event::Key(key) => handle_key(
-                    &mut app,
-                    key,
-                    &scan_tx,
-                    &cache_tx,
-                    &mut current_cache,
-                    &dash_tx,
-                    &notif_tx,
-                    &action_done_tx,
-                    &mut pending_rescan,
-                ),
+                Event::Key(key) => {
+                    last_input = Instant::now();
+                    handle_key(
+                        &mut app,
+                        key,
+                        &scan_tx,
+                        &cache_tx,
+                        &mut current_cache,
+                        &dash_tx,
+                        &notif_tx,
+                        &action_done_tx,
+                        &grep_tx,
+                        &load_tx,
+                        &mut pending_rescan,
+                    )
+                }
                 Event::Resize(_, _) => {}
                 _ => {}
             }
         }
 
-        if !app.is_scanning {
-            let interval = Duration::from_secs(app.config.refresh_interval_secs);
-            if last_refresh.elapsed() >= interval {
-                trigger_scan(
-                    app.config.clone(),
-                    scan_tx.clone(),
-                    current_cache.clone(),
-                    cache_tx.clone(),
-                    dash_tx.clone(),
-                );
-                app.is_scanning = true;
-                last_refresh = Instant::now();
-            }
+        let power_cfg = &app.config.power;
+        let on_battery = power_cfg.battery_saver && power.source() == power::PowerSource::Battery;
+        let idle = power_cfg.idle_after_secs > 0
+            && last_input.elapsed() >= Duration::from_secs(power_cfg.idle_after_secs);
+        app.power_save = on_battery || idle;
+        app.off_hours = !app.config.schedule.is_working(&Local::now());
+
+        if !app.is_scanning && last_refresh.elapsed() >= app.refresh_interval() {
+            trigger_scan(
+                app.role,
+                app.config.clone(),
+                app.collect_mode(),
+                scan_tx.clone(),
+                current_cache.clone(),
+                cache_tx.clone(),
+                dash_tx.clone(),
+            );
+            app.is_scanning = true;
+            last_refresh = Instant::now();
         }
 
         if app.should_quit || app.should_reconfigure {
@@ -351,26 +1455,82 @@ async fn event_loop(
         }
     }
 
+    // Best-effort: a failed write only means the next launch starts on Home.
+    if !role.is_read_only() {
+        let _ = state::save_session(&session_path, &app.session_state());
+    }
+
     Ok(app.should_reconfigure)
 }
 
+/// A dashboard snapshot and the repos it was built from, which may reach
+/// the event loop before or after their own scan message.
+type DashboardUpdate = (Vec<Repo>, dashboard::DashboardSnapshot);
+
 fn trigger_scan(
+    role: InstanceRole,
     config: config::Config,
+    mode: CollectMode,
     tx: Sender<Vec<Repo>>,
     cache: StatusCache,
     cache_tx: tokio::sync::mpsc::Sender<StatusCache>,
-    dash_tx: tokio::sync::mpsc::Sender<dashboard::DashboardSnapshot>,
+    dash_tx: tokio::sync::mpsc::Sender<DashboardUpdate>,
 ) {
     tokio::spawn(async move {
-        let mut cache = cache;
-        let repos = monitor::scan_all(&config, &mut cache).await;
-        let snapshot = dashboard::collect_and_build(&repos);
+        if let Some(shared) = OFFLINE_SNAPSHOT.get() {
+            let _ = dash_tx
+                .send((shared.repos.clone(), shared.dashboard.clone()))
+                .await;
+            let _ = tx.send(shared.repos.clone()).await;
+            return;
+        }
+        if role == InstanceRole::Attached {
+            let request = ipc::request(&ipc::socket_path(), &ipc::Request::GetSnapshot).await;
+            if let Ok(ipc::Response {
+                snapshot: Some(shared),
+                ..
+            }) = request
+            {
+                let _ = tx.send(shared.repos.clone()).await;
+                let _ = dash_tx.send((shared.repos, shared.dashboard)).await;
+                return;
+            }
+            // The primary went away: keep the view alive with a local scan.
+        }
+        let mut cache = cache;
+        let (mut repos, mut stats) = monitor::scan_all_with_stats(&config, &mut cache).await;
+        history::annotate_work_ages(&mut repos);
+        let spawns_before = git::git_spawn_count();
+        let collect_started = Instant::now();
+        let mut snapshot = dashboard::collect_and_build_with(&repos, mode);
+        history::annotate_cost_forecasts(&mut snapshot, &config);
+        stats.collect_ms = collect_started.elapsed().as_millis() as u64;
+        stats.git_spawns += git::git_spawn_count() - spawns_before;
+        snapshot.scan_stats = Some(stats);
         let _ = cache_tx.send(cache).await;
-        let _ = tx.send(repos).await;
-        let _ = dash_tx.send(snapshot).await;
+        let _ = tx.send(repos.clone()).await;
+        let _ = dash_tx.send((repos, snapshot)).await;
     });
 }
 
+/// Stage `action` for confirmation, or open the create-remote wizard when
+/// that is what it needs.
+fn review_action(app: &mut App, action: dashboard::ActionCommand) {
+    let wizard = RemoteWizard::from_action(
+        &action.action,
+        path_utils::resolve_binary_in_path("gh").is_some(),
+        path_utils::resolve_binary_in_path("glab").is_some(),
+    );
+    if let Some(wizard) = wizard {
+        app.remote_wizard = Some(wizard);
+        app.mode = AppMode::RemoteWizard;
+    } else {
+        let label = action.label.clone();
+        app.stage_action_confirmation(action);
+        app.notify(tr("notify-review-action", &[("action", &label)]));
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn handle_key(
     app: &mut App,
@@ -378,9 +1538,11 @@ fn handle_key(
     scan_tx: &Sender<Vec<Repo>>,
     cache_tx: &tokio::sync::mpsc::Sender<StatusCache>,
     current_cache: &mut StatusCache,
-    dash_tx: &tokio::sync::mpsc::Sender<dashboard::DashboardSnapshot>,
+    dash_tx: &tokio::sync::mpsc::Sender<DashboardUpdate>,
     notif_tx: &tokio::sync::mpsc::Sender<String>,
     action_done_tx: &tokio::sync::mpsc::Sender<actions::ActionCompletion>,
+    grep_tx: &tokio::sync::mpsc::Sender<(String, Vec<search::RepoHit>)>,
+    load_tx: &tokio::sync::mpsc::UnboundedSender<LoadedView>,
     pending_rescan: &mut bool,
 ) {
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
@@ -388,9 +1550,27 @@ fn handle_key(
         return;
     }
 
+    if app.mode == AppMode::Normal {
+        if let Some(prefix) = app.pending_key.take() {
+            if let KeyCode::Char(digit @ '1'..='9') = key.code {
+                let slot = digit as u8 - b'0';
+                let msg = if prefix == 'm' {
+                    app.set_bookmark(slot)
+                } else {
+                    app.jump_to_bookmark(slot)
+                };
+                app.notify(msg);
+            }
+            return;
+        }
+    }
+
     match app.mode {
         AppMode::Normal => match key.code {
             KeyCode::Char('q') | KeyCode::Char('Q') => app.should_quit = true,
+            KeyCode::Char('s') if OFFLINE_SNAPSHOT.get().is_some() => {
+                app.notify(app.role.read_only_notice());
+            }
             KeyCode::Char('s') => {
                 app.should_reconfigure = true;
                 app.should_quit = true;
@@ -399,45 +1579,23 @@ fn handle_key(
             KeyCode::Char('k') | KeyCode::Up => app.move_selection(-1),
             KeyCode::Char('h') | KeyCode::Left | KeyCode::BackTab => app.previous_section(),
             KeyCode::Char('l') | KeyCode::Right | KeyCode::Tab => app.next_section(),
-            KeyCode::Char('1') => {
-                app.section = dashboard::DashboardSection::Home;
-                app.selected = 0;
-            }
-            KeyCode::Char('2') => {
-                app.section = dashboard::DashboardSection::Repos;
-                app.selected = 0;
-            }
-            KeyCode::Char('3') => {
-                app.section = dashboard::DashboardSection::Worktrees;
-                app.selected = 0;
-            }
-            KeyCode::Char('4') => {
-                app.section = dashboard::DashboardSection::Processes;
-                app.selected = 0;
-            }
-            KeyCode::Char('5') => {
-                app.section = dashboard::DashboardSection::Dependencies;
-                app.selected = 0;
-            }
-            KeyCode::Char('6') => {
-                app.section = dashboard::DashboardSection::EnvAudit;
-                app.selected = 0;
-            }
-            KeyCode::Char('7') => {
-                app.section = dashboard::DashboardSection::McpHealth;
-                app.selected = 0;
-            }
-            KeyCode::Char('8') => {
-                app.section = dashboard::DashboardSection::AiCosts;
-                app.selected = 0;
+            // `0` is the tenth section; later ones are reached with h/l.
+            KeyCode::Char(digit @ '0'..='9') => {
+                let idx = (digit as usize + 9 - '1' as usize) % 10;
+                if let Some(section) = dashboard::DashboardSection::all().get(idx) {
+                    app.section = *section;
+                    app.selected = 0;
+                }
             }
             KeyCode::Char('r') => {
                 if app.is_scanning {
                     *pending_rescan = true;
-                    app.notify("Refresh queued");
+                    app.notify(t("notify-refresh-queued"));
                 } else {
                     trigger_scan(
+                        app.role,
                         app.config.clone(),
+                        app.collect_mode(),
                         scan_tx.clone(),
                         current_cache.clone(),
                         cache_tx.clone(),
@@ -452,10 +1610,15 @@ fn handle_key(
                     app.selected = 0;
                     app.mode = AppMode::Search;
                 } else {
-                    app.notify("Filter is available in Repos section");
+                    app.notify(t("notify-filter-repos-only"));
                 }
             }
-            KeyCode::Char('?') => app.mode = AppMode::Help,
+            KeyCode::Char('?') => {
+                app.help_query.clear();
+                app.help_scroll = 0;
+                app.mode = AppMode::Help;
+            }
+            KeyCode::Char(prefix @ ('m' | '\'')) => app.pending_key = Some(prefix),
             KeyCode::Char('g') if app.section == dashboard::DashboardSection::Repos => {
                 app.group_by_dir = !app.group_by_dir;
                 app.clamp_selection();
@@ -464,36 +1627,181 @@ fn handle_key(
                 app.agent_focus_mode = !app.agent_focus_mode;
                 app.clamp_selection();
                 if app.agent_focus_mode {
-                    app.notify("Agent focus: showing actionable repos");
+                    app.notify(t("notify-focus-actionable"));
                 } else {
-                    app.notify("Agent focus: showing all repos");
+                    app.notify(t("notify-focus-all"));
                 }
             }
-            KeyCode::Char('x') | KeyCode::Char('X') => {
-                if let Some(action) = app.selected_action() {
-                    let label = action.label.clone();
-                    app.stage_action_confirmation(action);
-                    app.notify(format!("Review action: {}", label));
-                } else {
-                    app.notify("No action available on this row");
+            KeyCode::Char('d') if app.section == dashboard::DashboardSection::Repos => {
+                if app.role.is_read_only() {
+                    app.notify(app.role.read_only_notice());
+                    return;
+                }
+                match app.toggle_dismissed() {
+                    Some(msg) => {
+                        if let Err(e) = app.suppressions.save(&suppress::suppressions_path()) {
+                            app.notify(tr("notify-save-dismissals-failed", &[("error", &e)]));
+                        } else {
+                            app.notify(msg);
+                        }
+                        app.clamp_selection();
+                    }
+                    None => app.notify(t("notify-nothing-to-dismiss")),
+                }
+            }
+            KeyCode::Char('x') | KeyCode::Char('X') => match app.selected_action() {
+                Some(action) => review_action(app, action),
+                None => app.notify(t("notify-no-action")),
+            },
+            KeyCode::Enter if app.section == dashboard::DashboardSection::Home => {
+                match app.next_best_action() {
+                    Some((_, _, Some(action))) => review_action(app, action),
+                    Some((_, rec, None)) => {
+                        app.notify(tr("notify-no-one-key-action", &[("command", &rec.command)]))
+                    }
+                    None => app.notify(t("notify-nothing-needs-attention")),
+                }
+            }
+            KeyCode::Char('n') if app.section == dashboard::DashboardSection::Home => {
+                let msg = app.skip_next_best_action();
+                app.notify(msg);
+            }
+            KeyCode::Char('t')
+                if matches!(
+                    app.section,
+                    dashboard::DashboardSection::Home | dashboard::DashboardSection::Repos
+                ) =>
+            {
+                match app.selected_task() {
+                    Some(task) => {
+                        app.stage_action_confirmation(task);
+                        app.notify(t("notify-review-task"));
+                    }
+                    None => app.notify(t("notify-nothing-to-file")),
+                }
+            }
+            KeyCode::Enter if app.section == dashboard::DashboardSection::Settings => {
+                app.activate_setting();
+            }
+            KeyCode::Char('w') if app.section == dashboard::DashboardSection::Settings => {
+                if app.role.is_read_only() {
+                    app.notify(app.role.read_only_notice());
+                    return;
+                }
+                let watch_mode = app.config.watch_mode;
+                match app.save_settings() {
+                    Ok(count) => {
+                        let key = if app.config.watch_mode != watch_mode {
+                            "notify-settings-saved-restart"
+                        } else {
+                            "notify-settings-saved"
+                        };
+                        app.notify(tr(
+                            key,
+                            &[("count", &count), ("path", &app.config_path.display())],
+                        ));
+                        if app.is_scanning {
+                            *pending_rescan = true;
+                        } else {
+                            trigger_scan(
+                                app.role,
+                                app.config.clone(),
+                                app.collect_mode(),
+                                scan_tx.clone(),
+                                current_cache.clone(),
+                                cache_tx.clone(),
+                                dash_tx.clone(),
+                            );
+                            app.is_scanning = true;
+                        }
+                    }
+                    Err(e) => app.notify(tr("notify-save-settings-failed", &[("error", &e)])),
+                }
+            }
+            KeyCode::Char('R') if app.section == dashboard::DashboardSection::Settings => {
+                match app.reload_settings() {
+                    Ok(()) => app.notify(tr(
+                        "notify-settings-reloaded",
+                        &[("path", &app.config_path.display())],
+                    )),
+                    Err(e) => app.notify(tr("notify-reload-settings-failed", &[("error", &e)])),
                 }
             }
             KeyCode::Enter if app.section == dashboard::DashboardSection::Repos => {
                 if let Some(repo) = app.selected_repo() {
                     let path = repo.path.clone();
-                    let editor = app
-                        .config
-                        .editor
-                        .clone()
-                        .or_else(|| std::env::var("EDITOR").ok())
-                        .unwrap_or_else(|| "code".to_string());
-                    let _ = actions::open_in_editor(&path, &editor);
+                    let _ = actions::open_in_editor(&path, &app.editor());
+                    app.record_use(&path);
+                }
+            }
+            KeyCode::Enter if app.section == dashboard::DashboardSection::PullRequests => {
+                let target =
+                    app.dashboard
+                        .pull_requests
+                        .get(app.selected)
+                        .and_then(|row| match &row.pull_request {
+                            Some(pr) => Some((format!("#{}", pr.number), pr.url.clone())),
+                            None => row
+                                .ci_url
+                                .clone()
+                                .map(|url| (t("notify-latest-ci-run"), url)),
+                        });
+                match target {
+                    Some((what, url)) => match actions::open_url(&url) {
+                        Ok(()) => app.notify(tr("notify-opening", &[("target", &what)])),
+                        Err(err) => app.notify(tr(
+                            "notify-open-failed",
+                            &[("target", &what), ("error", &err)],
+                        )),
+                    },
+                    None => app.notify(t("notify-no-pull-request")),
                 }
             }
             KeyCode::Char('o') if app.section == dashboard::DashboardSection::Repos => {
                 if let Some(repo) = app.selected_repo() {
                     let path = repo.path.clone();
                     let _ = actions::open_in_file_manager(&path);
+                    app.record_use(&path);
+                }
+            }
+            KeyCode::Char('O') if app.section == dashboard::DashboardSection::Repos => {
+                let ticket = app
+                    .selected_repo()
+                    .and_then(|repo| app.ticket_for(repo))
+                    .map(|ticket| (ticket.key.clone(), ticket.url.clone()));
+                match ticket {
+                    Some((key, Some(url))) => match actions::open_url(&url) {
+                        Ok(()) => app.notify(tr("notify-opening", &[("target", &key)])),
+                        Err(err) => app.notify(tr(
+                            "notify-open-failed",
+                            &[("target", &key), ("error", &err)],
+                        )),
+                    },
+                    Some((key, None)) => {
+                        app.notify(tr("notify-no-ticket-link", &[("ticket", &key)]))
+                    }
+                    None => app.notify(t("notify-no-ticket")),
+                }
+            }
+            KeyCode::Char('b') if app.section == dashboard::DashboardSection::Repos => {
+                let today = chrono::Local::now().date_naive();
+                let bundle = app.selected_repo().map(|repo| {
+                    let output = app.config.backup.bundle_path(&repo.path, today);
+                    (repo.path.to_string_lossy().to_string(), output)
+                });
+                match bundle {
+                    Some((repo_path, Some(output))) => {
+                        app.stage_action_confirmation(dashboard::ActionCommand::new(
+                            "bundle to backup drive",
+                            dashboard::ActionKind::GitBundleCreate {
+                                repo_path,
+                                output: output.to_string_lossy().to_string(),
+                            },
+                        ));
+                        app.notify(t("notify-review-bundle"));
+                    }
+                    Some((_, None)) => app.notify(t("notify-no-bundle-dir")),
+                    None => {}
                 }
             }
             KeyCode::Char('f') if app.section == dashboard::DashboardSection::Repos => {
@@ -504,9 +1812,12 @@ fn handle_key(
                             repo_path: repo.path.to_string_lossy().to_string(),
                         },
                     ));
-                    app.notify("Review fetch action");
+                    app.notify(t("notify-review-fetch"));
                 }
             }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.open_recent();
+            }
             KeyCode::Char('p') if app.section == dashboard::DashboardSection::Repos => {
                 if let Some(repo) = app.selected_repo() {
                     app.stage_action_confirmation(dashboard::ActionCommand::new(
@@ -515,21 +1826,177 @@ fn handle_key(
                             repo_path: repo.path.to_string_lossy().to_string(),
                         },
                     ));
-                    app.notify("Review pull action");
+                    app.notify(t("notify-review-pull"));
                 }
             }
             KeyCode::Char('P') if app.section == dashboard::DashboardSection::Repos => {
                 if let Some(repo) = app.selected_repo() {
-                    app.stage_action_confirmation(dashboard::ActionCommand::new(
-                        "push",
-                        dashboard::ActionKind::GitPush {
-                            repo_path: repo.path.to_string_lossy().to_string(),
+                    let action = app::push_command(repo);
+                    app.stage_action_confirmation(action);
+                    app.notify(t("notify-review-push"));
+                }
+            }
+            KeyCode::Char('L') if app.section == dashboard::DashboardSection::Repos => {
+                if let Some(repo) = app.selected_repo().cloned() {
+                    let path = repo.path.clone();
+                    spawn_view_load(
+                        load_tx,
+                        move || git::get_recent_commits(&path, app::COMMIT_LOG_LIMIT),
+                        move |app, entries| match entries {
+                            Ok(entries) if entries.is_empty() => app.notify(t("notify-no-commits")),
+                            Ok(entries) => {
+                                app.commit_log = Some(CommitLog::new(&repo, entries));
+                                app.mode = AppMode::CommitLog;
+                            }
+                            Err(e) => {
+                                app.notify(tr("notify-read-commits-failed", &[("error", &e)]))
+                            }
                         },
-                    ));
-                    app.notify("Review push action");
+                    );
+                }
+            }
+            KeyCode::Char('z') if app.section == dashboard::DashboardSection::Repos => {
+                if let Some(repo) = app.selected_repo().cloned() {
+                    let path = repo.path.clone();
+                    spawn_view_load(
+                        load_tx,
+                        move || git::list_changed_files(&path),
+                        move |app, files| match files {
+                            Ok(files) if files.is_empty() => {
+                                app.notify(t("notify-nothing-to-stash"))
+                            }
+                            Ok(files) => {
+                                app.stash_picker = Some(StashPicker::new(&repo, files));
+                                app.mode = AppMode::StashPick;
+                            }
+                            Err(e) => {
+                                app.notify(tr("notify-list-changes-failed", &[("error", &e)]))
+                            }
+                        },
+                    );
+                }
+            }
+            KeyCode::Char('D') if app.section == dashboard::DashboardSection::Repos => {
+                if let Some(repo) = app.selected_repo().cloned() {
+                    let path = repo.path.clone();
+                    spawn_view_load(
+                        load_tx,
+                        move || git::working_diff(&path),
+                        move |app, diff| match diff {
+                            Ok(diff) if diff.is_empty() => app.notify(t("notify-no-changes")),
+                            Ok(diff) => {
+                                app.diff_view = Some(DiffView::new(&repo.name, &diff));
+                                app.mode = AppMode::Diff;
+                            }
+                            Err(e) => app.notify(tr("notify-read-diff-failed", &[("error", &e)])),
+                        },
+                    );
                 }
             }
+            KeyCode::Char('Z') if app.section == dashboard::DashboardSection::Repos => {
+                if let Some(repo) = app.selected_repo().cloned() {
+                    let path = repo.path.clone();
+                    spawn_view_load(
+                        load_tx,
+                        move || git::list_stashes(&path),
+                        move |app, entries| match entries {
+                            Ok(entries) if entries.is_empty() => app.notify(t("notify-no-stashes")),
+                            Ok(entries) => {
+                                app.stash_browser = Some(StashBrowser::new(&repo, entries));
+                                app.mode = AppMode::Stashes;
+                            }
+                            Err(e) => {
+                                app.notify(tr("notify-list-stashes-failed", &[("error", &e)]))
+                            }
+                        },
+                    );
+                }
+            }
+            KeyCode::Char('B') if app.section == dashboard::DashboardSection::Repos => {
+                if let Some(repo) = app.selected_repo().cloned() {
+                    let path = repo.path.clone();
+                    spawn_view_load(
+                        load_tx,
+                        move || git::list_branches(&path),
+                        move |app, branches| match branches {
+                            Ok(branches) if branches.is_empty() => {
+                                app.notify(t("notify-no-branches"))
+                            }
+                            Ok(branches) => {
+                                app.branch_picker = Some(BranchPicker::new(&repo, branches));
+                                app.mode = AppMode::BranchPick;
+                            }
+                            Err(e) => {
+                                app.notify(tr("notify-list-branches-failed", &[("error", &e)]))
+                            }
+                        },
+                    );
+                }
+            }
+            KeyCode::Char('I') => app.mode = AppMode::Integrations,
+            KeyCode::Char('T') => {
+                spawn_view_load(
+                    load_tx,
+                    || history::load(&history::history_path()),
+                    |app, entries| {
+                        if entries.len() < 2 {
+                            app.notify(tr(
+                                "notify-history-too-short",
+                                &[("minutes", &(history::RECORD_INTERVAL_SECS / 60))],
+                            ));
+                        } else {
+                            app.time_travel = Some(TimeTravel::new(entries));
+                            app.mode = AppMode::TimeTravel;
+                        }
+                    },
+                );
+            }
+            KeyCode::Char('G') => {
+                if app.grep.take().is_some() {
+                    app.clamp_selection();
+                    app.notify(t("notify-search-cleared"));
+                } else {
+                    app.grep_input.clear();
+                    app.mode = AppMode::Grep;
+                }
+            }
+            KeyCode::Char('C') if app.section == dashboard::DashboardSection::Repos => {
+                if let Some(repo) = app.selected_repo().cloned() {
+                    let path = repo.path.clone();
+                    let list = move || {
+                        let commits = git::recent_commits(&path, CHERRY_PICK_LOG_LIMIT)?;
+                        Ok((
+                            commits,
+                            git::list_branches(&path)?,
+                            git::list_worktree_branches(&path)?,
+                        ))
+                    };
+                    spawn_view_load(load_tx, list, move |app, listed: anyhow::Result<_>| {
+                        match listed {
+                            Ok((commits, _, _)) if commits.is_empty() => {
+                                app.notify(t("notify-no-commits"))
+                            }
+                            Ok((commits, branches, worktrees)) => {
+                                app.cherry_picker =
+                                    Some(CherryPicker::new(&repo, commits, branches, worktrees));
+                                app.mode = AppMode::CherryPick;
+                            }
+                            Err(e) => {
+                                app.notify(tr("notify-read-history-failed", &[("error", &e)]))
+                            }
+                        }
+                    });
+                }
+            }
+            KeyCode::Char('u') if app.section == dashboard::DashboardSection::Repos => {
+                let msg = app.stage_undo_commit();
+                app.notify(msg);
+            }
             KeyCode::Char('c') if app.section == dashboard::DashboardSection::Repos => {
+                if app.role.is_read_only() {
+                    app.notify(app.role.read_only_notice());
+                    return;
+                }
                 app.commit_message.clear();
                 app.mode = AppMode::Commit;
             }
@@ -552,42 +2019,409 @@ fn handle_key(
             }
             _ => {}
         },
-        AppMode::Help => {
+        AppMode::EditSetting => match key.code {
+            KeyCode::Esc => {
+                app.settings.input = None;
+                app.mode = AppMode::Normal;
+            }
+            KeyCode::Enter => match app.settings.commit_input(app.selected) {
+                Ok(()) => app.mode = AppMode::Normal,
+                Err(msg) => app.notify(msg),
+            },
+            KeyCode::Backspace => {
+                if let Some(input) = app.settings.input.as_mut() {
+                    input.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(input) = app.settings.input.as_mut() {
+                    input.push(c);
+                }
+            }
+            _ => {}
+        },
+        AppMode::Grep => match key.code {
+            KeyCode::Esc => app.mode = AppMode::Normal,
+            KeyCode::Enter => {
+                if let Some(pattern) = app.start_grep() {
+                    let paths: Vec<PathBuf> = app.repos.iter().map(|r| r.path.clone()).collect();
+                    app.notify(tr(
+                        "notify-grep-started",
+                        &[("count", &paths.len()), ("pattern", &pattern)],
+                    ));
+                    let max_parallel = app.config.processes.max_git_processes;
+                    let tx = grep_tx.clone();
+                    tokio::spawn(async move {
+                        let hits = search::search(paths, &pattern, false, max_parallel).await;
+                        let _ = tx.send((pattern, hits)).await;
+                    });
+                }
+            }
+            KeyCode::Backspace => {
+                app.grep_input.pop();
+            }
+            KeyCode::Char(c) => app.grep_input.push(c),
+            _ => {}
+        },
+        AppMode::Help => match key.code {
+            KeyCode::Esc if !app.help_query.is_empty() => {
+                app.help_query.clear();
+                app.help_scroll = 0;
+            }
+            KeyCode::Esc | KeyCode::Enter => app.mode = AppMode::Normal,
+            KeyCode::Char('?') if app.help_query.is_empty() => app.mode = AppMode::Normal,
+            KeyCode::Up => app.help_scroll = app.help_scroll.saturating_sub(1),
+            KeyCode::PageUp => app.help_scroll = app.help_scroll.saturating_sub(10),
+            KeyCode::Down | KeyCode::PageDown => {
+                let step = if key.code == KeyCode::Down { 1 } else { 10 };
+                let last = ui::help::line_count(app).saturating_sub(1);
+                app.help_scroll = (app.help_scroll + step).min(last);
+            }
+            KeyCode::Backspace => {
+                app.help_query.pop();
+                app.help_scroll = 0;
+            }
+            KeyCode::Char(c) => {
+                app.help_query.push(c);
+                app.help_scroll = 0;
+            }
+            _ => {}
+        },
+        AppMode::Integrations => {
             app.mode = AppMode::Normal;
         }
+        AppMode::Tour => match key.code {
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                app.tour_step(true)
+            }
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Backspace => app.tour_step(false),
+            KeyCode::Esc | KeyCode::Char('q') => app.end_tour(),
+            _ => {}
+        },
         AppMode::ConfirmAction => match key.code {
             KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
                 app.clear_pending_action();
-                app.notify("Action cancelled");
+                app.notify(t("notify-action-cancelled"));
             }
             KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
-                if let Some(action) = app.pending_action.take() {
-                    let label = action.label.clone();
-                    actions::run_action(action.action, notif_tx.clone(), action_done_tx.clone());
-                    app.mode = AppMode::Normal;
-                    app.notify(format!("Running action: {}", label));
-                } else {
-                    app.mode = AppMode::Normal;
+                match app.confirm_pending_action() {
+                    Some((action, settings)) => {
+                        let label = action.label.clone();
+                        actions::run_action(
+                            action.action,
+                            settings,
+                            notif_tx.clone(),
+                            action_done_tx.clone(),
+                        );
+                        app.notify(tr("notify-running-action", &[("action", &label)]));
+                    }
+                    // Waiting for the second confirmation of a protected branch.
+                    None if app.pending_action.is_some() => {}
+                    None => app.mode = AppMode::Normal,
                 }
             }
             _ => {}
         },
+        AppMode::StashPick => {
+            let Some(picker) = app.stash_picker.as_mut() else {
+                app.mode = AppMode::Normal;
+                return;
+            };
+            if picker.editing_message {
+                match key.code {
+                    KeyCode::Esc => picker.editing_message = false,
+                    KeyCode::Enter => {
+                        if let Some(action) = picker.to_action() {
+                            app.stash_picker = None;
+                            app.stage_action_confirmation(action);
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        picker.message.pop();
+                    }
+                    KeyCode::Char(c) => picker.message.push(c),
+                    _ => {}
+                }
+                return;
+            }
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.stash_picker = None;
+                    app.mode = AppMode::Normal;
+                }
+                KeyCode::Char('j') | KeyCode::Down => picker.move_cursor(1),
+                KeyCode::Char('k') | KeyCode::Up => picker.move_cursor(-1),
+                KeyCode::Char(' ') => {
+                    picker.toggle();
+                    picker.move_cursor(1);
+                }
+                KeyCode::Char('a') => picker.toggle_all(),
+                KeyCode::Enter => {
+                    if picker.selected_paths().is_empty() {
+                        app.notify(t("notify-select-files"));
+                    } else {
+                        picker.editing_message = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        AppMode::RemoteWizard => {
+            let Some(wizard) = app.remote_wizard.as_mut() else {
+                app.mode = AppMode::Normal;
+                return;
+            };
+            match key.code {
+                KeyCode::Esc => {
+                    app.remote_wizard = None;
+                    app.mode = AppMode::Normal;
+                }
+                KeyCode::Up | KeyCode::BackTab => wizard.move_field(-1),
+                KeyCode::Down | KeyCode::Tab => wizard.move_field(1),
+                KeyCode::Left | KeyCode::Right | KeyCode::Char(' ')
+                    if wizard.field != WizardField::Name =>
+                {
+                    wizard.toggle()
+                }
+                KeyCode::Backspace if wizard.field == WizardField::Name => {
+                    wizard.name.pop();
+                }
+                KeyCode::Char(c) if wizard.field == WizardField::Name => wizard.name.push(c),
+                KeyCode::Enter => match wizard.to_action() {
+                    Some(action) => {
+                        app.remote_wizard = None;
+                        app.stage_action_confirmation(action);
+                    }
+                    None => app.notify(t("notify-bad-repo-name")),
+                },
+                _ => {}
+            }
+        }
+        AppMode::RecentRepos => {
+            let Some(picker) = app.recent_picker.as_mut() else {
+                app.mode = AppMode::Normal;
+                return;
+            };
+            match key.code {
+                KeyCode::Esc => {
+                    app.recent_picker = None;
+                    app.mode = AppMode::Normal;
+                }
+                KeyCode::Down => picker.move_cursor(1),
+                KeyCode::Up => picker.move_cursor(-1),
+                // Pressing Ctrl-P again steps down, as in editors' switchers.
+                KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    picker.move_cursor(1)
+                }
+                KeyCode::Enter => {
+                    let msg = app.jump_to_recent();
+                    app.notify(msg);
+                }
+                KeyCode::Backspace => picker.backspace(),
+                KeyCode::Char(c) => picker.type_char(c),
+                _ => {}
+            }
+        }
+        AppMode::BranchPick => {
+            let Some(picker) = app.branch_picker.as_mut() else {
+                app.mode = AppMode::Normal;
+                return;
+            };
+            if picker.creating {
+                match key.code {
+                    KeyCode::Esc => picker.creating = false,
+                    KeyCode::Enter => match picker.create_action() {
+                        Ok(action) => {
+                            app.branch_picker = None;
+                            app.stage_action_confirmation(action);
+                        }
+                        Err(msg) => app.notify(msg),
+                    },
+                    KeyCode::Backspace => {
+                        picker.new_name.pop();
+                    }
+                    KeyCode::Char(c) => picker.new_name.push(c),
+                    _ => {}
+                }
+                return;
+            }
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.branch_picker = None;
+                    app.mode = AppMode::Normal;
+                }
+                KeyCode::Char('j') | KeyCode::Down => picker.move_cursor(1),
+                KeyCode::Char('k') | KeyCode::Up => picker.move_cursor(-1),
+                KeyCode::Char('a') if picker.dirty_count > 0 => {
+                    picker.autostash = !picker.autostash
+                }
+                KeyCode::Char('n') => picker.creating = true,
+                KeyCode::Enter => match picker.switch_action() {
+                    Ok(action) => {
+                        app.branch_picker = None;
+                        app.stage_action_confirmation(action);
+                    }
+                    Err(msg) => app.notify(msg),
+                },
+                _ => {}
+            }
+        }
+        AppMode::CommitLog => {
+            let Some(log) = app.commit_log.as_mut() else {
+                app.mode = AppMode::Normal;
+                return;
+            };
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.commit_log = None;
+                    app.mode = AppMode::Normal;
+                }
+                KeyCode::Char('j') | KeyCode::Down => log.move_cursor(1),
+                KeyCode::Char('k') | KeyCode::Up => log.move_cursor(-1),
+                KeyCode::Char('P') => match log.push.take() {
+                    Some(action) => {
+                        app.commit_log = None;
+                        app.stage_action_confirmation(action);
+                    }
+                    None => app.notify(t("notify-nothing-to-push")),
+                },
+                _ => {}
+            }
+        }
+        AppMode::Diff => {
+            let Some(view) = app.diff_view.as_mut() else {
+                app.mode = AppMode::Normal;
+                return;
+            };
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.diff_view = None;
+                    app.mode = AppMode::Normal;
+                }
+                KeyCode::Char('j') | KeyCode::Down => view.scroll_by(1),
+                KeyCode::Char('k') | KeyCode::Up => view.scroll_by(-1),
+                KeyCode::PageDown | KeyCode::Char(' ') => view.page(1),
+                KeyCode::PageUp => view.page(-1),
+                KeyCode::Char('g') | KeyCode::Home => view.scroll = 0,
+                KeyCode::Char('G') | KeyCode::End => view.scroll_by(i32::MAX),
+                KeyCode::Char('n') => view.jump_file(1),
+                KeyCode::Char('N') => view.jump_file(-1),
+                _ => {}
+            }
+        }
+        AppMode::Stashes => {
+            let Some(browser) = app.stash_browser.as_mut() else {
+                app.mode = AppMode::Normal;
+                return;
+            };
+            let op = match key.code {
+                KeyCode::Char('a') => StashOp::Apply,
+                KeyCode::Char('p') | KeyCode::Enter => StashOp::Pop,
+                KeyCode::Char('d') => StashOp::Drop,
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.stash_browser = None;
+                    app.mode = AppMode::Normal;
+                    return;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    browser.move_cursor(1);
+                    return;
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    browser.move_cursor(-1);
+                    return;
+                }
+                _ => return,
+            };
+            if let Some(action) = browser.action(op) {
+                app.stash_browser = None;
+                app.stage_action_confirmation(action);
+            }
+        }
+        AppMode::CherryPick => {
+            let Some(picker) = app.cherry_picker.as_mut() else {
+                app.mode = AppMode::Normal;
+                return;
+            };
+            match key.code {
+                KeyCode::Esc if picker.choosing_target => picker.choosing_target = false,
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.cherry_picker = None;
+                    app.mode = AppMode::Normal;
+                }
+                KeyCode::Char('j') | KeyCode::Down => picker.move_cursor(1),
+                KeyCode::Char('k') | KeyCode::Up => picker.move_cursor(-1),
+                KeyCode::Char(' ') if !picker.choosing_target => picker.toggle(),
+                KeyCode::Enter if !picker.choosing_target => {
+                    if let Err(msg) = picker.choose_target() {
+                        app.notify(msg);
+                    }
+                }
+                KeyCode::Enter => match picker.to_action() {
+                    Ok(action) => {
+                        app.cherry_picker = None;
+                        app.stage_action_confirmation(action);
+                    }
+                    Err(msg) => app.notify(msg),
+                },
+                _ => {}
+            }
+        }
+        AppMode::TimeTravel => {
+            let Some(travel) = app.time_travel.as_mut() else {
+                app.mode = AppMode::Normal;
+                return;
+            };
+            match key.code {
+                // Backs out of the diff or the first pick before closing.
+                KeyCode::Esc | KeyCode::Char('q') if !travel.back() => {
+                    app.time_travel = None;
+                    app.mode = AppMode::Normal;
+                }
+                KeyCode::Char('j') | KeyCode::Down if travel.compared.is_none() => {
+                    travel.move_cursor(1)
+                }
+                KeyCode::Char('k') | KeyCode::Up if travel.compared.is_none() => {
+                    travel.move_cursor(-1)
+                }
+                KeyCode::Enter | KeyCode::Char(' ') if travel.compared.is_none() => {
+                    travel.select(&app.config.display)
+                }
+                _ => {}
+            }
+        }
         AppMode::Commit => match key.code {
             KeyCode::Esc => {
                 app.commit_message.clear();
                 app.mode = AppMode::Normal;
             }
             KeyCode::Enter => {
-                if !app.commit_message.is_empty() {
-                    if let Some(repo) = app.selected_repo() {
-                        let path = repo.path.clone();
-                        let msg = app.commit_message.clone();
-                        actions::git_commit(&path, &msg, notif_tx.clone(), action_done_tx.clone());
-                        app.notify(format!("Committing \"{}\"…", msg));
-                    }
-                }
-                app.commit_message.clear();
+                let msg = std::mem::take(&mut app.commit_message);
                 app.mode = AppMode::Normal;
+                let Some(repo) = app.selected_repo().filter(|_| !msg.is_empty()) else {
+                    return;
+                };
+                // Same checks as any other commit action: protected branches
+                // ask twice, verify_state and the audit log apply.
+                let commit = dashboard::ActionCommand::new(
+                    "commit",
+                    dashboard::ActionKind::GitAddCommit {
+                        repo_path: repo.path.to_string_lossy().to_string(),
+                        message: msg.clone(),
+                    },
+                );
+                if app.protected_target(&commit.action).is_some() {
+                    app.stage_action_confirmation(commit);
+                    return;
+                }
+                let settings = app.action_settings(&commit.action);
+                actions::run_action(
+                    commit.action,
+                    settings,
+                    notif_tx.clone(),
+                    action_done_tx.clone(),
+                );
+                app.notify(tr("notify-committing", &[("message", &msg)]));
             }
             KeyCode::Backspace => {
                 app.commit_message.pop();
@@ -604,18 +2438,92 @@ fn invalidate_cache_for_repo(cache: &mut StatusCache, repo_path: &Path) {
     cache.remove(repo_path);
 }
 
-// ─── --once output ───────────────────────────────────────────────────────────
+/// Quiet time after a watched repo's last event before it is re-checked;
+/// one commit touches several files.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Re-check one repo in the background; the result arrives on `tx` for
+/// [`App::replace_repo`].
+fn spawn_rescan(
+    path: &Path,
+    cache: &StatusCache,
+    tx: &tokio::sync::mpsc::Sender<(Repo, StatusCache)>,
+) {
+    let mut entry: StatusCache = cache
+        .get_key_value(path)
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .into_iter()
+        .collect();
+    let (repo_path, tx) = (path.to_path_buf(), tx.clone());
+    tokio::spawn(async move {
+        let mut repo = monitor::rescan_repo(&repo_path, &mut entry).await;
+        history::annotate_work_ages(std::slice::from_mut(&mut repo));
+        let _ = tx.send((repo, entry)).await;
+    });
+}
 
-fn print_table(repos: &[Repo]) {
+/// List ignored paths for the repos `repo_watcher` asks about, off the UI
+/// loop; answers go to [`watcher::RepoWatcher::apply_ignored`].
+fn spawn_ignore_refresh(
+    repo_watcher: &mut watcher::RepoWatcher,
+    tx: &tokio::sync::mpsc::UnboundedSender<(PathBuf, Vec<PathBuf>)>,
+) {
+    for repo in repo_watcher.take_ignore_due() {
+        let tx = tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let ignored = watcher::ignored_paths(&repo);
+            let _ = tx.send((repo, ignored));
+        });
+    }
+}
+
+/// Read CODEOWNERS and list the changed files off the UI loop; the answer
+/// goes to [`app::App::apply_ownership`].
+fn spawn_ownership_load(
+    key: app::OwnershipKey,
+    tx: &tokio::sync::mpsc::UnboundedSender<(app::OwnershipKey, Option<Ownership>)>,
+) {
+    let tx = tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let ownership = Ownership::load(&key.0, key.1);
+        let _ = tx.send((key, ownership));
+    });
+}
+
+/// A view read off the UI loop, ready to be opened by [`spawn_view_load`].
+type LoadedView = Box<dyn FnOnce(&mut App) + Send>;
+
+/// Run `load` (git calls, file reads) off the UI loop; `open` then gets its
+/// result on the loop, unless the user has left normal mode by then.
+fn spawn_view_load<T: Send + 'static>(
+    tx: &tokio::sync::mpsc::UnboundedSender<LoadedView>,
+    load: impl FnOnce() -> T + Send + 'static,
+    open: impl FnOnce(&mut App, T) + Send + 'static,
+) {
+    let tx = tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let loaded = load();
+        let _ = tx.send(Box::new(move |app: &mut App| open(app, loaded)));
+    });
+}
+
+// ─── --once / --watch output ────────────────────────────────────────────────
+
+fn write_table(out: &mut impl std::fmt::Write, repos: &[Repo]) -> std::fmt::Result {
     if repos.is_empty() {
-        println!("No git repos found. Check your config.");
-        return;
+        return writeln!(out, "No git repos found. Check your config.");
     }
 
-    let name_w = repos.iter().map(|r| r.name.len()).max().unwrap_or(4).max(4);
+    // Rows start with the status glyph and a space, inside the NAME column.
+    let name_w = repos
+        .iter()
+        .map(|r| format::width(&r.name) + 2)
+        .max()
+        .unwrap_or(4)
+        .max(4);
     let branch_w = repos
         .iter()
-        .map(|r| r.status.branch.len())
+        .map(|r| format::width(&r.status.branch))
         .max()
         .unwrap_or(6)
         .max(6);
@@ -626,7 +2534,8 @@ fn print_table(repos: &[Repo]) {
         .unwrap_or(4)
         .max(4);
 
-    println!(
+    writeln!(
+        out,
         "{:<nw$}  {:<bw$}  {:>11}  {:>7}  {:<aw$}  STATUS",
         "NAME",
         "BRANCH",
@@ -636,16 +2545,22 @@ fn print_table(repos: &[Repo]) {
         nw = name_w,
         bw = branch_w,
         aw = next_w,
-    );
-    println!("{}", "─".repeat(name_w + branch_w + next_w + 41));
+    )?;
+    writeln!(
+        out,
+        "{}",
+        ui::theme::glyph(&"─".repeat(name_w + branch_w + next_w + 41))
+    )?;
 
     for repo in repos {
-        let (indicator, status_label) = match repo.status_color() {
-            git::StatusColor::Clean => ("○", "clean"),
-            git::StatusColor::Uncommitted => ("●", "uncommitted"),
-            git::StatusColor::Unpushed => ("●", "unpushed"),
-            git::StatusColor::Dirty => ("●", "dirty"),
-            git::StatusColor::NoRemote => ("○", "no remote"),
+        let state = repo.status_color();
+        let indicator = ui::theme::status_glyph(state);
+        let status_label = match state {
+            git::StatusColor::Clean => "clean",
+            git::StatusColor::Uncommitted => "uncommitted",
+            git::StatusColor::Unpushed => "unpushed",
+            git::StatusColor::Dirty => "dirty",
+            git::StatusColor::NoRemote => "no remote",
         };
 
         let uncommitted = if repo.status.uncommitted_count > 0 {
@@ -670,52 +2585,128 @@ fn print_table(repos: &[Repo]) {
         } else {
             rec.short_action
         };
+        // Only our own columns go through the glyph swap, never the repo
+        // name or branch.
+        let (uncommitted, sync, next) = (
+            ui::theme::glyph(&uncommitted),
+            ui::theme::glyph(&sync),
+            ui::theme::glyph(next),
+        );
 
-        println!(
-            "{} {:<nw$}  {:<bw$}  {:>11}  {:>7}  {:<aw$}  {}",
+        let line = format!(
+            "{} {}  {}  {}  {}  {:<aw$}  {}",
             indicator,
-            repo.name,
-            repo.status.branch,
-            uncommitted,
-            sync,
+            format::pad(&repo.name, name_w - 2),
+            format::pad(&repo.status.branch, branch_w),
+            format::pad_left(&uncommitted, 11),
+            format::pad_left(&sync, 7),
             next,
             status_label,
-            nw = name_w.saturating_sub(2),
-            bw = branch_w,
             aw = next_w,
         );
+        writeln!(out, "{}", line)?;
     }
+    Ok(())
 }
 
-fn print_json(repos: &[Repo]) {
-    println!("[");
+/// `--stats`: the week's totals, then one line per repo touched.
+fn week_report(
+    stats: &[dashboard::RepoWeekStats],
+    monday: chrono::NaiveDate,
+    display: &config::DisplayConfig,
+) -> String {
+    let since = format::date(display, &monday.and_time(chrono::NaiveTime::MIN).and_utc());
+    let number = |n: usize| format::number(display, n as u64);
+    let week = dashboard::WeekTotals::of(stats);
+    if week.commits == 0 {
+        return format!("{}\n", tr("stats-no-commits", &[("since", &since)]));
+    }
+    let mut out = format!(
+        "{}\n\n",
+        tr(
+            "stats-totals",
+            &[
+                ("since", &since),
+                ("commits", &number(week.commits)),
+                ("repos", &number(week.repos)),
+                ("insertions", &number(week.insertions)),
+                ("deletions", &number(week.deletions)),
+            ],
+        )
+    );
+    let commits: Vec<String> = stats
+        .iter()
+        .map(|s| tr("stats-repo-commits", &[("count", &number(s.commits))]))
+        .collect();
+    let name_w = stats
+        .iter()
+        .map(|s| format::width(&s.repo))
+        .max()
+        .unwrap_or(0);
+    let commits_w = commits.iter().map(|c| format::width(c)).max().unwrap_or(0);
+    for (repo, commits) in stats.iter().zip(&commits) {
+        out.push_str(&format!(
+            "  {}  {}  {:>7}  {:>7}\n",
+            format::pad(&repo.repo, name_w),
+            format::pad_left(commits, commits_w),
+            format!("+{}", number(repo.insertions)),
+            format!("-{}", number(repo.deletions)),
+        ));
+    }
+    out
+}
+
+/// A hand-printed JSON document on one line, keys in their printed order.
+/// Strings are escaped, so no line break falls inside a value.
+fn one_line(pretty: &str) -> String {
+    pretty.lines().map(str::trim).collect()
+}
+
+fn write_json(out: &mut impl std::fmt::Write, repos: &[Repo]) -> std::fmt::Result {
+    writeln!(out, "[")?;
     let last = repos.len().saturating_sub(1);
     for (i, repo) in repos.iter().enumerate() {
         let comma = if i < last { "," } else { "" };
-        println!(
-            "  {{\"name\":{:?},\"path\":{:?},\"branch\":{:?},\"uncommitted\":{},\"unpushed\":{},\"behind\":{},\"stash\":{},\"has_remote\":{},\"needs_attention\":{}}}{}",
+        writeln!(
+            out,
+            "  {{\"name\":{:?},\"path\":{:?},\"branch\":{:?},\"uncommitted\":{},\"staged\":{},\"unstaged\":{},\"untracked\":{},\"conflicted\":{},\"unpushed\":{},\"behind\":{},\"stash\":{},\"has_remote\":{},\"needs_attention\":{}}}{}",
             repo.name,
             repo.path.to_string_lossy(),
             repo.status.branch,
             repo.status.uncommitted_count,
+            repo.status.changes.staged,
+            repo.status.changes.unstaged,
+            repo.status.changes.untracked,
+            repo.status.changes.conflicted,
             repo.status.unpushed_count,
             repo.status.behind_count,
             repo.status.stash_count,
             repo.status.has_remote,
             repo.needs_attention(),
             comma,
-        );
+        )?;
     }
-    println!("]");
+    writeln!(out, "]")
 }
 
-fn print_agent_brief(repos: &[Repo]) {
-    println!("# AgentPulse Brief");
-    println!();
-    println!("- Generated: {}", Local::now().to_rfc3339());
-    println!("- Repositories scanned: {}", repos.len());
-
-    let recommendations = sorted_recommendations(repos);
+#[allow(clippy::too_many_arguments)]
+fn write_agent_brief(
+    out: &mut impl std::fmt::Write,
+    repos: &[Repo],
+    links: &HashMap<PathBuf, RepoLinks>,
+    suppressions: &suppress::Suppressions,
+    cfg: &config::Config,
+    budget: agent::Budget,
+    generated_at: &str,
+) -> std::fmt::Result {
+    writeln!(out, "# AgentPulse Brief")?;
+    writeln!(out)?;
+    writeln!(out, "- Generated: {}", generated_at)?;
+    writeln!(out, "- Repositories scanned: {}", repos.len())?;
+
+    let (dismissed, recommendations): (Vec<_>, Vec<_>) = sorted_recommendations(repos)
+        .into_iter()
+        .partition(|(repo, rec)| suppressions.is_dismissed(repo, rec));
     let critical = recommendations
         .iter()
         .filter(|(_, r)| r.priority == ActionPriority::Critical)
@@ -737,59 +2728,236 @@ fn print_agent_brief(repos: &[Repo]) {
         .filter(|(_, r)| r.priority != ActionPriority::Idle)
         .count();
 
-    println!("- Actionable repos: {}", actionable);
-    println!(
+    writeln!(out, "- Actionable repos: {}", actionable)?;
+    writeln!(
+        out,
         "- Priority mix: {} critical, {} high, {} medium, {} low",
         critical, high, medium, low
-    );
-    println!();
-    println!("## Priority Queue");
-    println!();
-
-    let mut rank = 1usize;
-    for (repo, rec) in recommendations
-        .iter()
-        .filter(|(_, r)| r.priority != ActionPriority::Idle)
-    {
-        println!(
-            "{}. {} (`{}`) [{}]",
-            rank,
+    )?;
+    if !dismissed.is_empty() {
+        writeln!(
+            out,
+            "- Dismissed: {} (hidden until their state changes)",
+            dismissed.len()
+        )?;
+    }
+    let queue = agent::budget_queue(&recommendations, budget);
+    if !budget.is_unbounded() {
+        let limit = budget
+            .limit
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "none".to_string());
+        let minutes = budget
+            .max_minutes
+            .map(|m| format!("{} min", m))
+            .unwrap_or_else(|| "none".to_string());
+        writeln!(
+            out,
+            "- Budget: limit {}, time {} → {} of {} queued (~{} min)",
+            limit,
+            minutes,
+            queue.len(),
+            actionable,
+            queue.iter().map(|(_, r)| r.effort_minutes).sum::<u32>()
+        )?;
+    }
+    writeln!(out)?;
+    writeln!(out, "## Priority Queue")?;
+    writeln!(out)?;
+
+    for (rank, (repo, rec)) in queue.iter().enumerate() {
+        writeln!(
+            out,
+            "{}. {} (`{}`) [{}, ~{} min]",
+            rank + 1,
             repo.name,
             repo.status.branch,
-            rec.priority.label()
-        );
-        println!("   path: `{}`", repo.path.display());
-        println!("   reason: {}", rec.reason);
-        println!("   next: {}", rec.action);
-        println!("   run: `{}`", rec.command);
-        println!();
-        rank += 1;
+            rec.priority.label(),
+            rec.effort_minutes
+        )?;
+        writeln!(out, "   path: `{}`", format::path(cfg, &repo.path))?;
+        if let Some(push) = &repo.status.push {
+            writeln!(
+                out,
+                "   push: `{}` ({} ahead, {} behind; upstream +{} -{})",
+                push.target,
+                repo.status.unpushed_count,
+                push.push_behind,
+                push.upstream_ahead,
+                repo.status.behind_count
+            )?;
+        }
+        let now = Local::now().timestamp();
+        let ages: Vec<String> = [
+            ("uncommitted", repo.status.dirty_since),
+            ("unpushed", repo.status.unpushed_since),
+        ]
+        .into_iter()
+        .filter_map(|(what, since)| {
+            since.map(|since| format!("{} {}", what, format::age(now - since)))
+        })
+        .collect();
+        if !ages.is_empty() {
+            writeln!(out, "   age: {}", ages.join(", "))?;
+        }
+        writeln!(out, "   reason: {}", rec.reason)?;
+        writeln!(out, "   next: {}", rec.action)?;
+        writeln!(out, "   run: `{}`", rec.command)?;
+        if let Some(l) = links.get(&repo.path) {
+            writeln!(
+                out,
+                "   web: {}",
+                l.branch_url.as_deref().unwrap_or(&l.web_url)
+            )?;
+            if let Some(compare) = &l.compare_url {
+                writeln!(out, "   diff: {}", compare)?;
+            }
+        }
+        writeln!(out)?;
     }
 
     if actionable == 0 {
-        println!("All repositories are clean and synced.");
+        writeln!(out, "All repositories are clean and synced.")?;
     }
+    Ok(())
 }
 
-fn print_agent_json(repos: &[Repo]) {
-    let recommendations = sorted_recommendations(repos);
-    let actionable = recommendations
+/// JSON Schema of `--agent-json`, kept by hand next to the printer.
+fn agent_json_schema() -> serde_json::Value {
+    let string = serde_json::json!({"type": "string"});
+    let url = serde_json::json!({"type": ["string", "null"]});
+    let count = serde_json::json!({"type": "integer", "minimum": 0});
+    let boolean = serde_json::json!({"type": "boolean"});
+    let repo = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "name": string,
+            "path": string,
+            "branch": string,
+            "priority": {"enum": ["critical", "high", "medium", "low", "idle"]},
+            "action": string,
+            "short_action": string,
+            "reason": string,
+            "command": string,
+            "uncommitted": count,
+            "staged": count,
+            "unstaged": count,
+            "untracked": count,
+            "conflicted": count,
+            "unpushed": count,
+            "behind": count,
+            "stash": count,
+            "has_remote": boolean,
+            "detached": boolean,
+            "push_target": url,
+            "push_behind": count,
+            "actionable": boolean,
+            "dismissed": boolean,
+            "effort_minutes": count,
+            "web_url": url,
+            "branch_url": url,
+            "compare_url": url,
+            "action_kind": {"anyOf": [{"$ref": "#/$defs/ActionKind"}, {"type": "null"}]},
+        },
+    });
+    // The typed action comes from its derived schema, with its own
+    // definitions hoisted so `#/$defs/...` references still resolve.
+    let mut action_kind =
+        serde_json::to_value(schemars::schema_for!(dashboard::ActionKind)).unwrap_or_default();
+    let mut defs = serde_json::Map::new();
+    if let Some(schema) = action_kind.as_object_mut() {
+        schema.remove("$schema");
+        if let Some(serde_json::Value::Object(nested)) = schema.remove("$defs") {
+            defs.extend(nested);
+        }
+    }
+    defs.insert("ActionKind".to_string(), action_kind);
+    serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "AgentJson",
+        "type": "object",
+        "required": ["tool", "schema_version", "generated_at", "total_repos", "actionable_repos", "repos"],
+        "properties": {
+            "tool": {"const": "agentpulse"},
+            "schema_version": {"const": dashboard::SCHEMA_VERSION},
+            "generated_at": {"type": "string", "format": "date-time"},
+            "total_repos": count,
+            "actionable_repos": count,
+            "budget": {
+                "type": "object",
+                "properties": {
+                    "limit": {"type": ["integer", "null"]},
+                    "max_minutes": {"type": ["integer", "null"]},
+                    "queued": count,
+                    "estimated_minutes": count,
+                },
+            },
+            "repos": {"type": "array", "items": repo},
+        },
+        "$defs": defs,
+    })
+}
+
+fn write_agent_json(
+    out: &mut impl std::fmt::Write,
+    repos: &[Repo],
+    links: &HashMap<PathBuf, RepoLinks>,
+    suppressions: &suppress::Suppressions,
+    budget: agent::Budget,
+    generated_at: &str,
+) -> std::fmt::Result {
+    let all = sorted_recommendations(repos);
+    let is_actionable = |repo: &Repo, rec: &agent::Recommendation| {
+        rec.priority != ActionPriority::Idle && !suppressions.is_dismissed(repo, rec)
+    };
+    let actionable = all
         .iter()
-        .filter(|(_, r)| r.priority != ActionPriority::Idle)
+        .filter(|(repo, r)| is_actionable(repo, r))
         .count();
+    // With a budget, `repos` is the work queue; otherwise every scanned repo.
+    let recommendations = if budget.is_unbounded() {
+        all
+    } else {
+        let active: Vec<_> = all
+            .into_iter()
+            .filter(|(repo, rec)| !suppressions.is_dismissed(repo, rec))
+            .collect();
+        agent::budget_queue(&active, budget)
+    };
 
-    println!("{{");
-    println!("  \"tool\": \"agentpulse\",");
-    println!("  \"generated_at\": {:?},", Local::now().to_rfc3339());
-    println!("  \"total_repos\": {},", repos.len());
-    println!("  \"actionable_repos\": {},", actionable);
-    println!("  \"repos\": [");
+    writeln!(out, "{{")?;
+    writeln!(out, "  \"tool\": \"agentpulse\",")?;
+    writeln!(out, "  \"schema_version\": {},", dashboard::SCHEMA_VERSION)?;
+    writeln!(out, "  \"generated_at\": {:?},", generated_at)?;
+    writeln!(out, "  \"total_repos\": {},", repos.len())?;
+    writeln!(out, "  \"actionable_repos\": {},", actionable)?;
+    if !budget.is_unbounded() {
+        let opt = |v: Option<String>| v.unwrap_or_else(|| "null".to_string());
+        writeln!(
+            out,
+            "  \"budget\": {{\"limit\":{},\"max_minutes\":{},\"queued\":{},\"estimated_minutes\":{}}},",
+            opt(budget.limit.map(|n| n.to_string())),
+            opt(budget.max_minutes.map(|m| m.to_string())),
+            recommendations.len(),
+            recommendations
+                .iter()
+                .map(|(_, r)| r.effort_minutes)
+                .sum::<u32>()
+        )?;
+    }
+    writeln!(out, "  \"repos\": [")?;
 
     let last = recommendations.len().saturating_sub(1);
     for (i, (repo, rec)) in recommendations.iter().enumerate() {
         let comma = if i < last { "," } else { "" };
-        println!(
-            "    {{\"name\":{:?},\"path\":{:?},\"branch\":{:?},\"priority\":{:?},\"action\":{:?},\"short_action\":{:?},\"reason\":{:?},\"command\":{:?},\"uncommitted\":{},\"unpushed\":{},\"behind\":{},\"stash\":{},\"has_remote\":{},\"detached\":{},\"actionable\":{}}}{}",
+        let repo_links = links.get(&repo.path);
+        let json_or_null = |url: Option<&String>| {
+            url.map(|u| format!("{:?}", u))
+                .unwrap_or_else(|| "null".to_string())
+        };
+        writeln!(
+            out,
+            "    {{\"name\":{:?},\"path\":{:?},\"branch\":{:?},\"priority\":{:?},\"action\":{:?},\"short_action\":{:?},\"reason\":{:?},\"command\":{:?},\"uncommitted\":{},\"staged\":{},\"unstaged\":{},\"untracked\":{},\"conflicted\":{},\"unpushed\":{},\"behind\":{},\"stash\":{},\"has_remote\":{},\"detached\":{},\"push_target\":{},\"push_behind\":{},\"actionable\":{},\"dismissed\":{},\"effort_minutes\":{},\"web_url\":{},\"branch_url\":{},\"compare_url\":{},\"action_kind\":{}}}{}",
             repo.name,
             repo.path.to_string_lossy(),
             repo.status.branch,
@@ -799,16 +2967,222 @@ fn print_agent_json(repos: &[Repo]) {
             rec.reason,
             rec.command,
             repo.status.uncommitted_count,
+            repo.status.changes.staged,
+            repo.status.changes.unstaged,
+            repo.status.changes.untracked,
+            repo.status.changes.conflicted,
             repo.status.unpushed_count,
             repo.status.behind_count,
             repo.status.stash_count,
             repo.status.has_remote,
             repo.status.is_detached,
-            rec.priority != ActionPriority::Idle,
+            json_or_null(repo.status.push.as_ref().map(|p| &p.target)),
+            repo.status.push.as_ref().map_or(0, |p| p.push_behind),
+            is_actionable(repo, rec),
+            suppressions.is_dismissed(repo, rec),
+            rec.effort_minutes,
+            json_or_null(repo_links.map(|l| &l.web_url)),
+            json_or_null(repo_links.and_then(|l| l.branch_url.as_ref())),
+            json_or_null(repo_links.and_then(|l| l.compare_url.as_ref())),
+            agent::recommended_action_kind(repo)
+                .and_then(|kind| serde_json::to_string(&kind).ok())
+                .unwrap_or_else(|| "null".to_string()),
             comma
+        )?;
+    }
+
+    writeln!(out, "  ]")?;
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::RepoStatus;
+
+    const GENERATED_AT: &str = "2026-01-02T03:04:05+00:00";
+
+    fn fixture_repos() -> Vec<Repo> {
+        let repo = |name: &str, status: RepoStatus| {
+            let mut repo = Repo::new(PathBuf::from(format!("/work/{}", name)));
+            repo.status = status;
+            repo
+        };
+        vec![
+            repo(
+                "api",
+                RepoStatus {
+                    branch: "main".to_string(),
+                    uncommitted_count: 3,
+                    behind_count: 2,
+                    has_remote: true,
+                    ..RepoStatus::default()
+                },
+            ),
+            repo(
+                "web",
+                RepoStatus {
+                    branch: "feature/login".to_string(),
+                    unpushed_count: 4,
+                    has_remote: true,
+                    push: Some(git::PushDivergence {
+                        target: "fork/feature/login".to_string(),
+                        upstream_ahead: 6,
+                        push_behind: 1,
+                    }),
+                    ..RepoStatus::default()
+                },
+            ),
+            repo(
+                "notes",
+                RepoStatus {
+                    branch: "main".to_string(),
+                    stash_count: 1,
+                    has_remote: true,
+                    ..RepoStatus::default()
+                },
+            ),
+            repo(
+                "docs",
+                RepoStatus {
+                    branch: "main".to_string(),
+                    has_remote: true,
+                    ..RepoStatus::default()
+                },
+            ),
+        ]
+    }
+
+    fn fixture_links() -> HashMap<PathBuf, RepoLinks> {
+        HashMap::from([(
+            PathBuf::from("/work/web"),
+            RepoLinks {
+                web_url: "https://github.com/acme/web".to_string(),
+                branch_url: Some("https://github.com/acme/web/tree/feature/login".to_string()),
+                compare_url: Some(
+                    "https://github.com/acme/web/compare/feature/login...fork:feature/login"
+                        .to_string(),
+                ),
+            },
+        )])
+    }
+
+    #[test]
+    fn agent_brief_output() {
+        let mut out = String::new();
+        write_agent_brief(
+            &mut out,
+            &fixture_repos(),
+            &fixture_links(),
+            &suppress::Suppressions::default(),
+            &config::Config::default(),
+            agent::Budget::default(),
+            GENERATED_AT,
+        )
+        .unwrap();
+        insta::assert_snapshot!(out);
+    }
+
+    #[test]
+    fn agent_brief_output_with_budget() {
+        let mut out = String::new();
+        write_agent_brief(
+            &mut out,
+            &fixture_repos(),
+            &HashMap::new(),
+            &suppress::Suppressions::default(),
+            &config::Config::default(),
+            agent::Budget {
+                limit: Some(2),
+                max_minutes: None,
+            },
+            GENERATED_AT,
+        )
+        .unwrap();
+        insta::assert_snapshot!(out);
+    }
+
+    #[test]
+    fn agent_json_output() {
+        let mut out = String::new();
+        write_agent_json(
+            &mut out,
+            &fixture_repos(),
+            &fixture_links(),
+            &suppress::Suppressions::default(),
+            agent::Budget::default(),
+            GENERATED_AT,
+        )
+        .unwrap();
+        // The hand-written printer must stay valid JSON.
+        let json = serde_json::from_str::<serde_json::Value>(&out).unwrap();
+        insta::assert_snapshot!(out);
+
+        // Every key is in the schema, and typed actions round-trip into the
+        // executor's `ActionKind`.
+        let schema = agent_json_schema();
+        let properties = &schema["properties"]["repos"]["items"]["properties"];
+        for repo in json["repos"].as_array().unwrap() {
+            for key in repo.as_object().unwrap().keys() {
+                assert!(properties.get(key).is_some(), "{} missing from schema", key);
+            }
+            if !repo["action_kind"].is_null() {
+                serde_json::from_value::<dashboard::ActionKind>(repo["action_kind"].clone())
+                    .unwrap();
+            }
+        }
+        assert!(json["repos"][0]["action_kind"]["type"].is_string());
+        assert!(schema["$defs"]["ActionKind"].is_object());
+    }
+
+    #[test]
+    fn week_report_output() {
+        let monday = chrono::NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let stats = |repo: &str, commits, insertions, deletions| dashboard::RepoWeekStats {
+            repo: repo.to_string(),
+            commits,
+            insertions,
+            deletions,
+        };
+        let display = config::DisplayConfig::default();
+        assert_eq!(
+            week_report(&[], monday, &display),
+            "This week (since 2026-10-12): no commits yet.\n"
         );
+        insta::assert_snapshot!(week_report(
+            &[stats("web", 9, 1412, 138), stats("payments-api", 1, 7, 0)],
+            monday,
+            &display
+        ));
     }
 
-    println!("  ]");
-    println!("}}");
+    #[test]
+    fn watch_documents_fit_on_one_line() {
+        let mut out = String::new();
+        write_agent_json(
+            &mut out,
+            &fixture_repos(),
+            &fixture_links(),
+            &suppress::Suppressions::default(),
+            agent::Budget::default(),
+            GENERATED_AT,
+        )
+        .unwrap();
+        let line = one_line(&out);
+        assert!(!line.contains('\n') && line.starts_with("{\"tool\": \"agentpulse\","));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&line).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&out).unwrap()
+        );
+
+        // `--watch --json` streams the `--json` array the same way.
+        let mut out = String::new();
+        write_json(&mut out, &fixture_repos()).unwrap();
+        let line = one_line(&out);
+        let repos = serde_json::from_str::<serde_json::Value>(&line).unwrap();
+        assert!(!line.contains('\n'));
+        assert_eq!(repos[0]["name"], "api");
+        assert_eq!(repos.as_array().unwrap().len(), fixture_repos().len());
+    }
 }
@@ -1,14 +1,21 @@
 use crate::config::Config;
-use crate::git::{check_repo_status, Repo, RepoStatus};
+use crate::dashboard::{RepoTiming, ScanStats};
+use crate::git::{
+    check_repo_status, configure_process_limits, get_repo_identity, git_spawn_count, Repo,
+    RepoStatus,
+};
 use crate::scanner::find_repos;
 use chrono::Local;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant, SystemTime};
 use tokio::task::JoinSet;
 
-const MAX_CONCURRENT: usize = 20;
+/// Repos checked per batch. Git child processes are capped separately via
+/// `[processes] max_git_processes`.
+pub(crate) const MAX_CONCURRENT: usize = 20;
 
 /// Cached entry: the mtime of `.git/index` at last check plus the result.
 #[derive(Clone)]
@@ -16,6 +23,7 @@ pub struct CacheEntry {
     signals: CacheSignals,
     checked_at: Instant,
     status: RepoStatus,
+    identity: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -31,91 +39,280 @@ struct CacheSignals {
 /// when the `.git/index` file hasn't changed.
 pub type StatusCache = HashMap<PathBuf, CacheEntry>;
 
+/// Quiet repos re-checked per pass once their sweep age has passed; the rest
+/// wait for the next pass so a large clean workspace never lands at once.
+const SWEEP_PER_PASS: usize = MAX_CONCURRENT;
+
+/// Slowest status checks kept in [`ScanStats::slowest`].
+const SLOWEST_KEPT: usize = 5;
+
+/// Repos whose git dir and working tree are both under a filesystem watch.
+fn watched_worktrees() -> &'static Mutex<HashSet<PathBuf>> {
+    static WATCHED: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
+    WATCHED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Mark repos whose every change arrives as a filesystem event. Their cached
+/// status is trusted until the watcher invalidates it (or the sweep age
+/// passes) instead of being re-probed on the interval.
+pub fn set_watched_worktrees(repos: impl IntoIterator<Item = PathBuf>) {
+    if let Ok(mut watched) = watched_worktrees().lock() {
+        *watched = repos.into_iter().collect();
+    }
+}
+
+fn is_watched_worktree(path: &Path) -> bool {
+    watched_worktrees()
+        .lock()
+        .map(|w| w.contains(path))
+        .unwrap_or(false)
+}
+
+/// Repos under `watch_directories`, minus `ignored_repos` (by directory name).
+pub fn watched_repo_paths(config: &Config) -> Vec<PathBuf> {
+    partition_ignored(config).0
+}
+
+/// Discovered repos split into watched paths and the names `ignored_repos`
+/// filtered out.
+fn partition_ignored(config: &Config) -> (Vec<PathBuf>, Vec<String>) {
+    let mut ignored = Vec::new();
+    let watched = find_repos(&config.watch_directories, config.max_scan_depth)
+        .into_iter()
+        .filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let skip = config.ignored_repos.iter().any(|ig| ig == name);
+            if skip {
+                ignored.push(name.to_string());
+            }
+            !skip
+        })
+        .collect();
+    (watched, ignored)
+}
+
 /// Scan all configured directories, check each repo's git status concurrently,
 /// and return a sorted list with dirty repos first.
 ///
 /// `cache` is updated in-place: entries whose `.git/index` mtime is unchanged
 /// are reused without spawning new git processes.
 pub async fn scan_all(config: &Config, cache: &mut StatusCache) -> Vec<Repo> {
-    let paths = find_repos(&config.watch_directories, config.max_scan_depth);
+    scan_all_with_stats(config, cache).await.0
+}
 
-    // Filter ignored repos by directory name
-    let paths: Vec<PathBuf> = paths
-        .into_iter()
-        .filter(|p| {
-            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
-            !config.ignored_repos.iter().any(|ig| ig == name)
-        })
-        .collect();
+/// Apply the config the status checks and collectors read from globals.
+pub fn configure(config: &Config) {
+    configure_process_limits(&config.processes);
+    crate::collectors::configure_providers(&config.providers);
+    crate::collectors::configure_licenses(&config.licenses);
+    crate::collectors::configure_connectivity(&config.connectivity);
+    crate::collectors::configure_tickets(&config.tickets);
+    crate::collectors::configure_forge(&config.forge);
+    crate::collectors::configure_backup(&config.backup);
+    crate::collectors::configure_fsck(&config.fsck, &config.display);
+    crate::git::configure_dirty_ignore(&config.dirty_ignore);
+    crate::credentials::configure(&config.credentials);
+    crate::agent::configure_thresholds(&config.thresholds);
+    crate::agent::configure_rules(config);
+}
+
+/// [`scan_all`], also reporting discovery/status timings and cache counters.
+/// `collect_ms` is left for the caller, which runs the collectors.
+pub async fn scan_all_with_stats(
+    config: &Config,
+    cache: &mut StatusCache,
+) -> (Vec<Repo>, ScanStats) {
+    configure(config);
+    let spawns_before = git_spawn_count();
+    let started = Instant::now();
+    let (paths, ignored) = partition_ignored(config);
+    let discover_ms = elapsed_ms(started);
 
-    // Split into cache-hit repos (no git needed) and repos that need checking
+    // Split into cache-hit repos (no git needed) and repos that need checking.
+    // Quiet repos past their sweep age go last, oldest first, a batch per pass.
+    let max_age = stale_after(config.refresh_interval_secs);
+    let sweep_age = Duration::from_secs(config.clean_sweep_secs).max(max_age);
     let mut repos: Vec<Repo> = Vec::with_capacity(paths.len());
     let mut to_check: Vec<PathBuf> = Vec::new();
+    let mut sweep_due: Vec<(Instant, &PathBuf)> = Vec::new();
+    let cached_repo = |path: &PathBuf, status: RepoStatus| {
+        let mut repo = Repo::new(path.clone());
+        repo.status = status;
+        repo.last_checked = Some(Local::now());
+        repo.identity = cache.get(path).and_then(|e| e.identity.clone());
+        repo
+    };
 
     for path in &paths {
-        if let Some(cached) = cache_hit(path, cache, stale_after(config.refresh_interval_secs)) {
-            let mut repo = Repo::new(path.clone());
-            repo.status = cached;
-            repo.last_checked = Some(Local::now());
-            repos.push(repo);
-        } else {
-            to_check.push(path.clone());
+        match freshness(path, cache, max_age, sweep_age) {
+            Freshness::Fresh(status) => repos.push(cached_repo(path, status)),
+            Freshness::SweepDue(checked_at) => sweep_due.push((checked_at, path)),
+            Freshness::Check => to_check.push(path.clone()),
         }
     }
+    sweep_due.sort();
+    let deferred = sweep_due.len().saturating_sub(SWEEP_PER_PASS);
+    for (idx, (_, path)) in sweep_due.into_iter().enumerate() {
+        match cache.get(path) {
+            Some(entry) if idx >= SWEEP_PER_PASS => {
+                repos.push(cached_repo(path, entry.status.clone()))
+            }
+            _ => to_check.push(path.clone()),
+        }
+    }
+
+    let cache_hits = repos.len();
+    let started = Instant::now();
+    let mut timings: Vec<RepoTiming> = Vec::with_capacity(to_check.len());
 
     // Check remaining repos in bounded concurrent batches
     for chunk in to_check.chunks(MAX_CONCURRENT) {
-        let mut set: JoinSet<(PathBuf, Repo)> = JoinSet::new();
+        let mut set: JoinSet<(PathBuf, Repo, u64)> = JoinSet::new();
         for path in chunk {
             let path = path.clone();
+            let known_identity = cache.get(&path).and_then(|e| e.identity.clone());
             set.spawn(async move {
-                let mut repo = Repo::new(path.clone());
-                if let Ok(status) = check_repo_status(&path).await {
-                    repo.status = status;
-                    repo.last_checked = Some(Local::now());
-                }
-                (path, repo)
+                let checked = Instant::now();
+                let repo = check_repo(&path, known_identity).await;
+                (path, repo, elapsed_ms(checked))
             });
         }
         while let Some(res) = set.join_next().await {
-            if let Ok((path, repo)) = res {
-                // Update cache with new repo state signals.
-                if let Some(signals) = read_cache_signals(&path) {
-                    cache.insert(
-                        path,
-                        CacheEntry {
-                            signals,
-                            checked_at: Instant::now(),
-                            status: repo.status.clone(),
-                        },
-                    );
-                }
+            if let Ok((path, repo, ms)) = res {
+                timings.push(RepoTiming {
+                    repo: repo.name.clone(),
+                    path: path.to_string_lossy().to_string(),
+                    ms,
+                });
+                remember(cache, &repo);
                 repos.push(repo);
             }
         }
     }
 
-    // Sort: highest urgency first, then alphabetical by name
+    let status_ms = elapsed_ms(started);
+
+    // Forget repos that were deleted or are no longer watched so the cache
+    // doesn't grow for the lifetime of the process.
+    let live: HashSet<&PathBuf> = paths.iter().collect();
+    cache.retain(|path, _| live.contains(path));
+
+    sort_by_urgency(&mut repos);
+
+    timings.sort_by(|a, b| b.ms.cmp(&a.ms).then_with(|| a.repo.cmp(&b.repo)));
+    timings.truncate(SLOWEST_KEPT);
+    let stats = ScanStats {
+        discover_ms,
+        status_ms,
+        collect_ms: 0,
+        repos_found: paths.len(),
+        cache_hits,
+        cache_misses: to_check.len(),
+        deferred,
+        ignored,
+        git_spawns: git_spawn_count() - spawns_before,
+        slowest: timings,
+    };
+    (repos, stats)
+}
+
+/// Highest urgency first, then alphabetical by name.
+pub(crate) fn sort_by_urgency(repos: &mut [Repo]) {
     repos.sort_by(|a, b| {
         b.urgency()
             .cmp(&a.urgency())
             .then_with(|| a.name.cmp(&b.name))
     });
+}
 
-    repos
+/// Re-check one repo, e.g. after an action touched it, and refresh its cache
+/// entry; the rest of the workspace is left alone. Uses the limits set up by
+/// the last [`scan_all`].
+pub async fn rescan_repo(path: &Path, cache: &mut StatusCache) -> Repo {
+    let known_identity = cache.get(path).and_then(|e| e.identity.clone());
+    let repo = check_repo(path, known_identity).await;
+    remember(cache, &repo);
+    repo
 }
 
-/// Return the cached `RepoStatus` if `.git/index` hasn't changed, otherwise `None`.
-fn cache_hit(path: &Path, cache: &StatusCache, max_age: Duration) -> Option<RepoStatus> {
-    let signals = read_cache_signals(path)?;
-    let entry = cache.get(path)?;
-    if entry.checked_at.elapsed() <= max_age && entry.signals == signals {
-        Some(entry.status.clone())
+pub(crate) async fn check_repo(path: &Path, known_identity: Option<String>) -> Repo {
+    let mut repo = Repo::new(path.to_path_buf());
+    if let Ok(status) = check_repo_status(path).await {
+        repo.status = status;
+        repo.last_checked = Some(Local::now());
+    }
+    // Identity never changes for a given checkout, so resolve it once per path.
+    repo.identity = match known_identity {
+        Some(id) => Some(id),
+        None => get_repo_identity(path).await,
+    };
+    repo
+}
+
+/// Cache `repo`'s status under its current git metadata signals.
+fn remember(cache: &mut StatusCache, repo: &Repo) {
+    if let Some(signals) = read_cache_signals(&repo.path) {
+        cache.insert(
+            repo.path.clone(),
+            CacheEntry {
+                signals,
+                checked_at: Instant::now(),
+                status: repo.status.clone(),
+                identity: repo.identity.clone(),
+            },
+        );
+    }
+}
+
+fn elapsed_ms(since: Instant) -> u64 {
+    since.elapsed().as_millis() as u64
+}
+
+/// Whether a repo's cached status can stand in for a git check this pass.
+#[derive(Debug)]
+enum Freshness {
+    Fresh(RepoStatus),
+    /// Quiet repo past the sweep age; carries when it was last checked.
+    SweepDue(Instant),
+    /// Git metadata changed, nothing cached, or an actionable repo gone stale.
+    Check,
+}
+
+/// Unchanged git metadata keeps a cached status for `max_age`, or for
+/// `sweep_age` when the repo is quiet (nothing to commit, push, or pull) or
+/// its working tree is watched.
+fn freshness(
+    path: &Path,
+    cache: &StatusCache,
+    max_age: Duration,
+    sweep_age: Duration,
+) -> Freshness {
+    let (Some(signals), Some(entry)) = (read_cache_signals(path), cache.get(path)) else {
+        return Freshness::Check;
+    };
+    if entry.signals != signals {
+        return Freshness::Check;
+    }
+    let age = entry.checked_at.elapsed();
+    let settled = is_quiet(&entry.status) || is_watched_worktree(path);
+    if age <= max_age || (settled && age <= sweep_age) {
+        Freshness::Fresh(entry.status.clone())
+    } else if is_quiet(&entry.status) {
+        Freshness::SweepDue(entry.checked_at)
     } else {
-        None
+        Freshness::Check
     }
 }
 
+fn is_quiet(status: &RepoStatus) -> bool {
+    status.uncommitted_count == 0
+        && status.unpushed_count == 0
+        && status.behind_count == 0
+        && !status.is_detached
+        && !status.missing_upstream
+        && status.probe_errors.is_empty()
+}
+
 fn stale_after(refresh_interval_secs: u64) -> Duration {
     // Keep remote-derived values fresh even if local mtimes don't change.
     let secs = refresh_interval_secs.saturating_mul(2).clamp(6, 30);
@@ -137,7 +334,9 @@ fn file_mtime(path: PathBuf) -> Option<SystemTime> {
     fs::metadata(path).ok().and_then(|m| m.modified().ok())
 }
 
-fn resolve_git_dir(repo: &Path) -> Option<PathBuf> {
+/// The repo's git directory, following `gitdir:` files of worktrees and
+/// submodules.
+pub fn resolve_git_dir(repo: &Path) -> Option<PathBuf> {
     let dot_git = repo.join(".git");
     if dot_git.is_dir() {
         return Some(dot_git);
@@ -222,19 +421,115 @@ mod tests {
     }
 
     #[test]
-    fn cache_hit_invalidates_on_age() {
+    fn quiet_repos_keep_their_cache_until_the_sweep() {
         let repo = init_repo("age");
         let signals = read_cache_signals(&repo).unwrap();
+        let entry = |status: RepoStatus| CacheEntry {
+            signals: signals.clone(),
+            checked_at: Instant::now() - Duration::from_secs(60),
+            status,
+            identity: None,
+        };
         let mut cache = StatusCache::new();
-        cache.insert(
-            repo.clone(),
-            CacheEntry {
-                signals,
-                checked_at: Instant::now() - Duration::from_secs(60),
-                status: RepoStatus::default(),
-            },
+        let (max_age, sweep_age) = (Duration::from_secs(5), Duration::from_secs(120));
+
+        cache.insert(repo.clone(), entry(RepoStatus::default()));
+        assert!(matches!(
+            freshness(&repo, &cache, max_age, sweep_age),
+            Freshness::Fresh(_)
+        ));
+        assert!(matches!(
+            freshness(&repo, &cache, max_age, Duration::from_secs(30)),
+            Freshness::SweepDue(_)
+        ));
+
+        let dirty = RepoStatus {
+            uncommitted_count: 1,
+            ..RepoStatus::default()
+        };
+        cache.insert(repo.clone(), entry(dirty));
+        assert!(matches!(
+            freshness(&repo, &cache, max_age, sweep_age),
+            Freshness::Check
+        ));
+
+        // A watched working tree reports its own edits, so no re-probe.
+        set_watched_worktrees([repo.clone()]);
+        assert!(matches!(
+            freshness(&repo, &cache, max_age, sweep_age),
+            Freshness::Fresh(_)
+        ));
+        set_watched_worktrees([]);
+        let _ = fs::remove_dir_all(&repo);
+    }
+
+    #[tokio::test]
+    async fn scan_drops_cache_entries_for_deleted_repos() {
+        let repo = init_repo("gc/repo");
+        let watch = repo.parent().unwrap().to_path_buf();
+        let config = Config {
+            watch_directories: vec![watch.clone()],
+            max_scan_depth: 2,
+            ..Config::default()
+        };
+        let mut cache = StatusCache::new();
+        scan_all(&config, &mut cache).await;
+        assert!(cache.contains_key(&repo));
+
+        fs::remove_dir_all(&repo).unwrap();
+        scan_all(&config, &mut cache).await;
+        assert!(cache.is_empty());
+        let _ = fs::remove_dir_all(&watch);
+    }
+
+    #[tokio::test]
+    async fn rescan_repo_refreshes_one_cache_entry() {
+        let repo = init_repo("rescan");
+        let mut cache = StatusCache::new();
+        assert_eq!(
+            rescan_repo(&repo, &mut cache)
+                .await
+                .status
+                .uncommitted_count,
+            0
         );
-        assert!(cache_hit(&repo, &cache, Duration::from_secs(5)).is_none());
+
+        fs::write(repo.join("new.txt"), "x").unwrap();
+        // The cached status is stale: creating a file touches no git metadata.
+        assert!(matches!(
+            freshness(&repo, &cache, Duration::from_secs(60), Duration::from_secs(60)),
+            Freshness::Fresh(ref status) if status.uncommitted_count == 0
+        ));
+        let rescanned = rescan_repo(&repo, &mut cache).await;
+        assert_eq!(rescanned.status.uncommitted_count, 1);
+        assert_eq!(cache[&repo].status.uncommitted_count, 1);
+        assert_eq!(cache.len(), 1);
         let _ = fs::remove_dir_all(&repo);
     }
+
+    #[tokio::test]
+    async fn scan_stats_count_cache_hits_and_ignored_repos() {
+        let repo = init_repo("stats/kept");
+        let skipped = init_repo("stats/skipped");
+        let watch = repo.parent().unwrap().to_path_buf();
+        let config = Config {
+            watch_directories: vec![watch.clone()],
+            max_scan_depth: 2,
+            ignored_repos: vec!["skipped".to_string()],
+            ..Config::default()
+        };
+        let mut cache = StatusCache::new();
+        let (repos, first) = scan_all_with_stats(&config, &mut cache).await;
+        assert_eq!(repos.len(), 1);
+        assert_eq!((first.cache_hits, first.cache_misses), (0, 1));
+        assert_eq!(first.ignored, ["skipped"]);
+        assert_eq!(first.slowest.len(), 1);
+        assert!(first.git_spawns > 0);
+
+        let (_, second) = scan_all_with_stats(&config, &mut cache).await;
+        assert_eq!((second.cache_hits, second.cache_misses), (1, 0));
+        assert!(second.slowest.is_empty());
+        let _ = fs::remove_dir_all(&skipped);
+        let _ = fs::remove_dir_all(&watch);
+    }
 }
@@ -0,0 +1,256 @@
+//! `--plain`: line-oriented output for screen readers and low-vision setups.
+//!
+//! No box drawing, glyphs, color, spinners or columns: each repo and alert
+//! is one sentence. The report is reprinted on every refresh only when its
+//! text changed, so a screen reader is not made to re-read an identical
+//! screen, and commands are typed a line at a time.
+
+use crate::agent::{self, ActionPriority};
+use crate::config::Config;
+use crate::dashboard::{self, DashboardSnapshot};
+use crate::format;
+use crate::git::Repo;
+use crate::history;
+use crate::i18n::{t, tr};
+use crate::monitor::{self, StatusCache};
+use anyhow::Result;
+use chrono::Local;
+use std::io::BufRead;
+use std::time::Duration;
+
+/// Scan, print the report, and keep refreshing until `q` or end of input.
+/// With `once`, print a single report and return whether any repo needs
+/// attention.
+pub async fn run(cfg: &Config, once: bool) -> Result<bool> {
+    let mut cache = StatusCache::new();
+    if once {
+        let mut repos = monitor::scan_all(cfg, &mut cache).await;
+        history::annotate_work_ages(&mut repos);
+        let snapshot = dashboard::collect_and_build(&repos);
+        print!("{}", report(&repos, &snapshot, false));
+        return Ok(repos.iter().any(agent::needs_attention));
+    }
+
+    println!("{}", t("plain-intro"));
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let interval = Duration::from_secs(cfg.refresh_interval_secs.max(1));
+    let mut input_open = true;
+    let mut show_all = false;
+    let mut forced = false;
+    let mut last = String::new();
+    loop {
+        let mut repos = monitor::scan_all(cfg, &mut cache).await;
+        history::annotate_work_ages(&mut repos);
+        let snapshot = dashboard::collect_and_build(&repos);
+        let body = report(&repos, &snapshot, show_all);
+        if body != last {
+            let time = format::clock(&cfg.display, &Local::now());
+            println!();
+            println!("{}", tr("plain-updated", &[("time", &time)]));
+            print!("{}", body);
+            last = body;
+        } else if forced {
+            println!("{}", t("plain-unchanged"));
+        }
+
+        forced = false;
+        let deadline = tokio::time::Instant::now() + interval;
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep_until(deadline) => break,
+                line = rx.recv(), if input_open => match line.as_deref().map(str::trim) {
+                    None => input_open = false,
+                    Some("q" | "quit") => return Ok(false),
+                    Some("r" | "refresh") => {
+                        forced = true;
+                        break;
+                    }
+                    Some("a" | "all") => {
+                        show_all = !show_all;
+                        forced = true;
+                        break;
+                    }
+                    Some("") => {}
+                    Some(_) => println!("{}", t("plain-commands")),
+                },
+            }
+        }
+    }
+}
+
+/// The report as plain sentences, one per line. Clean repos are only
+/// counted unless `show_all` is set.
+pub fn report(repos: &[Repo], snapshot: &DashboardSnapshot, show_all: bool) -> String {
+    if repos.is_empty() {
+        return format!("{}\n", t("plain-no-repos"));
+    }
+    let recommendations: Vec<_> = repos.iter().map(agent::recommend).collect();
+    let attention = recommendations
+        .iter()
+        .filter(|rec| rec.priority != ActionPriority::Idle)
+        .count();
+
+    let mut lines = vec![tr(
+        "plain-summary",
+        &[
+            ("repos", &repos.len()),
+            ("attention", &attention),
+            ("alerts", &snapshot.alerts.len()),
+        ],
+    )];
+    for (repo, rec) in repos.iter().zip(&recommendations) {
+        let idle = rec.priority == ActionPriority::Idle;
+        if idle && !show_all {
+            continue;
+        }
+        let state = repo_state(repo);
+        let args: [(&str, &dyn std::fmt::Display); 4] = [
+            ("name", &repo.name),
+            ("branch", &repo.status.branch),
+            ("state", &state),
+            ("action", &rec.action),
+        ];
+        lines.push(if idle {
+            tr("plain-repo", &args)
+        } else {
+            tr("plain-repo-next", &args)
+        });
+    }
+    if !show_all && attention < repos.len() {
+        lines.push(tr(
+            "plain-clean-count",
+            &[("count", &(repos.len() - attention))],
+        ));
+    }
+    for alert in &snapshot.alerts {
+        lines.push(
+            tr(
+                "plain-alert",
+                &[
+                    ("severity", &severity(&alert.severity)),
+                    ("title", &alert.title),
+                    ("detail", &alert.detail),
+                ],
+            )
+            .trim_end()
+            .to_string(),
+        );
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// "2 uncommitted files, 1 commit not pushed", or "clean".
+fn repo_state(repo: &Repo) -> String {
+    let status = &repo.status;
+    let mut parts = Vec::new();
+    if status.is_detached {
+        parts.push(t("plain-detached"));
+    }
+    if !status.has_remote {
+        parts.push(t("plain-no-remote"));
+    }
+    let counted = [
+        (
+            status.uncommitted_count,
+            "plain-dirty-one",
+            "plain-dirty-many",
+        ),
+        (status.unpushed_count, "plain-ahead-one", "plain-ahead-many"),
+        (status.behind_count, "plain-behind-one", "plain-behind-many"),
+        (status.stash_count, "plain-stash-one", "plain-stash-many"),
+    ];
+    for (count, one, many) in counted {
+        match count {
+            0 => {}
+            1 => parts.push(t(one)),
+            _ => parts.push(tr(many, &[("count", &count)])),
+        }
+    }
+    if parts.is_empty() {
+        t("plain-clean")
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn severity(raw: &str) -> String {
+    match raw {
+        "critical" => t("plain-severity-critical"),
+        "high" => t("plain-severity-high"),
+        "warn" => t("plain-severity-warn"),
+        "info" => t("plain-severity-info"),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dashboard::DashboardAlert;
+    use crate::git::RepoStatus;
+    use std::path::PathBuf;
+
+    #[test]
+    fn reports_repos_and_alerts_as_plain_sentences() {
+        let repo = |name: &str, status: RepoStatus| {
+            let mut repo = Repo::new(PathBuf::from(format!("/work/{}", name)));
+            repo.status = status;
+            repo
+        };
+        let repos = vec![
+            repo(
+                "api",
+                RepoStatus {
+                    branch: "main".to_string(),
+                    uncommitted_count: 1,
+                    unpushed_count: 2,
+                    has_remote: true,
+                    ..RepoStatus::default()
+                },
+            ),
+            repo(
+                "docs",
+                RepoStatus {
+                    branch: "main".to_string(),
+                    has_remote: true,
+                    ..RepoStatus::default()
+                },
+            ),
+        ];
+        let snapshot = DashboardSnapshot {
+            alerts: vec![DashboardAlert {
+                severity: "warn".to_string(),
+                title: "api has local changes".to_string(),
+                detail: String::new(),
+                repo: Some("api".to_string()),
+                action: None,
+            }],
+            ..DashboardSnapshot::default()
+        };
+
+        let text = report(&repos, &snapshot, false);
+        let action = agent::recommend(&repos[0]).action;
+        assert_eq!(
+            text,
+            format!(
+                "Repos: 2. Need attention: 1. Alerts: 1.\n\
+                 api on main: 1 uncommitted file, 2 commits not pushed. Next: {action}.\n\
+                 Clean repos not listed: 1.\n\
+                 Warning: api has local changes.\n"
+            )
+        );
+        assert!(text.is_ascii());
+
+        let all = report(&repos, &snapshot, true);
+        assert!(all.contains("docs on main: clean.\n"));
+        assert!(!all.contains("not listed"));
+    }
+}
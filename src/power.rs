@@ -0,0 +1,149 @@
+//! AC/battery detection used to throttle scanning on laptops.
+//!
+//! Linux reads `/sys/class/power_supply`; macOS asks `pmset`. Anything else
+//! (or any read failure) reports `Unknown`, which never triggers power-save.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerSource {
+    Ac,
+    Battery,
+    Unknown,
+}
+
+/// How long a reading is trusted before probing again.
+const RECHECK_AFTER: Duration = Duration::from_secs(30);
+
+/// Caches the last power reading so the event loop can ask every tick.
+pub struct PowerMonitor {
+    last: Option<(Instant, PowerSource)>,
+}
+
+impl PowerMonitor {
+    pub fn new() -> Self {
+        Self { last: None }
+    }
+
+    pub fn source(&mut self) -> PowerSource {
+        if let Some((at, source)) = self.last {
+            if at.elapsed() < RECHECK_AFTER {
+                return source;
+            }
+        }
+        let source = detect();
+        self.last = Some((Instant::now(), source));
+        source
+    }
+}
+
+pub fn detect() -> PowerSource {
+    if cfg!(target_os = "linux") {
+        detect_sysfs(Path::new("/sys/class/power_supply"))
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| parse_pmset(&String::from_utf8_lossy(&o.stdout)))
+            .unwrap_or(PowerSource::Unknown)
+    } else {
+        PowerSource::Unknown
+    }
+}
+
+/// Any online `Mains` supply means AC; otherwise a discharging battery means battery.
+fn detect_sysfs(root: &Path) -> PowerSource {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return PowerSource::Unknown;
+    };
+    let read = |dir: &Path, file: &str| {
+        std::fs::read_to_string(dir.join(file))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let mut discharging = false;
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        match read(&dir, "type").as_str() {
+            "Mains" | "USB" if read(&dir, "online") == "1" => return PowerSource::Ac,
+            "Battery" if read(&dir, "status") == "Discharging" => discharging = true,
+            _ => {}
+        }
+    }
+    if discharging {
+        PowerSource::Battery
+    } else {
+        PowerSource::Unknown
+    }
+}
+
+fn parse_pmset(raw: &str) -> PowerSource {
+    if raw.contains("'Battery Power'") {
+        PowerSource::Battery
+    } else if raw.contains("'AC Power'") {
+        PowerSource::Ac
+    } else {
+        PowerSource::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supply(root: &Path, name: &str, files: &[(&str, &str)]) {
+        let dir = root.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, value) in files {
+            std::fs::write(dir.join(file), format!("{}\n", value)).unwrap();
+        }
+    }
+
+    #[test]
+    fn sysfs_battery_discharging() {
+        let root = std::env::temp_dir().join("agentpulse_test_power_battery");
+        let _ = std::fs::remove_dir_all(&root);
+        supply(&root, "AC", &[("type", "Mains"), ("online", "0")]);
+        supply(
+            &root,
+            "BAT0",
+            &[("type", "Battery"), ("status", "Discharging")],
+        );
+        assert_eq!(detect_sysfs(&root), PowerSource::Battery);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn sysfs_mains_online_wins() {
+        let root = std::env::temp_dir().join("agentpulse_test_power_ac");
+        let _ = std::fs::remove_dir_all(&root);
+        supply(&root, "AC", &[("type", "Mains"), ("online", "1")]);
+        supply(
+            &root,
+            "BAT0",
+            &[("type", "Battery"), ("status", "Charging")],
+        );
+        assert_eq!(detect_sysfs(&root), PowerSource::Ac);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn sysfs_missing_is_unknown() {
+        let root = std::env::temp_dir().join("agentpulse_test_power_none");
+        assert_eq!(detect_sysfs(&root), PowerSource::Unknown);
+    }
+
+    #[test]
+    fn pmset_output() {
+        assert_eq!(
+            parse_pmset("Now drawing from 'Battery Power'\n -InternalBattery-0 80%"),
+            PowerSource::Battery
+        );
+        assert_eq!(parse_pmset("Now drawing from 'AC Power'"), PowerSource::Ac);
+        assert_eq!(parse_pmset(""), PowerSource::Unknown);
+    }
+}
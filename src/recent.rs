@@ -0,0 +1,158 @@
+//! Repo usage behind the `Ctrl-P` quick switcher.
+//!
+//! Opening a repo (editor, file manager, the switcher itself) and finishing
+//! an action in it count as a use. Repos are ranked by frecency: the use
+//! count, weighted up for recent use and down as it ages, so the few repos
+//! in play today sort above ones that were busy last month.
+
+use crate::git::Repo;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Repos remembered; the lowest-ranked fall off beyond this.
+const MAX_TRACKED: usize = 50;
+
+const HOUR: i64 = 3600;
+const DAY: i64 = 24 * HOUR;
+
+/// Uses of one repo; `identity` finds it again after a move.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepoUse {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub identity: Option<String>,
+    pub count: u32,
+    /// Unix seconds of the latest use.
+    pub last_used: i64,
+}
+
+impl RepoUse {
+    /// Frecency at `now`: uses, scaled by how long ago the last one was.
+    pub fn score(&self, now: i64) -> f64 {
+        let age = now.saturating_sub(self.last_used);
+        let weight = match age {
+            a if a < HOUR => 4.0,
+            a if a < DAY => 2.0,
+            a if a < 7 * DAY => 0.5,
+            _ => 0.25,
+        };
+        self.count as f64 * weight
+    }
+}
+
+/// Every tracked repo, persisted with the session state.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RecentRepos(Vec<RepoUse>);
+
+impl RecentRepos {
+    /// Count a use of `repo` at `now` (Unix seconds). Entries are keyed by
+    /// path; the identity only picks up an entry whose path is gone, since
+    /// clones and worktrees of one repo share it.
+    pub fn record(&mut self, repo: &Repo, now: i64) {
+        let known = match self.0.iter().position(|u| u.path == repo.path) {
+            Some(idx) => Some(idx),
+            None => self.0.iter().position(|u| {
+                repo.identity.is_some() && u.identity == repo.identity && !u.path.exists()
+            }),
+        };
+        match known.map(|idx| &mut self.0[idx]) {
+            Some(entry) => {
+                entry.path = repo.path.clone();
+                entry.identity = repo.identity.clone().or(entry.identity.take());
+                entry.count = entry.count.saturating_add(1);
+                entry.last_used = now;
+            }
+            None => self.0.push(RepoUse {
+                path: repo.path.clone(),
+                identity: repo.identity.clone(),
+                count: 1,
+                last_used: now,
+            }),
+        }
+        if self.0.len() > MAX_TRACKED {
+            self.0 = self
+                .ranked(now)
+                .into_iter()
+                .take(MAX_TRACKED)
+                .cloned()
+                .collect();
+        }
+    }
+
+    /// Highest frecency first; ties go to the most recently used.
+    pub fn ranked(&self, now: i64) -> Vec<&RepoUse> {
+        let mut uses: Vec<&RepoUse> = self.0.iter().collect();
+        uses.sort_by(|a, b| {
+            b.score(now)
+                .total_cmp(&a.score(now))
+                .then_with(|| b.last_used.cmp(&a.last_used))
+        });
+        uses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(path: &str, identity: Option<&str>) -> Repo {
+        let mut repo = Repo::new(PathBuf::from(path));
+        repo.identity = identity.map(str::to_string);
+        repo
+    }
+
+    #[test]
+    fn ranks_by_frequency_weighted_by_recency() {
+        let now = 1_000 * DAY;
+        let mut recent = RecentRepos::default();
+        // Busy last month, touched once an hour ago, and used twice today.
+        for _ in 0..6 {
+            recent.record(&repo("/src/old", None), now - 30 * DAY);
+        }
+        recent.record(&repo("/src/once", None), now - 2 * HOUR);
+        recent.record(&repo("/src/today", None), now - 3 * HOUR);
+        recent.record(&repo("/src/today", None), now - 2 * HOUR);
+        let order: Vec<_> = recent
+            .ranked(now)
+            .iter()
+            .map(|u| u.path.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(order, ["/src/today", "/src/once", "/src/old"]);
+    }
+
+    #[test]
+    fn follows_moved_repos_and_stays_bounded() {
+        let mut recent = RecentRepos::default();
+        recent.record(&repo("/src/api", Some("root:abc")), 10);
+        recent.record(&repo("/work/api", Some("root:abc")), 20);
+        let ranked = recent.ranked(20);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].path, PathBuf::from("/work/api"));
+        assert_eq!(ranked[0].count, 2);
+
+        for n in 0..MAX_TRACKED + 5 {
+            recent.record(&repo(&format!("/src/r{}", n), None), 30 + n as i64);
+        }
+        assert_eq!(recent.ranked(100).len(), MAX_TRACKED);
+        // The repo used twice outranks the single uses and is kept.
+        assert_eq!(recent.ranked(100)[0].path, PathBuf::from("/work/api"));
+    }
+
+    #[test]
+    fn clones_that_share_an_identity_are_tracked_apart() {
+        let base = std::env::temp_dir().join("agentpulse_recent_test");
+        let (a, b) = (base.join("api"), base.join("api-review"));
+        std::fs::create_dir_all(&a).unwrap();
+        std::fs::create_dir_all(&b).unwrap();
+        let mut recent = RecentRepos::default();
+        recent.record(&repo(&a.to_string_lossy(), Some("root:abc")), 10);
+        recent.record(&repo(&b.to_string_lossy(), Some("root:abc")), 20);
+        recent.record(&repo(&a.to_string_lossy(), Some("root:abc")), 30);
+        let ranked = recent.ranked(30);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!((ranked[0].path.clone(), ranked[0].count), (a, 2));
+        assert_eq!((ranked[1].path.clone(), ranked[1].count), (b, 1));
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
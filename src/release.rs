@@ -0,0 +1,441 @@
+//! Release readiness across repos (`agentpulse release-check`): per repo,
+//! commits on the default branch since the last release tag, CI on that
+//! branch, a dirty tree, and release branches not merged back, as one
+//! Markdown report for whoever is cutting releases of many services at once.
+
+use crate::collectors::forge::github_run_status;
+use crate::dashboard::CiStatus;
+use crate::forge_links;
+use crate::git::std_git_command;
+use crate::path_utils::resolve_binary_in_path;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// What `release-check` looks for.
+#[derive(Debug, Clone)]
+pub struct ReleaseQuery {
+    /// Release tags, e.g. `v*`.
+    pub tag_pattern: String,
+    /// Release branch names, e.g. `release/*`.
+    pub branch_pattern: String,
+}
+
+/// A release branch with commits the default branch lacks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnmergedBranch {
+    pub name: String,
+    pub commits: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoRelease {
+    pub name: String,
+    pub path: PathBuf,
+    /// `origin/main` when the remote's HEAD is known, else the local branch.
+    pub default_branch: String,
+    pub last_tag: Option<String>,
+    /// Commits on the default branch since `last_tag`; all of them without one.
+    pub unreleased: usize,
+    pub uncommitted: usize,
+    /// `Unknown` without a GitHub remote, without `gh`, or without runs.
+    pub ci: CiStatus,
+    /// Workflow name and run URL behind `ci`.
+    pub ci_detail: Option<String>,
+    pub unmerged_release_branches: Vec<UnmergedBranch>,
+}
+
+impl RepoRelease {
+    /// Reasons this repo should not be released as it stands.
+    pub fn blockers(&self) -> Vec<String> {
+        let mut blockers = Vec::new();
+        if self.ci == CiStatus::Failing {
+            blockers.push(format!(
+                "CI failing on {}{}",
+                self.default_branch,
+                self.ci_detail
+                    .as_ref()
+                    .map(|detail| format!(" ({})", detail))
+                    .unwrap_or_default()
+            ));
+        }
+        if self.uncommitted > 0 {
+            blockers.push(format!("{} uncommitted file(s)", self.uncommitted));
+        }
+        for branch in &self.unmerged_release_branches {
+            blockers.push(format!(
+                "{} has {} commit(s) not merged into {}",
+                branch.name, branch.commits, self.default_branch
+            ));
+        }
+        blockers
+    }
+
+    fn verdict(&self) -> &'static str {
+        if !self.blockers().is_empty() {
+            "blocked"
+        } else if self.unreleased == 0 {
+            "up to date"
+        } else if self.ci == CiStatus::Running {
+            "waiting on CI"
+        } else {
+            "ready"
+        }
+    }
+}
+
+/// Check every repo in `repos`, running at most `max_parallel` at once.
+/// Results keep the order of `repos`.
+pub async fn check(
+    repos: Vec<PathBuf>,
+    query: &ReleaseQuery,
+    max_parallel: usize,
+) -> Vec<RepoRelease> {
+    let slots = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let tasks: Vec<_> = repos
+        .into_iter()
+        .map(|path| {
+            let slots = slots.clone();
+            let query = query.clone();
+            tokio::spawn(async move {
+                let _permit = slots.acquire_owned().await.ok()?;
+                tokio::task::spawn_blocking(move || check_repo(&path, &query))
+                    .await
+                    .ok()
+            })
+        })
+        .collect();
+    let mut releases = Vec::new();
+    for task in tasks {
+        if let Ok(Some(release)) = task.await {
+            releases.push(release);
+        }
+    }
+    releases
+}
+
+fn git_stdout(path: &Path, args: &[&str]) -> Option<String> {
+    std_git_command(args)
+        .current_dir(path)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+/// The remote's default branch (`origin/main`), else a local `main` or
+/// `master`, else whatever is checked out.
+fn default_branch(path: &Path) -> String {
+    if let Some(remote_head) = git_stdout(
+        path,
+        &[
+            "symbolic-ref",
+            "--quiet",
+            "--short",
+            "refs/remotes/origin/HEAD",
+        ],
+    ) {
+        return remote_head;
+    }
+    ["main", "master"]
+        .into_iter()
+        .find(|name| {
+            git_stdout(
+                path,
+                &[
+                    "rev-parse",
+                    "--verify",
+                    "--quiet",
+                    &format!("refs/heads/{}", name),
+                ],
+            )
+            .is_some()
+        })
+        .map(str::to_string)
+        .or_else(|| git_stdout(path, &["rev-parse", "--abbrev-ref", "HEAD"]))
+        .unwrap_or_else(|| "HEAD".to_string())
+}
+
+fn count(path: &Path, range: &str) -> usize {
+    git_stdout(path, &["rev-list", "--count", range])
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0)
+}
+
+fn check_repo(path: &Path, query: &ReleaseQuery) -> RepoRelease {
+    let base = default_branch(path);
+    let last_tag = git_stdout(
+        path,
+        &[
+            "describe",
+            "--tags",
+            "--abbrev=0",
+            "--match",
+            &query.tag_pattern,
+            &base,
+        ],
+    );
+    let unreleased = match &last_tag {
+        Some(tag) => count(path, &format!("{}..{}", tag, base)),
+        None => count(path, &base),
+    };
+    let uncommitted = git_stdout(path, &["status", "--porcelain"])
+        .map(|raw| raw.lines().count())
+        .unwrap_or(0);
+    let no_merged = format!("--no-merged={}", base);
+    let local = format!("refs/heads/{}", query.branch_pattern);
+    let remote = format!("refs/remotes/*/{}", query.branch_pattern);
+    let branches = git_stdout(
+        path,
+        &[
+            "for-each-ref",
+            &no_merged,
+            "--format=%(refname)",
+            &local,
+            &remote,
+        ],
+    )
+    .unwrap_or_default();
+    let unmerged_release_branches = unmerged(
+        branches
+            .lines()
+            .map(|name| (name, count(path, &format!("{}..{}", base, name)))),
+    );
+    let (ci, ci_detail) = ci_status(path, &base);
+
+    RepoRelease {
+        name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: path.to_path_buf(),
+        default_branch: base,
+        last_tag,
+        unreleased,
+        uncommitted,
+        ci,
+        ci_detail,
+        unmerged_release_branches,
+    }
+}
+
+/// Release branches (full ref names) with their unmerged commit counts; a
+/// local branch and its remote-tracking twin are listed once, under the
+/// local name and the larger count.
+fn unmerged<'a>(branches: impl Iterator<Item = (&'a str, usize)>) -> Vec<UnmergedBranch> {
+    let mut out: Vec<(&str, UnmergedBranch)> = Vec::new();
+    for (refname, commits) in branches.filter(|(_, commits)| *commits > 0) {
+        let (name, branch) = match refname.strip_prefix("refs/heads/") {
+            Some(local) => (local, local),
+            None => {
+                let name = refname.strip_prefix("refs/remotes/").unwrap_or(refname);
+                (
+                    name,
+                    name.split_once('/').map_or(name, |(_, branch)| branch),
+                )
+            }
+        };
+        match out.iter_mut().find(|(seen, _)| *seen == branch) {
+            Some((_, existing)) => existing.commits = existing.commits.max(commits),
+            None => out.push((
+                branch,
+                UnmergedBranch {
+                    name: name.to_string(),
+                    commits,
+                },
+            )),
+        }
+    }
+    out.into_iter().map(|(_, branch)| branch).collect()
+}
+
+/// The latest GitHub Actions run on `branch`, through `gh`.
+fn ci_status(path: &Path, branch: &str) -> (CiStatus, Option<String>) {
+    let on_github = git_stdout(path, &["remote", "get-url", "origin"])
+        .and_then(|url| forge_links::web_url(&url))
+        .is_some_and(|url| url.starts_with("https://github.com/"));
+    if !on_github || resolve_binary_in_path("gh").is_none() {
+        return (CiStatus::Unknown, None);
+    }
+    let branch = branch.strip_prefix("origin/").unwrap_or(branch);
+    let output = std::process::Command::new("gh")
+        .args(["run", "list", "--branch", branch, "--limit", "1"])
+        .args(["--json", "status,conclusion,workflowName,url"])
+        .current_dir(path)
+        .output();
+    match output {
+        Ok(out) if out.status.success() => serde_json::from_slice(&out.stdout)
+            .map(|runs| parse_runs(&runs))
+            .unwrap_or((CiStatus::Unknown, None)),
+        _ => (CiStatus::Unknown, None),
+    }
+}
+
+/// `gh run list --json status,conclusion,workflowName,url`.
+fn parse_runs(runs: &Value) -> (CiStatus, Option<String>) {
+    let Some(run) = runs.as_array().and_then(|runs| runs.first()) else {
+        return (CiStatus::Unknown, None);
+    };
+    let field = |name: &str| run.get(name).and_then(Value::as_str).unwrap_or_default();
+    let state = github_run_status(run);
+    let detail = [field("workflowName"), field("url")]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (state, (!detail.is_empty()).then_some(detail))
+}
+
+/// The consolidated Markdown report: a table of every repo, then the
+/// blockers of those that are not ready.
+pub fn format_report(query: &ReleaseQuery, date: &str, releases: &[RepoRelease]) -> String {
+    let verdicts: Vec<&str> = releases.iter().map(RepoRelease::verdict).collect();
+    let tally = |verdict: &str| verdicts.iter().filter(|v| **v == verdict).count();
+    let mut out = format!("# Release readiness ({})\n\n", date);
+    out.push_str(&format!(
+        "Tags `{}`, release branches `{}`: {} repo(s), {} ready, {} blocked, {} up to date.\n\n",
+        query.tag_pattern,
+        query.branch_pattern,
+        releases.len(),
+        tally("ready"),
+        tally("blocked"),
+        tally("up to date"),
+    ));
+    out.push_str(
+        "| Repo | Branch | Last release | Unreleased | CI | Tree | Release branches | Status |\n",
+    );
+    out.push_str("|---|---|---|---|---|---|---|---|\n");
+    for (release, verdict) in releases.iter().zip(&verdicts) {
+        let tree = match release.uncommitted {
+            0 => "clean".to_string(),
+            n => format!("{} dirty", n),
+        };
+        let branches = if release.unmerged_release_branches.is_empty() {
+            "—".to_string()
+        } else {
+            release
+                .unmerged_release_branches
+                .iter()
+                .map(|b| format!("{} (+{})", b.name, b.commits))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            release.name,
+            release.default_branch,
+            release.last_tag.as_deref().unwrap_or("none"),
+            release.unreleased,
+            match release.ci {
+                CiStatus::Failing => "**failing**",
+                ci => ci.label(),
+            },
+            tree,
+            branches,
+            verdict,
+        ));
+    }
+    let blocked: Vec<&RepoRelease> = releases
+        .iter()
+        .filter(|release| !release.blockers().is_empty())
+        .collect();
+    if !blocked.is_empty() {
+        out.push_str("\n## Blockers\n\n");
+        for release in blocked {
+            out.push_str(&format!(
+                "- **{}**: {}\n",
+                release.name,
+                release.blockers().join("; ")
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn reads_the_latest_run_and_merges_branch_twins() {
+        let runs = serde_json::json!([
+            { "status": "completed", "conclusion": "failure", "workflowName": "build",
+              "url": "https://github.com/acme/api/actions/runs/1" }
+        ]);
+        assert_eq!(
+            parse_runs(&runs),
+            (
+                CiStatus::Failing,
+                Some("build https://github.com/acme/api/actions/runs/1".to_string())
+            )
+        );
+        let running = serde_json::json!([{ "status": "in_progress", "conclusion": "" }]);
+        assert_eq!(parse_runs(&running).0, CiStatus::Running);
+        assert_eq!(parse_runs(&serde_json::json!([])).0, CiStatus::Unknown);
+
+        let branches = unmerged(
+            [
+                ("refs/heads/release/1.0", 0),
+                ("refs/heads/release/2.0", 2),
+                ("refs/remotes/origin/release/2.0", 3),
+                ("refs/remotes/origin/release/3.0", 1),
+            ]
+            .into_iter(),
+        );
+        let names: Vec<(&str, usize)> = branches
+            .iter()
+            .map(|b| (b.name.as_str(), b.commits))
+            .collect();
+        assert_eq!(names, [("release/2.0", 3), ("origin/release/3.0", 1)]);
+    }
+
+    #[tokio::test]
+    async fn reports_unreleased_commits_dirty_trees_and_open_release_branches() {
+        let dir = std::env::temp_dir().join("agentpulse_release_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(&dir)
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-b", "main"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        git(&["commit", "--allow-empty", "-m", "first"]);
+        git(&["tag", "v1.0.0"]);
+        git(&["commit", "--allow-empty", "-m", "feat: second"]);
+        git(&["commit", "--allow-empty", "-m", "fix: third"]);
+        git(&["switch", "-c", "release/1.1"]);
+        git(&["commit", "--allow-empty", "-m", "hotfix"]);
+        git(&["switch", "main"]);
+        fs::write(dir.join("notes.txt"), "wip\n").unwrap();
+
+        let query = ReleaseQuery {
+            tag_pattern: "v*".to_string(),
+            branch_pattern: "release/*".to_string(),
+        };
+        let releases = check(vec![dir.clone()], &query, 1).await;
+        let release = &releases[0];
+        assert_eq!(release.default_branch, "main");
+        assert_eq!(release.last_tag.as_deref(), Some("v1.0.0"));
+        assert_eq!(release.unreleased, 2);
+        assert_eq!(release.uncommitted, 1);
+        assert_eq!(release.ci, CiStatus::Unknown);
+        assert_eq!(release.unmerged_release_branches[0].name, "release/1.1");
+
+        let report = format_report(&query, "2026-10-16", &releases);
+        assert!(report.contains(&format!(
+            "| {} | main | v1.0.0 | 2 | — | 1 dirty | release/1.1 (+1) | blocked |",
+            release.name
+        )));
+        assert!(report
+            .contains("1 uncommitted file(s); release/1.1 has 1 commit(s) not merged into main"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,241 @@
+//! Path-independent index of known repositories.
+//!
+//! Persistent per-repo data should key off `Repo::identity` (root commit or
+//! origin URL) rather than the checkout path. This index maps identities to
+//! their last known paths so a moved or renamed repo is recognised as the
+//! same repo, and callers can migrate any path-keyed state.
+
+use crate::git::Repo;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepoIndex {
+    #[serde(default)]
+    entries: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IndexEntry {
+    id: String,
+    path: PathBuf,
+}
+
+/// A repo whose identity was last seen at `from` and is now at `to`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoMove {
+    pub id: String,
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// Index location: `~/.config/agentpulse/repo_index.json`.
+pub fn index_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".config")
+        .join("agentpulse")
+        .join("repo_index.json")
+}
+
+impl RepoIndex {
+    /// Load the index, starting empty if the file is missing or unreadable.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of entries `prune` would drop for the given watch directories.
+    pub fn stale_count(&self, watch_dirs: &[PathBuf]) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| is_stale(&e.path, watch_dirs))
+            .count()
+    }
+
+    /// Drop entries whose path no longer exists or is outside every watch directory.
+    /// Run after `record` so moves seen in the same scan are migrated first.
+    pub fn prune(&mut self, watch_dirs: &[PathBuf]) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|e| !is_stale(&e.path, watch_dirs));
+        before - self.entries.len()
+    }
+
+    /// Record the repos from a scan and return any detected moves.
+    ///
+    /// A move is an identity seen at a new path while one of its previously
+    /// recorded paths no longer exists on disk. Two live clones with the same
+    /// root commit are tracked side by side and never treated as a move.
+    pub fn record(&mut self, repos: &[Repo]) -> Vec<RepoMove> {
+        let mut moves = Vec::new();
+        for repo in repos {
+            let Some(id) = repo.identity.as_deref() else {
+                continue;
+            };
+            if self
+                .entries
+                .iter()
+                .any(|e| e.id == id && e.path == repo.path)
+            {
+                continue;
+            }
+            let stale = self
+                .entries
+                .iter_mut()
+                .find(|e| e.id == id && !e.path.exists());
+            match stale {
+                Some(entry) => {
+                    moves.push(RepoMove {
+                        id: id.to_string(),
+                        from: std::mem::replace(&mut entry.path, repo.path.clone()),
+                        to: repo.path.clone(),
+                    });
+                }
+                None => {
+                    // A different repo may now live at this path; drop its old mapping.
+                    self.entries.retain(|e| e.path != repo.path);
+                    self.entries.push(IndexEntry {
+                        id: id.to_string(),
+                        path: repo.path.clone(),
+                    });
+                }
+            }
+        }
+        moves
+    }
+}
+
+fn is_stale(path: &Path, watch_dirs: &[PathBuf]) -> bool {
+    !path.exists() || !watch_dirs.iter().any(|dir| path.starts_with(dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(path: &Path, id: &str) -> Repo {
+        let mut r = Repo::new(path.to_path_buf());
+        r.identity = Some(id.to_string());
+        r
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join("agentpulse_repo_index_test")
+            .join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn detects_move_when_old_path_is_gone() {
+        let base = scratch("move");
+        let old = base.join("old-name");
+        let new = base.join("new-name");
+        std::fs::create_dir_all(&old).unwrap();
+
+        let mut index = RepoIndex::default();
+        assert!(index.record(&[repo(&old, "root:abc")]).is_empty());
+
+        std::fs::rename(&old, &new).unwrap();
+        let moves = index.record(&[repo(&new, "root:abc")]);
+        assert_eq!(
+            moves,
+            vec![RepoMove {
+                id: "root:abc".to_string(),
+                from: old.clone(),
+                to: new.clone(),
+            }]
+        );
+        assert_eq!(
+            index.entries,
+            vec![IndexEntry {
+                id: "root:abc".to_string(),
+                path: new.clone(),
+            }]
+        );
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn live_clones_are_not_moves() {
+        let base = scratch("clones");
+        let a = base.join("a");
+        let b = base.join("b");
+        std::fs::create_dir_all(&a).unwrap();
+        std::fs::create_dir_all(&b).unwrap();
+
+        let mut index = RepoIndex::default();
+        let moves = index.record(&[repo(&a, "root:abc"), repo(&b, "root:abc")]);
+        assert!(moves.is_empty());
+        assert_eq!(index.entries.len(), 2);
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn repos_without_identity_are_skipped() {
+        let mut index = RepoIndex::default();
+        assert!(index
+            .record(&[Repo::new(PathBuf::from("/tmp/x"))])
+            .is_empty());
+        assert!(index.entries.is_empty());
+    }
+
+    #[test]
+    fn prune_drops_deleted_and_unwatched_paths() {
+        let base = scratch("prune");
+        let watched = base.join("watched");
+        let kept = watched.join("kept");
+        let deleted = watched.join("deleted");
+        let elsewhere = base.join("elsewhere");
+        for dir in [&kept, &deleted, &elsewhere] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+
+        let mut index = RepoIndex::default();
+        index.record(&[
+            repo(&kept, "root:1"),
+            repo(&deleted, "root:2"),
+            repo(&elsewhere, "root:3"),
+        ]);
+        std::fs::remove_dir_all(&deleted).unwrap();
+
+        let watch = vec![watched.clone()];
+        assert_eq!(index.stale_count(&watch), 2);
+        assert_eq!(index.prune(&watch), 2);
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.entries[0].path, kept);
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn round_trips_through_disk() {
+        let base = scratch("disk");
+        let path = base.join("repo_index.json");
+        let mut index = RepoIndex::default();
+        index.record(&[repo(&base, "remote:git@example.com:me/x.git")]);
+        index.save(&path).unwrap();
+        assert_eq!(RepoIndex::load(&path), index);
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
@@ -0,0 +1,197 @@
+//! Repo statuses kept between one-shot runs with `--cache` (`--once`,
+//! `--summary`, `--agent-json`, …), keyed by [`StatusFingerprint`] rather
+//! than file mtimes, which fresh CI checkouts and container layers reset.
+//!
+//! A repo's cached status is only reused when its HEAD and status hash both
+//! still match, so a different checkout at the same path is always
+//! re-checked. The fingerprint never walks the worktree, so it can't see
+//! edits that are not staged yet; that is why the cache is opt-in and meant
+//! for CI, where nobody edits the checkout between runs.
+
+use crate::config::Config;
+use crate::git::{status_fingerprint, Repo, RepoStatus, StatusFingerprint};
+use crate::monitor;
+use anyhow::Result;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::task::JoinSet;
+
+/// Bumped when the file layout changes; older files are ignored.
+const VERSION: u32 = 2;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanCache {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    repos: BTreeMap<PathBuf, CachedRepo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedRepo {
+    fingerprint: StatusFingerprint,
+    status: RepoStatus,
+    #[serde(default)]
+    identity: Option<String>,
+}
+
+/// `~/.config/agentpulse/scan_cache.json`.
+pub fn cache_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".config")
+        .join("agentpulse")
+        .join("scan_cache.json")
+}
+
+impl ScanCache {
+    /// Load the cache, or an empty one when missing, unreadable, or written
+    /// by another version.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<ScanCache>(&raw).ok())
+            .filter(|cache| cache.version == VERSION)
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = ScanCache {
+            version: VERSION,
+            repos: self.repos.clone(),
+        };
+        std::fs::write(path, serde_json::to_string(&file)?)?;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.repos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.repos.is_empty()
+    }
+}
+
+/// [`monitor::scan_all`] for one-shot runs: repos whose fingerprint matches
+/// `cache` reuse the cached status, the rest get a full check. `cache` ends
+/// up holding exactly the watched repos.
+pub async fn scan_all(config: &Config, cache: &mut ScanCache) -> Vec<Repo> {
+    monitor::configure(config);
+    let paths = monitor::watched_repo_paths(config);
+    let mut repos = Vec::with_capacity(paths.len());
+    let mut fresh = BTreeMap::new();
+
+    for chunk in paths.chunks(monitor::MAX_CONCURRENT) {
+        let mut set: JoinSet<(Repo, Option<CachedRepo>)> = JoinSet::new();
+        for path in chunk {
+            let path = path.clone();
+            let cached = cache.repos.get(&path).cloned();
+            set.spawn(async move {
+                let fingerprint = status_fingerprint(&path).await;
+                match cached {
+                    Some(cached) if fingerprint.as_ref() == Some(&cached.fingerprint) => {
+                        let mut repo = Repo::new(path);
+                        repo.status = cached.status.clone();
+                        repo.identity = cached.identity.clone();
+                        repo.last_checked = Some(Local::now());
+                        (repo, Some(cached))
+                    }
+                    // Another checkout may sit at this path, so its identity
+                    // is looked up again too.
+                    _ => {
+                        let repo = monitor::check_repo(&path, None).await;
+                        let entry = fingerprint
+                            .filter(|_| {
+                                repo.last_checked.is_some() && repo.status.probe_errors.is_empty()
+                            })
+                            .map(|fingerprint| CachedRepo {
+                                fingerprint,
+                                status: repo.status.clone(),
+                                identity: repo.identity.clone(),
+                            });
+                        (repo, entry)
+                    }
+                }
+            });
+        }
+        while let Some(res) = set.join_next().await {
+            if let Ok((repo, entry)) = res {
+                if let Some(entry) = entry {
+                    fresh.insert(repo.path.clone(), entry);
+                }
+                repos.push(repo);
+            }
+        }
+    }
+
+    cache.repos = fresh;
+    monitor::sort_by_urgency(&mut repos);
+    repos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let out = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "git {:?} failed", args);
+    }
+
+    #[tokio::test]
+    async fn reuses_status_only_while_head_and_status_match() {
+        let base = std::env::temp_dir().join("agentpulse_scan_cache_test");
+        let _ = std::fs::remove_dir_all(&base);
+        let repo = base.join("api");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-q"]);
+        git(&repo, &["config", "user.email", "test@test.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        std::fs::write(repo.join("a.txt"), "a").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-q", "-m", "init"]);
+        let config = Config {
+            watch_directories: vec![base.clone()],
+            ..Config::default()
+        };
+        let path = base.join("scan_cache.json");
+
+        let mut cache = ScanCache::default();
+        scan_all(&config, &mut cache).await;
+        assert_eq!(cache.len(), 1);
+        cache.save(&path).unwrap();
+
+        // A planted status shows the next scan answered from the cache.
+        let mut cache = ScanCache::load(&path);
+        let key = cache.repos.keys().next().unwrap().clone();
+        cache.repos.get_mut(&key).unwrap().status.stash_count = 7;
+        let repos = scan_all(&config, &mut cache).await;
+        assert_eq!(repos[0].status.stash_count, 7);
+
+        // Staging a change is enough to re-check.
+        std::fs::write(repo.join("a.txt"), "b").unwrap();
+        git(&repo, &["add", "a.txt"]);
+        let repos = scan_all(&config, &mut cache).await;
+        assert_eq!(repos[0].status.stash_count, 0);
+        assert_eq!(repos[0].status.uncommitted_count, 1);
+
+        // So is a new HEAD with a clean tree again.
+        cache.repos.get_mut(&key).unwrap().status.stash_count = 7;
+        git(&repo, &["commit", "-q", "-m", "b"]);
+        let repos = scan_all(&config, &mut cache).await;
+        assert_eq!(repos[0].status.stash_count, 0);
+        assert_eq!(repos[0].status.uncommitted_count, 0);
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
@@ -0,0 +1,239 @@
+//! Cross-repo search (`agentpulse grep`, `G` in the TUI): which watched repos
+//! contain a string in tracked files, optionally in history (`git log -S`),
+//! or a commit with the given hash.
+
+use crate::git::std_git_command;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Files listed per repo before collapsing the rest into a count.
+const FILES_SHOWN: usize = 5;
+/// History commits listed per repo with `--history`.
+const HISTORY_LIMIT: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileHit {
+    pub path: String,
+    pub count: usize,
+}
+
+/// Where one repo matched; repos without any match are not reported.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoHit {
+    pub name: String,
+    pub path: PathBuf,
+    /// Tracked files in the working tree containing the pattern.
+    pub files: Vec<FileHit>,
+    /// The pattern is a commit hash that exists in this repo.
+    pub has_commit: bool,
+    /// `<short sha> <subject>` of commits adding or removing the pattern.
+    pub history: Vec<String>,
+}
+
+impl RepoHit {
+    pub fn match_count(&self) -> usize {
+        self.files.iter().map(|f| f.count).sum()
+    }
+
+    fn is_match(&self) -> bool {
+        !self.files.is_empty() || self.has_commit || !self.history.is_empty()
+    }
+}
+
+/// Search every repo in `repos`, running at most `max_parallel` at once.
+/// Results keep the order of `repos`.
+pub async fn search(
+    repos: Vec<PathBuf>,
+    pattern: &str,
+    history: bool,
+    max_parallel: usize,
+) -> Vec<RepoHit> {
+    let slots = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let tasks: Vec<_> = repos
+        .into_iter()
+        .map(|path| {
+            let slots = slots.clone();
+            let pattern = pattern.to_string();
+            tokio::spawn(async move {
+                let _permit = slots.acquire_owned().await.ok()?;
+                tokio::task::spawn_blocking(move || search_repo(&path, &pattern, history))
+                    .await
+                    .ok()
+            })
+        })
+        .collect();
+    let mut hits = Vec::new();
+    for task in tasks {
+        if let Ok(Some(hit)) = task.await {
+            if hit.is_match() {
+                hits.push(hit);
+            }
+        }
+    }
+    hits
+}
+
+fn search_repo(path: &Path, pattern: &str, history: bool) -> RepoHit {
+    let stdout = |args: &[&str]| {
+        std_git_command(args)
+            .current_dir(path)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+    };
+    let files = stdout(&["grep", "-I", "-c", "-F", "-e", pattern])
+        .map(|raw| parse_grep_counts(&raw))
+        .unwrap_or_default();
+    let has_commit = looks_like_commit(pattern)
+        && stdout(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", pattern),
+        ])
+        .is_some();
+    let history = if history {
+        stdout(&[
+            "log",
+            "--all",
+            &format!("-{}", HISTORY_LIMIT),
+            "--format=%h %s",
+            "-S",
+            pattern,
+        ])
+        .map(|raw| raw.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    RepoHit {
+        name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: path.to_path_buf(),
+        files,
+        has_commit,
+        history,
+    }
+}
+
+/// `git grep -c` output: `path:count` per line (paths may contain `:`).
+fn parse_grep_counts(raw: &str) -> Vec<FileHit> {
+    raw.lines()
+        .filter_map(|line| {
+            let (path, count) = line.rsplit_once(':')?;
+            Some(FileHit {
+                path: path.to_string(),
+                count: count.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Abbreviated or full hex object name.
+fn looks_like_commit(pattern: &str) -> bool {
+    (7..=40).contains(&pattern.len()) && pattern.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Human-readable report for `agentpulse grep`.
+pub fn format_report(pattern: &str, searched: usize, hits: &[RepoHit]) -> String {
+    let mut out = String::new();
+    for hit in hits {
+        out.push_str(&format!("{}  {}\n", hit.name, hit.path.display()));
+        if hit.has_commit {
+            out.push_str(&format!("    contains commit {}\n", pattern));
+        }
+        for file in hit.files.iter().take(FILES_SHOWN) {
+            out.push_str(&format!("    {}: {}\n", file.path, file.count));
+        }
+        if hit.files.len() > FILES_SHOWN {
+            out.push_str(&format!(
+                "    … {} more file(s)\n",
+                hit.files.len() - FILES_SHOWN
+            ));
+        }
+        for commit in &hit.history {
+            out.push_str(&format!("    history: {}\n", commit));
+        }
+    }
+    out.push_str(&format!(
+        "{:?} found in {} of {} repo(s)\n",
+        pattern,
+        hits.len(),
+        searched
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn parses_counts_for_paths_with_colons() {
+        assert_eq!(
+            parse_grep_counts("src/lib.rs:3\ndocs/a:b.md:1\n"),
+            vec![
+                FileHit {
+                    path: "src/lib.rs".to_string(),
+                    count: 3
+                },
+                FileHit {
+                    path: "docs/a:b.md".to_string(),
+                    count: 1
+                },
+            ]
+        );
+        assert!(looks_like_commit("deadbeef"));
+        assert!(!looks_like_commit("dead"));
+        assert!(!looks_like_commit("old_api()"));
+    }
+
+    #[tokio::test]
+    async fn reports_only_repos_that_match() {
+        let base = std::env::temp_dir().join("agentpulse_search_test");
+        let _ = fs::remove_dir_all(&base);
+        let mut paths = Vec::new();
+        for (name, content) in [("uses", "call(old_api)\n"), ("clean", "call(new_api)\n")] {
+            let dir = base.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            let git = |args: &[&str]| {
+                std::process::Command::new("git")
+                    .args(args)
+                    .current_dir(&dir)
+                    .output()
+                    .unwrap()
+            };
+            git(&["init"]);
+            git(&["config", "user.email", "test@test.com"]);
+            git(&["config", "user.name", "Test"]);
+            fs::write(dir.join("main.rs"), content).unwrap();
+            git(&["add", "."]);
+            git(&["commit", "-m", "init"]);
+            paths.push(dir);
+        }
+
+        let hits = search(paths.clone(), "old_api", true, 1).await;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].name, "uses");
+        assert_eq!(hits[0].match_count(), 1);
+        assert_eq!(hits[0].history.len(), 1);
+
+        let head = std::process::Command::new("git")
+            .args(["rev-parse", "--short=10", "HEAD"])
+            .current_dir(&paths[1])
+            .output()
+            .unwrap();
+        let sha = String::from_utf8_lossy(&head.stdout).trim().to_string();
+        let hits = search(paths, &sha, false, 2).await;
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].has_commit);
+        assert_eq!(hits[0].name, "clean");
+        let _ = fs::remove_dir_all(&base);
+    }
+}
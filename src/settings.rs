@@ -0,0 +1,459 @@
+//! The Settings section: an editable form over the config keys tweaked most
+//! often, so common changes need neither `--setup` nor a text editor.
+//!
+//! Saving rewrites only the edited keys' lines in the file on disk, so
+//! comments and every other key survive. A key that was also changed on disk
+//! since the form was filled is a conflict: nothing is written and the form
+//! has to be reloaded first.
+
+use crate::config::{load_config, update_config_atomic, Config};
+use crate::config_docs::lookup;
+use anyhow::{bail, Result};
+use std::path::Path;
+
+/// How a field is edited and validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Comma-separated directories, at least one.
+    Dirs,
+    /// A whole number in `min..=max`.
+    Number { min: i64, max: i64 },
+    /// Flipped with Enter.
+    Toggle,
+}
+
+/// One editable key; `section` is `""` for top-level keys.
+pub struct Field {
+    pub section: &'static str,
+    pub key: &'static str,
+    pub kind: FieldKind,
+}
+
+impl Field {
+    /// `thresholds.behind_warn`, or just the key at the top level.
+    pub fn name(&self) -> String {
+        if self.section.is_empty() {
+            self.key.to_string()
+        } else {
+            format!("{}.{}", self.section, self.key)
+        }
+    }
+}
+
+const fn number(section: &'static str, key: &'static str, min: i64, max: i64) -> Field {
+    Field {
+        section,
+        key,
+        kind: FieldKind::Number { min, max },
+    }
+}
+
+const fn toggle(section: &'static str, key: &'static str) -> Field {
+    Field {
+        section,
+        key,
+        kind: FieldKind::Toggle,
+    }
+}
+
+/// Form rows, in display order.
+pub const FIELDS: &[Field] = &[
+    Field {
+        section: "",
+        key: "watch_directories",
+        kind: FieldKind::Dirs,
+    },
+    number("", "refresh_interval_secs", 1, 3600),
+    number("", "clean_sweep_secs", 0, 86_400),
+    number("", "max_scan_depth", 1, 10),
+    toggle("", "show_clean"),
+    toggle("", "watch_mode"),
+    number("thresholds", "behind_warn", 0, 10_000),
+    number("thresholds", "dirty_file_warn", 0, 10_000),
+    number("thresholds", "unpushed_warn", 0, 10_000),
+    number("thresholds", "stash_warn", 0, 10_000),
+    number("thresholds", "work_age_high_hours", 0, 8760),
+    number("thresholds", "work_age_critical_hours", 0, 8760),
+    toggle("thresholds", "untracked_actionable"),
+];
+
+/// Field values as loaded, as edited, and the text being typed.
+pub struct SettingsForm {
+    /// Values the form was filled from, one per field.
+    loaded: Vec<toml::Value>,
+    pub values: Vec<toml::Value>,
+    /// Text being typed for the field under the cursor.
+    pub input: Option<String>,
+}
+
+impl SettingsForm {
+    pub fn from_config(config: &Config) -> Self {
+        let loaded = field_values(config);
+        Self {
+            values: loaded.clone(),
+            loaded,
+            input: None,
+        }
+    }
+
+    pub fn is_modified(&self, idx: usize) -> bool {
+        self.values.get(idx) != self.loaded.get(idx)
+    }
+
+    pub fn modified_count(&self) -> usize {
+        (0..FIELDS.len())
+            .filter(|idx| self.is_modified(*idx))
+            .count()
+    }
+
+    /// The value as shown and edited; directories are comma-separated.
+    pub fn display(&self, idx: usize) -> String {
+        self.values.get(idx).map(display_value).unwrap_or_default()
+    }
+
+    /// Enter on a field: flip a toggle, otherwise start typing with the
+    /// current value filled in.
+    pub fn activate(&mut self, idx: usize) {
+        let Some(field) = FIELDS.get(idx) else {
+            return;
+        };
+        if field.kind == FieldKind::Toggle {
+            if let Some(toml::Value::Boolean(on)) = self.values.get_mut(idx) {
+                *on = !*on;
+            }
+        } else {
+            self.input = Some(self.display(idx));
+        }
+    }
+
+    /// Keep the typed text if it is valid for the field; `Err` says what is
+    /// wrong and leaves the input open.
+    pub fn commit_input(&mut self, idx: usize) -> Result<(), String> {
+        let (Some(field), Some(text)) = (FIELDS.get(idx), self.input.as_deref()) else {
+            return Ok(());
+        };
+        let text = text.trim();
+        let value = match field.kind {
+            // Retyping the shown text must not turn `~` into a change.
+            FieldKind::Dirs if text == display_value(&self.loaded[idx]) => self.loaded[idx].clone(),
+            FieldKind::Dirs => {
+                let dirs: Vec<toml::Value> = text
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|dir| !dir.is_empty())
+                    .map(|dir| toml::Value::String(dir.to_string()))
+                    .collect();
+                if dirs.is_empty() {
+                    return Err(format!("{} needs at least one directory", field.key));
+                }
+                toml::Value::Array(dirs)
+            }
+            FieldKind::Number { min, max } => {
+                let n: i64 = text
+                    .parse()
+                    .map_err(|_| format!("{} must be a whole number", field.key))?;
+                if !(min..=max).contains(&n) {
+                    return Err(format!("{} must be between {} and {}", field.key, min, max));
+                }
+                toml::Value::Integer(n)
+            }
+            FieldKind::Toggle => return Ok(()),
+        };
+        self.values[idx] = value;
+        self.input = None;
+        Ok(())
+    }
+
+    /// Write the edited keys into the config file at `path` and return the
+    /// config as reloaded from it. The form is refilled from that config.
+    pub fn save(&mut self, path: &Path) -> Result<Config> {
+        let changed: Vec<usize> = (0..FIELDS.len())
+            .filter(|idx| self.is_modified(*idx))
+            .collect();
+        if changed.is_empty() {
+            bail!("no changes to save");
+        }
+        update_config_atomic(path, |old| {
+            let on_disk = field_values(&load_config(Some(&path.to_path_buf()))?);
+            for &idx in &changed {
+                if on_disk[idx] != self.loaded[idx] && on_disk[idx] != self.values[idx] {
+                    bail!(
+                        "{} was changed on disk since the form was filled; press R to reload",
+                        FIELDS[idx].name()
+                    );
+                }
+            }
+
+            let mut content = old.to_string();
+            for &idx in &changed {
+                let field = &FIELDS[idx];
+                content = set_key(
+                    &content,
+                    field.section,
+                    field.key,
+                    &self.values[idx].to_string(),
+                );
+            }
+            // Catch an edit the line patcher got wrong before anything is written.
+            let written: toml::Value = toml::from_str(&content)?;
+            for &idx in &changed {
+                let field = &FIELDS[idx];
+                if lookup(&written, field.section, field.key) != Some(&self.values[idx]) {
+                    bail!("could not update {} in {}", field.name(), path.display());
+                }
+            }
+            toml::from_str::<Config>(&content)?;
+            Ok(content)
+        })?;
+        let saved = load_config(Some(&path.to_path_buf()))?;
+        *self = Self::from_config(&saved);
+        Ok(saved)
+    }
+}
+
+/// Each field's current value in `config`.
+fn field_values(config: &Config) -> Vec<toml::Value> {
+    let table = toml::Value::try_from(config).ok();
+    FIELDS
+        .iter()
+        .map(|field| {
+            table
+                .as_ref()
+                .and_then(|t| lookup(t, field.section, field.key))
+                .cloned()
+                .unwrap_or_else(|| toml::Value::String(String::new()))
+        })
+        .collect()
+}
+
+/// Arrays as `a, b` with the home directory shown as `~`; the rest as TOML.
+fn display_value(value: &toml::Value) -> String {
+    let toml::Value::Array(items) = value else {
+        return value.to_string();
+    };
+    let home = dirs::home_dir().map(|h| h.to_string_lossy().into_owned());
+    items
+        .iter()
+        .filter_map(|item| item.as_str())
+        .map(|item| match home.as_deref() {
+            Some(home) if !home.is_empty() && item.starts_with(home) => {
+                format!("~{}", &item[home.len()..])
+            }
+            _ => item.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `content` with `key = literal` in `[section]` (`""` for the top level).
+/// An existing assignment, multi-line arrays included, is replaced in place;
+/// otherwise the key is added at the start of its table, which is appended
+/// if missing. Comments and all other lines are kept as they are.
+fn set_key(content: &str, section: &str, key: &str, literal: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let assignment = format!("{} = {}", key, literal);
+    let mut current = String::new();
+    let mut section_header = None;
+    let mut first_header = None;
+    let mut idx = 0;
+    while idx < lines.len() {
+        let trimmed = lines[idx].trim();
+        if let Some(name) = table_header(trimmed) {
+            first_header.get_or_insert(idx);
+            if name == section {
+                section_header = Some(idx);
+            }
+            current = name;
+            idx += 1;
+            continue;
+        }
+        let end = value_end(&lines, idx);
+        if current == section && assigns(trimmed, key) {
+            let mut out: Vec<&str> = lines[..idx].to_vec();
+            out.push(&assignment);
+            out.extend(&lines[end + 1..]);
+            return join_lines(&out);
+        }
+        idx = end + 1;
+    }
+
+    let mut out = lines.clone();
+    let header = format!("[{}]", section);
+    match (section.is_empty(), section_header, first_header) {
+        (true, _, Some(first)) => {
+            // After the last top-level line, above the first table and the
+            // comments that introduce it.
+            let mut at = first;
+            while at > 0 && lines[at - 1].trim().starts_with('#') {
+                at -= 1;
+            }
+            while at > 0 && lines[at - 1].trim().is_empty() {
+                at -= 1;
+            }
+            if at == 0 {
+                out.splice(0..0, [assignment.as_str(), ""]);
+            } else {
+                out.insert(at, &assignment);
+            }
+        }
+        (true, _, None) => out.push(&assignment),
+        (false, Some(at), _) => out.insert(at + 1, &assignment),
+        (false, None, _) => {
+            if !out.is_empty() {
+                out.push("");
+            }
+            out.extend([header.as_str(), assignment.as_str()]);
+        }
+    }
+    join_lines(&out)
+}
+
+fn join_lines(lines: &[&str]) -> String {
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+/// `name` for a `[name]` or `[[name]]` header line.
+fn table_header(trimmed: &str) -> Option<String> {
+    let inner = trimmed.strip_prefix('[')?;
+    let inner = inner.strip_prefix('[').unwrap_or(inner);
+    let name = &inner[..inner.find(']')?];
+    Some(name.trim().trim_matches('"').to_string())
+}
+
+fn assigns(trimmed: &str, key: &str) -> bool {
+    trimmed
+        .strip_prefix(key)
+        .is_some_and(|rest| rest.trim_start().starts_with('='))
+}
+
+/// Last line of the value starting on line `start`: where the brackets and
+/// braces it opens are closed again.
+fn value_end(lines: &[&str], start: usize) -> usize {
+    let mut depth = 0;
+    for (idx, line) in lines.iter().enumerate().skip(start) {
+        depth += bracket_depth(line);
+        if depth <= 0 {
+            return idx;
+        }
+    }
+    lines.len().saturating_sub(1)
+}
+
+/// Net `[`/`{` opened on a line, ignoring strings and comments.
+fn bracket_depth(line: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in line.chars() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '#' => break,
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_key_keeps_comments_and_replaces_multi_line_arrays() {
+        let content = "# AgentPulse configuration\n\
+                       watch_directories = [\n  \"~/src\", # main\n  \"~/work\",\n]\n\
+                       refresh_interval_secs = 10 # seconds\n\n\
+                       # Counter limits.\n[thresholds]\n# dirty_file_warn = 1\n";
+        let out = set_key(content, "", "watch_directories", "[\"~/code\"]");
+        let out = set_key(&out, "thresholds", "dirty_file_warn", "5");
+        let out = set_key(&out, "", "watch_mode", "true");
+        let out = set_key(&out, "fsck", "enabled", "true");
+        assert_eq!(
+            out,
+            "# AgentPulse configuration\n\
+             watch_directories = [\"~/code\"]\n\
+             refresh_interval_secs = 10 # seconds\n\
+             watch_mode = true\n\n\
+             # Counter limits.\n[thresholds]\ndirty_file_warn = 5\n# dirty_file_warn = 1\n\n\
+             [fsck]\nenabled = true\n"
+        );
+        let parsed: toml::Value = toml::from_str(&out).unwrap();
+        assert_eq!(
+            parsed["thresholds"]["dirty_file_warn"].as_integer(),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn inputs_are_validated_per_field() {
+        let mut form = SettingsForm::from_config(&Config::default());
+        let field = |key: &str| FIELDS.iter().position(|f| f.key == key).unwrap();
+
+        let interval = field("refresh_interval_secs");
+        form.input = Some("0".to_string());
+        assert!(form
+            .commit_input(interval)
+            .unwrap_err()
+            .contains("between 1"));
+        form.input = Some("ten".to_string());
+        assert!(form.commit_input(interval).is_err());
+        form.input = Some(" 30 ".to_string());
+        form.commit_input(interval).unwrap();
+        assert_eq!(form.display(interval), "30");
+
+        let dirs = field("watch_directories");
+        form.input = Some(" , ".to_string());
+        assert!(form.commit_input(dirs).is_err());
+        form.activate(dirs);
+        form.commit_input(dirs).unwrap();
+        assert!(!form.is_modified(dirs));
+
+        form.activate(field("watch_mode"));
+        assert_eq!(form.modified_count(), 2);
+        form.activate(field("watch_mode"));
+        assert_eq!(form.modified_count(), 1);
+    }
+
+    #[test]
+    fn save_writes_edited_keys_and_refuses_conflicts() {
+        let dir = std::env::temp_dir().join("agentpulse_settings_save");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "# mine\nrefresh_interval_secs = 10\n").unwrap();
+        let config = load_config(Some(&path)).unwrap();
+        let field = |key: &str| FIELDS.iter().position(|f| f.key == key).unwrap();
+
+        let mut form = SettingsForm::from_config(&config);
+        form.input = Some("20".to_string());
+        form.commit_input(field("refresh_interval_secs")).unwrap();
+        form.activate(field("untracked_actionable"));
+        let saved = form.save(&path).unwrap();
+        assert_eq!(saved.refresh_interval_secs, 20);
+        assert!(!saved.thresholds.untracked_actionable);
+        assert_eq!(form.modified_count(), 0);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# mine\nrefresh_interval_secs = 20\n\n[thresholds]\nuntracked_actionable = false\n"
+        );
+
+        // Someone else changes the same key: nothing is written.
+        let mut stale = SettingsForm::from_config(&config);
+        stale.input = Some("30".to_string());
+        stale.commit_input(field("refresh_interval_secs")).unwrap();
+        let err = stale.save(&path).unwrap_err().to_string();
+        assert!(err.contains("changed on disk"), "{}", err);
+        assert_eq!(load_config(Some(&path)).unwrap().refresh_interval_secs, 20);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
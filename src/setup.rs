@@ -1,4 +1,6 @@
-use crate::config::{default_config_path, default_directories, Config};
+use crate::config::{default_config_path, default_directories, write_config_atomic, Config};
+use crate::i18n::{t, tr};
+use crate::ui::theme::glyph;
 use anyhow::Result;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
@@ -12,11 +14,11 @@ pub fn run_setup(existing: Option<&Config>, config_path: Option<&PathBuf>) -> Re
     let home = dirs::home_dir().unwrap_or_default();
 
     println!();
-    println!("  ╔══════════════════════════════════════╗");
-    println!("  ║      AgentPulse — Setup Wizard       ║");
-    println!("  ╚══════════════════════════════════════╝");
+    println!("  {}", glyph("╔══════════════════════════════════════╗"));
+    println!("  {}", glyph(&format!("║{:^38}║", t("setup-title"))));
+    println!("  {}", glyph("╚══════════════════════════════════════╝"));
     println!();
-    println!("  AgentPulse will scan directories you choose for git repos.");
+    println!("  {}", t("setup-intro"));
     println!();
 
     // Gather suggestions: standard dirs that actually exist on disk
@@ -33,27 +35,29 @@ pub fn run_setup(existing: Option<&Config>, config_path: Option<&PathBuf>) -> Re
 
     // ── show current dirs if reconfiguring ──────────────────────────────────
     if !chosen.is_empty() {
-        println!("  Current watch directories:");
+        println!("  {}", t("setup-current"));
+        let not_found = t("setup-not-found");
         for (i, dir) in chosen.iter().enumerate() {
-            let status = if dir.exists() { "✓" } else { "✗ not found" };
-            println!("    [{}] {}  ({})", i + 1, dir.display(), status);
+            let status = if dir.exists() { "✓" } else { &not_found };
+            println!("    [{}] {}  ({})", i + 1, dir.display(), glyph(status));
         }
         println!();
-        println!("  Press Enter to keep these, or type new paths to replace them.");
+        println!("  {}", t("setup-keep-hint"));
         println!();
     }
 
     // ── show suggestions ────────────────────────────────────────────────────
     if !suggestions.is_empty() {
-        println!("  Detected directories on this machine:");
+        println!("  {}", t("setup-detected"));
         for (i, dir) in suggestions.iter().enumerate() {
             println!("    [{}] {}", i + 1, dir.display());
         }
         println!();
-        println!("  Enter a number to add a suggestion, a full path, or leave");
-        println!("  blank to finish. Supports ~ and $HOME.");
+        for line in t("setup-input-hint").lines() {
+            println!("  {}", line);
+        }
     } else {
-        println!("  No standard directories detected. Enter full paths below.");
+        println!("  {}", t("setup-none-detected"));
     }
 
     println!();
@@ -79,11 +83,11 @@ pub fn run_setup(existing: Option<&Config>, config_path: Option<&PathBuf>) -> Re
         if let Ok(n) = trimmed.parse::<usize>() {
             if n >= 1 && n <= suggestions.len() {
                 let path = suggestions[n - 1].clone();
-                println!("    Added: {}", path.display());
+                println!("    {}", tr("setup-added", &[("path", &path.display())]));
                 new_dirs.push(path);
                 continue;
             } else {
-                println!("    No suggestion [{}] — try again or enter a path", n);
+                println!("    {}", tr("setup-no-suggestion", &[("number", &n)]));
                 continue;
             }
         }
@@ -92,11 +96,11 @@ pub fn run_setup(existing: Option<&Config>, config_path: Option<&PathBuf>) -> Re
         let path = expand_home(trimmed, &home);
         if !path.exists() {
             println!(
-                "    Warning: {} does not exist yet — added anyway",
-                path.display()
+                "    {}",
+                tr("setup-missing-path", &[("path", &path.display())])
             );
         } else {
-            println!("    Added: {}", path.display());
+            println!("    {}", tr("setup-added", &[("path", &path.display())]));
         }
         new_dirs.push(path);
     }
@@ -105,11 +109,11 @@ pub fn run_setup(existing: Option<&Config>, config_path: Option<&PathBuf>) -> Re
     let final_dirs = if new_dirs.is_empty() {
         if !chosen.is_empty() {
             // User pressed Enter immediately → keep existing
-            println!("  Keeping current directories.");
+            println!("  {}", t("setup-keeping"));
             chosen
         } else {
             // Truly nothing entered → fall back to compile-time defaults
-            println!("  Nothing entered — using default directories.");
+            println!("  {}", t("setup-defaults"));
             default_directories()
         }
     } else {
@@ -137,8 +141,8 @@ pub fn run_setup(existing: Option<&Config>, config_path: Option<&PathBuf>) -> Re
     save_config(&config, &path)?;
 
     println!();
-    println!("  Saved to {}", path.display());
-    println!("  Tip: run `agentpulse --setup` anytime to change these.");
+    println!("  {}", tr("setup-saved", &[("path", &path.display())]));
+    println!("  {}", t("setup-tip"));
     println!();
 
     Ok(config)
@@ -161,10 +165,6 @@ fn expand_home(s: &str, home: &Path) -> PathBuf {
 
 /// Serialise `Config` to the provided path with a comment header.
 pub fn save_config(config: &Config, path: &Path) -> Result<()> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-
     // Serialise to TOML, then prepend a comment block
     let body = toml::to_string_pretty(config)?;
     let content = format!(
@@ -175,6 +175,5 @@ pub fn save_config(config: &Config, path: &Path) -> Result<()> {
         body
     );
 
-    std::fs::write(path, content)?;
-    Ok(())
+    write_config_atomic(path, &content)
 }
@@ -0,0 +1,167 @@
+//! Persisted UI session state (last section, selection, filter, toggles).
+//!
+//! Saved to `~/.config/agentpulse/state.json` when the TUI exits and restored
+//! on the next launch. Failures are never fatal — a missing or corrupt file
+//! simply means starting fresh on Home.
+
+use crate::dashboard::DashboardSection;
+use crate::recent::RecentRepos;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    #[serde(default = "default_section")]
+    pub section: DashboardSection,
+    /// Row index within `section` (used for non-repo sections).
+    #[serde(default)]
+    pub selected: usize,
+    /// Path of the selected repo, so the selection survives reordering.
+    #[serde(default)]
+    pub selected_repo: Option<PathBuf>,
+    /// Identity of the selected repo, used when it has moved since last launch.
+    #[serde(default)]
+    pub selected_repo_id: Option<String>,
+    #[serde(default)]
+    pub filter_text: String,
+    #[serde(default)]
+    pub group_by_dir: bool,
+    #[serde(default)]
+    pub agent_focus_mode: bool,
+    /// Repos saved with `m1`..`m9`, keyed by profile (the config file in
+    /// use), then slot.
+    #[serde(default)]
+    pub profile_bookmarks: BTreeMap<PathBuf, BTreeMap<u8, Bookmark>>,
+    /// The first-launch tour was finished or skipped. State files written
+    /// before the tour existed count as seen.
+    #[serde(default = "default_tour_seen")]
+    pub tour_seen: bool,
+    /// Repo usage for the `Ctrl-P` quick switcher.
+    #[serde(default)]
+    pub recent: RecentRepos,
+}
+
+/// A bookmarked repo; `identity` finds it again after a move.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub identity: Option<String>,
+}
+
+impl Default for SessionState {
+    fn default() -> Self {
+        Self {
+            section: default_section(),
+            selected: 0,
+            selected_repo: None,
+            selected_repo_id: None,
+            filter_text: String::new(),
+            group_by_dir: false,
+            agent_focus_mode: false,
+            profile_bookmarks: BTreeMap::new(),
+            tour_seen: false,
+            recent: RecentRepos::default(),
+        }
+    }
+}
+
+fn default_section() -> DashboardSection {
+    DashboardSection::Home
+}
+
+fn default_tour_seen() -> bool {
+    true
+}
+
+/// Session state location: `~/.config/agentpulse/state.json`.
+pub fn state_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".config")
+        .join("agentpulse")
+        .join("state.json")
+}
+
+/// Load session state, falling back to defaults if the file is missing or unreadable.
+pub fn load_session(path: &Path) -> SessionState {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_session(path: &Path, state: &SessionState) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_disk() {
+        let dir = std::env::temp_dir().join("agentpulse_test_state_roundtrip");
+        let path = dir.join("state.json");
+        let state = SessionState {
+            section: DashboardSection::Repos,
+            selected: 3,
+            selected_repo: Some(PathBuf::from("/tmp/some-repo")),
+            selected_repo_id: Some("root:abc123".to_string()),
+            filter_text: "api".to_string(),
+            group_by_dir: true,
+            agent_focus_mode: true,
+            profile_bookmarks: BTreeMap::from([(
+                PathBuf::from("/tmp/work.toml"),
+                BTreeMap::from([(
+                    2,
+                    Bookmark {
+                        path: PathBuf::from("/tmp/other-repo"),
+                        identity: None,
+                    },
+                )]),
+            )]),
+            tour_seen: true,
+            recent: {
+                let mut recent = RecentRepos::default();
+                recent.record(&crate::git::Repo::new(PathBuf::from("/tmp/some-repo")), 100);
+                recent
+            },
+        };
+        save_session(&path, &state).unwrap();
+        assert_eq!(load_session(&path), state);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_or_corrupt_file_yields_default() {
+        let dir = std::env::temp_dir().join("agentpulse_test_state_corrupt");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+        assert_eq!(load_session(&path), SessionState::default());
+        std::fs::write(&path, "{not json").unwrap();
+        assert_eq!(load_session(&path), SessionState::default());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn partial_file_fills_defaults() {
+        let dir = std::env::temp_dir().join("agentpulse_test_state_partial");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+        std::fs::write(&path, r#"{"section":"AiCosts"}"#).unwrap();
+        let state = load_session(&path);
+        assert_eq!(state.section, DashboardSection::AiCosts);
+        assert!(state.filter_text.is_empty());
+        // Written before the tour existed: don't show it to existing users.
+        assert!(state.tour_seen);
+        assert!(!SessionState::default().tour_seen);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
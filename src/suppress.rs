@@ -0,0 +1,223 @@
+//! Dismissed recommendations that stay quiet until the repo's state changes.
+//!
+//! A dismissal records the repo, the recommended action, and a fingerprint of
+//! the status that produced it. Any change to that status (new edits, new
+//! commits, a different branch) voids the dismissal and the suggestion returns.
+
+use crate::agent::Recommendation;
+use crate::git::Repo;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Suppressions {
+    #[serde(default)]
+    entries: Vec<Dismissal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Dismissal {
+    /// Canonical repo path. Older files stored the identity here instead.
+    repo: String,
+    /// Root commit or origin URL, only used to follow a repo that moved.
+    #[serde(default)]
+    identity: Option<String>,
+    short_action: String,
+    fingerprint: String,
+}
+
+/// Suppression file location: `~/.config/agentpulse/dismissed.json`.
+pub fn suppressions_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".config")
+        .join("agentpulse")
+        .join("dismissed.json")
+}
+
+/// Per-checkout key: the canonical path. Sibling clones and worktrees share
+/// an identity, so it can't tell them apart.
+fn repo_key(repo: &Repo) -> String {
+    std::fs::canonicalize(&repo.path)
+        .unwrap_or_else(|_| repo.path.clone())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Whether `key` is an identity written by an older version.
+fn is_legacy_identity(key: &str) -> bool {
+    key.starts_with("root:") || key.starts_with("remote:")
+}
+
+/// Status fields that drive recommendations; any change re-surfaces the suggestion.
+fn fingerprint(repo: &Repo) -> String {
+    let s = &repo.status;
+    format!(
+        "{}|{}|{}|{}|{}|{}|{}",
+        s.branch,
+        s.uncommitted_count,
+        s.unpushed_count,
+        s.behind_count,
+        s.stash_count,
+        s.has_remote,
+        s.is_detached
+    )
+}
+
+impl Suppressions {
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn is_dismissed(&self, repo: &Repo, rec: &Recommendation) -> bool {
+        let key = repo_key(repo);
+        let print = fingerprint(repo);
+        self.entries
+            .iter()
+            .any(|d| d.repo == key && d.short_action == rec.short_action && d.fingerprint == print)
+    }
+
+    pub fn dismiss(&mut self, repo: &Repo, rec: &Recommendation) {
+        let key = repo_key(repo);
+        self.entries.retain(|d| d.repo != key);
+        self.entries.push(Dismissal {
+            repo: key,
+            identity: repo.identity.clone(),
+            short_action: rec.short_action.to_string(),
+            fingerprint: fingerprint(repo),
+        });
+    }
+
+    /// Remove any dismissal for `repo`. Returns true if one existed.
+    pub fn restore(&mut self, repo: &Repo) -> bool {
+        let key = repo_key(repo);
+        let before = self.entries.len();
+        self.entries.retain(|d| d.repo != key);
+        before != self.entries.len()
+    }
+
+    /// Drop dismissals voided by a state change. Repos missing from this scan
+    /// are kept (they may live on an unmounted drive), unless their path is
+    /// gone and the one scanned repo with their identity takes them over.
+    /// Returns true if changed.
+    pub fn prune(&mut self, repos: &[Repo]) -> bool {
+        let before = self.entries.clone();
+        let keyed: Vec<(String, &Repo)> = repos.iter().map(|r| (repo_key(r), r)).collect();
+        for d in &mut self.entries {
+            if keyed.iter().any(|(key, _)| *key == d.repo) || Path::new(&d.repo).exists() {
+                continue;
+            }
+            let identity = d
+                .identity
+                .clone()
+                .or_else(|| is_legacy_identity(&d.repo).then(|| d.repo.clone()));
+            let mut matches = keyed
+                .iter()
+                .filter(|(_, r)| identity.is_some() && r.identity == identity);
+            if let (Some((key, repo)), None) = (matches.next(), matches.next()) {
+                d.repo = key.clone();
+                d.identity = repo.identity.clone();
+            }
+        }
+        let mut seen = std::collections::HashSet::new();
+        self.entries.retain(|d| {
+            seen.insert(d.repo.clone())
+                && keyed
+                    .iter()
+                    .find(|(key, _)| *key == d.repo)
+                    .is_none_or(|(_, r)| fingerprint(r) == d.fingerprint)
+        });
+        before != self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::recommend;
+
+    fn dirty_repo(files: usize) -> Repo {
+        let mut repo = Repo::new(PathBuf::from("/tmp/experiment"));
+        repo.status.branch = "main".to_string();
+        repo.status.has_remote = true;
+        repo.status.uncommitted_count = files;
+        repo
+    }
+
+    #[test]
+    fn dismissal_holds_while_state_is_unchanged() {
+        let repo = dirty_repo(3);
+        let rec = recommend(&repo);
+        let mut supp = Suppressions::default();
+        supp.dismiss(&repo, &rec);
+        assert!(supp.is_dismissed(&repo, &rec));
+        assert!(!supp.prune(std::slice::from_ref(&repo)));
+    }
+
+    #[test]
+    fn state_change_resurfaces_and_prunes() {
+        let repo = dirty_repo(3);
+        let mut supp = Suppressions::default();
+        supp.dismiss(&repo, &recommend(&repo));
+
+        let changed = dirty_repo(4);
+        assert!(!supp.is_dismissed(&changed, &recommend(&changed)));
+        assert!(supp.prune(&[changed]));
+        assert_eq!(supp, Suppressions::default());
+    }
+
+    #[test]
+    fn sibling_clones_keep_separate_dismissals() {
+        let mut repo = dirty_repo(1);
+        repo.identity = Some("root:abc".to_string());
+        let mut supp = Suppressions::default();
+        supp.dismiss(&repo, &recommend(&repo));
+
+        let mut sibling = dirty_repo(1);
+        sibling.path = PathBuf::from("/tmp/oss/experiment");
+        sibling.identity = repo.identity.clone();
+        assert!(!supp.is_dismissed(&sibling, &recommend(&sibling)));
+        assert!(!supp.restore(&sibling));
+        assert!(supp.is_dismissed(&repo, &recommend(&repo)));
+    }
+
+    #[test]
+    fn moved_repo_takes_its_dismissal_along() {
+        let mut repo = dirty_repo(1);
+        repo.path = PathBuf::from("/nonexistent/agentpulse/old");
+        repo.identity = Some("root:abc".to_string());
+        let mut supp = Suppressions::default();
+        supp.dismiss(&repo, &recommend(&repo));
+
+        repo.path = PathBuf::from("/nonexistent/agentpulse/new");
+        assert!(!supp.is_dismissed(&repo, &recommend(&repo)));
+        assert!(supp.prune(std::slice::from_ref(&repo)));
+        assert!(supp.is_dismissed(&repo, &recommend(&repo)));
+    }
+
+    #[test]
+    fn legacy_identity_entries_migrate_to_paths() {
+        let mut repo = dirty_repo(1);
+        repo.identity = Some("root:abc".to_string());
+        let mut supp: Suppressions = serde_json::from_str(&format!(
+            r#"{{"entries":[{{"repo":"root:abc","short_action":"{}","fingerprint":"{}"}}]}}"#,
+            recommend(&repo).short_action,
+            fingerprint(&repo)
+        ))
+        .unwrap();
+        assert!(supp.prune(std::slice::from_ref(&repo)));
+        assert!(supp.is_dismissed(&repo, &recommend(&repo)));
+    }
+}
@@ -1,5 +1,6 @@
-use super::theme;
+use super::{centered_rect, theme};
 use crate::app::App;
+use crate::i18n::{t, tr};
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -13,18 +14,21 @@ pub fn render(frame: &mut Frame, app: &App) {
         return;
     };
 
-    let area = centered_rect(88, 16, frame.area());
+    let area = dialog_area(frame.area());
     let risk = action.action.risk_level();
     let risk_color = match risk {
-        "high" => theme::ACCENT_RED,
-        "medium" => theme::ACCENT_YELLOW,
+        "high" => theme::palette().critical,
+        "medium" => theme::palette().warn,
         _ => theme::ACCENT_CYAN,
     };
 
     let mut lines = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("  Action: ", Style::default().fg(theme::FG_DIMMED)),
+            Span::styled(
+                format!("  {:<8}", t("confirm-action")),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
             Span::styled(
                 action.label.clone(),
                 Style::default()
@@ -33,7 +37,10 @@ pub fn render(frame: &mut Frame, app: &App) {
             ),
         ]),
         Line::from(vec![
-            Span::styled("  Risk:   ", Style::default().fg(theme::FG_DIMMED)),
+            Span::styled(
+                format!("  {:<8}", t("confirm-risk")),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
             Span::styled(
                 risk.to_uppercase(),
                 Style::default().fg(risk_color).add_modifier(Modifier::BOLD),
@@ -41,7 +48,7 @@ pub fn render(frame: &mut Frame, app: &App) {
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
-            "  Command preview:",
+            format!("  {}", t("confirm-preview")),
             Style::default().fg(theme::FG_DIMMED),
         )]),
         Line::from(vec![Span::styled(
@@ -58,17 +65,23 @@ pub fn render(frame: &mut Frame, app: &App) {
             Span::styled(
                 "  Enter / y",
                 Style::default()
-                    .fg(theme::ACCENT_GREEN)
+                    .fg(theme::palette().ok)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" run once   ", Style::default().fg(theme::FG_DIMMED)),
+            Span::styled(
+                format!(" {}   ", t("hint-run-once")),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
             Span::styled(
                 "Esc / n",
                 Style::default()
                     .fg(theme::ACCENT_YELLOW)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" cancel", Style::default().fg(theme::FG_DIMMED)),
+            Span::styled(
+                format!(" {}", t("hint-cancel")),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
         ]),
     ];
 
@@ -76,23 +89,47 @@ pub fn render(frame: &mut Frame, app: &App) {
         lines.insert(
             1,
             Line::from(vec![Span::styled(
-                "  Destructive action: review command before running.",
+                format!("  {}", t("confirm-destructive")),
                 Style::default()
-                    .fg(theme::ACCENT_RED)
+                    .fg(theme::palette().critical)
                     .add_modifier(Modifier::BOLD),
             )]),
         );
     }
 
+    if let Some(branch) = app.protected_target(&action.action) {
+        let (text, color) = if app.protected_confirmed.is_some() {
+            (
+                format!(
+                    "  {}",
+                    tr("confirm-protected-again", &[("branch", &branch)])
+                ),
+                theme::palette().critical,
+            )
+        } else {
+            (
+                format!("  {}", tr("confirm-protected", &[("branch", &branch)])),
+                theme::palette().warn,
+            )
+        };
+        lines.insert(
+            1,
+            Line::from(vec![Span::styled(
+                text,
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )]),
+        );
+    }
+
     frame.render_widget(Clear, area);
     frame.render_widget(
         Paragraph::new(Text::from(lines))
             .wrap(Wrap { trim: false })
             .block(
                 Block::bordered()
-                    .border_type(BorderType::Rounded)
+                    .border_set(theme::border(BorderType::Rounded))
                     .border_style(Style::default().fg(theme::BORDER_FOCUSED))
-                    .title(" Confirm Action ")
+                    .title(format!(" {} ", t("confirm-title")))
                     .title_style(
                         Style::default()
                             .fg(theme::ACCENT_BLUE)
@@ -104,15 +141,7 @@ pub fn render(frame: &mut Frame, app: &App) {
     );
 }
 
-fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
-    let w = width.min(area.width);
-    let h = height.min(area.height);
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    Rect {
-        x,
-        y,
-        width: w,
-        height: h,
-    }
+/// Where the dialog is drawn within `area`.
+pub fn dialog_area(area: Rect) -> Rect {
+    centered_rect(88, 16, area)
 }
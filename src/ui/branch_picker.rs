@@ -0,0 +1,137 @@
+use super::{centered_rect, theme};
+use crate::app::App;
+use crate::format;
+use crate::i18n::{t, tr};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(picker) = app.branch_picker.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(80, 22, frame.area());
+    // Borders, header, blank lines, guard/new-branch lines, and key hints.
+    let visible = area.height.saturating_sub(9).max(1) as usize;
+    let start = picker.cursor.saturating_sub(visible.saturating_sub(1));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!(
+                "  {}",
+                tr("branches-header", &[("repo", &picker.repo_name)])
+            ),
+            Style::default().fg(theme::FG_SECONDARY),
+        )]),
+        Line::from(""),
+    ];
+
+    for (idx, branch) in picker.branches.iter().enumerate().skip(start).take(visible) {
+        let is_cursor = idx == picker.cursor && !picker.creating;
+        let name_style = if is_cursor {
+            Style::default()
+                .fg(theme::FG_PRIMARY)
+                .bg(theme::BG_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme::FG_PRIMARY)
+        };
+        let sync = match &branch.upstream {
+            None => t("branches-no-upstream"),
+            Some(_) if branch.ahead == 0 && branch.behind == 0 => t("branches-in-sync"),
+            Some(_) => format!(
+                "{}{} {}{}",
+                theme::glyph("↑"),
+                branch.ahead,
+                theme::glyph("↓"),
+                branch.behind
+            ),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                if branch.is_current { "  * " } else { "    " },
+                Style::default().fg(theme::palette().label),
+            ),
+            Span::styled(format::pad(&branch.name, 36), name_style),
+            Span::styled(
+                format!(" {:<12}", sync),
+                Style::default().fg(if branch.ahead + branch.behind > 0 {
+                    theme::ACCENT_YELLOW
+                } else {
+                    theme::FG_DIMMED
+                }),
+            ),
+            Span::styled(
+                format::ago_epoch(branch.last_commit_epoch),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    if picker.dirty_count > 0 {
+        let (state, color) = if picker.autostash {
+            (t("branches-on"), theme::palette().ok)
+        } else {
+            (t("branches-off"), theme::palette().critical)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  {} ",
+                    tr("branches-dirty", &[("count", &picker.dirty_count)])
+                ),
+                Style::default().fg(theme::ACCENT_YELLOW),
+            ),
+            Span::styled(state, Style::default().fg(color)),
+        ]));
+    }
+    if picker.creating {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} ", t("branches-new")),
+                Style::default().fg(theme::palette().label),
+            ),
+            Span::styled(
+                picker.new_name.clone(),
+                Style::default().fg(theme::FG_PRIMARY),
+            ),
+            Span::styled(theme::glyph("▌"), Style::default().fg(theme::ACCENT_BLUE)),
+        ]));
+    }
+
+    let hints = if picker.creating {
+        t("branches-hint-create")
+    } else if picker.dirty_count > 0 {
+        t("branches-hint-autostash")
+    } else {
+        t("branches-hint")
+    };
+    lines.push(Line::from(vec![Span::styled(
+        format!("  {}", hints),
+        Style::default().fg(theme::FG_DIMMED),
+    )]));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::BORDER_FOCUSED))
+                    .title(format!(" {} ", t("branches-title")))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_BLUE)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        area,
+    );
+}
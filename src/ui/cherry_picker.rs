@@ -0,0 +1,140 @@
+use super::{centered_rect, theme};
+use crate::app::App;
+use crate::format;
+use crate::i18n::{t, tr};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(picker) = app.cherry_picker.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(84, 24, frame.area());
+    // Borders, header, blank lines, and key hints.
+    let visible = area.height.saturating_sub(7).max(1) as usize;
+    let cursor_style = Style::default()
+        .fg(theme::FG_PRIMARY)
+        .bg(theme::BG_HIGHLIGHT)
+        .add_modifier(Modifier::BOLD);
+    let marked = picker.marked.iter().filter(|m| **m).count();
+
+    let header = if picker.choosing_target {
+        format!(
+            "  {}",
+            tr(
+                "cherry-target-header",
+                &[("count", &marked), ("branch", &picker.current_branch)],
+            )
+        )
+    } else {
+        format!(
+            "  {}",
+            tr(
+                "cherry-header",
+                &[
+                    ("branch", &picker.current_branch),
+                    ("repo", &picker.repo_name),
+                    ("count", &marked),
+                ],
+            )
+        )
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            header,
+            Style::default().fg(theme::FG_SECONDARY),
+        )]),
+        Line::from(""),
+    ];
+
+    if picker.choosing_target {
+        let start = picker
+            .target_cursor
+            .saturating_sub(visible.saturating_sub(1));
+        for (idx, target) in picker.targets.iter().enumerate().skip(start).take(visible) {
+            let style = if idx == picker.target_cursor {
+                cursor_style
+            } else {
+                Style::default().fg(theme::FG_PRIMARY)
+            };
+            let place = match &target.worktree {
+                Some(path) => tr(
+                    "cherry-worktree",
+                    &[("path", &format::path(&app.config, path))],
+                ),
+                None => t("cherry-temp-worktree"),
+            };
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(format::pad(&target.branch, 36), style),
+                Span::styled(format!(" {}", place), Style::default().fg(theme::FG_DIMMED)),
+            ]));
+        }
+    } else {
+        let start = picker.cursor.saturating_sub(visible.saturating_sub(1));
+        for (idx, (commit, checked)) in picker
+            .commits
+            .iter()
+            .zip(&picker.marked)
+            .enumerate()
+            .skip(start)
+            .take(visible)
+        {
+            let style = if idx == picker.cursor {
+                cursor_style
+            } else {
+                Style::default().fg(theme::FG_PRIMARY)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {} ", if *checked { "[x]" } else { "[ ]" }),
+                    Style::default().fg(theme::palette().label),
+                ),
+                Span::styled(
+                    format!("{} ", commit.short_sha()),
+                    Style::default().fg(theme::ACCENT_YELLOW),
+                ),
+                Span::styled(format::pad(&commit.subject, 48), style),
+                Span::styled(
+                    format!(" {}", format::ago_epoch(commit.epoch)),
+                    Style::default().fg(theme::FG_DIMMED),
+                ),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    let hints = if picker.choosing_target {
+        t("cherry-hint-target")
+    } else {
+        t("cherry-hint")
+    };
+    lines.push(Line::from(vec![Span::styled(
+        hints,
+        Style::default().fg(theme::FG_DIMMED),
+    )]));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::BORDER_FOCUSED))
+                    .title(format!(" {} ", t("cherry-title")))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_BLUE)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        area,
+    );
+}
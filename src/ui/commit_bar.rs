@@ -1,5 +1,6 @@
 use super::theme;
 use crate::app::App;
+use crate::i18n::t;
 use ratatui::{
     layout::Rect,
     style::Style,
@@ -10,11 +11,14 @@ use ratatui::{
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let line = Line::from(vec![
-        Span::styled(" Commit: ", Style::default().fg(theme::ACCENT_GREEN)),
+        Span::styled(
+            format!(" {} ", t("commit-bar-label")),
+            Style::default().fg(theme::palette().label),
+        ),
         Span::styled(&app.commit_message, Style::default().fg(theme::FG_PRIMARY)),
-        Span::styled("▌", Style::default().fg(theme::ACCENT_BLUE)),
+        Span::styled(theme::glyph("▌"), Style::default().fg(theme::ACCENT_BLUE)),
         Span::styled(
-            "  Enter to confirm · Esc to cancel",
+            format!("  {}", t("commit-bar-hint")),
             Style::default().fg(theme::FG_DIMMED),
         ),
     ]);
@@ -0,0 +1,107 @@
+use super::{centered_rect, theme};
+use crate::app::App;
+use crate::format;
+use crate::i18n::{t, tr};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(log) = app.commit_log.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(90, 24, frame.area());
+    // Borders, header, blank lines and key hints.
+    let visible = area.height.saturating_sub(7).max(1) as usize;
+    let start = log.cursor.saturating_sub(visible.saturating_sub(1));
+    let subject_cells = area.width.saturating_sub(44).max(10) as usize;
+
+    let unpushed = log.unpushed_count();
+    let mut header = vec![Span::styled(
+        format!(
+            "  {}",
+            tr(
+                "commit-log-header",
+                &[("repo", &log.repo_name), ("branch", &log.branch)],
+            )
+        ),
+        Style::default().fg(theme::FG_SECONDARY),
+    )];
+    if unpushed > 0 {
+        header.push(Span::styled(
+            format!(
+                "{}{}",
+                theme::glyph(" · "),
+                tr("commit-log-unpushed", &[("count", &unpushed)])
+            ),
+            Style::default().fg(theme::ACCENT_YELLOW),
+        ));
+    }
+    let mut lines = vec![Line::from(""), Line::from(header), Line::from("")];
+
+    for (idx, entry) in log.entries.iter().enumerate().skip(start).take(visible) {
+        let subject_style = if idx == log.cursor {
+            Style::default()
+                .fg(theme::FG_PRIMARY)
+                .bg(theme::BG_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme::FG_PRIMARY)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                theme::glyph(if entry.unpushed { "  ↑ " } else { "    " }),
+                Style::default().fg(theme::ACCENT_YELLOW),
+            ),
+            Span::styled(
+                format!("{} ", entry.commit.short_sha()),
+                Style::default().fg(theme::ACCENT_BLUE),
+            ),
+            Span::styled(
+                format::pad(&entry.commit.subject, subject_cells),
+                subject_style,
+            ),
+            Span::styled(
+                format!(" {}", format::pad(&entry.commit.author, 14)),
+                Style::default().fg(theme::FG_SECONDARY),
+            ),
+            Span::styled(
+                format!(" {}", format::ago_epoch(entry.commit.epoch)),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let hints = if log.push.is_some() {
+        t("commit-log-hint-push")
+    } else {
+        t("commit-log-hint")
+    };
+    lines.push(Line::from(Span::styled(
+        format!("  {}", hints),
+        Style::default().fg(theme::FG_DIMMED),
+    )));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::BORDER_FOCUSED))
+                    .title(format!(" {} ", t("commit-log-title")))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_BLUE)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        area,
+    );
+}
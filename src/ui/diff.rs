@@ -0,0 +1,255 @@
+use super::{centered_rect, theme};
+use crate::app::App;
+use crate::i18n::{t, tr};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(view) = app.diff_view.as_ref() else {
+        return;
+    };
+
+    let full = frame.area();
+    let area = centered_rect(
+        full.width.saturating_sub(4.min(full.width / 5)),
+        full.height.saturating_sub(2.min(full.height / 5)),
+        full,
+    );
+    // Borders and the key-hint line.
+    let visible = area.height.saturating_sub(3).max(1) as usize;
+
+    let mut part = part_before(&view.lines, view.scroll);
+    let mut lines: Vec<Line> = Vec::with_capacity(visible + 1);
+    for (idx, line) in view
+        .lines
+        .iter()
+        .enumerate()
+        .skip(view.scroll)
+        .take(visible)
+    {
+        part = if idx < view.stat_lines {
+            Part::Stat
+        } else {
+            part.next(line)
+        };
+        lines.push(diff_line(line, part));
+    }
+    while lines.len() < visible {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(" {}", t("diff-hint")),
+            Style::default().fg(theme::FG_DIMMED),
+        ),
+        Span::styled(
+            format!(
+                "  {}/{}",
+                (view.scroll + 1).min(view.lines.len()),
+                view.lines.len()
+            ),
+            Style::default().fg(theme::FG_SECONDARY),
+        ),
+    ]));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::BORDER_FOCUSED))
+                    .title(format!(
+                        " {} ",
+                        tr(
+                            "diff-title",
+                            &[("repo", &view.repo_name), ("count", &view.files_changed)],
+                        )
+                    ))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_BLUE)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        area,
+    );
+}
+
+/// Which block of the diff view a line belongs to; the same prefix means
+/// different things in each.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Part {
+    /// The `--stat` summary at the top.
+    Stat,
+    /// Untracked paths and blank lines before the first file.
+    Other,
+    /// `diff --git` up to the first `@@`: `---`/`+++`, `index`, modes.
+    FileHeader,
+    /// Hunk lines, where `+`/`-` are content whatever follows them.
+    Hunk,
+}
+
+impl Part {
+    /// The part `line` belongs to, coming from `self`. Content lines always
+    /// start with ` `, `+`, `-` or `\`, so neither header can be mistaken.
+    fn next(self, line: &str) -> Self {
+        if line.starts_with("diff --git ") {
+            Part::FileHeader
+        } else if line.starts_with("@@") && matches!(self, Part::FileHeader | Part::Hunk) {
+            Part::Hunk
+        } else if self == Part::Stat {
+            Part::Other
+        } else {
+            self
+        }
+    }
+}
+
+/// The part the line before `idx` ends in, found from the last file or
+/// hunk header above it.
+fn part_before(lines: &[String], idx: usize) -> Part {
+    lines[..idx.min(lines.len())]
+        .iter()
+        .rev()
+        .find_map(|line| {
+            if line.starts_with("diff --git ") {
+                Some(Part::FileHeader)
+            } else if line.starts_with("@@") {
+                Some(Part::Hunk)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(Part::Other)
+}
+
+/// Colour one line by its diff prefix within its part, whatever the
+/// file's language.
+fn diff_line(line: &str, part: Part) -> Line<'static> {
+    let style = |color| Style::default().fg(color);
+    match part {
+        Part::Stat => {
+            if let Some(row) = stat_row(line) {
+                return row;
+            }
+        }
+        Part::FileHeader if line.starts_with("diff --git ") => {
+            return Line::from(Span::styled(
+                line.to_string(),
+                style(theme::ACCENT_YELLOW).add_modifier(Modifier::BOLD),
+            ));
+        }
+        Part::FileHeader => {
+            return Line::from(Span::styled(line.to_string(), style(theme::FG_DIMMED)));
+        }
+        Part::Hunk if line.starts_with("@@") => {
+            return Line::from(Span::styled(line.to_string(), style(theme::ACCENT_CYAN)));
+        }
+        Part::Hunk if line.starts_with('+') => {
+            return Line::from(Span::styled(
+                line.to_string(),
+                style(theme::palette().added),
+            ));
+        }
+        Part::Hunk if line.starts_with('-') => {
+            return Line::from(Span::styled(
+                line.to_string(),
+                style(theme::palette().removed),
+            ));
+        }
+        Part::Hunk | Part::Other => {}
+    }
+    Line::from(Span::styled(line.to_string(), style(theme::FG_SECONDARY)))
+}
+
+/// A `--stat` row with its +/- graph coloured after the bar; `None` for the
+/// closing `N files changed` line.
+fn stat_row(line: &str) -> Option<Line<'static>> {
+    let style = |color| Style::default().fg(color);
+    let (name, graph) = line.split_once(" | ")?;
+    let mut spans = vec![Span::styled(
+        format!("{} | ", name),
+        style(theme::FG_PRIMARY),
+    )];
+    let (count, bars) = graph.split_at(graph.find(['+', '-']).unwrap_or(graph.len()));
+    let added = bars.chars().take_while(|c| *c == '+').count();
+    spans.push(Span::styled(count.to_string(), style(theme::FG_SECONDARY)));
+    spans.push(Span::styled(
+        bars[..added].to_string(),
+        style(theme::palette().added),
+    ));
+    spans.push(Span::styled(
+        bars[added..].to_string(),
+        style(theme::palette().removed),
+    ));
+    Some(Line::from(spans))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(lines: &[&str], stat_lines: usize) -> Vec<Part> {
+        let mut part = Part::Other;
+        lines
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                part = if idx < stat_lines {
+                    Part::Stat
+                } else {
+                    part.next(line)
+                };
+                part
+            })
+            .collect()
+    }
+
+    #[test]
+    fn prefixes_are_read_within_their_part() {
+        let lines = [
+            " schema.sql | 2 +-",
+            " 1 file changed, 1 insertion(+), 1 deletion(-)",
+            "",
+            "diff --git a/schema.sql b/schema.sql",
+            "--- a/schema.sql",
+            "+++ b/schema.sql",
+            "@@ -1,2 +1,2 @@",
+            "-- old comment",
+            "+++ counter",
+            "     a | b => c",
+        ];
+        assert_eq!(
+            parts(&lines, 2),
+            [
+                Part::Stat,
+                Part::Stat,
+                Part::Other,
+                Part::FileHeader,
+                Part::FileHeader,
+                Part::FileHeader,
+                Part::Hunk,
+                Part::Hunk,
+                Part::Hunk,
+                Part::Hunk,
+            ]
+        );
+        let owned: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        assert_eq!(part_before(&owned, 8), Part::Hunk);
+        assert_eq!(part_before(&owned, 5), Part::FileHeader);
+        assert_eq!(part_before(&owned, 2), Part::Other);
+
+        // A removed SQL comment stays a removal, and a context line with a
+        // bar is not a stat row.
+        let removed = diff_line(lines[7], Part::Hunk);
+        assert_eq!(removed.spans[0].style.fg, Some(theme::palette().removed));
+        assert_eq!(diff_line(lines[9], Part::Hunk).spans.len(), 1);
+        assert_eq!(diff_line(lines[0], Part::Stat).spans.len(), 4);
+    }
+}
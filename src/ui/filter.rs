@@ -1,5 +1,6 @@
 use super::theme;
 use crate::app::App;
+use crate::i18n::t;
 use ratatui::{
     layout::Rect,
     style::Style,
@@ -12,7 +13,27 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let line = Line::from(vec![
         Span::styled(" / ", Style::default().fg(theme::ACCENT_CYAN)),
         Span::styled(&app.filter_text, Style::default().fg(theme::FG_PRIMARY)),
-        Span::styled("▌", Style::default().fg(theme::ACCENT_BLUE)),
+        Span::styled(theme::glyph("▌"), Style::default().fg(theme::ACCENT_BLUE)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(line).style(Style::default().bg(theme::BG_SECONDARY)),
+        area,
+    );
+}
+
+/// Input line for `G` (cross-repo search).
+pub fn render_grep(frame: &mut Frame, app: &App, area: Rect) {
+    let line = Line::from(vec![
+        Span::styled(
+            format!(" {} ", t("grep-label")),
+            Style::default().fg(theme::ACCENT_CYAN),
+        ),
+        Span::styled(&app.grep_input, Style::default().fg(theme::FG_PRIMARY)),
+        Span::styled(theme::glyph("▌"), Style::default().fg(theme::ACCENT_BLUE)),
+        Span::styled(
+            format!("  {}", t("grep-hint")),
+            Style::default().fg(theme::FG_DIMMED),
+        ),
     ]);
     frame.render_widget(
         Paragraph::new(line).style(Style::default().bg(theme::BG_SECONDARY)),
@@ -1,99 +1,45 @@
-use super::theme;
+use super::{centered_rect, theme};
 use crate::app::App;
+use crate::i18n::{t, tr};
+use crate::keymap::{self, Binding, Category};
 use ratatui::{
-    layout::Rect,
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, Clear, Paragraph},
     Frame,
 };
 
-pub fn render(frame: &mut Frame, _app: &App) {
-    let area = centered_rect(74, 30, frame.area());
+/// Key bindings for the current section first, then everything else by
+/// category; typing filters, arrows scroll.
+pub fn render(frame: &mut Frame, app: &App) {
+    let lines = help_lines(app);
+    let area = centered_rect(74, lines.len() as u16 + 5, frame.area());
+    let visible = area.height.saturating_sub(5) as usize;
+    let scroll = app.help_scroll.min(lines.len().saturating_sub(visible));
 
-    let categories: &[(&str, &[(&str, &str)])] = &[
-        (
-            "NAVIGATION",
-            &[
-                ("h/l Tab", "Switch section"),
-                ("1..8", "Jump to section"),
-                ("j / ↓", "Move down"),
-                ("k / ↑", "Move up"),
-            ],
-        ),
-        (
-            "ACTIONS",
-            &[
-                ("x", "Review selected action"),
-                ("Enter / y", "Confirm pending action"),
-                ("Esc / n", "Cancel pending action"),
-                ("r", "Force refresh"),
-                ("/", "Filter search"),
-                ("Enter (repos)", "Open in editor"),
-                ("o", "Open in file manager"),
-            ],
-        ),
-        (
-            "GIT",
-            &[
-                ("f", "Fetch"),
-                ("p", "Pull"),
-                ("P", "Push"),
-                ("c", "Commit tracked changes"),
-            ],
-        ),
-        (
-            "GENERAL",
-            &[
-                ("g", "Group by directory"),
-                ("A", "Actionable-only mode"),
-                ("s", "Setup watch dirs"),
-                ("?", "Toggle help"),
-                ("q", "Quit"),
-            ],
-        ),
-    ];
-
-    let mut lines: Vec<Line> = vec![Line::from(""), Line::from("")];
-
-    for (cat_name, shortcuts) in categories {
-        // Category header
-        lines.push(Line::from(Span::styled(
-            format!("  {}", cat_name),
-            Style::default()
-                .fg(theme::FG_DIMMED)
-                .add_modifier(Modifier::BOLD),
-        )));
-        lines.push(Line::from(""));
-
-        for (key, desc) in *shortcuts {
-            lines.push(Line::from(vec![
-                Span::styled(
-                    format!("    {:<14}", key),
-                    Style::default()
-                        .fg(theme::ACCENT_CYAN)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(*desc, Style::default().fg(theme::FG_PRIMARY)),
-            ]));
-        }
-
-        lines.push(Line::from(""));
-    }
-
-    lines.push(Line::from(Span::styled(
-        "  Press any key to close",
-        Style::default().fg(theme::FG_DIMMED),
-    )));
+    let dim = Style::default().fg(theme::FG_DIMMED);
+    let search = if app.help_query.is_empty() {
+        Line::from(Span::styled(format!("  {}", t("help-search-hint")), dim))
+    } else {
+        Line::from(vec![
+            Span::styled(
+                format!("  {}", tr("help-search", &[("query", &app.help_query)])),
+                Style::default().fg(theme::FG_PRIMARY),
+            ),
+            Span::styled(theme::glyph("▌"), Style::default().fg(theme::ACCENT_CYAN)),
+        ])
+    };
+    let mut shown = vec![Line::from(""), search, Line::from("")];
+    shown.extend(lines.into_iter().skip(scroll).take(visible));
 
     frame.render_widget(Clear, area);
     frame.render_widget(
-        Paragraph::new(lines)
+        Paragraph::new(shown)
             .block(
                 Block::bordered()
-                    .border_type(BorderType::Rounded)
+                    .border_set(theme::border(BorderType::Rounded))
                     .border_style(Style::default().fg(theme::BORDER_FOCUSED))
-                    .title(" Help ")
+                    .title(format!(" {} ", t("help-title")))
                     .title_style(
                         Style::default()
                             .fg(theme::ACCENT_BLUE)
@@ -105,15 +51,102 @@ pub fn render(frame: &mut Frame, _app: &App) {
     );
 }
 
-fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
-    let w = width.min(area.width);
-    let h = height.min(area.height);
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    Rect {
-        x,
-        y,
-        width: w,
-        height: h,
+/// Rows in the scrollable part, to bound scrolling.
+pub fn line_count(app: &App) -> usize {
+    help_lines(app).len()
+}
+
+/// The scrollable part of the overlay.
+fn help_lines(app: &App) -> Vec<Line<'static>> {
+    let bindings: Vec<Binding> = keymap::bindings()
+        .into_iter()
+        .filter(|b| b.matches(&app.help_query))
+        .collect();
+    let (here, elsewhere): (Vec<&Binding>, Vec<&Binding>) =
+        bindings.iter().partition(|b| b.applies_to(app.section));
+
+    let mut lines = Vec::new();
+    let section_specific: Vec<&Binding> = here
+        .iter()
+        .copied()
+        .filter(|b| !b.sections.is_empty())
+        .collect();
+    push_group(
+        &mut lines,
+        tr("help-cat-section", &[("section", &app.section.title())]),
+        &section_specific,
+        false,
+    );
+    for category in Category::all() {
+        let group: Vec<&Binding> = here
+            .iter()
+            .copied()
+            .filter(|b| b.sections.is_empty() && b.category == category)
+            .collect();
+        push_group(&mut lines, category.title(), &group, false);
+    }
+    push_group(&mut lines, t("help-cat-other"), &elsewhere, true);
+
+    if bindings.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", tr("help-no-match", &[("query", &app.help_query)])),
+            Style::default().fg(theme::FG_SECONDARY),
+        )));
+        lines.push(Line::from(""));
+    }
+    if app.help_query.is_empty() {
+        let legend = [
+            ("○ C", t("help-status-clean")),
+            ("◐ U", t("help-status-uncommitted")),
+            ("◆ P", t("help-status-unpushed")),
+            ("● D", t("help-status-dirty")),
+            ("◌ N", t("help-status-no-remote")),
+        ];
+        lines.push(category_header(t("help-cat-status")));
+        for (key, desc) in legend {
+            lines.push(key_line(key, desc, None));
+        }
+    }
+    lines
+}
+
+fn push_group(lines: &mut Vec<Line<'static>>, title: String, group: &[&Binding], other: bool) {
+    if group.is_empty() {
+        return;
+    }
+    lines.push(category_header(title));
+    for binding in group {
+        let only_in = other.then(|| {
+            let sections: Vec<&str> = binding.sections.iter().map(|s| s.title()).collect();
+            sections.join(", ")
+        });
+        lines.push(key_line(binding.keys, binding.description.clone(), only_in));
+    }
+    lines.push(Line::from(""));
+}
+
+fn category_header(title: String) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("  {}", title),
+        Style::default()
+            .fg(theme::FG_DIMMED)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+fn key_line(key: &str, desc: String, only_in: Option<String>) -> Line<'static> {
+    let key_style = Style::default()
+        .fg(theme::ACCENT_CYAN)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = vec![
+        Span::styled(format!("    {:<14}", theme::glyph(key)), key_style),
+        Span::styled(desc, Style::default().fg(theme::FG_PRIMARY)),
+    ];
+    if let Some(sections) = only_in {
+        spans.push(Span::styled(
+            format!("  ({})", sections),
+            Style::default().fg(theme::FG_DIMMED),
+        ));
     }
+    Line::from(spans)
 }
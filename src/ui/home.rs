@@ -1,59 +1,147 @@
 use super::{theme, widgets};
 use crate::app::App;
-use chrono::Local;
+use crate::collectors::activity;
+use crate::dashboard::WeekTotals;
+use crate::format;
+use crate::i18n::{t, tr};
+use chrono::{Datelike, Duration as Days, Local, NaiveDate, NaiveTime, Weekday};
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
+    layout::{Alignment, Constraint, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, List, ListItem, ListState, Paragraph},
     Frame,
 };
+use std::collections::HashMap;
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let hero_height = if app.next_best_action().is_some() {
+        5
+    } else {
+        0
+    };
     let chunks = Layout::vertical([
-        Constraint::Length(3), // greeting
-        Constraint::Length(5), // stat cards
-        Constraint::Fill(1),   // alerts list
+        Constraint::Length(3),           // greeting
+        Constraint::Length(hero_height), // next best action
+        Constraint::Length(5),           // stat cards
+        Constraint::Max(9),              // activity heatmap, shrinks first
+        Constraint::Min(4),              // alerts list
     ])
     .split(area);
 
     render_greeting(frame, app, chunks[0]);
-    render_stat_cards(frame, app, chunks[1]);
-    render_alerts(frame, app, chunks[2]);
+    render_hero(frame, app, chunks[1]);
+    render_stat_cards(frame, app, chunks[2]);
+    render_activity(frame, app, chunks[3]);
+    render_alerts(frame, app, chunks[4]);
+}
+
+/// The one thing to do first: top recommendation, why, and what would run.
+fn render_hero(frame: &mut Frame, app: &App, area: Rect) {
+    let Some((repo, rec, action)) = app.next_best_action() else {
+        return;
+    };
+    let color = theme::severity_color(rec.priority.label());
+    let block = Block::bordered()
+        .border_set(theme::border(BorderType::Rounded))
+        .border_style(Style::default().fg(color))
+        .title(format!(" {} ", t("home-hero-title")))
+        .title_style(Style::default().fg(color).add_modifier(Modifier::BOLD));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let keys = if action.is_some() {
+        t("home-hero-keys")
+    } else {
+        t("home-hero-skip")
+    };
+    let width = inner.width as usize;
+    let command = action.map_or(rec.command.clone(), |a| a.command);
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!(" {} ", rec.priority.label().to_uppercase()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{} ", repo.name),
+                Style::default()
+                    .fg(theme::FG_PRIMARY)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(rec.action, Style::default().fg(theme::FG_PRIMARY)),
+            Span::styled(
+                format!(
+                    "  {}",
+                    tr("home-effort", &[("minutes", &rec.effort_minutes)])
+                ),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
+        ]),
+        Line::from(Span::styled(
+            format!(
+                " {}",
+                format::truncate(&rec.reason, width.saturating_sub(1))
+            ),
+            Style::default().fg(theme::FG_SECONDARY),
+        )),
+        Line::from(vec![
+            Span::styled(
+                format!(
+                    " $ {}",
+                    format::truncate(&command, width.saturating_sub(keys.len() + 5))
+                ),
+                Style::default().fg(theme::ACCENT_CYAN),
+            ),
+            Span::styled(format!("  {}", keys), Style::default().fg(theme::FG_DIMMED)),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(lines), inner);
 }
 
 fn render_greeting(frame: &mut Frame, app: &App, area: Rect) {
-    let hour = Local::now().hour();
+    let now = Local::now();
+    let hour = now.hour();
     let greeting = match hour {
-        5..=11 => "Good morning",
-        12..=16 => "Good afternoon",
-        17..=21 => "Good evening",
-        _ => "Good night",
+        5..=11 => t("home-morning"),
+        12..=16 => t("home-afternoon"),
+        17..=21 => t("home-evening"),
+        _ => t("home-night"),
     };
 
     let overview = &app.dashboard.overview;
     let attention = overview.actionable_repos;
     let summary = if attention == 0 {
-        format!(
-            "{}. {} repos monitored, all clear.",
-            greeting, overview.total_repos
+        tr(
+            "home-all-clear",
+            &[("greeting", &greeting), ("count", &overview.total_repos)],
         )
     } else {
-        format!(
-            "{}. {} repos monitored, {} need attention.",
-            greeting, overview.total_repos, attention
+        tr(
+            "home-attention",
+            &[
+                ("greeting", &greeting),
+                ("count", &overview.total_repos),
+                ("attention", &attention),
+            ],
         )
     };
 
     let block = Block::bordered()
-        .border_type(BorderType::Rounded)
+        .border_set(theme::border(BorderType::Rounded))
         .border_style(Style::default().fg(theme::BORDER_NORMAL));
 
     frame.render_widget(
-        Paragraph::new(Line::from(vec![Span::styled(
-            format!(" {}", summary),
-            Style::default().fg(theme::FG_PRIMARY),
-        )]))
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!(" {}", summary),
+                Style::default().fg(theme::FG_PRIMARY),
+            ),
+            Span::styled(
+                format!("  {}", format::date_time(&app.config.display, &now)),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
+        ]))
         .block(block),
         area,
     );
@@ -64,35 +152,36 @@ fn render_stat_cards(frame: &mut Frame, app: &App, area: Rect) {
     let cost = app.dashboard.total_estimated_cost_usd();
 
     let card_areas = Layout::horizontal([
-        Constraint::Percentage(25),
-        Constraint::Percentage(25),
-        Constraint::Percentage(25),
-        Constraint::Percentage(25),
+        Constraint::Fill(1),
+        Constraint::Fill(1),
+        Constraint::Fill(1),
+        Constraint::Fill(1),
+        Constraint::Length(26),
     ])
     .split(area);
 
     widgets::render_stat_card(
         frame,
         card_areas[0],
-        "Repos",
+        &t("home-card-repos"),
         &overview.total_repos.to_string(),
         theme::ACCENT_BLUE,
     );
     widgets::render_stat_card(
         frame,
         card_areas[1],
-        "Dirty",
+        &t("home-card-dirty"),
         &overview.dirty_repos.to_string(),
         if overview.dirty_repos > 0 {
-            theme::ACCENT_YELLOW
+            theme::palette().warn
         } else {
-            theme::ACCENT_GREEN
+            theme::palette().ok
         },
     );
     widgets::render_stat_card(
         frame,
         card_areas[2],
-        "Procs",
+        &t("home-card-procs"),
         &overview.repo_processes.to_string(),
         if overview.repo_processes > 0 {
             theme::ACCENT_CYAN
@@ -103,21 +192,245 @@ fn render_stat_cards(frame: &mut Frame, app: &App, area: Rect) {
     widgets::render_stat_card(
         frame,
         card_areas[3],
-        "AI Cost",
-        &format!("${:.2}", cost),
+        &t("home-card-cost"),
+        &format::currency(&app.config.display, cost),
         if cost > 10.0 {
-            theme::ACCENT_ORANGE
+            theme::palette().high
         } else if cost > 0.0 {
-            theme::ACCENT_YELLOW
+            theme::palette().warn
         } else {
             theme::FG_DIMMED
         },
     );
+    render_week_card(frame, app, card_areas[4]);
+}
+
+/// Commits, repos touched and changed lines since Monday.
+fn render_week_card(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered()
+        .border_set(theme::border(BorderType::Rounded))
+        .border_style(Style::default().fg(theme::BORDER_NORMAL));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let week = WeekTotals::of(&app.dashboard.this_week);
+    let dot = if week.commits > 0 {
+        theme::palette().ok
+    } else {
+        theme::FG_DIMMED
+    };
+    let lines = vec![
+        Line::from(Span::styled(
+            tr(
+                "home-week",
+                &[("commits", &week.commits), ("repos", &week.repos)],
+            ),
+            Style::default()
+                .fg(theme::FG_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::styled(
+                format!("+{}", week.insertions),
+                Style::default().fg(theme::palette().added),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format!("-{}", week.deletions),
+                Style::default().fg(theme::palette().removed),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", t("home-this-week")),
+                Style::default().fg(theme::FG_SECONDARY),
+            ),
+            Span::styled(theme::glyph("●"), Style::default().fg(dot)),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), inner);
+}
+
+/// Short weekday name from the catalog.
+fn weekday_name(day: Weekday) -> String {
+    t(match day {
+        Weekday::Mon => "home-mon",
+        Weekday::Tue => "home-tue",
+        Weekday::Wed => "home-wed",
+        Weekday::Thu => "home-thu",
+        Weekday::Fri => "home-fri",
+        Weekday::Sat => "home-sat",
+        Weekday::Sun => "home-sun",
+    })
+}
+
+/// Commits per day as a weekday-by-week grid, with the period's totals and
+/// busiest repos beside it.
+fn render_activity(frame: &mut Frame, app: &App, area: Rect) {
+    let title = tr("home-activity-title", &[("weeks", &activity::WEEKS)]);
+    let block = theme::block_default(&title);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let days = &app.dashboard.activity;
+    if days.is_empty() {
+        frame.render_widget(
+            Paragraph::new(format!(
+                " {}",
+                tr("home-no-activity", &[("weeks", &activity::WEEKS)])
+            ))
+            .style(Style::default().fg(theme::FG_DIMMED)),
+            inner,
+        );
+        return;
+    }
+
+    let today = Local::now().date_naive();
+    let start = activity::window_start(today);
+    let counts: HashMap<NaiveDate, usize> = days
+        .iter()
+        .filter_map(|day| {
+            let date = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").ok()?;
+            Some((date, day.commits))
+        })
+        .collect();
+    let max = counts.values().copied().max().unwrap_or(0);
+    let dim = Style::default().fg(theme::FG_DIMMED);
+
+    let weekdays = [
+        weekday_name(Weekday::Mon),
+        String::new(),
+        weekday_name(Weekday::Wed),
+        String::new(),
+        weekday_name(Weekday::Fri),
+        String::new(),
+        weekday_name(Weekday::Sun),
+    ];
+    let grid: Vec<Line> = weekdays
+        .iter()
+        .enumerate()
+        .map(|(weekday, label)| {
+            let mut spans = vec![Span::styled(format!(" {:<4}", label), dim)];
+            for week in 0..activity::WEEKS {
+                let date = start + Days::days((week * 7 + weekday) as i64);
+                let cell = if date > today {
+                    Span::raw("  ")
+                } else {
+                    let count = counts.get(&date).copied().unwrap_or(0);
+                    let color = if count == 0 {
+                        theme::FG_DIMMED
+                    } else {
+                        theme::palette().ok
+                    };
+                    Span::styled(
+                        format!("{} ", theme::glyph(shade(count, max))),
+                        Style::default().fg(color),
+                    )
+                };
+                spans.push(cell);
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    let mut per_repo: HashMap<&str, usize> = HashMap::new();
+    for day in days {
+        for (repo, commits) in &day.repos {
+            *per_repo.entry(repo.as_str()).or_default() += commits;
+        }
+    }
+    let mut per_repo: Vec<(&str, usize)> = per_repo.into_iter().collect();
+    per_repo.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let total: usize = days.iter().map(|day| day.commits).sum();
+    let busiest = days
+        .iter()
+        .max_by(|a, b| a.commits.cmp(&b.commits).then_with(|| a.date.cmp(&b.date)));
+
+    let mut summary = vec![Line::from(vec![
+        Span::styled(
+            tr("home-commits", &[("count", &total)]),
+            Style::default()
+                .fg(theme::FG_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "{}{}",
+                theme::glyph(" · "),
+                tr(
+                    "home-active",
+                    &[("days", &days.len()), ("repos", &per_repo.len())],
+                )
+            ),
+            Style::default().fg(theme::FG_SECONDARY),
+        ),
+    ])];
+    if let Some(busiest) = busiest {
+        let (weekday, label) = NaiveDate::parse_from_str(&busiest.date, "%Y-%m-%d")
+            .map(|date| {
+                let midnight = date.and_time(NaiveTime::MIN).and_utc();
+                (
+                    weekday_name(date.weekday()),
+                    format::date(&app.config.display, &midnight),
+                )
+            })
+            .unwrap_or_else(|_| (String::new(), busiest.date.clone()));
+        summary.push(Line::from(Span::styled(
+            tr(
+                "home-busiest",
+                &[
+                    ("weekday", &weekday),
+                    ("day", &label),
+                    ("count", &busiest.commits),
+                ],
+            ),
+            dim,
+        )));
+    }
+    summary.push(Line::from(""));
+    let top = per_repo.first().map_or(1, |(_, commits)| *commits).max(1);
+    let bar_width = 16;
+    for (repo, commits) in per_repo
+        .iter()
+        .take((inner.height as usize).saturating_sub(summary.len()))
+    {
+        let filled = (commits * bar_width).div_ceil(top);
+        summary.push(Line::from(vec![
+            Span::styled(
+                format!("{} ", format::pad(repo, 18)),
+                Style::default().fg(theme::FG_PRIMARY),
+            ),
+            Span::styled(
+                theme::glyph("█").repeat(filled),
+                Style::default().fg(theme::palette().ok),
+            ),
+            Span::styled(format!(" {}", commits), dim),
+        ]));
+    }
+
+    let [grid_area, summary_area] = Layout::horizontal([
+        Constraint::Length(5 + 2 * activity::WEEKS as u16 + 2),
+        Constraint::Fill(1),
+    ])
+    .areas(inner);
+    frame.render_widget(Paragraph::new(grid), grid_area);
+    frame.render_widget(Paragraph::new(summary), summary_area);
+}
+
+/// Heatmap cell for `count` commits, in quarters of the busiest day.
+fn shade(count: usize, max: usize) -> &'static str {
+    match count {
+        0 => "·",
+        _ if count * 4 <= max => "░",
+        _ if count * 2 <= max => "▒",
+        _ if count * 4 <= max * 3 => "▓",
+        _ => "█",
+    }
 }
 
 fn render_alerts(frame: &mut Frame, app: &App, area: Rect) {
     if app.dashboard.alerts.is_empty() {
-        widgets::render_empty_state(frame, area, "✓", "No alerts. Workspace looks healthy.");
+        widgets::render_empty_state(frame, area, "✓", &t("home-no-alerts"));
         return;
     }
 
@@ -127,11 +440,11 @@ fn render_alerts(frame: &mut Frame, app: &App, area: Rect) {
         .iter()
         .map(|a| {
             let sev_color = theme::severity_color(&a.severity);
-            let dot = if a.severity == "critical" || a.severity == "high" {
+            let dot = theme::glyph(if a.severity == "critical" || a.severity == "high" {
                 "●"
             } else {
                 "○"
-            };
+            });
             let action_text = a
                 .action
                 .as_ref()
@@ -145,12 +458,12 @@ fn render_alerts(frame: &mut Frame, app: &App, area: Rect) {
                     Style::default().fg(sev_color).add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
-                    truncate_str(&a.title, 32),
+                    format::truncate(&a.title, 32),
                     Style::default().fg(theme::FG_PRIMARY),
                 ),
                 Span::raw("  "),
                 Span::styled(
-                    truncate_str(&a.detail, 30),
+                    format::truncate(&a.detail, 30),
                     Style::default().fg(theme::FG_SECONDARY),
                 ),
                 Span::raw("  "),
@@ -159,7 +472,10 @@ fn render_alerts(frame: &mut Frame, app: &App, area: Rect) {
         })
         .collect();
 
-    let title = format!("Alerts ({})", app.dashboard.alerts.len());
+    let title = tr(
+        "home-alerts-title",
+        &[("count", &app.dashboard.alerts.len())],
+    );
     let list = List::new(items)
         .block(theme::block_focused(&title))
         .highlight_style(theme::style_row_highlight());
@@ -173,16 +489,6 @@ fn render_alerts(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_stateful_widget(list, area, &mut state);
 }
 
-fn truncate_str(s: &str, max: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max {
-        s.to_string()
-    } else {
-        let truncated: String = s.chars().take(max.saturating_sub(1)).collect();
-        format!("{}…", truncated)
-    }
-}
-
 trait HourExt {
     fn hour(&self) -> u32;
 }
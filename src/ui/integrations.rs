@@ -0,0 +1,117 @@
+use super::{centered_rect, theme};
+use crate::app::App;
+use crate::dashboard::ProbeStatus;
+use crate::format;
+use crate::i18n::t;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+/// Per-host connectivity from the `[connectivity]` probe.
+pub fn render(frame: &mut Frame, app: &App) {
+    let probes = &app.dashboard.host_probes;
+    let area = centered_rect(90, (probes.len() as u16 + 9).max(11), frame.area());
+    let dim = Style::default().fg(theme::FG_DIMMED);
+
+    let mut lines = vec![Line::from("")];
+    if probes.is_empty() {
+        let message = if app.config.connectivity.enabled {
+            t("integrations-none")
+        } else {
+            t("integrations-off")
+        };
+        lines.push(Line::from(vec![Span::styled(
+            format!("  {}", message),
+            Style::default().fg(theme::FG_SECONDARY),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("  {}", t("integrations-how")),
+            dim,
+        )]));
+    } else {
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  {:<24}{:<7}{:<14}{:>7}{:>7}  {}",
+                t("integrations-col-host"),
+                t("integrations-col-via"),
+                t("integrations-col-status"),
+                t("integrations-col-time"),
+                t("integrations-col-repos"),
+                t("integrations-col-detail")
+            ),
+            dim.add_modifier(Modifier::BOLD),
+        )]));
+        // Left columns plus borders; the detail gets what remains.
+        let detail_width = (area.width as usize).saturating_sub(2 + 2 + 24 + 7 + 14 + 7 + 7 + 2);
+        for probe in probes {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(
+                        "  {} ",
+                        format::pad(
+                            &if probe.vpn {
+                                format!("{} (vpn)", probe.host)
+                            } else {
+                                probe.host.clone()
+                            },
+                            23
+                        )
+                    ),
+                    Style::default().fg(theme::FG_PRIMARY),
+                ),
+                Span::styled(format!("{:<7}", probe.transport), dim),
+                Span::styled(
+                    format!("{:<14}", probe.status.label()),
+                    Style::default().fg(status_color(probe.status)),
+                ),
+                Span::styled(format!("{:>7}", format::millis(probe.latency_ms)), dim),
+                Span::styled(format!("{:>7}", probe.repos.len()), dim),
+                Span::styled(
+                    format!("  {}", format::truncate(&probe.detail, detail_width)),
+                    Style::default().fg(theme::FG_SECONDARY),
+                ),
+            ]));
+        }
+        if probes.iter().any(|p| !p.status.is_reachable()) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![Span::styled(
+                format!("  {}", t("integrations-stale")),
+                Style::default().fg(theme::ACCENT_YELLOW),
+            )]));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        format!("  {}", t("integrations-close")),
+        dim,
+    )]));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::BORDER_FOCUSED))
+                    .title(format!(" {} ", t("integrations-title")))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_BLUE)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        area,
+    );
+}
+
+fn status_color(status: ProbeStatus) -> Color {
+    match status {
+        ProbeStatus::Authenticated | ProbeStatus::Reachable => theme::palette().ok,
+        ProbeStatus::AuthFailed | ProbeStatus::HostKey => theme::palette().warn,
+        ProbeStatus::Unreachable => theme::palette().critical,
+    }
+}
@@ -1,18 +1,31 @@
 pub mod action_confirm;
+pub mod branch_picker;
+pub mod cherry_picker;
 pub mod commit_bar;
+pub mod commit_log;
+pub mod diff;
 pub mod filter;
 pub mod help;
 pub mod home;
+pub mod integrations;
+pub mod recent;
+pub mod remote_wizard;
+pub mod settings;
 pub mod sidebar;
+pub mod stash_list;
+pub mod stash_picker;
 pub mod summary_bar;
 pub mod table;
 pub mod theme;
+pub mod time_travel;
+pub mod tour;
 pub mod widgets;
 
 use crate::app::{App, AppMode};
 use crate::dashboard::DashboardSection;
+use crate::i18n::{t, tr};
 use ratatui::{
-    layout::{Constraint, Layout},
+    layout::{Constraint, Layout, Rect},
     style::Style,
     text::{Line, Span},
     widgets::Paragraph,
@@ -25,15 +38,20 @@ const SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧
 const MIN_WIDTH: u16 = 80;
 const MIN_HEIGHT: u16 = 18;
 
-/// Top-level render: lays out the screen and delegates to sub-renderers.
+/// Lays out the screen and delegates to sub-renderers.
 pub fn render(frame: &mut Frame, app: &App) {
     let area = frame.area();
 
     // Guard: tell the user to resize if the terminal is too small
     if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
-        let msg = format!(
-            "Terminal too small ({}×{})\nPlease resize to at least {}×{}",
-            area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+        let msg = tr(
+            "too-small",
+            &[
+                ("width", &area.width),
+                ("height", &area.height),
+                ("min_width", &MIN_WIDTH),
+                ("min_height", &MIN_HEIGHT),
+            ],
         );
         frame.render_widget(
             Paragraph::new(msg)
@@ -44,29 +62,28 @@ pub fn render(frame: &mut Frame, app: &App) {
         return;
     }
 
-    let chunks = Layout::vertical([
-        Constraint::Length(3), // summary
-        Constraint::Fill(1),   // sidebar + section content
-        Constraint::Length(1), // status / filter / commit
-    ])
-    .split(frame.area());
-
-    let body = Layout::horizontal([Constraint::Length(24), Constraint::Fill(1)]).split(chunks[1]);
+    if app.mode == AppMode::Tour {
+        tour::render(frame, app);
+        return;
+    }
 
-    summary_bar::render(frame, app, chunks[0]);
-    sidebar::render(frame, app, body[0]);
+    let [summary, sidebar, content, status] = regions(area);
+    summary_bar::render(frame, app, summary);
+    sidebar::render(frame, app, sidebar);
 
     // Route Home to home::render, everything else to table::render
     if app.section == DashboardSection::Home {
-        home::render(frame, app, body[1]);
+        home::render(frame, app, content);
     } else {
-        table::render(frame, app, body[1]);
+        table::render(frame, app, content);
     }
 
     match app.mode {
-        AppMode::Search => filter::render(frame, app, chunks[2]),
-        AppMode::Commit => commit_bar::render(frame, app, chunks[2]),
-        _ => render_status_bar(frame, app, chunks[2]),
+        AppMode::Search => filter::render(frame, app, status),
+        AppMode::Grep => filter::render_grep(frame, app, status),
+        AppMode::Commit => commit_bar::render(frame, app, status),
+        AppMode::EditSetting => settings::render_input(frame, app, status),
+        _ => render_status_bar(frame, app, status),
     }
 
     if app.mode == AppMode::Help {
@@ -75,16 +92,79 @@ pub fn render(frame: &mut Frame, app: &App) {
     if app.mode == AppMode::ConfirmAction {
         action_confirm::render(frame, app);
     }
+    if app.mode == AppMode::StashPick {
+        stash_picker::render(frame, app);
+    }
+    if app.mode == AppMode::RemoteWizard {
+        remote_wizard::render(frame, app);
+    }
+    if app.mode == AppMode::BranchPick {
+        branch_picker::render(frame, app);
+    }
+    if app.mode == AppMode::RecentRepos {
+        recent::render(frame, app);
+    }
+    if app.mode == AppMode::CommitLog {
+        commit_log::render(frame, app);
+    }
+    if app.mode == AppMode::Diff {
+        diff::render(frame, app);
+    }
+    if app.mode == AppMode::Stashes {
+        stash_list::render(frame, app);
+    }
+    if app.mode == AppMode::CherryPick {
+        cherry_picker::render(frame, app);
+    }
+    if app.mode == AppMode::TimeTravel {
+        time_travel::render(frame, app);
+    }
+    if app.mode == AppMode::Integrations {
+        integrations::render(frame, app);
+    }
+}
+
+/// Summary bar, sidebar, section content and status line.
+fn regions(area: Rect) -> [Rect; 4] {
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // summary
+        Constraint::Fill(1),   // sidebar + section content
+        Constraint::Length(1), // status / filter / commit
+    ])
+    .split(area);
+    let body = Layout::horizontal([Constraint::Length(24), Constraint::Fill(1)]).split(chunks[1]);
+    [chunks[0], body[0], body[1], chunks[2]]
+}
+
+/// A `width` × `height` box centred in `area`, shrunk to fit it; where the
+/// modals are drawn.
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let w = width.min(area.width);
+    let h = height.min(area.height);
+    let x = area.x + (area.width.saturating_sub(w)) / 2;
+    let y = area.y + (area.height.saturating_sub(h)) / 2;
+    Rect {
+        x,
+        y,
+        width: w,
+        height: h,
+    }
 }
 
 fn render_status_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     if app.mode == AppMode::ConfirmAction {
         let line = Line::from(vec![
             Span::raw(" "),
-            Span::styled("Enter / y", Style::default().fg(theme::ACCENT_GREEN)),
-            Span::styled(" run once  ", Style::default().fg(theme::FG_DIMMED)),
+            Span::styled("Enter / y", Style::default().fg(theme::palette().ok)),
+            Span::styled(
+                format!(" {}  ", t("hint-run-once")),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
             Span::styled("Esc / n", Style::default().fg(theme::ACCENT_YELLOW)),
-            Span::styled(" cancel", Style::default().fg(theme::FG_DIMMED)),
+            Span::styled(
+                format!(" {}", t("hint-cancel")),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
         ]);
         frame.render_widget(
             Paragraph::new(line).style(Style::default().bg(theme::BG_SECONDARY)),
@@ -97,7 +177,7 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect)
     if let Some((msg, _)) = &app.notification {
         let line = Line::from(vec![
             Span::styled(" ", Style::default()),
-            Span::styled(msg, Style::default().fg(theme::ACCENT_GREEN)),
+            Span::styled(msg, Style::default().fg(theme::palette().ok)),
         ]);
         frame.render_widget(
             Paragraph::new(line).style(Style::default().bg(theme::BG_SECONDARY)),
@@ -108,33 +188,72 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect)
 
     let mut spans: Vec<Span> = vec![Span::raw(" ")];
 
+    if let Some(grep) = &app.grep {
+        let summary = match &grep.hits {
+            None => format!(
+                "{} ",
+                tr(
+                    "status-grep-searching",
+                    &[("pattern", &format!("{:?}", grep.pattern))]
+                )
+            ),
+            Some(hits) => format!(
+                "{} ",
+                tr(
+                    "status-grep-results",
+                    &[
+                        ("pattern", &format!("{:?}", grep.pattern)),
+                        ("repos", &hits.len()),
+                        (
+                            "matches",
+                            &hits.iter().map(|h| h.match_count()).sum::<usize>()
+                        ),
+                    ]
+                )
+            ),
+        };
+        spans.push(Span::styled(
+            summary,
+            Style::default().fg(theme::ACCENT_CYAN),
+        ));
+        spans.extend(widgets::key_hint("G", &t("hint-clear")));
+        spans.push(Span::styled(
+            theme::glyph("│ "),
+            Style::default().fg(theme::FG_DIMMED),
+        ));
+    }
+
     // Core navigation hints
-    let hints: &[(&str, &str)] = &[
-        ("h/l", "section"),
-        ("j/k", "row"),
-        ("x", "review"),
-        ("r", "refresh"),
-        ("/", "filter"),
-        ("?", "help"),
-        ("q", "quit"),
+    let hints = [
+        ("h/l", t("hint-section")),
+        ("j/k", t("hint-row")),
+        ("x", t("hint-review")),
+        ("r", t("hint-refresh")),
+        ("/", t("hint-filter")),
+        ("?", t("hint-help")),
+        ("q", t("hint-quit")),
     ];
 
-    for (key, desc) in hints {
+    for (key, desc) in &hints {
         spans.extend(widgets::key_hint(key, desc));
     }
 
     // Section-specific hints for Repos
     if app.section == DashboardSection::Repos {
-        spans.push(Span::styled("│ ", Style::default().fg(theme::FG_DIMMED)));
-        let repo_hints: &[(&str, &str)] = &[
-            ("↵", "open"),
-            ("f", "fetch"),
-            ("p", "pull"),
-            ("P", "push"),
-            ("c", "commit"),
-            ("g", "group"),
+        spans.push(Span::styled(
+            theme::glyph("│ "),
+            Style::default().fg(theme::FG_DIMMED),
+        ));
+        let repo_hints = [
+            ("↵", t("hint-open")),
+            ("f", t("hint-fetch")),
+            ("p", t("hint-pull")),
+            ("P", t("hint-push")),
+            ("c", t("hint-commit")),
+            ("z", t("hint-stash-files")),
+            ("g", t("hint-group")),
         ];
-        for (key, desc) in repo_hints {
+        for (key, desc) in &repo_hints {
             spans.extend(widgets::key_hint(key, desc));
         }
     }
@@ -147,7 +266,11 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect)
             .as_millis();
         let frame_idx = ((millis / 100) as usize) % SPINNER.len();
         spans.push(Span::styled(
-            format!(" {} scanning", SPINNER[frame_idx]),
+            format!(
+                " {} {}",
+                theme::glyph(SPINNER[frame_idx]),
+                t("status-scanning")
+            ),
             Style::default().fg(theme::ACCENT_YELLOW),
         ));
     }
@@ -158,3 +281,281 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect)
         area,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::TourStep;
+    use crate::config::Config;
+    use crate::dashboard::models::OverviewMetrics;
+    use crate::dashboard::{
+        ActivityDay, DashboardAlert, DashboardSnapshot, HostProbe, ProbeStatus, RepoTiming,
+        ScanStats,
+    };
+    use crate::git::{Repo, RepoStatus};
+    use chrono::Local;
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::path::PathBuf;
+
+    fn repo(name: &str, status: RepoStatus) -> Repo {
+        let mut repo = Repo::new(PathBuf::from(format!("/work/{}", name)));
+        repo.status = status;
+        repo
+    }
+
+    /// A scanned app with one repo per common state; no clock or filesystem
+    /// state reaches the frame.
+    fn fixture_app() -> App {
+        let mut app = App::new(Config {
+            watch_directories: vec![PathBuf::from("/work")],
+            ..Config::default()
+        });
+        app.is_scanning = false;
+        app.repos = vec![
+            repo(
+                "api",
+                RepoStatus {
+                    branch: "main".to_string(),
+                    uncommitted_count: 3,
+                    behind_count: 2,
+                    has_remote: true,
+                    ..RepoStatus::default()
+                },
+            ),
+            repo(
+                "web",
+                RepoStatus {
+                    branch: "feature/login".to_string(),
+                    unpushed_count: 4,
+                    has_remote: true,
+                    ..RepoStatus::default()
+                },
+            ),
+            repo(
+                "notes",
+                RepoStatus {
+                    branch: "main".to_string(),
+                    ..RepoStatus::default()
+                },
+            ),
+        ];
+        app.dashboard = DashboardSnapshot {
+            overview: OverviewMetrics {
+                total_repos: 3,
+                actionable_repos: 2,
+                dirty_repos: 1,
+                repos_ahead: 1,
+                repos_behind: 1,
+                ..OverviewMetrics::default()
+            },
+            alerts: vec![DashboardAlert {
+                severity: "high".to_string(),
+                title: "api has local changes and is behind".to_string(),
+                detail: "3 uncommitted · 2 behind".to_string(),
+                repo: Some("api".to_string()),
+                action: None,
+            }],
+            ..DashboardSnapshot::default()
+        };
+        app
+    }
+
+    fn frame_text(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 28)).unwrap();
+        terminal.draw(|frame| render(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                let row: String = (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect();
+                row.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn home_frame() {
+        // The greeting carries the current time of day.
+        insta::with_settings!({filters => vec![(r"│ Good \w+\.[^│]*│", "│ [greeting] │")]}, {
+            insta::assert_snapshot!(frame_text(&fixture_app()));
+        });
+    }
+
+    #[test]
+    fn home_activity_heatmap() {
+        let mut app = fixture_app();
+        app.dashboard.activity = vec![ActivityDay {
+            date: Local::now().format("%Y-%m-%d").to_string(),
+            commits: 5,
+            repos: [("api".to_string(), 4), ("web".to_string(), 1)].into(),
+        }];
+        let text = frame_text(&app);
+        assert!(text.contains("Activity (8 weeks)"), "{text}");
+        assert!(
+            text.contains("5 commits · 1 active days · 2 repos"),
+            "{text}"
+        );
+        assert!(text.contains("█"), "{text}");
+        let api = text.lines().find(|l| l.contains(" api   ")).unwrap();
+        let web = text.lines().find(|l| l.contains(" web   ")).unwrap();
+        assert!(api.matches('█').count() > web.matches('█').count());
+    }
+
+    #[test]
+    fn repos_frame() {
+        let mut app = fixture_app();
+        app.section = DashboardSection::Repos;
+        insta::assert_snapshot!(frame_text(&app));
+    }
+
+    #[test]
+    fn stats_frame() {
+        let mut app = fixture_app();
+        app.section = DashboardSection::Stats;
+        app.dashboard.scan_stats = Some(ScanStats {
+            discover_ms: 40,
+            status_ms: 1_850,
+            collect_ms: 620,
+            repos_found: 3,
+            cache_hits: 1,
+            cache_misses: 2,
+            deferred: 0,
+            ignored: vec!["archive".to_string()],
+            git_spawns: 27,
+            slowest: vec![
+                RepoTiming {
+                    repo: "api".to_string(),
+                    path: "/work/api".to_string(),
+                    ms: 1_720,
+                },
+                RepoTiming {
+                    repo: "web".to_string(),
+                    path: "/work/web".to_string(),
+                    ms: 310,
+                },
+            ],
+        });
+        insta::assert_snapshot!(frame_text(&app));
+    }
+
+    #[test]
+    fn settings_frame() {
+        let mut app = fixture_app();
+        app.section = DashboardSection::Settings;
+        app.config_path = PathBuf::from("/home/me/.config/agentpulse/config.toml");
+        app.selected = 1;
+        app.activate_setting();
+        app.settings.input = Some("30".to_string());
+        app.settings.commit_input(1).unwrap();
+        app.mode = AppMode::Normal;
+        app.selected = 5;
+        app.activate_setting();
+        insta::assert_snapshot!(frame_text(&app));
+    }
+
+    #[test]
+    fn integrations_frame() {
+        let mut app = fixture_app();
+        app.section = DashboardSection::Repos;
+        app.mode = AppMode::Integrations;
+        app.dashboard.host_probes = vec![
+            HostProbe {
+                host: "github.com".to_string(),
+                transport: "ssh".to_string(),
+                status: ProbeStatus::Authenticated,
+                detail: "Hi me! You've successfully authenticated, but GitHub does not provide shell access.".to_string(),
+                latency_ms: 420,
+                repos: vec!["api".to_string(), "web".to_string()],
+                repo_paths: vec!["/src/api".to_string(), "/src/web".to_string()],
+                vpn: false,
+            },
+            HostProbe {
+                host: "git.corp.example".to_string(),
+                transport: "https".to_string(),
+                status: ProbeStatus::Unreachable,
+                detail: "timed out".to_string(),
+                latency_ms: 5_000,
+                repos: vec!["billing".to_string()],
+                repo_paths: vec!["/src/billing".to_string()],
+                vpn: true,
+            },
+        ];
+        insta::assert_snapshot!(frame_text(&app));
+    }
+
+    #[test]
+    fn repos_frame_in_ascii() {
+        let mut app = fixture_app();
+        app.section = DashboardSection::Repos;
+        // User text is drawn as written, glyphs and all.
+        app.repos[1].name = "wéb—app".to_string();
+        crate::i18n::set_template_filter(theme::glyph);
+        theme::TEST_ASCII.with(|on| on.set(true));
+        let mut terminal = Terminal::new(TestBackend::new(100, 28)).unwrap();
+        terminal.draw(|frame| render(frame, &app)).unwrap();
+        theme::TEST_ASCII.with(|on| on.set(false));
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("wéb—app"), "{text}");
+        let ours = text.replace("wéb—app", "");
+        assert!(ours.is_ascii(), "{ours}");
+        let first_row: String = buffer.content[..100].iter().map(|c| c.symbol()).collect();
+        assert!(first_row.starts_with("+ AgentPulse") && first_row.trim_end().ends_with("-+"));
+    }
+
+    #[test]
+    fn tour_frames() {
+        let mut app = fixture_app();
+        app.start_tour();
+        app.tour_step(true);
+        app.tour_step(true);
+        let filters = vec![
+            // The clock's hour is one or two digits, so swallow the padding too.
+            (
+                r"\d+s ago \(\d{1,2}:\d{2} [AP]M\)([^│]*?) +│",
+                "[scanned]$1 │",
+            ),
+            (r"Good \w+\.[^│]*│", "[greeting] │"),
+        ];
+        insta::with_settings!({filters => filters}, {
+            insta::assert_snapshot!("tour_action_key", frame_text(&app));
+            app.tour_step(true);
+            assert_eq!(app.tour.as_ref().unwrap().step, TourStep::Confirm);
+            insta::assert_snapshot!("tour_confirm", frame_text(&app));
+        });
+        app.tour_step(false);
+        app.tour_step(true);
+        app.tour_step(true);
+        assert!(app.tour.is_none() && app.tour_seen && app.mode == AppMode::Normal);
+    }
+
+    #[test]
+    fn diff_overlay_frame() {
+        let mut app = fixture_app();
+        app.section = DashboardSection::Repos;
+        app.diff_view = Some(crate::app::DiffView::new(
+            "api",
+            &crate::git::WorkingDiff {
+                stat: " src/lib.rs | 3 ++-\n 1 file changed, 2 insertions(+), 1 deletion(-)\n"
+                    .to_string(),
+                patch: "diff --git a/src/lib.rs b/src/lib.rs\nindex 1111111..2222222 100644\n\
+                        --- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,3 +1,4 @@ mod api;\n \
+                        pub fn run() {\n-    old();\n+    new();\n+    log();\n }\n"
+                    .to_string(),
+                untracked: vec!["notes.md".to_string()],
+            },
+        ));
+        app.mode = AppMode::Diff;
+        insta::assert_snapshot!(frame_text(&app));
+    }
+
+    #[test]
+    fn help_overlay_frame() {
+        let mut app = fixture_app();
+        app.section = DashboardSection::Repos;
+        app.mode = AppMode::Help;
+        insta::assert_snapshot!(frame_text(&app));
+    }
+}
@@ -0,0 +1,88 @@
+use super::{centered_rect, theme};
+use crate::app::App;
+use crate::format;
+use crate::i18n::t;
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(picker) = app.recent_picker.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(72, 20, frame.area());
+    // Borders, query line, blank lines and key hints.
+    let visible = area.height.saturating_sub(7).max(1) as usize;
+    let start = picker.cursor.saturating_sub(visible.saturating_sub(1));
+    let path_cells = area.width.saturating_sub(34) as usize;
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                theme::glyph("  › "),
+                Style::default().fg(theme::palette().label),
+            ),
+            Span::styled(picker.query.clone(), Style::default().fg(theme::FG_PRIMARY)),
+            Span::styled(theme::glyph("▌"), Style::default().fg(theme::ACCENT_BLUE)),
+        ]),
+        Line::from(""),
+    ];
+
+    let matches = picker.matches();
+    if matches.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", t("recent-no-match")),
+            Style::default().fg(theme::FG_DIMMED),
+        )));
+    }
+    for (idx, (path, name)) in matches.iter().enumerate().skip(start).take(visible) {
+        let name_style = if idx == picker.cursor {
+            Style::default()
+                .fg(theme::FG_PRIMARY)
+                .bg(theme::BG_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme::FG_PRIMARY)
+        };
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(format::pad(name, 26), name_style),
+            Span::styled(
+                format!(
+                    " {}",
+                    format::truncate_middle(&format::path(&app.config, path), path_cells)
+                ),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {}", t("recent-hint")),
+        Style::default().fg(theme::FG_DIMMED),
+    )));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::BORDER_FOCUSED))
+                    .title(format!(" {} ", t("recent-title")))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_BLUE)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        area,
+    );
+}
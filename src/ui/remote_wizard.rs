@@ -0,0 +1,98 @@
+use super::{centered_rect, theme};
+use crate::app::{App, WizardField};
+use crate::i18n::{t, tr};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(wizard) = app.remote_wizard.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(64, 12, frame.area());
+    let visibility = if wizard.private {
+        t("remote-private")
+    } else {
+        t("remote-public")
+    };
+    let rows = [
+        (
+            WizardField::Forge,
+            t("remote-host"),
+            wizard.forge.label().to_string(),
+        ),
+        (WizardField::Name, t("remote-name"), wizard.name.clone()),
+        (WizardField::Visibility, t("remote-visibility"), visibility),
+    ];
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!("  {}", t("remote-intro")),
+            Style::default().fg(theme::FG_SECONDARY),
+        )]),
+        Line::from(""),
+    ];
+    for (field, label, value) in rows {
+        let focused = field == wizard.field;
+        let value_style = if focused {
+            Style::default()
+                .fg(theme::FG_PRIMARY)
+                .bg(theme::BG_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme::FG_PRIMARY)
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!("  {:<11}", label),
+                Style::default().fg(theme::palette().label),
+            ),
+            Span::styled(value, value_style),
+        ];
+        if focused && field == WizardField::Name {
+            spans.push(Span::styled(
+                theme::glyph("▌"),
+                Style::default().fg(theme::ACCENT_BLUE),
+            ));
+        } else if focused {
+            spans.push(Span::styled(
+                format!("  {}", t("remote-change")),
+                Style::default().fg(theme::FG_DIMMED),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        format!("  {}", tr("remote-cli", &[("cli", &wizard.forge.cli())])),
+        Style::default().fg(theme::FG_DIMMED),
+    )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!("  {}", t("remote-hint")),
+        Style::default().fg(theme::FG_DIMMED),
+    )]));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::BORDER_FOCUSED))
+                    .title(format!(" {} ", t("remote-title")))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_BLUE)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        area,
+    );
+}
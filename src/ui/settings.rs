@@ -0,0 +1,100 @@
+use super::{theme, widgets};
+use crate::app::App;
+use crate::format;
+use crate::i18n::{t, tr};
+use crate::settings::{FieldKind, FIELDS};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Cell, Paragraph, Row},
+    Frame,
+};
+
+/// The Settings form: one row per field, edited values marked.
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let form = &app.settings;
+    let header = Row::new(vec![
+        Cell::from(""),
+        Cell::from(t("settings-col-setting")),
+        Cell::from(t("settings-col-value")),
+    ])
+    .style(theme::style_header());
+    let widths = [
+        Constraint::Length(2),
+        Constraint::Length(36),
+        Constraint::Fill(1),
+    ];
+    let value_cells = widgets::column_widths(area, &widths)[2];
+    let rows: Vec<Row> = FIELDS
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let (marker, color) = if form.is_modified(idx) {
+                (theme::glyph("●"), theme::ACCENT_YELLOW)
+            } else {
+                ("".into(), theme::FG_PRIMARY)
+            };
+            let value = match (&form.input, field.kind) {
+                (Some(input), _) if idx == app.selected => {
+                    format!("{}{}", input, theme::glyph("▌"))
+                }
+                (_, FieldKind::Toggle) if form.display(idx) == "true" => t("settings-on"),
+                (_, FieldKind::Toggle) => t("settings-off"),
+                _ => form.display(idx),
+            };
+            Row::new(vec![
+                Cell::from(marker).style(Style::default().fg(theme::ACCENT_YELLOW)),
+                Cell::from(field.name()).style(Style::default().fg(theme::FG_SECONDARY)),
+                Cell::from(format::truncate_middle(&value, value_cells))
+                    .style(Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    let title = match form.modified_count() {
+        0 => tr("settings-title", &[("path", &app.config_path.display())]),
+        n => tr("settings-title-unsaved", &[("count", &n)]),
+    };
+    widgets::render_styled_table(
+        frame,
+        area,
+        &title,
+        header,
+        rows,
+        widths,
+        app.selected,
+        FIELDS.len(),
+    );
+}
+
+/// Status line while typing a value.
+pub fn render_input(frame: &mut Frame, app: &App, area: Rect) {
+    let name = FIELDS
+        .get(app.selected)
+        .map(|f| f.name())
+        .unwrap_or_default();
+    // Validation errors arrive as notifications; this bar hides the usual spot.
+    let (hint, hint_color) = match (&app.notification, FIELDS.get(app.selected)) {
+        (Some((msg, _)), _) => (msg.clone(), theme::ACCENT_YELLOW),
+        (None, Some(field)) if field.kind == FieldKind::Dirs => {
+            (t("settings-input-dirs"), theme::FG_DIMMED)
+        }
+        _ => (t("settings-input"), theme::FG_DIMMED),
+    };
+    let line = Line::from(vec![
+        Span::styled(
+            format!(" {}: ", name),
+            Style::default().fg(theme::palette().label),
+        ),
+        Span::styled(
+            app.settings.input.clone().unwrap_or_default(),
+            Style::default().fg(theme::FG_PRIMARY),
+        ),
+        Span::styled(theme::glyph("▌"), Style::default().fg(theme::ACCENT_BLUE)),
+        Span::styled(format!("  {}", hint), Style::default().fg(hint_color)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(line).style(Style::default().bg(theme::BG_SECONDARY)),
+        area,
+    );
+}
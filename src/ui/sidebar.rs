@@ -35,8 +35,12 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         let is_active = *section == app.section;
         let count = app.section_row_count(*section);
 
-        let indicator = if is_active { "▸" } else { " " };
-        let num = idx + 1;
+        let indicator = theme::glyph(if is_active { "▸" } else { " " });
+        // Digit shortcuts stop at `0`; later sections show no number.
+        let num = match idx {
+            0..=9 => format!("{}.", (idx + 1) % 10),
+            _ => "  ".to_string(),
+        };
         let label = section.title();
 
         // Build the label portion
@@ -44,7 +48,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         // Compute padding so count aligns to right edge
         // Area inner width minus border (2) minus left padding (1)
         let inner_width = area.width.saturating_sub(2) as usize;
-        let label_part = format!(" {} {}. {}", indicator, num, label);
+        let label_part = format!(" {} {} {}", indicator, num, label);
         let padding = inner_width
             .saturating_sub(label_part.len())
             .saturating_sub(count_str.len())
@@ -76,7 +80,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 
     let list = List::new(items).block(
         Block::bordered()
-            .border_type(BorderType::Rounded)
+            .border_set(theme::border(BorderType::Rounded))
             .border_style(Style::default().fg(theme::BORDER_NORMAL))
             .title(" AgentPulse ")
             .title_style(
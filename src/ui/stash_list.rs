@@ -0,0 +1,93 @@
+use super::{centered_rect, theme};
+use crate::app::App;
+use crate::format;
+use crate::i18n::{t, tr};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(browser) = app.stash_browser.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(84, 20, frame.area());
+    // Borders, header, blank lines and key hints.
+    let visible = area.height.saturating_sub(7).max(1) as usize;
+    let start = browser.cursor.saturating_sub(visible.saturating_sub(1));
+    let message_cells = area.width.saturating_sub(50).max(10) as usize;
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!(
+                "  {}",
+                tr("stashes-header", &[("repo", &browser.repo_name)])
+            ),
+            Style::default().fg(theme::FG_SECONDARY),
+        )]),
+        Line::from(""),
+    ];
+
+    for (idx, entry) in browser.entries.iter().enumerate().skip(start).take(visible) {
+        let message_style = if idx == browser.cursor {
+            Style::default()
+                .fg(theme::FG_PRIMARY)
+                .bg(theme::BG_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme::FG_PRIMARY)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<11}", entry.name),
+                Style::default().fg(theme::ACCENT_BLUE),
+            ),
+            Span::styled(format::pad(&entry.message, message_cells), message_style),
+            Span::styled(
+                format!(
+                    " {}",
+                    format::pad(
+                        &entry
+                            .branch
+                            .clone()
+                            .unwrap_or_else(|| t("stashes-detached")),
+                        18
+                    )
+                ),
+                Style::default().fg(theme::palette().label),
+            ),
+            Span::styled(
+                format!(" {}", format::ago_epoch(entry.epoch)),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {}", t("stashes-hint")),
+        Style::default().fg(theme::FG_DIMMED),
+    )));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::BORDER_FOCUSED))
+                    .title(format!(" {} ", t("stashes-title")))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_BLUE)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        area,
+    );
+}
@@ -0,0 +1,112 @@
+use super::{centered_rect, theme};
+use crate::app::App;
+use crate::i18n::{t, tr};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(picker) = app.stash_picker.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(80, 22, frame.area());
+    // Borders, header, blank lines, message line, and key hints.
+    let visible = area.height.saturating_sub(8).max(1) as usize;
+    let start = picker.cursor.saturating_sub(visible.saturating_sub(1));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!(
+                "  {}",
+                tr("stash-files-header", &[("repo", &picker.repo_name)])
+            ),
+            Style::default().fg(theme::FG_SECONDARY),
+        )]),
+        Line::from(""),
+    ];
+
+    for (idx, (file, checked)) in picker
+        .files
+        .iter()
+        .zip(&picker.checked)
+        .enumerate()
+        .skip(start)
+        .take(visible)
+    {
+        let mark = if *checked { "[x]" } else { "[ ]" };
+        let is_cursor = idx == picker.cursor && !picker.editing_message;
+        let style = if is_cursor {
+            Style::default()
+                .fg(theme::FG_PRIMARY)
+                .bg(theme::BG_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme::FG_PRIMARY)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} ", mark),
+                Style::default().fg(theme::palette().label),
+            ),
+            Span::styled(
+                format!("{} ", file.status),
+                Style::default().fg(theme::ACCENT_YELLOW),
+            ),
+            Span::styled(file.path.clone(), style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let message_style = if picker.editing_message {
+        Style::default().fg(theme::FG_PRIMARY)
+    } else {
+        Style::default().fg(theme::FG_DIMMED)
+    };
+    let mut message = vec![
+        Span::styled(
+            format!("  {} ", t("stash-files-message")),
+            Style::default().fg(theme::palette().label),
+        ),
+        Span::styled(picker.message.clone(), message_style),
+    ];
+    if picker.editing_message {
+        message.push(Span::styled(
+            theme::glyph("▌"),
+            Style::default().fg(theme::ACCENT_BLUE),
+        ));
+    }
+    lines.push(Line::from(message));
+
+    let hints = if picker.editing_message {
+        t("stash-files-hint-message")
+    } else {
+        t("stash-files-hint")
+    };
+    lines.push(Line::from(vec![Span::styled(
+        format!("  {}", hints),
+        Style::default().fg(theme::FG_DIMMED),
+    )]));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::BORDER_FOCUSED))
+                    .title(format!(" {} ", t("stash-files-title")))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_BLUE)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        area,
+    );
+}
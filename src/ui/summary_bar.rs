@@ -1,6 +1,7 @@
 use super::theme;
 use crate::app::App;
-use chrono::Local;
+use crate::format;
+use crate::i18n::{t, tr};
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -20,16 +21,15 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 
     // Right: scan status
     let scan_info = if app.is_scanning {
-        "Scanning…".to_string()
+        t("summary-scanning")
     } else if let Some(t) = &app.last_scan {
-        let secs = Local::now().signed_duration_since(*t).num_seconds();
-        if secs < 60 {
-            format!("{}s ago", secs)
-        } else {
-            format!("{}m ago", secs / 60)
-        }
+        format!(
+            "{} ({})",
+            format::ago(t),
+            format::clock(&app.config.display, t)
+        )
     } else {
-        "Never".to_string()
+        t("summary-never")
     };
 
     let active_count = app.active_row_count();
@@ -48,28 +48,28 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
                 .fg(theme::ACCENT_BLUE)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(" · ", Style::default().fg(theme::FG_DIMMED)),
+        Span::styled(theme::glyph(" · "), Style::default().fg(theme::FG_DIMMED)),
         Span::styled(
-            format!("{} repos", ov.total_repos),
+            tr("summary-repos", &[("count", &ov.total_repos)]),
             Style::default().fg(theme::FG_SECONDARY),
         ),
-        Span::styled(" · ", Style::default().fg(theme::FG_DIMMED)),
+        Span::styled(theme::glyph(" · "), Style::default().fg(theme::FG_DIMMED)),
         Span::styled(
-            format!("{} dirty", ov.dirty_repos),
+            tr("summary-dirty", &[("count", &ov.dirty_repos)]),
             Style::default().fg(if ov.dirty_repos > 0 {
                 theme::ACCENT_YELLOW
             } else {
                 theme::FG_SECONDARY
             }),
         ),
-        Span::styled(" · ", Style::default().fg(theme::FG_DIMMED)),
+        Span::styled(theme::glyph(" · "), Style::default().fg(theme::FG_DIMMED)),
         Span::styled(
-            format!("{} proc", ov.repo_processes),
+            tr("summary-procs", &[("count", &ov.repo_processes)]),
             Style::default().fg(theme::FG_SECONDARY),
         ),
-        Span::styled(" · ", Style::default().fg(theme::FG_DIMMED)),
+        Span::styled(theme::glyph(" · "), Style::default().fg(theme::FG_DIMMED)),
         Span::styled(
-            format!("${:.2}", cost),
+            format::currency(&app.config.display, cost),
             Style::default().fg(if cost > 0.0 {
                 theme::ACCENT_YELLOW
             } else {
@@ -80,22 +80,48 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 
     // Filters
     if !app.filter_text.is_empty() {
-        spans.push(Span::styled(" · ", Style::default().fg(theme::FG_DIMMED)));
         spans.push(Span::styled(
-            format!("filter: \"{}\"", app.filter_text),
+            theme::glyph(" · "),
+            Style::default().fg(theme::FG_DIMMED),
+        ));
+        spans.push(Span::styled(
+            tr("summary-filter", &[("filter", &app.filter_text)]),
             Style::default().fg(theme::ACCENT_CYAN),
         ));
     }
     if app.agent_focus_mode {
-        spans.push(Span::styled(" · ", Style::default().fg(theme::FG_DIMMED)));
         spans.push(Span::styled(
-            "focus: actionable",
+            theme::glyph(" · "),
+            Style::default().fg(theme::FG_DIMMED),
+        ));
+        spans.push(Span::styled(
+            t("summary-focus"),
             Style::default().fg(theme::ACCENT_CYAN),
         ));
     }
+    if let Some(role) = app.role.label() {
+        spans.push(Span::styled(
+            theme::glyph(" · "),
+            Style::default().fg(theme::FG_DIMMED),
+        ));
+        spans.push(Span::styled(role, Style::default().fg(theme::ACCENT_CYAN)));
+    }
+    if app.power_save {
+        spans.push(Span::styled(
+            theme::glyph(" · "),
+            Style::default().fg(theme::FG_DIMMED),
+        ));
+        spans.push(Span::styled(
+            t("summary-power-save"),
+            Style::default().fg(theme::ACCENT_YELLOW),
+        ));
+    }
 
     // Right side: scan + counter
-    spans.push(Span::styled(" · ", Style::default().fg(theme::FG_DIMMED)));
+    spans.push(Span::styled(
+        theme::glyph(" · "),
+        Style::default().fg(theme::FG_DIMMED),
+    ));
     spans.push(Span::styled(
         scan_info,
         Style::default().fg(if app.is_scanning {
@@ -106,11 +132,21 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     ));
 
     if !counter.is_empty() {
-        spans.push(Span::styled(" · ", Style::default().fg(theme::FG_DIMMED)));
+        spans.push(Span::styled(
+            theme::glyph(" · "),
+            Style::default().fg(theme::FG_DIMMED),
+        ));
         spans.push(Span::styled(counter, Style::default().fg(theme::FG_DIMMED)));
     }
 
     let mut lines = vec![Line::from(spans)];
+    let para_style = if app.is_flashing() {
+        Style::default()
+            .bg(theme::palette().critical)
+            .fg(theme::BG_SECONDARY)
+    } else {
+        Style::default()
+    };
 
     // Missing directories warning
     if !app.config.missing_directories.is_empty() {
@@ -121,22 +157,32 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             .map(|p| p.display().to_string())
             .collect();
         lines.push(Line::from(vec![
-            Span::styled(" ⚠ not found: ", Style::default().fg(theme::ACCENT_YELLOW)),
+            Span::styled(
+                format!(" {} ", t("summary-not-found")),
+                Style::default().fg(theme::ACCENT_YELLOW),
+            ),
             Span::styled(names.join(", "), Style::default().fg(theme::FG_DIMMED)),
         ]));
     }
 
-    let para = Paragraph::new(lines).block(
-        Block::bordered()
-            .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(theme::BORDER_NORMAL))
-            .title(" AgentPulse Dashboard ")
-            .title_style(
-                Style::default()
-                    .fg(theme::ACCENT_BLUE)
-                    .add_modifier(Modifier::BOLD),
-            ),
-    );
+    let border = if app.is_flashing() {
+        theme::palette().critical
+    } else {
+        theme::BORDER_NORMAL
+    };
+    let para = Paragraph::new(lines)
+        .block(
+            Block::bordered()
+                .border_set(theme::border(BorderType::Rounded))
+                .border_style(Style::default().fg(border))
+                .title(" AgentPulse Dashboard ")
+                .title_style(
+                    Style::default()
+                        .fg(theme::ACCENT_BLUE)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        )
+        .style(para_style);
 
     frame.render_widget(para, area);
 }
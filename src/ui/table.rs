@@ -1,14 +1,20 @@
 use super::{theme, widgets};
-use crate::agent;
+use crate::agent::{self, Counter, Level};
 use crate::app::App;
-use crate::dashboard::DashboardSection;
-use crate::git::{Repo, StatusColor};
+use crate::config_docs;
+use crate::dashboard::{CiStatus, DashboardSection, RepoTicket};
+use crate::format;
+use crate::git::{ChangeCounts, Repo, RepoStatus};
+use crate::i18n::{t, tr};
+use crate::settings::{FieldKind, FIELDS};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::Style,
+    text::{Line, Span},
     widgets::{Cell, Paragraph, Row, Wrap},
     Frame,
 };
+use std::path::Path;
 
 // ─── grouping helpers (repos section) ──────────────────────────────────────
 
@@ -73,9 +79,16 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         DashboardSection::Worktrees => render_worktrees(frame, app, main),
         DashboardSection::Processes => render_processes(frame, app, main),
         DashboardSection::Dependencies => render_dependencies(frame, app, main),
+        DashboardSection::LocalDeps => render_local_deps(frame, app, main),
         DashboardSection::EnvAudit => render_env_audit(frame, app, main),
+        DashboardSection::Licenses => render_licenses(frame, app, main),
         DashboardSection::McpHealth => render_mcp(frame, app, main),
         DashboardSection::AiCosts => render_ai_costs(frame, app, main),
+        DashboardSection::PullRequests => render_pull_requests(frame, app, main),
+        DashboardSection::AiClients => render_ai_clients(frame, app, main),
+        DashboardSection::Stats => render_stats(frame, app, main),
+        DashboardSection::ActionLog => render_action_log(frame, app, main),
+        DashboardSection::Settings => super::settings::render(frame, app, main),
     }
 
     if chunks.len() > 1 {
@@ -88,10 +101,9 @@ fn render_repos(frame: &mut Frame, app: &App, area: Rect) {
 
     if filtered.is_empty() {
         let msg = if app.filter_text.is_empty() {
-            "No repositories found — run `agentpulse --setup` to configure watch directories"
-                .to_string()
+            t("repos-empty")
         } else {
-            format!("No repos matching \"{}\"", app.filter_text)
+            tr("repos-no-match", &[("filter", &app.filter_text)])
         };
         widgets::render_empty_state(frame, area, "◇", &msg);
         return;
@@ -101,16 +113,28 @@ fn render_repos(frame: &mut Frame, app: &App, area: Rect) {
 
     let header = Row::new(vec![
         Cell::from(""),
-        Cell::from("NAME"),
-        Cell::from("BRANCH"),
-        Cell::from("DIRTY"),
-        Cell::from("SYNC"),
-        Cell::from("STASH"),
-        Cell::from("NEXT"),
+        Cell::from(t("col-name")),
+        Cell::from(t("col-branch")),
+        Cell::from(t("col-dirty")),
+        Cell::from(t("col-sync")),
+        Cell::from(t("col-stash")),
+        Cell::from(t("col-next")),
     ])
     .style(theme::style_header())
     .height(1);
 
+    let widths = [
+        Constraint::Length(if theme::status_letters() { 4 } else { 2 }),
+        Constraint::Fill(2),
+        Constraint::Fill(1),
+        Constraint::Length(14),
+        Constraint::Length(13),
+        Constraint::Length(6),
+        Constraint::Length(13),
+    ];
+
+    let cells = widgets::column_widths(area, &widths);
+    let now = chrono::Utc::now().timestamp();
     let mut data_row_idx: usize = 0;
     let rows: Vec<Row> = entries
         .iter()
@@ -131,54 +155,42 @@ fn render_repos(frame: &mut Frame, app: &App, area: Rect) {
             ),
 
             Entry::Repo(repo) => {
-                let (indicator, color) = match repo.status_color() {
-                    StatusColor::Clean => ("○", theme::ACCENT_GREEN),
-                    StatusColor::Uncommitted => ("●", theme::ACCENT_YELLOW),
-                    StatusColor::Unpushed => ("●", theme::ACCENT_BLUE),
-                    StatusColor::Dirty => ("●", theme::ACCENT_RED),
-                    StatusColor::NoRemote => ("○", theme::FG_DIMMED),
-                };
-
-                let dirty = if repo.status.uncommitted_count > 0 {
-                    if repo.status.uncommitted_count == 1 {
-                        "1 file".to_string()
-                    } else {
-                        format!("{} files", repo.status.uncommitted_count)
-                    }
+                let state = repo.status_color();
+                let indicator = if theme::status_letters() {
+                    format!(
+                        "{} {}",
+                        theme::status_glyph(state),
+                        theme::status_letter(state)
+                    )
                 } else {
-                    "—".to_string()
+                    theme::status_glyph(state).to_string()
                 };
-
-                let sync = if !repo.status.has_remote {
-                    "n/a".to_string()
+                let color = if agent::repo_level(&repo.status) == Level::Critical {
+                    theme::palette().critical
                 } else {
-                    let ahead = repo.status.unpushed_count;
-                    let behind = repo.status.behind_count;
-                    match (ahead, behind) {
-                        (0, 0) => "—".to_string(),
-                        (a, 0) => format!("↑{}", a),
-                        (0, b) => format!("↓{}", b),
-                        (a, b) => format!("↑{} ↓{}", a, b),
-                    }
+                    theme::status_color(state)
                 };
 
+                let dirty = with_age(dirty_label(&repo.status), repo.status.dirty_since, now);
+                let sync = with_age(sync_label(&repo.status), repo.status.unpushed_since, now);
+
                 let stash = if repo.status.stash_count > 0 {
-                    format!("⚑{}", repo.status.stash_count)
+                    format!("{}{}", theme::glyph("⚑"), repo.status.stash_count)
                 } else {
                     String::new()
                 };
                 let rec = agent::recommend(repo);
+                let dismissed = app.suppressions.is_dismissed(repo, &rec);
                 let next = if rec.short_action == "noop" {
-                    "—".to_string()
+                    theme::glyph("—").into_owned()
+                } else if dismissed {
+                    format!("({})", rec.short_action)
                 } else {
                     rec.short_action.to_string()
                 };
 
                 let (branch_text, branch_style) = if repo.status.is_detached {
-                    (
-                        "(detached)".to_string(),
-                        Style::default().fg(theme::FG_DIMMED),
-                    )
+                    (t("repos-detached"), Style::default().fg(theme::FG_DIMMED))
                 } else {
                     (
                         repo.status.branch.clone(),
@@ -187,8 +199,9 @@ fn render_repos(frame: &mut Frame, app: &App, area: Rect) {
                 };
 
                 let rec_color = match rec.short_action {
+                    _ if dismissed => theme::FG_DIMMED,
                     "commit" | "add+commit" => theme::ACCENT_YELLOW,
-                    "push" => theme::ACCENT_BLUE,
+                    "push" | "publish" => theme::ACCENT_BLUE,
                     "pull" | "fetch+pull" => theme::ACCENT_CYAN,
                     "stash-or-commit" => theme::ACCENT_ORANGE,
                     _ => theme::ACCENT_CYAN,
@@ -196,10 +209,17 @@ fn render_repos(frame: &mut Frame, app: &App, area: Rect) {
 
                 let row = Row::new(vec![
                     Cell::from(indicator).style(Style::default().fg(color)),
-                    Cell::from(repo.name.clone()).style(Style::default().fg(theme::FG_PRIMARY)),
-                    Cell::from(branch_text).style(branch_style),
-                    Cell::from(dirty).style(Style::default().fg(theme::FG_PRIMARY)),
-                    Cell::from(sync).style(Style::default().fg(theme::FG_PRIMARY)),
+                    Cell::from(format::truncate(&repo.name, cells[1]))
+                        .style(Style::default().fg(theme::FG_PRIMARY)),
+                    branch_cell(app.ticket_for(repo), &branch_text, branch_style, cells[2]),
+                    Cell::from(dirty).style(counter_style(&[(
+                        Counter::Dirty,
+                        repo.status.uncommitted_count,
+                    )])),
+                    Cell::from(sync).style(counter_style(&[
+                        (Counter::Behind, repo.status.behind_count),
+                        (Counter::Unpushed, repo.status.unpushed_count),
+                    ])),
                     Cell::from(stash).style(Style::default().fg(theme::ACCENT_PINK)),
                     Cell::from(next).style(Style::default().fg(rec_color)),
                 ]);
@@ -215,17 +235,7 @@ fn render_repos(frame: &mut Frame, app: &App, area: Rect) {
         })
         .collect();
 
-    let widths = [
-        Constraint::Length(2),
-        Constraint::Fill(2),
-        Constraint::Fill(1),
-        Constraint::Length(10),
-        Constraint::Length(9),
-        Constraint::Length(6),
-        Constraint::Length(13),
-    ];
-
-    let title = format!("Repos ({})", filtered.len());
+    let title = tr("title-repos", &[("count", &filtered.len())]);
     let table = ratatui::widgets::Table::new(rows, widths)
         .header(header)
         .block(theme::block_focused(&title))
@@ -240,70 +250,97 @@ fn render_repos(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_stateful_widget(table, area, &mut state);
 }
 
+/// The branch, led by its linked ticket key when there is one.
+fn branch_cell<'a>(
+    ticket: Option<&RepoTicket>,
+    branch: &str,
+    style: Style,
+    width: usize,
+) -> Cell<'a> {
+    let Some(ticket) = ticket else {
+        return Cell::from(format::truncate(branch, width)).style(style);
+    };
+    let key = format!("{} ", ticket.key);
+    let rest = width.saturating_sub(format::width(&key));
+    Cell::from(Line::from(vec![
+        Span::styled(key, Style::default().fg(theme::ACCENT_CYAN)),
+        Span::styled(format::truncate(branch, rest), style),
+    ]))
+}
+
 fn render_worktrees(frame: &mut Frame, app: &App, area: Rect) {
     if app.dashboard.worktrees.is_empty() {
-        widgets::render_empty_state(frame, area, "◇", "No worktree data yet.");
+        widgets::render_empty_state(frame, area, "◇", &t("worktrees-empty"));
         return;
     }
 
     let header = Row::new(vec![
-        Cell::from("REPO"),
-        Cell::from("PATH"),
-        Cell::from("BRANCH"),
-        Cell::from("STATE"),
-        Cell::from("ACTION"),
+        Cell::from(t("col-repo")),
+        Cell::from(t("col-path")),
+        Cell::from(t("col-branch")),
+        Cell::from(t("col-state")),
+        Cell::from(t("col-action")),
     ])
     .style(theme::style_header());
 
+    let widths = [
+        Constraint::Length(22),
+        Constraint::Fill(1),
+        Constraint::Length(18),
+        Constraint::Length(10),
+        Constraint::Length(16),
+    ];
+    let path_cells = widgets::column_widths(area, &widths)[1];
     let rows: Vec<Row> = app
         .dashboard
         .worktrees
         .iter()
         .map(|r| {
             let state_text = if r.detached {
-                "detached"
+                t("worktree-detached")
             } else if r.bare {
-                "bare"
+                t("worktree-bare")
             } else {
-                "normal"
+                t("worktree-normal")
             };
             let state_color = if r.detached {
-                theme::ACCENT_YELLOW
+                theme::palette().warn
             } else if r.bare {
                 theme::FG_DIMMED
             } else {
-                theme::ACCENT_GREEN
+                theme::palette().ok
             };
             Row::new(vec![
                 Cell::from(r.repo.clone()).style(Style::default().fg(theme::FG_PRIMARY)),
-                Cell::from(r.path.clone()).style(Style::default().fg(theme::FG_SECONDARY)),
+                Cell::from(format::truncate_middle(
+                    &format::path(&app.config, &r.path),
+                    path_cells,
+                ))
+                .style(Style::default().fg(theme::FG_SECONDARY)),
                 Cell::from(r.branch.clone()).style(Style::default().fg(theme::FG_PRIMARY)),
                 Cell::from(state_text).style(Style::default().fg(state_color)),
                 Cell::from(
                     r.action
                         .as_ref()
                         .map(|a| a.label.clone())
-                        .unwrap_or_else(|| "—".to_string()),
+                        .unwrap_or_else(|| theme::glyph("—").into_owned()),
                 )
                 .style(Style::default().fg(theme::ACCENT_CYAN)),
             ])
         })
         .collect();
 
-    let title = format!("Worktrees ({})", app.dashboard.worktrees.len());
+    let title = tr(
+        "title-worktrees",
+        &[("count", &app.dashboard.worktrees.len())],
+    );
     widgets::render_styled_table(
         frame,
         area,
         &title,
         header,
         rows,
-        [
-            Constraint::Length(22),
-            Constraint::Fill(1),
-            Constraint::Length(18),
-            Constraint::Length(10),
-            Constraint::Length(16),
-        ],
+        widths,
         app.selected,
         app.dashboard.worktrees.len(),
     );
@@ -311,21 +348,16 @@ fn render_worktrees(frame: &mut Frame, app: &App, area: Rect) {
 
 fn render_processes(frame: &mut Frame, app: &App, area: Rect) {
     if app.dashboard.processes.is_empty() {
-        widgets::render_empty_state(
-            frame,
-            area,
-            "◇",
-            "No repo-scoped running processes detected.",
-        );
+        widgets::render_empty_state(frame, area, "◇", &t("processes-empty"));
         return;
     }
 
     let header = Row::new(vec![
-        Cell::from("REPO"),
-        Cell::from("PID"),
-        Cell::from("ELAPSED"),
-        Cell::from("COMMAND"),
-        Cell::from("ACTION"),
+        Cell::from(t("col-repo")),
+        Cell::from(t("col-pid")),
+        Cell::from(t("col-elapsed")),
+        Cell::from(t("col-command")),
+        Cell::from(t("col-action")),
     ])
     .style(theme::style_header());
 
@@ -344,14 +376,17 @@ fn render_processes(frame: &mut Frame, app: &App, area: Rect) {
                     p.action
                         .as_ref()
                         .map(|a| a.label.clone())
-                        .unwrap_or_else(|| "—".to_string()),
+                        .unwrap_or_else(|| theme::glyph("—").into_owned()),
                 )
                 .style(Style::default().fg(theme::ACCENT_CYAN)),
             ])
         })
         .collect();
 
-    let title = format!("Processes ({})", app.dashboard.processes.len());
+    let title = tr(
+        "title-processes",
+        &[("count", &app.dashboard.processes.len())],
+    );
     widgets::render_styled_table(
         frame,
         area,
@@ -372,21 +407,23 @@ fn render_processes(frame: &mut Frame, app: &App, area: Rect) {
 
 fn render_dependencies(frame: &mut Frame, app: &App, area: Rect) {
     if app.dashboard.dependencies.is_empty() {
-        widgets::render_empty_state(
+        widgets::render_empty_state_guide(
             frame,
             area,
             "◇",
-            "No known dependency manifests found in scanned repos.",
+            &t("deps-empty"),
+            &t("deps-empty-hint"),
+            Some(("s", &t("deps-empty-key"))),
         );
         return;
     }
 
     let header = Row::new(vec![
-        Cell::from("REPO"),
-        Cell::from("ECOSYSTEMS"),
-        Cell::from("ISSUES"),
-        Cell::from("DETAILS"),
-        Cell::from("ACTION"),
+        Cell::from(t("col-repo")),
+        Cell::from(t("col-ecosystems")),
+        Cell::from(t("col-issues")),
+        Cell::from(t("col-details")),
+        Cell::from(t("col-action")),
     ])
     .style(theme::style_header());
 
@@ -396,14 +433,14 @@ fn render_dependencies(frame: &mut Frame, app: &App, area: Rect) {
         .iter()
         .map(|d| {
             let (issue_text, issue_color) = if d.issue_count == 0 {
-                ("✓".to_string(), theme::ACCENT_GREEN)
+                (theme::glyph("✓").into_owned(), theme::palette().ok)
             } else {
                 (
                     d.issue_count.to_string(),
                     if d.issue_count > 3 {
-                        theme::ACCENT_RED
+                        theme::palette().critical
                     } else {
-                        theme::ACCENT_YELLOW
+                        theme::palette().warn
                     },
                 )
             };
@@ -413,7 +450,7 @@ fn render_dependencies(frame: &mut Frame, app: &App, area: Rect) {
                 Cell::from(d.ecosystems.join(", ")).style(Style::default().fg(theme::FG_SECONDARY)),
                 Cell::from(issue_text).style(Style::default().fg(issue_color)),
                 Cell::from(if d.issues.is_empty() {
-                    "clean".to_string()
+                    t("deps-clean")
                 } else {
                     d.issues.join("; ")
                 })
@@ -422,14 +459,17 @@ fn render_dependencies(frame: &mut Frame, app: &App, area: Rect) {
                     d.action
                         .as_ref()
                         .map(|a| a.label.clone())
-                        .unwrap_or_else(|| "—".to_string()),
+                        .unwrap_or_else(|| theme::glyph("—").into_owned()),
                 )
                 .style(Style::default().fg(theme::ACCENT_CYAN)),
             ])
         })
         .collect();
 
-    let title = format!("Dependencies ({})", app.dashboard.dependencies.len());
+    let title = tr(
+        "title-dependencies",
+        &[("count", &app.dashboard.dependencies.len())],
+    );
     widgets::render_styled_table(
         frame,
         area,
@@ -448,19 +488,86 @@ fn render_dependencies(frame: &mut Frame, app: &App, area: Rect) {
     );
 }
 
+fn render_local_deps(frame: &mut Frame, app: &App, area: Rect) {
+    if app.dashboard.local_deps.is_empty() {
+        widgets::render_empty_state_guide(
+            frame,
+            area,
+            "◇",
+            &t("local-deps-empty"),
+            &t("local-deps-empty-hint"),
+            None,
+        );
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from(t("col-dependency")),
+        Cell::from(t("col-used-by")),
+        Cell::from(t("col-via")),
+        Cell::from(t("col-unshared")),
+    ])
+    .style(theme::style_header());
+
+    let rows: Vec<Row> = app
+        .dashboard
+        .local_deps
+        .iter()
+        .map(|d| {
+            let (unshared, color) = match (d.dependency_dirty, d.dependency_unpushed) {
+                (0, 0) => (t("local-deps-shared"), theme::palette().ok),
+                (dirty, unpushed) => (
+                    tr(
+                        "local-deps-unshared",
+                        &[("dirty", &dirty), ("unpushed", &unpushed)],
+                    ),
+                    theme::palette().warn,
+                ),
+            };
+            Row::new(vec![
+                Cell::from(d.dependency.clone()).style(Style::default().fg(theme::FG_PRIMARY)),
+                Cell::from(d.dependent.clone()).style(Style::default().fg(theme::FG_SECONDARY)),
+                Cell::from(format!("{} {}", d.kind, d.package))
+                    .style(Style::default().fg(theme::FG_DIMMED)),
+                Cell::from(unshared).style(Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let title = tr(
+        "title-local-dependencies",
+        &[("count", &app.dashboard.local_deps.len())],
+    );
+    widgets::render_styled_table(
+        frame,
+        area,
+        &title,
+        header,
+        rows,
+        [
+            Constraint::Length(22),
+            Constraint::Length(22),
+            Constraint::Fill(1),
+            Constraint::Length(24),
+        ],
+        app.selected,
+        app.dashboard.local_deps.len(),
+    );
+}
+
 fn render_env_audit(frame: &mut Frame, app: &App, area: Rect) {
     if app.dashboard.env_audit.is_empty() {
-        widgets::render_empty_state(frame, area, "◇", "No .env files found in scanned repos.");
+        widgets::render_empty_state(frame, area, "◇", &t("env-empty"));
         return;
     }
 
     let header = Row::new(vec![
-        Cell::from("REPO"),
-        Cell::from("FILES"),
-        Cell::from("MISSING"),
-        Cell::from("EXTRA"),
-        Cell::from("TRACKED"),
-        Cell::from("ACTION"),
+        Cell::from(t("col-repo")),
+        Cell::from(t("col-files")),
+        Cell::from(t("col-missing")),
+        Cell::from(t("col-extra")),
+        Cell::from(t("col-tracked")),
+        Cell::from(t("col-action")),
     ])
     .style(theme::style_header());
 
@@ -477,32 +584,35 @@ fn render_env_audit(frame: &mut Frame, app: &App, area: Rect) {
                 Cell::from(e.env_files.join(", ")).style(Style::default().fg(theme::FG_SECONDARY)),
                 Cell::from(missing_count.to_string()).style(Style::default().fg(
                     if missing_count == 0 {
-                        theme::ACCENT_GREEN
+                        theme::palette().ok
                     } else {
-                        theme::ACCENT_YELLOW
+                        theme::palette().warn
                     },
                 )),
                 Cell::from(e.extra_keys.len().to_string())
                     .style(Style::default().fg(theme::FG_PRIMARY)),
                 Cell::from(tracked_count.to_string()).style(Style::default().fg(
                     if tracked_count == 0 {
-                        theme::ACCENT_GREEN
+                        theme::palette().ok
                     } else {
-                        theme::ACCENT_RED
+                        theme::palette().critical
                     },
                 )),
                 Cell::from(
                     e.action
                         .as_ref()
                         .map(|a| a.label.clone())
-                        .unwrap_or_else(|| "—".to_string()),
+                        .unwrap_or_else(|| theme::glyph("—").into_owned()),
                 )
                 .style(Style::default().fg(theme::ACCENT_CYAN)),
             ])
         })
         .collect();
 
-    let title = format!("Env Audit ({})", app.dashboard.env_audit.len());
+    let title = tr(
+        "title-env-audit",
+        &[("count", &app.dashboard.env_audit.len())],
+    );
     widgets::render_styled_table(
         frame,
         area,
@@ -522,22 +632,476 @@ fn render_env_audit(frame: &mut Frame, app: &App, area: Rect) {
     );
 }
 
+fn render_licenses(frame: &mut Frame, app: &App, area: Rect) {
+    if app.dashboard.licenses.is_empty() {
+        widgets::render_empty_state_guide(
+            frame,
+            area,
+            "◇",
+            &t("licenses-empty"),
+            &t("licenses-empty-hint"),
+            None,
+        );
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from(t("col-repo")),
+        Cell::from(t("col-license")),
+        Cell::from(t("col-deps")),
+        Cell::from(t("col-flagged")),
+    ])
+    .style(theme::style_header());
+
+    let rows: Vec<Row> = app
+        .dashboard
+        .licenses
+        .iter()
+        .map(|l| {
+            let (declared, declared_color) = match (&l.declared, l.commercial) {
+                (Some(license), false) => (license.clone(), theme::FG_SECONDARY),
+                (Some(license), true) => (
+                    tr("licenses-commercial", &[("license", license)]),
+                    theme::ACCENT_CYAN,
+                ),
+                (None, _) => (t("licenses-none-commercial"), theme::ACCENT_CYAN),
+            };
+            let deps = if l.dependencies_scanned == 0 {
+                theme::glyph("—").into_owned()
+            } else if l.unknown_licenses > 0 {
+                tr(
+                    "licenses-unknown",
+                    &[
+                        ("count", &l.dependencies_scanned),
+                        ("unknown", &l.unknown_licenses),
+                    ],
+                )
+            } else {
+                l.dependencies_scanned.to_string()
+            };
+            let (flagged, flagged_color) = if l.flagged.is_empty() {
+                (theme::glyph("✓").into_owned(), theme::palette().ok)
+            } else {
+                (
+                    l.flagged
+                        .iter()
+                        .map(|f| format!("{} ({})", f.package, f.license))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    if l.flagged.iter().any(|f| f.copyleft) {
+                        theme::palette().critical
+                    } else {
+                        theme::palette().warn
+                    },
+                )
+            };
+            Row::new(vec![
+                Cell::from(l.repo.clone()).style(Style::default().fg(theme::FG_PRIMARY)),
+                Cell::from(declared).style(Style::default().fg(declared_color)),
+                Cell::from(deps).style(Style::default().fg(theme::FG_DIMMED)),
+                Cell::from(flagged).style(Style::default().fg(flagged_color)),
+            ])
+        })
+        .collect();
+
+    let title = tr(
+        "title-licenses",
+        &[("count", &app.dashboard.licenses.len())],
+    );
+    widgets::render_styled_table(
+        frame,
+        area,
+        &title,
+        header,
+        rows,
+        [
+            Constraint::Length(22),
+            Constraint::Length(28),
+            Constraint::Length(16),
+            Constraint::Fill(1),
+        ],
+        app.selected,
+        app.dashboard.licenses.len(),
+    );
+}
+
+fn render_pull_requests(frame: &mut Frame, app: &App, area: Rect) {
+    if app.dashboard.pull_requests.is_empty() {
+        let message = if app.config.forge.enabled {
+            t("prs-empty")
+        } else {
+            t("prs-off")
+        };
+        widgets::render_empty_state_guide(frame, area, "⇄", &message, &t("prs-empty-hint"), None);
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from(t("col-repo")),
+        Cell::from(t("col-branch")),
+        Cell::from(t("col-pull-request")),
+        Cell::from(t("col-review")),
+        Cell::from(t("col-ci")),
+    ])
+    .style(theme::style_header());
+
+    let rows: Vec<Row> = app
+        .dashboard
+        .pull_requests
+        .iter()
+        .map(|row| {
+            let (pr, pr_color) = match (&row.pull_request, &row.error) {
+                (_, Some(err)) => (err.clone(), theme::palette().critical),
+                (Some(pr), None) => (format!("#{} {}", pr.number, pr.title), theme::FG_PRIMARY),
+                (None, None) => (t("prs-none"), theme::FG_DIMMED),
+            };
+            let (review, review_color) = match &row.pull_request {
+                Some(pr) if pr.draft => (t("prs-draft"), theme::FG_DIMMED),
+                Some(pr) if pr.awaiting_review => (t("prs-awaiting"), theme::palette().warn),
+                Some(_) => (theme::glyph("—").into_owned(), theme::FG_DIMMED),
+                None => (String::new(), theme::FG_DIMMED),
+            };
+            let ci_color = match row.ci {
+                CiStatus::Passing => theme::palette().ok,
+                CiStatus::Failing => theme::palette().critical,
+                CiStatus::Running => theme::palette().warn,
+                CiStatus::Unknown => theme::FG_DIMMED,
+            };
+            Row::new(vec![
+                Cell::from(row.repo.clone()).style(Style::default().fg(theme::FG_PRIMARY)),
+                Cell::from(row.branch.clone()).style(Style::default().fg(theme::ACCENT_CYAN)),
+                Cell::from(pr).style(Style::default().fg(pr_color)),
+                Cell::from(review).style(Style::default().fg(review_color)),
+                Cell::from(row.ci.label()).style(Style::default().fg(ci_color)),
+            ])
+        })
+        .collect();
+
+    let title = tr(
+        "title-pull-requests",
+        &[("count", &app.dashboard.pull_requests.len())],
+    );
+    widgets::render_styled_table(
+        frame,
+        area,
+        &title,
+        header,
+        rows,
+        [
+            Constraint::Length(22),
+            Constraint::Length(24),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+            Constraint::Length(9),
+        ],
+        app.selected,
+        app.dashboard.pull_requests.len(),
+    );
+}
+
+fn render_ai_clients(frame: &mut Frame, app: &App, area: Rect) {
+    if app.dashboard.ai_clients.is_empty() {
+        if app.is_scanning {
+            widgets::render_empty_state(frame, area, "…", &t("ai-clients-loading"));
+        } else {
+            widgets::render_empty_state_guide(
+                frame,
+                area,
+                "◇",
+                &t("ai-clients-empty"),
+                &t("ai-clients-empty-hint"),
+                None,
+            );
+        }
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from(t("col-client")),
+        Cell::from(t("col-file")),
+        Cell::from(t("col-servers")),
+        Cell::from(t("col-models")),
+        Cell::from(t("col-perms")),
+        Cell::from(t("col-drift")),
+    ])
+    .style(theme::style_header());
+
+    let rows: Vec<Row> = app
+        .dashboard
+        .ai_clients
+        .iter()
+        .map(|c| {
+            let servers = c
+                .servers
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let (drift, drift_color) = match (&c.error, c.drift.len()) {
+                (Some(_), _) => (t("ai-clients-unreadable"), theme::palette().critical),
+                (None, 0) => (t("ai-clients-in-sync"), theme::palette().ok),
+                (None, n) => (
+                    tr("ai-clients-drift", &[("count", &n)]),
+                    theme::palette().warn,
+                ),
+            };
+            Row::new(vec![
+                Cell::from(c.client.clone()).style(Style::default().fg(theme::FG_PRIMARY)),
+                Cell::from(c.path.clone()).style(Style::default().fg(theme::FG_SECONDARY)),
+                Cell::from(if servers.is_empty() {
+                    theme::glyph("—").into_owned()
+                } else {
+                    servers
+                })
+                .style(Style::default().fg(theme::FG_PRIMARY)),
+                Cell::from(c.models.join(", ")).style(Style::default().fg(theme::ACCENT_CYAN)),
+                Cell::from(c.permissions.len().to_string())
+                    .style(Style::default().fg(theme::FG_SECONDARY)),
+                Cell::from(drift).style(Style::default().fg(drift_color)),
+            ])
+        })
+        .collect();
+
+    let title = tr(
+        "title-ai-clients",
+        &[("count", &app.dashboard.ai_clients.len())],
+    );
+    widgets::render_styled_table(
+        frame,
+        area,
+        &title,
+        header,
+        rows,
+        [
+            Constraint::Length(16),
+            Constraint::Fill(1),
+            Constraint::Length(28),
+            Constraint::Length(16),
+            Constraint::Length(6),
+            Constraint::Length(16),
+        ],
+        app.selected,
+        app.dashboard.ai_clients.len(),
+    );
+}
+
+fn render_action_log(frame: &mut Frame, app: &App, area: Rect) {
+    if app.action_log.is_empty() {
+        let path = format::path(&app.config, crate::audit::log_path());
+        widgets::render_empty_state_guide(
+            frame,
+            area,
+            "◇",
+            &t("action-log-empty"),
+            &tr("action-log-empty-hint", &[("path", &path)]),
+            None,
+        );
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from(t("col-when")),
+        Cell::from(t("col-repo")),
+        Cell::from(t("col-action")),
+        Cell::from(t("col-result")),
+        Cell::from(t("col-output")),
+    ])
+    .style(theme::style_header());
+
+    let rows: Vec<Row> = app
+        .action_log
+        .iter()
+        .map(|entry| {
+            let repo = entry
+                .repo
+                .as_deref()
+                .and_then(|p| Path::new(p).file_name())
+                .map_or(theme::glyph("—").into_owned(), |name| {
+                    name.to_string_lossy().to_string()
+                });
+            let (result, result_color) = match (entry.ok, entry.exit_code) {
+                (true, _) => (t("action-log-ok"), theme::palette().ok),
+                (false, Some(code)) => (
+                    tr("action-log-exit", &[("code", &code)]),
+                    theme::palette().critical,
+                ),
+                (false, None) if entry.refused => (t("action-log-refused"), theme::palette().warn),
+                (false, None) => (t("action-log-failed"), theme::palette().critical),
+            };
+            Row::new(vec![
+                Cell::from(format::ago_epoch(entry.at))
+                    .style(Style::default().fg(theme::FG_DIMMED)),
+                Cell::from(repo).style(Style::default().fg(theme::FG_PRIMARY)),
+                Cell::from(entry.action.clone()).style(Style::default().fg(theme::ACCENT_CYAN)),
+                Cell::from(result).style(Style::default().fg(result_color)),
+                Cell::from(entry.output.clone()).style(Style::default().fg(theme::FG_SECONDARY)),
+            ])
+        })
+        .collect();
+
+    let title = tr("title-action-log", &[("count", &app.action_log.len())]);
+    widgets::render_styled_table(
+        frame,
+        area,
+        &title,
+        header,
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(20),
+            Constraint::Length(22),
+            Constraint::Length(9),
+            Constraint::Fill(1),
+        ],
+        app.selected,
+        app.action_log.len(),
+    );
+}
+
+fn render_stats(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(stats) = app.dashboard.scan_stats.as_ref() else {
+        let msg = if app.is_scanning {
+            t("stats-waiting")
+        } else {
+            t("stats-none")
+        };
+        widgets::render_empty_state(frame, area, "◇", &msg);
+        return;
+    };
+
+    let chunks = Layout::vertical([Constraint::Length(7), Constraint::Fill(1)]).split(area);
+    let label = |text: String| Span::styled(text, Style::default().fg(theme::FG_DIMMED));
+    let value = |text: String| Span::styled(text, Style::default().fg(theme::FG_PRIMARY));
+    let checked = stats.cache_hits + stats.cache_misses;
+    let hit_rate = (stats.cache_hits * 100).checked_div(checked).unwrap_or(0);
+    let ignored = if stats.ignored.is_empty() {
+        t("stats-ignored-none")
+    } else {
+        format!("{} ({})", stats.ignored.len(), stats.ignored.join(", "))
+    };
+    let lines = vec![
+        Line::from(vec![
+            label(format!(" {:<13} ", t("stats-last-scan"))),
+            value(format::millis(stats.total_ms())),
+            label(format!("   {} ", t("stats-discover"))),
+            value(format::millis(stats.discover_ms)),
+            label(format!("{}{} ", theme::glyph(" · "), t("stats-status"))),
+            value(format::millis(stats.status_ms)),
+            label(format!("{}{} ", theme::glyph(" · "), t("stats-collectors"))),
+            value(format::millis(stats.collect_ms)),
+        ]),
+        Line::from(vec![
+            label(format!(" {:<13} ", t("stats-cache"))),
+            value(tr(
+                "stats-cache-counts",
+                &[
+                    ("hits", &stats.cache_hits),
+                    ("misses", &stats.cache_misses),
+                    ("deferred", &stats.deferred),
+                ],
+            )),
+            Span::styled(
+                format!("   {}", tr("stats-hit-rate", &[("rate", &hit_rate)])),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
+        ]),
+        Line::from(vec![
+            label(format!(" {:<13} ", t("stats-repos"))),
+            value(tr("stats-watched", &[("count", &stats.repos_found)])),
+            label(format!("   {} ", t("stats-ignored"))),
+            value(ignored),
+        ]),
+        Line::from(vec![
+            label(format!(" {:<13} ", t("stats-git"))),
+            value(stats.git_spawns.to_string()),
+        ]),
+        Line::from(vec![
+            label(format!(" {:<13} ", t("stats-refresh"))),
+            value(
+                app.watch_coverage
+                    .as_ref()
+                    .map_or(t("stats-polled"), |w| w.summary()),
+            ),
+        ]),
+    ];
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(theme::block_default(&t("stats-title")))
+            .wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+
+    if stats.slowest.is_empty() {
+        widgets::render_empty_state(frame, chunks[1], "✓", &t("stats-all-cached"));
+        return;
+    }
+    let header = Row::new(vec![
+        Cell::from(t("col-repo")),
+        Cell::from(t("col-status")),
+        Cell::from(t("col-path")),
+    ])
+    .style(theme::style_header());
+    let widths = [
+        Constraint::Length(24),
+        Constraint::Length(10),
+        Constraint::Fill(1),
+    ];
+    let path_cells = widgets::column_widths(chunks[1], &widths)[2];
+    let rows: Vec<Row> = stats
+        .slowest
+        .iter()
+        .map(|t| {
+            let color = if t.ms >= 1000 {
+                theme::ACCENT_ORANGE
+            } else {
+                theme::FG_SECONDARY
+            };
+            Row::new(vec![
+                Cell::from(t.repo.clone()).style(Style::default().fg(theme::FG_PRIMARY)),
+                Cell::from(format::millis(t.ms)).style(Style::default().fg(color)),
+                Cell::from(format::truncate_middle(
+                    &format::path(&app.config, &t.path),
+                    path_cells,
+                ))
+                .style(Style::default().fg(theme::FG_DIMMED)),
+            ])
+        })
+        .collect();
+    widgets::render_styled_table(
+        frame,
+        chunks[1],
+        &t("stats-slowest"),
+        header,
+        rows,
+        widths,
+        app.selected,
+        stats.slowest.len(),
+    );
+}
+
 fn render_mcp(frame: &mut Frame, app: &App, area: Rect) {
     if app.dashboard.mcp_servers.is_empty() {
         if app.is_scanning {
-            widgets::render_empty_state(frame, area, "…", "Loading MCP health data…");
+            widgets::render_empty_state(frame, area, "…", &t("mcp-loading"));
         } else {
-            widgets::render_empty_state(frame, area, "◇", "No MCP configuration files detected.");
+            widgets::render_empty_state_guide(
+                frame,
+                area,
+                "◇",
+                &t("mcp-empty"),
+                &t("mcp-empty-hint"),
+                Some(("x", &t("mcp-empty-key"))),
+            );
         }
         return;
     }
 
     let header = Row::new(vec![
-        Cell::from("SERVER"),
-        Cell::from("SOURCE"),
-        Cell::from("HEALTH"),
-        Cell::from("DETAIL"),
-        Cell::from("ACTION"),
+        Cell::from(t("col-server")),
+        Cell::from(t("col-source")),
+        Cell::from(t("col-health")),
+        Cell::from(t("col-detail")),
+        Cell::from(t("col-action")),
     ])
     .style(theme::style_header());
 
@@ -547,27 +1111,38 @@ fn render_mcp(frame: &mut Frame, app: &App, area: Rect) {
         .iter()
         .map(|m| {
             let (health_text, health_color) = if m.healthy {
-                ("● healthy", theme::ACCENT_GREEN)
+                (t("mcp-healthy"), theme::palette().ok)
             } else {
-                ("● unhealthy", theme::ACCENT_RED)
+                (t("mcp-unhealthy"), theme::palette().critical)
             };
             Row::new(vec![
                 Cell::from(m.server_name.clone()).style(Style::default().fg(theme::FG_PRIMARY)),
                 Cell::from(m.source.clone()).style(Style::default().fg(theme::FG_SECONDARY)),
                 Cell::from(health_text).style(Style::default().fg(health_color)),
-                Cell::from(m.detail.clone()).style(Style::default().fg(theme::FG_PRIMARY)),
+                Cell::from(if m.lint.is_empty() {
+                    m.detail.clone()
+                } else {
+                    tr(
+                        "mcp-lint",
+                        &[("count", &m.lint.len()), ("detail", &m.detail)],
+                    )
+                })
+                .style(Style::default().fg(theme::FG_PRIMARY)),
                 Cell::from(
                     m.action
                         .as_ref()
                         .map(|a| a.label.clone())
-                        .unwrap_or_else(|| "—".to_string()),
+                        .unwrap_or_else(|| theme::glyph("—").into_owned()),
                 )
                 .style(Style::default().fg(theme::ACCENT_CYAN)),
             ])
         })
         .collect();
 
-    let title = format!("MCP Health ({})", app.dashboard.mcp_servers.len());
+    let title = tr(
+        "title-mcp-health",
+        &[("count", &app.dashboard.mcp_servers.len())],
+    );
     widgets::render_styled_table(
         frame,
         area,
@@ -587,64 +1162,111 @@ fn render_mcp(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_ai_costs(frame: &mut Frame, app: &App, area: Rect) {
-    if app.dashboard.providers.is_empty() {
-        if app.is_scanning {
-            widgets::render_empty_state(frame, area, "…", "Loading AI usage and cost data…");
+    if app.dashboard.providers.is_empty() && app.is_scanning {
+        widgets::render_empty_state(frame, area, "…", &t("costs-loading"));
+        return;
+    }
+    let open_config_label = t("costs-open-config");
+    let open_config = Some(("x", open_config_label.as_str()));
+    if !app.dashboard.providers.is_empty()
+        && app
+            .dashboard
+            .providers
+            .iter()
+            .all(|p| p.data_source == "disabled")
+    {
+        let reason = if app.config.providers.privacy_mode {
+            t("costs-privacy")
         } else {
-            widgets::render_empty_state(frame, area, "◇", "No AI provider data available yet.");
-        }
+            t("costs-disabled")
+        };
+        widgets::render_empty_state_guide(
+            frame,
+            area,
+            "⊘",
+            &reason,
+            &t("costs-disabled-hint"),
+            open_config,
+        );
+        return;
+    }
+    if app.ai_costs_unconfigured() {
+        widgets::render_empty_state_guide(
+            frame,
+            area,
+            "◇",
+            &t("costs-empty"),
+            &t("costs-empty-hint"),
+            open_config,
+        );
         return;
     }
 
     let header = Row::new(vec![
-        Cell::from("PROVIDER"),
-        Cell::from("SOURCE"),
-        Cell::from("UPDATED"),
-        Cell::from("CONFIG"),
-        Cell::from("SESSIONS"),
-        Cell::from("INPUT TOKENS"),
-        Cell::from("OUTPUT TOKENS"),
-        Cell::from("COST USD"),
-        Cell::from("NOTES"),
+        Cell::from(t("col-provider")),
+        Cell::from(t("col-source")),
+        Cell::from(t("col-updated")),
+        Cell::from(t("col-config")),
+        Cell::from(t("col-sessions")),
+        Cell::from(t("col-input-tokens")),
+        Cell::from(t("col-output-tokens")),
+        Cell::from(t("col-cost")),
+        Cell::from(t("col-forecast")),
+        Cell::from(t("col-notes")),
     ])
     .style(theme::style_header());
 
+    let display = &app.config.display;
     let rows: Vec<Row> = app
         .dashboard
         .providers
         .iter()
         .map(|p| {
             let cost_color = if p.estimated_cost_usd > 10.0 {
-                theme::ACCENT_RED
+                theme::palette().critical
             } else if p.estimated_cost_usd > 1.0 {
-                theme::ACCENT_ORANGE
+                theme::palette().high
             } else if p.estimated_cost_usd > 0.0 {
-                theme::ACCENT_YELLOW
+                theme::palette().warn
             } else {
                 theme::FG_DIMMED
             };
 
+            let forecast_color = match (p.forecast_usd, p.budget_usd) {
+                (Some(forecast), Some(budget)) if forecast > budget => theme::palette().critical,
+                _ => theme::FG_SECONDARY,
+            };
+
             Row::new(vec![
                 Cell::from(p.provider.as_str()).style(Style::default().fg(theme::FG_PRIMARY)),
                 Cell::from(p.data_source.clone()).style(Style::default().fg(theme::FG_SECONDARY)),
-                Cell::from(format_updated_secs(p.source_updated_at_epoch_secs))
+                Cell::from(format::ago_epoch(p.source_updated_at_epoch_secs))
                     .style(Style::default().fg(theme::FG_DIMMED)),
-                Cell::from(if p.configured { "yes" } else { "no" }).style(Style::default().fg(
-                    if p.configured {
-                        theme::ACCENT_GREEN
-                    } else {
-                        theme::ACCENT_YELLOW
-                    },
-                )),
+                Cell::from(if p.configured {
+                    t("costs-yes")
+                } else {
+                    t("costs-no")
+                })
+                .style(Style::default().fg(if p.configured {
+                    theme::palette().ok
+                } else {
+                    theme::palette().warn
+                })),
                 Cell::from(p.sessions.to_string()).style(Style::default().fg(theme::FG_PRIMARY)),
-                Cell::from(widgets::format_number(p.total_input_tokens))
+                Cell::from(format::number(display, p.total_input_tokens))
                     .style(Style::default().fg(theme::FG_PRIMARY)),
-                Cell::from(widgets::format_number(p.total_output_tokens))
+                Cell::from(format::number(display, p.total_output_tokens))
                     .style(Style::default().fg(theme::FG_PRIMARY)),
-                Cell::from(format!("${:.2}", p.estimated_cost_usd))
+                Cell::from(format::currency(display, p.estimated_cost_usd))
                     .style(Style::default().fg(cost_color)),
+                Cell::from(
+                    p.forecast_usd
+                        .map(|f| format::currency(display, f))
+                        .unwrap_or_else(|| theme::glyph("—").into_owned()),
+                )
+                .style(Style::default().fg(forecast_color)),
                 Cell::from(if p.notes.is_empty() {
-                    "—".to_string()
+                    theme::glyph("—").into_owned()
                 } else {
                     p.notes.join("; ")
                 })
@@ -653,7 +1275,10 @@ fn render_ai_costs(frame: &mut Frame, app: &App, area: Rect) {
         })
         .collect();
 
-    let title = format!("AI Usage & Cost ({})", app.dashboard.providers.len());
+    let title = tr(
+        "title-ai-usage-cost",
+        &[("count", &app.dashboard.providers.len())],
+    );
     widgets::render_styled_table(
         frame,
         area,
@@ -669,6 +1294,7 @@ fn render_ai_costs(frame: &mut Frame, app: &App, area: Rect) {
             Constraint::Length(14),
             Constraint::Length(14),
             Constraint::Length(10),
+            Constraint::Length(10),
             Constraint::Fill(1),
         ],
         app.selected,
@@ -676,20 +1302,86 @@ fn render_ai_costs(frame: &mut Frame, app: &App, area: Rect) {
     );
 }
 
-fn format_updated_secs(epoch_secs: i64) -> String {
-    if epoch_secs <= 0 {
-        return "unknown".to_string();
+/// The critical color past a `[thresholds]` critical limit, dimmed when every non-zero
+/// counter is below its warn limit.
+fn counter_style(counters: &[(Counter, usize)]) -> Style {
+    let counted = counters.iter().filter(|(_, count)| *count > 0);
+    match counted
+        .map(|(counter, count)| agent::counter_level(*counter, *count))
+        .max()
+    {
+        Some(Level::Critical) => Style::default()
+            .fg(theme::palette().critical)
+            .add_modifier(ratatui::style::Modifier::BOLD),
+        Some(Level::Quiet) => Style::default().fg(theme::FG_DIMMED),
+        _ => Style::default().fg(theme::FG_PRIMARY),
     }
-    let now = chrono::Utc::now().timestamp();
-    let delta = now.saturating_sub(epoch_secs);
-    if delta < 60 {
-        format!("{}s ago", delta)
-    } else if delta < 3600 {
-        format!("{}m ago", delta / 60)
-    } else if delta < 86_400 {
-        format!("{}h ago", delta / 3600)
+}
+
+/// `7 files · 2d` once local work is at least an hour old.
+fn with_age(text: String, since: Option<i64>, now: i64) -> String {
+    match since.map(|since| now - since) {
+        Some(age) if age >= 3600 => {
+            format!("{}{}{}", text, theme::glyph(" · "), format::age(age))
+        }
+        _ => text,
+    }
+}
+
+/// Changed paths by kind: `+` staged, `~` unstaged, `?` untracked, `!`
+/// conflicted. Statuses without a breakdown (e.g. from history) show a count.
+fn dirty_label(status: &RepoStatus) -> String {
+    let changes = status.changes;
+    let parts: Vec<String> = [
+        ('!', changes.conflicted),
+        ('+', changes.staged),
+        ('~', changes.unstaged),
+        ('?', changes.untracked),
+    ]
+    .into_iter()
+    .filter(|(_, count)| *count > 0)
+    .map(|(mark, count)| format!("{}{}", mark, count))
+    .collect();
+    match status.uncommitted_count {
+        0 => theme::glyph("—").into_owned(),
+        _ if !parts.is_empty() => parts.join(" "),
+        1 => t("dirty-one-file"),
+        n => tr("dirty-files", &[("count", &n)]),
+    }
+}
+
+/// Ahead/behind against the upstream, plus push-side deltas (`⇡`/`⇣`) when
+/// the branch pushes somewhere else.
+fn sync_label(status: &RepoStatus) -> String {
+    if !status.has_remote {
+        return "n/a".to_string();
+    }
+    let (ahead, push) = match &status.push {
+        Some(p) => (
+            p.upstream_ahead,
+            Some((status.unpushed_count, p.push_behind)),
+        ),
+        None => (status.unpushed_count, None),
+    };
+    let mut parts = Vec::new();
+    if ahead > 0 {
+        parts.push(format!("{}{}", theme::glyph("↑"), ahead));
+    }
+    if status.behind_count > 0 {
+        parts.push(format!("{}{}", theme::glyph("↓"), status.behind_count));
+    }
+    if let Some((push_ahead, push_behind)) = push {
+        if push_ahead > 0 {
+            parts.push(format!("{}{}", theme::glyph("⇡"), push_ahead));
+        }
+        if push_behind > 0 {
+            parts.push(format!("{}{}", theme::glyph("⇣"), push_behind));
+        }
+    }
+    if parts.is_empty() {
+        theme::glyph("—").into_owned()
     } else {
-        format!("{}d ago", delta / 86_400)
+        parts.join(" ")
     }
 }
 
@@ -704,24 +1396,85 @@ fn render_selected_detail(frame: &mut Frame, app: &App, area: Rect) {
     );
 }
 
+/// ` ticket=PROJ-142 "Login with SSO" status=In Progress`.
+fn ticket_detail(ticket: &RepoTicket) -> String {
+    let mut detail = format!(" ticket={}", ticket.key);
+    if let Some(title) = &ticket.title {
+        detail.push_str(&format!(" {:?}", title));
+    }
+    if let Some(status) = &ticket.status {
+        detail.push_str(&format!(" status={}", status));
+    }
+    if let Some(error) = &ticket.error {
+        detail.push_str(&format!(" (lookup failed: {})", error));
+    }
+    detail
+}
+
+/// ` (staged=1 unstaged=2 …)`, non-zero kinds only; empty when clean.
+fn change_breakdown(changes: &ChangeCounts) -> String {
+    let parts: Vec<String> = [
+        ("staged", changes.staged),
+        ("unstaged", changes.unstaged),
+        ("untracked", changes.untracked),
+        ("conflicted", changes.conflicted),
+    ]
+    .into_iter()
+    .filter(|(_, count)| *count > 0)
+    .map(|(kind, count)| format!("{}={}", kind, count))
+    .collect();
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", parts.join(" "))
+    }
+}
+
 fn selected_detail_text(app: &App) -> String {
     match app.section {
         DashboardSection::Repos => {
             if let Some(repo) = app.selected_repo() {
                 let rec = agent::recommend(repo);
                 format!(
-                    "repo={} path={} branch={} dirty={} ahead={} behind={} next={} reason={}",
+                    "repo={} path={} branch={} dirty={}{} ahead={} behind={}{}{}{}{} next={}{} reason={}",
                     repo.name,
-                    repo.path.display(),
+                    format::path(&app.config, &repo.path),
                     repo.status.branch,
                     repo.status.uncommitted_count,
+                    change_breakdown(&repo.status.changes),
                     repo.status.unpushed_count,
                     repo.status.behind_count,
+                    repo.status
+                        .push
+                        .as_ref()
+                        .map(|p| format!(
+                            " push={} push_ahead={} push_behind={}",
+                            p.target, repo.status.unpushed_count, p.push_behind
+                        ))
+                        .unwrap_or_default(),
+                    app.ownership
+                        .as_ref()
+                        .map(|o| o.detail())
+                        .unwrap_or_default(),
+                    app.watch_coverage
+                        .as_ref()
+                        .map(|w| if w.is_event_driven(&repo.path) {
+                            " refresh=events"
+                        } else {
+                            " refresh=polled"
+                        })
+                        .unwrap_or_default(),
+                    app.ticket_for(repo).map(ticket_detail).unwrap_or_default(),
                     rec.short_action,
+                    if app.suppressions.is_dismissed(repo, &rec) {
+                        " (dismissed)"
+                    } else {
+                        ""
+                    },
                     rec.reason
                 )
             } else {
-                "No selected repo".to_string()
+                t("detail-no-repo")
             }
         }
         DashboardSection::Worktrees => app
@@ -732,7 +1485,7 @@ fn selected_detail_text(app: &App) -> String {
                 format!(
                     "repo={} path={} branch={} detached={} bare={} action={}",
                     wt.repo,
-                    wt.path,
+                    format::path(&app.config, &wt.path),
                     wt.branch,
                     wt.detached,
                     wt.bare,
@@ -742,13 +1495,13 @@ fn selected_detail_text(app: &App) -> String {
                         .unwrap_or_else(|| "none".to_string())
                 )
             })
-            .unwrap_or_else(|| "No selected worktree".to_string()),
+            .unwrap_or_else(|| t("detail-no-worktree")),
         DashboardSection::Processes => app
             .dashboard
             .processes
             .get(app.selected)
             .map(|p| format!("repo={} pid={} elapsed={} cmd={}", p.repo, p.pid, p.elapsed, p.command))
-            .unwrap_or_else(|| "No selected process".to_string()),
+            .unwrap_or_else(|| t("detail-no-process")),
         DashboardSection::Dependencies => app
             .dashboard
             .dependencies
@@ -766,7 +1519,25 @@ fn selected_detail_text(app: &App) -> String {
                     }
                 )
             })
-            .unwrap_or_else(|| "No selected dependency row".to_string()),
+            .unwrap_or_else(|| t("detail-no-dependency")),
+        DashboardSection::LocalDeps => app
+            .dashboard
+            .local_deps
+            .get(app.selected)
+            .map(|d| {
+                format!(
+                    "{} {} {} via {} {} ({}) dirty={} unpushed={}",
+                    d.dependent,
+                    theme::glyph("→"),
+                    d.dependency,
+                    d.kind,
+                    d.package,
+                    d.dependency_path,
+                    d.dependency_dirty,
+                    d.dependency_unpushed
+                )
+            })
+            .unwrap_or_else(|| t("detail-no-local-dependency")),
         DashboardSection::EnvAudit => app
             .dashboard
             .env_audit
@@ -781,37 +1552,176 @@ fn selected_detail_text(app: &App) -> String {
                     e.tracked_secret_files.join(",")
                 )
             })
-            .unwrap_or_else(|| "No selected env audit row".to_string()),
+            .unwrap_or_else(|| t("detail-no-env")),
+        DashboardSection::Licenses => app
+            .dashboard
+            .licenses
+            .get(app.selected)
+            .map(|l| {
+                format!(
+                    "repo={} license={} commercial={} deps={} unknown={} flagged=[{}]",
+                    l.repo,
+                    l.declared.as_deref().unwrap_or("none"),
+                    l.commercial,
+                    l.dependencies_scanned,
+                    l.unknown_licenses,
+                    l.flagged
+                        .iter()
+                        .map(|f| format!("{} {}", f.package, f.license))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+            .unwrap_or_else(|| t("detail-no-license")),
         DashboardSection::McpHealth => app
             .dashboard
             .mcp_servers
             .get(app.selected)
             .map(|m| {
-                format!(
+                let mut text = format!(
                     "server={} source={} healthy={} detail={} command={}",
                     m.server_name, m.source, m.healthy, m.detail, m.command
-                )
+                );
+                for finding in &m.lint {
+                    let at = match finding.line {
+                        Some(line) => format!("{}:{}", m.source, line),
+                        None => m.source.clone(),
+                    };
+                    text.push_str(&format!(
+                        "\n{}: {}: {}",
+                        at,
+                        finding.severity.label(),
+                        finding.message
+                    ));
+                }
+                text
             })
-            .unwrap_or_else(|| "No selected MCP row".to_string()),
+            .unwrap_or_else(|| t("detail-no-mcp")),
         DashboardSection::AiCosts => app
             .dashboard
             .providers
             .get(app.selected)
             .map(|p| {
                 format!(
-                    "provider={} source={} updated={} sessions={} input={} output={} cost=${:.2} notes={}",
+                    "provider={} source={} updated={} sessions={} input={} output={} cost={}{}{} notes={}",
                     p.provider.as_str(),
                     p.data_source,
-                    format_updated_secs(p.source_updated_at_epoch_secs),
+                    format::ago_epoch(p.source_updated_at_epoch_secs),
                     p.sessions,
-                    p.total_input_tokens,
-                    p.total_output_tokens,
-                    p.estimated_cost_usd,
+                    format::number(&app.config.display, p.total_input_tokens),
+                    format::number(&app.config.display, p.total_output_tokens),
+                    format::currency(&app.config.display, p.estimated_cost_usd),
+                    p.forecast_usd
+                        .map(|f| format!(" forecast={}", format::currency(&app.config.display, f)))
+                        .unwrap_or_default(),
+                    p.budget_usd
+                        .map(|b| format!(" budget={}", format::currency(&app.config.display, b)))
+                        .unwrap_or_default(),
                     p.notes.join(" | ")
                 )
             })
-            .unwrap_or_else(|| "No selected provider row".to_string()),
-        DashboardSection::Home => "Use Home for overview alerts".to_string(),
+            .unwrap_or_else(|| t("detail-no-provider")),
+        DashboardSection::PullRequests => app
+            .dashboard
+            .pull_requests
+            .get(app.selected)
+            .map(|row| {
+                let pr = row
+                    .pull_request
+                    .as_ref()
+                    .map(|pr| format!("#{} {}", pr.number, pr.url))
+                    .unwrap_or_else(|| "none".to_string());
+                format!(
+                    "repo={} forge={} branch={} pr={} ci={} run={}{}{}Enter opens",
+                    row.repo,
+                    row.forge.label(),
+                    row.branch,
+                    pr,
+                    row.ci.label(),
+                    row.ci_url.as_deref().or(row.ci_name.as_deref()).unwrap_or("none"),
+                    row.error
+                        .as_ref()
+                        .map(|e| format!(" error={}", e))
+                        .unwrap_or_default(),
+                    theme::glyph(" · ")
+                )
+            })
+            .unwrap_or_else(|| t("detail-no-pull-request")),
+        DashboardSection::AiClients => app
+            .dashboard
+            .ai_clients
+            .get(app.selected)
+            .map(|c| {
+                if let Some(err) = &c.error {
+                    return format!("client={} file={} error={}", c.client, c.path, err);
+                }
+                let mut text = format!(
+                    "client={} file={} models=[{}] permissions=[{}]{}x opens",
+                    c.client,
+                    c.path,
+                    c.models.join(", "),
+                    c.permissions.join(", "),
+                    theme::glyph(" · ")
+                );
+                for server in &c.servers {
+                    text.push_str(&format!("\n{}: {}", server.name, server.command));
+                }
+                for drift in &c.drift {
+                    text.push_str(&format!("\ndrift: {}", drift));
+                }
+                text
+            })
+            .unwrap_or_else(|| t("detail-no-ai-client")),
+        DashboardSection::Settings => FIELDS
+            .get(app.selected)
+            .map(|field| {
+                let doc = config_docs::key_doc(field.section, field.key).unwrap_or_default();
+                let edit = match field.kind {
+                    FieldKind::Toggle => t("detail-toggle"),
+                    _ => t("detail-edit"),
+                };
+                tr(
+                    "detail-setting",
+                    &[
+                        ("name", &field.name()),
+                        ("doc", &doc.replace('\n', " ")),
+                        ("edit", &edit),
+                    ],
+                )
+            })
+            .unwrap_or_default(),
+        DashboardSection::ActionLog => app
+            .action_log
+            .get(app.selected)
+            .map(|entry| {
+                format!(
+                    "repo={} command={} exit={}{}{}",
+                    entry
+                        .repo
+                        .clone()
+                        .unwrap_or_else(|| theme::glyph("—").into_owned()),
+                    entry.command,
+                    entry
+                        .exit_code
+                        .map_or(theme::glyph("—").into_owned(), |c| c.to_string()),
+                    theme::glyph(" · "),
+                    entry.output
+                )
+            })
+            .unwrap_or_else(|| t("detail-no-action")),
+        DashboardSection::Stats => app
+            .dashboard
+            .scan_stats
+            .as_ref()
+            .and_then(|s| s.slowest.get(app.selected))
+            .map(|t| format!(
+                "repo={} status={} path={}",
+                t.repo,
+                format::millis(t.ms),
+                format::path(&app.config, &t.path)
+            ))
+            .unwrap_or_else(|| t("detail-no-slow-repo")),
+        DashboardSection::Home => t("detail-home"),
     }
 }
 
@@ -820,28 +1730,72 @@ fn elapsed_color(elapsed: &str) -> ratatui::style::Color {
     // Elapsed is typically "Xm Ys" or "Xs" format
     let lower = elapsed.to_lowercase();
     if lower.contains('h') {
-        return theme::ACCENT_RED;
+        return theme::palette().critical;
     }
     // Try to extract minute value
     if let Some(m_pos) = lower.find('m') {
         if let Ok(mins) = lower[..m_pos].trim().parse::<u32>() {
             return if mins >= 30 {
-                theme::ACCENT_RED
+                theme::palette().critical
             } else if mins >= 5 {
-                theme::ACCENT_ORANGE
+                theme::palette().high
             } else {
-                theme::ACCENT_YELLOW
+                theme::palette().warn
             };
         }
     }
     // Seconds only — short-lived
-    theme::ACCENT_GREEN
+    theme::palette().ok
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn sync_label_shows_push_side_for_triangular_branches() {
+        let mut status = RepoStatus {
+            has_remote: true,
+            unpushed_count: 2,
+            behind_count: 1,
+            ..Default::default()
+        };
+        assert_eq!(sync_label(&status), "↑2 ↓1");
+
+        status.push = Some(crate::git::PushDivergence {
+            target: "fork/feature".to_string(),
+            upstream_ahead: 5,
+            push_behind: 0,
+        });
+        assert_eq!(sync_label(&status), "↑5 ↓1 ⇡2");
+
+        status.has_remote = false;
+        assert_eq!(sync_label(&status), "n/a");
+    }
+
+    #[test]
+    fn dirty_label_breaks_changes_down_by_kind() {
+        let mut status = RepoStatus {
+            uncommitted_count: 4,
+            ..Default::default()
+        };
+        assert_eq!(dirty_label(&status), "4 files");
+        status.changes = ChangeCounts {
+            staged: 2,
+            unstaged: 1,
+            untracked: 1,
+            conflicted: 0,
+        };
+        assert_eq!(dirty_label(&status), "+2 ~1 ?1");
+        assert_eq!(
+            change_breakdown(&status.changes),
+            " (staged=2 unstaged=1 untracked=1)"
+        );
+        status.changes.conflicted = 1;
+        assert_eq!(dirty_label(&status), "!1 +2 ~1 ?1");
+        assert_eq!(dirty_label(&RepoStatus::default()), "—");
+    }
+
     #[test]
     fn elapsed_seconds_is_green() {
         assert_eq!(elapsed_color("30s"), theme::ACCENT_GREEN);
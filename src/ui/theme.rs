@@ -1,7 +1,13 @@
+use crate::config::DisplayConfig;
+use crate::git::StatusColor;
 use ratatui::{
     style::{Color, Modifier, Style},
+    symbols::border,
     widgets::{Block, BorderType},
 };
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
 
 // ─── Background tones ──────────────────────────────────────────────────────
 pub const BG_HIGHLIGHT: Color = Color::Rgb(38, 40, 52); // selected row
@@ -28,6 +34,276 @@ pub const ACCENT_ORANGE: Color = Color::Rgb(230, 160, 80); // high priority
 pub const ACCENT_PINK: Color = Color::Rgb(200, 140, 200); // stash / misc
 pub const ACCENT_PURPLE: Color = Color::Rgb(160, 130, 220); // group headers
 
+// ─── Status palette ────────────────────────────────────────────────────────
+
+/// Colors that carry meaning (repo state, alert severity, success and
+/// failure, diff lines), chosen by `[display] palette`. The neutral tones
+/// above never change; UI code takes these roles rather than a fixed accent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub clean: Color,
+    pub uncommitted: Color,
+    pub unpushed: Color,
+    pub dirty: Color,
+    pub no_remote: Color,
+    pub critical: Color,
+    pub high: Color,
+    pub warn: Color,
+    pub info: Color,
+    /// Passed, healthy, in sync.
+    pub ok: Color,
+    pub added: Color,
+    pub removed: Color,
+    /// Field labels and markers in dialogs.
+    pub label: Color,
+}
+
+impl Palette {
+    pub const DEFAULT: Palette = Palette {
+        clean: ACCENT_GREEN,
+        uncommitted: ACCENT_YELLOW,
+        unpushed: ACCENT_BLUE,
+        dirty: ACCENT_RED,
+        no_remote: FG_DIMMED,
+        critical: ACCENT_RED,
+        high: ACCENT_ORANGE,
+        warn: ACCENT_YELLOW,
+        info: ACCENT_BLUE,
+        ok: ACCENT_GREEN,
+        added: ACCENT_GREEN,
+        removed: ACCENT_RED,
+        label: ACCENT_GREEN,
+    };
+
+    /// Okabe–Ito colors: no red/green pair, so states stay apart with
+    /// deuteranopia and protanopia.
+    pub const COLORBLIND: Palette = Palette {
+        clean: Color::Rgb(86, 180, 233),       // sky blue
+        uncommitted: Color::Rgb(240, 228, 66), // yellow
+        unpushed: Color::Rgb(204, 121, 167),   // reddish purple
+        dirty: Color::Rgb(213, 94, 0),         // vermillion
+        no_remote: FG_DIMMED,
+        critical: Color::Rgb(213, 94, 0),
+        high: Color::Rgb(230, 159, 0), // orange
+        warn: Color::Rgb(240, 228, 66),
+        info: Color::Rgb(86, 180, 233),
+        ok: Color::Rgb(86, 180, 233),
+        added: Color::Rgb(0, 114, 178), // blue
+        removed: Color::Rgb(230, 159, 0),
+        label: Color::Rgb(0, 158, 115), // bluish green
+    };
+
+    /// Terminal default everywhere; glyphs and letters carry the state.
+    pub const MONOCHROME: Palette = Palette {
+        clean: Color::Reset,
+        uncommitted: Color::Reset,
+        unpushed: Color::Reset,
+        dirty: Color::Reset,
+        no_remote: Color::Reset,
+        critical: Color::Reset,
+        high: Color::Reset,
+        warn: Color::Reset,
+        info: Color::Reset,
+        ok: Color::Reset,
+        added: Color::Reset,
+        removed: Color::Reset,
+        label: Color::Reset,
+    };
+
+    /// Preset by config name; `None` for unknown names.
+    pub fn named(name: &str) -> Option<Palette> {
+        match name.trim().to_lowercase().as_str() {
+            "default" => Some(Self::DEFAULT),
+            "colorblind" | "color-blind" => Some(Self::COLORBLIND),
+            "monochrome" | "mono" => Some(Self::MONOCHROME),
+            _ => None,
+        }
+    }
+
+    pub fn status(&self, status: StatusColor) -> Color {
+        match status {
+            StatusColor::Clean => self.clean,
+            StatusColor::Uncommitted => self.uncommitted,
+            StatusColor::Unpushed => self.unpushed,
+            StatusColor::Dirty => self.dirty,
+            StatusColor::NoRemote => self.no_remote,
+        }
+    }
+}
+
+static PALETTE: RwLock<Palette> = RwLock::new(Palette::DEFAULT);
+static STATUS_LETTERS: AtomicBool = AtomicBool::new(false);
+
+/// Apply `[display] palette`, `status_letters` and `ascii`. An unknown palette name
+/// keeps the default; `NO_COLOR` turns the default into monochrome.
+pub fn configure(display: &DisplayConfig) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let palette = match Palette::named(&display.palette).unwrap_or(Palette::DEFAULT) {
+        palette if no_color && palette == Palette::DEFAULT => Palette::MONOCHROME,
+        palette => palette,
+    };
+    if let Ok(mut current) = PALETTE.write() {
+        *current = palette;
+    }
+    STATUS_LETTERS.store(display.status_letters, Ordering::Relaxed);
+    ASCII.store(
+        display.ascii.unwrap_or_else(detect_ascii),
+        Ordering::Relaxed,
+    );
+    crate::i18n::set_template_filter(glyph);
+}
+
+pub fn palette() -> Palette {
+    PALETTE.read().map(|p| *p).unwrap_or(Palette::DEFAULT)
+}
+
+pub fn status_letters() -> bool {
+    STATUS_LETTERS.load(Ordering::Relaxed)
+}
+
+/// A distinct shape per state, so it reads without color.
+pub fn status_glyph(status: StatusColor) -> Cow<'static, str> {
+    glyph(match status {
+        StatusColor::Clean => "○",
+        StatusColor::Uncommitted => "◐",
+        StatusColor::Unpushed => "◆",
+        StatusColor::Dirty => "●",
+        StatusColor::NoRemote => "◌",
+    })
+}
+
+/// Letter for the optional status column.
+pub fn status_letter(status: StatusColor) -> &'static str {
+    match status {
+        StatusColor::Clean => "C",
+        StatusColor::Uncommitted => "U",
+        StatusColor::Unpushed => "P",
+        StatusColor::Dirty => "D",
+        StatusColor::NoRemote => "N",
+    }
+}
+
+pub fn status_color(status: StatusColor) -> Color {
+    palette().status(status)
+}
+
+// ─── ASCII fallback ────────────────────────────────────────────────────────
+
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+#[cfg(test)]
+thread_local! {
+    /// ASCII for one test's thread only, leaving tests that run alongside it
+    /// on the default glyphs.
+    pub static TEST_ASCII: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Draw with ASCII only (`[display] ascii`, or detected when unset).
+pub fn ascii() -> bool {
+    #[cfg(test)]
+    if TEST_ASCII.with(|on| on.get()) {
+        return true;
+    }
+    ASCII.load(Ordering::Relaxed)
+}
+
+/// Terminals unlikely to draw box and arrow glyphs: `TERM` is a bare console
+/// (`dumb`, `linux`, `vt100`…), the locale is set but not UTF-8, or this is
+/// the legacy Windows console rather than Windows Terminal.
+fn wants_ascii(term: Option<&str>, locale: Option<&str>, legacy_console: bool) -> bool {
+    let bare_term = term
+        .is_some_and(|term| matches!(term, "dumb" | "linux" | "cons25") || term.starts_with("vt"));
+    let narrow_locale = locale.is_some_and(|locale| {
+        let lower = locale.to_lowercase();
+        !lower.contains("utf-8") && !lower.contains("utf8")
+    });
+    bare_term || narrow_locale || legacy_console
+}
+
+fn detect_ascii() -> bool {
+    let term = std::env::var("TERM").ok();
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty());
+    let legacy_console = cfg!(windows)
+        && std::env::var_os("WT_SESSION").is_none()
+        && std::env::var_os("TERM_PROGRAM").is_none();
+    wants_ascii(term.as_deref(), locale.as_deref(), legacy_console)
+}
+
+/// ASCII stand-in for a glyph the UI draws; `None` for anything else.
+fn ascii_char(c: char) -> Option<char> {
+    Some(match c {
+        '─' | '━' | '═' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '╴' | '╶' | '╸' | '╺' | '╼' | '╾' => {
+            '-'
+        }
+        '│' | '┃' | '║' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '╵' | '╷' | '╹' | '╻' | '╽' | '╿' => {
+            '|'
+        }
+        '\u{2500}'..='\u{257F}' => '+',
+        '▌' | '▐' => '|',
+        '░' => '.',
+        '▒' => ':',
+        '\u{2580}'..='\u{259F}' => '#',
+        '●' => '*',
+        '○' | '◇' => 'o',
+        '◐' => '+',
+        '◆' | '▲' | '↑' | '⇡' => '^',
+        '▼' | '↓' | '⇣' => 'v',
+        '◌' | '·' => '.',
+        '→' | '▸' | '▶' | '›' => '>',
+        '←' | '◀' | '↵' => '<',
+        '—' | '–' => '-',
+        '…' => '~',
+        '✓' | '✔' => '+',
+        '✗' | '✘' | '×' => 'x',
+        '⚠' => '!',
+        '⚑' => '$',
+        '⊘' => '/',
+        '‘' | '’' => '\'',
+        '“' | '”' => '"',
+        '⠋' | '⠼' | '⠇' => '|',
+        '⠙' | '⠴' | '⠏' => '/',
+        '⠹' | '⠦' => '-',
+        '⠸' | '⠧' => '\\',
+        '\u{2800}'..='\u{28FF}' => '.',
+        _ => return None,
+    })
+}
+
+/// The UI's own `text` with its glyphs swapped for ASCII when [`ascii`] is
+/// on. Only for text the UI writes itself; repo names, paths and other user
+/// text are drawn as they are.
+pub fn glyph(text: &str) -> Cow<'_, str> {
+    if ascii() && text.chars().any(|c| ascii_char(c).is_some()) {
+        Cow::Owned(text.chars().map(|c| ascii_char(c).unwrap_or(c)).collect())
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// `+`, `-` and `|` borders for ASCII mode; every kind looks the same.
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// Border lines of `kind`, or plain ASCII ones when [`ascii`] is on.
+pub fn border(kind: BorderType) -> border::Set {
+    if ascii() {
+        ASCII_BORDER
+    } else {
+        kind.to_border_set()
+    }
+}
+
 // ─── Prebuilt styles ───────────────────────────────────────────────────────
 
 pub fn style_header() -> Style {
@@ -51,7 +327,7 @@ pub fn style_table_alt_row() -> Style {
 
 pub fn block_default(title: &str) -> Block<'_> {
     Block::bordered()
-        .border_type(BorderType::Rounded)
+        .border_set(border(BorderType::Rounded))
         .border_style(Style::default().fg(BORDER_NORMAL))
         .title(format!(" {} ", title))
         .title_style(Style::default().fg(FG_SECONDARY))
@@ -59,7 +335,7 @@ pub fn block_default(title: &str) -> Block<'_> {
 
 pub fn block_focused(title: &str) -> Block<'_> {
     Block::bordered()
-        .border_type(BorderType::Rounded)
+        .border_set(border(BorderType::Rounded))
         .border_style(Style::default().fg(BORDER_FOCUSED))
         .title(format!(" {} ", title))
         .title_style(
@@ -72,11 +348,12 @@ pub fn block_focused(title: &str) -> Block<'_> {
 // ─── Color mappers ─────────────────────────────────────────────────────────
 
 pub fn severity_color(severity: &str) -> Color {
+    let palette = palette();
     match severity {
-        "critical" => ACCENT_RED,
-        "high" => ACCENT_ORANGE,
-        "warn" | "warning" => ACCENT_YELLOW,
-        "info" => ACCENT_BLUE,
+        "critical" => palette.critical,
+        "high" => palette.high,
+        "warn" | "warning" => palette.warn,
+        "info" => palette.info,
         _ => FG_SECONDARY,
     }
 }
@@ -106,6 +383,66 @@ mod tests {
         assert_eq!(severity_color("info"), ACCENT_BLUE);
     }
 
+    #[test]
+    fn palettes_keep_every_state_apart() {
+        let states = [
+            StatusColor::Clean,
+            StatusColor::Uncommitted,
+            StatusColor::Unpushed,
+            StatusColor::Dirty,
+            StatusColor::NoRemote,
+        ];
+        for palette in [Palette::DEFAULT, Palette::COLORBLIND] {
+            let colors: std::collections::HashSet<String> = states
+                .iter()
+                .map(|s| format!("{:?}", palette.status(*s)))
+                .collect();
+            assert_eq!(colors.len(), states.len());
+        }
+        // Without color, shape and letter still tell every state apart.
+        let glyphs: std::collections::HashSet<_> =
+            states.iter().map(|s| status_glyph(*s)).collect();
+        let letters: std::collections::HashSet<_> =
+            states.iter().map(|s| status_letter(*s)).collect();
+        assert_eq!(glyphs.len(), states.len());
+        assert_eq!(letters.len(), states.len());
+        // Success and failure, additions and removals never rest on red/green.
+        let colorblind = Palette::COLORBLIND;
+        assert_ne!(colorblind.ok, colorblind.critical);
+        assert_ne!(colorblind.added, colorblind.removed);
+        for role in [colorblind.ok, colorblind.added, colorblind.removed] {
+            assert!(![ACCENT_GREEN, ACCENT_RED].contains(&role));
+        }
+        assert_eq!(Palette::named("Colorblind"), Some(Palette::COLORBLIND));
+        assert_eq!(Palette::named("neon"), None);
+    }
+
+    #[test]
+    fn detects_terminals_that_need_ascii() {
+        assert!(!wants_ascii(
+            Some("xterm-256color"),
+            Some("en_US.UTF-8"),
+            false
+        ));
+        assert!(!wants_ascii(Some("xterm"), None, false));
+        assert!(wants_ascii(Some("linux"), Some("en_US.UTF-8"), false));
+        assert!(wants_ascii(Some("vt100"), None, false));
+        assert!(wants_ascii(Some("xterm"), Some("C"), false));
+        assert!(wants_ascii(Some("xterm"), Some("de_DE.ISO-8859-1"), false));
+        assert!(wants_ascii(None, Some("en_US.utf8"), true));
+    }
+
+    #[test]
+    fn ascii_stand_ins_are_single_ascii_characters() {
+        for glyph in "╭─╮│╰╯┌┐└┘├┤═║●○◐◆◌◇↑↓⇡⇣→←↵▸▌·—…✓✗×⚠⚑⊘⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏█░".chars()
+        {
+            let ascii = ascii_char(glyph).unwrap_or_else(|| panic!("no stand-in for {glyph}"));
+            assert!(ascii.is_ascii_graphic(), "{glyph} -> {ascii:?}");
+        }
+        assert_eq!(ascii_char('é'), None);
+        assert_eq!(ascii_char('日'), None);
+    }
+
     #[test]
     fn severity_unknown_falls_back() {
         assert_eq!(severity_color("debug"), FG_SECONDARY);
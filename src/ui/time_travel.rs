@@ -0,0 +1,190 @@
+use super::{centered_rect, theme};
+use crate::app::{App, TimeTravel};
+use crate::format;
+use crate::history::{Change, HistoryEntry};
+use crate::i18n::{t, tr};
+use chrono::{Local, TimeZone};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(travel) = app.time_travel.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(96, 26, frame.area());
+    // Borders, header, blank lines, and key hints.
+    let visible = area.height.saturating_sub(7).max(1) as usize;
+    let stamp = |entry: &HistoryEntry| match Local.timestamp_opt(entry.at, 0).single() {
+        Some(t) => format::date_time(&app.config.display, &t),
+        None => t("time-travel-unknown"),
+    };
+
+    let (mut lines, hints) = match &travel.compared {
+        Some((older, newer, changes)) => (
+            diff_lines(
+                &stamp(&travel.entries[*older]),
+                &stamp(&travel.entries[*newer]),
+                changes,
+                visible,
+            ),
+            t("time-travel-hint-back"),
+        ),
+        None => (
+            list_lines(app, travel, &stamp, visible),
+            if travel.from.is_some() {
+                t("time-travel-hint-second")
+            } else {
+                t("time-travel-hint-first")
+            },
+        ),
+    };
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        format!("  {}", hints),
+        Style::default().fg(theme::FG_DIMMED),
+    )]));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::BORDER_FOCUSED))
+                    .title(format!(" {} ", t("time-travel-title")))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_BLUE)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        area,
+    );
+}
+
+fn list_lines<'a>(
+    app: &App,
+    travel: &TimeTravel,
+    stamp: &dyn Fn(&HistoryEntry) -> String,
+    visible: usize,
+) -> Vec<Line<'a>> {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!(
+                "  {}",
+                tr("time-travel-header", &[("count", &travel.entries.len())])
+            ),
+            Style::default().fg(theme::FG_SECONDARY),
+        )]),
+        Line::from(""),
+    ];
+    let start = travel.cursor.saturating_sub(visible.saturating_sub(1));
+    for (idx, entry) in travel.entries.iter().enumerate().skip(start).take(visible) {
+        let marked = travel.from == Some(idx);
+        let style = if idx == travel.cursor {
+            Style::default()
+                .fg(theme::FG_PRIMARY)
+                .bg(theme::BG_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme::FG_PRIMARY)
+        };
+        let dirty = entry.repos.iter().filter(|r| r.dirty > 0).count();
+        let cost: f64 = entry.costs.values().sum();
+        lines.push(Line::from(vec![
+            Span::styled(
+                theme::glyph(if marked { "  ● " } else { "    " }),
+                Style::default().fg(theme::ACCENT_CYAN),
+            ),
+            Span::styled(format!("{:<22}", stamp(entry)), style),
+            Span::styled(
+                tr(
+                    "time-travel-entry",
+                    &[
+                        ("repos", &entry.repos.len()),
+                        ("dirty", &dirty),
+                        ("alerts", &entry.alerts.len()),
+                        ("cost", &format::currency(&app.config.display, cost)),
+                    ],
+                ),
+                Style::default().fg(theme::FG_SECONDARY),
+            ),
+        ]));
+    }
+    lines
+}
+
+fn diff_lines<'a>(from: &str, to: &str, changes: &[Change], visible: usize) -> Vec<Line<'a>> {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  ", Style::default()),
+            Span::styled(
+                from.to_string(),
+                Style::default().fg(theme::palette().removed),
+            ),
+            Span::styled(theme::glyph("  →  "), Style::default().fg(theme::FG_DIMMED)),
+            Span::styled(to.to_string(), Style::default().fg(theme::palette().added)),
+            Span::styled(
+                format!(
+                    "   {}",
+                    tr("time-travel-changes", &[("count", &changes.len())])
+                ),
+                Style::default().fg(theme::FG_SECONDARY),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    if changes.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            format!("  {}", t("time-travel-unchanged")),
+            Style::default().fg(theme::FG_DIMMED),
+        )]));
+        return lines;
+    }
+    for change in changes.iter().take(visible) {
+        let sign = change.kind.sign();
+        let color = sign_color(sign);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} {:<10}", sign, change.kind.label()),
+                Style::default().fg(color),
+            ),
+            Span::styled(
+                format!("{} ", format::pad(&change.subject, 28)),
+                Style::default().fg(theme::FG_PRIMARY),
+            ),
+            Span::styled(
+                format::pad(&change.before, 16),
+                Style::default().fg(theme::FG_DIMMED),
+            ),
+            Span::styled(change.after.clone(), Style::default().fg(color)),
+        ]));
+    }
+    if changes.len() > visible {
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  {}",
+                tr("time-travel-more", &[("count", &(changes.len() - visible))])
+            ),
+            Style::default().fg(theme::FG_DIMMED),
+        )]));
+    }
+    lines
+}
+
+fn sign_color(sign: &str) -> Color {
+    match sign {
+        "+" => theme::palette().added,
+        "-" => theme::palette().removed,
+        _ => theme::ACCENT_YELLOW,
+    }
+}
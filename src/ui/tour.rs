@@ -0,0 +1,123 @@
+use super::{action_confirm, regions, theme};
+use crate::app::{App, TourStep};
+use crate::format;
+use crate::i18n::{t, tr};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+const CALLOUT_WIDTH: u16 = 58;
+
+/// The example workspace with the current stop outlined and a callout
+/// explaining it.
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(tour) = app.tour.as_ref() else {
+        return;
+    };
+    super::render(frame, &tour.demo);
+
+    let area = frame.area();
+    let [summary, sidebar, content, status] = regions(area);
+    let (title, body) = match tour.step {
+        TourStep::Sidebar => (t("tour-sidebar-title"), t("tour-sidebar")),
+        TourStep::Summary => (t("tour-summary-title"), t("tour-summary")),
+        TourStep::ActionKey => (t("tour-action-title"), t("tour-action")),
+        TourStep::Confirm => (t("tour-confirm-title"), t("tour-confirm")),
+    };
+    let mut lines: Vec<Line> = wrap(&body, CALLOUT_WIDTH as usize - 4)
+        .into_iter()
+        .map(|line| {
+            Line::from(Span::styled(
+                format!(" {}", line),
+                Style::default().fg(theme::FG_PRIMARY),
+            ))
+        })
+        .collect();
+    lines.push(Line::from(Span::styled(
+        format!(" {}", t("tour-keys")),
+        Style::default().fg(theme::FG_DIMMED),
+    )));
+    let height = lines.len() as u16 + 2;
+
+    let (target, callout) = match tour.step {
+        TourStep::Sidebar => (Some(sidebar), (sidebar.right() + 2, sidebar.y + 2)),
+        TourStep::Summary => (Some(summary), (content.x + 2, summary.bottom() + 1)),
+        TourStep::ActionKey => (None, (content.x + 2, status.y.saturating_sub(height + 1))),
+        TourStep::Confirm => (
+            Some(action_confirm::dialog_area(area)),
+            (
+                area.x + area.width.saturating_sub(CALLOUT_WIDTH) / 2,
+                area.y,
+            ),
+        ),
+    };
+    match target {
+        Some(target) => frame.render_widget(
+            Block::bordered()
+                .border_set(theme::border(BorderType::Double))
+                .border_style(Style::default().fg(theme::ACCENT_YELLOW)),
+            target,
+        ),
+        // One row is too thin to outline; invert it instead.
+        None => frame
+            .buffer_mut()
+            .set_style(status, Style::default().add_modifier(Modifier::REVERSED)),
+    }
+
+    let (x, y) = callout;
+    let callout = Rect {
+        x,
+        y,
+        width: CALLOUT_WIDTH,
+        height,
+    }
+    .intersection(area);
+    let position = tour.step.index() + 1;
+    let count = TourStep::ALL.len();
+    frame.render_widget(Clear, callout);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .border_set(theme::border(BorderType::Rounded))
+                    .border_style(Style::default().fg(theme::ACCENT_YELLOW))
+                    .title(format!(
+                        " {} ",
+                        tr(
+                            "tour-title",
+                            &[("step", &position), ("count", &count), ("title", &title)]
+                        )
+                    ))
+                    .title_style(
+                        Style::default()
+                            .fg(theme::ACCENT_YELLOW)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .style(Style::default().bg(theme::BG_ELEVATED)),
+        callout,
+    );
+}
+
+/// Greedy word wrap to `width` display cells.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && format::width(&line) + 1 + format::width(word) > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
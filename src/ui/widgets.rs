@@ -1,6 +1,6 @@
 use super::theme;
 use ratatui::{
-    layout::{Alignment, Constraint, Layout, Rect},
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, Paragraph, Row, Table, TableState},
@@ -16,7 +16,7 @@ pub fn render_stat_card(
     dot_color: ratatui::style::Color,
 ) {
     let block = Block::bordered()
-        .border_type(BorderType::Rounded)
+        .border_set(theme::border(BorderType::Rounded))
         .border_style(Style::default().fg(theme::BORDER_NORMAL));
 
     let inner = block.inner(area);
@@ -41,7 +41,7 @@ pub fn render_stat_card(
             format!("{} ", label),
             Style::default().fg(theme::FG_SECONDARY),
         ),
-        Span::styled("●", Style::default().fg(dot_color)),
+        Span::styled(theme::glyph("●"), Style::default().fg(dot_color)),
     ])
     .alignment(Alignment::Center);
 
@@ -85,11 +85,23 @@ pub fn render_styled_table<const N: usize>(
     frame.render_stateful_widget(table, area, &mut state);
 }
 
+/// Cells ratatui gives each column of a bordered table drawn in `area`, so
+/// text can be shortened with an ellipsis instead of clipped.
+pub fn column_widths(area: Rect, widths: &[Constraint]) -> Vec<usize> {
+    Layout::horizontal(widths.iter().copied())
+        .flex(Flex::Start)
+        .spacing(1)
+        .split(Rect::new(0, 0, area.width.saturating_sub(2), 1))
+        .iter()
+        .map(|column| column.width as usize)
+        .collect()
+}
+
 /// Build a pair of spans for a keyboard hint: accent-colored key + muted description.
-pub fn key_hint<'a>(key: &'a str, desc: &'a str) -> Vec<Span<'a>> {
+pub fn key_hint<'a>(key: &'a str, desc: &str) -> Vec<Span<'a>> {
     vec![
         Span::styled(
-            key,
+            theme::glyph(key),
             Style::default()
                 .fg(theme::ACCENT_CYAN)
                 .add_modifier(Modifier::BOLD),
@@ -100,7 +112,7 @@ pub fn key_hint<'a>(key: &'a str, desc: &'a str) -> Vec<Span<'a>> {
 
 /// Render a styled empty state with centered icon and message.
 pub fn render_empty_state(frame: &mut Frame, area: Rect, icon: &str, message: &str) {
-    let text = format!("{}\n{}", icon, message);
+    let text = format!("{}\n{}", theme::glyph(icon), message);
     frame.render_widget(
         Paragraph::new(text)
             .block(theme::block_default(""))
@@ -110,49 +122,47 @@ pub fn render_empty_state(frame: &mut Frame, area: Rect, icon: &str, message: &s
     );
 }
 
-/// Format a number with comma separators (e.g., 1234567 -> "1,234,567").
-pub fn format_number(n: u64) -> String {
-    let s = n.to_string();
-    let mut result = String::with_capacity(s.len() + s.len() / 3);
-    for (i, c) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.push(',');
-        }
-        result.push(c);
-    }
-    result.chars().rev().collect()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn format_number_zero() {
-        assert_eq!(format_number(0), "0");
-    }
-
-    #[test]
-    fn format_number_small() {
-        assert_eq!(format_number(42), "42");
-        assert_eq!(format_number(999), "999");
-    }
-
-    #[test]
-    fn format_number_thousands() {
-        assert_eq!(format_number(1_000), "1,000");
-        assert_eq!(format_number(12_345), "12,345");
-        assert_eq!(format_number(999_999), "999,999");
-    }
-
-    #[test]
-    fn format_number_millions() {
-        assert_eq!(format_number(1_000_000), "1,000,000");
-        assert_eq!(format_number(1_234_567), "1,234,567");
+/// Empty state with explanatory hints, one per line of `hints`, and the key
+/// that runs its setup action.
+pub fn render_empty_state_guide(
+    frame: &mut Frame,
+    area: Rect,
+    icon: &str,
+    message: &str,
+    hints: &str,
+    key_hint: Option<(&str, &str)>,
+) {
+    let mut lines = vec![
+        Line::from(theme::glyph(icon).into_owned()),
+        Line::from(Span::styled(
+            message.to_string(),
+            Style::default().fg(theme::FG_PRIMARY),
+        )),
+        Line::from(""),
+    ];
+    for hint in hints.lines() {
+        lines.push(Line::from(Span::styled(
+            hint.to_string(),
+            Style::default().fg(theme::FG_SECONDARY),
+        )));
     }
-
-    #[test]
-    fn format_number_large() {
-        assert_eq!(format_number(1_000_000_000), "1,000,000,000");
+    if let Some((key, label)) = key_hint {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {} ", key),
+                Style::default()
+                    .fg(theme::ACCENT_CYAN)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(label.to_string(), Style::default().fg(theme::FG_SECONDARY)),
+        ]));
     }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(theme::block_default(""))
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme::FG_DIMMED)),
+        area,
+    );
 }
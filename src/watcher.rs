@@ -0,0 +1,516 @@
+//! Filesystem-event refresh for `watch_mode`.
+//!
+//! Each repo's git directory gets one non-recursive watch: the index, HEAD,
+//! FETCH_HEAD and COMMIT_EDITMSG all live there, so one inotify watch per
+//! repo catches staging, commits, checkouts and fetches. When the kernel's
+//! watch budget runs out (`fs.inotify.max_user_watches`, reported as ENOSPC)
+//! the remaining repos fall back to the regular poll and are listed as polled.
+//!
+//! Once its git dir is covered, a repo's working tree is watched too: the
+//! root non-recursively and each top-level directory git doesn't ignore
+//! recursively, so `target/` or `node_modules/` never eat the watch budget.
+//! Events under ignored paths are dropped. Repos with a watched working tree
+//! are re-probed only when an event invalidates them; if the budget runs out
+//! here, the remaining working trees keep the interval poll.
+//!
+//! Which paths git ignores comes from `git ls-files`, which callers run off
+//! the UI loop: [`RepoWatcher::take_ignore_due`] lists the repos that need
+//! it (newly watched, a `.gitignore` changed, or a top-level directory
+//! appeared) and [`RepoWatcher::apply_ignored`] takes the answer.
+
+use crate::git::std_git_command;
+use crate::monitor::{resolve_git_dir, set_watched_worktrees};
+use notify::{ErrorKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Linux `ENOSPC`, which inotify returns when `max_user_watches` is used up.
+const ENOSPC: i32 = 28;
+
+/// Which repos refresh on events and which wait for the poll.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WatchCoverage {
+    pub event_driven: BTreeSet<PathBuf>,
+    pub polled: BTreeSet<PathBuf>,
+    /// Why repos fell back to polling, e.g. the inotify watch limit.
+    pub fallback_reason: Option<String>,
+    /// The watch budget is spent; new repos go straight to `polled`.
+    pub limit_reached: bool,
+    /// Event-driven repos whose working tree is watched as well.
+    pub worktrees: BTreeSet<PathBuf>,
+    /// The budget ran out on working trees; git dirs are still watched.
+    pub worktree_limit_reached: bool,
+}
+
+impl WatchCoverage {
+    /// Record the outcome of watching `repo`.
+    fn record(&mut self, repo: PathBuf, result: notify::Result<()>) {
+        match result {
+            Ok(()) => {
+                self.event_driven.insert(repo);
+            }
+            Err(err) => {
+                if is_watch_limit(&err) {
+                    self.limit_reached = true;
+                    self.fallback_reason = Some(
+                        "inotify watch limit reached (raise fs.inotify.max_user_watches)"
+                            .to_string(),
+                    );
+                } else if self.fallback_reason.is_none() {
+                    self.fallback_reason = Some(err.to_string());
+                }
+                self.polled.insert(repo);
+            }
+        }
+    }
+
+    pub fn is_event_driven(&self, repo: &Path) -> bool {
+        self.event_driven.contains(repo)
+    }
+
+    /// One-line summary for the Stats section and notifications.
+    pub fn summary(&self) -> String {
+        let mut line = format!("{} event-driven", self.event_driven.len());
+        if !self.event_driven.is_empty() {
+            line.push_str(&format!(" ({} with working tree)", self.worktrees.len()));
+        }
+        line.push_str(&format!(" · {} polled", self.polled.len()));
+        if let (false, Some(reason)) = (self.polled.is_empty(), &self.fallback_reason) {
+            line.push_str(&format!(" ({})", reason));
+        }
+        line
+    }
+}
+
+fn is_watch_limit(err: &notify::Error) -> bool {
+    match &err.kind {
+        ErrorKind::MaxFilesWatch => true,
+        ErrorKind::Io(io) => io.raw_os_error() == Some(ENOSPC),
+        _ => false,
+    }
+}
+
+/// Owns the OS watcher; events arrive as raw paths on the channel given to
+/// [`RepoWatcher::new`] and are mapped back with [`RepoWatcher::repo_for`].
+pub struct RepoWatcher {
+    watcher: Option<RecommendedWatcher>,
+    /// Watched git dir → repo path.
+    git_dirs: BTreeMap<PathBuf, PathBuf>,
+    /// Repo path → its working-tree watches.
+    worktrees: BTreeMap<PathBuf, Worktree>,
+    /// Repos whose ignored paths must be listed (again).
+    ignore_due: BTreeSet<PathBuf>,
+    pub coverage: WatchCoverage,
+}
+
+/// Working-tree watches of one repo.
+#[derive(Debug, Default)]
+struct Worktree {
+    /// Watched paths, the repo root first.
+    watched: Vec<PathBuf>,
+    /// Ignored paths whose events are dropped.
+    ignored: Vec<PathBuf>,
+}
+
+impl RepoWatcher {
+    /// A watcher that sends every changed path to `tx`. If the OS refuses a
+    /// watcher outright (e.g. `max_user_instances`), every repo is polled.
+    pub fn new(tx: UnboundedSender<PathBuf>) -> Self {
+        let created = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+        });
+        let mut coverage = WatchCoverage::default();
+        let watcher = match created {
+            Ok(watcher) => Some(watcher),
+            Err(err) => {
+                coverage.limit_reached = true;
+                coverage.fallback_reason = Some(format!("file watcher unavailable: {}", err));
+                None
+            }
+        };
+        Self {
+            watcher,
+            git_dirs: BTreeMap::new(),
+            worktrees: BTreeMap::new(),
+            ignore_due: BTreeSet::new(),
+            coverage,
+        }
+    }
+
+    /// Watch newly discovered repos and drop repos that are gone.
+    pub fn sync(&mut self, repos: &[PathBuf]) {
+        let live: BTreeSet<&PathBuf> = repos.iter().collect();
+        let gone: Vec<PathBuf> = self
+            .git_dirs
+            .iter()
+            .filter(|(_, repo)| !live.contains(repo))
+            .map(|(git_dir, _)| git_dir.clone())
+            .collect();
+        for git_dir in gone {
+            if let Some(repo) = self.git_dirs.remove(&git_dir) {
+                if let Some(watcher) = self.watcher.as_mut() {
+                    let _ = watcher.unwatch(&git_dir);
+                }
+                self.coverage.event_driven.remove(&repo);
+            }
+        }
+        let gone: Vec<PathBuf> = self
+            .worktrees
+            .keys()
+            .filter(|repo| !live.contains(repo))
+            .cloned()
+            .collect();
+        for repo in gone {
+            self.unwatch_worktree(&repo);
+        }
+        self.coverage.polled.retain(|repo| live.contains(repo));
+        self.ignore_due.retain(|repo| live.contains(repo));
+
+        for repo in repos {
+            if self.coverage.event_driven.contains(repo) || self.coverage.polled.contains(repo) {
+                continue;
+            }
+            let watcher = match self.watcher.as_mut() {
+                Some(watcher) if !self.coverage.limit_reached => watcher,
+                _ => {
+                    self.coverage.polled.insert(repo.clone());
+                    continue;
+                }
+            };
+            let Some(git_dir) = resolve_git_dir(repo) else {
+                self.coverage.polled.insert(repo.clone());
+                continue;
+            };
+            let result = watcher.watch(&git_dir, RecursiveMode::NonRecursive);
+            if result.is_ok() {
+                self.git_dirs.insert(git_dir, repo.clone());
+            }
+            self.coverage.record(repo.clone(), result);
+        }
+
+        // Working trees once their ignored paths are known, so they never
+        // take budget a git dir needed.
+        if !self.coverage.worktree_limit_reached {
+            for repo in repos {
+                if self.coverage.is_event_driven(repo) && !self.worktrees.contains_key(repo) {
+                    self.ignore_due.insert(repo.clone());
+                }
+            }
+        }
+        set_watched_worktrees(self.coverage.worktrees.iter().cloned());
+    }
+
+    /// Repos to run [`ignored_paths`] for and hand to [`Self::apply_ignored`].
+    pub fn take_ignore_due(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.ignore_due).into_iter().collect()
+    }
+
+    /// [`Self::take_ignore_due`] and [`Self::apply_ignored`] in one go, with
+    /// git off the async runtime; for loops with no UI to keep responsive.
+    pub async fn refresh_ignores(&mut self) {
+        for repo in self.take_ignore_due() {
+            let lookup = repo.clone();
+            if let Ok(ignored) = tokio::task::spawn_blocking(move || ignored_paths(&lookup)).await {
+                self.apply_ignored(&repo, ignored);
+            }
+        }
+    }
+
+    /// Take `repo`'s ignored paths: watch its working tree, or bring the
+    /// watches of one already watched in line (new top-level directories in,
+    /// newly ignored ones out).
+    pub fn apply_ignored(&mut self, repo: &Path, ignored: Vec<PathBuf>) {
+        if !self.coverage.is_event_driven(repo) {
+            return;
+        }
+        match self.worktrees.get_mut(repo) {
+            Some(worktree) => {
+                worktree.ignored = ignored;
+                let Some(watcher) = self.watcher.as_mut() else {
+                    return;
+                };
+                let stale: Vec<PathBuf> = worktree
+                    .watched
+                    .iter()
+                    .skip(1)
+                    .filter(|path| !path.is_dir() || worktree.skips(repo, path))
+                    .cloned()
+                    .collect();
+                for path in &stale {
+                    let _ = watcher.unwatch(path);
+                }
+                worktree.watched.retain(|path| !stale.contains(path));
+                for path in top_level_dirs(repo) {
+                    if worktree.skips(repo, &path) || worktree.watched.contains(&path) {
+                        continue;
+                    }
+                    if watcher.watch(&path, RecursiveMode::Recursive).is_ok() {
+                        worktree.watched.push(path);
+                    }
+                }
+            }
+            None if !self.coverage.worktree_limit_reached => self.watch_worktree(repo, ignored),
+            None => {}
+        }
+        set_watched_worktrees(self.coverage.worktrees.iter().cloned());
+    }
+
+    /// Watch the root of `repo` and each top-level directory git tracks.
+    fn watch_worktree(&mut self, repo: &Path, ignored: Vec<PathBuf>) {
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        let mut worktree = Worktree {
+            ignored,
+            ..Worktree::default()
+        };
+        let mut targets = vec![(repo.to_path_buf(), RecursiveMode::NonRecursive)];
+        for path in top_level_dirs(repo) {
+            if !worktree.skips(repo, &path) {
+                targets.push((path, RecursiveMode::Recursive));
+            }
+        }
+        for (path, mode) in targets {
+            match watcher.watch(&path, mode) {
+                Ok(()) => worktree.watched.push(path),
+                Err(err) if is_watch_limit(&err) => {
+                    // Give back what this repo took; its git dir stays watched.
+                    for path in &worktree.watched {
+                        let _ = watcher.unwatch(path);
+                    }
+                    self.coverage.worktree_limit_reached = true;
+                    return;
+                }
+                // Removed between listing and watching; nothing to see there.
+                Err(_) => {}
+            }
+        }
+        self.coverage.worktrees.insert(repo.to_path_buf());
+        self.worktrees.insert(repo.to_path_buf(), worktree);
+    }
+
+    fn unwatch_worktree(&mut self, repo: &Path) {
+        if let Some(worktree) = self.worktrees.remove(repo) {
+            if let Some(watcher) = self.watcher.as_mut() {
+                for path in &worktree.watched {
+                    let _ = watcher.unwatch(path);
+                }
+            }
+        }
+        self.coverage.worktrees.remove(repo);
+    }
+
+    /// Repo an event path belongs to: a watched git dir, or else the
+    /// innermost watched working tree, unless the path is ignored there.
+    pub fn repo_for(&self, path: &Path) -> Option<&PathBuf> {
+        if let Some((_, repo)) = self
+            .git_dirs
+            .iter()
+            .find(|(git_dir, _)| path.starts_with(git_dir))
+        {
+            return Some(repo);
+        }
+        let (repo, worktree) = self
+            .worktrees
+            .iter()
+            .filter(|(repo, _)| path.starts_with(repo))
+            .max_by_key(|(repo, _)| repo.components().count())?;
+        (!worktree.skips(repo, path)).then_some(repo)
+    }
+
+    /// [`Self::repo_for`], also queueing an ignore refresh when a
+    /// `.gitignore` changed or a directory appeared at the top of a working
+    /// tree (the root watch isn't recursive, so it gets watched then).
+    pub fn observe(&mut self, path: &Path) -> Option<PathBuf> {
+        let repo = self.repo_for(path)?.clone();
+        let new_top_dir = path.parent() == Some(repo.as_path())
+            && path.is_dir()
+            && self
+                .worktrees
+                .get(&repo)
+                .is_some_and(|w| !w.watched.iter().any(|p| p == path));
+        let rules_changed = path.file_name().is_some_and(|name| name == ".gitignore");
+        if (new_top_dir || rules_changed) && self.worktrees.contains_key(&repo) {
+            self.ignore_due.insert(repo.clone());
+        }
+        Some(repo)
+    }
+}
+
+impl Worktree {
+    /// Whether events at `path` inside the working tree of `repo` can't
+    /// change its status: the `.git` entry itself and ignored paths.
+    fn skips(&self, repo: &Path, path: &Path) -> bool {
+        path.starts_with(repo.join(".git")) || self.ignored.iter().any(|i| path.starts_with(i))
+    }
+}
+
+/// Ignored files and directories of `repo`, directories collapsed to their
+/// top-most ignored ancestor. Runs git and blocks.
+pub fn ignored_paths(repo: &Path) -> Vec<PathBuf> {
+    let output = std_git_command(&[
+        "ls-files",
+        "--others",
+        "--ignored",
+        "--exclude-standard",
+        "--directory",
+        "-z",
+    ])
+    .current_dir(repo)
+    .output();
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| repo.join(entry.trim_end_matches('/')))
+        .collect()
+}
+
+fn top_level_dirs(repo: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(repo)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watch_limit_sends_remaining_repos_to_polling() {
+        let mut coverage = WatchCoverage::default();
+        coverage.record(PathBuf::from("/src/a"), Ok(()));
+        coverage.record(
+            PathBuf::from("/src/b"),
+            Err(notify::Error::io(std::io::Error::from_raw_os_error(ENOSPC))),
+        );
+        assert!(coverage.limit_reached);
+        assert!(coverage.is_event_driven(Path::new("/src/a")));
+        assert!(!coverage.is_event_driven(Path::new("/src/b")));
+        assert!(coverage
+            .summary()
+            .starts_with("1 event-driven (0 with working tree) · 1 polled (inotify"));
+
+        // A missing path is polled too, but does not stop further watches.
+        let mut coverage = WatchCoverage::default();
+        coverage.record(
+            PathBuf::from("/src/c"),
+            Err(notify::Error::path_not_found()),
+        );
+        assert!(!coverage.limit_reached);
+        assert_eq!(coverage.polled.len(), 1);
+    }
+
+    #[test]
+    fn maps_events_back_to_repos_and_forgets_removed_ones() {
+        let base = std::env::temp_dir().join("agentpulse_watcher_test");
+        let _ = std::fs::remove_dir_all(&base);
+        let repo = base.join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = RepoWatcher::new(tx);
+        if watcher.watcher.is_none() {
+            // No watcher in this sandbox: everything is polled.
+            watcher.sync(std::slice::from_ref(&repo));
+            assert_eq!(watcher.coverage.polled.len(), 1);
+            return;
+        }
+
+        watcher.sync(std::slice::from_ref(&repo));
+        assert!(watcher.coverage.is_event_driven(&repo));
+        assert_eq!(
+            watcher.repo_for(&repo.join(".git").join("index")),
+            Some(&repo)
+        );
+        assert_eq!(watcher.repo_for(&base.join("elsewhere")), None);
+
+        watcher.sync(&[]);
+        assert!(watcher.coverage.event_driven.is_empty());
+        assert_eq!(watcher.repo_for(&repo.join(".git").join("index")), None);
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn working_tree_events_skip_ignored_paths() {
+        let repo = std::env::temp_dir().join("agentpulse_watcher_worktree");
+        let _ = std::fs::remove_dir_all(&repo);
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        std::fs::create_dir_all(repo.join("target").join("debug")).unwrap();
+        std::fs::write(repo.join(".gitignore"), "target/\n*.log\n").unwrap();
+        std::fs::write(repo.join("build.log"), "").unwrap();
+        let init = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(&repo)
+            .status()
+            .unwrap();
+        assert!(init.success());
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = RepoWatcher::new(tx);
+        watcher.sync(std::slice::from_ref(&repo));
+        for due in watcher.take_ignore_due() {
+            watcher.apply_ignored(&due, ignored_paths(&due));
+        }
+        if !watcher.coverage.worktrees.contains(&repo) {
+            // No watcher (or no budget) in this sandbox.
+            let _ = std::fs::remove_dir_all(&repo);
+            return;
+        }
+
+        let watched = &watcher.worktrees[&repo].watched;
+        assert!(watched.contains(&repo.join("src")));
+        assert!(!watched.contains(&repo.join("target")));
+        assert_eq!(
+            watcher.repo_for(&repo.join("src").join("lib.rs")),
+            Some(&repo)
+        );
+        assert_eq!(watcher.repo_for(&repo.join("README.md")), Some(&repo));
+        assert_eq!(watcher.repo_for(&repo.join("target").join("debug")), None);
+        assert_eq!(watcher.repo_for(&repo.join("build.log")), None);
+        assert!(watcher.coverage.summary().contains("(1 with working tree)"));
+
+        // A directory created later at the top is picked up after its first
+        // event, once the ignore refresh ran.
+        std::fs::create_dir_all(repo.join("docs")).unwrap();
+        assert_eq!(watcher.observe(&repo.join("docs")), Some(repo.clone()));
+        assert_eq!(watcher.take_ignore_due(), vec![repo.clone()]);
+        watcher.apply_ignored(&repo, ignored_paths(&repo));
+        assert!(watcher.worktrees[&repo]
+            .watched
+            .contains(&repo.join("docs")));
+
+        // Newly ignored directories stop being watched, and their events
+        // stop counting.
+        std::fs::write(repo.join(".gitignore"), "target/\n*.log\ndocs/\n").unwrap();
+        assert_eq!(
+            watcher.observe(&repo.join(".gitignore")),
+            Some(repo.clone())
+        );
+        assert_eq!(watcher.take_ignore_due(), vec![repo.clone()]);
+        watcher.apply_ignored(&repo, ignored_paths(&repo));
+        assert!(!watcher.worktrees[&repo]
+            .watched
+            .contains(&repo.join("docs")));
+        assert_eq!(watcher.repo_for(&repo.join("docs").join("a.md")), None);
+
+        watcher.sync(&[]);
+        assert!(watcher.coverage.worktrees.is_empty());
+        assert_eq!(watcher.repo_for(&repo.join("src").join("lib.rs")), None);
+        let _ = std::fs::remove_dir_all(&repo);
+    }
+}
@@ -190,12 +190,18 @@ async fn test_urgency_ordering() {
         r.status = RepoStatus {
             branch: "main".into(),
             uncommitted_count: uncommitted,
+            changes: Default::default(),
             unpushed_count: unpushed,
             behind_count: 0,
             stash_count: 0,
             has_remote,
             is_detached: false,
             probe_errors: Vec::new(),
+            push: None,
+            missing_upstream: false,
+            dirty_since: None,
+            unpushed_since: None,
+            head_sha: None,
         };
         r
     };
@@ -237,6 +243,7 @@ async fn test_monitor_dirty_repos_sorted_first() {
         ignored_repos: vec![],
         watch_mode: false,
         missing_directories: vec![],
+        ..Default::default()
     };
 
     let mut cache = agentpulse::monitor::StatusCache::new();